@@ -9,6 +9,7 @@ operations:
   - ``publish(address, data)`` — inject data at a node's input
   - ``intercept(address, ...)`` — edit / drop a node's output
   - ``set_node_state(node, state)`` — enable / bypass / disable a node
+  - ``apply_patch(patch)`` — add / remove / rewire / re-parameterise nodes
 
 Usage::
 
@@ -29,6 +30,7 @@ from .client import (
     AttachedSession,
     Data,
     NodeState,
+    PatchRejectedError,
     UnknownNodeError,
     SessionNotFoundError,
     attach,
@@ -38,6 +40,7 @@ __all__ = [
     "AttachedSession",
     "Data",
     "NodeState",
+    "PatchRejectedError",
     "UnknownNodeError",
     "SessionNotFoundError",
     "attach",
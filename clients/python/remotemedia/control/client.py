@@ -14,7 +14,9 @@ attach to the same session over gRPC. The server keys lookups by
 from __future__ import annotations
 
 import asyncio
+import collections
 import enum
+import json
 import logging
 import re
 from contextlib import asynccontextmanager
@@ -142,6 +144,10 @@ class ControlAddressError(ValueError):
     pass
 
 
+class PatchRejectedError(RuntimeError):
+    """The server rejected a pipeline patch; the pipeline is unchanged."""
+
+
 # ─── Address parsing ─────────────────────────────────────────────────────────
 
 
@@ -210,6 +216,10 @@ class AttachedSession:
         self._closed_event = asyncio.Event()
         self._attached_event = asyncio.Event()
         self._attach_error: Optional[Exception] = None
+        # Replies to `apply_patch` are not correlated on the wire; calls are
+        # serialised so the single pending future is the one being answered.
+        self._patch_lock = asyncio.Lock()
+        self._pending_patches: "collections.deque[asyncio.Future]" = collections.deque()
 
     async def _send(self, frame: _pb.ControlFrame) -> None:
         await self._out_queue.put(frame)
@@ -267,9 +277,19 @@ class AttachedSession:
                             _reply_fn=self._send_intercept_reply,
                         )
                         await q.put(req)
+                elif which == "patch_applied":
+                    if self._pending_patches:
+                        fut = self._pending_patches.popleft()
+                        if not fut.done():
+                            fut.set_result(json.loads(event.patch_applied.report_json))
                 elif which == "error":
                     code = event.error.code
                     msg = event.error.message
+                    if code == _pb.CONTROL_ERROR_CODE_INVALID_PATCH and self._pending_patches:
+                        fut = self._pending_patches.popleft()
+                        if not fut.done():
+                            fut.set_exception(PatchRejectedError(msg))
+                        continue
                     # SESSION_NOT_FOUND is fatal to the attach — raise.
                     if code == _pb.CONTROL_ERROR_CODE_SESSION_NOT_FOUND:
                         self._attach_error = SessionNotFoundError(msg)
@@ -292,6 +312,12 @@ class AttachedSession:
                 self._attach_error = e
             self._closed_event.set()
         finally:
+            while self._pending_patches:
+                fut = self._pending_patches.popleft()
+                if not fut.done():
+                    fut.set_exception(
+                        self._attach_error or RuntimeError("control attach closed")
+                    )
             self._closed_event.set()
 
     async def _send_intercept_reply(
@@ -413,6 +439,22 @@ class AttachedSession:
             _pb.ControlFrame(clear_node_state=_pb.ClearNodeState(node_id=node_id))
         )
 
+    async def apply_patch(self, patch: Dict[str, Any]) -> Dict[str, Any]:
+        """Edit the running pipeline and return the server's patch report.
+
+        ``patch`` is a ``PipelinePatch`` as JSON, e.g.
+        ``{"ops": [{"op": "update_params", "node_id": "tts", "params": {...}}]}``.
+        Raises :class:`PatchRejectedError` if the patch was rejected, in
+        which case the pipeline is unchanged.
+        """
+        async with self._patch_lock:
+            fut: asyncio.Future = asyncio.get_running_loop().create_future()
+            self._pending_patches.append(fut)
+            await self._send(
+                _pb.ControlFrame(apply_patch=_pb.ApplyPatch(patch_json=json.dumps(patch)))
+            )
+            return await fut
+
     async def close(self) -> None:
         if self._closed_event.is_set():
             return
@@ -47,8 +47,8 @@ pub fn whisper_schema() -> NodeSchema {
                 },
                 "language": {
                     "type": "string",
-                    "default": "en",
-                    "description": "Target language code"
+                    "default": "auto",
+                    "description": "Target language code, or \"auto\" to detect it"
                 },
                 "device": {
                     "type": "string",
@@ -61,11 +61,27 @@ pub fn whisper_schema() -> NodeSchema {
                     "maximum": 10,
                     "default": 5,
                     "description": "Beam search width"
+                },
+                "timestamps": {
+                    "type": "boolean",
+                    "default": true,
+                    "description": "Decode segment timestamps"
+                },
+                "word_timestamps": {
+                    "type": "boolean",
+                    "default": false,
+                    "description": "Align each word to the audio"
+                },
+                "output_format": {
+                    "type": "string",
+                    "enum": ["json", "text"],
+                    "default": "json",
+                    "description": "Structured transcript or plain text"
                 }
             }
         }),
         input_type: MediaType::Audio,
-        output_type: MediaType::Text,
+        output_type: MediaType::Json,
     }
 }
 
@@ -211,6 +227,7 @@ mod tests {
         let schema = whisper_schema();
         assert_eq!(schema.node_type, "candle-whisper");
         assert_eq!(schema.input_type, MediaType::Audio);
+        assert_eq!(schema.output_type, MediaType::Json);
     }
 
     #[test]
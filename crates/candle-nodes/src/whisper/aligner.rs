@@ -0,0 +1,187 @@
+//! Cross-attention capture for word timestamps
+//!
+//! Candle's Whisper decoder keeps its attention weights to itself, so word
+//! alignment runs this second copy of the decoder once per window. It is
+//! loaded from the same checkpoint but only up to the deepest alignment
+//! head, and returns those heads' cross-attention instead of logits.
+
+use candle_core::{Device, IndexOp, Result, Tensor, D};
+use candle_nn::{
+    embedding, layer_norm, linear, linear_no_bias, Embedding, LayerNorm, Linear, Module, VarBuilder,
+};
+use candle_transformers::models::whisper::Config;
+
+/// Multi-head attention that also returns its weights
+struct Attention {
+    query: Linear,
+    key: Linear,
+    value: Linear,
+    out: Linear,
+    n_head: usize,
+}
+
+impl Attention {
+    fn load(n_state: usize, n_head: usize, vb: VarBuilder) -> Result<Self> {
+        Ok(Self {
+            query: linear(n_state, n_state, vb.pp("q_proj"))?,
+            key: linear_no_bias(n_state, n_state, vb.pp("k_proj"))?,
+            value: linear(n_state, n_state, vb.pp("v_proj"))?,
+            out: linear(n_state, n_state, vb.pp("out_proj"))?,
+            n_head,
+        })
+    }
+
+    fn split_heads(&self, x: &Tensor) -> Result<Tensor> {
+        let (n_batch, n_ctx, n_state) = x.dims3()?;
+        x.reshape((n_batch, n_ctx, self.n_head, n_state / self.n_head))?
+            .transpose(1, 2)
+    }
+
+    /// Attend from `x` to `xa`, or to itself, returning the output and the
+    /// weights as `(batch, head, query, key)`
+    fn forward(
+        &self,
+        x: &Tensor,
+        xa: Option<&Tensor>,
+        mask: Option<&Tensor>,
+    ) -> Result<(Tensor, Tensor)> {
+        let source = xa.unwrap_or(x);
+        let q = self.query.forward(x)?;
+        let k = self.key.forward(source)?;
+        let v = self.value.forward(source)?;
+
+        let scale = ((q.dim(D::Minus1)? / self.n_head) as f64).powf(-0.25);
+        let q = (self.split_heads(&q)? * scale)?;
+        let k = (self.split_heads(&k)?.transpose(2, 3)? * scale)?;
+        let v = self.split_heads(&v)?.contiguous()?;
+        let mut qk = q.matmul(&k)?;
+        if let Some(mask) = mask {
+            qk = qk.broadcast_add(mask)?;
+        }
+        let weights = candle_nn::ops::softmax_last_dim(&qk)?;
+        let wv = weights.matmul(&v)?.transpose(1, 2)?.flatten_from(2)?;
+        Ok((self.out.forward(&wv)?, weights))
+    }
+}
+
+/// Decoder layer returning its cross-attention weights
+struct Block {
+    attn: Attention,
+    attn_ln: LayerNorm,
+    cross_attn: Attention,
+    cross_attn_ln: LayerNorm,
+    fc1: Linear,
+    fc2: Linear,
+    mlp_ln: LayerNorm,
+}
+
+impl Block {
+    fn load(n_state: usize, n_head: usize, vb: VarBuilder) -> Result<Self> {
+        Ok(Self {
+            attn: Attention::load(n_state, n_head, vb.pp("self_attn"))?,
+            attn_ln: layer_norm(n_state, 1e-5, vb.pp("self_attn_layer_norm"))?,
+            cross_attn: Attention::load(n_state, n_head, vb.pp("encoder_attn"))?,
+            cross_attn_ln: layer_norm(n_state, 1e-5, vb.pp("encoder_attn_layer_norm"))?,
+            fc1: linear(n_state, 4 * n_state, vb.pp("fc1"))?,
+            fc2: linear(4 * n_state, n_state, vb.pp("fc2"))?,
+            mlp_ln: layer_norm(n_state, 1e-5, vb.pp("final_layer_norm"))?,
+        })
+    }
+
+    fn forward(&self, x: &Tensor, xa: &Tensor, mask: &Tensor) -> Result<(Tensor, Tensor)> {
+        let (attn, _) = self
+            .attn
+            .forward(&self.attn_ln.forward(x)?, None, Some(mask))?;
+        let x = (x + attn)?;
+        let (cross, weights) =
+            self.cross_attn
+                .forward(&self.cross_attn_ln.forward(&x)?, Some(xa), None)?;
+        let x = (x + cross)?;
+        let mlp = self
+            .fc2
+            .forward(&self.fc1.forward(&self.mlp_ln.forward(&x)?)?.gelu()?)?;
+        Ok(((x + mlp)?, weights))
+    }
+}
+
+/// Whisper decoder that exposes the cross-attention of its alignment heads
+pub(crate) struct CrossAttentionDecoder {
+    token_embedding: Embedding,
+    positional_embedding: Tensor,
+    blocks: Vec<Block>,
+    /// `(layer, head)` pairs that track the audio position of each token
+    heads: Vec<(usize, usize)>,
+}
+
+impl CrossAttentionDecoder {
+    /// Load from the `model.decoder` weights of a Whisper checkpoint
+    pub(crate) fn load(vb: VarBuilder, cfg: &Config, heads: Vec<(usize, usize)>) -> Result<Self> {
+        let n_state = cfg.d_model;
+        let n_layers = heads
+            .iter()
+            .map(|&(layer, _)| layer + 1)
+            .max()
+            .unwrap_or(0)
+            .min(cfg.decoder_layers);
+        let blocks = (0..n_layers)
+            .map(|i| {
+                Block::load(
+                    n_state,
+                    cfg.decoder_attention_heads,
+                    vb.pp(format!("layers.{i}")),
+                )
+            })
+            .collect::<Result<Vec<_>>>()?;
+        Ok(Self {
+            token_embedding: embedding(cfg.vocab_size, n_state, vb.pp("embed_tokens"))?,
+            positional_embedding: vb.get(
+                (cfg.max_target_positions, n_state),
+                "embed_positions.weight",
+            )?,
+            blocks,
+            heads,
+        })
+    }
+
+    /// Cross-attention of each alignment head, as token-by-frame rows
+    pub(crate) fn cross_attention(
+        &self,
+        tokens: &[u32],
+        audio_features: &Tensor,
+    ) -> Result<Vec<Vec<Vec<f32>>>> {
+        let device = audio_features.device();
+        let n_ctx = tokens.len();
+        let tokens = Tensor::new(tokens, device)?.unsqueeze(0)?;
+        let positions = self.positional_embedding.narrow(0, 0, n_ctx)?;
+        let mut x = self
+            .token_embedding
+            .forward(&tokens)?
+            .broadcast_add(&positions)?;
+        let mask = causal_mask(n_ctx, device)?;
+
+        let mut weights = Vec::with_capacity(self.heads.len());
+        for (layer, block) in self.blocks.iter().enumerate() {
+            let (next, cross) = block.forward(&x, audio_features, &mask)?;
+            x = next;
+            for &(_, head) in self.heads.iter().filter(|&&(l, _)| l == layer) {
+                weights.push(cross.i((0, head))?.to_vec2::<f32>()?);
+            }
+        }
+        Ok(weights)
+    }
+}
+
+/// Alignment heads for checkpoints that don't list any: every head in the
+/// upper half of the decoder
+pub(crate) fn default_alignment_heads(cfg: &Config) -> Vec<(usize, usize)> {
+    (cfg.decoder_layers / 2..cfg.decoder_layers)
+        .flat_map(|layer| (0..cfg.decoder_attention_heads).map(move |head| (layer, head)))
+        .collect()
+}
+
+fn causal_mask(n_ctx: usize, device: &Device) -> Result<Tensor> {
+    let mask: Vec<f32> = (0..n_ctx)
+        .flat_map(|i| (0..n_ctx).map(move |j| if j > i { f32::NEG_INFINITY } else { 0.0 }))
+        .collect();
+    Tensor::from_vec(mask, (n_ctx, n_ctx), device)
+}
@@ -120,8 +120,33 @@ pub struct WhisperConfig {
     /// Task: "transcribe" or "translate"
     #[serde(default = "default_task")]
     pub task: String,
+
+    /// Decode segment timestamps from Whisper's timestamp tokens
+    #[serde(default = "default_timestamps")]
+    pub timestamps: bool,
+
+    /// Align every word to the audio using cross-attention
+    #[serde(default)]
+    pub word_timestamps: bool,
+
+    /// Output format: "json" (segments, words, language) or "text"
+    #[serde(default = "default_output_format")]
+    pub output_format: String,
 }
 
+/// Language codes Whisper was trained on, in token order
+///
+/// `yue` only exists in the large-v3 vocabulary.
+pub const LANGUAGES: &[&str] = &[
+    "en", "zh", "de", "es", "ru", "ko", "fr", "ja", "pt", "tr", "pl", "ca", "nl", "ar", "sv", "it",
+    "id", "hi", "fi", "vi", "he", "uk", "el", "ms", "cs", "ro", "da", "hu", "ta", "no", "th", "ur",
+    "hr", "bg", "lt", "la", "mi", "ml", "cy", "sk", "te", "fa", "lv", "bn", "sr", "az", "sl", "kn",
+    "et", "mk", "br", "eu", "is", "hy", "ne", "mn", "bs", "kk", "sq", "sw", "gl", "mr", "pa", "si",
+    "km", "sn", "yo", "so", "af", "oc", "ka", "be", "tg", "sd", "gu", "am", "yi", "lo", "uz", "fo",
+    "ht", "ps", "tk", "nn", "mt", "sa", "lb", "my", "bo", "tl", "mg", "as", "tt", "haw", "ln",
+    "ha", "ba", "jw", "su", "yue",
+];
+
 fn default_language() -> String {
     "auto".to_string()
 }
@@ -138,6 +163,14 @@ fn default_task() -> String {
     "transcribe".to_string()
 }
 
+fn default_timestamps() -> bool {
+    true
+}
+
+fn default_output_format() -> String {
+    "json".to_string()
+}
+
 impl Default for WhisperConfig {
     fn default() -> Self {
         Self {
@@ -146,6 +179,9 @@ impl Default for WhisperConfig {
             device: default_device(),
             streaming: default_streaming(),
             task: default_task(),
+            timestamps: default_timestamps(),
+            word_timestamps: false,
+            output_format: default_output_format(),
         }
     }
 }
@@ -159,9 +195,9 @@ impl WhisperConfig {
     /// Validate configuration
     pub fn validate(&self) -> Result<(), String> {
         // Validate language code
-        if self.language != "auto" && self.language.len() != 2 {
+        if self.language != "auto" && !LANGUAGES.contains(&self.language.as_str()) {
             return Err(format!(
-                "Invalid language code: {}. Use 'auto' or ISO 639-1 code (e.g., 'en', 'es')",
+                "Invalid language code: {}. Use 'auto' or a Whisper language code (e.g., 'en', 'yue')",
                 self.language
             ));
         }
@@ -174,6 +210,14 @@ impl WhisperConfig {
             ));
         }
 
+        // Validate output format
+        if self.output_format != "json" && self.output_format != "text" {
+            return Err(format!(
+                "Invalid output_format: {}. Use 'json' or 'text'",
+                self.output_format
+            ));
+        }
+
         Ok(())
     }
 }
@@ -207,5 +251,23 @@ mod tests {
         assert_eq!(config.model, WhisperModel::Small);
         assert_eq!(config.language, "en");
         assert!(!config.streaming);
+        assert!(config.timestamps);
+        assert!(!config.word_timestamps);
+        assert_eq!(config.output_format, "json");
+    }
+
+    #[test]
+    fn test_validate_language() {
+        let mut config = WhisperConfig::default();
+        config.language = "haw".to_string();
+        assert!(config.validate().is_ok());
+        config.language = "xx".to_string();
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_languages_unique() {
+        let unique: std::collections::HashSet<_> = LANGUAGES.iter().collect();
+        assert_eq!(unique.len(), LANGUAGES.len());
     }
 }
@@ -1,11 +1,20 @@
 //! Whisper speech-to-text node
 //!
 //! Provides speech-to-text transcription using OpenAI's Whisper models
-//! via the Candle ML framework.
+//! via the Candle ML framework. Output is a JSON [`Transcript`] with the
+//! detected language, timed segments and (optionally) word timings, or
+//! plain text with `output_format: "text"`.
 
+#[cfg(feature = "whisper")]
+mod aligner;
 mod config;
+#[cfg_attr(not(feature = "whisper"), allow(dead_code))]
+mod timing;
+#[cfg_attr(not(feature = "whisper"), allow(dead_code))]
+mod transcript;
 
-pub use config::{WhisperConfig, WhisperModel};
+pub use config::{WhisperConfig, WhisperModel, LANGUAGES};
+pub use transcript::{Segment, SpeakerTurn, Transcript, Word};
 
 use crate::cache::ModelCache;
 use crate::convert::{AudioData, RuntimeDataConverter};
@@ -27,13 +36,21 @@ use tokio::sync::RwLock;
 use tracing::{debug, info};
 
 #[cfg(feature = "whisper")]
-use candle_core::{Device, IndexOp, Tensor};
+use aligner::CrossAttentionDecoder;
+#[cfg(feature = "whisper")]
+use candle_core::{Device, IndexOp, Tensor, D};
 #[cfg(feature = "whisper")]
 use candle_nn::VarBuilder;
 #[cfg(feature = "whisper")]
 use candle_transformers::models::whisper::{self as m, audio, Config};
 #[cfg(feature = "whisper")]
+use timing::FRAMES_PER_SECOND;
+#[cfg(feature = "whisper")]
 use tokenizers::Tokenizer;
+#[cfg(feature = "whisper")]
+use tracing::warn;
+#[cfg(feature = "whisper")]
+use transcript::{split_segments, WindowSegment};
 
 /// Whisper speech-to-text node
 pub struct WhisperNode {
@@ -62,6 +79,177 @@ struct WhisperModelState {
     config: Config,
     /// Mel filters for audio processing
     mel_filters: Vec<f32>,
+    /// Decoder copy exposing cross-attention (only with `word_timestamps`)
+    aligner: Option<CrossAttentionDecoder>,
+}
+
+/// Special token ids of the loaded tokenizer
+#[cfg(feature = "whisper")]
+struct SpecialTokens {
+    sot: u32,
+    /// `<|transcribe|>` or `<|translate|>`, per the configured task
+    task: u32,
+    eot: u32,
+    no_timestamps: u32,
+    no_speech: Option<u32>,
+    /// First timestamp token (`<|0.00|>`)
+    timestamp_begin: u32,
+    /// Language codes the vocabulary has a token for
+    languages: Vec<(&'static str, u32)>,
+}
+
+/// Tokens sampled for one 30 second window
+#[cfg(feature = "whisper")]
+#[derive(Default)]
+struct DecodedWindow {
+    /// Sampled tokens, prompt and end-of-text excluded
+    tokens: Vec<u32>,
+    /// Log probability of each sampled token
+    logprobs: Vec<f32>,
+    /// Probability of the no-speech token at the start of the window
+    no_speech_prob: f32,
+}
+
+#[cfg(feature = "whisper")]
+impl WhisperModelState {
+    /// Most likely spoken language and its probability, if the vocabulary
+    /// has language tokens
+    fn detect_language(
+        &mut self,
+        audio_features: &Tensor,
+        special: &SpecialTokens,
+    ) -> candle_core::Result<Option<(&'static str, f32)>> {
+        if special.languages.is_empty() {
+            return Ok(None);
+        }
+        let sot = Tensor::new(&[special.sot], &self.candle_device)?.unsqueeze(0)?;
+        let ys = self.model.decoder.forward(&sot, audio_features, true)?;
+        let logits = self.model.decoder.final_linear(&ys.i(..1)?)?.i(0)?.i(0)?;
+
+        let ids: Vec<u32> = special.languages.iter().map(|&(_, id)| id).collect();
+        let ids = Tensor::new(ids.as_slice(), &self.candle_device)?;
+        let probs: Vec<f32> =
+            candle_nn::ops::softmax(&logits.index_select(&ids, 0)?, D::Minus1)?.to_vec1()?;
+        Ok(probs
+            .iter()
+            .enumerate()
+            .max_by(|a, b| a.1.total_cmp(b.1))
+            .map(|(i, &p)| (special.languages[i].0, p)))
+    }
+
+    /// Greedily decode one window after `prompt`
+    fn decode_window(
+        &mut self,
+        audio_features: &Tensor,
+        prompt: &[u32],
+        special: &SpecialTokens,
+        timestamps: bool,
+    ) -> candle_core::Result<DecodedWindow> {
+        let mut tokens = prompt.to_vec();
+        let mut window = DecodedWindow::default();
+        let sample_len = self.config.max_target_positions / 2;
+        for i in 0..sample_len {
+            let tokens_t = Tensor::new(tokens.as_slice(), &self.candle_device)?.unsqueeze(0)?;
+            let ys = self
+                .model
+                .decoder
+                .forward(&tokens_t, audio_features, i == 0)?;
+
+            if i == 0 {
+                if let Some(no_speech) = special.no_speech {
+                    let logits = self.model.decoder.final_linear(&ys.i(..1)?)?.i(0)?.i(0)?;
+                    window.no_speech_prob = candle_nn::ops::softmax(&logits, D::Minus1)?
+                        .i(no_speech as usize)?
+                        .to_scalar::<f32>()?;
+                }
+            }
+
+            let (_, seq_len, _) = ys.dims3()?;
+            let mut logits: Vec<f32> = self
+                .model
+                .decoder
+                .final_linear(&ys.i((..1, seq_len - 1..))?)?
+                .i(0)?
+                .i(0)?
+                .to_vec1()?;
+
+            // Never sample control tokens (start, language, task, ...)
+            let control = (special.eot as usize + 1)..(special.timestamp_begin as usize);
+            if let Some(control) = logits.get_mut(control) {
+                control.fill(f32::NEG_INFINITY);
+            }
+            for &token in &self.config.suppress_tokens {
+                if let Some(logit) = logits.get_mut(token as usize) {
+                    *logit = f32::NEG_INFINITY;
+                }
+            }
+            if timestamps {
+                timing::apply_timestamp_rules(
+                    &mut logits,
+                    &window.tokens,
+                    special.eot,
+                    special.timestamp_begin,
+                );
+            } else if let Some(ts) = logits.get_mut(special.timestamp_begin as usize..) {
+                ts.fill(f32::NEG_INFINITY);
+            }
+
+            // Greedy decoding - take argmax
+            let logprobs = timing::log_softmax(&logits);
+            let (next_token, logprob) = logprobs
+                .iter()
+                .enumerate()
+                .max_by(|a, b| a.1.total_cmp(b.1))
+                .map(|(i, &logprob)| (i as u32, logprob))
+                .unwrap_or((special.eot, 0.0));
+
+            if next_token == special.eot || tokens.len() > self.config.max_target_positions {
+                break;
+            }
+            tokens.push(next_token);
+            window.tokens.push(next_token);
+            window.logprobs.push(logprob);
+        }
+        Ok(window)
+    }
+
+    /// Start and end of each word of `text`, in seconds from the window
+    /// start, from the cross-attention over its first `frames` encoder frames
+    fn word_times(
+        &self,
+        audio_features: &Tensor,
+        sot_sequence: &[u32],
+        special: &SpecialTokens,
+        text: &[u32],
+        word_lengths: &[usize],
+        frames: usize,
+    ) -> candle_core::Result<Vec<(f64, f64)>> {
+        let Some(aligner) = &self.aligner else {
+            return Ok(Vec::new());
+        };
+        let mut tokens = sot_sequence.to_vec();
+        tokens.push(special.no_timestamps);
+        tokens.extend_from_slice(text);
+        tokens.push(special.eot);
+
+        let heads = aligner.cross_attention(&tokens, audio_features)?;
+        let matrix = timing::alignment_matrix(&heads, frames);
+        Ok(timing::word_times(
+            &matrix[sot_sequence.len()..matrix.len() - 1],
+            word_lengths,
+        ))
+    }
+}
+
+/// Arithmetic mean (0 without values)
+#[cfg(feature = "whisper")]
+fn mean(values: impl Iterator<Item = f32>) -> f32 {
+    let (sum, count) = values.fold((0.0, 0), |(sum, count), v| (sum + v, count + 1));
+    if count == 0 {
+        0.0
+    } else {
+        sum / count as f32
+    }
 }
 
 #[cfg(not(feature = "whisper"))]
@@ -154,6 +342,24 @@ impl WhisperNode {
         let model = m::model::Whisper::load(&vb, config.clone())
             .map_err(|e| CandleNodeError::model_load("whisper", e.to_string()))?;
 
+        let aligner = if self.config.word_timestamps {
+            let heads = match self.alignment_heads(model_id).await {
+                Some(heads) => heads,
+                None => {
+                    warn!(
+                        "No alignment heads listed for {}, using the upper decoder layers",
+                        model_id
+                    );
+                    aligner::default_alignment_heads(&config)
+                }
+            };
+            let aligner = CrossAttentionDecoder::load(vb.pp("model.decoder"), &config, heads)
+                .map_err(|e| CandleNodeError::model_load("whisper", e.to_string()))?;
+            Some(aligner)
+        } else {
+            None
+        };
+
         info!("Whisper model loaded successfully");
 
         *state = Some(WhisperModelState {
@@ -162,11 +368,24 @@ impl WhisperNode {
             tokenizer,
             config,
             mel_filters,
+            aligner,
         });
 
         Ok(())
     }
 
+    /// Alignment heads listed in the checkpoint's generation config
+    #[cfg(feature = "whisper")]
+    async fn alignment_heads(&self, model_id: &str) -> Option<Vec<(usize, usize)>> {
+        let path = self
+            .cache
+            .download_model(model_id, "generation_config.json", None)
+            .await
+            .ok()?;
+        let config: Value = serde_json::from_str(&std::fs::read_to_string(path).ok()?).ok()?;
+        serde_json::from_value(config.get("alignment_heads")?.clone()).ok()
+    }
+
     /// Get mel filter bank coefficients
     #[cfg(feature = "whisper")]
     fn get_mel_filters(num_mel_bins: usize) -> Result<Vec<f32>> {
@@ -192,7 +411,7 @@ impl WhisperNode {
 
     /// Transcribe audio data
     #[cfg(feature = "whisper")]
-    async fn transcribe(&self, audio: AudioData) -> Result<String> {
+    async fn transcribe(&self, audio: AudioData) -> Result<Transcript> {
         // Ensure model is loaded
         self.load_model().await?;
 
@@ -209,87 +428,193 @@ impl WhisperNode {
         let mut state = self.model_state.write().await;
         let state = state.as_mut()
             .ok_or_else(|| CandleNodeError::inference(&self.node_id, "Model not loaded"))?;
+        let inference =
+            |e: candle_core::Error| CandleNodeError::inference(&self.node_id, e.to_string());
 
         // Convert audio to mel spectrogram
         let mel = audio::pcm_to_mel(&state.config, &prepared.samples, &state.mel_filters);
-        let mel_len = mel.len();
+        let mel_frames = mel.len() / state.config.num_mel_bins;
         let mel = Tensor::from_vec(
             mel,
-            (1, state.config.num_mel_bins, mel_len / state.config.num_mel_bins),
+            (1, state.config.num_mel_bins, mel_frames),
             &state.candle_device,
-        ).map_err(|e| CandleNodeError::inference(&self.node_id, e.to_string()))?;
+        )
+        .map_err(inference)?;
 
         debug!("Mel spectrogram shape: {:?}", mel.dims());
 
-        // Run encoder
-        let audio_features = state.model.encoder.forward(&mel, true)
-            .map_err(|e| CandleNodeError::inference(&self.node_id, e.to_string()))?;
-
-        // Get special tokens
-        let sot_token = self.token_id(&state.tokenizer, m::SOT_TOKEN)?;
-        let transcribe_token = self.token_id(&state.tokenizer, m::TRANSCRIBE_TOKEN)?;
-        let eot_token = self.token_id(&state.tokenizer, m::EOT_TOKEN)?;
-        let no_timestamps_token = self.token_id(&state.tokenizer, m::NO_TIMESTAMPS_TOKEN)?;
-
-        // Get language token if multilingual
-        let language_token = if self.config.model.is_multilingual() {
-            let lang_str = format!("<|{}|>", self.config.language);
-            self.token_id(&state.tokenizer, &lang_str).ok()
-        } else {
-            None
+        let special = self.special_tokens(&state.tokenizer)?;
+        let mut language = (self.config.language != "auto").then(|| self.config.language.clone());
+        let mut transcript = Transcript {
+            duration: prepared.samples.len() as f64 / m::SAMPLE_RATE as f64,
+            ..Default::default()
         };
 
-        // Build initial tokens
-        let mut tokens = vec![sot_token];
-        if let Some(lang_token) = language_token {
-            tokens.push(lang_token);
-        }
-        tokens.push(transcribe_token);
-        tokens.push(no_timestamps_token);
+        // Decode 30 second windows, resuming after the last complete segment
+        let content_frames = prepared.samples.len() / m::HOP_LENGTH;
+        let mut seek = 0;
+        while seek < content_frames {
+            let window_frames = usize::min(content_frames - seek, m::N_FRAMES);
+            let window_start = seek as f64 / FRAMES_PER_SECOND;
+            let mel_window = mel
+                .narrow(2, seek, usize::min(m::N_FRAMES, mel_frames - seek))
+                .map_err(inference)?;
+            let audio_features = state
+                .model
+                .encoder
+                .forward(&mel_window, true)
+                .map_err(inference)?;
+
+            // Identify the language from the first window
+            if language.is_none() {
+                let detected = state
+                    .detect_language(&audio_features, &special)
+                    .map_err(inference)?;
+                language = Some(match detected {
+                    Some((code, probability)) => {
+                        debug!("Detected language {} (p={:.2})", code, probability);
+                        transcript.language_probability = Some(probability);
+                        code.to_string()
+                    }
+                    // English-only checkpoints have no language tokens
+                    None => "en".to_string(),
+                });
+            }
+            let code = language.as_deref().unwrap_or("en");
 
-        // Decode loop
-        let sample_len = state.config.max_target_positions / 2;
-        for i in 0..sample_len {
-            let tokens_t = Tensor::new(tokens.as_slice(), &state.candle_device)
-                .map_err(|e| CandleNodeError::inference(&self.node_id, e.to_string()))?
-                .unsqueeze(0)
-                .map_err(|e| CandleNodeError::inference(&self.node_id, e.to_string()))?;
-
-            let ys = state.model.decoder.forward(&tokens_t, &audio_features, i == 0)
-                .map_err(|e| CandleNodeError::inference(&self.node_id, e.to_string()))?;
-
-            let (_, seq_len, _) = ys.dims3()
-                .map_err(|e| CandleNodeError::inference(&self.node_id, e.to_string()))?;
-            
-            let logits = state.model.decoder.final_linear(&ys.i((..1, seq_len - 1..))
-                .map_err(|e| CandleNodeError::inference(&self.node_id, e.to_string()))?)
-                .map_err(|e| CandleNodeError::inference(&self.node_id, e.to_string()))?
-                .i(0)
-                .map_err(|e| CandleNodeError::inference(&self.node_id, e.to_string()))?
-                .i(0)
-                .map_err(|e| CandleNodeError::inference(&self.node_id, e.to_string()))?;
+            let mut sot_sequence = vec![special.sot];
+            if let Some(&(_, token)) = special.languages.iter().find(|(c, _)| *c == code) {
+                sot_sequence.push(token);
+            }
+            sot_sequence.push(special.task);
+            let mut prompt = sot_sequence.clone();
+            if !self.config.timestamps {
+                prompt.push(special.no_timestamps);
+            }
 
-            // Greedy decoding - take argmax
-            let logits_v: Vec<f32> = logits.to_vec1()
-                .map_err(|e| CandleNodeError::inference(&self.node_id, e.to_string()))?;
-            let next_token = logits_v
-                .iter()
-                .enumerate()
-                .max_by(|(_, a), (_, b)| a.partial_cmp(b).unwrap())
-                .map(|(i, _)| i as u32)
-                .unwrap_or(eot_token);
+            let window = state
+                .decode_window(&audio_features, &prompt, &special, self.config.timestamps)
+                .map_err(inference)?;
+            let avg_logprob = mean(window.logprobs.iter().copied());
+            if window.no_speech_prob > m::NO_SPEECH_THRESHOLD as f32
+                && avg_logprob < m::LOGPROB_THRESHOLD as f32
+            {
+                debug!("Skipping window at {:.2}s without speech", window_start);
+                seek += window_frames;
+                continue;
+            }
 
-            if next_token == eot_token || tokens.len() > state.config.max_target_positions {
-                break;
+            let (window_segments, advance) = split_segments(
+                &window.tokens,
+                special.timestamp_begin,
+                window_frames as f64 / FRAMES_PER_SECOND,
+            );
+            seek += advance.map_or(window_frames, |t| {
+                ((t * FRAMES_PER_SECOND).round() as usize).min(window_frames)
+            });
+
+            // Indices of each segment's text tokens, empty segments dropped
+            let segments: Vec<(WindowSegment, Vec<usize>)> = window_segments
+                .into_iter()
+                .map(|segment| {
+                    let text: Vec<usize> = segment
+                        .tokens
+                        .clone()
+                        .filter(|&i| window.tokens[i] < special.eot)
+                        .collect();
+                    (segment, text)
+                })
+                .filter(|(_, text)| !text.is_empty())
+                .collect();
+            let ids = |indices: &[usize]| -> Vec<u32> {
+                indices.iter().map(|&i| window.tokens[i]).collect()
+            };
+            let decode = |ids: &[u32]| state.tokenizer.decode(ids, true).unwrap_or_default();
+
+            let mut words: Vec<Vec<Word>> = vec![Vec::new(); segments.len()];
+            if self.config.word_timestamps {
+                let mut text = Vec::new();
+                let mut word_tokens = Vec::new();
+                for (index, (_, indices)) in segments.iter().enumerate() {
+                    let segment_text = ids(indices);
+                    for range in timing::split_words(&segment_text, code, decode) {
+                        word_tokens.push((index, &indices[range]));
+                    }
+                    text.extend(segment_text);
+                }
+                let lengths: Vec<usize> = word_tokens.iter().map(|(_, w)| w.len()).collect();
+                let times = state
+                    .word_times(
+                        &audio_features,
+                        &sot_sequence,
+                        &special,
+                        &text,
+                        &lengths,
+                        window_frames / 2,
+                    )
+                    .map_err(inference)?;
+
+                for ((index, indices), (start, end)) in word_tokens.into_iter().zip(times) {
+                    let segment = &segments[index].0;
+                    let clamp = |t: f64| window_start + t.max(segment.start).min(segment.end);
+                    words[index].push(Word {
+                        word: decode(&ids(indices)).trim().to_string(),
+                        start: clamp(start),
+                        end: clamp(end),
+                        probability: mean(indices.iter().map(|&i| window.logprobs[i].exp())),
+                        speaker: None,
+                    });
+                }
+            }
+
+            for ((segment, indices), words) in segments.into_iter().zip(words) {
+                let text = decode(&ids(&indices));
+                transcript.text.push_str(&text);
+                transcript.segments.push(Segment {
+                    id: transcript.segments.len(),
+                    start: window_start + segment.start,
+                    end: window_start + segment.end,
+                    text: text.trim().to_string(),
+                    avg_logprob: mean(indices.iter().map(|&i| window.logprobs[i])),
+                    no_speech_prob: window.no_speech_prob,
+                    words,
+                    speaker: None,
+                });
             }
-            tokens.push(next_token);
         }
 
-        // Decode tokens to text
-        let text = state.tokenizer.decode(&tokens, true)
-            .map_err(|e| CandleNodeError::inference(&self.node_id, e.to_string()))?;
+        transcript.text = transcript.text.trim().to_string();
+        transcript.language = language.unwrap_or_else(|| self.config.language.clone());
+        Ok(transcript)
+    }
 
-        Ok(text)
+    /// Look up the special tokens used to prompt and parse the decoder
+    #[cfg(feature = "whisper")]
+    fn special_tokens(&self, tokenizer: &Tokenizer) -> Result<SpecialTokens> {
+        let task = if self.config.task == "translate" {
+            m::TRANSLATE_TOKEN
+        } else {
+            m::TRANSCRIBE_TOKEN
+        };
+        let no_timestamps = self.token_id(tokenizer, m::NO_TIMESTAMPS_TOKEN)?;
+        Ok(SpecialTokens {
+            sot: self.token_id(tokenizer, m::SOT_TOKEN)?,
+            task: self.token_id(tokenizer, task)?,
+            eot: self.token_id(tokenizer, m::EOT_TOKEN)?,
+            no_timestamps,
+            no_speech: m::NO_SPEECH_TOKENS
+                .iter()
+                .find_map(|token| tokenizer.token_to_id(token)),
+            timestamp_begin: no_timestamps + 1,
+            languages: LANGUAGES
+                .iter()
+                .filter_map(|&code| {
+                    tokenizer
+                        .token_to_id(&format!("<|{}|>", code))
+                        .map(|id| (code, id))
+                })
+                .collect(),
+        })
     }
 
     /// Get token ID from tokenizer
@@ -300,7 +625,7 @@ impl WhisperNode {
     }
 
     #[cfg(not(feature = "whisper"))]
-    async fn transcribe(&self, _audio: AudioData) -> Result<String> {
+    async fn transcribe(&self, _audio: AudioData) -> Result<Transcript> {
         Err(CandleNodeError::configuration(
             "candle-whisper",
             "Whisper feature not enabled at compile time",
@@ -330,9 +655,13 @@ impl AsyncStreamingNode for WhisperNode {
 
     async fn process(&self, data: RuntimeData) -> std::result::Result<RuntimeData, Error> {
         // Extract metadata from input (extract_audio borrows &data, so we can read both)
-        let input_metadata = match &data {
-            RuntimeData::Audio { metadata, .. } => metadata.clone(),
-            _ => None,
+        let (input_metadata, timestamp_us) = match &data {
+            RuntimeData::Audio {
+                metadata,
+                timestamp_us,
+                ..
+            } => (metadata.clone(), *timestamp_us),
+            _ => (None, None),
         };
 
         // Extract audio from input (borrows &data)
@@ -340,21 +669,45 @@ impl AsyncStreamingNode for WhisperNode {
             .map_err(|e| Error::Execution(e.to_string()))?;
 
         // Transcribe
-        let transcription = self
+        let mut transcript = self
             .transcribe(audio)
             .await
             .map_err(|e| Error::Execution(e.to_string()))?;
+        let diarization = input_metadata
+            .as_ref()
+            .and_then(|meta| meta.get("diarization"))
+            .cloned();
+
+        if self.config.output_format == "text" {
+            // Forward metadata if it contains diarization info
+            return Ok(match diarization {
+                Some(diarization) => RuntimeData::Json(serde_json::json!({
+                    "text": transcript.text,
+                    "diarization": diarization,
+                })),
+                None => RuntimeData::Text(transcript.text),
+            });
+        }
 
-        // Forward metadata if it contains diarization info
-        match input_metadata {
-            Some(ref meta) if meta.get("diarization").is_some() => {
-                Ok(RuntimeData::Json(serde_json::json!({
-                    "text": transcription,
-                    "diarization": meta["diarization"],
-                })))
-            }
-            _ => Ok(RuntimeData::Text(transcription)),
+        // Report times on the stream's clock, which diarization also uses
+        let offset = diarization
+            .as_ref()
+            .and_then(|d| d["time_offset"].as_f64())
+            .or(timestamp_us.map(|us| us as f64 / 1_000_000.0))
+            .unwrap_or(0.0);
+        transcript.shift(offset);
+        if let Some(diarization) = &diarization {
+            let turns: Vec<SpeakerTurn> =
+                serde_json::from_value(diarization["segments"].clone()).unwrap_or_default();
+            transcript.assign_speakers(&turns);
         }
+
+        let mut output =
+            serde_json::to_value(&transcript).map_err(|e| Error::Execution(e.to_string()))?;
+        if let Some(diarization) = diarization {
+            output["diarization"] = diarization;
+        }
+        Ok(RuntimeData::Json(output))
     }
 }
 
@@ -472,6 +825,12 @@ mod tests {
         assert!(node.is_ok());
     }
 
+    #[test]
+    fn test_whisper_node_rejects_unknown_output_format() {
+        let params = serde_json::json!({ "output_format": "srt" });
+        assert!(WhisperNode::from_params("test-whisper", &params).is_err());
+    }
+
     #[test]
     fn test_factory_node_type() {
         let factory = WhisperNodeFactory::new();
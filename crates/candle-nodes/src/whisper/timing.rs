@@ -0,0 +1,357 @@
+//! Timestamp sampling rules and word alignment
+//!
+//! Ports the parts of OpenAI Whisper's `ApplyTimestampRules` and
+//! `find_alignment` that work on plain vectors, so they run (and are
+//! tested) without a model.
+
+use std::ops::Range;
+
+/// Mel frames per second of audio (10 ms hop)
+pub(crate) const FRAMES_PER_SECOND: f64 = 100.0;
+
+/// Encoder frames per second (the encoder halves the mel frame rate)
+const TOKENS_PER_SECOND: f64 = 50.0;
+
+/// Latest timestamp step the first sampled token may use (1 second)
+const MAX_INITIAL_TIMESTAMP: usize = 50;
+
+/// Width of the median filter smoothing the alignment weights
+const MEDIAN_FILTER_WIDTH: usize = 7;
+
+/// Languages written without spaces, where every character is a word
+const UNSPACED_LANGUAGES: &[&str] = &["zh", "ja", "th", "lo", "my", "yue"];
+
+/// Log-softmax of `logits`
+pub(crate) fn log_softmax(logits: &[f32]) -> Vec<f32> {
+    let lse = log_sum_exp(logits);
+    logits.iter().map(|l| l - lse).collect()
+}
+
+fn log_sum_exp(values: &[f32]) -> f32 {
+    let max = values.iter().copied().fold(f32::NEG_INFINITY, f32::max);
+    if max == f32::NEG_INFINITY {
+        return max;
+    }
+    max + values.iter().map(|v| (v - max).exp()).sum::<f32>().ln()
+}
+
+/// Mask `logits` so the next token keeps the timestamp grammar
+///
+/// The first token must be a timestamp of at most one second, timestamps
+/// come in pairs around text and never go backwards, and a timestamp is
+/// forced whenever all timestamps together outweigh the best text token.
+pub(crate) fn apply_timestamp_rules(
+    logits: &mut [f32],
+    sampled: &[u32],
+    eot: u32,
+    timestamp_begin: u32,
+) {
+    let begin = (timestamp_begin as usize).min(logits.len());
+    let eot = (eot as usize).min(begin);
+    let is_timestamp = |t: u32| t >= timestamp_begin;
+    let suppress = |logits: &mut [f32], range: Range<usize>| {
+        let end = range.end.min(logits.len());
+        logits[range.start.min(end)..end].fill(f32::NEG_INFINITY);
+    };
+
+    let last_was_timestamp = sampled.last().is_some_and(|&t| is_timestamp(t));
+    let penultimate_was_timestamp = sampled.len() < 2 || is_timestamp(sampled[sampled.len() - 2]);
+    if last_was_timestamp {
+        if penultimate_was_timestamp {
+            suppress(logits, begin..logits.len());
+        } else {
+            suppress(logits, 0..eot);
+        }
+    }
+
+    if let Some(&last) = sampled.iter().rev().find(|&&t| is_timestamp(t)) {
+        // A closing timestamp may be repeated to open the next segment
+        let min = if last_was_timestamp && !penultimate_was_timestamp {
+            last
+        } else {
+            last + 1
+        };
+        suppress(logits, begin..min as usize);
+    }
+
+    if sampled.is_empty() {
+        suppress(logits, 0..begin);
+        suppress(logits, begin + MAX_INITIAL_TIMESTAMP + 1..logits.len());
+    }
+
+    let logprobs = log_softmax(logits);
+    let timestamp_logprob = log_sum_exp(&logprobs[begin..]);
+    let max_text_logprob = logprobs[..begin]
+        .iter()
+        .copied()
+        .fold(f32::NEG_INFINITY, f32::max);
+    if timestamp_logprob > max_text_logprob {
+        suppress(logits, 0..begin);
+    }
+}
+
+/// Group text tokens into words, returning each word's token range
+///
+/// A word starts at a token whose text begins with whitespace. For
+/// languages written without spaces every character is a word. Tokens
+/// that hold part of a multi-byte character stay with the next one.
+pub(crate) fn split_words(
+    tokens: &[u32],
+    language: &str,
+    decode: impl Fn(&[u32]) -> String,
+) -> Vec<Range<usize>> {
+    let mut characters: Vec<(Range<usize>, String)> = Vec::new();
+    let mut start = 0;
+    for end in 1..=tokens.len() {
+        let text = decode(&tokens[start..end]);
+        if !text.contains('\u{FFFD}') || end == tokens.len() {
+            characters.push((start..end, text));
+            start = end;
+        }
+    }
+
+    if UNSPACED_LANGUAGES.contains(&language) {
+        return characters.into_iter().map(|(range, _)| range).collect();
+    }
+    let mut words: Vec<Range<usize>> = Vec::new();
+    for (range, text) in characters {
+        match words.last_mut() {
+            Some(word) if !text.starts_with(char::is_whitespace) => word.end = range.end,
+            _ => words.push(range),
+        }
+    }
+    words
+}
+
+/// Replace every value with the median of the `width` values around it
+fn median_filter(values: &mut [f32], width: usize) {
+    let n = values.len();
+    if n <= width / 2 {
+        return;
+    }
+    let half = (width / 2) as isize;
+    let source = values.to_vec();
+    let mut window = Vec::with_capacity(width);
+    for (i, value) in values.iter_mut().enumerate() {
+        window.clear();
+        for offset in -half..=half {
+            // Reflect at the edges, as numpy's `reflect` padding does
+            let mut j = i as isize + offset;
+            if j < 0 {
+                j = -j;
+            } else if j >= n as isize {
+                j = 2 * (n as isize - 1) - j;
+            }
+            window.push(source[j as usize]);
+        }
+        window.sort_by(f32::total_cmp);
+        *value = window[width / 2];
+    }
+}
+
+/// Combine cross-attention weights into one token-by-frame matrix
+///
+/// Each head's weights are limited to the first `frames` encoder frames,
+/// standardized per frame across tokens, median filtered along time and
+/// then averaged over heads.
+pub(crate) fn alignment_matrix(heads: &[Vec<Vec<f32>>], frames: usize) -> Vec<Vec<f32>> {
+    let n_tokens = heads.first().map_or(0, Vec::len);
+    let mut matrix = vec![vec![0f32; frames]; n_tokens];
+    for head in heads {
+        let mut weights: Vec<Vec<f32>> = head
+            .iter()
+            .map(|row| row[..frames.min(row.len())].to_vec())
+            .collect();
+        for frame in 0..frames.min(weights.first().map_or(0, Vec::len)) {
+            let column: Vec<f32> = weights.iter().map(|row| row[frame]).collect();
+            let mean = column.iter().sum::<f32>() / n_tokens as f32;
+            let var = column.iter().map(|w| (w - mean).powi(2)).sum::<f32>() / n_tokens as f32;
+            let std = var.sqrt().max(1e-10);
+            for row in weights.iter_mut() {
+                row[frame] = (row[frame] - mean) / std;
+            }
+        }
+        for (row, out) in weights.iter_mut().zip(matrix.iter_mut()) {
+            median_filter(row, MEDIAN_FILTER_WIDTH);
+            for (w, o) in row.iter().zip(out.iter_mut()) {
+                *o += w / heads.len() as f32;
+            }
+        }
+    }
+    matrix
+}
+
+/// Cheapest monotonic path through `cost`, as `(row, column)` pairs
+fn dtw(cost: &[Vec<f32>]) -> Vec<(usize, usize)> {
+    let n = cost.len();
+    let m = cost.first().map_or(0, Vec::len);
+    let mut total = vec![vec![f32::INFINITY; m + 1]; n + 1];
+    let mut trace = vec![vec![0u8; m + 1]; n + 1];
+    total[0][0] = 0.0;
+    for j in 1..=m {
+        for i in 1..=n {
+            let (c0, c1, c2) = (total[i - 1][j - 1], total[i - 1][j], total[i][j - 1]);
+            let (c, t) = if c0 < c1 && c0 < c2 {
+                (c0, 0)
+            } else if c1 < c0 && c1 < c2 {
+                (c1, 1)
+            } else {
+                (c2, 2)
+            };
+            total[i][j] = cost[i - 1][j - 1] + c;
+            trace[i][j] = t;
+        }
+    }
+
+    let (mut i, mut j) = (n, m);
+    let mut path = Vec::with_capacity(n + m);
+    while i > 0 && j > 0 {
+        path.push((i - 1, j - 1));
+        match trace[i][j] {
+            0 => {
+                i -= 1;
+                j -= 1;
+            }
+            1 => i -= 1,
+            _ => j -= 1,
+        }
+    }
+    path.reverse();
+    path
+}
+
+/// Start and end time of every word, in seconds from the window start
+///
+/// `matrix` holds one row for the no-timestamps token followed by one per
+/// text token; row `k` attends where text token `k` is spoken, since each
+/// position predicts the next token. `word_lengths` are the number of text
+/// tokens in each word.
+pub(crate) fn word_times(matrix: &[Vec<f32>], word_lengths: &[usize]) -> Vec<(f64, f64)> {
+    let cost: Vec<Vec<f32>> = matrix
+        .iter()
+        .map(|row| row.iter().map(|w| -w).collect())
+        .collect();
+    let path = dtw(&cost);
+
+    // Frame where the path first reaches each row
+    let mut jump_times = Vec::with_capacity(matrix.len());
+    for (k, &(row, frame)) in path.iter().enumerate() {
+        if k == 0 || path[k - 1].0 != row {
+            jump_times.push(frame as f64 / TOKENS_PER_SECOND);
+        }
+    }
+
+    let mut times = Vec::with_capacity(word_lengths.len());
+    let mut boundary = 0;
+    for &length in word_lengths {
+        let start = jump_times.get(boundary).copied().unwrap_or(0.0);
+        boundary += length;
+        let end = jump_times.get(boundary).copied().unwrap_or(start);
+        times.push((start, end));
+    }
+    times
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EOT: u32 = 10;
+    const TS: u32 = 20;
+
+    fn logits() -> Vec<f32> {
+        vec![0.0; 100]
+    }
+
+    #[test]
+    fn test_first_token_is_an_early_timestamp() {
+        let mut l = logits();
+        apply_timestamp_rules(&mut l, &[], EOT, TS);
+        assert!(l[..TS as usize].iter().all(|v| v.is_infinite()));
+        assert!(l[TS as usize..=TS as usize + MAX_INITIAL_TIMESTAMP]
+            .iter()
+            .all(|v| v.is_finite()));
+        assert!(l[TS as usize + MAX_INITIAL_TIMESTAMP + 1..]
+            .iter()
+            .all(|v| v.is_infinite()));
+    }
+
+    #[test]
+    fn test_timestamps_come_in_pairs() {
+        // After text and one timestamp: only a timestamp (>= the last) or EOT
+        let mut l = logits();
+        l[EOT as usize] = 50.0;
+        apply_timestamp_rules(&mut l, &[TS, 3, TS + 5], EOT, TS);
+        assert!(l[..EOT as usize].iter().all(|v| v.is_infinite()));
+        assert!(l[EOT as usize].is_finite());
+        assert!(l[TS as usize + 4].is_infinite());
+        assert!(l[TS as usize + 5].is_finite());
+
+        // After a pair: text only, so a timestamp can't follow a pair
+        let mut l = logits();
+        l[3] = 50.0;
+        apply_timestamp_rules(&mut l, &[TS, 3, TS + 5, TS + 5], EOT, TS);
+        assert!(l[TS as usize..].iter().all(|v| v.is_infinite()));
+        assert!(l[3].is_finite());
+    }
+
+    #[test]
+    fn test_timestamp_mass_forces_a_timestamp() {
+        // One text token beats every single timestamp, but not all of them
+        let mut l = logits();
+        l[3] = 3.0;
+        apply_timestamp_rules(&mut l, &[TS, 3], EOT, TS);
+        assert!(l[3].is_infinite());
+        assert!(l[TS as usize + 1].is_finite());
+    }
+
+    #[test]
+    fn test_split_words() {
+        let pieces = ["He", "llo", ",", " wor", "ld", " !"];
+        let decode = |ids: &[u32]| ids.iter().map(|&i| pieces[i as usize]).collect::<String>();
+        let tokens = [0, 1, 2, 3, 4, 5];
+        assert_eq!(split_words(&tokens, "en", decode), [0..3, 3..5, 5..6]);
+        assert_eq!(split_words(&tokens, "ja", decode).len(), 6);
+    }
+
+    #[test]
+    fn test_split_words_keeps_partial_characters_together() {
+        // Tokens 0 and 1 each hold half of one character
+        let decode = |ids: &[u32]| match ids {
+            [0] | [1] => "\u{FFFD}".to_string(),
+            [0, 1] => "日".to_string(),
+            _ => "本".to_string(),
+        };
+        assert_eq!(split_words(&[0, 1, 2], "ja", decode), [0..2, 2..3]);
+    }
+
+    #[test]
+    fn test_median_filter() {
+        let mut values = [1.0, 1.0, 1.0, 9.0, 1.0, 1.0, 1.0, 1.0];
+        median_filter(&mut values, 3);
+        assert!(values.iter().all(|&v| v == 1.0));
+    }
+
+    #[test]
+    fn test_dtw_follows_the_diagonal() {
+        let cost = vec![
+            vec![0.0, 0.0, 9.0, 9.0],
+            vec![9.0, 9.0, 0.0, 9.0],
+            vec![9.0, 9.0, 9.0, 0.0],
+        ];
+        assert_eq!(dtw(&cost), [(0, 0), (0, 1), (1, 2), (2, 3)]);
+    }
+
+    #[test]
+    fn test_word_times() {
+        // Rows: no-timestamps, then text tokens a, b, c; b and c form one word
+        let mut matrix = vec![vec![-1.0; 8]; 4];
+        for (row, frames) in [0..2, 2..4, 4..6, 6..8].into_iter().enumerate() {
+            for frame in frames {
+                matrix[row][frame] = 1.0;
+            }
+        }
+        let times = word_times(&matrix, &[1, 2]);
+        assert_eq!(times, [(0.0, 0.04), (0.04, 0.12)]);
+    }
+}
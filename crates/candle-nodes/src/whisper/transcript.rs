@@ -0,0 +1,280 @@
+//! Structured transcription output
+//!
+//! Whisper decodes audio 30 seconds at a time. The helpers here turn the
+//! tokens sampled for one window into timed segments, and label segments
+//! and words with the speakers found by upstream diarization.
+
+use serde::{Deserialize, Serialize};
+use std::ops::Range;
+
+/// Seconds per timestamp token step
+pub(crate) const TIME_PRECISION: f64 = 0.02;
+
+/// Transcription of one audio input
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct Transcript {
+    /// Full text
+    pub text: String,
+    /// Language code, configured or detected
+    pub language: String,
+    /// Probability of the detected language (absent when configured)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub language_probability: Option<f32>,
+    /// Audio duration in seconds
+    pub duration: f64,
+    /// Timed segments
+    pub segments: Vec<Segment>,
+}
+
+/// A run of text between two timestamp tokens
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct Segment {
+    /// Index within the transcript
+    pub id: usize,
+    /// Start time in seconds
+    pub start: f64,
+    /// End time in seconds
+    pub end: f64,
+    /// Segment text
+    pub text: String,
+    /// Mean log probability of the segment's text tokens
+    pub avg_logprob: f32,
+    /// Probability that the segment's window holds no speech
+    pub no_speech_prob: f32,
+    /// Word timings (only with `word_timestamps`)
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub words: Vec<Word>,
+    /// Speaker overlapping the segment most (only with diarization input)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub speaker: Option<String>,
+}
+
+/// A word aligned to the audio
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct Word {
+    /// Word text
+    pub word: String,
+    /// Start time in seconds
+    pub start: f64,
+    /// End time in seconds
+    pub end: f64,
+    /// Mean probability of the word's tokens
+    pub probability: f32,
+    /// Speaker overlapping the word most (only with diarization input)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub speaker: Option<String>,
+}
+
+/// Speaker turn as emitted by `SpeakerDiarizationNode`
+#[derive(Debug, Clone, Deserialize)]
+pub struct SpeakerTurn {
+    /// Start time in seconds
+    pub start: f64,
+    /// End time in seconds
+    pub end: f64,
+    /// Speaker identifier
+    pub speaker: String,
+}
+
+/// A segment's place within its window's sampled tokens
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct WindowSegment {
+    /// Start in seconds from the window start
+    pub start: f64,
+    /// End in seconds from the window start
+    pub end: f64,
+    /// Range of the sampled tokens, timestamp tokens included
+    pub tokens: Range<usize>,
+}
+
+/// Split a window's sampled tokens into segments on timestamp pairs
+///
+/// Follows OpenAI Whisper: consecutive timestamp tokens close one segment
+/// and open the next. When the window ends mid-segment, that segment is
+/// dropped and the second value is how far, in seconds, to seek before
+/// decoding it again; `None` means the whole window was consumed.
+pub(crate) fn split_segments(
+    tokens: &[u32],
+    timestamp_begin: u32,
+    window_duration: f64,
+) -> (Vec<WindowSegment>, Option<f64>) {
+    let is_timestamp = |t: u32| t >= timestamp_begin;
+    let time = |t: u32| {
+        if is_timestamp(t) {
+            (t - timestamp_begin) as f64 * TIME_PRECISION
+        } else {
+            0.0
+        }
+    };
+    let n = tokens.len();
+    let single_timestamp_ending =
+        n >= 2 && !is_timestamp(tokens[n - 2]) && is_timestamp(tokens[n - 1]);
+    let mut slices: Vec<usize> = (1..n)
+        .filter(|&i| is_timestamp(tokens[i - 1]) && is_timestamp(tokens[i]))
+        .collect();
+
+    if slices.is_empty() {
+        // At most one timestamp: the window is a single segment
+        let end = tokens
+            .iter()
+            .rev()
+            .copied()
+            .find(|&t| is_timestamp(t) && t != timestamp_begin)
+            .map_or(window_duration, time);
+        return (
+            vec![WindowSegment {
+                start: 0.0,
+                end,
+                tokens: 0..n,
+            }],
+            None,
+        );
+    }
+
+    if single_timestamp_ending {
+        slices.push(n);
+    }
+    let mut segments = Vec::with_capacity(slices.len());
+    let mut last = 0;
+    for current in slices {
+        segments.push(WindowSegment {
+            start: time(tokens[last]),
+            end: time(tokens[current - 1]),
+            tokens: last..current,
+        });
+        last = current;
+    }
+
+    let seek = if single_timestamp_ending {
+        None
+    } else {
+        Some(time(tokens[last - 1])).filter(|&t| t > 0.0)
+    };
+    (segments, seek)
+}
+
+/// Speaker whose turns overlap `[start, end]` the most
+fn speaker_at(turns: &[SpeakerTurn], start: f64, end: f64) -> Option<String> {
+    turns
+        .iter()
+        .map(|turn| {
+            let overlap = if end > start {
+                turn.end.min(end) - turn.start.max(start)
+            } else if turn.start <= start && start < turn.end {
+                // Zero-length spans take the turn they fall in
+                f64::MIN_POSITIVE
+            } else {
+                0.0
+            };
+            (turn, overlap)
+        })
+        .filter(|(_, overlap)| *overlap > 0.0)
+        .max_by(|a, b| a.1.total_cmp(&b.1))
+        .map(|(turn, _)| turn.speaker.clone())
+}
+
+impl Transcript {
+    /// Move every segment and word `offset` seconds later
+    pub fn shift(&mut self, offset: f64) {
+        for segment in &mut self.segments {
+            segment.start += offset;
+            segment.end += offset;
+            for word in &mut segment.words {
+                word.start += offset;
+                word.end += offset;
+            }
+        }
+    }
+
+    /// Label segments and words with the speaker they overlap most
+    pub fn assign_speakers(&mut self, turns: &[SpeakerTurn]) {
+        for segment in &mut self.segments {
+            segment.speaker = speaker_at(turns, segment.start, segment.end);
+            for word in &mut segment.words {
+                word.speaker = speaker_at(turns, word.start, word.end);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TS: u32 = 50365;
+
+    #[test]
+    fn test_split_segments_on_timestamp_pairs() {
+        // <0.00> a b <1.00><1.00> c <2.00> d
+        let tokens = [TS, 1, 2, TS + 50, TS + 50, 3, TS + 100, 4];
+        let (segments, seek) = split_segments(&tokens, TS, 30.0);
+        assert_eq!(
+            segments,
+            [WindowSegment {
+                start: 0.0,
+                end: 1.0,
+                tokens: 0..4,
+            }]
+        );
+        // The unfinished `c` segment is decoded again from 1.00
+        assert_eq!(seek, Some(1.0));
+    }
+
+    #[test]
+    fn test_split_segments_single_timestamp_ending() {
+        // <0.00> a <1.00><1.00> b <2.00>
+        let tokens = [TS, 1, TS + 50, TS + 50, 2, TS + 100];
+        let (segments, seek) = split_segments(&tokens, TS, 30.0);
+        assert_eq!(segments.len(), 2);
+        assert_eq!((segments[1].start, segments[1].end), (1.0, 2.0));
+        assert_eq!(segments[1].tokens, 3..6);
+        assert_eq!(seek, None);
+    }
+
+    #[test]
+    fn test_split_segments_without_pairs() {
+        let (segments, seek) = split_segments(&[1, 2, 3], TS, 12.5);
+        assert_eq!((segments[0].start, segments[0].end), (0.0, 12.5));
+        assert_eq!(seek, None);
+
+        let (segments, _) = split_segments(&[TS, 1, 2, TS + 150], TS, 12.5);
+        assert_eq!(segments[0].end, 3.0);
+    }
+
+    #[test]
+    fn test_assign_speakers() {
+        let turns = vec![
+            SpeakerTurn {
+                start: 0.0,
+                end: 1.5,
+                speaker: "0".to_string(),
+            },
+            SpeakerTurn {
+                start: 1.5,
+                end: 4.0,
+                speaker: "1".to_string(),
+            },
+        ];
+        let word = |start, end| Word {
+            start,
+            end,
+            ..Default::default()
+        };
+        let mut transcript = Transcript {
+            segments: vec![Segment {
+                start: 0.0,
+                end: 2.0,
+                words: vec![word(0.0, 0.8), word(1.2, 2.0), word(2.0, 2.0)],
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+        transcript.shift(1.0);
+        transcript.assign_speakers(&turns);
+
+        let segment = &transcript.segments[0];
+        assert_eq!(segment.speaker.as_deref(), Some("1"));
+        let speakers: Vec<_> = segment.words.iter().map(|w| w.speaker.as_deref()).collect();
+        assert_eq!(speakers, [Some("0"), Some("1"), Some("1")]);
+    }
+}
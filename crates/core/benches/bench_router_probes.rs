@@ -94,6 +94,7 @@ fn chain_manifest(n: usize) -> Manifest {
         .map(|i| Connection {
             from: format!("n{i}"),
             to: format!("n{}", i + 1),
+            ..Default::default()
         })
         .collect();
     Manifest {
@@ -124,6 +125,7 @@ fn audio_packet(session: &str, seq: u64, samples: usize) -> DataPacket {
         session_id: session.to_string(),
         sequence: seq,
         sub_sequence: 0,
+        trace_context: None,
     }
 }
 
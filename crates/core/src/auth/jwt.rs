@@ -0,0 +1,139 @@
+//! HMAC-signed JWT API keys
+
+use super::{AuthError, Authenticator, Principal, RateLimit, Scope};
+use jsonwebtoken::{decode, encode, Algorithm, DecodingKey, EncodingKey, Header, Validation};
+use serde::{Deserialize, Serialize};
+
+/// Claims of a JWT API key
+///
+/// ```json
+/// { "sub": "key-42", "tenant": "acme", "scopes": ["stream"],
+///   "manifests": ["transcribe"], "exp": 1767225600 }
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ApiKeyClaims {
+    /// Key id
+    pub sub: String,
+    pub tenant: String,
+    /// Defaults to [`Scope::defaults`] when empty
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub scopes: Vec<Scope>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub manifests: Option<Vec<String>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub node_types: Option<Vec<String>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rate_limit: Option<RateLimit>,
+    /// Expiry (Unix seconds); keys without one never expire
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub exp: Option<i64>,
+}
+
+impl From<ApiKeyClaims> for Principal {
+    fn from(claims: ApiKeyClaims) -> Self {
+        Principal {
+            key_id: claims.sub,
+            tenant_id: claims.tenant,
+            scopes: if claims.scopes.is_empty() {
+                Scope::defaults()
+            } else {
+                claims.scopes
+            },
+            allowed_manifests: claims.manifests,
+            allowed_node_types: claims.node_types,
+            rate_limit: claims.rate_limit,
+        }
+    }
+}
+
+/// Validates HS256 JWT API keys signed with a shared secret
+pub struct JwtAuthenticator {
+    secret: String,
+}
+
+impl JwtAuthenticator {
+    pub fn new(secret: impl Into<String>) -> Self {
+        Self {
+            secret: secret.into(),
+        }
+    }
+
+    /// Mint a key for `claims`
+    pub fn issue(&self, claims: &ApiKeyClaims) -> Result<String, AuthError> {
+        encode(
+            &Header::new(Algorithm::HS256),
+            claims,
+            &EncodingKey::from_secret(self.secret.as_bytes()),
+        )
+        .map_err(|e| AuthError::InvalidCredentials(e.to_string()))
+    }
+}
+
+impl Authenticator for JwtAuthenticator {
+    fn authenticate(&self, token: &str) -> Result<Principal, AuthError> {
+        let mut validation = Validation::new(Algorithm::HS256);
+        validation.leeway = 0;
+        // `exp` is optional: long-lived keys are revoked by rotating the secret
+        validation.required_spec_claims.clear();
+
+        let data = decode::<ApiKeyClaims>(
+            token,
+            &DecodingKey::from_secret(self.secret.as_bytes()),
+            &validation,
+        )
+        .map_err(|e| match e.kind() {
+            jsonwebtoken::errors::ErrorKind::ExpiredSignature => AuthError::Expired,
+            _ => AuthError::InvalidCredentials(e.to_string()),
+        })?;
+        Ok(data.claims.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn claims() -> ApiKeyClaims {
+        ApiKeyClaims {
+            sub: "key-42".to_string(),
+            tenant: "acme".to_string(),
+            scopes: vec![Scope::Stream],
+            manifests: Some(vec!["transcribe".to_string()]),
+            node_types: None,
+            rate_limit: None,
+            exp: None,
+        }
+    }
+
+    #[test]
+    fn test_roundtrip() {
+        let auth = JwtAuthenticator::new("secret");
+        let token = auth.issue(&claims()).unwrap();
+        let principal = auth.authenticate(&token).unwrap();
+        assert_eq!(principal.key_id, "key-42");
+        assert_eq!(principal.tenant_id, "acme");
+        assert_eq!(principal.scopes, vec![Scope::Stream]);
+        assert_eq!(
+            principal.allowed_manifests,
+            Some(vec!["transcribe".to_string()])
+        );
+    }
+
+    #[test]
+    fn test_rejects_wrong_secret_and_expired() {
+        let token = JwtAuthenticator::new("secret").issue(&claims()).unwrap();
+        assert!(matches!(
+            JwtAuthenticator::new("other").authenticate(&token),
+            Err(AuthError::InvalidCredentials(_))
+        ));
+
+        let auth = JwtAuthenticator::new("secret");
+        let expired = auth
+            .issue(&ApiKeyClaims {
+                exp: Some(chrono::Utc::now().timestamp() - 60),
+                ..claims()
+            })
+            .unwrap();
+        assert_eq!(auth.authenticate(&expired), Err(AuthError::Expired));
+    }
+}
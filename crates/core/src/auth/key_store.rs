@@ -0,0 +1,210 @@
+//! File-backed API key store
+//!
+//! ```yaml
+//! keys:
+//!   - id: acme-transcriber
+//!     tenant: acme
+//!     key_sha256: 9f86d081884c7d659a2feaa0c55ad015a3bf4f1b2b0b822cd15d6c15b0f00a08
+//!     scopes: [execute, stream]
+//!     node_types: [WhisperNode, SileroVADNode]
+//!     rate_limit: { requests_per_minute: 120 }
+//! ```
+//!
+//! Keys are stored as SHA-256 hex digests ([`hash_key`]) so the file never
+//! holds a usable secret. A plain `key` field is accepted for local
+//! development. YAML is used for `.yaml`/`.yml` files, JSON otherwise.
+
+use super::{AuthError, Authenticator, Principal, RateLimit, Scope};
+use parking_lot::RwLock;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+/// SHA-256 hex digest of an API key, as stored in `key_sha256`
+pub fn hash_key(key: &str) -> String {
+    hex::encode(Sha256::digest(key.as_bytes()))
+}
+
+/// One key in a [`KeyStoreFile`]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KeyEntry {
+    pub id: String,
+    pub tenant: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub key_sha256: Option<String>,
+    /// Plain-text key (development only)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub key: Option<String>,
+    /// Defaults to [`Scope::defaults`] when empty
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub scopes: Vec<Scope>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub manifests: Option<Vec<String>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub node_types: Option<Vec<String>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rate_limit: Option<RateLimit>,
+    /// Disabled keys are rejected without being removed from the file
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub disabled: bool,
+}
+
+impl KeyEntry {
+    fn principal(&self) -> Principal {
+        Principal {
+            key_id: self.id.clone(),
+            tenant_id: self.tenant.clone(),
+            scopes: if self.scopes.is_empty() {
+                Scope::defaults()
+            } else {
+                self.scopes.clone()
+            },
+            allowed_manifests: self.manifests.clone(),
+            allowed_node_types: self.node_types.clone(),
+            rate_limit: self.rate_limit,
+        }
+    }
+}
+
+/// On-disk layout of a key store
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct KeyStoreFile {
+    pub keys: Vec<KeyEntry>,
+}
+
+impl KeyStoreFile {
+    /// Index by key hash, rejecting entries without a key
+    fn index(self) -> Result<HashMap<String, KeyEntry>, AuthError> {
+        let mut index = HashMap::with_capacity(self.keys.len());
+        for entry in self.keys {
+            let hash = match (&entry.key_sha256, &entry.key) {
+                (Some(hash), _) => hash.to_ascii_lowercase(),
+                (None, Some(key)) => hash_key(key),
+                (None, None) => {
+                    return Err(AuthError::KeyStore(format!(
+                        "key '{}' has neither key_sha256 nor key",
+                        entry.id
+                    )))
+                }
+            };
+            index.insert(hash, entry);
+        }
+        Ok(index)
+    }
+}
+
+/// Authenticates against a [`KeyStoreFile`] on disk
+pub struct KeyStoreAuthenticator {
+    path: Option<PathBuf>,
+    keys: RwLock<HashMap<String, KeyEntry>>,
+}
+
+impl KeyStoreAuthenticator {
+    /// Load the key store at `path`
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, AuthError> {
+        let path = path.as_ref().to_path_buf();
+        let keys = Self::read(&path)?;
+        tracing::info!(path = %path.display(), keys = keys.len(), "Loaded API key store");
+        Ok(Self {
+            path: Some(path),
+            keys: RwLock::new(keys),
+        })
+    }
+
+    /// An in-memory store, mainly for tests
+    pub fn from_file(file: KeyStoreFile) -> Result<Self, AuthError> {
+        Ok(Self {
+            path: None,
+            keys: RwLock::new(file.index()?),
+        })
+    }
+
+    /// Re-read the file; on error the previous keys stay in effect
+    pub fn reload(&self) -> Result<usize, AuthError> {
+        let Some(path) = &self.path else {
+            return Ok(self.keys.read().len());
+        };
+        let keys = Self::read(path)?;
+        let count = keys.len();
+        *self.keys.write() = keys;
+        Ok(count)
+    }
+
+    fn read(path: &Path) -> Result<HashMap<String, KeyEntry>, AuthError> {
+        let text = std::fs::read_to_string(path)
+            .map_err(|e| AuthError::KeyStore(format!("{}: {}", path.display(), e)))?;
+        let is_yaml = matches!(
+            path.extension().and_then(|e| e.to_str()),
+            Some("yaml") | Some("yml")
+        );
+        let file: KeyStoreFile = if is_yaml {
+            serde_yaml::from_str(&text).map_err(|e| AuthError::KeyStore(e.to_string()))?
+        } else {
+            serde_json::from_str(&text).map_err(|e| AuthError::KeyStore(e.to_string()))?
+        };
+        file.index()
+    }
+}
+
+impl Authenticator for KeyStoreAuthenticator {
+    fn authenticate(&self, token: &str) -> Result<Principal, AuthError> {
+        let keys = self.keys.read();
+        match keys.get(&hash_key(token)) {
+            Some(entry) if entry.disabled => Err(AuthError::Forbidden(format!(
+                "key '{}' is disabled",
+                entry.id
+            ))),
+            Some(entry) => Ok(entry.principal()),
+            None => Err(AuthError::InvalidCredentials("unknown key".to_string())),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_load_and_reload_yaml() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("keys.yaml");
+        std::fs::write(
+            &path,
+            format!(
+                "keys:\n  - id: acme\n    tenant: acme\n    key_sha256: {}\n    scopes: [stream]\n",
+                hash_key("acme-secret")
+            ),
+        )
+        .unwrap();
+
+        let store = KeyStoreAuthenticator::load(&path).unwrap();
+        let principal = store.authenticate("acme-secret").unwrap();
+        assert_eq!(principal.tenant_id, "acme");
+        assert_eq!(principal.scopes, vec![Scope::Stream]);
+        assert!(store.authenticate("wrong").is_err());
+
+        std::fs::write(
+            &path,
+            "keys:\n  - id: dev\n    tenant: dev\n    key: dev-secret\n  - id: old\n    tenant: acme\n    key: old-secret\n    disabled: true\n",
+        )
+        .unwrap();
+        assert_eq!(store.reload().unwrap(), 2);
+        assert!(store.authenticate("acme-secret").is_err());
+        assert_eq!(store.authenticate("dev-secret").unwrap().key_id, "dev");
+        assert!(matches!(
+            store.authenticate("old-secret"),
+            Err(AuthError::Forbidden(_))
+        ));
+    }
+
+    #[test]
+    fn test_entry_without_key_is_rejected() {
+        let file: KeyStoreFile =
+            serde_json::from_str(r#"{ "keys": [ { "id": "x", "tenant": "t" } ] }"#).unwrap();
+        assert!(matches!(
+            KeyStoreAuthenticator::from_file(file),
+            Err(AuthError::KeyStore(_))
+        ));
+    }
+}
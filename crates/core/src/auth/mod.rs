@@ -0,0 +1,546 @@
+//! API keys, scopes and tenants shared by every transport
+//!
+//! An [`Authenticator`] turns a bearer token into a [`Principal`]: the
+//! key's id, its tenant, the [`Scope`]s it grants, the manifests and node
+//! types it may run, and its rate limit. Three implementations ship here:
+//!
+//! - [`StaticTokenAuthenticator`] — a fixed token → principal table
+//!   (what `--auth-token` style flags build).
+//! - [`JwtAuthenticator`] — HMAC-signed JWTs carrying the principal in
+//!   their claims, so keys can be minted without touching the server.
+//! - [`KeyStoreAuthenticator`] — a JSON/YAML file of hashed keys that can
+//!   be reloaded while the server runs.
+//!
+//! Transports do not call authenticators directly. They hold an
+//! [`AuthGuard`], which chains the configured authenticators, applies
+//! per-key rate limits and checks scopes, and then check the manifest a
+//! request wants to run with [`Principal::check_manifest`]. The principal's
+//! tenant is passed on to
+//! [`PipelineExecutor::create_session_as`](crate::transport::PipelineExecutor::create_session_as)
+//! so admission quotas and control-bus ownership follow the key.
+//!
+//! With no authenticator configured the guard is open: every request runs
+//! as [`Principal::anonymous`].
+
+mod jwt;
+mod key_store;
+mod rate_limit;
+
+pub use jwt::{ApiKeyClaims, JwtAuthenticator};
+pub use key_store::{hash_key, KeyEntry, KeyStoreAuthenticator, KeyStoreFile};
+pub use rate_limit::RateLimiter;
+
+use crate::manifest::Manifest;
+use crate::transport::admission::DEFAULT_TENANT;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// Environment variable holding comma-separated static API tokens
+pub const ENV_AUTH_TOKENS: &str = "REMOTEMEDIA_AUTH_TOKENS";
+/// Environment variable holding the HMAC secret for JWT API keys
+pub const ENV_AUTH_JWT_SECRET: &str = "REMOTEMEDIA_AUTH_JWT_SECRET";
+/// Environment variable pointing at a key store file
+pub const ENV_AUTH_KEYS_FILE: &str = "REMOTEMEDIA_AUTH_KEYS_FILE";
+
+/// What a key may do
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Scope {
+    /// Unary pipeline execution
+    Execute,
+    /// Streaming sessions (gRPC streams, HTTP streams, WebRTC peers)
+    Stream,
+    /// Attaching to a running session's control bus
+    Control,
+    /// Session administration; implies every other scope
+    Admin,
+}
+
+impl Scope {
+    /// Scopes granted to keys that do not list any
+    pub fn defaults() -> Vec<Scope> {
+        vec![Scope::Execute, Scope::Stream, Scope::Control]
+    }
+}
+
+/// Per-key request rate limit
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RateLimit {
+    /// Sustained requests per minute
+    pub requests_per_minute: u32,
+    /// Requests allowed in a burst; defaults to `requests_per_minute`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub burst: Option<u32>,
+}
+
+/// The identity behind an authenticated request
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Principal {
+    /// Key identifier (never the secret itself)
+    pub key_id: String,
+    /// Tenant the key belongs to
+    pub tenant_id: String,
+    pub scopes: Vec<Scope>,
+    /// Manifest names (`metadata.name`) this key may run; `None` allows any
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub allowed_manifests: Option<Vec<String>>,
+    /// Node types this key may run; `None` allows any
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub allowed_node_types: Option<Vec<String>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rate_limit: Option<RateLimit>,
+}
+
+impl Principal {
+    /// A key for `tenant_id` with the default scopes and no restrictions
+    pub fn new(key_id: impl Into<String>, tenant_id: impl Into<String>) -> Self {
+        Self {
+            key_id: key_id.into(),
+            tenant_id: tenant_id.into(),
+            scopes: Scope::defaults(),
+            allowed_manifests: None,
+            allowed_node_types: None,
+            rate_limit: None,
+        }
+    }
+
+    /// The principal of unauthenticated requests when auth is disabled
+    pub fn anonymous() -> Self {
+        Self {
+            scopes: vec![Scope::Admin],
+            ..Self::new("anonymous", DEFAULT_TENANT)
+        }
+    }
+
+    pub fn with_scopes(mut self, scopes: Vec<Scope>) -> Self {
+        self.scopes = scopes;
+        self
+    }
+
+    pub fn with_rate_limit(mut self, rate_limit: RateLimit) -> Self {
+        self.rate_limit = Some(rate_limit);
+        self
+    }
+
+    /// Whether the key grants `scope`
+    pub fn has_scope(&self, scope: Scope) -> bool {
+        self.scopes.contains(&scope) || self.scopes.contains(&Scope::Admin)
+    }
+
+    /// Check that the key may run `manifest`
+    pub fn check_manifest(&self, manifest: &Manifest) -> Result<(), AuthError> {
+        if let Some(allowed) = &self.allowed_manifests {
+            if !allowed.contains(&manifest.metadata.name) {
+                return Err(AuthError::Forbidden(format!(
+                    "key '{}' may not run manifest '{}'",
+                    self.key_id, manifest.metadata.name
+                )));
+            }
+        }
+        if let Some(allowed) = &self.allowed_node_types {
+            if let Some(node) = manifest
+                .nodes
+                .iter()
+                .find(|n| !allowed.contains(&n.node_type))
+            {
+                return Err(AuthError::Forbidden(format!(
+                    "key '{}' may not run node type '{}' (node '{}')",
+                    self.key_id, node.node_type, node.id
+                )));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Authentication and authorization failures
+#[derive(Debug, Clone, PartialEq, thiserror::Error)]
+pub enum AuthError {
+    /// No credentials were presented
+    #[error("Missing API key. Send 'Authorization: Bearer <key>'.")]
+    MissingCredentials,
+
+    /// The credentials are unknown or malformed
+    #[error("Invalid API key: {0}")]
+    InvalidCredentials(String),
+
+    /// The credentials have expired
+    #[error("API key has expired")]
+    Expired,
+
+    /// The key is valid but not allowed to do this
+    #[error("Forbidden: {0}")]
+    Forbidden(String),
+
+    /// The key has exceeded its rate limit
+    #[error("Rate limit exceeded for key '{key_id}' (retry after {retry_after_ms} ms)")]
+    RateLimited { key_id: String, retry_after_ms: u64 },
+
+    /// The key store could not be loaded
+    #[error("Key store error: {0}")]
+    KeyStore(String),
+}
+
+impl AuthError {
+    /// Whether the caller failed to prove who it is, as opposed to being
+    /// refused (`401` vs `403`/`429`)
+    pub fn is_unauthenticated(&self) -> bool {
+        matches!(
+            self,
+            AuthError::MissingCredentials | AuthError::InvalidCredentials(_) | AuthError::Expired
+        )
+    }
+
+    /// HTTP status every transport answers this failure with: `401`,
+    /// `403`, `429`, or `500` when the key store is broken
+    pub fn http_status(&self) -> u16 {
+        match self {
+            AuthError::Forbidden(_) => 403,
+            AuthError::RateLimited { .. } => 429,
+            AuthError::KeyStore(_) => 500,
+            _ => 401,
+        }
+    }
+
+    /// Short name of the failure for JSON error bodies
+    pub fn error_type(&self) -> &'static str {
+        match self {
+            AuthError::Forbidden(_) => "forbidden",
+            AuthError::RateLimited { .. } => "rate_limited",
+            AuthError::KeyStore(_) => "internal",
+            _ => "unauthenticated",
+        }
+    }
+
+    /// How long a rate-limited key should wait before retrying
+    pub fn retry_after_ms(&self) -> Option<u64> {
+        match self {
+            AuthError::RateLimited { retry_after_ms, .. } => Some(*retry_after_ms),
+            _ => None,
+        }
+    }
+}
+
+/// gRPC status for an auth failure, with `retry-after-ms` metadata when
+/// the key is rate limited
+#[cfg(feature = "grpc-client")]
+impl From<AuthError> for tonic::Status {
+    fn from(err: AuthError) -> Self {
+        let mut status = match err {
+            AuthError::Forbidden(_) => tonic::Status::permission_denied(err.to_string()),
+            AuthError::RateLimited { .. } => tonic::Status::resource_exhausted(err.to_string()),
+            AuthError::KeyStore(_) => tonic::Status::internal(err.to_string()),
+            _ => tonic::Status::unauthenticated(err.to_string()),
+        };
+        if let Some(retry_after_ms) = err.retry_after_ms() {
+            status
+                .metadata_mut()
+                .insert("retry-after-ms", retry_after_ms.into());
+        }
+        status
+    }
+}
+
+/// Resolves a bearer token to a [`Principal`]
+pub trait Authenticator: Send + Sync {
+    /// Authenticate `token`
+    ///
+    /// Returns [`AuthError::InvalidCredentials`] for a token this
+    /// authenticator does not recognise, so [`AuthGuard`] can try the next
+    /// one.
+    fn authenticate(&self, token: &str) -> Result<Principal, AuthError>;
+}
+
+/// A fixed table of tokens
+#[derive(Debug, Clone, Default)]
+pub struct StaticTokenAuthenticator {
+    tokens: HashMap<String, Principal>,
+}
+
+impl StaticTokenAuthenticator {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Grant `token` to `principal`
+    pub fn with_token(mut self, token: impl Into<String>, principal: Principal) -> Self {
+        self.tokens.insert(token.into(), principal);
+        self
+    }
+
+    /// Tokens for the default tenant with the default scopes
+    ///
+    /// Each token gets a key id derived from its position, so logs never
+    /// carry the token itself.
+    pub fn from_tokens(tokens: impl IntoIterator<Item = String>) -> Self {
+        let mut auth = Self::new();
+        for (i, token) in tokens.into_iter().filter(|t| !t.is_empty()).enumerate() {
+            auth = auth.with_token(
+                token,
+                Principal::new(format!("static-{}", i), DEFAULT_TENANT),
+            );
+        }
+        auth
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.tokens.is_empty()
+    }
+}
+
+impl Authenticator for StaticTokenAuthenticator {
+    fn authenticate(&self, token: &str) -> Result<Principal, AuthError> {
+        // Every token is compared in full, so response timing doesn't
+        // reveal how much of a guess matched
+        let mut found = None;
+        for (candidate, principal) in &self.tokens {
+            if constant_time_eq(candidate.as_bytes(), token.as_bytes()) {
+                found = Some(principal);
+            }
+        }
+        found
+            .cloned()
+            .ok_or_else(|| AuthError::InvalidCredentials("unknown token".to_string()))
+    }
+}
+
+/// Byte equality in time independent of where the inputs differ
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+    a.iter().zip(b).fold(0u8, |diff, (x, y)| diff | (x ^ y)) == 0
+}
+
+/// The authentication entry point transports hold
+///
+/// Chains authenticators (first match wins), enforces per-key rate limits
+/// and checks scopes. Cheap to share behind an `Arc`.
+#[derive(Clone, Default)]
+pub struct AuthGuard {
+    authenticators: Vec<Arc<dyn Authenticator>>,
+    limiter: Arc<RateLimiter>,
+}
+
+impl AuthGuard {
+    /// A guard that admits every request as [`Principal::anonymous`]
+    pub fn open() -> Self {
+        Self::default()
+    }
+
+    /// Add an authenticator; configuring any makes credentials required
+    pub fn with_authenticator(mut self, authenticator: Arc<dyn Authenticator>) -> Self {
+        self.authenticators.push(authenticator);
+        self
+    }
+
+    /// Build from [`ENV_AUTH_TOKENS`], [`ENV_AUTH_JWT_SECRET`] and
+    /// [`ENV_AUTH_KEYS_FILE`]; open if none is set
+    pub fn from_env() -> Result<Self, AuthError> {
+        let mut guard = Self::open();
+        if let Ok(tokens) = std::env::var(ENV_AUTH_TOKENS) {
+            let tokens = StaticTokenAuthenticator::from_tokens(
+                tokens.split(',').map(|t| t.trim().to_string()),
+            );
+            if !tokens.is_empty() {
+                guard = guard.with_authenticator(Arc::new(tokens));
+            }
+        }
+        if let Ok(secret) = std::env::var(ENV_AUTH_JWT_SECRET) {
+            if !secret.is_empty() {
+                guard = guard.with_authenticator(Arc::new(JwtAuthenticator::new(secret)));
+            }
+        }
+        if let Ok(path) = std::env::var(ENV_AUTH_KEYS_FILE) {
+            if !path.is_empty() {
+                guard = guard.with_authenticator(Arc::new(KeyStoreAuthenticator::load(path)?));
+            }
+        }
+        Ok(guard)
+    }
+
+    /// Whether requests must carry credentials
+    pub fn is_required(&self) -> bool {
+        !self.authenticators.is_empty()
+    }
+
+    /// Identify the caller behind `token` and charge its rate limit
+    pub fn authenticate(&self, token: Option<&str>) -> Result<Principal, AuthError> {
+        if !self.is_required() {
+            return Ok(Principal::anonymous());
+        }
+        let token = token
+            .filter(|t| !t.is_empty())
+            .ok_or(AuthError::MissingCredentials)?;
+
+        let mut last_err = AuthError::InvalidCredentials("unknown token".to_string());
+        for authenticator in &self.authenticators {
+            match authenticator.authenticate(token) {
+                Ok(principal) => {
+                    self.limiter.check(&principal)?;
+                    return Ok(principal);
+                }
+                Err(AuthError::InvalidCredentials(reason)) => {
+                    last_err = AuthError::InvalidCredentials(reason);
+                }
+                Err(e) => return Err(e),
+            }
+        }
+        Err(last_err)
+    }
+
+    /// [`Self::authenticate`], then require `scope`
+    pub fn authorize(&self, token: Option<&str>, scope: Scope) -> Result<Principal, AuthError> {
+        let principal = self.authenticate(token)?;
+        if !principal.has_scope(scope) {
+            return Err(AuthError::Forbidden(format!(
+                "key '{}' lacks the '{:?}' scope",
+                principal.key_id, scope
+            )));
+        }
+        Ok(principal)
+    }
+}
+
+impl std::fmt::Debug for AuthGuard {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AuthGuard")
+            .field("authenticators", &self.authenticators.len())
+            .finish()
+    }
+}
+
+/// The token from an `Authorization: Bearer <token>` header value
+pub fn bearer_token(header_value: &str) -> Option<&str> {
+    header_value
+        .strip_prefix("Bearer ")
+        .map(str::trim)
+        .filter(|t| !t.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::manifest::{ManifestMetadata, NodeManifest};
+
+    fn manifest(name: &str, node_types: &[&str]) -> Manifest {
+        Manifest {
+            version: "v1".to_string(),
+            metadata: ManifestMetadata {
+                name: name.to_string(),
+                ..Default::default()
+            },
+            nodes: node_types
+                .iter()
+                .enumerate()
+                .map(|(i, t)| NodeManifest {
+                    id: format!("n{}", i),
+                    node_type: t.to_string(),
+                    ..Default::default()
+                })
+                .collect(),
+            connections: vec![],
+            python_env: None,
+        }
+    }
+
+    fn guard() -> AuthGuard {
+        let tokens = StaticTokenAuthenticator::new()
+            .with_token("user-key", Principal::new("user", "acme"))
+            .with_token(
+                "viewer-key",
+                Principal::new("viewer", "acme").with_scopes(vec![Scope::Control]),
+            )
+            .with_token(
+                "admin-key",
+                Principal::new("admin", "ops").with_scopes(vec![Scope::Admin]),
+            );
+        AuthGuard::open().with_authenticator(Arc::new(tokens))
+    }
+
+    #[test]
+    fn test_open_guard_is_anonymous() {
+        let principal = AuthGuard::open().authorize(None, Scope::Admin).unwrap();
+        assert_eq!(principal, Principal::anonymous());
+        assert_eq!(principal.tenant_id, DEFAULT_TENANT);
+    }
+
+    #[test]
+    fn test_scopes() {
+        let guard = guard();
+        assert_eq!(
+            guard.authorize(None, Scope::Stream),
+            Err(AuthError::MissingCredentials)
+        );
+        assert!(guard
+            .authorize(Some("nope"), Scope::Stream)
+            .unwrap_err()
+            .is_unauthenticated());
+
+        let user = guard.authorize(Some("user-key"), Scope::Stream).unwrap();
+        assert_eq!(user.tenant_id, "acme");
+        assert!(matches!(
+            guard.authorize(Some("user-key"), Scope::Admin),
+            Err(AuthError::Forbidden(_))
+        ));
+        assert!(matches!(
+            guard.authorize(Some("viewer-key"), Scope::Execute),
+            Err(AuthError::Forbidden(_))
+        ));
+        // Admin implies every scope
+        guard.authorize(Some("admin-key"), Scope::Execute).unwrap();
+    }
+
+    #[test]
+    fn test_manifest_restrictions() {
+        let mut principal = Principal::new("k", "acme");
+        principal.allowed_manifests = Some(vec!["transcribe".to_string()]);
+        principal.allowed_node_types = Some(vec!["WhisperNode".to_string()]);
+
+        principal
+            .check_manifest(&manifest("transcribe", &["WhisperNode"]))
+            .unwrap();
+        assert!(principal
+            .check_manifest(&manifest("other", &["WhisperNode"]))
+            .is_err());
+        let err = principal
+            .check_manifest(&manifest("transcribe", &["WhisperNode", "PythonNode"]))
+            .unwrap_err();
+        assert!(err.to_string().contains("PythonNode"));
+    }
+
+    #[test]
+    fn test_static_token_must_match_in_full() {
+        assert!(constant_time_eq(b"user-key", b"user-key"));
+        assert!(!constant_time_eq(b"user-key", b"user-kez"));
+        assert!(!constant_time_eq(b"user-key", b"user-key2"));
+
+        let guard = guard();
+        let principal = guard.authorize(Some("viewer-key"), Scope::Control).unwrap();
+        assert_eq!(principal.key_id, "viewer");
+        assert!(guard.authorize(Some("viewer-ke"), Scope::Control).is_err());
+    }
+
+    #[test]
+    fn test_bearer_token() {
+        assert_eq!(bearer_token("Bearer abc"), Some("abc"));
+        assert_eq!(bearer_token("Bearer "), None);
+        assert_eq!(bearer_token("Basic abc"), None);
+    }
+
+    #[test]
+    fn test_error_status() {
+        let limited = AuthError::RateLimited {
+            key_id: "k".to_string(),
+            retry_after_ms: 1500,
+        };
+        assert_eq!(limited.http_status(), 429);
+        assert_eq!(limited.error_type(), "rate_limited");
+        assert_eq!(limited.retry_after_ms(), Some(1500));
+        assert_eq!(AuthError::Expired.http_status(), 401);
+        assert_eq!(AuthError::Forbidden("x".to_string()).http_status(), 403);
+        assert_eq!(AuthError::KeyStore("x".to_string()).http_status(), 500);
+        assert_eq!(AuthError::Expired.retry_after_ms(), None);
+    }
+}
@@ -0,0 +1,100 @@
+//! Per-key token buckets
+
+use super::{AuthError, Principal};
+use parking_lot::Mutex;
+use std::collections::HashMap;
+use std::time::Instant;
+
+struct Bucket {
+    tokens: f64,
+    updated: Instant,
+}
+
+/// Token-bucket rate limiter keyed by [`Principal::key_id`]
+///
+/// Keys without a [`RateLimit`](super::RateLimit) are never limited.
+#[derive(Default)]
+pub struct RateLimiter {
+    buckets: Mutex<HashMap<String, Bucket>>,
+}
+
+impl RateLimiter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Take one request from `principal`'s bucket
+    pub fn check(&self, principal: &Principal) -> Result<(), AuthError> {
+        self.check_at(principal, Instant::now())
+    }
+
+    fn check_at(&self, principal: &Principal, now: Instant) -> Result<(), AuthError> {
+        let Some(limit) = principal.rate_limit else {
+            return Ok(());
+        };
+        if limit.requests_per_minute == 0 {
+            return Err(AuthError::RateLimited {
+                key_id: principal.key_id.clone(),
+                retry_after_ms: 60_000,
+            });
+        }
+        let capacity = f64::from(limit.burst.unwrap_or(limit.requests_per_minute).max(1));
+        let per_minute = f64::from(limit.requests_per_minute);
+
+        let mut buckets = self.buckets.lock();
+        let bucket = buckets.entry(principal.key_id.clone()).or_insert(Bucket {
+            tokens: capacity,
+            updated: now,
+        });
+        let elapsed_ms = now.saturating_duration_since(bucket.updated).as_secs_f64() * 1000.0;
+        bucket.tokens = (bucket.tokens + elapsed_ms * per_minute / 60_000.0).min(capacity);
+        bucket.updated = now;
+
+        if bucket.tokens >= 1.0 {
+            bucket.tokens -= 1.0;
+            Ok(())
+        } else {
+            Err(AuthError::RateLimited {
+                key_id: principal.key_id.clone(),
+                retry_after_ms: ((1.0 - bucket.tokens) * 60_000.0 / per_minute).ceil() as u64,
+            })
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::RateLimit;
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn test_bucket_refills() {
+        let limiter = RateLimiter::new();
+        let principal = Principal::new("k", "t").with_rate_limit(RateLimit {
+            requests_per_minute: 60,
+            burst: Some(2),
+        });
+        let start = Instant::now();
+
+        limiter.check_at(&principal, start).unwrap();
+        limiter.check_at(&principal, start).unwrap();
+        match limiter.check_at(&principal, start) {
+            Err(AuthError::RateLimited { retry_after_ms, .. }) => {
+                assert_eq!(retry_after_ms, 1_000)
+            }
+            other => panic!("expected rate limit, got {:?}", other),
+        }
+
+        // One request per second refills
+        limiter
+            .check_at(&principal, start + Duration::from_secs(1))
+            .unwrap();
+
+        // Unlimited keys are never charged
+        let unlimited = Principal::new("free", "t");
+        for _ in 0..1_000 {
+            limiter.check_at(&unlimited, start).unwrap();
+        }
+    }
+}
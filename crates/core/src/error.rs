@@ -118,6 +118,18 @@ pub enum Error {
     #[error("Parameter validation failed: {} error(s)", .0.len())]
     Validation(Vec<ValidationError>),
 
+    /// Session refused by executor admission control
+    #[error("Session rejected by admission control ({resource}): {message} (retry after {retry_after_ms} ms)")]
+    AdmissionRejected {
+        /// Exhausted quota (`sessions`, `tenant:<id>`, `node_type:<type>`,
+        /// `cpu_cores`, `memory_gb` or `gpu_memory_gb`)
+        resource: String,
+        /// Error message
+        message: String,
+        /// Suggested wait before retrying, in milliseconds
+        retry_after_ms: u64,
+    },
+
     // =========================================================================
     // Ingestion errors (spec 028)
     // =========================================================================
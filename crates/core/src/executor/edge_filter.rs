@@ -0,0 +1,284 @@
+//! Edge predicates for conditional routing
+//!
+//! A manifest connection may carry a `filter`. The session router evaluates
+//! it against every output travelling along that edge and forwards only the
+//! frames that match, so routing decisions like "only final transcripts go
+//! to the LLM" live in the manifest instead of in one-off filter nodes.
+//!
+//! # Manifest syntax
+//!
+//! ```json
+//! { "from": "stt", "to": "llm",
+//!   "filter": { "all": [
+//!     { "data_type": ["json"] },
+//!     { "metadata": { "pointer": "/is_final", "equals": true } }
+//!   ] } }
+//! ```
+//!
+//! - `data_type`: the frame is one of the listed `RuntimeData` variants
+//! - `metadata`: a JSON-pointer lookup on the frame's metadata compared
+//!   against a value (see [`MetadataMatch`])
+//! - `text_channel`: a text frame tagged with this channel (see
+//!   [`crate::data::text_channel`])
+//! - `all` / `any` / `not`: combinators
+//!
+//! The filter sees the output exactly as the source node emitted it, before
+//! any target-port envelope is applied.
+
+use crate::data::{split_text_str, RuntimeData};
+use crate::nodes::schema::RuntimeDataType;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// Predicate attached to a pipeline edge
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EdgeFilter {
+    /// Frame is one of these RuntimeData variants
+    DataType(Vec<RuntimeDataType>),
+
+    /// JSON-pointer comparison on the frame's metadata
+    Metadata(MetadataMatch),
+
+    /// Text frame routed on this channel (`"tts"` for untagged text)
+    TextChannel(String),
+
+    /// Every inner filter matches
+    All(Vec<EdgeFilter>),
+
+    /// At least one inner filter matches
+    Any(Vec<EdgeFilter>),
+
+    /// The inner filter does not match
+    Not(Box<EdgeFilter>),
+}
+
+/// JSON-pointer comparison for [`EdgeFilter::Metadata`]
+///
+/// The pointer is resolved against the `metadata` field of Audio, Image and
+/// Tensor frames, and against the value itself for JSON frames (which is
+/// where transcripts, VAD events and the like carry their flags). Other
+/// variants have no metadata and never match.
+///
+/// With no comparison set the pointer only has to resolve. `exists: false`
+/// inverts that and matches frames where it does not.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct MetadataMatch {
+    /// RFC 6901 pointer, e.g. `/is_final` or `/speaker/id`
+    pub pointer: String,
+
+    /// Resolved value must equal this
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub equals: Option<Value>,
+
+    /// Resolved value must not equal this
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub not_equals: Option<Value>,
+
+    /// Resolved value must be one of these
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub one_of: Option<Vec<Value>>,
+
+    /// Whether the pointer must resolve (default: true)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub exists: Option<bool>,
+}
+
+impl EdgeFilter {
+    /// Whether `data` may travel along the edge
+    pub fn matches(&self, data: &RuntimeData) -> bool {
+        match self {
+            EdgeFilter::DataType(types) => {
+                RuntimeDataType::of(data).is_some_and(|ty| types.contains(&ty))
+            }
+            EdgeFilter::Metadata(m) => m.matches(data),
+            EdgeFilter::TextChannel(channel) => match data {
+                RuntimeData::Text(text) => split_text_str(text).0 == channel,
+                _ => false,
+            },
+            EdgeFilter::All(filters) => filters.iter().all(|f| f.matches(data)),
+            EdgeFilter::Any(filters) => filters.iter().any(|f| f.matches(data)),
+            EdgeFilter::Not(filter) => !filter.matches(data),
+        }
+    }
+
+    /// Check the filter is well-formed; returns a description of the problem
+    pub fn validate(&self) -> std::result::Result<(), String> {
+        match self {
+            EdgeFilter::DataType(types) if types.is_empty() => {
+                Err("data_type filter lists no types".to_string())
+            }
+            EdgeFilter::Metadata(m) => {
+                if !m.pointer.is_empty() && !m.pointer.starts_with('/') {
+                    return Err(format!(
+                        "metadata pointer '{}' must be empty or start with '/'",
+                        m.pointer
+                    ));
+                }
+                Ok(())
+            }
+            EdgeFilter::TextChannel(channel) if channel.is_empty() => {
+                Err("text_channel filter has an empty channel".to_string())
+            }
+            EdgeFilter::All(filters) | EdgeFilter::Any(filters) => {
+                filters.iter().try_for_each(EdgeFilter::validate)
+            }
+            EdgeFilter::Not(filter) => filter.validate(),
+            _ => Ok(()),
+        }
+    }
+}
+
+impl MetadataMatch {
+    fn matches(&self, data: &RuntimeData) -> bool {
+        let metadata = match data {
+            RuntimeData::Audio { metadata, .. }
+            | RuntimeData::Image { metadata, .. }
+            | RuntimeData::Tensor { metadata, .. } => metadata.as_ref(),
+            RuntimeData::Json(value) => Some(value),
+            _ => None,
+        };
+        let resolved = metadata.and_then(|m| m.pointer(&self.pointer));
+
+        let Some(value) = resolved else {
+            return self.exists == Some(false);
+        };
+        if self.exists == Some(false) {
+            return false;
+        }
+
+        self.equals.as_ref().map_or(true, |v| value == v)
+            && self.not_equals.as_ref().map_or(true, |v| value != v)
+            && self.one_of.as_ref().map_or(true, |vs| vs.contains(value))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::tag_text_str;
+    use serde_json::json;
+
+    fn audio(metadata: Option<Value>) -> RuntimeData {
+        RuntimeData::Audio {
+            samples: vec![0.0; 16].into(),
+            sample_rate: 16_000,
+            channels: 1,
+            stream_id: None,
+            timestamp_us: None,
+            arrival_ts_us: None,
+            metadata,
+        }
+    }
+
+    #[test]
+    fn test_parse_manifest_syntax() {
+        let filter: EdgeFilter = serde_json::from_value(json!({
+            "all": [
+                { "data_type": ["json"] },
+                { "metadata": { "pointer": "/is_final", "equals": true } }
+            ]
+        }))
+        .unwrap();
+
+        assert_eq!(
+            filter,
+            EdgeFilter::All(vec![
+                EdgeFilter::DataType(vec![RuntimeDataType::Json]),
+                EdgeFilter::Metadata(MetadataMatch {
+                    pointer: "/is_final".to_string(),
+                    equals: Some(json!(true)),
+                    ..Default::default()
+                }),
+            ])
+        );
+        assert!(filter.validate().is_ok());
+    }
+
+    #[test]
+    fn test_final_transcripts_only() {
+        let filter: EdgeFilter = serde_json::from_value(json!({
+            "metadata": { "pointer": "/is_final", "equals": true }
+        }))
+        .unwrap();
+
+        assert!(filter.matches(&RuntimeData::Json(json!({"text": "hi", "is_final": true}))));
+        assert!(!filter.matches(&RuntimeData::Json(json!({"text": "h", "is_final": false}))));
+        assert!(!filter.matches(&RuntimeData::Json(json!({"text": "h"}))));
+        assert!(!filter.matches(&RuntimeData::Text("hi".into())));
+    }
+
+    #[test]
+    fn test_data_type() {
+        let filter = EdgeFilter::DataType(vec![RuntimeDataType::Audio, RuntimeDataType::Text]);
+        assert!(filter.matches(&audio(None)));
+        assert!(filter.matches(&RuntimeData::Text("x".into())));
+        assert!(!filter.matches(&RuntimeData::Json(json!({}))));
+        assert!(EdgeFilter::DataType(vec![]).validate().is_err());
+    }
+
+    #[test]
+    fn test_metadata_comparisons() {
+        let frame = audio(Some(json!({"speaker": {"id": "spk_1"}, "confidence": 0.9})));
+
+        let one_of = EdgeFilter::Metadata(MetadataMatch {
+            pointer: "/speaker/id".to_string(),
+            one_of: Some(vec![json!("spk_0"), json!("spk_1")]),
+            ..Default::default()
+        });
+        assert!(one_of.matches(&frame));
+
+        let not_equals = EdgeFilter::Metadata(MetadataMatch {
+            pointer: "/speaker/id".to_string(),
+            not_equals: Some(json!("spk_1")),
+            ..Default::default()
+        });
+        assert!(!not_equals.matches(&frame));
+
+        let present = EdgeFilter::Metadata(MetadataMatch {
+            pointer: "/confidence".to_string(),
+            ..Default::default()
+        });
+        assert!(present.matches(&frame));
+        assert!(!present.matches(&audio(None)));
+
+        let absent = EdgeFilter::Metadata(MetadataMatch {
+            pointer: "/confidence".to_string(),
+            exists: Some(false),
+            ..Default::default()
+        });
+        assert!(!absent.matches(&frame));
+        assert!(absent.matches(&audio(None)));
+
+        let bad = EdgeFilter::Metadata(MetadataMatch {
+            pointer: "confidence".to_string(),
+            ..Default::default()
+        });
+        assert!(bad.validate().is_err());
+    }
+
+    #[test]
+    fn test_text_channel() {
+        let filter = EdgeFilter::TextChannel("ui".to_string());
+        assert!(filter.matches(&RuntimeData::Text(tag_text_str("# title", "ui"))));
+        assert!(!filter.matches(&RuntimeData::Text("speak this".into())));
+
+        let tts = EdgeFilter::TextChannel("tts".to_string());
+        assert!(tts.matches(&RuntimeData::Text("speak this".into())));
+        assert!(!tts.matches(&RuntimeData::Json(json!({}))));
+    }
+
+    #[test]
+    fn test_combinators() {
+        let filter = EdgeFilter::Any(vec![
+            EdgeFilter::TextChannel("ui".to_string()),
+            EdgeFilter::Not(Box::new(EdgeFilter::DataType(vec![RuntimeDataType::Text]))),
+        ]);
+        assert!(filter.matches(&RuntimeData::Text(tag_text_str("x", "ui"))));
+        assert!(!filter.matches(&RuntimeData::Text("x".into())));
+        assert!(filter.matches(&audio(None)));
+
+        let nested_bad = EdgeFilter::All(vec![EdgeFilter::TextChannel(String::new())]);
+        assert!(nested_bad.validate().is_err());
+    }
+}
@@ -7,10 +7,12 @@
 //! - Handles node lifecycle (init, process, cleanup)
 //! - Runtime selection for Python nodes (Phase 1.10)
 
+pub mod edge_filter;
 pub mod error;
 pub mod graph;
 pub mod metrics;
 pub mod node_executor;
+pub mod remote_hosts;
 pub mod retry;
 pub mod runtime_selector;
 pub mod scheduler;
@@ -29,9 +31,11 @@ pub mod drift_metrics;
 pub mod streaming_scheduler;
 
 // Re-export key types for convenience
+pub use edge_filter::{EdgeFilter, MetadataMatch};
 pub use error::ExecutionErrorExt;
 pub use graph::{PipelineGraph as Graph, PipelineNode as Node};
 pub use metrics::{NodeMetrics, PipelineMetrics};
+pub use remote_hosts::{remote_members, wrap_remote_hosts};
 pub use retry::{CircuitBreaker, CircuitState, RetryPolicy};
 pub use scheduler::{ExecutionContext, Scheduler};
 
@@ -86,6 +90,43 @@ pub struct GraphNode {
     pub outputs: Vec<String>,
 }
 
+/// A port-level edge between two nodes
+///
+/// `from_port` / `to_port` are `None` when the manifest connection leaves
+/// them unset, which addresses the node's implicit `main` port.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GraphEdge {
+    /// Source node ID
+    pub from: String,
+
+    /// Source output port
+    pub from_port: Option<String>,
+
+    /// Target node ID
+    pub to: String,
+
+    /// Target input port
+    pub to_port: Option<String>,
+
+    /// Predicate an output must satisfy to travel along this edge
+    pub filter: Option<EdgeFilter>,
+
+    /// What the edge does when its target's input queue is full
+    pub overflow: crate::manifest::Overflow,
+}
+
+impl std::fmt::Display for GraphEdge {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        crate::manifest::fmt_connection(
+            f,
+            &self.from,
+            self.from_port.as_deref(),
+            &self.to,
+            self.to_port.as_deref(),
+        )
+    }
+}
+
 /// Pipeline execution graph
 #[derive(Debug)]
 pub struct PipelineGraph {
@@ -100,6 +141,9 @@ pub struct PipelineGraph {
 
     /// Sink nodes (nodes with no outputs)
     pub sinks: Vec<String>,
+
+    /// Port-level edges, in manifest order
+    pub edges: Vec<GraphEdge>,
 }
 
 impl PipelineGraph {
@@ -107,14 +151,20 @@ impl PipelineGraph {
     pub fn from_manifest(manifest: &Manifest) -> Result<Self> {
         let mut nodes = HashMap::new();
 
+        // Standby nodes only run in place of the node they back up
+        let standby = manifest.standby_nodes()?;
+
         // First pass: Create all nodes
         for node_manifest in &manifest.nodes {
+            if standby.contains(node_manifest.id.as_str()) {
+                continue;
+            }
             let graph_node = GraphNode {
                 id: node_manifest.id.clone(),
                 node_type: node_manifest.node_type.clone(),
                 params: node_manifest.params.clone(),
                 is_streaming: node_manifest.is_streaming,
-                capabilities: node_manifest.capabilities.clone(),
+                capabilities: node_manifest.resource_requirements().cloned(),
                 host: node_manifest.host.clone(),
                 inputs: Vec::new(),
                 outputs: Vec::new(),
@@ -123,6 +173,7 @@ impl PipelineGraph {
         }
 
         // Second pass: Build connections
+        let mut edges = Vec::with_capacity(manifest.connections.len());
         for connection in &manifest.connections {
             // Add output connection to source node
             if let Some(from_node) = nodes.get_mut(&connection.from) {
@@ -143,6 +194,15 @@ impl PipelineGraph {
                     connection.to
                 )));
             }
+
+            edges.push(GraphEdge {
+                from: connection.from.clone(),
+                from_port: connection.from_port.clone(),
+                to: connection.to.clone(),
+                to_port: connection.to_port.clone(),
+                filter: connection.filter.clone(),
+                overflow: connection.overflow(),
+            });
         }
 
         // Identify sources and sinks
@@ -166,9 +226,60 @@ impl PipelineGraph {
             execution_order,
             sources,
             sinks,
+            edges,
         })
     }
 
+    /// Edges leaving `node_id`, in manifest order
+    pub fn edges_from<'a>(&'a self, node_id: &'a str) -> impl Iterator<Item = &'a GraphEdge> + 'a {
+        self.edges.iter().filter(move |e| e.from == node_id)
+    }
+
+    /// Check every named port against the node schemas
+    ///
+    /// `schema_for` maps a node type to its schema. Nodes without a schema
+    /// (Python nodes, test doubles) are not checked; for everything else a
+    /// port other than `main` must be declared in the schema.
+    pub fn validate_ports<F>(&self, schema_for: F) -> Result<()>
+    where
+        F: Fn(&str) -> Option<crate::nodes::schema::NodeSchema>,
+    {
+        for edge in &self.edges {
+            for (node_id, port, is_output) in [
+                (&edge.from, edge.from_port.as_deref(), true),
+                (&edge.to, edge.to_port.as_deref(), false),
+            ] {
+                let Some(port) = port else { continue };
+                let node_type = &self.nodes[node_id].node_type;
+                let Some(schema) = schema_for(node_type) else {
+                    tracing::debug!(
+                        "Node '{}' ({}) has no schema; not checking port '{}'",
+                        node_id,
+                        node_type,
+                        port
+                    );
+                    continue;
+                };
+                let declared = if is_output {
+                    schema.has_output_port(port)
+                } else {
+                    schema.has_input_port(port)
+                };
+                if !declared {
+                    return Err(Error::Manifest(format!(
+                        "Connection {}: node '{}' ({}) has no {} port '{}'",
+                        edge,
+                        node_id,
+                        node_type,
+                        if is_output { "output" } else { "input" },
+                        port
+                    )));
+                }
+            }
+        }
+        Ok(())
+    }
+
     /// Perform topological sort using Kahn's algorithm
     fn topological_sort(nodes: &HashMap<String, GraphNode>) -> Result<Vec<String>> {
         let mut in_degree: HashMap<String, usize> = HashMap::new();
@@ -1764,10 +1875,12 @@ mod tests {
                 Connection {
                     from: "A".to_string(),
                     to: "B".to_string(),
+                    ..Default::default()
                 },
                 Connection {
                     from: "B".to_string(),
                     to: "C".to_string(),
+                    ..Default::default()
                 },
             ],
             python_env: None,
@@ -1865,18 +1978,22 @@ mod tests {
                 Connection {
                     from: "A".to_string(),
                     to: "B".to_string(),
+                    ..Default::default()
                 },
                 Connection {
                     from: "A".to_string(),
                     to: "C".to_string(),
+                    ..Default::default()
                 },
                 Connection {
                     from: "B".to_string(),
                     to: "D".to_string(),
+                    ..Default::default()
                 },
                 Connection {
                     from: "C".to_string(),
                     to: "D".to_string(),
+                    ..Default::default()
                 },
             ],
             python_env: None,
@@ -1952,14 +2069,17 @@ mod tests {
                 Connection {
                     from: "A".to_string(),
                     to: "B".to_string(),
+                    ..Default::default()
                 },
                 Connection {
                     from: "B".to_string(),
                     to: "C".to_string(),
+                    ..Default::default()
                 },
                 Connection {
                     from: "C".to_string(),
                     to: "A".to_string(),
+                    ..Default::default()
                 }, // Cycle!
             ],
             python_env: None,
@@ -1970,6 +2090,69 @@ mod tests {
         assert!(result.unwrap_err().to_string().contains("cycle"));
     }
 
+    #[test]
+    fn test_graph_port_edges() {
+        use crate::nodes::schema::{NodeSchema, RuntimeDataType};
+
+        // vad.out.events -> correlator.in.alerts, vad -> asr
+        let node = |id: &str, node_type: &str| crate::manifest::NodeManifest {
+            id: id.to_string(),
+            node_type: node_type.to_string(),
+            params: serde_json::json!({}),
+            ..Default::default()
+        };
+        let manifest = Manifest {
+            version: "v1".to_string(),
+            metadata: ManifestMetadata {
+                name: "port-test".to_string(),
+                ..Default::default()
+            },
+            nodes: vec![
+                node("vad", "VadNode"),
+                node("correlator", "CorrelatorNode"),
+                node("asr", "AsrNode"),
+            ],
+            connections: vec![
+                Connection::new("vad", "correlator")
+                    .from_port("events")
+                    .to_port("alerts"),
+                Connection::new("vad", "asr"),
+            ],
+            python_env: None,
+        };
+
+        let graph = PipelineGraph::from_manifest(&manifest).unwrap();
+        let edges: Vec<_> = graph.edges_from("vad").collect();
+        assert_eq!(edges.len(), 2);
+        assert_eq!(edges[0].from_port.as_deref(), Some("events"));
+        assert_eq!(edges[0].to_port.as_deref(), Some("alerts"));
+        assert_eq!(edges[0].to_string(), "vad.out.events -> correlator.in.alerts");
+        assert_eq!(edges[1].from_port, None);
+        assert_eq!(graph.edges_from("asr").count(), 0);
+
+        let schema_for = |node_type: &str| match node_type {
+            "VadNode" => Some(
+                NodeSchema::new("VadNode").output_port("events", [RuntimeDataType::Json]),
+            ),
+            "CorrelatorNode" => Some(
+                NodeSchema::new("CorrelatorNode").input_port("alerts", [RuntimeDataType::Json]),
+            ),
+            _ => None,
+        };
+        assert!(graph.validate_ports(schema_for).is_ok());
+
+        // Undeclared port on a node that has a schema is rejected
+        let strict = |node_type: &str| match node_type {
+            "VadNode" => Some(NodeSchema::new("VadNode")),
+            _ => None,
+        };
+        let err = graph.validate_ports(strict).unwrap_err().to_string();
+        assert!(err.contains("no output port 'events'"), "{}", err);
+
+        // Nodes without a schema are not checked
+        assert!(graph.validate_ports(|_| None).is_ok());
+    }
+
     #[tokio::test]
     async fn test_executor_with_graph() {
         let manifest = Manifest {
@@ -2006,6 +2189,7 @@ mod tests {
             connections: vec![Connection {
                 from: "input_0".to_string(),
                 to: "process_1".to_string(),
+                ..Default::default()
             }],
             python_env: None,
         };
@@ -2067,6 +2251,7 @@ mod tests {
             connections: vec![Connection {
                 from: "pass_0".to_string(),
                 to: "echo_1".to_string(),
+                ..Default::default()
             }],
             python_env: None,
         };
@@ -0,0 +1,502 @@
+//! Remote execution of nodes that declare a `host`
+//!
+//! A node with a `host` is not created in the local session. Before the
+//! pipeline graph is built, [`wrap_remote_hosts`] replaces it with a
+//! `RemotePipelineNode` that runs it on that host over gRPC, so the node
+//! gets the remote node's endpoint pool, retries and circuit breaker:
+//!
+//! ```yaml
+//! nodes:
+//!   - id: vad
+//!     node_type: SileroVADNode
+//!   - id: stt
+//!     node_type: WhisperXNode
+//!     host: gpu-box:50051
+//!   - id: llm
+//!     node_type: LlamaCppGenerationNode
+//!     host: gpu-box:50051
+//! connections:
+//!   - { from: vad, to: stt }
+//!   - { from: stt, to: llm }
+//! ```
+//!
+//! Neighbours on the same host share one remote pipeline (`stt` and `llm`
+//! above), so data crosses the network once per change of host rather
+//! than once per node. Two groups merge when the edges between them are
+//! the only way out of the first and the only way into the second; every
+//! remote pipeline is therefore a chain. A branch inside one host splits
+//! it into several remote pipelines.
+//!
+//! A wrapper takes the id of the last node of its chain, whose output it
+//! emits, so downstream edges, taps and intercepts on that node address it
+//! unchanged. [`remote_members`] maps every node of a chain to its
+//! wrapper: the session router feeds an input published to any of them in
+//! at the head of the chain, and applies pipeline patches to the manifest
+//! as declared before wrapping it again, so patches address each node by
+//! its own id.
+//! The outputs of the other nodes of a chain stay on the host and cannot
+//! be tapped locally.
+//!
+//! Each wrapper holds one streaming session open on its host for the life
+//! of the local session; the remote nodes keep their state between inputs
+//! and may answer an input with any number of outputs.
+//!
+//! `host` holds one endpoint, or several separated by commas that are load
+//! balanced; a session that fails on one endpoint is reopened on the next.
+//! The token in [`ENV_REMOTE_AUTH_TOKEN`], when set, is sent to every host.
+
+use crate::manifest::{Connection, Manifest, ManifestMetadata, NodeManifest};
+use crate::nodes::schema::MAIN_PORT;
+use crate::transport::pipeline_patch::PatchReport;
+use crate::{Error, Result};
+use std::collections::HashMap;
+
+/// Node type that runs a pipeline on another host
+const REMOTE_NODE_TYPE: &str = "RemotePipelineNode";
+
+/// Environment variable holding the bearer token sent to remote hosts
+pub const ENV_REMOTE_AUTH_TOKEN: &str = "REMOTEMEDIA_REMOTE_AUTH_TOKEN";
+
+/// A chain of same-host nodes, in data-flow order
+struct Group {
+    host: String,
+    members: Vec<usize>,
+    /// A fallback pair keeps its own wrapper and never merges
+    solo: bool,
+}
+
+/// Replace every node with a `host` by a `RemotePipelineNode`
+///
+/// Returns `None` when no node needs it, so callers can keep the original
+/// manifest. Each chain of same-host nodes becomes one wrapper named after
+/// its last node. Nodes that already are a `RemotePipelineNode` are left
+/// alone, which makes the rewrite idempotent.
+///
+/// Fails when an edge into or out of a remote node uses a named port, since
+/// a remote pipeline exchanges only `main` data.
+pub fn wrap_remote_hosts(manifest: &Manifest) -> Result<Option<Manifest>> {
+    let nodes = &manifest.nodes;
+    let standby = manifest.standby_nodes()?;
+
+    let mut groups = Vec::new();
+    let mut group_of: HashMap<&str, usize> = HashMap::new();
+    for (index, node) in nodes.iter().enumerate() {
+        let Some(host) = node.host.as_deref() else {
+            continue;
+        };
+        if node.node_type == REMOTE_NODE_TYPE {
+            continue;
+        }
+        group_of.insert(node.id.as_str(), groups.len());
+        groups.push(Group {
+            host: host.to_string(),
+            members: vec![index],
+            solo: node.fallback().is_some() || standby.contains(node.id.as_str()),
+        });
+    }
+    if groups.is_empty() {
+        return Ok(None);
+    }
+
+    // Contract edges between same-host chains until none qualifies
+    loop {
+        let merge = manifest.connections.iter().find_map(|c| {
+            let (&from, &to) = (group_of.get(c.from.as_str())?, group_of.get(c.to.as_str())?);
+            mergeable(manifest, &groups, &group_of, from, to).then_some((from, to))
+        });
+        let Some((from, to)) = merge else {
+            break;
+        };
+        let absorbed = std::mem::take(&mut groups[to].members);
+        for &index in &absorbed {
+            group_of.insert(nodes[index].id.as_str(), from);
+        }
+        groups[from].members.extend(absorbed);
+    }
+
+    // A chain is named after its last node, whose output it emits
+    let group_ids: Vec<&str> = groups
+        .iter()
+        .map(|g| g.members.last().map_or("", |&i| nodes[i].id.as_str()))
+        .collect();
+
+    // Edges between groups and local nodes now attach to the wrappers
+    let mut connections: Vec<Connection> = Vec::new();
+    let mut internal: Vec<Vec<Connection>> = vec![Vec::new(); groups.len()];
+    for conn in &manifest.connections {
+        let from = group_of.get(conn.from.as_str()).copied();
+        let to = group_of.get(conn.to.as_str()).copied();
+        if let (Some(a), Some(b)) = (from, to) {
+            if a == b {
+                internal[a].push(conn.clone());
+                continue;
+            }
+        }
+        let mut conn = conn.clone();
+        if let Some(group) = from {
+            check_main_port(&conn, &conn.from, conn.from_port.as_deref())?;
+            conn.from = group_ids[group].to_string();
+            conn.from_port = None;
+        }
+        if let Some(group) = to {
+            check_main_port(&conn, &conn.to, conn.to_port.as_deref())?;
+            conn.to = group_ids[group].to_string();
+            conn.to_port = None;
+        }
+        if !connections.contains(&conn) {
+            connections.push(conn);
+        }
+    }
+
+    let mut wrapped = Vec::with_capacity(nodes.len());
+    for (index, node) in nodes.iter().enumerate() {
+        let Some(&group) = group_of.get(node.id.as_str()) else {
+            wrapped.push(node.clone());
+            continue;
+        };
+        // A chain takes the place of its first member
+        if groups[group].members[0] == index {
+            wrapped.push(remote_node(
+                manifest,
+                &groups[group],
+                group_ids[group],
+                std::mem::take(&mut internal[group]),
+            )?);
+        }
+    }
+
+    Ok(Some(Manifest {
+        version: manifest.version.clone(),
+        metadata: manifest.metadata.clone(),
+        nodes: wrapped,
+        connections,
+        python_env: manifest.python_env.clone(),
+    }))
+}
+
+/// Map every node a wrapper runs on its host to the wrapper's id
+///
+/// `manifest` is the output of [`wrap_remote_hosts`]. A wrapper's own id
+/// maps to itself.
+pub fn remote_members(manifest: &Manifest) -> HashMap<String, String> {
+    let mut members = HashMap::new();
+    for node in &manifest.nodes {
+        if node.node_type != REMOTE_NODE_TYPE || node.host.is_none() {
+            continue;
+        }
+        let Some(remote) = node.params["manifest"]["nodes"].as_array() else {
+            continue;
+        };
+        for id in remote.iter().filter_map(|n| n["id"].as_str()) {
+            // A local node of the same id keeps it
+            if id == node.id || !manifest.nodes.iter().any(|n| n.id == id) {
+                members.insert(id.to_string(), node.id.clone());
+            }
+        }
+    }
+    members
+}
+
+/// Translate a patch `report` on the declared manifest into what changes
+/// between the wrapped manifests `before` and `after`
+///
+/// A wrapper is restarted when its remote pipeline changed or when one of
+/// its nodes was restarted or replaced; wrappers that appear or disappear
+/// with a regrouping are added or removed.
+pub(crate) fn wrapped_report(
+    report: &PatchReport,
+    before: &Manifest,
+    after: &Manifest,
+) -> PatchReport {
+    let members_before = remote_members(before);
+    let members_after = remote_members(after);
+    let touched = |id: &str| {
+        report.fresh_nodes().chain(&report.removed).any(|member| {
+            members_before.get(member).map(String::as_str) == Some(id)
+                || members_after.get(member).map(String::as_str) == Some(id)
+        })
+    };
+    let find = |manifest: &Manifest, id: &str| {
+        manifest
+            .nodes
+            .iter()
+            .find(|n| n.id == id)
+            .map(|n| serde_json::to_value(n).unwrap_or_default())
+    };
+
+    let mut wrapped = PatchReport {
+        connections_added: report.connections_added,
+        connections_removed: report.connections_removed,
+        ..Default::default()
+    };
+    let ids = before
+        .nodes
+        .iter()
+        .chain(after.nodes.iter().filter(|n| find(before, &n.id).is_none()))
+        .map(|n| n.id.as_str());
+    for id in ids {
+        match (find(before, id), find(after, id)) {
+            (None, Some(_)) => wrapped.added.push(id.to_string()),
+            (Some(_), None) => wrapped.removed.push(id.to_string()),
+            (Some(old), Some(new)) => {
+                if report.replaced.iter().any(|n| n == id) {
+                    wrapped.replaced.push(id.to_string());
+                } else if report.restarted.iter().any(|n| n == id) || old != new || touched(id) {
+                    wrapped.restarted.push(id.to_string());
+                }
+            }
+            (None, None) => {}
+        }
+    }
+    wrapped
+}
+
+/// Whether group `from` can absorb group `to`, its successor on `host`
+fn mergeable(
+    manifest: &Manifest,
+    groups: &[Group],
+    group_of: &HashMap<&str, usize>,
+    from: usize,
+    to: usize,
+) -> bool {
+    let (a, b) = (&groups[from], &groups[to]);
+    if from == to || a.solo || b.solo || a.host != b.host {
+        return false;
+    }
+    // The tail of `from` must not also answer the client
+    let tail = &manifest.nodes[*a.members.last().unwrap_or(&0)];
+    if tail.is_output_node {
+        return false;
+    }
+    let group = |id: &str| group_of.get(id).copied();
+    manifest.connections.iter().all(|c| {
+        let (src, dst) = (group(&c.from), group(&c.to));
+        let leaves_from = src == Some(from) && dst != Some(from);
+        let enters_to = dst == Some(to) && src != Some(to);
+        let back_edge = src == Some(to) && dst == Some(from);
+        (!leaves_from || dst == Some(to)) && (!enters_to || src == Some(from)) && !back_edge
+    })
+}
+
+fn check_main_port(conn: &Connection, node: &str, port: Option<&str>) -> Result<()> {
+    match port {
+        Some(port) if port != MAIN_PORT => Err(Error::Manifest(format!(
+            "Connection {} uses port '{}' of remote node '{}' (only `main` crosses hosts)",
+            conn, port, node
+        ))),
+        _ => Ok(()),
+    }
+}
+
+/// The `RemotePipelineNode` running `group` on its host
+fn remote_node(
+    manifest: &Manifest,
+    group: &Group,
+    id: &str,
+    connections: Vec<Connection>,
+) -> Result<NodeManifest> {
+    let members: Vec<&NodeManifest> = group.members.iter().map(|&i| &manifest.nodes[i]).collect();
+    let endpoints: Vec<&str> = group
+        .host
+        .split(',')
+        .map(str::trim)
+        .filter(|e| !e.is_empty())
+        .collect();
+    if endpoints.is_empty() {
+        return Err(Error::Manifest(format!("Node '{}' has an empty host", id)));
+    }
+
+    let remote = Manifest {
+        version: manifest.version.clone(),
+        metadata: ManifestMetadata {
+            name: format!("{}@{}", manifest.metadata.name, id),
+            ..Default::default()
+        },
+        nodes: members
+            .iter()
+            .map(|&node| NodeManifest {
+                host: None,
+                execution: None,
+                ..node.clone()
+            })
+            .collect(),
+        connections,
+        python_env: manifest.python_env.clone(),
+    };
+    let remote = serde_json::to_value(&remote).map_err(|e| {
+        Error::Manifest(format!("Cannot serialize remote pipeline '{}': {}", id, e))
+    })?;
+
+    let mut params = serde_json::json!({ "transport": "grpc", "manifest": remote });
+    match endpoints.as_slice() {
+        [endpoint] => params["endpoint"] = serde_json::json!(endpoint),
+        _ => {
+            // A session that fails on one endpoint moves straight on to the next
+            params["endpoints"] = serde_json::json!(endpoints);
+            params["retry"] = serde_json::json!({
+                "max_retries": endpoints.len() - 1,
+                "backoff_ms": 0,
+            });
+        }
+    }
+    // Referenced rather than copied, so the token stays out of the manifest
+    if std::env::var_os(ENV_REMOTE_AUTH_TOKEN).is_some() {
+        params["auth_token"] = serde_json::json!(format!("${{{}}}", ENV_REMOTE_AUTH_TOKEN));
+    }
+
+    let last = members[members.len() - 1];
+    Ok(NodeManifest {
+        id: id.to_string(),
+        node_type: REMOTE_NODE_TYPE.to_string(),
+        params,
+        is_output_node: last.is_output_node,
+        host: Some(group.host.clone()),
+        // Only a solo node can take part in a fallback pair
+        execution: if group.solo {
+            members[0].execution.clone()
+        } else {
+            None
+        },
+        ..Default::default()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn manifest(nodes: &[(&str, Option<&str>)], edges: &[(&str, &str)]) -> Manifest {
+        Manifest {
+            version: "v1".to_string(),
+            metadata: ManifestMetadata {
+                name: "split".to_string(),
+                ..Default::default()
+            },
+            nodes: nodes
+                .iter()
+                .map(|&(id, host)| NodeManifest {
+                    id: id.to_string(),
+                    node_type: "Stage".to_string(),
+                    host: host.map(str::to_string),
+                    ..Default::default()
+                })
+                .collect(),
+            connections: edges.iter().map(|&(a, b)| Connection::new(a, b)).collect(),
+            python_env: None,
+        }
+    }
+
+    fn ids(manifest: &Manifest) -> Vec<&str> {
+        manifest.nodes.iter().map(|n| n.id.as_str()).collect()
+    }
+
+    fn edges(manifest: &Manifest) -> Vec<(&str, &str)> {
+        manifest
+            .connections
+            .iter()
+            .map(|c| (c.from.as_str(), c.to.as_str()))
+            .collect()
+    }
+
+    #[test]
+    fn test_local_manifest_is_untouched() {
+        let m = manifest(&[("a", None), ("b", None)], &[("a", "b")]);
+        assert!(wrap_remote_hosts(&m).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_same_host_chain_merges() {
+        let h1 = Some("h1:50051");
+        let h2 = Some("h2:50051,h2b:50051");
+        let m = manifest(
+            &[("in", None), ("a", h1), ("b", h1), ("c", h2), ("out", None)],
+            &[("in", "a"), ("a", "b"), ("b", "c"), ("c", "out")],
+        );
+        let wrapped = wrap_remote_hosts(&m).unwrap().unwrap();
+        assert_eq!(ids(&wrapped), ["in", "b", "c", "out"]);
+        assert_eq!(edges(&wrapped), [("in", "b"), ("b", "c"), ("c", "out")]);
+
+        let ab = &wrapped.nodes[1];
+        assert_eq!(ab.node_type, "RemotePipelineNode");
+        assert_eq!(ab.params["endpoint"], "h1:50051");
+        let remote: Manifest = serde_json::from_value(ab.params["manifest"].clone()).unwrap();
+        assert_eq!(ids(&remote), ["a", "b"]);
+        assert_eq!(edges(&remote), [("a", "b")]);
+        assert!(remote.nodes.iter().all(|n| n.host.is_none()));
+
+        assert_eq!(
+            wrapped.nodes[2].params["endpoints"],
+            serde_json::json!(["h2:50051", "h2b:50051"])
+        );
+
+        let members = remote_members(&wrapped);
+        assert_eq!(members["a"], "b");
+        assert_eq!(members["b"], "b");
+        assert_eq!(members["c"], "c");
+        assert!(!members.contains_key("in"));
+
+        // Wrapping again changes nothing
+        assert!(wrap_remote_hosts(&wrapped).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_branch_splits_the_host() {
+        // `b` also feeds a local node, so `c` gets a session of its own
+        let h = Some("h:1");
+        let m = manifest(
+            &[("a", h), ("b", h), ("c", h), ("tap", None)],
+            &[("a", "b"), ("b", "c"), ("b", "tap")],
+        );
+        let wrapped = wrap_remote_hosts(&m).unwrap().unwrap();
+        assert_eq!(ids(&wrapped), ["b", "c", "tap"]);
+        assert_eq!(edges(&wrapped), [("b", "c"), ("b", "tap")]);
+
+        // A local detour between two same-host nodes keeps them apart
+        let m = manifest(
+            &[("a", h), ("x", None), ("b", h)],
+            &[("a", "x"), ("x", "b"), ("a", "b")],
+        );
+        let wrapped = wrap_remote_hosts(&m).unwrap().unwrap();
+        assert_eq!(ids(&wrapped), ["a", "x", "b"]);
+    }
+
+    #[test]
+    fn test_patch_on_a_chain_member_restarts_its_wrapper() {
+        use crate::transport::pipeline_patch::PipelinePatch;
+
+        let h = Some("h:1");
+        let m = manifest(
+            &[("in", None), ("a", h), ("b", h), ("out", None)],
+            &[("in", "a"), ("a", "b"), ("b", "out")],
+        );
+        let before = wrap_remote_hosts(&m).unwrap().unwrap();
+
+        let (patched, report) = PipelinePatch::new()
+            .update_params("a", serde_json::json!({ "x": 1 }))
+            .apply_to(&m)
+            .unwrap();
+        let after = wrap_remote_hosts(&patched).unwrap().unwrap();
+        let wrapped = wrapped_report(&report, &before, &after);
+        assert_eq!(wrapped.restarted, ["b"]);
+        assert!(wrapped.added.is_empty() && wrapped.removed.is_empty());
+
+        // Cutting the chain gives `a` a wrapper of its own
+        let (patched, report) = PipelinePatch::new()
+            .disconnect(Connection::new("a", "b"))
+            .connect(Connection::new("in", "b"))
+            .apply_to(&m)
+            .unwrap();
+        let after = wrap_remote_hosts(&patched).unwrap().unwrap();
+        let wrapped = wrapped_report(&report, &before, &after);
+        assert_eq!(wrapped.added, ["a"]);
+        assert_eq!(wrapped.restarted, ["b"]);
+    }
+
+    #[test]
+    fn test_named_port_on_remote_edge_is_rejected() {
+        let mut m = manifest(&[("a", Some("h:1")), ("b", None)], &[("a", "b")]);
+        m.connections[0].from_port = Some("events".to_string());
+        let err = wrap_remote_hosts(&m).unwrap_err().to_string();
+        assert!(err.contains("port 'events'"), "{}", err);
+    }
+}
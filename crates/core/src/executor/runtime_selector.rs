@@ -136,7 +136,7 @@ impl RuntimeSelector {
     /// - Otherwise → RustPython (faster, lower overhead for simple nodes)
     fn auto_detect_runtime(&self, node: &NodeManifest) -> SelectedRuntime {
         // Check for GPU requirements
-        if let Some(caps) = &node.capabilities {
+        if let Some(caps) = node.resource_requirements() {
             if caps.gpu.is_some() {
                 tracing::info!(
                     "Node {} requires GPU, selecting CPython (likely ML workload)",
@@ -290,6 +290,19 @@ mod tests {
         assert_eq!(selector.select_runtime(&node), SelectedRuntime::CPython);
     }
 
+    #[test]
+    fn test_auto_detection_v2_resources() {
+        let selector = RuntimeSelector::new();
+
+        let mut node = create_test_node("mem_node", "SimpleNode", None, None);
+        node.resources = Some(CapabilityRequirements {
+            gpu: None,
+            cpu: None,
+            memory_gb: Some(8.0),
+        });
+        assert_eq!(selector.select_runtime(&node), SelectedRuntime::CPython);
+    }
+
     #[test]
     fn test_auto_detection_node_type() {
         let selector = RuntimeSelector::new();
@@ -497,6 +497,7 @@ mod tests {
             .map(|i| Connection {
                 from: format!("g{i}"),
                 to: format!("g{}", i + 1),
+                ..Default::default()
             })
             .collect();
         Manifest {
@@ -576,10 +577,12 @@ mod tests {
             Connection {
                 from: "g0".into(),
                 to: "g1".into(),
+                ..Default::default()
             },
             Connection {
                 from: "g0".into(),
                 to: "g2".into(),
+                ..Default::default()
             },
         ];
         let manifest = Manifest {
@@ -40,6 +40,7 @@ extern crate self as remotemedia_core;
 
 // Core execution modules
 pub mod audio;
+pub mod auth;
 pub mod capabilities;
 pub mod executor;
 pub mod ingestion;
@@ -47,6 +48,7 @@ pub mod llm;
 pub mod metrics;
 pub mod nodes;
 pub mod python;
+pub mod telemetry;
 pub mod validation;
 /// Public entrypoint for ergonomic registration macros.
 pub mod registration_macros {
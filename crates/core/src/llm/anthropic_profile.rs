@@ -230,7 +230,7 @@ impl ProviderProfile for AnthropicProfile {
         "anthropic"
     }
 
-    fn endpoint(&self, base_url: &str) -> String {
+    fn endpoint(&self, base_url: &str, _model: &str, _streaming: bool) -> String {
         format!("{}/messages", base_url)
     }
 
@@ -355,6 +355,47 @@ impl ProviderProfile for AnthropicProfile {
             _ => Vec::new(),
         }
     }
+
+    fn parse_response(&self, body: &Value) -> Vec<ChatStreamEvent> {
+        // A Messages response lists whole content blocks
+        let blocks = body
+            .get("content")
+            .and_then(Value::as_array)
+            .map(Vec::as_slice)
+            .unwrap_or_default();
+        let mut out: Vec<ChatStreamEvent> = Vec::new();
+        for (index, block) in blocks.iter().enumerate() {
+            match block.get("type").and_then(Value::as_str).unwrap_or("") {
+                "text" => {
+                    if let Some(text) = block
+                        .get("text")
+                        .and_then(Value::as_str)
+                        .filter(|s| !s.is_empty())
+                    {
+                        out.push(ChatStreamEvent::VisibleText(text.to_string()));
+                    }
+                }
+                "tool_use" => out.push(ChatStreamEvent::ToolCallDelta {
+                    index: index as u64,
+                    id: block.get("id").and_then(Value::as_str).map(str::to_string),
+                    name: block
+                        .get("name")
+                        .and_then(Value::as_str)
+                        .map(str::to_string),
+                    arguments_chunk: Some(
+                        block
+                            .get("input")
+                            .cloned()
+                            .unwrap_or_else(|| Value::Object(serde_json::Map::new()))
+                            .to_string(),
+                    ),
+                }),
+                _ => {}
+            }
+        }
+        out.push(ChatStreamEvent::Done);
+        out
+    }
 }
 
 #[cfg(test)]
@@ -365,7 +406,7 @@ mod tests {
     fn endpoint_appends_messages() {
         let p = AnthropicProfile;
         assert_eq!(
-            p.endpoint("https://api.anthropic.com/v1"),
+            p.endpoint("https://api.anthropic.com/v1", "claude-3-5-haiku-latest", true),
             "https://api.anthropic.com/v1/messages"
         );
     }
@@ -1,22 +1,32 @@
 //! Modality-agnostic streaming chat-completion backend.
 //!
-//! Owns the HTTP client, per-session conversation history, and the
-//! request → SSE stream → tool-dispatch → history-commit pipeline.
+//! Owns the HTTP client, per-session conversation history (through a
+//! pluggable [`HistoryStore`]), and the request → SSE stream →
+//! tool-dispatch → history-commit pipeline.
 //! The caller (a pipeline node) supplies a pre-shaped user message
 //! (text-only or content-parts array) plus per-call config and a
 //! callback that receives streaming `RuntimeData::Text` outputs.
 //!
-//! Vendor-specific wire shaping is delegated to [`ProviderProfile`].
-//! v1 only ships [`OpenAIProfile`], but the trait surface is sized to
-//! drop in Anthropic / Gemini profiles without touching this module.
+//! When the model calls `return_value` tools, the backend executes
+//! them through [`ToolHandlerRegistry`], commits the results to
+//! history and re-generates, up to
+//! [`ChatBackendConfig::max_tool_iterations`] follow-up passes per
+//! user turn.
+//!
+//! Vendor-specific wire shaping is delegated to [`ProviderProfile`]
+//! (OpenAI, Anthropic, Gemini); this module never looks at vendor
+//! field names.
 
 use crate::data::{tag_text_str, RuntimeData, TEXT_CHANNEL_DEFAULT};
 use crate::error::Error;
 use crate::llm::history::{window_start, HistoryEntry};
+use crate::llm::history_store::{
+    compaction_split, HistoryBudget, HistoryStore, HistorySummarizer, InMemoryHistoryStore,
+};
 use crate::llm::provider::{ChatRequest, ChatStreamEvent, ProviderProfile};
-use crate::llm::tool_dispatch::{dispatch_tool_call, ToolCallAccum};
-use crate::nodes::tool_spec::ToolSpec;
-use parking_lot::Mutex;
+use crate::llm::tool_dispatch::{dispatch_tool_call, lookup_tool, ToolCallAccum};
+use crate::llm::tool_handler::{execute_tool_call, ToolHandlerRegistry, ToolInvocation};
+use crate::nodes::tool_spec::{ToolKind, ToolSpec};
 use serde_json::Value;
 use std::collections::HashMap;
 use std::sync::Arc;
@@ -47,6 +57,71 @@ pub struct ChatBackendConfig {
     pub reasoning_channel: Option<String>,
     pub tools: Vec<ToolSpec>,
     pub tool_choice: Option<Value>,
+    /// Executors for `return_value` tools, keyed by tool name.
+    pub tool_handlers: ToolHandlerRegistry,
+    /// Follow-up generation passes allowed per user turn after
+    /// `return_value` tools run. Once exhausted, results are still
+    /// committed to history but the backend stops re-generating.
+    pub max_tool_iterations: usize,
+}
+
+/// Default for [`ChatBackendConfig::max_tool_iterations`].
+pub const DEFAULT_MAX_TOOL_ITERATIONS: usize = 5;
+
+/// What one generation pass produced.
+#[derive(Default)]
+struct StreamedPass {
+    full_text: String,
+    token_count: usize,
+    tool_calls: HashMap<u64, ToolCallAccum>,
+}
+
+impl StreamedPass {
+    /// Fold one event into the pass, forwarding visible and reasoning
+    /// text to `callback`. Returns `true` on [`ChatStreamEvent::Done`].
+    fn apply<F>(
+        &mut self,
+        ev: ChatStreamEvent,
+        cfg: &ChatBackendConfig,
+        callback: &mut F,
+    ) -> Result<bool, Error>
+    where
+        F: FnMut(RuntimeData) -> Result<(), Error> + Send,
+    {
+        match ev {
+            ChatStreamEvent::ReasoningText(text) => {
+                if let Some(ref rc) = cfg.reasoning_channel {
+                    if !rc.is_empty() {
+                        callback(RuntimeData::Text(tag_text_str(&text, rc)))?;
+                    }
+                }
+            }
+            ChatStreamEvent::VisibleText(text) => {
+                self.full_text.push_str(&text);
+                self.token_count += 1;
+                callback(RuntimeData::Text(tag_text_str(&text, &cfg.output_channel)))?;
+            }
+            ChatStreamEvent::ToolCallDelta {
+                index,
+                id,
+                name,
+                arguments_chunk,
+            } => {
+                let entry = self.tool_calls.entry(index).or_default();
+                if let Some(id) = id {
+                    entry.id = id;
+                }
+                if let Some(n) = name {
+                    entry.name = n;
+                }
+                if let Some(args) = arguments_chunk {
+                    entry.arguments.push_str(&args);
+                }
+            }
+            ChatStreamEvent::Done => return Ok(true),
+        }
+        Ok(false)
+    }
 }
 
 // ---------------------------------------------------------------------------
@@ -59,7 +134,9 @@ pub struct ChatBackendConfig {
 /// `session_id` for history isolation.
 pub struct ChatBackend {
     client: Arc<reqwest::Client>,
-    history: Arc<Mutex<HashMap<String, Vec<HistoryEntry>>>>,
+    history: Arc<dyn HistoryStore>,
+    /// Compaction policy applied at the end of each user turn.
+    compaction: Option<(HistoryBudget, Arc<dyn HistorySummarizer>)>,
     profile: Arc<dyn ProviderProfile>,
 }
 
@@ -84,75 +161,206 @@ impl ChatBackend {
                     .build()
                     .expect("build reqwest client"),
             ),
-            history: Arc::new(Mutex::new(HashMap::new())),
+            history: Arc::new(InMemoryHistoryStore::new()),
+            compaction: None,
             profile,
         }
     }
 
+    /// Keep history in `store` instead of process memory.
+    pub fn with_history_store(mut self, store: Arc<dyn HistoryStore>) -> Self {
+        self.history = store;
+        self
+    }
+
+    /// Compact a session with `summarizer` whenever its history
+    /// outgrows `budget`.
+    pub fn with_summarizer(
+        mut self,
+        budget: HistoryBudget,
+        summarizer: Arc<dyn HistorySummarizer>,
+    ) -> Self {
+        self.compaction = Some((budget, summarizer));
+        self
+    }
+
+    /// The store backing this backend's history.
+    pub fn history_store(&self) -> &Arc<dyn HistoryStore> {
+        &self.history
+    }
+
+    /// Full stored history for `session_id`, e.g. to hand a
+    /// conversation to another process.
+    pub async fn export_history(&self, session_id: &str) -> Result<Vec<HistoryEntry>, Error> {
+        let id = session_id.to_string();
+        self.on_store(move |store| store.load(&id)).await
+    }
+
+    /// Replace `session_id`'s history, e.g. to resume an exported
+    /// conversation under a new session id.
+    pub async fn seed_history(
+        &self,
+        session_id: &str,
+        entries: &[HistoryEntry],
+    ) -> Result<(), Error> {
+        let (id, entries) = (session_id.to_string(), entries.to_vec());
+        self.on_store(move |store| store.replace(&id, &entries))
+            .await
+    }
+
+    /// Forget `session_id`'s history.
+    pub async fn clear_history(&self, session_id: &str) -> Result<(), Error> {
+        let id = session_id.to_string();
+        self.on_store(move |store| store.remove(&id)).await
+    }
+
     /// Append one history entry for `session_id`.
-    pub fn append_history(&self, session_id: &str, entry: HistoryEntry) {
-        let mut hist = self.history.lock();
-        hist.entry(session_id.to_string())
-            .or_insert_with(Vec::new)
-            .push(entry);
+    pub async fn append_history(&self, session_id: &str, entry: HistoryEntry) {
+        self.extend_history(session_id, vec![entry]).await;
     }
 
     /// Append a batch atomically. Used to commit an assistant +
     /// tool-result group at end-of-turn so a barge / drop never leaves
-    /// a dangling `tool_call_id`.
-    pub fn extend_history(&self, session_id: &str, entries: Vec<HistoryEntry>) {
+    /// a dangling `tool_call_id`. A store error is logged, not returned,
+    /// so a failed write never aborts the turn.
+    pub async fn extend_history(&self, session_id: &str, entries: Vec<HistoryEntry>) {
         if entries.is_empty() {
             return;
         }
-        let mut hist = self.history.lock();
-        hist.entry(session_id.to_string())
-            .or_insert_with(Vec::new)
-            .extend(entries);
+        let id = session_id.to_string();
+        if let Err(e) = self
+            .on_store(move |store| store.append(&id, &entries))
+            .await
+        {
+            tracing::error!(
+                session_id,
+                error = %e,
+                "[llm] failed to persist conversation history"
+            );
+        }
+    }
+
+    /// Run `op` against the store on the blocking pool, so a store
+    /// doing file I/O (SQLite) never stalls the async runtime.
+    async fn on_store<T, F>(&self, op: F) -> Result<T, Error>
+    where
+        T: Send + 'static,
+        F: FnOnce(&dyn HistoryStore) -> Result<T, Error> + Send + 'static,
+    {
+        let store = Arc::clone(&self.history);
+        tokio::task::spawn_blocking(move || op(store.as_ref()))
+            .await
+            .map_err(|e| Error::Execution(format!("History store task failed: {}", e)))?
+    }
+
+    /// Stored history for `session_id`; a store error reads as an
+    /// empty history so the turn can still go ahead.
+    async fn load_history(&self, session_id: &str) -> Vec<HistoryEntry> {
+        let id = session_id.to_string();
+        self.on_store(move |store| store.load(&id))
+            .await
+            .unwrap_or_else(|e| {
+                tracing::error!(
+                    session_id,
+                    error = %e,
+                    "[llm] failed to load conversation history; continuing without it"
+                );
+                Vec::new()
+            })
+    }
+
+    /// Summarise old turns if the session is over its token budget.
+    /// Failures leave history untouched.
+    async fn compact_history(&self, session_id: &str) {
+        let Some((budget, summarizer)) = &self.compaction else {
+            return;
+        };
+        let entries = self.load_history(session_id).await;
+        let split = compaction_split(&entries, budget);
+        if split == 0 {
+            return;
+        }
+        let mut compacted = match summarizer.summarize(session_id, &entries[..split]).await {
+            Ok(replacement) => replacement,
+            Err(e) => {
+                tracing::warn!(
+                    session_id,
+                    error = %e,
+                    "[llm] history summariser failed; keeping full history"
+                );
+                return;
+            }
+        };
+        compacted.extend_from_slice(&entries[split..]);
+        let (id, after) = (session_id.to_string(), compacted.len());
+        match self
+            .on_store(move |store| store.replace(&id, &compacted))
+            .await
+        {
+            Ok(()) => tracing::info!(
+                session_id,
+                before = entries.len(),
+                after,
+                "[llm] compacted conversation history"
+            ),
+            Err(e) => tracing::error!(
+                session_id,
+                error = %e,
+                "[llm] failed to store compacted history"
+            ),
+        }
     }
 
     /// Build the full `messages` array (system + bounded history +
-    /// new user message) for one request. Pure function over snapshot
-    /// state; doesn't mutate history.
+    /// new user message) for one request from a history snapshot.
+    /// Follow-up tool passes pass `None`: the user turn is already in
+    /// history.
+    ///
+    /// Stored summaries are folded into the single system message —
+    /// some vendors only honour one.
     fn build_messages(
-        &self,
-        session_id: &str,
+        entries: &[HistoryEntry],
         cfg: &ChatBackendConfig,
-        user_message: &Value,
+        user_message: Option<&Value>,
     ) -> Vec<Value> {
         let mut messages: Vec<Value> = Vec::new();
+        let summaries = entries.iter().take_while(|e| e.is_summary()).count();
+        let (summary_entries, turns) = entries.split_at(summaries);
 
-        if let Some(ref sys) = cfg.system_prompt {
+        let mut system = cfg.system_prompt.clone().unwrap_or_default();
+        if !summary_entries.is_empty() {
+            if !system.is_empty() {
+                system.push_str("\n\n");
+            }
+            system.push_str("Summary of the conversation so far:");
+            for entry in summary_entries {
+                if let Some(text) = entry.message["content"].as_str() {
+                    system.push('\n');
+                    system.push_str(text);
+                }
+            }
+        }
+        if !system.is_empty() {
             messages.push(serde_json::json!({
                 "role": "system",
-                "content": sys,
+                "content": system,
             }));
         }
 
-        {
-            let hist = self.history.lock();
-            if let Some(entries) = hist.get(session_id) {
-                let start = window_start(entries, cfg.history_turns);
-                for entry in entries.iter().skip(start) {
-                    messages.push(entry.message.clone());
-                }
-            }
+        let start = window_start(turns, cfg.history_turns);
+        for entry in turns.iter().skip(start) {
+            messages.push(entry.message.clone());
         }
 
-        messages.push(user_message.clone());
+        if let Some(user_message) = user_message {
+            messages.push(user_message.clone());
+        }
         messages
     }
 
-    /// Test/inspection helper — `OpenAIChatNode`'s tests reach in for
-    /// the constructed request body.
-    #[cfg(test)]
-    pub(crate) fn build_request_body_for_test(
-        &self,
-        session_id: &str,
-        cfg: &ChatBackendConfig,
-        user_message: &Value,
-    ) -> Value {
-        let messages = self.build_messages(session_id, cfg, user_message);
-        let req = ChatRequest {
+    /// Vendor-shape one request body.
+    fn shape_body(&self, cfg: &ChatBackendConfig, messages: Vec<Value>) -> Value {
+        let request = ChatRequest {
             model: &cfg.model,
             messages,
             tools: &cfg.tools,
@@ -162,7 +370,21 @@ impl ChatBackend {
             top_p: cfg.top_p,
             streaming: cfg.streaming,
         };
-        self.profile.shape_request(&req)
+        self.profile.shape_request(&request)
+    }
+
+    /// Test/inspection helper — `OpenAIChatNode`'s tests reach in for
+    /// the constructed request body.
+    #[cfg(test)]
+    pub(crate) fn build_request_body_for_test(
+        &self,
+        session_id: &str,
+        cfg: &ChatBackendConfig,
+        user_message: &Value,
+    ) -> Value {
+        let entries = self.history.load(session_id).unwrap_or_default();
+        let messages = Self::build_messages(&entries, cfg, Some(user_message));
+        self.shape_body(cfg, messages)
     }
 
     /// Issue one request, drive the stream, dispatch tool calls, and
@@ -170,8 +392,15 @@ impl ChatBackend {
     /// message (e.g. `{role:"user",content:"hi"}` or
     /// `{role:"user",content:[parts…]}`).
     ///
-    /// Returns the count of streamed tokens (visible text) — the
-    /// existing `OpenAIChatNode` contract preserved.
+    /// A pass that calls `return_value` tools is followed by another
+    /// pass over the updated history until the model stops calling them
+    /// or `max_tool_iterations` is reached, streaming or not.
+    ///
+    /// A session over its [`HistoryBudget`] is compacted once the turn
+    /// is committed.
+    ///
+    /// Returns the count of streamed tokens (visible text) across all
+    /// passes — the existing `OpenAIChatNode` contract preserved.
     pub async fn run<F>(
         &self,
         session_id: &str,
@@ -183,20 +412,11 @@ impl ChatBackend {
         F: FnMut(RuntimeData) -> Result<(), Error> + Send,
     {
         // Snapshot history → build messages → record user turn.
-        let messages = self.build_messages(session_id, cfg, &user_message);
-        self.append_history(session_id, HistoryEntry::user_message(user_message));
-
-        let request = ChatRequest {
-            model: &cfg.model,
-            messages,
-            tools: &cfg.tools,
-            tool_choice: cfg.tool_choice.as_ref(),
-            max_tokens: cfg.max_tokens,
-            temperature: cfg.temperature,
-            top_p: cfg.top_p,
-            streaming: cfg.streaming,
-        };
-        let body = self.profile.shape_request(&request);
+        let history = self.load_history(session_id).await;
+        let messages = Self::build_messages(&history, cfg, Some(&user_message));
+        self.extend_history(session_id, vec![HistoryEntry::user_message(user_message)])
+            .await;
+        let body = self.shape_body(cfg, messages);
 
         tracing::info!(
             provider = self.profile.name(),
@@ -206,24 +426,71 @@ impl ChatBackend {
             "[llm] sending chat completion request"
         );
 
-        if cfg.streaming {
-            self.run_streaming(session_id, cfg, body, callback).await
-        } else {
-            self.run_blocking(session_id, cfg, body, callback).await
+        let token_count = self.run_tool_loop(session_id, cfg, body, callback).await?;
+        self.compact_history(session_id).await;
+        Ok(token_count)
+    }
+
+    /// Passes until the model stops calling `return_value` tools or the
+    /// iteration guard trips.
+    async fn run_tool_loop<F>(
+        &self,
+        session_id: &str,
+        cfg: &ChatBackendConfig,
+        mut body: Value,
+        callback: &mut F,
+    ) -> Result<usize, Error>
+    where
+        F: FnMut(RuntimeData) -> Result<(), Error> + Send,
+    {
+        let mut token_count = 0usize;
+        let mut follow_ups = 0usize;
+        loop {
+            let pass = if cfg.streaming {
+                self.run_streaming(cfg, body, callback).await?
+            } else {
+                self.run_blocking(cfg, body, callback).await?
+            };
+            token_count += pass.token_count;
+            let executed = self.commit_pass(session_id, cfg, pass, callback).await?;
+            if executed == 0 {
+                break;
+            }
+            if follow_ups >= cfg.max_tool_iterations {
+                tracing::warn!(
+                    provider = self.profile.name(),
+                    max_tool_iterations = cfg.max_tool_iterations,
+                    "[llm] return_value tool loop hit its iteration limit; \
+                     not re-generating"
+                );
+                break;
+            }
+            follow_ups += 1;
+            tracing::debug!(
+                provider = self.profile.name(),
+                pass = follow_ups,
+                tools = executed,
+                "[llm] re-generating with tool results"
+            );
+            let history = self.load_history(session_id).await;
+            body = self.shape_body(cfg, Self::build_messages(&history, cfg, None));
         }
+        Ok(token_count)
     }
 
+    /// Send one streaming request and accumulate its text and tool
+    /// calls. Visible and reasoning text are forwarded to `callback`
+    /// as they arrive; tool calls are left for [`Self::commit_pass`].
     async fn run_streaming<F>(
         &self,
-        session_id: &str,
         cfg: &ChatBackendConfig,
         body: Value,
         callback: &mut F,
-    ) -> Result<usize, Error>
+    ) -> Result<StreamedPass, Error>
     where
         F: FnMut(RuntimeData) -> Result<(), Error> + Send,
     {
-        let url = self.profile.endpoint(&cfg.base_url);
+        let url = self.profile.endpoint(&cfg.base_url, &cfg.model, true);
         let mut req = self.client.post(url).json(&body);
         req = self.profile.apply_auth(req, cfg.api_key.as_deref());
         let response = req
@@ -243,9 +510,7 @@ impl ChatBackend {
             )));
         }
 
-        let mut full_text = String::new();
-        let mut token_count = 0usize;
-        let mut tool_calls: HashMap<u64, ToolCallAccum> = HashMap::new();
+        let mut pass = StreamedPass::default();
 
         let mut stream = response.bytes_stream();
         let mut buf = Vec::<u8>::new();
@@ -276,43 +541,7 @@ impl ChatBackend {
                     Err(_) => continue,
                 };
                 for ev in self.profile.parse_sse_payload(&json) {
-                    match ev {
-                        ChatStreamEvent::ReasoningText(text) => {
-                            if let Some(ref rc) = cfg.reasoning_channel {
-                                if !rc.is_empty() {
-                                    callback(RuntimeData::Text(tag_text_str(&text, rc)))?;
-                                }
-                            }
-                        }
-                        ChatStreamEvent::VisibleText(text) => {
-                            full_text.push_str(&text);
-                            token_count += 1;
-                            callback(RuntimeData::Text(tag_text_str(
-                                &text,
-                                &cfg.output_channel,
-                            )))?;
-                        }
-                        ChatStreamEvent::ToolCallDelta {
-                            index,
-                            id,
-                            name,
-                            arguments_chunk,
-                        } => {
-                            let entry = tool_calls.entry(index).or_default();
-                            if let Some(id) = id {
-                                entry.id = id;
-                            }
-                            if let Some(n) = name {
-                                entry.name = n;
-                            }
-                            if let Some(args) = arguments_chunk {
-                                entry.arguments.push_str(&args);
-                            }
-                        }
-                        ChatStreamEvent::Done => {
-                            done = true;
-                        }
-                    }
+                    done |= pass.apply(ev, cfg, callback)?;
                 }
             }
             if done {
@@ -320,15 +549,39 @@ impl ChatBackend {
             }
         }
 
-        // Dispatch tool calls + build history additions.
-        let mut indices: Vec<u64> = tool_calls.keys().copied().collect();
+        tracing::info!(
+            provider = self.profile.name(),
+            tokens = pass.token_count,
+            chars = pass.full_text.len(),
+            "[llm] streaming complete"
+        );
+        Ok(pass)
+    }
+
+    /// Dispatch one pass's tool calls and commit the assistant turn
+    /// (plus one tool result per call) to history atomically.
+    /// Side-effect tools get an empty result; `return_value` tools are
+    /// executed and their output becomes the result. Returns how many
+    /// `return_value` tools ran.
+    async fn commit_pass<F>(
+        &self,
+        session_id: &str,
+        cfg: &ChatBackendConfig,
+        mut pass: StreamedPass,
+        callback: &mut F,
+    ) -> Result<usize, Error>
+    where
+        F: FnMut(RuntimeData) -> Result<(), Error> + Send,
+    {
+        let mut indices: Vec<u64> = pass.tool_calls.keys().copied().collect();
         indices.sort_unstable();
 
         let mut tool_calls_for_history: Vec<Value> = Vec::with_capacity(indices.len());
         let mut tool_result_entries: Vec<HistoryEntry> = Vec::with_capacity(indices.len());
+        let mut executed = 0usize;
 
         for idx in indices {
-            let entry = match tool_calls.remove(&idx) {
+            let entry = match pass.tool_calls.remove(&idx) {
                 Some(e) => e,
                 None => continue,
             };
@@ -348,14 +601,29 @@ impl ChatBackend {
                     "arguments": entry.arguments,
                 },
             }));
-            tool_result_entries.push(HistoryEntry::tool_result(
-                &call_id,
-                &entry.name,
-                "",
-            ));
-            dispatch_tool_call(&cfg.tools, &entry, &cfg.output_channel, callback)?;
+
+            let is_return_value = lookup_tool(&cfg.tools, &entry.name)
+                .is_some_and(|spec| spec.kind == ToolKind::ReturnValue);
+            let content = if is_return_value {
+                executed += 1;
+                let invocation = ToolInvocation {
+                    session_id: session_id.to_string(),
+                    call_id: call_id.clone(),
+                    name: entry.name.clone(),
+                    arguments: serde_json::from_str(&entry.arguments)
+                        .unwrap_or_else(|_| Value::Object(serde_json::Map::new())),
+                };
+                execute_tool_call(&cfg.tool_handlers, &invocation)
+                    .await
+                    .content
+            } else {
+                dispatch_tool_call(&cfg.tools, &entry, &cfg.output_channel, callback)?;
+                String::new()
+            };
+            tool_result_entries.push(HistoryEntry::tool_result(&call_id, &entry.name, &content));
         }
 
+        let full_text = pass.full_text;
         let mut turn_entries: Vec<HistoryEntry> = Vec::new();
         if !tool_calls_for_history.is_empty() {
             turn_entries.push(HistoryEntry::assistant_with_tool_calls(
@@ -370,28 +638,23 @@ impl ChatBackend {
         } else if !full_text.is_empty() {
             turn_entries.push(HistoryEntry::assistant_text(&full_text));
         }
-        self.extend_history(session_id, turn_entries);
-
-        tracing::info!(
-            provider = self.profile.name(),
-            tokens = token_count,
-            chars = full_text.len(),
-            "[llm] streaming complete"
-        );
-        Ok(token_count)
+        self.extend_history(session_id, turn_entries).await;
+        Ok(executed)
     }
 
+    /// Send one non-streaming request. The reply arrives whole, so its
+    /// text reaches `callback` in one piece; tool calls are left for
+    /// [`Self::commit_pass`] as in streaming mode.
     async fn run_blocking<F>(
         &self,
-        session_id: &str,
         cfg: &ChatBackendConfig,
         body: Value,
         callback: &mut F,
-    ) -> Result<usize, Error>
+    ) -> Result<StreamedPass, Error>
     where
         F: FnMut(RuntimeData) -> Result<(), Error> + Send,
     {
-        let url = self.profile.endpoint(&cfg.base_url);
+        let url = self.profile.endpoint(&cfg.base_url, &cfg.model, false);
         let mut req = self.client.post(url).json(&body);
         req = self.profile.apply_auth(req, cfg.api_key.as_deref());
         let response = req
@@ -416,24 +679,17 @@ impl ChatBackend {
             .await
             .map_err(|e| Error::Execution(format!("LLM JSON parse error: {}", e)))?;
 
-        let content = json["choices"][0]["message"]["content"]
-            .as_str()
-            .unwrap_or("")
-            .to_string();
-        if content.is_empty() {
-            return Ok(0);
+        let mut pass = StreamedPass::default();
+        for ev in self.profile.parse_response(&json) {
+            pass.apply(ev, cfg, callback)?;
         }
-        self.append_history(session_id, HistoryEntry::assistant_text(&content));
-        callback(RuntimeData::Text(tag_text_str(
-            &content,
-            &cfg.output_channel,
-        )))?;
         tracing::info!(
             provider = self.profile.name(),
-            chars = content.len(),
+            chars = pass.full_text.len(),
+            tool_calls = pass.tool_calls.len(),
             "[llm] non-streaming response complete"
         );
-        Ok(1)
+        Ok(pass)
     }
 }
 
@@ -441,10 +697,373 @@ impl ChatBackend {
 impl ChatBackend {
     /// Test-only access to history snapshot.
     pub(crate) fn history_snapshot(&self, session_id: &str) -> Vec<HistoryEntry> {
-        self.history
-            .lock()
-            .get(session_id)
-            .cloned()
-            .unwrap_or_default()
+        self.history.load(session_id).unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::llm::provider::OpenAIProfile;
+    use crate::llm::tool_handler::tool_handler_fn;
+    use parking_lot::Mutex;
+    use serde_json::json;
+
+    /// OpenAI-shape mock: the Nth request gets `replies[min(N, last)]`
+    /// as its SSE stream, or its first entry as the body of a
+    /// non-streaming request. Returns the base URL and every request body.
+    async fn scripted_server(replies: Vec<Vec<Value>>) -> (String, Arc<Mutex<Vec<Value>>>) {
+        use axum::extract::Json;
+        use axum::response::IntoResponse;
+
+        let seen: Arc<Mutex<Vec<Value>>> = Default::default();
+        let seen_srv = seen.clone();
+        let app = axum::Router::new().route(
+            "/v1/chat/completions",
+            axum::routing::post(move |Json(body): Json<Value>| {
+                let seen = seen_srv.clone();
+                let replies = replies.clone();
+                async move {
+                    let streaming = body["stream"] != false;
+                    let n = {
+                        let mut seen = seen.lock();
+                        seen.push(body);
+                        seen.len() - 1
+                    };
+                    let chunks = &replies[n.min(replies.len() - 1)];
+                    if !streaming {
+                        return Json(chunks[0].clone()).into_response();
+                    }
+                    let mut sse: String =
+                        chunks.iter().map(|c| format!("data: {}\n\n", c)).collect();
+                    sse.push_str("data: [DONE]\n\n");
+                    ([("content-type", "text/event-stream")], sse).into_response()
+                }
+            }),
+        );
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            axum::serve(listener, app).await.unwrap();
+        });
+        (format!("http://{}/v1", addr), seen)
+    }
+
+    fn tool_call_chunk(id: &str, name: &str, arguments: &str) -> Value {
+        json!({"choices": [{"delta": {"tool_calls": [{
+            "index": 0, "id": id,
+            "function": {"name": name, "arguments": arguments}
+        }]}}]})
+    }
+
+    fn text_chunk(text: &str) -> Value {
+        json!({"choices": [{"delta": {"content": text}}]})
+    }
+
+    fn lookup_order_tool() -> ToolSpec {
+        ToolSpec {
+            name: "lookup_order".into(),
+            description: "Look up an order".into(),
+            parameters: json!({"type": "object", "properties": {"id": {"type": "string"}}}),
+            kind: ToolKind::ReturnValue,
+            handler: None,
+        }
+    }
+
+    fn config(base_url: String, handlers: ToolHandlerRegistry) -> ChatBackendConfig {
+        ChatBackendConfig {
+            api_key: None,
+            base_url,
+            model: "test-model".into(),
+            system_prompt: None,
+            max_tokens: None,
+            temperature: None,
+            top_p: None,
+            history_turns: 10,
+            streaming: true,
+            output_channel: TEXT_CHANNEL_DEFAULT.into(),
+            reasoning_channel: None,
+            tools: vec![lookup_order_tool()],
+            tool_choice: None,
+            tool_handlers: handlers,
+            max_tool_iterations: DEFAULT_MAX_TOOL_ITERATIONS,
+        }
+    }
+
+    #[tokio::test]
+    async fn return_value_tool_result_drives_second_pass() {
+        let (base_url, seen) = scripted_server(vec![
+            vec![tool_call_chunk("call_a", "lookup_order", r#"{"id":"42"}"#)],
+            vec![text_chunk("It "), text_chunk("shipped.")],
+        ])
+        .await;
+
+        let handlers = ToolHandlerRegistry::new().with_handler(
+            "lookup_order",
+            tool_handler_fn(|inv| {
+                assert_eq!(inv.session_id, "s1");
+                assert_eq!(inv.call_id, "call_a");
+                Ok(json!({"order": inv.arguments["id"], "status": "shipped"}))
+            }),
+        );
+        let cfg = config(base_url, handlers);
+        let backend = ChatBackend::new(Arc::new(OpenAIProfile));
+
+        let mut outputs: Vec<String> = Vec::new();
+        let mut cb = |d: RuntimeData| {
+            if let RuntimeData::Text(t) = d {
+                outputs.push(t);
+            }
+            Ok(())
+        };
+        let tokens = backend
+            .run(
+                "s1",
+                json!({"role": "user", "content": "Where is order 42?"}),
+                &cfg,
+                &mut cb,
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(tokens, 2);
+        assert_eq!(outputs.concat(), "It shipped.");
+
+        // The follow-up request carries the tool round-trip and no
+        // duplicated user turn.
+        let requests = seen.lock().clone();
+        assert_eq!(requests.len(), 2);
+        let roles: Vec<&str> = requests[1]["messages"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|m| m["role"].as_str().unwrap())
+            .collect();
+        assert_eq!(roles, vec!["user", "assistant", "tool"]);
+        let tool_msg = &requests[1]["messages"][2];
+        assert_eq!(tool_msg["tool_call_id"], "call_a");
+        let result: Value = serde_json::from_str(tool_msg["content"].as_str().unwrap()).unwrap();
+        assert_eq!(result, json!({"order": "42", "status": "shipped"}));
+
+        let history = backend.history_snapshot("s1");
+        let roles: Vec<&str> = history.iter().map(HistoryEntry::role).collect();
+        assert_eq!(roles, vec!["user", "assistant", "tool", "assistant"]);
+        assert_eq!(history[3].message["content"], "It shipped.");
+    }
+
+    #[tokio::test]
+    async fn non_streaming_runs_the_tool_loop() {
+        let (base_url, seen) = scripted_server(vec![
+            vec![json!({"choices": [{"message": {"tool_calls": [{
+                "id": "call_a", "type": "function",
+                "function": {"name": "lookup_order", "arguments": r#"{"id":"42"}"#}
+            }]}}]})],
+            vec![json!({"choices": [{"message": {"content": "It shipped."}}]})],
+        ])
+        .await;
+
+        let handlers = ToolHandlerRegistry::new().with_handler(
+            "lookup_order",
+            tool_handler_fn(|inv| Ok(json!({"order": inv.arguments["id"], "status": "shipped"}))),
+        );
+        let mut cfg = config(base_url, handlers);
+        cfg.streaming = false;
+        let backend = ChatBackend::new(Arc::new(OpenAIProfile));
+
+        let mut outputs: Vec<String> = Vec::new();
+        let mut cb = |d: RuntimeData| {
+            if let RuntimeData::Text(t) = d {
+                outputs.push(t);
+            }
+            Ok(())
+        };
+        backend
+            .run(
+                "s1",
+                json!({"role": "user", "content": "Where is order 42?"}),
+                &cfg,
+                &mut cb,
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(outputs, vec!["It shipped."]);
+        assert_eq!(seen.lock().len(), 2);
+        let history = backend.history_snapshot("s1");
+        let roles: Vec<&str> = history.iter().map(HistoryEntry::role).collect();
+        assert_eq!(roles, vec!["user", "assistant", "tool", "assistant"]);
+    }
+
+    #[tokio::test]
+    async fn tool_loop_stops_at_max_iterations() {
+        let (base_url, seen) = scripted_server(vec![vec![tool_call_chunk(
+            "call_loop",
+            "lookup_order",
+            "{}",
+        )]])
+        .await;
+
+        let handlers = ToolHandlerRegistry::new()
+            .with_handler("lookup_order", tool_handler_fn(|_| Ok(json!("try again"))));
+        let mut cfg = config(base_url, handlers);
+        cfg.max_tool_iterations = 2;
+        let backend = ChatBackend::new(Arc::new(OpenAIProfile));
+
+        let mut cb = |_: RuntimeData| Ok(());
+        backend
+            .run(
+                "s1",
+                json!({"role": "user", "content": "loop"}),
+                &cfg,
+                &mut cb,
+            )
+            .await
+            .unwrap();
+
+        // Initial pass + 2 follow-ups.
+        assert_eq!(seen.lock().len(), 3);
+
+        // Every assistant tool call is still paired with its result.
+        let history = backend.history_snapshot("s1");
+        let roles: Vec<&str> = history.iter().map(HistoryEntry::role).collect();
+        assert_eq!(
+            roles,
+            vec![
+                "user",
+                "assistant",
+                "tool",
+                "assistant",
+                "tool",
+                "assistant",
+                "tool"
+            ]
+        );
+        assert_eq!(history[6].message["content"], "try again");
+    }
+
+    #[tokio::test]
+    async fn side_effect_tools_do_not_regenerate() {
+        let (base_url, seen) = scripted_server(vec![vec![tool_call_chunk(
+            "call_say",
+            "say",
+            r#"{"text":"hello"}"#,
+        )]])
+        .await;
+
+        let mut cfg = config(base_url, ToolHandlerRegistry::new());
+        cfg.tools.push(crate::nodes::tool_spec::default_say_tool());
+        let backend = ChatBackend::new(Arc::new(OpenAIProfile));
+
+        let mut outputs: Vec<String> = Vec::new();
+        let mut cb = |d: RuntimeData| {
+            if let RuntimeData::Text(t) = d {
+                outputs.push(t);
+            }
+            Ok(())
+        };
+        backend
+            .run(
+                "s1",
+                json!({"role": "user", "content": "hi"}),
+                &cfg,
+                &mut cb,
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(seen.lock().len(), 1);
+        assert_eq!(outputs, vec!["hello\n"]);
+        assert_eq!(backend.history_snapshot("s1")[2].message["content"], "");
+    }
+
+    /// Replaces everything it is given with one summary naming how
+    /// many entries it folded.
+    struct CountingSummarizer;
+
+    #[async_trait::async_trait]
+    impl HistorySummarizer for CountingSummarizer {
+        async fn summarize(
+            &self,
+            _session_id: &str,
+            older: &[HistoryEntry],
+        ) -> Result<Vec<HistoryEntry>, Error> {
+            Ok(vec![HistoryEntry::summary(&format!(
+                "{} earlier entries",
+                older.len()
+            ))])
+        }
+    }
+
+    #[tokio::test]
+    async fn summaries_fold_into_system_prompt() {
+        let backend = ChatBackend::new(Arc::new(OpenAIProfile));
+        backend
+            .seed_history(
+                "s1",
+                &[
+                    HistoryEntry::summary("User asked about order 42."),
+                    HistoryEntry::user("and the other one?"),
+                    HistoryEntry::assistant_text("Order 43 shipped."),
+                ],
+            )
+            .await
+            .unwrap();
+        let mut cfg = config("http://unused".into(), ToolHandlerRegistry::new());
+        cfg.system_prompt = Some("Be brief.".into());
+
+        let body = backend.build_request_body_for_test(
+            "s1",
+            &cfg,
+            &json!({"role": "user", "content": "thanks"}),
+        );
+        let messages = body["messages"].as_array().unwrap();
+        let roles: Vec<&str> = messages
+            .iter()
+            .map(|m| m["role"].as_str().unwrap())
+            .collect();
+        assert_eq!(roles, vec!["system", "user", "assistant", "user"]);
+        assert_eq!(
+            messages[0]["content"],
+            "Be brief.\n\nSummary of the conversation so far:\nUser asked about order 42."
+        );
+
+        // Export returns exactly what was seeded.
+        assert_eq!(backend.export_history("s1").await.unwrap().len(), 3);
+        backend.clear_history("s1").await.unwrap();
+        assert!(backend.export_history("s1").await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn over_budget_history_is_compacted_after_turn() {
+        let (base_url, _seen) = scripted_server(vec![vec![text_chunk("Sure.")]]).await;
+        let backend = ChatBackend::new(Arc::new(OpenAIProfile)).with_summarizer(
+            HistoryBudget {
+                max_tokens: 1,
+                keep_turns: 1,
+            },
+            Arc::new(CountingSummarizer),
+        );
+        let cfg = config(base_url, ToolHandlerRegistry::new());
+
+        let mut cb = |_: RuntimeData| Ok(());
+        for text in ["one", "two", "three"] {
+            backend
+                .run(
+                    "s1",
+                    json!({"role": "user", "content": text}),
+                    &cfg,
+                    &mut cb,
+                )
+                .await
+                .unwrap();
+        }
+
+        // After turn 2: [summary(2), user two, assistant]. After turn 3
+        // the old summary plus turn two are folded again.
+        let history = backend.history_snapshot("s1");
+        let roles: Vec<&str> = history.iter().map(HistoryEntry::role).collect();
+        assert_eq!(roles, vec!["system", "user", "assistant"]);
+        assert_eq!(history[0].message["content"], "3 earlier entries");
+        assert_eq!(history[1].message["content"], "three");
     }
 }
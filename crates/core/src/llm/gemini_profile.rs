@@ -1,55 +1,665 @@
-//! Gemini Generative Language API profile (stub).
+//! Gemini Generative Language API profile.
 //!
-//! Sized so the [`crate::llm::ProviderProfile`] trait surface is
-//! verified against three vendors (OpenAI, Anthropic, Gemini) — but
-//! the bodies are `unimplemented!()` and the profile is **not** wired
-//! into [`ProviderKind`] yet. Lands so the abstraction layer is
-//! clearly multi-vendor instead of OpenAI-shaped-by-accident.
+//! Wire-shapes a vendor-agnostic [`ChatRequest`] (built around the
+//! OpenAI chat-completions message shape) into Gemini's
+//! `generateContent` request body, and parses the SSE stream from
+//! `streamGenerateContent?alt=sse` into [`ChatStreamEvent`]s.
 //!
-//! TODO(profile): the request shape is `POST {base}/models/{model}:streamGenerateContent`,
-//! body is `{contents: [{role, parts: [...]}]}`, content parts use
-//! `text` / `inline_data` (mime_type + base64) / `function_call`
-//! shapes, and SSE chunks carry `candidates[].content.parts[].text`
-//! plus tool calls. `system_instruction` is a top-level field.
+//! Differences vs. [`crate::llm::OpenAIProfile`]:
+//!
+//! - Endpoint: `POST {base}/models/{model}:streamGenerateContent?alt=sse`
+//!   (`:generateContent` when not streaming). The model lives in the
+//!   URL, not the body.
+//! - Auth: `x-goog-api-key: <key>`.
+//! - Body: `contents: [{role, parts}]` with roles `user` / `model`;
+//!   the system prompt is a top-level `system_instruction`; sampling
+//!   knobs go under `generation_config`.
+//! - Content parts: `text`, `inline_data {mime_type, data}` for base64
+//!   images and audio, `file_data {mime_type, file_uri}` for remote
+//!   images.
+//! - Tools: one `tools[].function_declarations[]` list; `tool_choice`
+//!   maps onto `tool_config.function_calling_config`.
+//! - Tool calls arrive whole as `functionCall {name, args}` parts
+//!   rather than as argument deltas, and results go back as
+//!   `function_response` parts on a `user` turn.
+//!
+//! Responses use the camelCase JSON mapping (`functionCall`,
+//! `finishReason`); the parser accepts snake_case too so recorded
+//! fixtures in either form work.
 
 use crate::llm::provider::{ChatRequest, ChatStreamEvent, ProviderProfile};
 use serde_json::Value;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// Gemini Generative Language API profile.
+#[derive(Debug, Default)]
+pub struct GeminiProfile {
+    /// Source of tool-call indices. Gemini chunks carry no per-call
+    /// index, and two chunks can each carry a complete call, so every
+    /// call gets a fresh index to keep the backend's accumulator from
+    /// merging them. Monotonic across sessions, which is fine: indices
+    /// only need to be unique and ordered within one stream.
+    next_call_index: AtomicU64,
+}
+
+impl GeminiProfile {
+    /// Fetch a value under either its camelCase or snake_case key.
+    fn field<'a>(v: &'a Value, camel: &str, snake: &str) -> Option<&'a Value> {
+        v.get(camel).or_else(|| v.get(snake))
+    }
+
+    /// Collect every `system` message into one `system_instruction`.
+    fn shape_system(messages: &[Value]) -> Option<Value> {
+        let parts: Vec<Value> = messages
+            .iter()
+            .filter(|m| m["role"] == "system")
+            .filter_map(|m| m["content"].as_str())
+            .map(|text| serde_json::json!({ "text": text }))
+            .collect();
+        if parts.is_empty() {
+            None
+        } else {
+            Some(serde_json::json!({ "parts": parts }))
+        }
+    }
+
+    fn shape_user_parts(msg: &Value) -> Vec<Value> {
+        match msg.get("content") {
+            Some(Value::String(s)) => vec![serde_json::json!({ "text": s })],
+            Some(Value::Array(parts)) => parts.iter().filter_map(Self::shape_user_part).collect(),
+            _ => Vec::new(),
+        }
+    }
+
+    fn shape_user_part(part: &Value) -> Option<Value> {
+        let kind = part.get("type").and_then(Value::as_str)?;
+        match kind {
+            "text" => part
+                .get("text")
+                .and_then(Value::as_str)
+                .map(|t| serde_json::json!({ "text": t })),
+            "image_url" => {
+                let url = part.pointer("/image_url/url").and_then(Value::as_str)?;
+                if let Some(suffix) = url.strip_prefix("data:") {
+                    // `image/png;base64,XXXX` → mime_type + data.
+                    let (mime_type, data) = suffix.split_once(";base64,")?;
+                    Some(serde_json::json!({
+                        "inline_data": { "mime_type": mime_type, "data": data },
+                    }))
+                } else {
+                    Some(serde_json::json!({
+                        "file_data": {
+                            "mime_type": Self::guess_image_mime(url),
+                            "file_uri": url,
+                        },
+                    }))
+                }
+            }
+            "input_audio" => {
+                let data = part.pointer("/input_audio/data").and_then(Value::as_str)?;
+                let format = part
+                    .pointer("/input_audio/format")
+                    .and_then(Value::as_str)
+                    .unwrap_or("wav");
+                Some(serde_json::json!({
+                    "inline_data": { "mime_type": format!("audio/{}", format), "data": data },
+                }))
+            }
+            _ => None,
+        }
+    }
+
+    fn guess_image_mime(url: &str) -> &'static str {
+        let path = url
+            .split(['?', '#'])
+            .next()
+            .unwrap_or(url)
+            .to_ascii_lowercase();
+        if path.ends_with(".png") {
+            "image/png"
+        } else if path.ends_with(".webp") {
+            "image/webp"
+        } else if path.ends_with(".gif") {
+            "image/gif"
+        } else {
+            "image/jpeg"
+        }
+    }
+
+    /// OpenAI assistant message → `model` turn with `text` and
+    /// `function_call` parts.
+    fn shape_assistant(msg: &Value) -> Value {
+        let mut parts: Vec<Value> = Vec::new();
+        if let Some(s) = msg.get("content").and_then(Value::as_str) {
+            if !s.is_empty() {
+                parts.push(serde_json::json!({ "text": s }));
+            }
+        }
+        if let Some(tcs) = msg.get("tool_calls").and_then(Value::as_array) {
+            for tc in tcs {
+                let name = tc
+                    .pointer("/function/name")
+                    .and_then(Value::as_str)
+                    .unwrap_or("");
+                // Gemini wants `args` as an object, not a string.
+                let args: Value = tc
+                    .pointer("/function/arguments")
+                    .and_then(Value::as_str)
+                    .and_then(|s| serde_json::from_str(s).ok())
+                    .unwrap_or_else(|| Value::Object(serde_json::Map::new()));
+                parts.push(serde_json::json!({
+                    "function_call": { "name": name, "args": args },
+                }));
+            }
+        }
+        serde_json::json!({ "role": "model", "parts": parts })
+    }
+
+    /// OpenAI `tool` message → `function_response` part. The response
+    /// must be an object; non-object content is wrapped as `{result}`.
+    fn shape_tool_result(msg: &Value) -> Value {
+        let name = msg.get("name").and_then(Value::as_str).unwrap_or("");
+        let content = msg.get("content").and_then(Value::as_str).unwrap_or("");
+        let response = match serde_json::from_str::<Value>(content) {
+            Ok(Value::Object(obj)) => Value::Object(obj),
+            _ => serde_json::json!({ "result": content }),
+        };
+        serde_json::json!({
+            "function_response": { "name": name, "response": response },
+        })
+    }
 
-#[derive(Debug, Default, Clone, Copy)]
-pub struct GeminiProfile;
+    /// Build `contents` from OpenAI-shape history. Consecutive `tool`
+    /// results collapse into one `user` turn of `function_response`
+    /// parts, and adjacent turns with the same role are merged since
+    /// Gemini expects user/model alternation.
+    fn shape_contents(messages: &[Value]) -> Vec<Value> {
+        let mut out: Vec<Value> = Vec::new();
+        for msg in messages {
+            let (role, parts) = match msg.get("role").and_then(Value::as_str) {
+                Some("user") => ("user", Self::shape_user_parts(msg)),
+                Some("assistant") => {
+                    let shaped = Self::shape_assistant(msg);
+                    (
+                        "model",
+                        shaped["parts"].as_array().cloned().unwrap_or_default(),
+                    )
+                }
+                Some("tool") => ("user", vec![Self::shape_tool_result(msg)]),
+                _ => continue,
+            };
+            if parts.is_empty() {
+                continue;
+            }
+            match out.last_mut() {
+                Some(last) if last["role"] == role => {
+                    if let Some(existing) = last["parts"].as_array_mut() {
+                        existing.extend(parts);
+                    }
+                }
+                _ => out.push(serde_json::json!({ "role": role, "parts": parts })),
+            }
+        }
+        out
+    }
+
+    fn shape_tools(specs: &[crate::nodes::tool_spec::ToolSpec]) -> Value {
+        let declarations: Vec<Value> = specs
+            .iter()
+            .map(|s| {
+                serde_json::json!({
+                    "name": s.name,
+                    "description": s.description,
+                    "parameters": s.parameters,
+                })
+            })
+            .collect();
+        serde_json::json!([{ "function_declarations": declarations }])
+    }
+
+    /// Map an OpenAI-style `tool_choice` onto `function_calling_config`.
+    fn shape_tool_choice(choice: &Value) -> Option<Value> {
+        let config = match choice {
+            Value::String(s) => match s.as_str() {
+                "auto" => serde_json::json!({ "mode": "AUTO" }),
+                "required" | "any" => serde_json::json!({ "mode": "ANY" }),
+                "none" => serde_json::json!({ "mode": "NONE" }),
+                _ => return None,
+            },
+            Value::Object(_) => {
+                let name = choice.pointer("/function/name").and_then(Value::as_str)?;
+                serde_json::json!({ "mode": "ANY", "allowed_function_names": [name] })
+            }
+            _ => return None,
+        };
+        Some(serde_json::json!({ "function_calling_config": config }))
+    }
+}
 
 impl ProviderProfile for GeminiProfile {
     fn name(&self) -> &'static str {
         "gemini"
     }
 
-    fn endpoint(&self, _base_url: &str) -> String {
-        unimplemented!("GeminiProfile not wired up yet — see TODO in gemini_profile.rs")
+    fn endpoint(&self, base_url: &str, model: &str, streaming: bool) -> String {
+        // Accept both `gemini-2.0-flash` and the resource-name form
+        // `models/gemini-2.0-flash`.
+        let model = model.strip_prefix("models/").unwrap_or(model);
+        if streaming {
+            format!(
+                "{}/models/{}:streamGenerateContent?alt=sse",
+                base_url, model
+            )
+        } else {
+            format!("{}/models/{}:generateContent", base_url, model)
+        }
     }
 
     fn apply_auth(
         &self,
-        _req: reqwest::RequestBuilder,
-        _api_key: Option<&str>,
+        req: reqwest::RequestBuilder,
+        api_key: Option<&str>,
     ) -> reqwest::RequestBuilder {
-        unimplemented!("GeminiProfile not wired up yet")
+        match api_key {
+            Some(k) if !k.is_empty() => req.header("x-goog-api-key", k),
+            _ => req,
+        }
     }
 
-    fn shape_request(&self, _req: &ChatRequest<'_>) -> Value {
-        unimplemented!("GeminiProfile not wired up yet")
+    fn shape_request(&self, req: &ChatRequest<'_>) -> Value {
+        let mut body = serde_json::json!({
+            "contents": Self::shape_contents(&req.messages),
+        });
+        if let Some(system) = Self::shape_system(&req.messages) {
+            body["system_instruction"] = system;
+        }
+
+        let mut generation_config = serde_json::Map::new();
+        if let Some(max_tokens) = req.max_tokens {
+            generation_config.insert("max_output_tokens".into(), max_tokens.into());
+        }
+        if let Some(temperature) = req.temperature {
+            if let Some(num) = serde_json::Number::from_f64(temperature as f64) {
+                generation_config.insert("temperature".into(), Value::Number(num));
+            }
+        }
+        if let Some(top_p) = req.top_p {
+            if let Some(num) = serde_json::Number::from_f64(top_p as f64) {
+                generation_config.insert("top_p".into(), Value::Number(num));
+            }
+        }
+        if !generation_config.is_empty() {
+            body["generation_config"] = Value::Object(generation_config);
+        }
+
+        if !req.tools.is_empty() {
+            body["tools"] = Self::shape_tools(req.tools);
+            if let Some(tool_config) = req.tool_choice.and_then(Self::shape_tool_choice) {
+                body["tool_config"] = tool_config;
+            }
+        }
+        body
     }
 
-    fn parse_sse_payload(&self, _payload: &Value) -> Vec<ChatStreamEvent> {
-        unimplemented!("GeminiProfile not wired up yet")
+    fn parse_sse_payload(&self, payload: &Value) -> Vec<ChatStreamEvent> {
+        let candidate = &payload["candidates"][0];
+        let mut out: Vec<ChatStreamEvent> = Vec::new();
+
+        if let Some(parts) = candidate
+            .pointer("/content/parts")
+            .and_then(Value::as_array)
+        {
+            for part in parts {
+                if let Some(text) = part.get("text").and_then(Value::as_str) {
+                    if text.is_empty() {
+                        continue;
+                    }
+                    // Thinking models flag thought summaries with
+                    // `thought: true`.
+                    if part.get("thought").and_then(Value::as_bool) == Some(true) {
+                        out.push(ChatStreamEvent::ReasoningText(text.to_string()));
+                    } else {
+                        out.push(ChatStreamEvent::VisibleText(text.to_string()));
+                    }
+                    continue;
+                }
+                if let Some(call) = Self::field(part, "functionCall", "function_call") {
+                    let name = call
+                        .get("name")
+                        .and_then(Value::as_str)
+                        .filter(|s| !s.is_empty())
+                        .map(str::to_string);
+                    let id = call
+                        .get("id")
+                        .and_then(Value::as_str)
+                        .filter(|s| !s.is_empty())
+                        .map(str::to_string);
+                    let args = call
+                        .get("args")
+                        .cloned()
+                        .unwrap_or_else(|| Value::Object(serde_json::Map::new()));
+                    out.push(ChatStreamEvent::ToolCallDelta {
+                        index: self.next_call_index.fetch_add(1, Ordering::Relaxed),
+                        id,
+                        name,
+                        arguments_chunk: Some(args.to_string()),
+                    });
+                }
+            }
+        }
+
+        let finished = Self::field(candidate, "finishReason", "finish_reason")
+            .and_then(Value::as_str)
+            .is_some_and(|r| r != "FINISH_REASON_UNSPECIFIED");
+        if finished {
+            out.push(ChatStreamEvent::Done);
+        }
+        out
+    }
+
+    fn parse_response(&self, body: &Value) -> Vec<ChatStreamEvent> {
+        // The unary response has the same shape as one stream chunk.
+        self.parse_sse_payload(body)
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::llm::{ChatBackend, ChatBackendConfig};
+    use crate::nodes::tool_spec::default_say_tool;
+    use std::sync::Arc;
+
+    fn request<'a>(messages: Vec<Value>) -> ChatRequest<'a> {
+        ChatRequest {
+            model: "gemini-2.0-flash",
+            messages,
+            tools: &[],
+            tool_choice: None,
+            max_tokens: Some(256),
+            temperature: Some(0.5),
+            top_p: None,
+            streaming: true,
+        }
+    }
 
     #[test]
     fn name_is_gemini() {
-        assert_eq!(GeminiProfile.name(), "gemini");
+        assert_eq!(GeminiProfile::default().name(), "gemini");
+    }
+
+    #[test]
+    fn endpoint_puts_model_in_path() {
+        let p = GeminiProfile::default();
+        let base = "https://generativelanguage.googleapis.com/v1beta";
+        assert_eq!(
+            p.endpoint(base, "gemini-2.0-flash", true),
+            "https://generativelanguage.googleapis.com/v1beta/models/gemini-2.0-flash:streamGenerateContent?alt=sse"
+        );
+        assert_eq!(
+            p.endpoint(base, "models/gemini-2.0-flash", false),
+            "https://generativelanguage.googleapis.com/v1beta/models/gemini-2.0-flash:generateContent"
+        );
+    }
+
+    #[test]
+    fn apply_auth_sets_goog_api_key() {
+        let client = reqwest::Client::new();
+        let req = GeminiProfile::default().apply_auth(client.post("http://localhost/"), Some("k"));
+        let built = req.build().unwrap();
+        assert_eq!(built.headers().get("x-goog-api-key").unwrap(), "k");
+        assert!(built.headers().get("authorization").is_none());
+    }
+
+    #[test]
+    fn system_prompt_becomes_system_instruction() {
+        let body = GeminiProfile::default().shape_request(&request(vec![
+            serde_json::json!({"role": "system", "content": "Be brief."}),
+            serde_json::json!({"role": "user", "content": "Hi"}),
+        ]));
+        assert_eq!(body["system_instruction"]["parts"][0]["text"], "Be brief.");
+        let contents = body["contents"].as_array().unwrap();
+        assert_eq!(contents.len(), 1);
+        assert_eq!(contents[0]["role"], "user");
+        assert_eq!(contents[0]["parts"][0]["text"], "Hi");
+        assert_eq!(body["generation_config"]["max_output_tokens"], 256);
+        assert!(body.get("model").is_none());
+        assert!(body.get("tools").is_none());
+    }
+
+    #[test]
+    fn image_and_audio_parts_become_inline_data() {
+        let body = GeminiProfile::default().shape_request(&request(vec![serde_json::json!({
+            "role": "user",
+            "content": [
+                {"type": "text", "text": "describe"},
+                {"type": "image_url", "image_url": {"url": "data:image/png;base64,AAAA"}},
+                {"type": "input_audio", "input_audio": {"data": "UklG", "format": "wav"}},
+                {"type": "image_url", "image_url": {"url": "https://example.com/cat.webp"}}
+            ]
+        })]));
+        let parts = body["contents"][0]["parts"].as_array().unwrap();
+        assert_eq!(parts.len(), 4);
+        assert_eq!(parts[1]["inline_data"]["mime_type"], "image/png");
+        assert_eq!(parts[1]["inline_data"]["data"], "AAAA");
+        assert_eq!(parts[2]["inline_data"]["mime_type"], "audio/wav");
+        assert_eq!(parts[2]["inline_data"]["data"], "UklG");
+        assert_eq!(parts[3]["file_data"]["mime_type"], "image/webp");
+        assert_eq!(
+            parts[3]["file_data"]["file_uri"],
+            "https://example.com/cat.webp"
+        );
+    }
+
+    #[test]
+    fn tool_history_round_trips_as_function_parts() {
+        let tools = [default_say_tool()];
+        let choice = serde_json::json!({"type": "function", "function": {"name": "say"}});
+        let mut req = request(vec![
+            serde_json::json!({"role": "user", "content": "go"}),
+            serde_json::json!({
+                "role": "assistant",
+                "content": null,
+                "tool_calls": [{
+                    "id": "call_0",
+                    "type": "function",
+                    "function": {"name": "say", "arguments": "{\"text\":\"Hi!\"}"}
+                }]
+            }),
+            serde_json::json!({"role": "tool", "tool_call_id": "call_0", "name": "say", "content": ""}),
+            serde_json::json!({"role": "user", "content": "again"}),
+        ]);
+        req.tools = &tools;
+        req.tool_choice = Some(&choice);
+
+        let body = GeminiProfile::default().shape_request(&req);
+        let contents = body["contents"].as_array().unwrap();
+        // user, model, user (function_response + "again" merged)
+        assert_eq!(contents.len(), 3);
+        assert_eq!(contents[1]["role"], "model");
+        assert_eq!(contents[1]["parts"][0]["function_call"]["name"], "say");
+        assert_eq!(
+            contents[1]["parts"][0]["function_call"]["args"]["text"],
+            "Hi!"
+        );
+        assert_eq!(contents[2]["parts"][0]["function_response"]["name"], "say");
+        assert_eq!(
+            contents[2]["parts"][0]["function_response"]["response"],
+            serde_json::json!({"result": ""})
+        );
+        assert_eq!(contents[2]["parts"][1]["text"], "again");
+
+        assert_eq!(body["tools"][0]["function_declarations"][0]["name"], "say");
+        let fcc = &body["tool_config"]["function_calling_config"];
+        assert_eq!(fcc["mode"], "ANY");
+        assert_eq!(fcc["allowed_function_names"][0], "say");
+    }
+
+    #[test]
+    fn parse_text_thought_and_finish() {
+        let p = GeminiProfile::default();
+        let evs = p.parse_sse_payload(&serde_json::json!({
+            "candidates": [{
+                "content": {"role": "model", "parts": [
+                    {"text": "pondering", "thought": true},
+                    {"text": "Hello"}
+                ]},
+                "finishReason": "STOP"
+            }]
+        }));
+        assert_eq!(evs.len(), 3);
+        assert!(matches!(&evs[0], ChatStreamEvent::ReasoningText(s) if s == "pondering"));
+        assert!(matches!(&evs[1], ChatStreamEvent::VisibleText(s) if s == "Hello"));
+        assert!(matches!(evs[2], ChatStreamEvent::Done));
+    }
+
+    #[test]
+    fn parse_function_calls_get_distinct_indices() {
+        let p = GeminiProfile::default();
+        let chunk = serde_json::json!({
+            "candidates": [{"content": {"parts": [
+                {"functionCall": {"name": "say", "args": {"text": "Hi"}}}
+            ]}}]
+        });
+        let mut indices = Vec::new();
+        for _ in 0..2 {
+            match &p.parse_sse_payload(&chunk)[0] {
+                ChatStreamEvent::ToolCallDelta {
+                    index,
+                    name,
+                    arguments_chunk,
+                    ..
+                } => {
+                    assert_eq!(name.as_deref(), Some("say"));
+                    let args: Value =
+                        serde_json::from_str(arguments_chunk.as_deref().unwrap()).unwrap();
+                    assert_eq!(args["text"], "Hi");
+                    indices.push(*index);
+                }
+                other => panic!("expected ToolCallDelta, got {:?}", other),
+            }
+        }
+        assert_ne!(indices[0], indices[1]);
+    }
+
+    /// Serve a canned SSE stream and capture what the backend sent.
+    async fn mock_sse_server(
+        chunks: Vec<Value>,
+    ) -> (String, tokio::sync::oneshot::Receiver<(String, Value)>) {
+        use axum::extract::{Json, OriginalUri};
+        use axum::http::HeaderMap;
+
+        let (seen_tx, seen_rx) = tokio::sync::oneshot::channel();
+        let seen_tx = Arc::new(parking_lot::Mutex::new(Some(seen_tx)));
+        let sse_body: String = chunks
+            .iter()
+            .map(|c| format!("data: {}\r\n\r\n", c))
+            .collect();
+
+        let app = axum::Router::new().fallback(
+            move |uri: OriginalUri, headers: HeaderMap, Json(body): Json<Value>| {
+                let seen_tx = seen_tx.clone();
+                let sse_body = sse_body.clone();
+                async move {
+                    let key = headers
+                        .get("x-goog-api-key")
+                        .and_then(|v| v.to_str().ok())
+                        .unwrap_or_default();
+                    let path = format!("{} key={}", uri.0, key);
+                    if let Some(tx) = seen_tx.lock().take() {
+                        let _ = tx.send((path, body));
+                    }
+                    ([("content-type", "text/event-stream")], sse_body)
+                }
+            },
+        );
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            axum::serve(listener, app).await.unwrap();
+        });
+        (format!("http://{}/v1beta", addr), seen_rx)
+    }
+
+    fn backend_config(base_url: String) -> ChatBackendConfig {
+        ChatBackendConfig {
+            api_key: Some("test-key".into()),
+            base_url,
+            model: "gemini-2.0-flash".into(),
+            system_prompt: Some("Be brief.".into()),
+            max_tokens: None,
+            temperature: None,
+            top_p: None,
+            history_turns: 10,
+            streaming: true,
+            output_channel: "tts".into(),
+            reasoning_channel: None,
+            tools: vec![default_say_tool()],
+            tool_choice: None,
+            tool_handlers: Default::default(),
+            max_tool_iterations: crate::llm::DEFAULT_MAX_TOOL_ITERATIONS,
+        }
+    }
+
+    #[tokio::test]
+    async fn backend_streams_text_and_tool_calls_from_mock_server() {
+        let (base_url, seen) = mock_sse_server(vec![
+            serde_json::json!({"candidates": [{"content": {"role": "model", "parts": [{"text": "Hel"}]}}]}),
+            serde_json::json!({"candidates": [{"content": {"role": "model", "parts": [{"text": "lo"}]}}]}),
+            serde_json::json!({"candidates": [{
+                "content": {"role": "model", "parts": [
+                    {"functionCall": {"name": "say", "args": {"text": "Spoken"}}}
+                ]},
+                "finishReason": "STOP"
+            }]}),
+        ])
+        .await;
+
+        let backend = ChatBackend::new(Arc::new(GeminiProfile::default()));
+        let cfg = backend_config(base_url);
+        let mut outputs: Vec<String> = Vec::new();
+        let mut cb = |d: crate::data::RuntimeData| {
+            if let crate::data::RuntimeData::Text(t) = d {
+                outputs.push(t);
+            }
+            Ok(())
+        };
+        let tokens = backend
+            .run(
+                "s1",
+                serde_json::json!({"role": "user", "content": "Hi"}),
+                &cfg,
+                &mut cb,
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(tokens, 2);
+        assert_eq!(outputs[0], "Hel");
+        assert_eq!(outputs[1], "lo");
+        assert!(
+            outputs[2].starts_with("Spoken"),
+            "say tool output: {:?}",
+            outputs
+        );
+
+        let (path, body) = seen.await.unwrap();
+        assert_eq!(
+            path,
+            "/v1beta/models/gemini-2.0-flash:streamGenerateContent?alt=sse key=test-key"
+        );
+        assert_eq!(body["system_instruction"]["parts"][0]["text"], "Be brief.");
+        assert_eq!(body["contents"][0]["parts"][0]["text"], "Hi");
+        assert_eq!(body["tools"][0]["function_declarations"][0]["name"], "say");
+
+        // The tool call is committed to history in OpenAI shape so the
+        // next request can re-shape it into function_call parts.
+        let history = backend.history_snapshot("s1");
+        assert_eq!(
+            history[1].message["tool_calls"][0]["function"]["name"],
+            "say"
+        );
+        assert_eq!(history[2].message["role"], "tool");
     }
 }
@@ -11,12 +11,19 @@
 //!
 //! The window is counted in **user turns**, not raw entries, so a
 //! single turn's `(assistant + N tool results)` group never gets sliced
-//! apart at the front of the window.
+//! apart at the front of the window. Summaries of compacted turns
+//! ([`HistoryEntry::summary`]) sit at the head of a session and are
+//! folded into the system prompt rather than counted as turns.
+//!
+//! Storage lives behind [`crate::llm::history_store::HistoryStore`];
+//! entries serialize as the bare message object.
 
+use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
 /// One chat-completions message in per-session history.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(transparent)]
 pub struct HistoryEntry {
     /// Pre-shaped message ready to drop into the `messages` array.
     pub message: Value,
@@ -74,6 +81,19 @@ impl HistoryEntry {
         }
     }
 
+    /// Summary of compacted earlier turns. Stored with the `system`
+    /// role; the backend merges it into the system prompt on send.
+    pub fn summary(content: &str) -> Self {
+        Self {
+            message: serde_json::json!({ "role": "system", "content": content }),
+        }
+    }
+
+    /// Whether this entry is a [`Self::summary`].
+    pub fn is_summary(&self) -> bool {
+        self.role() == "system"
+    }
+
     pub fn role(&self) -> &str {
         self.message
             .get("role")
@@ -142,6 +162,22 @@ mod tests {
         assert_eq!(e.message["content"], "");
     }
 
+    #[test]
+    fn entries_serialize_as_bare_messages() {
+        let entries = vec![HistoryEntry::summary("earlier"), HistoryEntry::user("hi")];
+        let json = serde_json::to_value(&entries).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!([
+                {"role": "system", "content": "earlier"},
+                {"role": "user", "content": "hi"}
+            ])
+        );
+        let back: Vec<HistoryEntry> = serde_json::from_value(json).unwrap();
+        assert_eq!(back, entries);
+        assert!(back[0].is_summary());
+    }
+
     #[test]
     fn window_start_zero_turns_yields_empty_suffix() {
         let entries = vec![HistoryEntry::user("a"), HistoryEntry::assistant_text("b")];
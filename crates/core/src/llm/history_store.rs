@@ -0,0 +1,692 @@
+//! Pluggable storage for per-session conversation history.
+//!
+//! [`crate::llm::ChatBackend`] reads and writes history through a
+//! [`HistoryStore`] so context can outlive the process: with
+//! [`SqliteHistoryStore`] a user who reconnects under the same session
+//! id picks the conversation back up. [`InMemoryHistoryStore`] is the
+//! default and matches the old behaviour.
+//!
+//! Both stores support a session TTL: a session untouched for longer
+//! than the TTL reads as empty and is removed by
+//! [`HistoryStore::evict_expired`]. Each store also runs that sweep on
+//! write, at most once per TTL, so sessions nobody returns to do not
+//! pile up in a long-running process.
+//!
+//! Long sessions can be compacted with a [`HistorySummarizer`]: once
+//! the estimated token count passes [`HistoryBudget::max_tokens`], the
+//! turns older than [`HistoryBudget::keep_turns`] are handed to the
+//! summarizer and replaced by what it returns (typically one
+//! [`HistoryEntry::summary`]).
+
+use crate::error::Error;
+use crate::llm::history::{window_start, HistoryEntry};
+use parking_lot::Mutex;
+use rusqlite::OptionalExtension;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::Arc;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+/// Storage backend for conversation history, keyed by session id.
+///
+/// Entries are stored in order; the backend only ever appends a whole
+/// turn at a time or replaces a session wholesale, so an assistant
+/// `tool_calls` message is never persisted without its tool results.
+pub trait HistoryStore: Send + Sync {
+    /// All entries for `session_id`, oldest first. Unknown or expired
+    /// sessions yield an empty vec.
+    fn load(&self, session_id: &str) -> Result<Vec<HistoryEntry>, Error>;
+
+    /// Append entries to the end of `session_id`'s history.
+    fn append(&self, session_id: &str, entries: &[HistoryEntry]) -> Result<(), Error>;
+
+    /// Replace `session_id`'s history (seeding, summarisation).
+    fn replace(&self, session_id: &str, entries: &[HistoryEntry]) -> Result<(), Error>;
+
+    /// Drop `session_id`'s history.
+    fn remove(&self, session_id: &str) -> Result<(), Error>;
+
+    /// Ids of every live (non-expired) session.
+    fn sessions(&self) -> Result<Vec<String>, Error>;
+
+    /// Remove sessions idle longer than the TTL; returns how many were
+    /// removed. No-op without a TTL.
+    fn evict_expired(&self) -> Result<usize, Error>;
+}
+
+// ---------------------------------------------------------------------------
+// Manifest config
+// ---------------------------------------------------------------------------
+
+/// Manifest selection of a history store for an LLM node.
+///
+/// ```json
+/// "history_store": { "type": "sqlite", "path": "/var/lib/agent/history.db", "ttl_secs": 86400 }
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, schemars::JsonSchema)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum HistoryStoreConfig {
+    /// Process-local history (lost on restart).
+    Memory {
+        #[serde(default, alias = "ttlSecs")]
+        ttl_secs: Option<u64>,
+    },
+    /// SQLite database file, created if missing. Several nodes may
+    /// share one file; sessions are keyed by session id.
+    Sqlite {
+        path: String,
+        #[serde(default, alias = "ttlSecs")]
+        ttl_secs: Option<u64>,
+    },
+}
+
+impl HistoryStoreConfig {
+    /// Open the configured store.
+    pub fn open(&self) -> Result<Arc<dyn HistoryStore>, Error> {
+        match self {
+            HistoryStoreConfig::Memory { ttl_secs } => Ok(Arc::new(
+                InMemoryHistoryStore::new().with_ttl(ttl_secs.map(Duration::from_secs)),
+            )),
+            HistoryStoreConfig::Sqlite { path, ttl_secs } => {
+                let store =
+                    SqliteHistoryStore::open(path)?.with_ttl(ttl_secs.map(Duration::from_secs));
+                // Sweep sessions that expired while nothing had the file open.
+                let evicted = store.evict_expired()?;
+                if evicted > 0 {
+                    tracing::info!(path = %path, evicted, "[llm] evicted expired history sessions");
+                }
+                Ok(Arc::new(store))
+            }
+        }
+    }
+}
+
+/// When a store last swept out expired sessions.
+struct SweepClock(Mutex<Instant>);
+
+impl Default for SweepClock {
+    fn default() -> Self {
+        Self(Mutex::new(Instant::now()))
+    }
+}
+
+impl SweepClock {
+    /// Run `store.evict_expired()` if the last sweep is at least one
+    /// `ttl` ago. No-op without a TTL.
+    fn sweep(&self, ttl: Option<Duration>, store: &dyn HistoryStore) -> Result<(), Error> {
+        let Some(ttl) = ttl else {
+            return Ok(());
+        };
+        {
+            let mut last = self.0.lock();
+            if last.elapsed() < ttl {
+                return Ok(());
+            }
+            *last = Instant::now();
+        }
+        let evicted = store.evict_expired()?;
+        if evicted > 0 {
+            tracing::debug!(evicted, "[llm] evicted expired history sessions");
+        }
+        Ok(())
+    }
+}
+
+// ---------------------------------------------------------------------------
+// In-memory store
+// ---------------------------------------------------------------------------
+
+struct MemorySession {
+    entries: Vec<HistoryEntry>,
+    touched: Instant,
+}
+
+/// Process-local [`HistoryStore`].
+#[derive(Default)]
+pub struct InMemoryHistoryStore {
+    sessions: Mutex<HashMap<String, MemorySession>>,
+    ttl: Option<Duration>,
+    sweep: SweepClock,
+}
+
+impl InMemoryHistoryStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Expire sessions idle for longer than `ttl` (`None` = never).
+    pub fn with_ttl(mut self, ttl: Option<Duration>) -> Self {
+        self.ttl = ttl;
+        self
+    }
+
+    fn is_expired(&self, session: &MemorySession) -> bool {
+        self.ttl.is_some_and(|ttl| session.touched.elapsed() > ttl)
+    }
+}
+
+impl HistoryStore for InMemoryHistoryStore {
+    fn load(&self, session_id: &str) -> Result<Vec<HistoryEntry>, Error> {
+        let mut sessions = self.sessions.lock();
+        let expired = match sessions.get(session_id) {
+            Some(session) if !self.is_expired(session) => return Ok(session.entries.clone()),
+            Some(_) => true,
+            None => false,
+        };
+        if expired {
+            sessions.remove(session_id);
+        }
+        Ok(Vec::new())
+    }
+
+    fn append(&self, session_id: &str, entries: &[HistoryEntry]) -> Result<(), Error> {
+        self.sweep.sweep(self.ttl, self)?;
+        let mut sessions = self.sessions.lock();
+        let expired = sessions
+            .get(session_id)
+            .is_some_and(|session| self.is_expired(session));
+        let session = sessions
+            .entry(session_id.to_string())
+            .or_insert_with(|| MemorySession {
+                entries: Vec::new(),
+                touched: Instant::now(),
+            });
+        if expired {
+            session.entries.clear();
+        }
+        session.entries.extend_from_slice(entries);
+        session.touched = Instant::now();
+        Ok(())
+    }
+
+    fn replace(&self, session_id: &str, entries: &[HistoryEntry]) -> Result<(), Error> {
+        self.sweep.sweep(self.ttl, self)?;
+        self.sessions.lock().insert(
+            session_id.to_string(),
+            MemorySession {
+                entries: entries.to_vec(),
+                touched: Instant::now(),
+            },
+        );
+        Ok(())
+    }
+
+    fn remove(&self, session_id: &str) -> Result<(), Error> {
+        self.sessions.lock().remove(session_id);
+        Ok(())
+    }
+
+    fn sessions(&self) -> Result<Vec<String>, Error> {
+        let sessions = self.sessions.lock();
+        let mut ids: Vec<String> = sessions
+            .iter()
+            .filter(|(_, session)| !self.is_expired(session))
+            .map(|(id, _)| id.clone())
+            .collect();
+        ids.sort_unstable();
+        Ok(ids)
+    }
+
+    fn evict_expired(&self) -> Result<usize, Error> {
+        let mut sessions = self.sessions.lock();
+        let before = sessions.len();
+        sessions.retain(|_, session| !self.is_expired(session));
+        Ok(before - sessions.len())
+    }
+}
+
+// ---------------------------------------------------------------------------
+// SQLite store
+// ---------------------------------------------------------------------------
+
+const SQLITE_SCHEMA: &str = "
+    PRAGMA journal_mode = WAL;
+    CREATE TABLE IF NOT EXISTS llm_history_sessions (
+        session_id TEXT PRIMARY KEY,
+        updated_at_ms INTEGER NOT NULL
+    );
+    CREATE TABLE IF NOT EXISTS llm_history_entries (
+        session_id TEXT NOT NULL,
+        seq INTEGER NOT NULL,
+        message TEXT NOT NULL,
+        PRIMARY KEY (session_id, seq)
+    );
+";
+
+fn db_err(e: rusqlite::Error) -> Error {
+    Error::Execution(format!("history store: {}", e))
+}
+
+fn now_ms() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as i64)
+        .unwrap_or(0)
+}
+
+/// SQLite-backed [`HistoryStore`]; survives restarts.
+///
+/// Each message is stored as its JSON text, so exported rows are the
+/// exact chat-completions messages the backend sends.
+pub struct SqliteHistoryStore {
+    conn: Mutex<rusqlite::Connection>,
+    ttl: Option<Duration>,
+    sweep: SweepClock,
+}
+
+impl SqliteHistoryStore {
+    /// Open (or create) the database at `path`.
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, Error> {
+        let conn = rusqlite::Connection::open(path).map_err(db_err)?;
+        Self::with_connection(conn)
+    }
+
+    /// Private in-memory database (tests, ephemeral use).
+    pub fn open_in_memory() -> Result<Self, Error> {
+        Self::with_connection(rusqlite::Connection::open_in_memory().map_err(db_err)?)
+    }
+
+    fn with_connection(conn: rusqlite::Connection) -> Result<Self, Error> {
+        conn.busy_timeout(Duration::from_secs(5)).map_err(db_err)?;
+        conn.execute_batch(SQLITE_SCHEMA).map_err(db_err)?;
+        Ok(Self {
+            conn: Mutex::new(conn),
+            ttl: None,
+            sweep: SweepClock::default(),
+        })
+    }
+
+    /// Expire sessions idle for longer than `ttl` (`None` = never).
+    pub fn with_ttl(mut self, ttl: Option<Duration>) -> Self {
+        self.ttl = ttl;
+        self
+    }
+
+    /// Sessions updated before this timestamp are expired.
+    fn cutoff_ms(&self) -> Option<i64> {
+        self.ttl.map(|ttl| now_ms() - ttl.as_millis() as i64)
+    }
+
+    fn delete_session(tx: &rusqlite::Transaction<'_>, session_id: &str) -> Result<(), Error> {
+        tx.execute(
+            "DELETE FROM llm_history_entries WHERE session_id = ?1",
+            [session_id],
+        )
+        .map_err(db_err)?;
+        tx.execute(
+            "DELETE FROM llm_history_sessions WHERE session_id = ?1",
+            [session_id],
+        )
+        .map_err(db_err)?;
+        Ok(())
+    }
+
+    fn insert_entries(
+        tx: &rusqlite::Transaction<'_>,
+        session_id: &str,
+        first_seq: i64,
+        entries: &[HistoryEntry],
+    ) -> Result<(), Error> {
+        let mut stmt = tx
+            .prepare_cached(
+                "INSERT INTO llm_history_entries (session_id, seq, message) VALUES (?1, ?2, ?3)",
+            )
+            .map_err(db_err)?;
+        for (i, entry) in entries.iter().enumerate() {
+            stmt.execute(rusqlite::params![
+                session_id,
+                first_seq + i as i64,
+                entry.message.to_string()
+            ])
+            .map_err(db_err)?;
+        }
+        tx.execute(
+            "INSERT INTO llm_history_sessions (session_id, updated_at_ms) VALUES (?1, ?2)
+             ON CONFLICT(session_id) DO UPDATE SET updated_at_ms = excluded.updated_at_ms",
+            rusqlite::params![session_id, now_ms()],
+        )
+        .map_err(db_err)?;
+        Ok(())
+    }
+
+    fn is_expired(&self, tx: &rusqlite::Transaction<'_>, session_id: &str) -> Result<bool, Error> {
+        let Some(cutoff) = self.cutoff_ms() else {
+            return Ok(false);
+        };
+        let updated: Option<i64> = tx
+            .query_row(
+                "SELECT updated_at_ms FROM llm_history_sessions WHERE session_id = ?1",
+                [session_id],
+                |row| row.get(0),
+            )
+            .optional()
+            .map_err(db_err)?;
+        Ok(updated.is_some_and(|t| t < cutoff))
+    }
+}
+
+impl HistoryStore for SqliteHistoryStore {
+    fn load(&self, session_id: &str) -> Result<Vec<HistoryEntry>, Error> {
+        let mut conn = self.conn.lock();
+        let tx = conn.transaction().map_err(db_err)?;
+        if self.is_expired(&tx, session_id)? {
+            Self::delete_session(&tx, session_id)?;
+            tx.commit().map_err(db_err)?;
+            return Ok(Vec::new());
+        }
+
+        let entries = {
+            let mut stmt = tx
+                .prepare_cached(
+                    "SELECT message FROM llm_history_entries WHERE session_id = ?1 ORDER BY seq",
+                )
+                .map_err(db_err)?;
+            let rows = stmt
+                .query_map([session_id], |row| row.get::<_, String>(0))
+                .map_err(db_err)?;
+            let mut entries = Vec::new();
+            for row in rows {
+                let text = row.map_err(db_err)?;
+                entries.push(HistoryEntry {
+                    message: serde_json::from_str(&text)?,
+                });
+            }
+            entries
+        };
+        tx.commit().map_err(db_err)?;
+        Ok(entries)
+    }
+
+    fn append(&self, session_id: &str, entries: &[HistoryEntry]) -> Result<(), Error> {
+        self.sweep.sweep(self.ttl, self)?;
+        let mut conn = self.conn.lock();
+        let tx = conn.transaction().map_err(db_err)?;
+        if self.is_expired(&tx, session_id)? {
+            Self::delete_session(&tx, session_id)?;
+        }
+        let next_seq: i64 = tx
+            .query_row(
+                "SELECT COALESCE(MAX(seq) + 1, 0) FROM llm_history_entries WHERE session_id = ?1",
+                [session_id],
+                |row| row.get(0),
+            )
+            .map_err(db_err)?;
+        Self::insert_entries(&tx, session_id, next_seq, entries)?;
+        tx.commit().map_err(db_err)
+    }
+
+    fn replace(&self, session_id: &str, entries: &[HistoryEntry]) -> Result<(), Error> {
+        self.sweep.sweep(self.ttl, self)?;
+        let mut conn = self.conn.lock();
+        let tx = conn.transaction().map_err(db_err)?;
+        Self::delete_session(&tx, session_id)?;
+        Self::insert_entries(&tx, session_id, 0, entries)?;
+        tx.commit().map_err(db_err)
+    }
+
+    fn remove(&self, session_id: &str) -> Result<(), Error> {
+        let mut conn = self.conn.lock();
+        let tx = conn.transaction().map_err(db_err)?;
+        Self::delete_session(&tx, session_id)?;
+        tx.commit().map_err(db_err)
+    }
+
+    fn sessions(&self) -> Result<Vec<String>, Error> {
+        let conn = self.conn.lock();
+        let mut stmt = conn
+            .prepare(
+                "SELECT session_id FROM llm_history_sessions
+                 WHERE updated_at_ms >= ?1 ORDER BY session_id",
+            )
+            .map_err(db_err)?;
+        let rows = stmt
+            .query_map([self.cutoff_ms().unwrap_or(i64::MIN)], |row| {
+                row.get::<_, String>(0)
+            })
+            .map_err(db_err)?;
+        rows.collect::<Result<Vec<String>, _>>().map_err(db_err)
+    }
+
+    fn evict_expired(&self) -> Result<usize, Error> {
+        let Some(cutoff) = self.cutoff_ms() else {
+            return Ok(0);
+        };
+        let mut conn = self.conn.lock();
+        let tx = conn.transaction().map_err(db_err)?;
+        tx.execute(
+            "DELETE FROM llm_history_entries WHERE session_id IN
+             (SELECT session_id FROM llm_history_sessions WHERE updated_at_ms < ?1)",
+            [cutoff],
+        )
+        .map_err(db_err)?;
+        let evicted = tx
+            .execute(
+                "DELETE FROM llm_history_sessions WHERE updated_at_ms < ?1",
+                [cutoff],
+            )
+            .map_err(db_err)?;
+        tx.commit().map_err(db_err)?;
+        Ok(evicted)
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Summarisation
+// ---------------------------------------------------------------------------
+
+/// Token budget that triggers history compaction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct HistoryBudget {
+    /// Compact once the estimated history size exceeds this many
+    /// tokens (see [`estimate_tokens`]).
+    #[serde(alias = "maxTokens")]
+    pub max_tokens: usize,
+    /// Most recent user turns kept verbatim; everything older is
+    /// summarised. Default: `2`.
+    #[serde(default = "default_keep_turns", alias = "keepTurns")]
+    pub keep_turns: usize,
+}
+
+fn default_keep_turns() -> usize {
+    2
+}
+
+/// Condenses old turns when a session outgrows its [`HistoryBudget`].
+#[async_trait::async_trait]
+pub trait HistorySummarizer: Send + Sync {
+    /// Return the entries that replace `older` — a prefix of the
+    /// session's history ending on a user-turn boundary (it may start
+    /// with an earlier summary). Return an empty vec to drop it.
+    async fn summarize(
+        &self,
+        session_id: &str,
+        older: &[HistoryEntry],
+    ) -> Result<Vec<HistoryEntry>, Error>;
+}
+
+/// Summarizer that simply drops the old turns.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct TruncatingSummarizer;
+
+#[async_trait::async_trait]
+impl HistorySummarizer for TruncatingSummarizer {
+    async fn summarize(
+        &self,
+        _session_id: &str,
+        _older: &[HistoryEntry],
+    ) -> Result<Vec<HistoryEntry>, Error> {
+        Ok(Vec::new())
+    }
+}
+
+/// Rough token estimate for a run of history entries (~4 bytes of
+/// message JSON per token). Only used to decide when to compact.
+pub fn estimate_tokens(entries: &[HistoryEntry]) -> usize {
+    entries
+        .iter()
+        .map(|e| e.message.to_string().len().div_ceil(4))
+        .sum()
+}
+
+/// Split point for compaction: entries before it get summarised.
+/// Returns 0 when there is nothing old enough to summarise (re-
+/// summarising a lone summary gains nothing).
+pub(crate) fn compaction_split(entries: &[HistoryEntry], budget: &HistoryBudget) -> usize {
+    if estimate_tokens(entries) <= budget.max_tokens {
+        return 0;
+    }
+    let summaries = entries.iter().take_while(|e| e.is_summary()).count();
+    let split = window_start(entries, budget.keep_turns.max(1));
+    if split <= summaries {
+        0
+    } else {
+        split
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn turn(user: &str, reply: &str) -> Vec<HistoryEntry> {
+        vec![
+            HistoryEntry::user(user),
+            HistoryEntry::assistant_text(reply),
+        ]
+    }
+
+    fn exercise_store(store: &dyn HistoryStore) {
+        assert!(store.load("a").unwrap().is_empty());
+
+        store.append("a", &turn("hi", "hello")).unwrap();
+        store
+            .append(
+                "a",
+                &[
+                    HistoryEntry::user("weather?"),
+                    HistoryEntry::assistant_with_tool_calls(
+                        None,
+                        json!([{"id": "c0", "type": "function",
+                                "function": {"name": "weather", "arguments": "{}"}}]),
+                    ),
+                    HistoryEntry::tool_result("c0", "weather", "sunny"),
+                ],
+            )
+            .unwrap();
+        store.append("b", &turn("other", "session")).unwrap();
+
+        let a = store.load("a").unwrap();
+        let roles: Vec<&str> = a.iter().map(HistoryEntry::role).collect();
+        assert_eq!(
+            roles,
+            vec!["user", "assistant", "user", "assistant", "tool"]
+        );
+        assert_eq!(a[3].message["tool_calls"][0]["id"], "c0");
+        assert_eq!(a[4].message["content"], "sunny");
+        assert_eq!(store.sessions().unwrap(), vec!["a", "b"]);
+
+        store
+            .replace("a", &[HistoryEntry::summary("they said hi")])
+            .unwrap();
+        store.append("a", &turn("again", "yes")).unwrap();
+        let a = store.load("a").unwrap();
+        assert_eq!(a.len(), 3);
+        assert!(a[0].is_summary());
+
+        store.remove("a").unwrap();
+        assert!(store.load("a").unwrap().is_empty());
+        assert_eq!(store.sessions().unwrap(), vec!["b"]);
+        assert_eq!(store.evict_expired().unwrap(), 0);
+    }
+
+    fn exercise_ttl(store: &dyn HistoryStore) {
+        store.append("old", &turn("hi", "hello")).unwrap();
+        std::thread::sleep(Duration::from_millis(80));
+        store.append("fresh", &turn("hi", "hello")).unwrap();
+
+        assert_eq!(store.sessions().unwrap(), vec!["fresh"]);
+        // The write to `fresh` already swept out `old`, which nothing
+        // touched again.
+        assert_eq!(store.evict_expired().unwrap(), 0);
+        assert!(store.load("old").unwrap().is_empty());
+        assert_eq!(store.load("fresh").unwrap().len(), 2);
+
+        // Appending to an expired session starts it afresh.
+        std::thread::sleep(Duration::from_millis(80));
+        store
+            .append("fresh", &turn("back", "welcome back"))
+            .unwrap();
+        assert_eq!(store.load("fresh").unwrap().len(), 2);
+    }
+
+    #[test]
+    fn memory_store_round_trip() {
+        exercise_store(&InMemoryHistoryStore::new());
+    }
+
+    #[test]
+    fn sqlite_store_round_trip() {
+        exercise_store(&SqliteHistoryStore::open_in_memory().unwrap());
+    }
+
+    #[test]
+    fn memory_store_ttl() {
+        exercise_ttl(&InMemoryHistoryStore::new().with_ttl(Some(Duration::from_millis(50))));
+    }
+
+    #[test]
+    fn sqlite_store_ttl() {
+        exercise_ttl(
+            &SqliteHistoryStore::open_in_memory()
+                .unwrap()
+                .with_ttl(Some(Duration::from_millis(50))),
+        );
+    }
+
+    #[test]
+    fn sqlite_store_survives_reopen() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("history.db");
+
+        let config: HistoryStoreConfig = serde_json::from_value(json!({
+            "type": "sqlite",
+            "path": path.to_str().unwrap(),
+            "ttlSecs": 3600
+        }))
+        .unwrap();
+        config
+            .open()
+            .unwrap()
+            .append("s1", &turn("remember 42", "ok"))
+            .unwrap();
+
+        let reopened = config.open().unwrap();
+        let entries = reopened.load("s1").unwrap();
+        assert_eq!(entries[0].message["content"], "remember 42");
+        assert_eq!(entries[1].message["content"], "ok");
+    }
+
+    #[test]
+    fn compaction_split_keeps_recent_turns() {
+        let mut entries = Vec::new();
+        for i in 0..4 {
+            entries.extend(turn(&format!("question {}", i), &"x".repeat(200)));
+        }
+        let tight = HistoryBudget {
+            max_tokens: 10,
+            keep_turns: 1,
+        };
+        assert_eq!(compaction_split(&entries, &tight), 6);
+
+        let roomy = HistoryBudget {
+            max_tokens: 100_000,
+            keep_turns: 1,
+        };
+        assert_eq!(compaction_split(&entries, &roomy), 0);
+
+        let budget: HistoryBudget = serde_json::from_value(json!({"maxTokens": 500})).unwrap();
+        assert_eq!(budget.keep_turns, 2);
+    }
+}
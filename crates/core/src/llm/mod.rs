@@ -8,12 +8,13 @@
 //!
 //! Two layers:
 //!
-//! - [`ChatBackend`] — owns history, HTTP client, SSE parser, tool
-//!   dispatch.
+//! - [`ChatBackend`] — owns history (via a [`HistoryStore`]), HTTP
+//!   client, SSE parser, tool dispatch and the `return_value` tool
+//!   loop ([`ToolHandler`]).
 //! - [`ProviderProfile`] — vendor wire-shaping (endpoint URL, auth
-//!   header, request body, SSE chunk parsing). v1 ships
-//!   [`OpenAIProfile`]; Anthropic / Gemini land in step 5 of the
-//!   multimodal-LLM plan.
+//!   header, request body, SSE chunk parsing): [`OpenAIProfile`],
+//!   [`AnthropicProfile`] and [`GeminiProfile`], selected by
+//!   [`ProviderKind`].
 
 pub mod anthropic_profile;
 pub mod audio_encode;
@@ -21,15 +22,25 @@ pub mod chat_backend;
 pub mod data_url;
 pub mod gemini_profile;
 pub mod history;
+pub mod history_store;
 pub mod provider;
 pub mod tool_dispatch;
+pub mod tool_handler;
 
 pub use anthropic_profile::AnthropicProfile;
-pub use chat_backend::{ChatBackend, ChatBackendConfig};
+pub use chat_backend::{ChatBackend, ChatBackendConfig, DEFAULT_MAX_TOOL_ITERATIONS};
 pub use gemini_profile::GeminiProfile;
 pub use history::HistoryEntry;
+pub use history_store::{
+    HistoryBudget, HistoryStore, HistoryStoreConfig, HistorySummarizer, InMemoryHistoryStore,
+    SqliteHistoryStore, TruncatingSummarizer,
+};
 pub use provider::{ChatRequest, ChatStreamEvent, OpenAIProfile, ProviderProfile};
 pub use tool_dispatch::{dispatch_tool_call, ToolCallAccum};
+pub use tool_handler::{
+    register_global_tool_handler, tool_handler_fn, unregister_global_tool_handler,
+    HttpToolHandler, ToolHandler, ToolHandlerRegistry, ToolInvocation, ToolOutput,
+};
 
 use std::sync::Arc;
 
@@ -38,12 +49,14 @@ use std::sync::Arc;
 /// Picks the [`ProviderProfile`] the [`ChatBackend`] uses. Default
 /// is [`ProviderKind::OpenAI`] (cloud OpenAI, Azure, vLLM, modern
 /// llama.cpp, Ollama). [`ProviderKind::Anthropic`] targets the
-/// Messages API. Gemini exists as a stub but isn't selectable yet.
+/// Messages API, [`ProviderKind::Gemini`] the Generative Language
+/// API (`streamGenerateContent`).
 #[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize, schemars::JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum ProviderKind {
     OpenAI,
     Anthropic,
+    Gemini,
 }
 
 impl Default for ProviderKind {
@@ -58,6 +71,7 @@ impl ProviderKind {
         match self {
             ProviderKind::OpenAI => Arc::new(OpenAIProfile),
             ProviderKind::Anthropic => Arc::new(AnthropicProfile),
+            ProviderKind::Gemini => Arc::new(GeminiProfile::default()),
         }
     }
 
@@ -66,6 +80,50 @@ impl ProviderKind {
         match self {
             ProviderKind::OpenAI => "https://api.openai.com/v1",
             ProviderKind::Anthropic => "https://api.anthropic.com/v1",
+            ProviderKind::Gemini => "https://generativelanguage.googleapis.com/v1beta",
         }
     }
+
+    /// Default model when the node config leaves `model` unset.
+    pub fn default_model(self) -> &'static str {
+        match self {
+            ProviderKind::OpenAI => "gpt-4o-mini",
+            ProviderKind::Anthropic => "claude-3-5-haiku-latest",
+            ProviderKind::Gemini => "gemini-2.0-flash",
+        }
+    }
+
+    /// API-key environment variables for this vendor, in lookup order.
+    ///
+    /// Anthropic still falls back to `OPENAI_API_KEY`, which every
+    /// provider read before vendor keys existed. Gemini reads only its
+    /// own, so an OpenAI key is never sent to Google.
+    pub fn api_key_env(self) -> &'static [&'static str] {
+        match self {
+            ProviderKind::OpenAI => &["OPENAI_API_KEY"],
+            ProviderKind::Anthropic => &["ANTHROPIC_API_KEY", "OPENAI_API_KEY"],
+            ProviderKind::Gemini => &["GEMINI_API_KEY", "GOOGLE_API_KEY"],
+        }
+    }
+
+    /// Resolve an API key: explicit config → the vendor's env vars.
+    pub fn resolve_api_key(self, configured: Option<&str>) -> Option<String> {
+        self.resolve_api_key_with(configured, |name| std::env::var(name).ok())
+    }
+
+    /// [`Self::resolve_api_key`] reading variables through `env`.
+    pub fn resolve_api_key_with(
+        self,
+        configured: Option<&str>,
+        env: impl Fn(&str) -> Option<String>,
+    ) -> Option<String> {
+        configured
+            .filter(|s| !s.is_empty())
+            .map(str::to_string)
+            .or_else(|| {
+                self.api_key_env()
+                    .iter()
+                    .find_map(|name| env(name).filter(|s| !s.is_empty()))
+            })
+    }
 }
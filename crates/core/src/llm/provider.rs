@@ -5,9 +5,9 @@
 //! `ChatBackend` is otherwise modality- and vendor-agnostic — it just
 //! drives the profile and routes streaming events.
 //!
-//! v1 ships only [`OpenAIProfile`] (cloud OpenAI, Azure, vLLM, modern
-//! llama.cpp, Ollama). Anthropic and Gemini profiles will be added in
-//! follow-up steps and don't affect the trait surface.
+//! [`OpenAIProfile`] covers cloud OpenAI, Azure, vLLM, modern
+//! llama.cpp and Ollama; Anthropic and Gemini live in their own
+//! modules.
 
 use crate::nodes::tool_spec::{to_openai_tools_array, ToolSpec};
 use serde_json::Value;
@@ -69,8 +69,10 @@ pub trait ProviderProfile: Send + Sync {
     /// Vendor name for logs / tracing (e.g. `"openai"`).
     fn name(&self) -> &'static str;
 
-    /// Resolve the full POST URL for streaming chat completions.
-    fn endpoint(&self, base_url: &str) -> String;
+    /// Resolve the full POST URL for one chat request. Most vendors
+    /// ignore `model` and `streaming` (they travel in the body);
+    /// Gemini puts both in the path.
+    fn endpoint(&self, base_url: &str, model: &str, streaming: bool) -> String;
 
     /// Apply auth headers / bearer token. Most vendors take a single
     /// `Authorization: Bearer …` header; Anthropic differs.
@@ -89,6 +91,25 @@ pub trait ProviderProfile: Send + Sync {
     /// and `[DONE]` handling before calling — implementations parse a
     /// JSON value (or whatever the vendor sends).
     fn parse_sse_payload(&self, payload: &Value) -> Vec<ChatStreamEvent>;
+
+    /// Parse a non-streaming response body into the events a stream
+    /// would have produced, tool calls included. Defaults to the
+    /// chat-completions shape, whose `message` matches a stream `delta`
+    /// except that its tool calls carry no `index`.
+    fn parse_response(&self, body: &Value) -> Vec<ChatStreamEvent> {
+        let mut message = body["choices"][0]["message"].clone();
+        if let Some(calls) = message.get_mut("tool_calls").and_then(Value::as_array_mut) {
+            for (index, call) in calls.iter_mut().enumerate() {
+                if let Some(call) = call.as_object_mut() {
+                    call.insert("index".to_string(), Value::from(index));
+                }
+            }
+        }
+        let mut events =
+            self.parse_sse_payload(&serde_json::json!({ "choices": [{ "delta": message }] }));
+        events.push(ChatStreamEvent::Done);
+        events
+    }
 }
 
 // ---------------------------------------------------------------------------
@@ -108,7 +129,7 @@ impl ProviderProfile for OpenAIProfile {
         "openai"
     }
 
-    fn endpoint(&self, base_url: &str) -> String {
+    fn endpoint(&self, base_url: &str, _model: &str, _streaming: bool) -> String {
         format!("{}/chat/completions", base_url)
     }
 
@@ -205,11 +226,38 @@ mod tests {
     fn endpoint_appends_chat_completions() {
         let p = OpenAIProfile;
         assert_eq!(
-            p.endpoint("https://api.openai.com/v1"),
+            p.endpoint("https://api.openai.com/v1", "gpt-4o-mini", true),
             "https://api.openai.com/v1/chat/completions"
         );
     }
 
+    #[test]
+    fn parse_response_includes_tool_calls() {
+        let body = serde_json::json!({"choices": [{"message": {
+            "content": "On it.",
+            "tool_calls": [{"id": "call_a", "type": "function", "function": {
+                "name": "lookup_order", "arguments": "{\"id\":\"42\"}"
+            }}]
+        }}]});
+        let events = OpenAIProfile.parse_response(&body);
+        assert!(matches!(&events[0], ChatStreamEvent::VisibleText(t) if t == "On it."));
+        match &events[1] {
+            ChatStreamEvent::ToolCallDelta {
+                index,
+                id,
+                name,
+                arguments_chunk,
+            } => {
+                assert_eq!(*index, 0);
+                assert_eq!(id.as_deref(), Some("call_a"));
+                assert_eq!(name.as_deref(), Some("lookup_order"));
+                assert_eq!(arguments_chunk.as_deref(), Some(r#"{"id":"42"}"#));
+            }
+            other => panic!("expected a tool call, got {:?}", other),
+        }
+        assert!(matches!(events[2], ChatStreamEvent::Done));
+    }
+
     #[test]
     fn shape_request_omits_tools_when_empty() {
         let p = OpenAIProfile;
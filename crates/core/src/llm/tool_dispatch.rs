@@ -3,8 +3,8 @@
 //! Modality-agnostic: the LLM backend collects per-index `tool_calls`
 //! deltas as the SSE stream arrives, then dispatches each accumulated
 //! call here. Side-effect tools (`say`, `show`, …) emit tagged text on
-//! the data path; return-value tools are not yet implemented (would
-//! require a second-pass generation loop).
+//! the data path; return-value tools are executed by the backend's
+//! multi-pass loop (see [`crate::llm::tool_handler`]), not here.
 //!
 //! The OpenAI streaming protocol splits each tool call across many SSE
 //! chunks: the first chunk carries `function.name` and (optionally) an
@@ -34,7 +34,7 @@ pub struct ToolCallAccum {
 }
 
 /// Look up a registered tool spec by name.
-fn lookup_tool<'a>(registry: &'a [ToolSpec], name: &str) -> Option<&'a ToolSpec> {
+pub(crate) fn lookup_tool<'a>(registry: &'a [ToolSpec], name: &str) -> Option<&'a ToolSpec> {
     registry.iter().find(|t| t.name == name)
 }
 
@@ -50,7 +50,8 @@ fn lookup_tool<'a>(registry: &'a [ToolSpec], name: &str) -> Option<&'a ToolSpec>
 /// - `show` → emit `content` argument on the `ui` channel.
 /// - any other registered `side_effect` tool → log + drop. Generic
 ///   dispatch surface for user-provided handlers is future work.
-/// - `return_value` tools → log + drop (multi-pass not implemented).
+/// - `return_value` tools → ignored here; [`crate::llm::ChatBackend`]
+///   runs them through its [`crate::llm::ToolHandlerRegistry`].
 pub fn dispatch_tool_call<F>(
     registry: &[ToolSpec],
     call: &ToolCallAccum,
@@ -77,10 +78,9 @@ where
     };
 
     if spec.kind == ToolKind::ReturnValue {
-        tracing::warn!(
+        tracing::debug!(
             tool = %call.name,
-            "[llm] return_value tools require a second generation pass \
-             (not yet implemented in the streaming path); skipping"
+            "[llm] return_value tool is executed by the backend's handler loop; skipping"
         );
         return Ok(());
     }
@@ -0,0 +1,409 @@
+//! Executors for `return_value` tool calls.
+//!
+//! A [`ToolKind::ReturnValue`](crate::nodes::tool_spec::ToolKind) call
+//! is not consumed inline like `say` / `show`: the backend hands it to
+//! a [`ToolHandler`], appends the result as a `tool` history entry and
+//! re-generates so the model can use it (see
+//! [`crate::llm::ChatBackend::run`]).
+//!
+//! Handlers come from two places:
+//!
+//! - Rust: any `Arc<dyn ToolHandler>` registered on a node's
+//!   [`ToolHandlerRegistry`], or process-wide via
+//!   [`register_global_tool_handler`] so manifest-built nodes pick it
+//!   up by tool name.
+//! - The manifest: a [`ToolSpec::handler`] such as
+//!   `{"type": "http", "url": …}` builds an [`HttpToolHandler`]
+//!   webhook.
+//!
+//! Lookup order is node registry first, then the global registry.
+
+use crate::error::Error;
+use crate::nodes::tool_spec::{ToolHandlerSpec, ToolSpec};
+use parking_lot::RwLock;
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::{BTreeMap, HashMap};
+use std::sync::{Arc, OnceLock};
+use std::time::Duration;
+
+/// Default per-call timeout for [`HttpToolHandler`].
+const DEFAULT_WEBHOOK_TIMEOUT_MS: u64 = 10_000;
+
+/// Process-wide handlers, consulted after a node's own registry.
+static GLOBAL_HANDLERS: OnceLock<RwLock<HashMap<String, Arc<dyn ToolHandler>>>> = OnceLock::new();
+
+fn global_handlers() -> &'static RwLock<HashMap<String, Arc<dyn ToolHandler>>> {
+    GLOBAL_HANDLERS.get_or_init(|| RwLock::new(HashMap::new()))
+}
+
+/// One `return_value` tool call handed to a [`ToolHandler`].
+///
+/// Also the JSON body [`HttpToolHandler`] POSTs to its webhook.
+#[derive(Debug, Clone, Serialize)]
+pub struct ToolInvocation {
+    pub session_id: String,
+    /// Tool-call id the result is paired with in history.
+    pub call_id: String,
+    pub name: String,
+    /// Parsed arguments object (empty object if the model sent
+    /// unparseable JSON).
+    pub arguments: Value,
+}
+
+/// Executes `return_value` tool calls.
+///
+/// The returned value is fed back to the model as the tool result:
+/// strings verbatim, anything else as JSON text. An `Err` is reported
+/// to the model as `{"error": "…"}` so it can recover in its reply,
+/// and flagged on the [`ToolOutput`].
+#[async_trait::async_trait]
+pub trait ToolHandler: Send + Sync {
+    async fn call(&self, invocation: &ToolInvocation) -> Result<Value, Error>;
+}
+
+/// Wrap a synchronous closure as a handler — convenient for lookups
+/// that don't need to await.
+pub fn tool_handler_fn<F>(f: F) -> Arc<dyn ToolHandler>
+where
+    F: Fn(&ToolInvocation) -> Result<Value, Error> + Send + Sync + 'static,
+{
+    Arc::new(FnToolHandler(f))
+}
+
+struct FnToolHandler<F>(F);
+
+#[async_trait::async_trait]
+impl<F> ToolHandler for FnToolHandler<F>
+where
+    F: Fn(&ToolInvocation) -> Result<Value, Error> + Send + Sync,
+{
+    async fn call(&self, invocation: &ToolInvocation) -> Result<Value, Error> {
+        (self.0)(invocation)
+    }
+}
+
+/// Register a handler for every node in the process that declares a
+/// `return_value` tool called `name`. Replaces any previous handler.
+pub fn register_global_tool_handler(name: impl Into<String>, handler: Arc<dyn ToolHandler>) {
+    global_handlers().write().insert(name.into(), handler);
+}
+
+/// Remove a handler registered with [`register_global_tool_handler`].
+pub fn unregister_global_tool_handler(name: &str) -> Option<Arc<dyn ToolHandler>> {
+    global_handlers().write().remove(name)
+}
+
+/// Tool-name → handler map owned by one LLM node.
+///
+/// Cheap to clone (handlers are `Arc`s), so it travels inside
+/// [`crate::llm::ChatBackendConfig`].
+#[derive(Clone, Default)]
+pub struct ToolHandlerRegistry {
+    handlers: HashMap<String, Arc<dyn ToolHandler>>,
+}
+
+impl std::fmt::Debug for ToolHandlerRegistry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut names: Vec<&str> = self.handlers.keys().map(String::as_str).collect();
+        names.sort_unstable();
+        f.debug_struct("ToolHandlerRegistry")
+            .field("handlers", &names)
+            .finish()
+    }
+}
+
+impl ToolHandlerRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Build handlers for every spec that declares one in the manifest.
+    pub fn from_specs(specs: &[ToolSpec]) -> Result<Self, Error> {
+        let mut registry = Self::new();
+        for spec in specs {
+            if let Some(ref handler) = spec.handler {
+                let handler = HttpToolHandler::from_spec(handler)
+                    .map_err(|e| Error::ConfigError(format!("tool '{}': {}", spec.name, e)))?;
+                registry.register(spec.name.clone(), Arc::new(handler));
+            }
+        }
+        Ok(registry)
+    }
+
+    /// Register (or replace) the handler for `name`.
+    pub fn register(&mut self, name: impl Into<String>, handler: Arc<dyn ToolHandler>) {
+        self.handlers.insert(name.into(), handler);
+    }
+
+    /// Builder form of [`Self::register`].
+    pub fn with_handler(mut self, name: impl Into<String>, handler: Arc<dyn ToolHandler>) -> Self {
+        self.register(name, handler);
+        self
+    }
+
+    /// Resolve the handler for `name`: this registry, then the global
+    /// one.
+    pub fn resolve(&self, name: &str) -> Option<Arc<dyn ToolHandler>> {
+        self.handlers
+            .get(name)
+            .cloned()
+            .or_else(|| global_handlers().read().get(name).cloned())
+    }
+}
+
+/// Result of one `return_value` tool call.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ToolOutput {
+    /// Tool-result message content fed back to the model.
+    pub content: String,
+    /// The call failed (no handler, or the handler returned `Err`);
+    /// `content` is then an `{"error": …}` object.
+    pub is_error: bool,
+}
+
+impl ToolOutput {
+    fn ok(content: String) -> Self {
+        Self {
+            content,
+            is_error: false,
+        }
+    }
+
+    fn error(message: String) -> Self {
+        Self {
+            content: serde_json::json!({ "error": message }).to_string(),
+            is_error: true,
+        }
+    }
+}
+
+/// Run one invocation and render the tool-result message content.
+///
+/// Never fails: a missing handler or handler error becomes an
+/// `{"error": …}` result the model can see, flagged with
+/// [`ToolOutput::is_error`].
+pub async fn execute_tool_call(
+    registry: &ToolHandlerRegistry,
+    invocation: &ToolInvocation,
+) -> ToolOutput {
+    let Some(handler) = registry.resolve(&invocation.name) else {
+        tracing::warn!(
+            tool = %invocation.name,
+            "[llm] return_value tool has no registered handler; reporting error to model"
+        );
+        return ToolOutput::error(format!(
+            "no handler registered for tool '{}'",
+            invocation.name
+        ));
+    };
+
+    match handler.call(invocation).await {
+        Ok(Value::String(s)) => ToolOutput::ok(s),
+        Ok(other) => ToolOutput::ok(other.to_string()),
+        Err(e) => {
+            tracing::warn!(
+                tool = %invocation.name,
+                error = %e,
+                "[llm] return_value tool handler failed; reporting error to model"
+            );
+            ToolOutput::error(e.to_string())
+        }
+    }
+}
+
+/// Webhook handler: POSTs the [`ToolInvocation`] as JSON and returns
+/// the response body (parsed as JSON when possible, else text).
+pub struct HttpToolHandler {
+    client: reqwest::Client,
+    url: String,
+    headers: BTreeMap<String, String>,
+}
+
+impl HttpToolHandler {
+    pub fn new(
+        url: impl Into<String>,
+        headers: BTreeMap<String, String>,
+        timeout: Duration,
+    ) -> Result<Self, Error> {
+        let url = url.into();
+        reqwest::Url::parse(&url)
+            .map_err(|e| Error::ConfigError(format!("invalid webhook url '{}': {}", url, e)))?;
+        let client = reqwest::Client::builder()
+            .timeout(timeout)
+            .build()
+            .map_err(|e| Error::ConfigError(format!("webhook client: {}", e)))?;
+        Ok(Self {
+            client,
+            url,
+            headers,
+        })
+    }
+
+    pub fn from_spec(spec: &ToolHandlerSpec) -> Result<Self, Error> {
+        match spec {
+            ToolHandlerSpec::Http {
+                url,
+                headers,
+                timeout_ms,
+            } => Self::new(
+                url.clone(),
+                headers.clone(),
+                Duration::from_millis(timeout_ms.unwrap_or(DEFAULT_WEBHOOK_TIMEOUT_MS)),
+            ),
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl ToolHandler for HttpToolHandler {
+    async fn call(&self, invocation: &ToolInvocation) -> Result<Value, Error> {
+        let mut req = self.client.post(&self.url).json(invocation);
+        for (k, v) in &self.headers {
+            req = req.header(k.as_str(), v.as_str());
+        }
+        let response = req
+            .send()
+            .await
+            .map_err(|e| Error::Execution(format!("tool webhook request failed: {}", e)))?;
+
+        let status = response.status();
+        let body = response
+            .text()
+            .await
+            .map_err(|e| Error::Execution(format!("tool webhook read error: {}", e)))?;
+        if !status.is_success() {
+            return Err(Error::Execution(format!(
+                "tool webhook returned {}: {}",
+                status, body
+            )));
+        }
+        Ok(serde_json::from_str(&body).unwrap_or(Value::String(body)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn invocation(name: &str) -> ToolInvocation {
+        ToolInvocation {
+            session_id: "s1".into(),
+            call_id: "call_0".into(),
+            name: name.into(),
+            arguments: json!({"id": "42"}),
+        }
+    }
+
+    #[tokio::test]
+    async fn registry_prefers_node_handler_over_global() {
+        register_global_tool_handler(
+            "registry_test_tool",
+            tool_handler_fn(|_| Ok(json!("global"))),
+        );
+        register_global_tool_handler(
+            "registry_test_global_only",
+            tool_handler_fn(|_| Ok(json!({"from": "global"}))),
+        );
+        let registry = ToolHandlerRegistry::new().with_handler(
+            "registry_test_tool",
+            tool_handler_fn(|inv| Ok(json!(format!("local {}", inv.arguments["id"])))),
+        );
+
+        let local = execute_tool_call(&registry, &invocation("registry_test_tool")).await;
+        assert_eq!(local.content, "local \"42\"");
+        assert!(!local.is_error);
+        let global = execute_tool_call(&registry, &invocation("registry_test_global_only")).await;
+        assert_eq!(global.content, r#"{"from":"global"}"#);
+
+        unregister_global_tool_handler("registry_test_tool");
+        unregister_global_tool_handler("registry_test_global_only");
+        let missing = execute_tool_call(&registry, &invocation("registry_test_global_only")).await;
+        assert!(missing.is_error);
+        assert!(
+            missing.content.contains("no handler registered"),
+            "{}",
+            missing.content
+        );
+    }
+
+    #[tokio::test]
+    async fn handler_error_is_reported_to_model() {
+        let registry = ToolHandlerRegistry::new().with_handler(
+            "fails",
+            tool_handler_fn(|_| Err(Error::Execution("backend down".into()))),
+        );
+        let output = execute_tool_call(&registry, &invocation("fails")).await;
+        assert!(output.is_error);
+        let parsed: Value = serde_json::from_str(&output.content).unwrap();
+        assert!(parsed["error"].as_str().unwrap().contains("backend down"));
+    }
+
+    #[tokio::test]
+    async fn http_handler_posts_invocation() {
+        use axum::extract::Json;
+        use axum::http::HeaderMap;
+
+        let seen: Arc<parking_lot::Mutex<Option<(Value, String)>>> = Default::default();
+        let seen_srv = seen.clone();
+        let app = axum::Router::new().route(
+            "/hook",
+            axum::routing::post(move |headers: HeaderMap, Json(body): Json<Value>| {
+                let seen = seen_srv.clone();
+                async move {
+                    let auth = headers
+                        .get("authorization")
+                        .and_then(|v| v.to_str().ok())
+                        .unwrap_or_default()
+                        .to_string();
+                    *seen.lock() = Some((body, auth));
+                    axum::Json(json!({"status": "shipped"}))
+                }
+            }),
+        );
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            axum::serve(listener, app).await.unwrap();
+        });
+
+        let spec: ToolSpec = serde_json::from_value(json!({
+            "name": "lookup_order",
+            "description": "",
+            "parameters": {"type": "object"},
+            "kind": "return_value",
+            "handler": {
+                "type": "http",
+                "url": format!("http://{}/hook", addr),
+                "headers": {"Authorization": "Bearer t"}
+            }
+        }))
+        .unwrap();
+        let registry = ToolHandlerRegistry::from_specs(&[spec]).unwrap();
+
+        let output = execute_tool_call(&registry, &invocation("lookup_order")).await;
+        assert_eq!(output.content, r#"{"status":"shipped"}"#);
+        assert!(!output.is_error);
+
+        let (body, auth) = seen.lock().take().unwrap();
+        assert_eq!(body["name"], "lookup_order");
+        assert_eq!(body["call_id"], "call_0");
+        assert_eq!(body["session_id"], "s1");
+        assert_eq!(body["arguments"]["id"], "42");
+        assert_eq!(auth, "Bearer t");
+    }
+
+    #[test]
+    fn from_specs_rejects_bad_url() {
+        let spec: ToolSpec = serde_json::from_value(json!({
+            "name": "broken",
+            "description": "",
+            "parameters": {"type": "object"},
+            "kind": "return_value",
+            "handler": {"type": "http", "url": "not a url"}
+        }))
+        .unwrap();
+        assert!(ToolHandlerRegistry::from_specs(&[spec]).is_err());
+    }
+}
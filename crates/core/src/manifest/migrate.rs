@@ -0,0 +1,339 @@
+//! Manifest schema versions and the v1 → v2 migrator.
+//!
+//! Both versions deserialize into the same [`Manifest`]; `v2` only adds
+//! fields ([`NodePorts`](super::NodePorts), [`EdgeOptions`](super::EdgeOptions),
+//! node `resources`) and renames node `capabilities` to `resources`.
+//! [`validate`](super::validate) enforces which fields each version may use.
+//!
+//! Migration works on the manifest *document*, before any templating is
+//! expanded, so `${ENV}` references, `template:` sections and includes are
+//! carried over untouched and key order is preserved. A v1 document is
+//! upgraded by:
+//!
+//! - renaming each node's `capabilities` to `resources`
+//! - declaring, on every node that uses named ports in a connection,
+//!   exactly the ports those connections use
+//! - setting `version: v2`
+//!
+//! Nothing else changes, so the upgraded manifest describes the same
+//! pipeline.
+
+use super::Manifest;
+use crate::{Error, Result};
+use serde_yaml::{Mapping, Value};
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::Path;
+
+/// Version new manifests should declare
+pub const CURRENT_VERSION: &str = "v2";
+
+/// Versions [`validate`](super::validate) accepts
+pub const SUPPORTED_VERSIONS: &[&str] = &["v1", CURRENT_VERSION];
+
+/// Serialization format of a manifest file
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ManifestFormat {
+    Json,
+    Yaml,
+}
+
+impl ManifestFormat {
+    /// `.yaml` / `.yml` are YAML, anything else JSON
+    pub fn from_path(path: impl AsRef<Path>) -> Self {
+        match path.as_ref().extension().and_then(|e| e.to_str()) {
+            Some("yaml" | "yml") => Self::Yaml,
+            _ => Self::Json,
+        }
+    }
+}
+
+/// What a migration did
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MigrationReport {
+    /// Version before migrating
+    pub from: String,
+    /// Version after migrating
+    pub to: String,
+    /// One human-readable line per edit
+    pub changes: Vec<String>,
+}
+
+impl MigrationReport {
+    /// The document was already current
+    pub fn is_noop(&self) -> bool {
+        self.from == self.to
+    }
+}
+
+/// Upgrade a manifest document to [`CURRENT_VERSION`] in place
+///
+/// Current documents are left alone (the report is a no-op). Unknown
+/// versions are an error.
+pub fn migrate_document(document: &mut Value) -> Result<MigrationReport> {
+    let root = document
+        .as_mapping_mut()
+        .ok_or_else(|| Error::Manifest("Manifest must be an object".to_string()))?;
+    let from = root
+        .get("version")
+        .and_then(Value::as_str)
+        .ok_or_else(|| Error::Manifest("Manifest has no `version`".to_string()))?
+        .to_string();
+    match from.as_str() {
+        CURRENT_VERSION => {
+            return Ok(MigrationReport {
+                to: from.clone(),
+                from,
+                changes: Vec::new(),
+            })
+        }
+        "v1" => {}
+        other => {
+            return Err(Error::Manifest(format!(
+                "Unsupported manifest version: {}",
+                other
+            )))
+        }
+    }
+
+    let ports_used = ports_used(root);
+    let mut changes = Vec::new();
+    if let Some(nodes) = root.get_mut("nodes").and_then(Value::as_sequence_mut) {
+        for node in nodes.iter_mut().filter_map(Value::as_mapping_mut) {
+            let id = node
+                .get("id")
+                .and_then(Value::as_str)
+                .unwrap_or_default()
+                .to_string();
+
+            if node.contains_key("capabilities") {
+                if node.contains_key("resources") {
+                    return Err(Error::Manifest(format!(
+                        "Node '{}' has both `capabilities` and `resources`",
+                        id
+                    )));
+                }
+                rename_key(node, "capabilities", "resources");
+                changes.push(format!(
+                    "node '{}': renamed `capabilities` to `resources`",
+                    id
+                ));
+            }
+
+            // Include entries are migrated in their own file.
+            if node.contains_key("include") || node.contains_key("ports") {
+                continue;
+            }
+            if let Some((outputs, inputs)) = ports_used.get(&id) {
+                let mut ports = Mapping::new();
+                for (key, names) in [("inputs", inputs), ("outputs", outputs)] {
+                    if !names.is_empty() {
+                        let names = names.iter().map(|p| Value::from(p.as_str())).collect();
+                        ports.insert(key.into(), Value::Sequence(names));
+                    }
+                }
+                node.insert("ports".into(), Value::Mapping(ports));
+                changes.push(format!(
+                    "node '{}': declared ports (inputs: [{}], outputs: [{}])",
+                    id,
+                    join(inputs),
+                    join(outputs)
+                ));
+            }
+        }
+    }
+
+    root.insert("version".into(), CURRENT_VERSION.into());
+    Ok(MigrationReport {
+        from,
+        to: CURRENT_VERSION.to_string(),
+        changes,
+    })
+}
+
+/// Upgrade manifest source text, keeping its format
+///
+/// Returns the rewritten text (unchanged when already current) and the
+/// report. YAML comments are not preserved.
+pub fn migrate_source(source: &str, format: ManifestFormat) -> Result<(String, MigrationReport)> {
+    let parse_error =
+        |e: &dyn std::fmt::Display| Error::Manifest(format!("Failed to parse manifest: {}", e));
+    // `serde_yaml::Value` keeps mapping order for JSON input too.
+    let mut document: Value = match format {
+        ManifestFormat::Json => serde_json::from_str(source).map_err(|e| parse_error(&e))?,
+        ManifestFormat::Yaml => serde_yaml::from_str(source).map_err(|e| parse_error(&e))?,
+    };
+    let report = migrate_document(&mut document)?;
+    if report.is_noop() {
+        return Ok((source.to_string(), report));
+    }
+
+    let write_error =
+        |e: &dyn std::fmt::Display| Error::Manifest(format!("Failed to serialize manifest: {}", e));
+    let migrated = match format {
+        ManifestFormat::Json => {
+            serde_json::to_string_pretty(&document).map_err(|e| write_error(&e))? + "\n"
+        }
+        ManifestFormat::Yaml => serde_yaml::to_string(&document).map_err(|e| write_error(&e))?,
+    };
+    Ok((migrated, report))
+}
+
+/// Upgrade an already-parsed manifest to [`CURRENT_VERSION`]
+pub fn upgrade(manifest: &Manifest) -> Result<Manifest> {
+    let mut document = serde_yaml::to_value(manifest)
+        .map_err(|e| Error::Manifest(format!("Failed to serialize manifest: {}", e)))?;
+    migrate_document(&mut document)?;
+    serde_yaml::from_value(document)
+        .map_err(|e| Error::Manifest(format!("Failed to parse manifest: {}", e)))
+}
+
+/// Named (non-`main`) ports each node uses in connections: `(outputs, inputs)`
+fn ports_used(root: &Mapping) -> BTreeMap<String, (BTreeSet<String>, BTreeSet<String>)> {
+    let mut used: BTreeMap<String, (BTreeSet<String>, BTreeSet<String>)> = BTreeMap::new();
+    let connections = root
+        .get("connections")
+        .and_then(Value::as_sequence)
+        .into_iter()
+        .flatten()
+        .filter_map(Value::as_mapping);
+    for conn in connections {
+        let str_field = |key: &str| conn.get(key).and_then(Value::as_str);
+        if let (Some(from), Some(port)) = (str_field("from"), str_field("from_port")) {
+            if port != super::MAIN_PORT {
+                let entry = used.entry(from.to_string()).or_default();
+                entry.0.insert(port.to_string());
+            }
+        }
+        if let (Some(to), Some(port)) = (str_field("to"), str_field("to_port")) {
+            if port != super::MAIN_PORT {
+                let entry = used.entry(to.to_string()).or_default();
+                entry.1.insert(port.to_string());
+            }
+        }
+    }
+    used
+}
+
+/// Rename `from` to `to` without moving the entry
+fn rename_key(map: &mut Mapping, from: &str, to: &str) {
+    *map = std::mem::take(map)
+        .into_iter()
+        .map(|(k, v)| {
+            if k.as_str() == Some(from) {
+                (to.into(), v)
+            } else {
+                (k, v)
+            }
+        })
+        .collect();
+}
+
+fn join(ports: &BTreeSet<String>) -> String {
+    ports.iter().cloned().collect::<Vec<_>>().join(", ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::manifest::{validate, NodePorts};
+
+    const V1: &str = r#"{
+  "version": "v1",
+  "metadata": { "name": "ported" },
+  "nodes": [
+    {
+      "id": "vad",
+      "node_type": "SileroVADNode",
+      "params": { "threshold": "${VAD_THRESHOLD:-0.5}" },
+      "capabilities": { "cpu": { "cores": 2 } }
+    },
+    { "id": "correlator", "node_type": "Correlator", "params": {} }
+  ],
+  "connections": [
+    { "from": "vad", "from_port": "events", "to": "correlator", "to_port": "alerts" },
+    { "from": "vad", "to": "correlator" }
+  ]
+}"#;
+
+    #[test]
+    fn test_migrate_v1_source() {
+        let (migrated, report) = migrate_source(V1, ManifestFormat::Json).unwrap();
+        assert_eq!((report.from.as_str(), report.to.as_str()), ("v1", "v2"));
+        assert_eq!(report.changes.len(), 3, "{:?}", report.changes);
+
+        // Key order and unexpanded references survive
+        assert!(migrated
+            .trim_start()
+            .starts_with("{\n  \"version\": \"v2\""));
+        assert!(migrated.contains("${VAD_THRESHOLD:-0.5}"));
+
+        let document: serde_json::Value = serde_json::from_str(&migrated).unwrap();
+        assert_eq!(document["nodes"][0]["resources"]["cpu"]["cores"], 2);
+        assert!(document["nodes"][0].get("capabilities").is_none());
+        assert_eq!(
+            document["nodes"][0]["ports"],
+            serde_json::json!({ "outputs": ["events"] })
+        );
+        assert_eq!(
+            document["nodes"][1]["ports"],
+            serde_json::json!({ "inputs": ["alerts"] })
+        );
+
+        // Migrating again is a no-op
+        let (again, report) = migrate_source(&migrated, ManifestFormat::Json).unwrap();
+        assert!(report.is_noop());
+        assert_eq!(again, migrated);
+    }
+
+    #[test]
+    fn test_migrate_yaml_keeps_includes() {
+        let source = "version: v1\nmetadata:\n  name: agent\nnodes:\n- id: asr\n  include: ./asr.yaml\n  args:\n    model: ${STT_MODEL}\nconnections: []\n";
+        let (migrated, report) = migrate_source(source, ManifestFormat::Yaml).unwrap();
+        assert!(report.changes.is_empty());
+
+        let before: Value = serde_yaml::from_str(source).unwrap();
+        let mut after: Value = serde_yaml::from_str(&migrated).unwrap();
+        assert_eq!(after["version"], "v2");
+        after["version"] = "v1".into();
+        assert_eq!(after, before);
+    }
+
+    #[test]
+    fn test_upgrade_is_lossless() {
+        let original = crate::manifest::parse(V1).unwrap();
+        validate(&original).unwrap();
+
+        let upgraded = upgrade(&original).unwrap();
+        validate(&upgraded).unwrap();
+        assert_eq!(upgraded.version, CURRENT_VERSION);
+        assert_eq!(upgraded.connections, original.connections);
+        assert_eq!(
+            upgraded.nodes[0]
+                .resource_requirements()
+                .unwrap()
+                .cpu
+                .as_ref()
+                .unwrap()
+                .cores,
+            Some(2)
+        );
+        assert_eq!(
+            upgraded.nodes[1].ports,
+            Some(NodePorts {
+                inputs: vec!["alerts".to_string()],
+                outputs: vec![],
+            })
+        );
+        assert_eq!(upgraded.nodes[0].params, original.nodes[0].params);
+    }
+
+    #[test]
+    fn test_unknown_version_is_rejected() {
+        let mut document: Value = serde_yaml::from_str("version: v9\nnodes: []").unwrap();
+        assert!(migrate_document(&mut document).is_err());
+
+        let mut conflicting = crate::manifest::parse(V1).unwrap();
+        conflicting.nodes[0].resources = conflicting.nodes[0].capabilities.clone();
+        assert!(upgrade(&conflicting).is_err());
+    }
+}
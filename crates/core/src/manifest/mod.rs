@@ -0,0 +1,965 @@
+//! Pipeline manifest parsing and validation
+//!
+//! This module handles JSON manifest parsing, schema validation,
+//! and conversion to internal pipeline representations. Manifest files
+//! can be templated (`${ENV}` substitution, includes, typed arguments);
+//! see [`template`].
+//!
+//! Two schema versions are accepted. `v2` adds declared node ports,
+//! per-connection [`EdgeOptions`] and node `resources` (the v1
+//! `capabilities` field, renamed so it stops colliding with media
+//! capabilities); [`migrate`] upgrades v1 manifests losslessly.
+//!
+//! Schema specification: ../schemas/manifest.v1.json
+
+pub mod migrate;
+pub mod template;
+
+pub use migrate::{CURRENT_VERSION, SUPPORTED_VERSIONS};
+pub use template::ManifestLoader;
+
+use crate::capabilities::MediaCapabilities;
+use crate::executor::edge_filter::EdgeFilter;
+use crate::{Error, Result};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+/// Pipeline manifest structure (v1 and v2)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Manifest {
+    /// Schema version (`v1` or `v2`)
+    pub version: String,
+
+    /// Pipeline metadata
+    pub metadata: ManifestMetadata,
+
+    /// List of nodes in the pipeline
+    pub nodes: Vec<NodeManifest>,
+
+    /// Connections between nodes
+    pub connections: Vec<Connection>,
+
+    /// Python environment configuration for managed venvs.
+    /// Parsed regardless of feature flags; ignored at runtime when `bundled-uv` is not enabled.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub python_env: Option<ManifestPythonEnv>,
+}
+
+impl Manifest {
+    /// Nodes that only serve as another node's fallback
+    ///
+    /// Standby nodes are not part of the pipeline graph. Fails when a
+    /// fallback is unknown, is the node itself, appears in a connection
+    /// or declares a fallback of its own.
+    pub fn standby_nodes(&self) -> Result<std::collections::HashSet<&str>> {
+        let mut standby = std::collections::HashSet::new();
+        for node in &self.nodes {
+            let Some(fallback) = node.fallback() else {
+                continue;
+            };
+            let Some(target) = self.nodes.iter().find(|n| n.id == fallback) else {
+                return Err(Error::Manifest(format!(
+                    "Node '{}' declares unknown fallback node '{}'",
+                    node.id, fallback
+                )));
+            };
+            if target.id == node.id {
+                return Err(Error::Manifest(format!(
+                    "Node '{}' cannot be its own fallback",
+                    node.id
+                )));
+            }
+            if target.fallback().is_some() {
+                return Err(Error::Manifest(format!(
+                    "Fallback node '{}' cannot declare a fallback of its own",
+                    target.id
+                )));
+            }
+            if self
+                .connections
+                .iter()
+                .any(|c| c.from == target.id || c.to == target.id)
+            {
+                return Err(Error::Manifest(format!(
+                    "Fallback node '{}' must not appear in connections; it inherits those of '{}'",
+                    target.id, node.id
+                )));
+            }
+            standby.insert(target.id.as_str());
+        }
+        Ok(standby)
+    }
+}
+
+/// Python environment settings declared at the manifest level.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ManifestPythonEnv {
+    /// Desired Python version (e.g. "3.11").
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub python_version: Option<String>,
+
+    /// Environment scope override.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub scope: Option<crate::python::env_manager::EnvScope>,
+
+    /// Extra dependencies added to all nodes in this pipeline.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub extra_deps: Vec<String>,
+}
+
+/// Pipeline metadata
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ManifestMetadata {
+    /// Pipeline name
+    #[serde(default)]
+    pub name: String,
+
+    /// Optional description
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+
+    /// Creation timestamp
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub created_at: Option<String>,
+
+    /// Enable automatic capability negotiation (spec 022, FR-014).
+    ///
+    /// When true, the system automatically inserts conversion nodes
+    /// to resolve capability mismatches. When false, mismatches result
+    /// in validation errors.
+    #[serde(default)]
+    pub auto_negotiate: bool,
+}
+
+/// Node manifest entry
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct NodeManifest {
+    /// Unique node ID within pipeline
+    pub id: String,
+
+    /// Node type (e.g., "AudioSource", "HFPipelineNode")
+    pub node_type: String,
+
+    /// Node-specific parameters
+    #[serde(default)]
+    pub params: serde_json::Value,
+
+    /// Whether this is a streaming node (async generator process method)
+    #[serde(default)]
+    pub is_streaming: bool,
+
+    /// Whether this node should stream outputs to the client (spec 021, User Story 3)
+    ///
+    /// By default, only terminal nodes (sinks - nodes with no outputs) send data to
+    /// the client. Setting `is_output_node: true` allows intermediate nodes to also
+    /// stream their outputs to the client alongside terminal nodes.
+    ///
+    /// Use cases:
+    /// - Debugging: see intermediate processing results
+    /// - Monitoring: track VAD results while also getting final transcription
+    /// - Branching: receive outputs from multiple stages of the pipeline
+    #[serde(default)]
+    pub is_output_node: bool,
+
+    /// Optional capability requirements (GPU, CPU, memory)
+    ///
+    /// v1 only; v2 manifests use [`resources`](Self::resources).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub capabilities: Option<CapabilityRequirements>,
+
+    /// Resource hints (GPU, CPU, memory) for placement (v2)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub resources: Option<CapabilityRequirements>,
+
+    /// Named ports this node exposes besides `main` (v2)
+    ///
+    /// When declared, connections may only use these ports. Useful for
+    /// nodes without a registered schema (e.g. Python nodes), whose ports
+    /// are otherwise unchecked.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ports: Option<NodePorts>,
+
+    /// Media format capabilities for input/output constraints (spec 022).
+    ///
+    /// Declares what media formats this node accepts as input and produces
+    /// as output. Used for capability negotiation and pipeline validation.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub media_capabilities: Option<MediaCapabilities>,
+
+    /// Optional execution host
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub host: Option<String>,
+
+    /// Optional runtime hint (Phase 1.10.5)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub runtime_hint: Option<RuntimeHint>,
+
+    /// Execution placement (Phase 1.3.6 - capability-aware execution)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub execution: Option<ExecutionMetadata>,
+
+    /// Docker configuration (integrated into multiprocess system)
+    #[cfg(feature = "docker")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub docker: Option<crate::python::multiprocess::docker_support::DockerNodeConfig>,
+
+    /// Use low-latency fast path execution (spec 026)
+    ///
+    /// When enabled, the node uses `execute_streaming_node_fast()` which provides:
+    /// - Lock-free circuit breaker check (atomic read only)
+    /// - No timeout wrapper (avoids tokio timer overhead)
+    /// - No HDR histogram metrics (just atomic sum/count/min/max)
+    /// - try_acquire() for semaphore (non-blocking when permits available)
+    ///
+    /// Target overhead: <100ns vs ~250ns for full path.
+    ///
+    /// Recommended for:
+    /// - Audio/video transforms that are CPU-bound and fast (<1ms)
+    /// - High-frequency nodes (>100 calls/sec)
+    /// - Nodes where timeout protection isn't critical
+    ///
+    /// NOT recommended for:
+    /// - External API calls (need timeout protection)
+    /// - Nodes that may hang (need circuit breaker full features)
+    /// - Nodes requiring detailed latency percentiles (P50/P95/P99)
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub fast_path: bool,
+
+    /// Python package dependencies for this node (override/extend node-declared deps).
+    /// Used by the managed Python environment system to provision venvs.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub python_deps: Option<Vec<String>>,
+
+    /// Restart policy for the node's worker process (Python nodes only).
+    /// Defaults to the executor's `restart_policy`, which never restarts.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub restart: Option<crate::python::multiprocess::RestartPolicy>,
+}
+
+impl NodeManifest {
+    /// Resource requirements, from `resources` (v2) or `capabilities` (v1)
+    pub fn resource_requirements(&self) -> Option<&CapabilityRequirements> {
+        self.resources.as_ref().or(self.capabilities.as_ref())
+    }
+
+    /// Declared placement, `auto` when the node has no `execution` entry
+    pub fn placement(&self) -> Placement {
+        self.execution
+            .as_ref()
+            .map_or(Placement::Auto, |execution| execution.placement)
+    }
+
+    /// ID of the node that stands in for this one
+    pub fn fallback(&self) -> Option<&str> {
+        self.execution.as_ref()?.fallback.as_deref()
+    }
+
+    /// Whether the node executes on another host
+    pub fn is_remote(&self) -> bool {
+        self.host.is_some() || self.node_type == "RemotePipelineNode"
+    }
+}
+
+/// Named ports declared by a node (v2)
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct NodePorts {
+    /// Auxiliary input ports
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub inputs: Vec<String>,
+
+    /// Output ports
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub outputs: Vec<String>,
+}
+
+impl NodePorts {
+    fn declares(ports: &[String], port: &str) -> bool {
+        port == MAIN_PORT || ports.iter().any(|p| p == port)
+    }
+}
+
+/// Runtime hint for Python node execution (Phase 1.10.5)
+///
+/// Specifies which Python runtime to use for executing the node.
+/// This allows fine-grained control over runtime selection on a per-node basis.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RuntimeHint {
+    /// Use RustPython embedded interpreter (pure Rust, limited stdlib)
+    RustPython,
+
+    /// Use CPython via PyO3 in-process (full Python ecosystem, C-extensions)
+    Cpython,
+
+    /// Use CPython compiled to WASM (sandboxed, Phase 3)
+    CpythonWasm,
+
+    /// Automatically select runtime based on node requirements
+    Auto,
+}
+
+/// Where a node (or its fallback) may run
+///
+/// A node is remote when it has a `host` or is a `RemotePipelineNode`.
+/// The candidates are the node itself and its declared fallback; the
+/// session runs the first one the placement allows whose node type is
+/// registered and which initializes.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Placement {
+    /// Only local candidates
+    Local,
+    /// Only remote candidates
+    Remote,
+    /// Local candidates first
+    PreferLocal,
+    /// Remote candidates first
+    PreferRemote,
+    /// The node, then its fallback (default)
+    #[default]
+    Auto,
+}
+
+impl Placement {
+    /// Order `candidates` by preference, dropping the ones not allowed
+    ///
+    /// The sort is stable, so candidates in the same tier keep their
+    /// manifest order.
+    pub fn order<'a>(&self, candidates: &[&'a NodeManifest]) -> Vec<&'a NodeManifest> {
+        let mut ordered: Vec<&NodeManifest> = candidates
+            .iter()
+            .copied()
+            .filter(|node| match self {
+                Placement::Local => !node.is_remote(),
+                Placement::Remote => node.is_remote(),
+                _ => true,
+            })
+            .collect();
+        match self {
+            Placement::PreferLocal => ordered.sort_by_key(|node| node.is_remote()),
+            Placement::PreferRemote => ordered.sort_by_key(|node| !node.is_remote()),
+            _ => {}
+        }
+        ordered
+    }
+}
+
+/// Execution metadata for capability-aware placement (Phase 1.3.6)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExecutionMetadata {
+    /// Execution placement strategy
+    #[serde(default)]
+    pub placement: Placement,
+
+    /// Reason for execution placement (e.g., "requires_native_libs")
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+
+    /// Fallback node if this one can't execute
+    ///
+    /// Names another node of the manifest that takes no part in any
+    /// connection. It is kept out of the graph and replaces this node
+    /// when it fails to initialize or its circuit breaker opens.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fallback: Option<String>,
+}
+
+/// Capability requirements for a node
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CapabilityRequirements {
+    /// GPU requirements
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub gpu: Option<GpuRequirement>,
+
+    /// CPU requirements
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cpu: Option<CpuRequirement>,
+
+    /// Memory requirements (GB)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub memory_gb: Option<f64>,
+}
+
+/// GPU capability requirement
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GpuRequirement {
+    /// GPU type (cuda, rocm, metal)
+    #[serde(rename = "type")]
+    pub gpu_type: String,
+
+    /// Minimum memory (GB)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min_memory_gb: Option<f64>,
+
+    /// Whether GPU is required or optional
+    #[serde(default = "default_required")]
+    pub required: bool,
+}
+
+/// CPU capability requirement
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CpuRequirement {
+    /// Minimum number of cores
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cores: Option<u32>,
+
+    /// CPU architecture preference
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub arch: Option<String>,
+}
+
+/// Connection between nodes
+///
+/// Ports are optional; leaving one unset addresses the node's implicit
+/// `main` port. An unset (or `main`) `from_port` forwards every output of
+/// the source node, a named one forwards only what the node emits on that
+/// port. A named `to_port` delivers into the target's auxiliary input of
+/// that name.
+///
+/// An optional `filter` restricts which outputs the edge forwards; see
+/// [`crate::executor::edge_filter`] for the predicate syntax.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Connection {
+    /// Source node ID
+    pub from: String,
+
+    /// Target node ID
+    pub to: String,
+
+    /// Source output port (default: `main`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub from_port: Option<String>,
+
+    /// Target input port (default: `main`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub to_port: Option<String>,
+
+    /// Only forward outputs matching this predicate (default: everything)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub filter: Option<EdgeFilter>,
+
+    /// Delivery options for this edge (v2)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub options: Option<EdgeOptions>,
+}
+
+/// Per-connection delivery options (v2)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EdgeOptions {
+    /// What to do when the target node's input queue is full
+    #[serde(default)]
+    pub overflow: Overflow,
+}
+
+/// Behaviour of an edge whose target cannot keep up
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Overflow {
+    /// Wait for room, pushing backpressure back to the source node
+    #[default]
+    Block,
+    /// Discard the output, so a slow branch (a preview, a recorder)
+    /// never stalls the rest of the pipeline
+    DropNewest,
+}
+
+impl Connection {
+    /// Connect the `main` ports of two nodes
+    pub fn new(from: impl Into<String>, to: impl Into<String>) -> Self {
+        Self {
+            from: from.into(),
+            to: to.into(),
+            ..Default::default()
+        }
+    }
+
+    /// Set the source output port
+    pub fn from_port(mut self, port: impl Into<String>) -> Self {
+        self.from_port = Some(port.into());
+        self
+    }
+
+    /// Set the target input port
+    pub fn to_port(mut self, port: impl Into<String>) -> Self {
+        self.to_port = Some(port.into());
+        self
+    }
+
+    /// Only forward outputs matching `filter`
+    pub fn filter(mut self, filter: EdgeFilter) -> Self {
+        self.filter = Some(filter);
+        self
+    }
+
+    /// Set the edge's delivery options
+    pub fn options(mut self, options: EdgeOptions) -> Self {
+        self.options = Some(options);
+        self
+    }
+
+    /// Overflow behaviour (default [`Overflow::Block`])
+    pub fn overflow(&self) -> Overflow {
+        self.options.map(|o| o.overflow).unwrap_or_default()
+    }
+}
+
+/// Renders as `vad.out.events -> correlator.in.alerts`, matching the
+/// control-bus address notation; `main` ports are elided.
+impl std::fmt::Display for Connection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        fmt_connection(
+            f,
+            &self.from,
+            self.from_port.as_deref(),
+            &self.to,
+            self.to_port.as_deref(),
+        )
+    }
+}
+
+pub(crate) fn fmt_connection(
+    f: &mut std::fmt::Formatter<'_>,
+    from: &str,
+    from_port: Option<&str>,
+    to: &str,
+    to_port: Option<&str>,
+) -> std::fmt::Result {
+    match from_port {
+        Some(port) => write!(f, "{}.out.{}", from, port)?,
+        None => write!(f, "{}", from)?,
+    }
+    f.write_str(" -> ")?;
+    match to_port {
+        Some(port) => write!(f, "{}.in.{}", to, port),
+        None => write!(f, "{}", to),
+    }
+}
+
+fn default_required() -> bool {
+    true
+}
+
+/// The implicit port every node has
+const MAIN_PORT: &str = "main";
+
+/// Parse a JSON manifest string into a Manifest struct
+///
+/// The manifest is taken as written: no `${ENV}` substitution or includes,
+/// so it is safe for manifests supplied by clients. Use [`load_file`] or
+/// [`ManifestLoader`] for templated manifests.
+pub fn parse(json: &str) -> Result<Manifest> {
+    serde_json::from_str(json)
+        .map_err(|e| Error::Manifest(format!("Failed to parse manifest: {}", e)))
+}
+
+/// Load a manifest file (JSON or YAML), expanding templates, includes and
+/// `${ENV}` references
+pub fn load_file(path: impl AsRef<Path>) -> Result<Manifest> {
+    ManifestLoader::new().load_file(path)
+}
+
+/// Validate a manifest for correctness
+pub fn validate(manifest: &Manifest) -> Result<()> {
+    // Check version
+    if !SUPPORTED_VERSIONS.contains(&manifest.version.as_str()) {
+        return Err(Error::Manifest(format!(
+            "Unsupported manifest version: {} (supported: {})",
+            manifest.version,
+            SUPPORTED_VERSIONS.join(", ")
+        )));
+    }
+    let v1 = manifest.version == "v1";
+
+    // Check nodes are not empty
+    if manifest.nodes.is_empty() {
+        return Err(Error::Manifest(
+            "Manifest must contain at least one node".to_string(),
+        ));
+    }
+
+    // Validate node IDs are unique
+    let mut seen_ids = std::collections::HashSet::new();
+    for node in &manifest.nodes {
+        if !seen_ids.insert(&node.id) {
+            return Err(Error::Manifest(format!("Duplicate node ID: {}", node.id)));
+        }
+    }
+
+    // Fields belong to one schema version or the other
+    for node in &manifest.nodes {
+        if v1 && (node.ports.is_some() || node.resources.is_some()) {
+            return Err(Error::Manifest(format!(
+                "Node '{}': `ports` and `resources` require manifest version v2",
+                node.id
+            )));
+        }
+        if !v1 && node.capabilities.is_some() {
+            return Err(Error::Manifest(format!(
+                "Node '{}': `capabilities` is named `resources` in manifest version v2",
+                node.id
+            )));
+        }
+        if let Some(ports) = &node.ports {
+            for port in ports.inputs.iter().chain(&ports.outputs) {
+                if port.is_empty() || port.contains('.') {
+                    return Err(Error::Manifest(format!(
+                        "Node '{}' declares invalid port name '{}'",
+                        node.id, port
+                    )));
+                }
+            }
+        }
+    }
+
+    // Fallbacks name standby nodes
+    manifest.standby_nodes()?;
+
+    // Validate connections reference valid nodes
+    let nodes: std::collections::HashMap<_, _> =
+        manifest.nodes.iter().map(|n| (&n.id, n)).collect();
+    for conn in &manifest.connections {
+        let Some(from) = nodes.get(&conn.from) else {
+            return Err(Error::Manifest(format!(
+                "Connection references unknown source node: {}",
+                conn.from
+            )));
+        };
+        let Some(to) = nodes.get(&conn.to) else {
+            return Err(Error::Manifest(format!(
+                "Connection references unknown target node: {}",
+                conn.to
+            )));
+        };
+        for port in [&conn.from_port, &conn.to_port].into_iter().flatten() {
+            if port.is_empty() || port.contains('.') {
+                return Err(Error::Manifest(format!(
+                    "Connection {} has invalid port name '{}'",
+                    conn, port
+                )));
+            }
+        }
+        if let Some(filter) = &conn.filter {
+            filter.validate().map_err(|e| {
+                Error::Manifest(format!("Connection {} has invalid filter: {}", conn, e))
+            })?;
+        }
+        if v1 && conn.options.is_some() {
+            return Err(Error::Manifest(format!(
+                "Connection {}: `options` requires manifest version v2",
+                conn
+            )));
+        }
+
+        // Declared ports are a closed set
+        let undeclared = [
+            (from, conn.from_port.as_deref(), true),
+            (to, conn.to_port.as_deref(), false),
+        ]
+        .into_iter()
+        .find_map(|(node, port, is_output)| {
+            let (declared, port) = (node.ports.as_ref()?, port?);
+            let ports = if is_output {
+                &declared.outputs
+            } else {
+                &declared.inputs
+            };
+            (!NodePorts::declares(ports, port)).then_some((node, port, is_output))
+        });
+        if let Some((node, port, is_output)) = undeclared {
+            return Err(Error::Manifest(format!(
+                "Connection {}: node '{}' declares no {} port '{}'",
+                conn,
+                node.id,
+                if is_output { "output" } else { "input" },
+                port
+            )));
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_simple_manifest() {
+        let json = r#"{
+            "version": "v1",
+            "metadata": {
+                "name": "test-pipeline"
+            },
+            "nodes": [
+                {
+                    "id": "node1",
+                    "node_type": "AudioSource",
+                    "params": {}
+                }
+            ],
+            "connections": []
+        }"#;
+
+        let manifest = parse(json).unwrap();
+        assert_eq!(manifest.version, "v1");
+        assert_eq!(manifest.metadata.name, "test-pipeline");
+        assert_eq!(manifest.nodes.len(), 1);
+    }
+
+    #[test]
+    fn test_validate_empty_nodes() {
+        let manifest = Manifest {
+            version: "v1".to_string(),
+            metadata: ManifestMetadata {
+                name: "test".to_string(),
+                ..Default::default()
+            },
+            nodes: vec![],
+            connections: vec![],
+            python_env: None,
+        };
+
+        assert!(validate(&manifest).is_err());
+    }
+
+    /// Test is_output_node field parsing from JSON (spec 021 User Story 3)
+    #[test]
+    fn test_parse_is_output_node_field() {
+        // Test with is_output_node explicitly set to true
+        let json = r#"{
+            "version": "v1",
+            "metadata": { "name": "test-pipeline" },
+            "nodes": [
+                {
+                    "id": "node1",
+                    "node_type": "AudioSource",
+                    "params": {},
+                    "is_output_node": true
+                },
+                {
+                    "id": "node2",
+                    "node_type": "AudioSink",
+                    "params": {},
+                    "is_output_node": false
+                }
+            ],
+            "connections": [{"from": "node1", "to": "node2"}]
+        }"#;
+
+        let manifest = parse(json).unwrap();
+        assert_eq!(manifest.nodes.len(), 2);
+        assert!(manifest.nodes[0].is_output_node);  // Explicitly true
+        assert!(!manifest.nodes[1].is_output_node); // Explicitly false
+    }
+
+    /// Test is_output_node defaults to false when not specified
+    #[test]
+    fn test_is_output_node_defaults_to_false() {
+        let json = r#"{
+            "version": "v1",
+            "metadata": { "name": "test-pipeline" },
+            "nodes": [
+                {
+                    "id": "node1",
+                    "node_type": "AudioSource",
+                    "params": {}
+                }
+            ],
+            "connections": []
+        }"#;
+
+        let manifest = parse(json).unwrap();
+        assert!(!manifest.nodes[0].is_output_node); // Defaults to false
+    }
+
+    #[test]
+    fn test_connection_ports() {
+        let json = r#"{
+            "version": "v1",
+            "metadata": { "name": "port-pipeline" },
+            "nodes": [
+                { "id": "vad", "node_type": "SileroVADNode", "params": {} },
+                { "id": "correlator", "node_type": "Correlator", "params": {} }
+            ],
+            "connections": [
+                { "from": "vad", "from_port": "events", "to": "correlator", "to_port": "alerts" },
+                { "from": "vad", "to": "correlator" }
+            ]
+        }"#;
+
+        let manifest = parse(json).unwrap();
+        assert!(validate(&manifest).is_ok());
+
+        let ported = &manifest.connections[0];
+        assert_eq!(
+            ported,
+            &Connection::new("vad", "correlator")
+                .from_port("events")
+                .to_port("alerts")
+        );
+        assert_eq!(ported.to_string(), "vad.out.events -> correlator.in.alerts");
+        assert_eq!(manifest.connections[1].to_string(), "vad -> correlator");
+
+        // Unset ports are omitted on the way back out
+        let plain = serde_json::to_value(&manifest.connections[1]).unwrap();
+        assert_eq!(plain, serde_json::json!({"from": "vad", "to": "correlator"}));
+    }
+
+    #[test]
+    fn test_connection_invalid_port_name() {
+        let mut manifest = parse(
+            r#"{
+                "version": "v1",
+                "metadata": { "name": "port-pipeline" },
+                "nodes": [
+                    { "id": "a", "node_type": "Echo", "params": {} },
+                    { "id": "b", "node_type": "Echo", "params": {} }
+                ],
+                "connections": [{ "from": "a", "from_port": "", "to": "b" }]
+            }"#,
+        )
+        .unwrap();
+        assert!(validate(&manifest).is_err());
+
+        manifest.connections[0] = Connection::new("a", "b").to_port("x.y");
+        assert!(validate(&manifest).is_err());
+    }
+
+    #[test]
+    fn test_connection_filter() {
+        let json = r#"{
+            "version": "v1",
+            "metadata": { "name": "filter-pipeline" },
+            "nodes": [
+                { "id": "stt", "node_type": "WhisperNode", "params": {} },
+                { "id": "llm", "node_type": "OpenAIChatNode", "params": {} }
+            ],
+            "connections": [{
+                "from": "stt",
+                "to": "llm",
+                "filter": { "metadata": { "pointer": "/is_final", "equals": true } }
+            }]
+        }"#;
+
+        let mut manifest = parse(json).unwrap();
+        assert!(validate(&manifest).is_ok());
+        assert!(matches!(
+            manifest.connections[0].filter,
+            Some(EdgeFilter::Metadata(ref m)) if m.pointer == "/is_final"
+        ));
+
+        manifest.connections[0] =
+            Connection::new("stt", "llm").filter(EdgeFilter::TextChannel(String::new()));
+        let err = validate(&manifest).unwrap_err().to_string();
+        assert!(err.contains("invalid filter"), "{}", err);
+    }
+
+    #[test]
+    fn test_v2_fields_and_version_rules() {
+        let json = r#"{
+            "version": "v2",
+            "metadata": { "name": "v2-pipeline" },
+            "nodes": [
+                {
+                    "id": "vad",
+                    "node_type": "silero_vad",
+                    "params": {},
+                    "ports": { "outputs": ["events"] },
+                    "resources": { "memory_gb": 1.0 }
+                },
+                { "id": "recorder", "node_type": "Recorder", "params": {} }
+            ],
+            "connections": [{
+                "from": "vad",
+                "from_port": "events",
+                "to": "recorder",
+                "options": { "overflow": "drop_newest" }
+            }]
+        }"#;
+
+        let mut manifest = parse(json).unwrap();
+        assert!(validate(&manifest).is_ok());
+        assert_eq!(manifest.connections[0].overflow(), Overflow::DropNewest);
+        assert_eq!(
+            manifest.nodes[0].resource_requirements().unwrap().memory_gb,
+            Some(1.0)
+        );
+
+        // Declared ports are a closed set
+        manifest.connections[0].from_port = Some("speech".to_string());
+        let err = validate(&manifest).unwrap_err().to_string();
+        assert!(err.contains("declares no output port 'speech'"), "{}", err);
+        manifest.connections[0].from_port = Some("events".to_string());
+
+        // `capabilities` is a v1 name
+        manifest.nodes[1].capabilities = manifest.nodes[0].resources.take();
+        assert!(validate(&manifest).is_err());
+        manifest.nodes[0].resources = manifest.nodes[1].capabilities.take();
+
+        // v2 fields are rejected in v1 manifests
+        manifest.version = "v1".to_string();
+        let err = validate(&manifest).unwrap_err().to_string();
+        assert!(err.contains("require manifest version v2"), "{}", err);
+
+        manifest.version = "v3".to_string();
+        assert!(validate(&manifest).is_err());
+    }
+
+    #[test]
+    fn test_execution_placement_and_fallback() {
+        let json = r#"{
+            "version": "v1",
+            "metadata": { "name": "fallback-pipeline" },
+            "nodes": [
+                {
+                    "id": "stt",
+                    "node_type": "RemotePipelineNode",
+                    "params": {},
+                    "execution": { "placement": "prefer_remote", "fallback": "whisper" }
+                },
+                { "id": "whisper", "node_type": "RustWhisperNode", "params": {} },
+                { "id": "sink", "node_type": "Recorder", "params": {} }
+            ],
+            "connections": [{ "from": "stt", "to": "sink" }]
+        }"#;
+
+        let mut manifest = parse(json).unwrap();
+        assert!(validate(&manifest).is_ok());
+        let stt = &manifest.nodes[0];
+        let whisper = &manifest.nodes[1];
+        assert_eq!(stt.placement(), Placement::PreferRemote);
+        assert_eq!(stt.fallback(), Some("whisper"));
+        assert_eq!(whisper.placement(), Placement::Auto);
+        assert!(stt.is_remote() && !whisper.is_remote());
+        let standby = manifest.standby_nodes().unwrap();
+        assert_eq!(standby.into_iter().collect::<Vec<_>>(), vec!["whisper"]);
+
+        let ids = |placement: Placement| {
+            placement
+                .order(&[stt, whisper])
+                .iter()
+                .map(|n| n.id.as_str())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(ids(Placement::Auto), vec!["stt", "whisper"]);
+        assert_eq!(ids(Placement::PreferLocal), vec!["whisper", "stt"]);
+        assert_eq!(ids(Placement::PreferRemote), vec!["stt", "whisper"]);
+        assert_eq!(ids(Placement::Local), vec!["whisper"]);
+        assert_eq!(ids(Placement::Remote), vec!["stt"]);
+
+        // Standby nodes stay out of the connections
+        let edge = Connection::new("whisper", "sink");
+        manifest.connections.push(edge);
+        let err = validate(&manifest).unwrap_err().to_string();
+        assert!(err.contains("must not appear in connections"), "{}", err);
+        manifest.connections.pop();
+
+        manifest.nodes[0].execution.as_mut().unwrap().fallback = Some("nope".to_string());
+        assert!(validate(&manifest).is_err());
+
+        assert!(serde_json::from_value::<ExecutionMetadata>(
+            serde_json::json!({ "placement": "somewhere" })
+        )
+        .is_err());
+    }
+}
@@ -0,0 +1,791 @@
+//! Manifest templating: environment substitution, includes and typed
+//! template arguments.
+//!
+//! [`parse`](super::parse) deserializes a manifest exactly as written.
+//! Manifest files written by an operator go through [`ManifestLoader`]
+//! instead, which expands the document before it is deserialized (and so
+//! before [`validate`](super::validate) sees it):
+//!
+//! - **Environment substitution.** `${VAR}` in any string value is replaced
+//!   with the variable's value and fails if it is unset. `${VAR:-default}`
+//!   falls back to `default` when `VAR` is unset or empty. `$${` produces a
+//!   literal `${`, for values that are substituted later at runtime.
+//! - **Template arguments.** A `template.params` section declares typed
+//!   arguments and `${args.name}` refers to one. A string that is *only* a
+//!   reference takes the argument's JSON value, so `"${args.sample_rate}"`
+//!   becomes the number `16000`; anywhere else it is interpolated as text.
+//! - **Includes.** A node entry with `include:` instead of `node_type`
+//!   splices another manifest file in as a subgraph. Its nodes are
+//!   namespaced under the entry's id (`asr/vad`), `args:` supplies its
+//!   template arguments, and connections to or from the entry's id attach
+//!   to the subgraph's source or sink nodes respectively.
+//!
+//! ```yaml
+//! # voice-agent.yaml
+//! version: v1
+//! metadata: { name: voice-agent }
+//! template:
+//!   params:
+//!     stt_model: { type: string, default: "${STT_MODEL:-base.en}" }
+//!     vad_threshold: { type: number, default: 0.5 }
+//! nodes:
+//!   - id: asr
+//!     include: ./asr.yaml
+//!     args: { model: "${args.stt_model}", threshold: "${args.vad_threshold}" }
+//!   - id: llm
+//!     node_type: OpenAIChatNode
+//!     params: { api_base: "${LLM_API_BASE:-http://localhost:8000/v1}" }
+//! connections:
+//!   - { from: asr, to: llm }
+//! ```
+//!
+//! Expansion reads the server's environment and filesystem, so it is only
+//! for trusted manifests. Manifests received from clients are parsed as-is.
+
+use super::{Connection, Manifest};
+use crate::{Error, Result};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+/// Separator between an include's id and the ids of the nodes it brings in
+pub const NAMESPACE_SEPARATOR: char = '/';
+
+/// Nesting limit for `include:`
+const MAX_INCLUDE_DEPTH: usize = 16;
+
+/// Declared type of a template argument
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ParamType {
+    String,
+    Integer,
+    Number,
+    Boolean,
+    Array,
+    Object,
+}
+
+impl ParamType {
+    /// Check `value` against this type
+    ///
+    /// Strings are parsed into the other types (arrays and objects as
+    /// JSON), since that is what environment substitution and command
+    /// lines produce.
+    fn coerce(self, value: Value) -> Option<Value> {
+        match (self, value) {
+            (Self::String, v @ Value::String(_)) => Some(v),
+            (Self::Integer, v) if v.is_i64() || v.is_u64() => Some(v),
+            (Self::Number, v @ Value::Number(_)) => Some(v),
+            (Self::Boolean, v @ Value::Bool(_)) => Some(v),
+            (Self::Array, v @ Value::Array(_)) => Some(v),
+            (Self::Object, v @ Value::Object(_)) => Some(v),
+            (Self::Integer, Value::String(s)) => s.trim().parse::<i64>().ok().map(Value::from),
+            (Self::Number, Value::String(s)) => {
+                let s = s.trim();
+                s.parse::<i64>().map(Value::from).ok().or_else(|| {
+                    s.parse::<f64>()
+                        .ok()
+                        .and_then(serde_json::Number::from_f64)
+                        .map(Value::Number)
+                })
+            }
+            (Self::Boolean, Value::String(s)) => s.trim().parse::<bool>().ok().map(Value::Bool),
+            (Self::Array | Self::Object, Value::String(s)) => {
+                serde_json::from_str(&s)
+                    .ok()
+                    .filter(|v: &Value| match self {
+                        Self::Array => v.is_array(),
+                        _ => v.is_object(),
+                    })
+            }
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for ParamType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::String => "string",
+            Self::Integer => "integer",
+            Self::Number => "number",
+            Self::Boolean => "boolean",
+            Self::Array => "array",
+            Self::Object => "object",
+        })
+    }
+}
+
+/// One declared template argument
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct TemplateParam {
+    /// Expected type of the argument
+    #[serde(rename = "type")]
+    pub param_type: ParamType,
+
+    /// Value used when the argument is not supplied; without one the
+    /// argument is required
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default: Option<Value>,
+
+    /// Free-form description for people reading the template
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+}
+
+/// The `template:` section of a manifest
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct TemplateSpec {
+    /// Declared arguments by name
+    #[serde(default)]
+    pub params: BTreeMap<String, TemplateParam>,
+}
+
+/// A node entry that includes another manifest
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct IncludeEntry {
+    id: String,
+    include: PathBuf,
+    #[serde(default)]
+    args: Map<String, Value>,
+}
+
+/// Prefixed ids of an included subgraph's boundary nodes
+struct Subgraph {
+    sources: Vec<String>,
+    sinks: Vec<String>,
+}
+
+type EnvLookup = Arc<dyn Fn(&str) -> Option<String> + Send + Sync>;
+
+/// Expands templated manifests; see the [module docs](self)
+#[derive(Clone)]
+pub struct ManifestLoader {
+    args: Map<String, Value>,
+    env: EnvLookup,
+}
+
+impl Default for ManifestLoader {
+    fn default() -> Self {
+        Self {
+            args: Map::new(),
+            env: Arc::new(|name: &str| std::env::var(name).ok()),
+        }
+    }
+}
+
+impl fmt::Debug for ManifestLoader {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ManifestLoader")
+            .field("args", &self.args)
+            .finish_non_exhaustive()
+    }
+}
+
+impl ManifestLoader {
+    /// Loader resolving `${VAR}` against the process environment
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Supply a template argument to the top-level manifest
+    pub fn arg(mut self, name: impl Into<String>, value: impl Into<Value>) -> Self {
+        self.args.insert(name.into(), value.into());
+        self
+    }
+
+    /// Resolve `${VAR}` through `lookup` instead of the process environment
+    pub fn env_lookup(
+        mut self,
+        lookup: impl Fn(&str) -> Option<String> + Send + Sync + 'static,
+    ) -> Self {
+        self.env = Arc::new(lookup);
+        self
+    }
+
+    /// Load and expand a manifest file
+    ///
+    /// `.yaml` / `.yml` files are read as YAML, anything else as JSON.
+    /// Includes are resolved relative to the file's directory.
+    pub fn load_file(&self, path: impl AsRef<Path>) -> Result<Manifest> {
+        into_manifest(self.expand_file(path)?)
+    }
+
+    /// Expand a manifest that is already in memory (JSON or YAML)
+    ///
+    /// Includes are resolved relative to `base_dir`.
+    pub fn load_str(&self, content: &str, base_dir: impl AsRef<Path>) -> Result<Manifest> {
+        let document = match serde_json::from_str(content) {
+            Ok(document) => document,
+            Err(_) => serde_yaml::from_str(content)
+                .map_err(|e| Error::Manifest(format!("Failed to parse manifest: {}", e)))?,
+        };
+        into_manifest(self.expand(document, base_dir)?)
+    }
+
+    /// Expand a manifest file without deserializing the result
+    pub fn expand_file(&self, path: impl AsRef<Path>) -> Result<Value> {
+        self.expand_included(path.as_ref(), &self.args, &mut Vec::new())
+    }
+
+    /// Expand a manifest document without deserializing the result
+    pub fn expand(&self, document: Value, base_dir: impl AsRef<Path>) -> Result<Value> {
+        self.expand_document(document, base_dir.as_ref(), &self.args, &mut Vec::new())
+    }
+
+    fn expand_included(
+        &self,
+        path: &Path,
+        args: &Map<String, Value>,
+        stack: &mut Vec<PathBuf>,
+    ) -> Result<Value> {
+        let path = path.canonicalize().map_err(|e| {
+            Error::Manifest(format!("Failed to read manifest {}: {}", path.display(), e))
+        })?;
+        if stack.contains(&path) {
+            let chain: Vec<_> = stack
+                .iter()
+                .chain(std::iter::once(&path))
+                .map(|p| p.display().to_string())
+                .collect();
+            return Err(Error::Manifest(format!(
+                "Include cycle: {}",
+                chain.join(" -> ")
+            )));
+        }
+        if stack.len() >= MAX_INCLUDE_DEPTH {
+            return Err(Error::Manifest(format!(
+                "Includes nested deeper than {} at {}",
+                MAX_INCLUDE_DEPTH,
+                path.display()
+            )));
+        }
+
+        let document = read_document(&path)?;
+        let base_dir = path.parent().unwrap_or(Path::new(".")).to_path_buf();
+        stack.push(path);
+        let expanded = self.expand_document(document, &base_dir, args, stack);
+        stack.pop();
+        expanded
+    }
+
+    fn expand_document(
+        &self,
+        document: Value,
+        base_dir: &Path,
+        args: &Map<String, Value>,
+        stack: &mut Vec<PathBuf>,
+    ) -> Result<Value> {
+        let Value::Object(mut document) = document else {
+            return Err(Error::Manifest("Manifest must be an object".to_string()));
+        };
+
+        // Defaults may reference the environment but not other arguments.
+        let no_args = Map::new();
+        let spec = match document.remove("template") {
+            Some(template) => {
+                let template = self.scope(&no_args).substitute(template)?;
+                serde_json::from_value(template)
+                    .map_err(|e| Error::Manifest(format!("Invalid template section: {}", e)))?
+            }
+            None => TemplateSpec::default(),
+        };
+        let args = resolve_args(&spec, args)?;
+
+        let Value::Object(mut document) = self.scope(&args).substitute(Value::Object(document))?
+        else {
+            unreachable!("substitution preserves objects");
+        };
+        self.splice_includes(&mut document, base_dir, stack)?;
+        Ok(Value::Object(document))
+    }
+
+    /// Replace `include:` entries with their (already expanded) subgraphs
+    fn splice_includes(
+        &self,
+        document: &mut Map<String, Value>,
+        base_dir: &Path,
+        stack: &mut Vec<PathBuf>,
+    ) -> Result<()> {
+        let nodes = match document.get_mut("nodes") {
+            Some(Value::Array(nodes)) if nodes.iter().any(|n| n.get("include").is_some()) => {
+                std::mem::take(nodes)
+            }
+            _ => return Ok(()),
+        };
+
+        let mut spliced = Vec::with_capacity(nodes.len());
+        let mut internal = Vec::new();
+        let mut subgraphs: HashMap<String, Subgraph> = HashMap::new();
+        for node in nodes {
+            if node.get("include").is_none() {
+                spliced.push(node);
+                continue;
+            }
+            let entry: IncludeEntry = serde_json::from_value(node)
+                .map_err(|e| Error::Manifest(format!("Invalid include entry: {}", e)))?;
+            let path = base_dir.join(&entry.include);
+            let mut sub = into_manifest(self.expand_included(&path, &entry.args, stack)?)
+                .map_err(|e| Error::Manifest(format!("Include '{}': {}", entry.id, e)))?;
+
+            let prefixed = |id: &str| format!("{}{}{}", entry.id, NAMESPACE_SEPARATOR, id);
+            let has_incoming: HashSet<&str> =
+                sub.connections.iter().map(|c| c.to.as_str()).collect();
+            let has_outgoing: HashSet<&str> =
+                sub.connections.iter().map(|c| c.from.as_str()).collect();
+            let subgraph = Subgraph {
+                sources: sub
+                    .nodes
+                    .iter()
+                    .filter(|n| !has_incoming.contains(n.id.as_str()))
+                    .map(|n| prefixed(&n.id))
+                    .collect(),
+                sinks: sub
+                    .nodes
+                    .iter()
+                    .filter(|n| !has_outgoing.contains(n.id.as_str()))
+                    .map(|n| prefixed(&n.id))
+                    .collect(),
+            };
+
+            for conn in &mut sub.connections {
+                conn.from = prefixed(&conn.from);
+                conn.to = prefixed(&conn.to);
+            }
+            for node in &mut sub.nodes {
+                node.id = prefixed(&node.id);
+            }
+            spliced.extend(sub.nodes.iter().map(to_value).collect::<Result<Vec<_>>>()?);
+            internal.extend(sub.connections);
+
+            if subgraphs.insert(entry.id.clone(), subgraph).is_some() {
+                return Err(Error::Manifest(format!("Duplicate node ID: {}", entry.id)));
+            }
+        }
+        if let Some(id) = spliced
+            .iter()
+            .filter_map(|n| n.get("id").and_then(Value::as_str))
+            .find(|id| subgraphs.contains_key(*id))
+        {
+            return Err(Error::Manifest(format!("Duplicate node ID: {}", id)));
+        }
+        document.insert("nodes".to_string(), Value::Array(spliced));
+
+        // Connections naming an include attach to its boundary nodes.
+        let declared = match document.remove("connections") {
+            Some(connections) => serde_json::from_value::<Vec<Connection>>(connections)
+                .map_err(|e| Error::Manifest(format!("Invalid connections: {}", e)))?,
+            None => Vec::new(),
+        };
+        let sinks = |id: &String| {
+            subgraphs
+                .get(id)
+                .map_or_else(|| vec![id.clone()], |sub| sub.sinks.clone())
+        };
+        let sources = |id: &String| {
+            subgraphs
+                .get(id)
+                .map_or_else(|| vec![id.clone()], |sub| sub.sources.clone())
+        };
+        let mut connections = Vec::with_capacity(declared.len() + internal.len());
+        for conn in declared {
+            for from in sinks(&conn.from) {
+                for to in sources(&conn.to) {
+                    connections.push(Connection {
+                        from: from.clone(),
+                        to,
+                        ..conn.clone()
+                    });
+                }
+            }
+        }
+        connections.extend(internal);
+        document.insert(
+            "connections".to_string(),
+            connections.iter().map(to_value).collect::<Result<_>>()?,
+        );
+        Ok(())
+    }
+
+    fn scope<'a>(&'a self, args: &'a Map<String, Value>) -> Scope<'a> {
+        Scope {
+            args,
+            env: &*self.env,
+        }
+    }
+}
+
+/// Expand `${VAR}` and `${VAR:-default}` against the process environment
+///
+/// The string-level building block of [`ManifestLoader`], for values that
+/// are resolved at runtime rather than when the manifest is loaded.
+///
+/// ```
+/// use remotemedia_core::manifest::template::substitute_env;
+///
+/// let url = substitute_env("${REMOTEMEDIA_DOC_UNSET_VAR:-http://localhost:8080}").unwrap();
+/// assert_eq!(url, "http://localhost:8080");
+/// ```
+pub fn substitute_env(value: &str) -> Result<String> {
+    let env = |name: &str| std::env::var(name).ok();
+    let args = Map::new();
+    match (Scope {
+        args: &args,
+        env: &env,
+    })
+    .substitute_str(value)?
+    {
+        Value::String(s) => Ok(s),
+        other => Ok(other.to_string()),
+    }
+}
+
+fn resolve_args(spec: &TemplateSpec, supplied: &Map<String, Value>) -> Result<Map<String, Value>> {
+    if let Some(unknown) = supplied.keys().find(|k| !spec.params.contains_key(*k)) {
+        return Err(Error::Manifest(format!(
+            "Unknown template argument '{}'",
+            unknown
+        )));
+    }
+
+    let mut resolved = Map::new();
+    for (name, param) in &spec.params {
+        let value = supplied
+            .get(name)
+            .or(param.default.as_ref())
+            .ok_or_else(|| {
+                Error::Manifest(format!("Missing required template argument '{}'", name))
+            })?;
+        let value = param.param_type.coerce(value.clone()).ok_or_else(|| {
+            Error::Manifest(format!(
+                "Template argument '{}' must be {}, got {}",
+                name, param.param_type, value
+            ))
+        })?;
+        resolved.insert(name.clone(), value);
+    }
+    Ok(resolved)
+}
+
+/// What `${...}` references resolve against
+struct Scope<'a> {
+    args: &'a Map<String, Value>,
+    env: &'a dyn Fn(&str) -> Option<String>,
+}
+
+impl Scope<'_> {
+    fn substitute(&self, value: Value) -> Result<Value> {
+        match value {
+            Value::String(s) => self.substitute_str(&s),
+            Value::Array(items) => items
+                .into_iter()
+                .map(|v| self.substitute(v))
+                .collect::<Result<_>>()
+                .map(Value::Array),
+            Value::Object(map) => map
+                .into_iter()
+                .map(|(k, v)| Ok((k, self.substitute(v)?)))
+                .collect::<Result<_>>()
+                .map(Value::Object),
+            other => Ok(other),
+        }
+    }
+
+    fn substitute_str(&self, s: &str) -> Result<Value> {
+        // A lone argument reference keeps the argument's type.
+        if let Some(name) = s
+            .strip_prefix("${args.")
+            .and_then(|rest| rest.strip_suffix('}'))
+        {
+            if !name.contains('}') {
+                return self.arg(name).cloned();
+            }
+        }
+
+        let mut out = String::with_capacity(s.len());
+        let mut rest = s;
+        while let Some(pos) = rest.find('$') {
+            out.push_str(&rest[..pos]);
+            let tail = &rest[pos..];
+            if let Some(after) = tail.strip_prefix("$${") {
+                out.push_str("${");
+                rest = after;
+            } else if let Some(after) = tail.strip_prefix("${") {
+                let end = after
+                    .find('}')
+                    .ok_or_else(|| Error::Manifest(format!("Unterminated '${{' in \"{}\"", s)))?;
+                self.expand_reference(&after[..end], &mut out)?;
+                rest = &after[end + 1..];
+            } else {
+                out.push('$');
+                rest = &tail[1..];
+            }
+        }
+        out.push_str(rest);
+        Ok(Value::String(out))
+    }
+
+    fn expand_reference(&self, expr: &str, out: &mut String) -> Result<()> {
+        if let Some(name) = expr.strip_prefix("args.") {
+            match self.arg(name)? {
+                Value::String(s) => out.push_str(s),
+                other => out.push_str(&other.to_string()),
+            }
+            return Ok(());
+        }
+
+        let (name, default) = match expr.split_once(":-") {
+            Some((name, default)) => (name, Some(default)),
+            None => (expr, None),
+        };
+        if !is_env_name(name) {
+            return Err(Error::Manifest(format!(
+                "Invalid substitution '${{{}}}'",
+                expr
+            )));
+        }
+        match ((self.env)(name), default) {
+            (Some(value), Some(default)) if value.is_empty() => out.push_str(default),
+            (Some(value), _) => out.push_str(&value),
+            (None, Some(default)) => out.push_str(default),
+            (None, None) => {
+                return Err(Error::Manifest(format!(
+                "Environment variable '{}' is not set (use ${{{}:-default}} to make it optional)",
+                name, name
+            )))
+            }
+        }
+        Ok(())
+    }
+
+    fn arg(&self, name: &str) -> Result<&Value> {
+        self.args
+            .get(name)
+            .ok_or_else(|| Error::Manifest(format!("Undeclared template argument '{}'", name)))
+    }
+}
+
+fn is_env_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+fn read_document(path: &Path) -> Result<Value> {
+    let content = std::fs::read_to_string(path).map_err(|e| {
+        Error::Manifest(format!("Failed to read manifest {}: {}", path.display(), e))
+    })?;
+    let parse_error = |e: &dyn fmt::Display| {
+        Error::Manifest(format!(
+            "Failed to parse manifest {}: {}",
+            path.display(),
+            e
+        ))
+    };
+    match path.extension().and_then(|e| e.to_str()) {
+        Some("yaml" | "yml") => serde_yaml::from_str(&content).map_err(|e| parse_error(&e)),
+        _ => serde_json::from_str(&content).map_err(|e| parse_error(&e)),
+    }
+}
+
+fn into_manifest(document: Value) -> Result<Manifest> {
+    serde_json::from_value(document)
+        .map_err(|e| Error::Manifest(format!("Failed to parse manifest: {}", e)))
+}
+
+fn to_value<T: Serialize>(value: T) -> Result<Value> {
+    serde_json::to_value(value)
+        .map_err(|e| Error::Manifest(format!("Failed to serialize manifest: {}", e)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn loader(env: &'static [(&'static str, &'static str)]) -> ManifestLoader {
+        ManifestLoader::new().env_lookup(move |name| {
+            env.iter()
+                .find(|(k, _)| *k == name)
+                .map(|(_, v)| v.to_string())
+        })
+    }
+
+    fn pipeline(nodes: Value, connections: Value) -> Value {
+        json!({
+            "version": "v1",
+            "metadata": { "name": "templated" },
+            "nodes": nodes,
+            "connections": connections,
+        })
+    }
+
+    fn write(dir: &Path, name: &str, content: &str) -> PathBuf {
+        let path = dir.join(name);
+        std::fs::write(&path, content).unwrap();
+        path
+    }
+
+    #[test]
+    fn test_env_substitution() {
+        let loader = loader(&[("STT_MODEL", "large-v3"), ("EMPTY", "")]);
+        let document = pipeline(
+            json!([{
+                "id": "stt",
+                "node_type": "WhisperNode",
+                "params": {
+                    "model": "${STT_MODEL}",
+                    "device": "${DEVICE:-cpu}",
+                    "lang": "${EMPTY:-en}",
+                    "auth_header": "Bearer $${API_TOKEN}",
+                    "cost": "$5",
+                }
+            }]),
+            json!([]),
+        );
+        let manifest = into_manifest(loader.expand(document, ".").unwrap()).unwrap();
+        assert_eq!(
+            manifest.nodes[0].params,
+            json!({
+                "model": "large-v3",
+                "device": "cpu",
+                "lang": "en",
+                "auth_header": "Bearer ${API_TOKEN}",
+                "cost": "$5",
+            })
+        );
+
+        let missing = pipeline(
+            json!([{ "id": "a", "node_type": "Echo", "params": { "x": "${MISSING}" } }]),
+            json!([]),
+        );
+        let err = loader.expand(missing, ".").unwrap_err().to_string();
+        assert!(err.contains("MISSING"), "{}", err);
+    }
+
+    #[test]
+    fn test_typed_template_args() {
+        let mut document = pipeline(
+            json!([{
+                "id": "vad",
+                "node_type": "SileroVADNode",
+                "params": {
+                    "sample_rate": "${args.sample_rate}",
+                    "threshold": "${args.threshold}",
+                    "label": "vad@${args.sample_rate}",
+                }
+            }]),
+            json!([]),
+        );
+        document["template"] = json!({ "params": {
+            "sample_rate": { "type": "integer", "default": "${RATE:-16000}" },
+            "threshold": { "type": "number" },
+        }});
+
+        let expanded = loader(&[])
+            .arg("threshold", "0.6")
+            .expand(document.clone(), ".")
+            .unwrap();
+        assert!(expanded.get("template").is_none());
+        assert_eq!(
+            expanded["nodes"][0]["params"],
+            json!({ "sample_rate": 16000, "threshold": 0.6, "label": "vad@16000" })
+        );
+
+        let err = loader(&[]).expand(document.clone(), ".").unwrap_err();
+        assert!(err.to_string().contains("Missing required"), "{}", err);
+
+        let err = loader(&[])
+            .arg("threshold", "high")
+            .expand(document.clone(), ".")
+            .unwrap_err();
+        assert!(err.to_string().contains("must be number"), "{}", err);
+
+        let err = loader(&[])
+            .arg("threshold", 0.5)
+            .arg("model", "tiny")
+            .expand(document, ".")
+            .unwrap_err();
+        assert!(
+            err.to_string().contains("Unknown template argument"),
+            "{}",
+            err
+        );
+    }
+
+    #[test]
+    fn test_include_namespaces_subgraph() {
+        let dir = tempfile::tempdir().unwrap();
+        write(
+            dir.path(),
+            "asr.yaml",
+            r#"
+version: v1
+metadata: { name: asr }
+template:
+  params:
+    model: { type: string, default: base.en }
+nodes:
+  - { id: vad, node_type: SileroVADNode }
+  - { id: stt, node_type: WhisperNode, params: { model: "${args.model}" } }
+connections:
+  - { from: vad, to: stt }
+"#,
+        );
+        let root = write(
+            dir.path(),
+            "agent.yaml",
+            r#"
+version: v1
+metadata: { name: agent }
+nodes:
+  - { id: mic, node_type: AudioSource }
+  - { id: asr, include: ./asr.yaml, args: { model: large-v3 } }
+  - { id: llm, node_type: OpenAIChatNode }
+connections:
+  - { from: mic, to: asr }
+  - { from: asr, to: llm }
+"#,
+        );
+
+        let manifest = ManifestLoader::new().load_file(&root).unwrap();
+        super::super::validate(&manifest).unwrap();
+        let ids: Vec<_> = manifest.nodes.iter().map(|n| n.id.as_str()).collect();
+        assert_eq!(ids, ["mic", "asr/vad", "asr/stt", "llm"]);
+        assert_eq!(manifest.nodes[2].params, json!({ "model": "large-v3" }));
+        assert_eq!(
+            manifest.connections,
+            vec![
+                Connection::new("mic", "asr/vad"),
+                Connection::new("asr/stt", "llm"),
+                Connection::new("asr/vad", "asr/stt"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_include_cycle_is_rejected() {
+        let dir = tempfile::tempdir().unwrap();
+        let entry = |target: &str| {
+            format!(
+                r#"{{ "version": "v1", "metadata": {{ "name": "cycle" }},
+                     "nodes": [{{ "id": "inner", "include": "{}" }}], "connections": [] }}"#,
+                target
+            )
+        };
+        let a = write(dir.path(), "a.json", &entry("b.json"));
+        write(dir.path(), "b.json", &entry("a.json"));
+
+        let err = ManifestLoader::new().load_file(a).unwrap_err().to_string();
+        assert!(err.contains("Include cycle"), "{}", err);
+    }
+}
@@ -4,6 +4,7 @@
 //! - Audio processing nodes (resampling, chunking, VAD)
 //! - Video processing nodes (flip, encode, decode, scale)
 //! - Text processing nodes (collector)
+//! - Media egress (HLS, subtitles)
 //! - Health monitoring nodes
 //! - Utility nodes (passthrough, calculator)
 //!
@@ -32,12 +33,15 @@ use crate::nodes::conversation_coordinator::ConversationCoordinatorNodeFactory;
 use crate::nodes::conversation_flow::ConversationFlowNodeFactory;
 use crate::nodes::event_correlator::EventCorrelatorNodeFactory;
 use crate::nodes::health_emitter::HealthEmitterNodeFactory;
+use crate::nodes::hls::HlsSinkNodeFactory;
+use crate::nodes::loudness::LoudnessNodeFactory;
 use crate::nodes::multimodal_llm::MultimodalLLMNodeFactory;
 use crate::nodes::openai_chat::OpenAIChatNodeFactory;
 use crate::nodes::remote_pipeline::RemotePipelineNodeFactory;
 use crate::nodes::session_health::SessionHealthNodeFactory;
 use crate::nodes::silence_detector::SilenceDetectorNodeFactory;
 use crate::nodes::speech_presence::SpeechPresenceNodeFactory;
+use crate::nodes::subtitles::SubtitleSinkNodeFactory;
 use crate::nodes::timing_drift::TimingDriftNodeFactory;
 
 /// Provider for core built-in nodes.
@@ -59,12 +63,13 @@ impl NodeProvider for CoreNodesProvider {
         {
             use super::streaming_registry::{
                 VideoDecoderNodeFactory, VideoEncoderNodeFactory, VideoFormatConverterNodeFactory,
-                VideoScalerNodeFactory,
+                VideoQualityNodeFactory, VideoScalerNodeFactory,
             };
             registry.register(Arc::new(VideoEncoderNodeFactory));
             registry.register(Arc::new(VideoDecoderNodeFactory));
             registry.register(Arc::new(VideoScalerNodeFactory));
             registry.register(Arc::new(VideoFormatConverterNodeFactory));
+            registry.register(Arc::new(VideoQualityNodeFactory));
         }
 
         // Audio processing nodes
@@ -108,6 +113,7 @@ impl NodeProvider for CoreNodesProvider {
         registry.register(Arc::new(ClippingDetectorNodeFactory));
         registry.register(Arc::new(ChannelBalanceNodeFactory));
         registry.register(Arc::new(SilenceDetectorNodeFactory));
+        registry.register(Arc::new(LoudnessNodeFactory));
 
         // Stream health monitoring - business layer
         registry.register(Arc::new(SpeechPresenceNodeFactory));
@@ -123,6 +129,10 @@ impl NodeProvider for CoreNodesProvider {
         use super::streaming_registry::SrtOutputNodeFactory;
         registry.register(Arc::new(SrtOutputNodeFactory));
 
+        // Media egress
+        registry.register(Arc::new(HlsSinkNodeFactory));
+        registry.register(Arc::new(SubtitleSinkNodeFactory));
+
         // llama.cpp nodes (native GGUF inference)
         #[cfg(feature = "llama-cpp")]
         {
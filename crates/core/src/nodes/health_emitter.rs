@@ -572,6 +572,47 @@ impl HealthEmitterNode {
                     active_issues.remove("DEAD_CHANNEL");
                 }
             }
+            "loudness_event" => {
+                // LoudnessNode: is_too_loud, is_too_quiet, is_true_peak_exceeded
+                let off_target = ["is_too_loud", "is_too_quiet"]
+                    .iter()
+                    .any(|k| json.get(*k).and_then(|v| v.as_bool()).unwrap_or(false));
+                if off_target {
+                    active_issues.insert("LOUDNESS_OUT_OF_RANGE".to_string());
+                } else {
+                    active_issues.remove("LOUDNESS_OUT_OF_RANGE");
+                }
+                if json
+                    .get("is_true_peak_exceeded")
+                    .and_then(|v| v.as_bool())
+                    .unwrap_or(false)
+                {
+                    active_issues.insert("TRUE_PEAK_EXCEEDED".to_string());
+                } else {
+                    active_issues.remove("TRUE_PEAK_EXCEEDED");
+                }
+            }
+            "video_quality_event" => {
+                // VideoQualityNode: is_frozen, is_black
+                if json
+                    .get("is_frozen")
+                    .and_then(|v| v.as_bool())
+                    .unwrap_or(false)
+                {
+                    active_issues.insert("FROZEN_PICTURE".to_string());
+                } else {
+                    active_issues.remove("FROZEN_PICTURE");
+                }
+                if json
+                    .get("is_black")
+                    .and_then(|v| v.as_bool())
+                    .unwrap_or(false)
+                {
+                    active_issues.insert("BLACK_FRAME".to_string());
+                } else {
+                    active_issues.remove("BLACK_FRAME");
+                }
+            }
             _ => {
                 // For other schemas or missing schema, try generic detection
                 // Look for common alert patterns
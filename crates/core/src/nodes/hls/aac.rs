@@ -0,0 +1,183 @@
+//! AAC-LC encoding for the HLS sink
+//!
+//! Uses FFmpeg's native `aac` encoder, so it needs the `video` feature
+//! (which links ac-ffmpeg). Without it, opening an encoder fails and
+//! audio input to the sink is an error.
+
+use crate::Error;
+
+/// AAC-LC encoder fed with interleaved f32 PCM
+///
+/// Buffers input until a full AAC frame (1024 samples per channel) is
+/// available and returns raw access units, without ADTS headers.
+pub(crate) struct AacEncoder {
+    sample_rate: u32,
+    channels: u16,
+    #[cfg(feature = "video")]
+    inner: ffmpeg::Encoder,
+}
+
+impl AacEncoder {
+    /// Samples per channel in one AAC-LC access unit
+    pub const FRAME_SIZE: u32 = 1024;
+
+    pub fn sample_rate(&self) -> u32 {
+        self.sample_rate
+    }
+
+    pub fn channels(&self) -> u16 {
+        self.channels
+    }
+}
+
+#[cfg(feature = "video")]
+impl AacEncoder {
+    pub fn new(sample_rate: u32, channels: u16, bitrate: u32) -> Result<Self, Error> {
+        Ok(Self {
+            sample_rate,
+            channels,
+            inner: ffmpeg::Encoder::new(sample_rate, channels, bitrate)?,
+        })
+    }
+
+    /// Encode interleaved samples, returning every completed access unit
+    pub fn encode(&mut self, interleaved: &[f32]) -> Result<Vec<Vec<u8>>, Error> {
+        self.inner.encode(interleaved)
+    }
+
+    /// Pad the last partial frame with silence and drain the encoder
+    pub fn flush(&mut self) -> Result<Vec<Vec<u8>>, Error> {
+        self.inner.flush()
+    }
+}
+
+#[cfg(not(feature = "video"))]
+impl AacEncoder {
+    pub fn new(_sample_rate: u32, _channels: u16, _bitrate: u32) -> Result<Self, Error> {
+        Err(Error::Execution(
+            "HLS audio needs AAC encoding, which requires the `video` feature (FFmpeg)".to_string(),
+        ))
+    }
+
+    pub fn encode(&mut self, _interleaved: &[f32]) -> Result<Vec<Vec<u8>>, Error> {
+        Ok(Vec::new())
+    }
+
+    pub fn flush(&mut self) -> Result<Vec<Vec<u8>>, Error> {
+        Ok(Vec::new())
+    }
+}
+
+#[cfg(feature = "video")]
+mod ffmpeg {
+    use super::AacEncoder;
+    use crate::Error;
+    use ac_ffmpeg::codec::audio::{AudioEncoder, AudioFrameMut, ChannelLayout, SampleFormat};
+    use ac_ffmpeg::codec::Encoder as _;
+    use ac_ffmpeg::time::{TimeBase, Timestamp};
+    use std::str::FromStr;
+
+    pub(super) struct Encoder {
+        encoder: AudioEncoder,
+        layout: ChannelLayout,
+        format: SampleFormat,
+        sample_rate: u32,
+        channels: usize,
+        time_base: TimeBase,
+        /// Interleaved samples not yet forming a whole frame
+        pending: Vec<f32>,
+        /// Presentation time of the next frame, in samples
+        pts: i64,
+    }
+
+    fn encode_error(e: impl std::fmt::Display) -> Error {
+        Error::Execution(format!("AAC encoding failed: {}", e))
+    }
+
+    impl Encoder {
+        pub(super) fn new(sample_rate: u32, channels: u16, bitrate: u32) -> Result<Self, Error> {
+            let layout = ChannelLayout::from_channels(u32::from(channels)).ok_or_else(|| {
+                Error::Execution(format!("No channel layout for {} channels", channels))
+            })?;
+            // The native encoder only takes planar float
+            let format = SampleFormat::from_str("fltp").map_err(encode_error)?;
+            let time_base = TimeBase::new(1, sample_rate as i32);
+
+            let encoder = AudioEncoder::builder("aac")
+                .map_err(|e| Error::Execution(format!("AAC encoder not available: {}", e)))?
+                .sample_rate(sample_rate)
+                .channel_layout(layout.clone())
+                .sample_format(format)
+                .bit_rate(u64::from(bitrate))
+                .time_base(time_base)
+                .build()
+                .map_err(|e| Error::Execution(format!("Failed to open AAC encoder: {}", e)))?;
+
+            Ok(Self {
+                encoder,
+                layout,
+                format,
+                sample_rate,
+                channels: usize::from(channels),
+                time_base,
+                pending: Vec::new(),
+                pts: 0,
+            })
+        }
+
+        pub(super) fn encode(&mut self, interleaved: &[f32]) -> Result<Vec<Vec<u8>>, Error> {
+            self.pending.extend_from_slice(interleaved);
+            let frame_len = AacEncoder::FRAME_SIZE as usize * self.channels;
+            let mut packets = Vec::new();
+            while self.pending.len() >= frame_len {
+                let frame: Vec<f32> = self.pending.drain(..frame_len).collect();
+                self.push_frame(&frame)?;
+                self.take_packets(&mut packets)?;
+            }
+            Ok(packets)
+        }
+
+        pub(super) fn flush(&mut self) -> Result<Vec<Vec<u8>>, Error> {
+            let mut packets = Vec::new();
+            if !self.pending.is_empty() {
+                let frame_len = AacEncoder::FRAME_SIZE as usize * self.channels;
+                let mut frame = std::mem::take(&mut self.pending);
+                frame.resize(frame_len, 0.0);
+                self.push_frame(&frame)?;
+                self.take_packets(&mut packets)?;
+            }
+            self.encoder.flush().map_err(encode_error)?;
+            self.take_packets(&mut packets)?;
+            Ok(packets)
+        }
+
+        /// Deinterleave one frame into FFmpeg's planes and push it
+        fn push_frame(&mut self, interleaved: &[f32]) -> Result<(), Error> {
+            let samples = AacEncoder::FRAME_SIZE as usize;
+            let mut frame =
+                AudioFrameMut::silence(&self.layout, self.format, self.sample_rate, samples);
+            {
+                let mut planes = frame.planes_mut();
+                for channel in 0..self.channels {
+                    let plane = planes[channel].data_mut();
+                    for (i, bytes) in plane.chunks_exact_mut(4).take(samples).enumerate() {
+                        let sample = interleaved[i * self.channels + channel];
+                        bytes.copy_from_slice(&sample.to_ne_bytes());
+                    }
+                }
+            }
+            let frame = frame
+                .with_pts(Timestamp::new(self.pts, self.time_base))
+                .freeze();
+            self.pts += samples as i64;
+            self.encoder.push(frame).map_err(encode_error)
+        }
+
+        fn take_packets(&mut self, packets: &mut Vec<Vec<u8>>) -> Result<(), Error> {
+            while let Some(packet) = self.encoder.take().map_err(encode_error)? {
+                packets.push(packet.data().to_vec());
+            }
+            Ok(())
+        }
+    }
+}
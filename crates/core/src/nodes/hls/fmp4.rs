@@ -0,0 +1,594 @@
+//! Fragmented MP4 (CMAF) box writer
+//!
+//! Just enough ISO BMFF to serve HLS: an init segment (`ftyp` + `moov`
+//! with empty sample tables and an `mvex`) and media fragments (`moof` +
+//! `mdat`) carrying AAC-LC and H.264 samples. Every fragment is
+//! self-contained, so a segment is simply its parts concatenated.
+
+/// Sample flags for a sync sample (`sample_depends_on = 2`)
+const SYNC_SAMPLE_FLAGS: u32 = 0x0200_0000;
+/// Sample flags for a non-sync sample (`sample_depends_on = 1`, `is_non_sync`)
+const NON_SYNC_SAMPLE_FLAGS: u32 = 0x0101_0000;
+/// `tfhd` flag: offsets are relative to the enclosing `moof`
+const TFHD_DEFAULT_BASE_IS_MOOF: u32 = 0x02_0000;
+/// `trun` flags: data offset, per-sample duration, size and flags present
+const TRUN_FLAGS: u32 = 0x0001 | 0x0100 | 0x0200 | 0x0400;
+/// Timescale for video tracks
+pub const VIDEO_TIMESCALE: u32 = 90_000;
+
+/// Codec configuration of one track
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TrackCodec {
+    /// AAC-LC; timescale is the sample rate
+    Aac {
+        sample_rate: u32,
+        channels: u16,
+        /// MPEG-4 AudioSpecificConfig
+        audio_specific_config: Vec<u8>,
+    },
+    /// H.264 with a single SPS/PPS pair; timescale is [`VIDEO_TIMESCALE`]
+    H264 {
+        width: u32,
+        height: u32,
+        sps: Vec<u8>,
+        pps: Vec<u8>,
+    },
+}
+
+/// One track of the init segment
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Track {
+    pub id: u32,
+    pub codec: TrackCodec,
+}
+
+impl Track {
+    /// Media timescale in ticks per second
+    pub fn timescale(&self) -> u32 {
+        match self.codec {
+            TrackCodec::Aac { sample_rate, .. } => sample_rate,
+            TrackCodec::H264 { .. } => VIDEO_TIMESCALE,
+        }
+    }
+
+    /// RFC 6381 codec string for the `CODECS` playlist attribute
+    pub fn codec_string(&self) -> String {
+        match &self.codec {
+            TrackCodec::Aac { .. } => "mp4a.40.2".to_string(),
+            TrackCodec::H264 { sps, .. } => format!(
+                "avc1.{:02x}{:02x}{:02x}",
+                sps.get(1).copied().unwrap_or(0),
+                sps.get(2).copied().unwrap_or(0),
+                sps.get(3).copied().unwrap_or(0)
+            ),
+        }
+    }
+}
+
+/// One media sample (an AAC access unit or a length-prefixed H.264 frame)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Sample {
+    /// Duration in track timescale ticks
+    pub duration: u32,
+    pub is_sync: bool,
+    pub data: Vec<u8>,
+}
+
+/// Samples of one track inside a fragment
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TrackRun {
+    pub track_id: u32,
+    /// Decode time of the first sample, in track timescale ticks
+    pub base_decode_time: u64,
+    pub samples: Vec<Sample>,
+}
+
+/// MPEG-4 AudioSpecificConfig for AAC-LC
+///
+/// Returns `None` for sample rates outside the standard frequency table.
+pub fn aac_audio_specific_config(sample_rate: u32, channels: u16) -> Option<Vec<u8>> {
+    const FREQUENCIES: [u32; 13] = [
+        96000, 88200, 64000, 48000, 44100, 32000, 24000, 22050, 16000, 12000, 11025, 8000, 7350,
+    ];
+    const AAC_LC: u8 = 2;
+    let index = FREQUENCIES.iter().position(|&f| f == sample_rate)? as u8;
+    if channels == 0 || channels > 7 {
+        return None;
+    }
+    let channels = channels as u8;
+    Some(vec![
+        (AAC_LC << 3) | (index >> 1),
+        ((index & 1) << 7) | (channels << 3),
+    ])
+}
+
+/// Split an Annex-B byte stream into NAL units (start codes removed)
+pub fn annex_b_nal_units(data: &[u8]) -> Vec<&[u8]> {
+    let mut units = Vec::new();
+    let mut start = None;
+    let mut i = 0;
+    while i + 3 <= data.len() {
+        if data[i] == 0 && data[i + 1] == 0 && data[i + 2] == 1 {
+            if let Some(s) = start {
+                units.push(trim_trailing_zeros(&data[s..i]));
+            }
+            i += 3;
+            start = Some(i);
+        } else {
+            i += 1;
+        }
+    }
+    if let Some(s) = start {
+        units.push(&data[s..]);
+    }
+    units.retain(|nal| !nal.is_empty());
+    units
+}
+
+/// Drop the leading zero of a 4-byte start code that belongs to the next NAL
+fn trim_trailing_zeros(nal: &[u8]) -> &[u8] {
+    let mut end = nal.len();
+    while end > 0 && nal[end - 1] == 0 {
+        end -= 1;
+    }
+    &nal[..end]
+}
+
+/// H.264 NAL unit type
+pub fn nal_type(nal: &[u8]) -> u8 {
+    nal.first().map(|b| b & 0x1f).unwrap_or(0)
+}
+
+/// NAL unit type of a sequence parameter set
+pub const NAL_SPS: u8 = 7;
+/// NAL unit type of a picture parameter set
+pub const NAL_PPS: u8 = 8;
+/// NAL unit type of an access unit delimiter
+const NAL_AUD: u8 = 9;
+
+/// Convert an Annex-B access unit into the length-prefixed form MP4 stores
+///
+/// Parameter sets and access unit delimiters are dropped; they live in
+/// the init segment's `avcC`.
+pub fn annex_b_to_length_prefixed(data: &[u8]) -> Vec<u8> {
+    let mut out = Vec::with_capacity(data.len());
+    for nal in annex_b_nal_units(data) {
+        if matches!(nal_type(nal), NAL_SPS | NAL_PPS | NAL_AUD) {
+            continue;
+        }
+        out.extend_from_slice(&(nal.len() as u32).to_be_bytes());
+        out.extend_from_slice(nal);
+    }
+    out
+}
+
+/// `ftyp` + `moov` for the given tracks
+pub fn init_segment(tracks: &[Track]) -> Vec<u8> {
+    let mut out = Vec::new();
+    write_box(&mut out, b"ftyp", |b| {
+        b.extend_from_slice(b"iso6");
+        b.extend_from_slice(&0u32.to_be_bytes());
+        for brand in [b"iso6", b"cmfc", b"mp41"] {
+            b.extend_from_slice(brand);
+        }
+    });
+    write_box(&mut out, b"moov", |moov| {
+        write_full_box(moov, b"mvhd", 0, 0, |b| {
+            b.extend_from_slice(&[0; 8]); // creation / modification time
+            b.extend_from_slice(&1000u32.to_be_bytes()); // timescale
+            b.extend_from_slice(&0u32.to_be_bytes()); // duration
+            b.extend_from_slice(&0x0001_0000u32.to_be_bytes()); // rate 1.0
+            b.extend_from_slice(&0x0100u16.to_be_bytes()); // volume 1.0
+            b.extend_from_slice(&[0; 10]);
+            write_matrix(b);
+            b.extend_from_slice(&[0; 24]); // pre_defined
+            let next_track_id = tracks.iter().map(|t| t.id).max().unwrap_or(0) + 1;
+            b.extend_from_slice(&next_track_id.to_be_bytes());
+        });
+        for track in tracks {
+            write_trak(moov, track);
+        }
+        write_box(moov, b"mvex", |mvex| {
+            for track in tracks {
+                write_full_box(mvex, b"trex", 0, 0, |b| {
+                    b.extend_from_slice(&track.id.to_be_bytes());
+                    b.extend_from_slice(&1u32.to_be_bytes()); // sample description index
+                    b.extend_from_slice(&[0; 12]); // default duration / size / flags
+                });
+            }
+        });
+    });
+    out
+}
+
+/// `moof` + `mdat` holding one run per track
+///
+/// Runs without samples are skipped.
+pub fn media_fragment(sequence_number: u32, runs: &[TrackRun]) -> Vec<u8> {
+    let runs: Vec<&TrackRun> = runs.iter().filter(|r| !r.samples.is_empty()).collect();
+
+    // The data offsets depend on the size of the moof they live in, which
+    // does not depend on their values: write it once to measure.
+    let moof_len = write_moof(sequence_number, &runs, 0).len();
+    let moof = write_moof(sequence_number, &runs, moof_len as u32 + 8);
+
+    let mut out = moof;
+    write_box(&mut out, b"mdat", |mdat| {
+        for run in &runs {
+            for sample in &run.samples {
+                mdat.extend_from_slice(&sample.data);
+            }
+        }
+    });
+    out
+}
+
+fn write_moof(sequence_number: u32, runs: &[&TrackRun], first_data_offset: u32) -> Vec<u8> {
+    let mut out = Vec::new();
+    write_box(&mut out, b"moof", |moof| {
+        write_full_box(moof, b"mfhd", 0, 0, |b| {
+            b.extend_from_slice(&sequence_number.to_be_bytes());
+        });
+        let mut data_offset = first_data_offset;
+        for run in runs {
+            write_box(moof, b"traf", |traf| {
+                write_full_box(traf, b"tfhd", 0, TFHD_DEFAULT_BASE_IS_MOOF, |b| {
+                    b.extend_from_slice(&run.track_id.to_be_bytes());
+                });
+                write_full_box(traf, b"tfdt", 1, 0, |b| {
+                    b.extend_from_slice(&run.base_decode_time.to_be_bytes());
+                });
+                write_full_box(traf, b"trun", 0, TRUN_FLAGS, |b| {
+                    b.extend_from_slice(&(run.samples.len() as u32).to_be_bytes());
+                    b.extend_from_slice(&data_offset.to_be_bytes());
+                    for sample in &run.samples {
+                        let flags = if sample.is_sync {
+                            SYNC_SAMPLE_FLAGS
+                        } else {
+                            NON_SYNC_SAMPLE_FLAGS
+                        };
+                        b.extend_from_slice(&sample.duration.to_be_bytes());
+                        b.extend_from_slice(&(sample.data.len() as u32).to_be_bytes());
+                        b.extend_from_slice(&flags.to_be_bytes());
+                    }
+                });
+            });
+            data_offset += run.samples.iter().map(|s| s.data.len() as u32).sum::<u32>();
+        }
+    });
+    out
+}
+
+fn write_trak(out: &mut Vec<u8>, track: &Track) {
+    let (width, height) = match track.codec {
+        TrackCodec::H264 { width, height, .. } => (width, height),
+        TrackCodec::Aac { .. } => (0, 0),
+    };
+    let is_audio = matches!(track.codec, TrackCodec::Aac { .. });
+
+    write_box(out, b"trak", |trak| {
+        // flags: enabled | in movie
+        write_full_box(trak, b"tkhd", 0, 0x3, |b| {
+            b.extend_from_slice(&[0; 8]); // creation / modification time
+            b.extend_from_slice(&track.id.to_be_bytes());
+            b.extend_from_slice(&[0; 4]);
+            b.extend_from_slice(&0u32.to_be_bytes()); // duration
+            b.extend_from_slice(&[0; 8]);
+            b.extend_from_slice(&[0; 4]); // layer, alternate group
+            let volume: u16 = if is_audio { 0x0100 } else { 0 };
+            b.extend_from_slice(&volume.to_be_bytes());
+            b.extend_from_slice(&[0; 2]);
+            write_matrix(b);
+            b.extend_from_slice(&(width << 16).to_be_bytes());
+            b.extend_from_slice(&(height << 16).to_be_bytes());
+        });
+        write_box(trak, b"mdia", |mdia| {
+            write_full_box(mdia, b"mdhd", 0, 0, |b| {
+                b.extend_from_slice(&[0; 8]); // creation / modification time
+                b.extend_from_slice(&track.timescale().to_be_bytes());
+                b.extend_from_slice(&0u32.to_be_bytes()); // duration
+                b.extend_from_slice(&0x55c4u16.to_be_bytes()); // language "und"
+                b.extend_from_slice(&[0; 2]);
+            });
+            write_full_box(mdia, b"hdlr", 0, 0, |b| {
+                b.extend_from_slice(&[0; 4]);
+                b.extend_from_slice(if is_audio { b"soun" } else { b"vide" });
+                b.extend_from_slice(&[0; 12]);
+                let name: &[u8] = if is_audio {
+                    b"SoundHandler\0"
+                } else {
+                    b"VideoHandler\0"
+                };
+                b.extend_from_slice(name);
+            });
+            write_box(mdia, b"minf", |minf| {
+                if is_audio {
+                    write_full_box(minf, b"smhd", 0, 0, |b| b.extend_from_slice(&[0; 4]));
+                } else {
+                    write_full_box(minf, b"vmhd", 0, 1, |b| b.extend_from_slice(&[0; 8]));
+                }
+                write_box(minf, b"dinf", |dinf| {
+                    write_full_box(dinf, b"dref", 0, 0, |b| {
+                        b.extend_from_slice(&1u32.to_be_bytes());
+                        // Self-contained: media data is in the same file
+                        write_full_box(b, b"url ", 0, 1, |_| {});
+                    });
+                });
+                write_box(minf, b"stbl", |stbl| {
+                    write_full_box(stbl, b"stsd", 0, 0, |b| {
+                        b.extend_from_slice(&1u32.to_be_bytes());
+                        write_sample_entry(b, &track.codec);
+                    });
+                    for empty in [b"stts", b"stsc", b"stco"] {
+                        write_full_box(stbl, empty, 0, 0, |b| b.extend_from_slice(&[0; 4]));
+                    }
+                    write_full_box(stbl, b"stsz", 0, 0, |b| b.extend_from_slice(&[0; 8]));
+                });
+            });
+        });
+    });
+}
+
+fn write_sample_entry(out: &mut Vec<u8>, codec: &TrackCodec) {
+    match codec {
+        TrackCodec::Aac {
+            sample_rate,
+            channels,
+            audio_specific_config,
+        } => write_box(out, b"mp4a", |b| {
+            b.extend_from_slice(&[0; 6]);
+            b.extend_from_slice(&1u16.to_be_bytes()); // data reference index
+            b.extend_from_slice(&[0; 8]);
+            b.extend_from_slice(&channels.to_be_bytes());
+            b.extend_from_slice(&16u16.to_be_bytes()); // sample size
+            b.extend_from_slice(&[0; 4]);
+            // 16.16 fixed point; rates above 65535 Hz do not fit and are
+            // taken from the AudioSpecificConfig by decoders anyway.
+            let rate = if *sample_rate <= 0xffff {
+                sample_rate << 16
+            } else {
+                0
+            };
+            b.extend_from_slice(&rate.to_be_bytes());
+            write_full_box(b, b"esds", 0, 0, |esds| {
+                write_esds(esds, audio_specific_config);
+            });
+        }),
+        TrackCodec::H264 {
+            width,
+            height,
+            sps,
+            pps,
+        } => write_box(out, b"avc1", |b| {
+            b.extend_from_slice(&[0; 6]);
+            b.extend_from_slice(&1u16.to_be_bytes()); // data reference index
+            b.extend_from_slice(&[0; 16]);
+            b.extend_from_slice(&(*width as u16).to_be_bytes());
+            b.extend_from_slice(&(*height as u16).to_be_bytes());
+            b.extend_from_slice(&0x0048_0000u32.to_be_bytes()); // 72 dpi
+            b.extend_from_slice(&0x0048_0000u32.to_be_bytes());
+            b.extend_from_slice(&[0; 4]);
+            b.extend_from_slice(&1u16.to_be_bytes()); // frame count
+            b.extend_from_slice(&[0; 32]); // compressor name
+            b.extend_from_slice(&0x0018u16.to_be_bytes()); // depth
+            b.extend_from_slice(&(-1i16).to_be_bytes());
+            write_box(b, b"avcC", |c| {
+                c.push(1); // configuration version
+                c.extend_from_slice(&[
+                    sps.get(1).copied().unwrap_or(0),
+                    sps.get(2).copied().unwrap_or(0),
+                    sps.get(3).copied().unwrap_or(0),
+                ]);
+                c.push(0xff); // 4-byte NAL lengths
+                c.push(0xe1); // one SPS
+                c.extend_from_slice(&(sps.len() as u16).to_be_bytes());
+                c.extend_from_slice(sps);
+                c.push(1); // one PPS
+                c.extend_from_slice(&(pps.len() as u16).to_be_bytes());
+                c.extend_from_slice(pps);
+            });
+        }),
+    }
+}
+
+/// ES_Descriptor for an AAC track (ISO/IEC 14496-1 §7.2.6.5)
+fn write_esds(out: &mut Vec<u8>, audio_specific_config: &[u8]) {
+    let mut decoder_specific = Vec::new();
+    write_descriptor(&mut decoder_specific, 0x05, audio_specific_config);
+
+    let mut decoder_config = vec![
+        0x40, // Audio ISO/IEC 14496-3
+        0x15, // audio stream
+        0, 0, 0, // buffer size
+    ];
+    decoder_config.extend_from_slice(&[0; 8]); // max / average bitrate
+    decoder_config.extend_from_slice(&decoder_specific);
+
+    let mut es = vec![0, 0, 0]; // ES_ID, flags
+    write_descriptor(&mut es, 0x04, &decoder_config);
+    write_descriptor(&mut es, 0x06, &[0x02]); // SLConfig: predefined MP4
+
+    write_descriptor(out, 0x03, &es);
+}
+
+fn write_descriptor(out: &mut Vec<u8>, tag: u8, body: &[u8]) {
+    out.push(tag);
+    // Four-byte length form, valid for any descriptor size we write
+    let len = body.len() as u32;
+    out.extend_from_slice(&[
+        0x80 | ((len >> 21) & 0x7f) as u8,
+        0x80 | ((len >> 14) & 0x7f) as u8,
+        0x80 | ((len >> 7) & 0x7f) as u8,
+        (len & 0x7f) as u8,
+    ]);
+    out.extend_from_slice(body);
+}
+
+fn write_matrix(out: &mut Vec<u8>) {
+    for value in [0x0001_0000u32, 0, 0, 0, 0x0001_0000, 0, 0, 0, 0x4000_0000] {
+        out.extend_from_slice(&value.to_be_bytes());
+    }
+}
+
+fn write_box(out: &mut Vec<u8>, kind: &[u8; 4], body: impl FnOnce(&mut Vec<u8>)) {
+    let start = out.len();
+    out.extend_from_slice(&[0; 4]);
+    out.extend_from_slice(kind);
+    body(out);
+    let size = (out.len() - start) as u32;
+    out[start..start + 4].copy_from_slice(&size.to_be_bytes());
+}
+
+fn write_full_box(
+    out: &mut Vec<u8>,
+    kind: &[u8; 4],
+    version: u8,
+    flags: u32,
+    body: impl FnOnce(&mut Vec<u8>),
+) {
+    write_box(out, kind, |b| {
+        b.extend_from_slice(&((u32::from(version) << 24) | (flags & 0x00ff_ffff)).to_be_bytes());
+        body(b);
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Top-level `(type, size)` pairs of an ISO BMFF buffer
+    fn boxes(data: &[u8]) -> Vec<(String, usize)> {
+        let mut out = Vec::new();
+        let mut offset = 0;
+        while offset + 8 <= data.len() {
+            let size = u32::from_be_bytes(data[offset..offset + 4].try_into().unwrap()) as usize;
+            let kind = String::from_utf8_lossy(&data[offset + 4..offset + 8]).into_owned();
+            out.push((kind, size));
+            offset += size;
+        }
+        assert_eq!(offset, data.len(), "box sizes must cover the buffer");
+        out
+    }
+
+    fn find(data: &[u8], kind: &[u8; 4]) -> usize {
+        data.windows(4)
+            .position(|w| w == kind)
+            .expect("box present")
+            - 4
+    }
+
+    #[test]
+    fn test_audio_specific_config() {
+        // AAC-LC, 48 kHz (index 3), stereo
+        assert_eq!(aac_audio_specific_config(48000, 2), Some(vec![0x11, 0x90]));
+        // AAC-LC, 44.1 kHz (index 4), mono
+        assert_eq!(aac_audio_specific_config(44100, 1), Some(vec![0x12, 0x08]));
+        assert_eq!(aac_audio_specific_config(47999, 2), None);
+    }
+
+    #[test]
+    fn test_annex_b_conversion() {
+        let annex_b = [
+            0, 0, 0, 1, 0x67, 0x64, 0x00, 0x1f, // SPS
+            0, 0, 1, 0x68, 0xee, // PPS
+            0, 0, 0, 1, 0x65, 0xaa, 0xbb, // IDR slice
+        ];
+        let nals = annex_b_nal_units(&annex_b);
+        assert_eq!(nals.len(), 3);
+        assert_eq!(nal_type(nals[0]), NAL_SPS);
+        assert_eq!(nal_type(nals[1]), NAL_PPS);
+
+        assert_eq!(
+            annex_b_to_length_prefixed(&annex_b),
+            vec![0, 0, 0, 3, 0x65, 0xaa, 0xbb]
+        );
+    }
+
+    #[test]
+    fn test_init_segment_layout() {
+        let tracks = [
+            Track {
+                id: 1,
+                codec: TrackCodec::H264 {
+                    width: 640,
+                    height: 360,
+                    sps: vec![0x67, 0x64, 0x00, 0x1f],
+                    pps: vec![0x68, 0xee],
+                },
+            },
+            Track {
+                id: 2,
+                codec: TrackCodec::Aac {
+                    sample_rate: 48000,
+                    channels: 2,
+                    audio_specific_config: vec![0x11, 0x90],
+                },
+            },
+        ];
+        let init = init_segment(&tracks);
+        let kinds: Vec<String> = boxes(&init).into_iter().map(|(k, _)| k).collect();
+        assert_eq!(kinds, ["ftyp", "moov"]);
+        find(&init, b"avcC");
+        find(&init, b"esds");
+        assert_eq!(init.windows(4).filter(|w| w == b"trex").count(), 2);
+        assert_eq!(tracks[0].codec_string(), "avc1.64001f");
+        assert_eq!(tracks[1].codec_string(), "mp4a.40.2");
+    }
+
+    #[test]
+    fn test_fragment_data_offsets_point_into_mdat() {
+        let runs = [
+            TrackRun {
+                track_id: 1,
+                base_decode_time: 9000,
+                samples: vec![Sample {
+                    duration: 3000,
+                    is_sync: true,
+                    data: vec![0xaa; 5],
+                }],
+            },
+            TrackRun {
+                track_id: 2,
+                base_decode_time: 0,
+                samples: Vec::new(),
+            },
+            TrackRun {
+                track_id: 3,
+                base_decode_time: 4800,
+                samples: vec![
+                    Sample {
+                        duration: 1024,
+                        is_sync: true,
+                        data: vec![0xbb; 3],
+                    },
+                    Sample {
+                        duration: 1024,
+                        is_sync: true,
+                        data: vec![0xcc; 2],
+                    },
+                ],
+            },
+        ];
+        let fragment = media_fragment(7, &runs);
+        let top = boxes(&fragment);
+        assert_eq!(top[0].0, "moof");
+        assert_eq!(top[1], ("mdat".to_string(), 8 + 10));
+
+        // Empty runs are not written
+        assert_eq!(fragment.windows(4).filter(|w| w == b"traf").count(), 2);
+
+        // Each trun's data offset lands on that track's first sample
+        let mut offsets = Vec::new();
+        let mut search = 0;
+        while let Some(pos) = fragment[search..].windows(4).position(|w| w == b"trun") {
+            let trun = search + pos - 4;
+            let offset_at = trun + 16;
+            offsets.push(
+                u32::from_be_bytes(fragment[offset_at..offset_at + 4].try_into().unwrap()) as usize,
+            );
+            search = trun + 8;
+        }
+        assert_eq!(fragment[offsets[0]], 0xaa);
+        assert_eq!(fragment[offsets[1]], 0xbb);
+    }
+}
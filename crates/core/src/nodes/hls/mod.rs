@@ -0,0 +1,442 @@
+//! HLS / LL-HLS egress
+//!
+//! [`HlsSinkNode`] muxes pipeline audio (encoded to AAC-LC) and H.264
+//! video into fragmented-MP4 segments with a rolling media playlist, so a
+//! processed stream can be watched in any HLS player.
+//!
+//! - **Audio**: raw `RuntimeData::Audio` is encoded here; this needs the
+//!   `video` feature, which provides FFmpeg.
+//! - **Video**: frames must already be H.264 (put a `VideoEncoderNode`
+//!   with `codec: h264` upstream). Segments start on keyframes, so the
+//!   encoder's `keyframe_interval` bounds how closely `segment_duration`
+//!   is met.
+//! - **LL-HLS**: set `part_duration` to publish partial segments with
+//!   `EXT-X-PART`, `EXT-X-PRELOAD-HINT` and blocking playlist reload.
+//!
+//! Output goes to `output_dir`, to an in-memory stream registered under
+//! `stream_name` (see [`streams`]), or both. `remotemedia-http` serves
+//! in-memory streams at `/hls/{stream_name}/index.m3u8`. When the node is
+//! dropped the last segment is flushed, the playlist ended and the
+//! in-memory stream unregistered.
+//!
+//! ```yaml
+//! - id: hls
+//!   node_type: HlsSinkNode
+//!   params:
+//!     stream_name: "dub-{session_id}"
+//!     segment_duration: 2.0
+//!     part_duration: 0.5
+//!     video: true
+//! ```
+
+mod aac;
+pub mod fmp4;
+mod muxer;
+pub mod playlist;
+mod store;
+
+pub use store::{streams, HlsStream, HlsStreams};
+
+use crate::data::video::VideoCodec;
+use crate::data::RuntimeData;
+use crate::nodes::{StreamingNode, SyncNodeWrapper, SyncStreamingNode};
+use crate::Error;
+use aac::AacEncoder;
+use muxer::{HlsMuxer, MuxerConfig};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use store::HlsOutput;
+
+/// Placeholder in `output_dir` / `stream_name` replaced by the session id
+const SESSION_PLACEHOLDER: &str = "{session_id}";
+
+/// Configuration for [`HlsSinkNode`]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct HlsSinkConfig {
+    /// Directory for the playlist (`index.m3u8`), `init.mp4` and segments
+    pub output_dir: Option<String>,
+    /// Name of the in-memory stream served over HTTP
+    pub stream_name: Option<String>,
+    /// Target segment duration in seconds
+    pub segment_duration: f64,
+    /// LL-HLS part duration in seconds; unset for plain HLS
+    pub part_duration: Option<f64>,
+    /// Completed segments kept in the playlist; older files are deleted
+    pub playlist_size: usize,
+    /// Mux an audio track
+    pub audio: bool,
+    /// Mux an H.264 video track
+    pub video: bool,
+    /// AAC bitrate in bits per second
+    pub audio_bitrate: u32,
+}
+
+impl Default for HlsSinkConfig {
+    fn default() -> Self {
+        Self {
+            output_dir: None,
+            stream_name: None,
+            segment_duration: 4.0,
+            part_duration: None,
+            playlist_size: 6,
+            audio: true,
+            video: false,
+            audio_bitrate: 128_000,
+        }
+    }
+}
+
+impl HlsSinkConfig {
+    fn validate(&self) -> Result<(), Error> {
+        if self.output_dir.is_none() && self.stream_name.is_none() {
+            return Err(Error::InvalidManifest(
+                "HlsSinkNode needs `output_dir`, `stream_name` or both".to_string(),
+            ));
+        }
+        if !self.audio && !self.video {
+            return Err(Error::InvalidManifest(
+                "HlsSinkNode needs at least one of `audio` / `video`".to_string(),
+            ));
+        }
+        if self.segment_duration.is_nan() || self.segment_duration <= 0.0 {
+            return Err(Error::InvalidManifest(
+                "HlsSinkNode `segment_duration` must be positive".to_string(),
+            ));
+        }
+        if let Some(part) = self.part_duration {
+            if part.is_nan() || part <= 0.0 || part > self.segment_duration {
+                return Err(Error::InvalidManifest(
+                    "HlsSinkNode `part_duration` must be positive and at most `segment_duration`"
+                        .to_string(),
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+struct SinkState {
+    muxer: HlsMuxer,
+    encoder: Option<AacEncoder>,
+}
+
+impl Drop for SinkState {
+    fn drop(&mut self) {
+        // Trailing audio still in the encoder belongs in the last segment;
+        // the muxer finishes the playlist when it drops right after this.
+        if let Some(encoder) = &mut self.encoder {
+            match encoder.flush() {
+                Ok(access_units) => {
+                    for access_unit in access_units {
+                        let _ = self.muxer.push_audio(access_unit, AacEncoder::FRAME_SIZE);
+                    }
+                }
+                Err(e) => tracing::warn!("Failed to flush AAC encoder: {}", e),
+            }
+        }
+    }
+}
+
+/// Sink that writes pipeline media as an HLS stream
+///
+/// Emits a JSON `hls_segment` event each time a segment is published.
+pub struct HlsSinkNode {
+    node_id: String,
+    config: HlsSinkConfig,
+    state: Mutex<SinkState>,
+}
+
+impl HlsSinkNode {
+    /// Create the sink, opening its output directory and/or stream
+    pub fn new(node_id: String, config: HlsSinkConfig) -> Result<Self, Error> {
+        config.validate()?;
+        let output = HlsOutput::open(
+            config.output_dir.as_ref().map(PathBuf::from),
+            config.stream_name.clone(),
+        )?;
+        let muxer = HlsMuxer::new(
+            MuxerConfig {
+                segment_duration: config.segment_duration,
+                part_duration: config.part_duration,
+                playlist_size: config.playlist_size,
+                audio: config.audio,
+                video: config.video,
+            },
+            output,
+        );
+        Ok(Self {
+            node_id,
+            config,
+            state: Mutex::new(SinkState {
+                muxer,
+                encoder: None,
+            }),
+        })
+    }
+
+    fn mux(&self, data: RuntimeData) -> Result<Vec<Value>, Error> {
+        let mut state = self
+            .state
+            .lock()
+            .map_err(|e| Error::Execution(format!("HlsSinkNode state poisoned: {}", e)))?;
+        let state = &mut *state;
+
+        match data {
+            RuntimeData::Audio {
+                samples,
+                sample_rate,
+                channels,
+                ..
+            } if self.config.audio => {
+                let channels = channels as u16;
+                if let Some(encoder) = &state.encoder {
+                    if (encoder.sample_rate(), encoder.channels()) != (sample_rate, channels) {
+                        return Err(Error::Execution(format!(
+                            "HlsSinkNode audio format changed from {} Hz / {} ch to {} Hz / {} ch",
+                            encoder.sample_rate(),
+                            encoder.channels(),
+                            sample_rate,
+                            channels
+                        )));
+                    }
+                } else {
+                    state.encoder = Some(AacEncoder::new(
+                        sample_rate,
+                        channels,
+                        self.config.audio_bitrate,
+                    )?);
+                }
+                let Some(encoder) = state.encoder.as_mut() else {
+                    return Ok(Vec::new());
+                };
+                if !state.muxer.has_audio_format() {
+                    state.muxer.set_audio_format(sample_rate, channels)?;
+                }
+
+                let mut events = Vec::new();
+                for access_unit in encoder.encode(&samples)? {
+                    events.extend(
+                        state
+                            .muxer
+                            .push_audio(access_unit, AacEncoder::FRAME_SIZE)?,
+                    );
+                }
+                Ok(events)
+            }
+            RuntimeData::Video {
+                pixel_data,
+                width,
+                height,
+                codec,
+                timestamp_us,
+                is_keyframe,
+                ..
+            } if self.config.video => match codec {
+                Some(VideoCodec::H264) => {
+                    state
+                        .muxer
+                        .push_video(&pixel_data, timestamp_us, is_keyframe, width, height)
+                }
+                Some(other) => Err(Error::Execution(format!(
+                    "HlsSinkNode needs H.264 video, got {:?}",
+                    other
+                ))),
+                None => Err(Error::Execution(
+                    "HlsSinkNode needs encoded video; add a VideoEncoderNode with codec h264 upstream"
+                        .to_string(),
+                )),
+            },
+            // Tracks that are not enabled, and non-media data, are ignored
+            _ => Ok(Vec::new()),
+        }
+    }
+}
+
+impl SyncStreamingNode for HlsSinkNode {
+    fn node_type(&self) -> &str {
+        "HlsSinkNode"
+    }
+
+    fn process(&self, data: RuntimeData) -> Result<RuntimeData, Error> {
+        let events = self.mux(data)?;
+        Ok(RuntimeData::Json(Value::Array(events)))
+    }
+
+    fn process_streaming(
+        &self,
+        data: RuntimeData,
+        _session_id: Option<&str>,
+        callback: &mut dyn FnMut(RuntimeData) -> Result<(), Error>,
+    ) -> Result<usize, Error> {
+        let events = self.mux(data)?;
+        let count = events.len();
+        for mut event in events {
+            event["node_id"] = Value::String(self.node_id.clone());
+            callback(RuntimeData::Json(event))?;
+        }
+        Ok(count)
+    }
+}
+
+/// Factory for creating HlsSinkNode instances
+pub struct HlsSinkNodeFactory;
+
+impl crate::nodes::StreamingNodeFactory for HlsSinkNodeFactory {
+    fn create(
+        &self,
+        node_id: String,
+        params: &Value,
+        session_id: Option<String>,
+    ) -> Result<Box<dyn StreamingNode>, Error> {
+        let mut config: HlsSinkConfig = if params.is_null() {
+            HlsSinkConfig::default()
+        } else {
+            serde_json::from_value(params.clone())
+                .map_err(|e| Error::InvalidManifest(format!("HlsSinkNode params: {}", e)))?
+        };
+
+        // Concurrent sessions need distinct outputs
+        let session = session_id.as_deref().unwrap_or(&node_id).to_string();
+        for value in [&mut config.output_dir, &mut config.stream_name]
+            .into_iter()
+            .flatten()
+        {
+            *value = value.replace(SESSION_PLACEHOLDER, &session);
+        }
+
+        Ok(Box::new(SyncNodeWrapper(HlsSinkNode::new(
+            node_id, config,
+        )?)))
+    }
+
+    fn node_type(&self) -> &str {
+        "HlsSinkNode"
+    }
+
+    fn is_multi_output_streaming(&self) -> bool {
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::video::PixelFormat;
+    use crate::nodes::StreamingNodeFactory;
+
+    fn h264_frame(frame_number: u64, keyframe: bool) -> RuntimeData {
+        let pixel_data = if keyframe {
+            vec![
+                0, 0, 0, 1, 0x67, 0x42, 0xc0, 0x1e, 0, 0, 0, 1, 0x68, 0xce, 0, 0, 0, 1, 0x65, 0x88,
+            ]
+        } else {
+            vec![0, 0, 0, 1, 0x41, 0x9a]
+        };
+        RuntimeData::Video {
+            pixel_data,
+            width: 320,
+            height: 240,
+            format: PixelFormat::Encoded,
+            codec: Some(VideoCodec::H264),
+            frame_number,
+            timestamp_us: frame_number * 40_000,
+            is_keyframe: keyframe,
+            stream_id: None,
+            arrival_ts_us: None,
+        }
+    }
+
+    #[test]
+    fn test_config_validation() {
+        let node = |params: Value| {
+            HlsSinkNodeFactory
+                .create("hls".to_string(), &params, None)
+                .map(|_| ())
+        };
+        assert!(node(serde_json::json!({})).is_err());
+        assert!(node(serde_json::json!({"stream_name": "x", "audio": false})).is_err());
+        assert!(node(serde_json::json!({
+            "stream_name": "x",
+            "segment_duration": 1.0,
+            "part_duration": 2.0
+        }))
+        .is_err());
+    }
+
+    #[test]
+    fn test_output_dir_per_session() {
+        let dir = tempfile::tempdir().unwrap();
+        HlsSinkNodeFactory
+            .create(
+                "hls".to_string(),
+                &serde_json::json!({ "output_dir": dir.path().join("{session_id}") }),
+                Some("session-1".to_string()),
+            )
+            .unwrap();
+        assert!(dir.path().join("session-1").is_dir());
+    }
+
+    #[test]
+    fn test_video_to_disk() {
+        let dir = tempfile::tempdir().unwrap();
+        let node = HlsSinkNode::new(
+            "hls".to_string(),
+            HlsSinkConfig {
+                output_dir: Some(dir.path().to_string_lossy().into_owned()),
+                segment_duration: 1.0,
+                playlist_size: 2,
+                audio: false,
+                video: true,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        let mut events = Vec::new();
+        for i in 0..151 {
+            node.process_streaming(h264_frame(i, i % 25 == 0), None, &mut |event| {
+                events.push(event);
+                Ok(())
+            })
+            .unwrap();
+        }
+        // Keyframes every second: a segment per keyframe interval, the
+        // last one still open
+        assert_eq!(events.len(), 5);
+        assert!(matches!(&events[0], RuntimeData::Json(e) if e["node_id"] == "hls"));
+
+        // Only the playlist window stays on disk
+        assert!(dir.path().join("init.mp4").exists());
+        assert!(!dir.path().join("seg2.m4s").exists());
+        assert!(dir.path().join("seg3.m4s").exists());
+        assert!(dir.path().join("seg4.m4s").exists());
+
+        // Dropping the node flushes the open segment and ends the playlist
+        drop(node);
+        let playlist = std::fs::read_to_string(dir.path().join("index.m3u8")).unwrap();
+        assert!(playlist.ends_with("#EXT-X-ENDLIST\n"), "{}", playlist);
+        assert!(dir.path().join("seg5.m4s").exists());
+    }
+
+    #[test]
+    fn test_raw_video_is_rejected() {
+        let node = HlsSinkNode::new(
+            "hls".to_string(),
+            HlsSinkConfig {
+                stream_name: Some("node-test-raw".to_string()),
+                audio: false,
+                video: true,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        let mut frame = h264_frame(0, true);
+        if let RuntimeData::Video { codec, .. } = &mut frame {
+            *codec = None;
+        }
+        assert!(node.process(frame).is_err());
+    }
+}
@@ -0,0 +1,500 @@
+//! Cuts encoded samples into fMP4 parts and segments and keeps the
+//! playlist current
+
+use super::fmp4::{self, Sample, Track, TrackCodec, TrackRun, VIDEO_TIMESCALE};
+use super::playlist::{self, MediaPlaylist, Part, INIT_SEGMENT};
+use super::store::HlsOutput;
+use crate::Error;
+use serde_json::{json, Value};
+
+/// Segmenting settings
+#[derive(Debug, Clone)]
+pub(crate) struct MuxerConfig {
+    pub segment_duration: f64,
+    /// LL-HLS part duration; `None` publishes whole segments only
+    pub part_duration: Option<f64>,
+    pub playlist_size: usize,
+    pub audio: bool,
+    pub video: bool,
+}
+
+/// One track's codec and timeline
+struct TrackState {
+    track: Track,
+    /// Decode time of the next sample, in track timescale ticks
+    next_decode_time: u64,
+    /// Samples of the part being built
+    run: Vec<Sample>,
+    /// Decode time of the first sample in `run`
+    run_start: u64,
+}
+
+impl TrackState {
+    fn new(id: u32, codec: TrackCodec) -> Self {
+        Self {
+            track: Track { id, codec },
+            next_decode_time: 0,
+            run: Vec::new(),
+            run_start: 0,
+        }
+    }
+
+    fn push(&mut self, sample: Sample) {
+        if self.run.is_empty() {
+            self.run_start = self.next_decode_time;
+        }
+        self.next_decode_time += u64::from(sample.duration);
+        self.run.push(sample);
+    }
+
+    fn take_run(&mut self) -> TrackRun {
+        TrackRun {
+            track_id: self.track.id,
+            base_decode_time: self.run_start,
+            samples: std::mem::take(&mut self.run),
+        }
+    }
+}
+
+/// A video frame waiting for its successor to learn its duration
+struct PendingFrame {
+    timestamp_us: u64,
+    is_sync: bool,
+    data: Vec<u8>,
+}
+
+/// Encoded samples in, HLS files out
+pub(crate) struct HlsMuxer {
+    config: MuxerConfig,
+    output: HlsOutput,
+    playlist: MediaPlaylist,
+    audio: Option<TrackState>,
+    video: Option<TrackState>,
+    /// Init segment written; samples are accepted from here on
+    started: bool,
+    /// `timestamp_us` of the first video frame, the video timeline origin
+    video_origin_us: Option<u64>,
+    pending_video: Option<PendingFrame>,
+    /// Last video frame duration, used for the final frame
+    last_video_duration: u32,
+    fragment_sequence: u32,
+    /// Primary-track ticks in the open part / segment
+    part_elapsed: u64,
+    segment_elapsed: u64,
+    /// Fragments of the open segment
+    segment_data: Vec<u8>,
+    finished: bool,
+}
+
+impl HlsMuxer {
+    pub fn new(config: MuxerConfig, output: HlsOutput) -> Self {
+        let playlist = MediaPlaylist::new(
+            config.segment_duration,
+            config.part_duration,
+            config.playlist_size,
+        );
+        Self {
+            config,
+            output,
+            playlist,
+            audio: None,
+            video: None,
+            started: false,
+            video_origin_us: None,
+            pending_video: None,
+            last_video_duration: VIDEO_TIMESCALE / 30,
+            fragment_sequence: 1,
+            part_elapsed: 0,
+            segment_elapsed: 0,
+            segment_data: Vec::new(),
+            finished: false,
+        }
+    }
+
+    /// Whether the audio track is configured
+    pub fn has_audio_format(&self) -> bool {
+        self.audio.is_some()
+    }
+
+    /// Configure the audio track from the encoder's format
+    pub fn set_audio_format(&mut self, sample_rate: u32, channels: u16) -> Result<(), Error> {
+        let audio_specific_config = fmp4::aac_audio_specific_config(sample_rate, channels)
+            .ok_or_else(|| {
+                Error::Execution(format!(
+                    "AAC cannot carry {} Hz / {} channel audio",
+                    sample_rate, channels
+                ))
+            })?;
+        let id = if self.config.video { 2 } else { 1 };
+        self.audio = Some(TrackState::new(
+            id,
+            TrackCodec::Aac {
+                sample_rate,
+                channels,
+                audio_specific_config,
+            },
+        ));
+        self.try_start()
+    }
+
+    /// Add one AAC access unit
+    pub fn push_audio(&mut self, access_unit: Vec<u8>, duration: u32) -> Result<Vec<Value>, Error> {
+        if !self.started {
+            return Ok(Vec::new());
+        }
+        let mut events = Vec::new();
+        if !self.config.video {
+            self.before_primary_sample(true, &mut events)?;
+            self.part_elapsed += u64::from(duration);
+            self.segment_elapsed += u64::from(duration);
+        }
+        if let Some(audio) = &mut self.audio {
+            audio.push(Sample {
+                duration,
+                is_sync: true,
+                data: access_unit,
+            });
+        }
+        Ok(events)
+    }
+
+    /// Add one encoded H.264 access unit (Annex-B)
+    ///
+    /// Frames before the first keyframe carrying SPS and PPS are dropped.
+    pub fn push_video(
+        &mut self,
+        annex_b: &[u8],
+        timestamp_us: u64,
+        is_keyframe: bool,
+        width: u32,
+        height: u32,
+    ) -> Result<Vec<Value>, Error> {
+        if self.video.is_none() {
+            if !is_keyframe {
+                return Ok(Vec::new());
+            }
+            let nals = fmp4::annex_b_nal_units(annex_b);
+            let find = |kind| nals.iter().find(|n| fmp4::nal_type(n) == kind);
+            let (Some(sps), Some(pps)) = (find(fmp4::NAL_SPS), find(fmp4::NAL_PPS)) else {
+                return Ok(Vec::new());
+            };
+            self.video = Some(TrackState::new(
+                1,
+                TrackCodec::H264 {
+                    width,
+                    height,
+                    sps: sps.to_vec(),
+                    pps: pps.to_vec(),
+                },
+            ));
+            self.try_start()?;
+        }
+        if !self.started {
+            return Ok(Vec::new());
+        }
+
+        let origin = *self.video_origin_us.get_or_insert(timestamp_us);
+        if timestamp_us < origin {
+            return Ok(Vec::new());
+        }
+        let mut events = Vec::new();
+        if let Some(previous) = self.pending_video.take() {
+            let ticks = (timestamp_us.saturating_sub(previous.timestamp_us)
+                * u64::from(VIDEO_TIMESCALE)
+                / 1_000_000)
+                .max(1) as u32;
+            self.push_video_sample(previous, ticks, &mut events)?;
+        }
+        self.pending_video = Some(PendingFrame {
+            timestamp_us,
+            is_sync: is_keyframe,
+            data: fmp4::annex_b_to_length_prefixed(annex_b),
+        });
+        Ok(events)
+    }
+
+    /// Flush everything buffered and end the playlist
+    pub fn finish(&mut self) -> Result<Vec<Value>, Error> {
+        if self.finished {
+            return Ok(Vec::new());
+        }
+        self.finished = true;
+        let mut events = Vec::new();
+        if !self.started {
+            return Ok(events);
+        }
+        if let Some(last) = self.pending_video.take() {
+            let duration = self.last_video_duration;
+            self.push_video_sample(last, duration, &mut events)?;
+        }
+        self.flush_part()?;
+        if !self.segment_data.is_empty() {
+            events.push(self.close_segment()?);
+        }
+        self.playlist.end();
+        self.output.write_playlist(&self.playlist)?;
+        Ok(events)
+    }
+
+    /// Timescale of the track segments are cut on: video if muxed, else audio
+    fn primary_timescale(&self) -> u32 {
+        let primary = if self.config.video {
+            &self.video
+        } else {
+            &self.audio
+        };
+        primary.as_ref().map(|t| t.track.timescale()).unwrap_or(1)
+    }
+
+    /// `seconds` in primary-track ticks
+    fn ticks(&self, seconds: f64) -> u64 {
+        (seconds * f64::from(self.primary_timescale())).round() as u64
+    }
+
+    /// Primary-track `ticks` in seconds
+    fn seconds(&self, ticks: u64) -> f64 {
+        ticks as f64 / f64::from(self.primary_timescale())
+    }
+
+    fn push_video_sample(
+        &mut self,
+        frame: PendingFrame,
+        duration: u32,
+        events: &mut Vec<Value>,
+    ) -> Result<(), Error> {
+        self.before_primary_sample(frame.is_sync, events)?;
+        self.part_elapsed += u64::from(duration);
+        self.segment_elapsed += u64::from(duration);
+        self.last_video_duration = duration;
+        if let Some(video) = &mut self.video {
+            video.push(Sample {
+                duration,
+                is_sync: frame.is_sync,
+                data: frame.data,
+            });
+        }
+        Ok(())
+    }
+
+    /// Write the init segment once every enabled track is configured
+    fn try_start(&mut self) -> Result<(), Error> {
+        if self.started
+            || (self.config.audio && self.audio.is_none())
+            || (self.config.video && self.video.is_none())
+        {
+            return Ok(());
+        }
+        let tracks: Vec<Track> = [&self.video, &self.audio]
+            .into_iter()
+            .flatten()
+            .map(|t| t.track.clone())
+            .collect();
+        self.output
+            .write(INIT_SEGMENT, fmp4::init_segment(&tracks))?;
+        self.output.write_playlist(&self.playlist)?;
+        self.started = true;
+        tracing::info!(
+            "HLS output started ({})",
+            tracks
+                .iter()
+                .map(Track::codec_string)
+                .collect::<Vec<_>>()
+                .join(", ")
+        );
+        Ok(())
+    }
+
+    /// Cut the part, and the segment at a sync sample, before adding the
+    /// next primary-track sample
+    fn before_primary_sample(
+        &mut self,
+        is_sync: bool,
+        events: &mut Vec<Value>,
+    ) -> Result<(), Error> {
+        let segment_target = self.ticks(self.config.segment_duration);
+        let part_target = self.ticks(
+            self.config
+                .part_duration
+                .unwrap_or(self.config.segment_duration),
+        );
+        if is_sync && self.segment_elapsed >= segment_target {
+            self.flush_part()?;
+            if !self.segment_data.is_empty() {
+                events.push(self.close_segment()?);
+            }
+        } else if self.part_elapsed >= part_target {
+            self.flush_part()?;
+        }
+        Ok(())
+    }
+
+    /// Write the open part as one fragment
+    fn flush_part(&mut self) -> Result<(), Error> {
+        let independent = match &self.video {
+            Some(video) => video.run.first().map(|s| s.is_sync).unwrap_or(false),
+            None => true,
+        };
+        let runs: Vec<TrackRun> = [&mut self.video, &mut self.audio]
+            .into_iter()
+            .flatten()
+            .map(TrackState::take_run)
+            .collect();
+        if runs.iter().all(|r| r.samples.is_empty()) {
+            return Ok(());
+        }
+
+        let fragment = fmp4::media_fragment(self.fragment_sequence, &runs);
+        self.fragment_sequence = self.fragment_sequence.wrapping_add(1);
+        if self.playlist.is_low_latency() {
+            let name =
+                playlist::part_name(self.playlist.next_msn(), self.playlist.next_part_index());
+            self.output.write(&name, fragment.clone())?;
+        }
+        self.segment_data.extend_from_slice(&fragment);
+        self.playlist.push_part(Part {
+            duration: self.seconds(self.part_elapsed),
+            independent,
+        });
+        self.part_elapsed = 0;
+        if self.playlist.is_low_latency() {
+            self.output.write_playlist(&self.playlist)?;
+        }
+        Ok(())
+    }
+
+    /// Write the open segment, roll the window and publish the playlist
+    fn close_segment(&mut self) -> Result<Value, Error> {
+        let msn = self.playlist.next_msn();
+        let name = playlist::segment_name(msn);
+        self.output
+            .write(&name, std::mem::take(&mut self.segment_data))?;
+        let duration = self.seconds(self.segment_elapsed);
+        self.segment_elapsed = 0;
+
+        let stale = self.playlist.close_segment();
+        self.output.write_playlist(&self.playlist)?;
+        self.output.remove(&stale);
+
+        Ok(json!({
+            "event": "hls_segment",
+            "uri": name,
+            "media_sequence": msn,
+            "duration": duration,
+        }))
+    }
+}
+
+impl Drop for HlsMuxer {
+    fn drop(&mut self) {
+        if let Err(e) = self.finish() {
+            tracing::warn!("Failed to finalize HLS output: {}", e);
+        }
+        self.output.unpublish();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::nodes::hls::store::streams;
+
+    fn muxer(name: &str, part_duration: Option<f64>, video: bool) -> HlsMuxer {
+        let output = HlsOutput::open(None, Some(name.to_string())).unwrap();
+        HlsMuxer::new(
+            MuxerConfig {
+                segment_duration: 1.0,
+                part_duration,
+                playlist_size: 3,
+                audio: !video,
+                video,
+            },
+            output,
+        )
+    }
+
+    /// Annex-B access unit: SPS + PPS + IDR slice, or a single P slice
+    fn frame(keyframe: bool) -> Vec<u8> {
+        if keyframe {
+            vec![
+                0, 0, 0, 1, 0x67, 0x42, 0xc0, 0x1e, 0, 0, 0, 1, 0x68, 0xce, 0, 0, 0, 1, 0x65, 0x88,
+            ]
+        } else {
+            vec![0, 0, 0, 1, 0x41, 0x9a]
+        }
+    }
+
+    #[test]
+    fn test_video_segments_start_on_keyframes() {
+        let name = "muxer-test-video";
+        let mut muxer = muxer(name, None, true);
+        let mut events = Vec::new();
+        // 25 fps, keyframe every 15 frames (0.6 s), 1 s segments:
+        // segments are cut at the first keyframe after 1 s, i.e. every 1.2 s.
+        for i in 0..62u64 {
+            events.extend(
+                muxer
+                    .push_video(&frame(i % 15 == 0), i * 40_000, i % 15 == 0, 320, 240)
+                    .unwrap(),
+            );
+        }
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[0]["uri"], "seg0.m4s");
+        assert!((events[0]["duration"].as_f64().unwrap() - 1.2).abs() < 1e-6);
+
+        let stream = streams().get(name).unwrap();
+        assert!(stream.file(INIT_SEGMENT).is_some());
+        let segment = stream.file("seg1.m4s").unwrap();
+        assert_eq!(&segment[4..8], b"moof");
+
+        drop(muxer);
+        assert!(streams().get(name).is_none());
+        let playlist = stream.playlist().unwrap();
+        assert!(
+            playlist.contains("#EXT-X-TARGETDURATION:1\n"),
+            "{}",
+            playlist
+        );
+        assert!(playlist.contains("seg2.m4s"));
+        assert!(playlist.ends_with("#EXT-X-ENDLIST\n"));
+    }
+
+    #[test]
+    fn test_frames_before_first_keyframe_are_dropped() {
+        let mut muxer = muxer("muxer-test-wait-keyframe", None, true);
+        muxer.push_video(&frame(false), 0, false, 320, 240).unwrap();
+        assert!(!muxer.started);
+        // A keyframe without parameter sets cannot start the stream either
+        muxer
+            .push_video(&[0, 0, 1, 0x65, 0x88], 40_000, true, 320, 240)
+            .unwrap();
+        assert!(!muxer.started);
+        muxer
+            .push_video(&frame(true), 80_000, true, 320, 240)
+            .unwrap();
+        assert!(muxer.started);
+        assert_eq!(muxer.video_origin_us, Some(80_000));
+    }
+
+    #[test]
+    fn test_audio_low_latency_parts() {
+        let name = "muxer-test-audio-ll";
+        let mut muxer = muxer(name, Some(0.25), false);
+        muxer.set_audio_format(48000, 2).unwrap();
+        let mut events = Vec::new();
+        // 1024-sample frames at 48 kHz: ~21.3 ms each, 100 frames ≈ 2.13 s
+        for _ in 0..100 {
+            events.extend(muxer.push_audio(vec![0x21; 8], 1024).unwrap());
+        }
+        assert_eq!(events.len(), 2);
+
+        let stream = streams().get(name).unwrap();
+        let playlist = stream.playlist().unwrap();
+        assert!(playlist.contains("#EXT-X-PART-INF:PART-TARGET=0.250"));
+        assert!(playlist.contains("URI=\"seg0.0.m4s\",INDEPENDENT=YES"));
+        assert!(playlist.contains("#EXT-X-PRELOAD-HINT:TYPE=PART,URI=\"seg2."));
+        assert!(stream.file("seg0.0.m4s").is_some());
+        assert!(stream.contains(1, Some(3)));
+        // The open segment has no complete part yet
+        assert!(!stream.contains(2, Some(0)));
+    }
+}
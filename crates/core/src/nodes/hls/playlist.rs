@@ -0,0 +1,313 @@
+//! Rolling HLS media playlist with optional LL-HLS parts
+
+use std::collections::VecDeque;
+use std::fmt::Write as _;
+
+/// Name of the init segment every playlist maps
+pub const INIT_SEGMENT: &str = "init.mp4";
+/// Name of the media playlist
+pub const PLAYLIST: &str = "index.m3u8";
+
+/// Completed segments whose parts stay listed (LL-HLS asks for at least
+/// the last three target durations)
+const PART_SEGMENTS: usize = 3;
+
+/// File name of segment `msn`
+pub fn segment_name(msn: u64) -> String {
+    format!("seg{}.m4s", msn)
+}
+
+/// File name of part `index` of segment `msn`
+pub fn part_name(msn: u64, index: u32) -> String {
+    format!("seg{}.{}.m4s", msn, index)
+}
+
+/// A published part
+#[derive(Debug, Clone, PartialEq)]
+pub struct Part {
+    pub duration: f64,
+    /// Starts with a sync sample on every track
+    pub independent: bool,
+}
+
+/// A completed segment
+#[derive(Debug, Clone, PartialEq)]
+pub struct Segment {
+    pub msn: u64,
+    pub duration: f64,
+    pub parts: Vec<Part>,
+}
+
+/// Media playlist state
+#[derive(Debug, Clone)]
+pub struct MediaPlaylist {
+    /// Configured segment duration, the floor of `EXT-X-TARGETDURATION`
+    segment_duration: f64,
+    /// LL-HLS part target; `None` for a plain HLS playlist
+    part_target: Option<f64>,
+    /// How many completed segments stay listed
+    window: usize,
+    segments: VecDeque<Segment>,
+    /// Parts of the segment being written
+    open_parts: Vec<Part>,
+    /// Sequence number of the segment being written
+    next_msn: u64,
+    /// Parts of segments before this one are no longer listed
+    parts_pruned_to: u64,
+    /// Longest segment so far, in seconds
+    max_segment: f64,
+    ended: bool,
+}
+
+impl MediaPlaylist {
+    pub fn new(segment_duration: f64, part_target: Option<f64>, window: usize) -> Self {
+        Self {
+            segment_duration,
+            part_target,
+            window: window.max(1),
+            segments: VecDeque::new(),
+            open_parts: Vec::new(),
+            next_msn: 0,
+            parts_pruned_to: 0,
+            max_segment: 0.0,
+            ended: false,
+        }
+    }
+
+    /// Sequence number of the segment being written
+    pub fn next_msn(&self) -> u64 {
+        self.next_msn
+    }
+
+    /// Index the next part of the open segment will get
+    pub fn next_part_index(&self) -> u32 {
+        self.open_parts.len() as u32
+    }
+
+    /// Whether parts are published (LL-HLS)
+    pub fn is_low_latency(&self) -> bool {
+        self.part_target.is_some()
+    }
+
+    /// Latest published `(msn, part)`: the last part of the open segment,
+    /// or the last completed segment (`part: None`)
+    pub fn latest(&self) -> Option<(u64, Option<u32>)> {
+        if !self.open_parts.is_empty() {
+            return Some((self.next_msn, Some(self.open_parts.len() as u32 - 1)));
+        }
+        self.segments.back().map(|s| (s.msn, None))
+    }
+
+    /// Whether segment `msn` (or part `part` of it) is already listed
+    pub fn contains(&self, msn: u64, part: Option<u32>) -> bool {
+        match part {
+            None => self.segments.back().is_some_and(|s| s.msn >= msn),
+            Some(part) => {
+                msn < self.next_msn
+                    || (msn == self.next_msn && (part as usize) < self.open_parts.len())
+            }
+        }
+    }
+
+    pub fn has_ended(&self) -> bool {
+        self.ended
+    }
+
+    /// Add a part to the open segment
+    pub fn push_part(&mut self, part: Part) {
+        self.open_parts.push(part);
+    }
+
+    /// Close the open segment
+    ///
+    /// Returns the files the playlist no longer lists: segments that fell
+    /// out of the window and parts that aged out of the part window.
+    pub fn close_segment(&mut self) -> Vec<String> {
+        let parts = std::mem::take(&mut self.open_parts);
+        let duration: f64 = parts.iter().map(|p| p.duration).sum();
+        self.max_segment = self.max_segment.max(duration);
+        self.segments.push_back(Segment {
+            msn: self.next_msn,
+            duration,
+            parts,
+        });
+        self.next_msn += 1;
+
+        let mut stale = Vec::new();
+        let parts_from = self.next_msn.saturating_sub(PART_SEGMENTS as u64);
+        while self.segments.len() > self.window {
+            if let Some(segment) = self.segments.pop_front() {
+                stale.push(segment_name(segment.msn));
+                if self.is_low_latency() && segment.msn >= self.parts_pruned_to {
+                    stale.extend(segment_part_names(&segment));
+                }
+            }
+        }
+        if self.is_low_latency() {
+            for segment in &self.segments {
+                if (self.parts_pruned_to..parts_from).contains(&segment.msn) {
+                    stale.extend(segment_part_names(segment));
+                }
+            }
+        }
+        self.parts_pruned_to = self.parts_pruned_to.max(parts_from);
+        stale
+    }
+
+    /// Mark the stream finished (`EXT-X-ENDLIST`)
+    pub fn end(&mut self) {
+        self.ended = true;
+    }
+
+    /// `EXT-X-TARGETDURATION`: the configured duration, raised if a
+    /// segment ran long waiting for a keyframe
+    pub fn target_duration(&self) -> u64 {
+        self.segment_duration
+            .ceil()
+            .max(self.max_segment.round())
+            .max(1.0) as u64
+    }
+
+    /// Render the playlist
+    pub fn render(&self) -> String {
+        let mut out = String::from("#EXTM3U\n");
+        let version = if self.is_low_latency() { 9 } else { 7 };
+        let _ = writeln!(out, "#EXT-X-VERSION:{}", version);
+        let _ = writeln!(out, "#EXT-X-TARGETDURATION:{}", self.target_duration());
+        if let Some(part_target) = self.part_target {
+            let _ = writeln!(
+                out,
+                "#EXT-X-SERVER-CONTROL:CAN-BLOCK-RELOAD=YES,PART-HOLD-BACK={:.3}",
+                part_target * 3.0
+            );
+            let _ = writeln!(out, "#EXT-X-PART-INF:PART-TARGET={:.3}", part_target);
+        }
+        let first_msn = self
+            .segments
+            .front()
+            .map(|s| s.msn)
+            .unwrap_or(self.next_msn);
+        let _ = writeln!(out, "#EXT-X-MEDIA-SEQUENCE:{}", first_msn);
+        let _ = writeln!(out, "#EXT-X-MAP:URI=\"{}\"", INIT_SEGMENT);
+
+        let parts_from = self.next_msn.saturating_sub(PART_SEGMENTS as u64);
+        for segment in &self.segments {
+            if self.is_low_latency() && segment.msn >= parts_from {
+                write_parts(&mut out, segment.msn, &segment.parts);
+            }
+            let _ = writeln!(out, "#EXTINF:{:.3},", segment.duration);
+            let _ = writeln!(out, "{}", segment_name(segment.msn));
+        }
+
+        if self.ended {
+            out.push_str("#EXT-X-ENDLIST\n");
+        } else if self.is_low_latency() {
+            write_parts(&mut out, self.next_msn, &self.open_parts);
+            let _ = writeln!(
+                out,
+                "#EXT-X-PRELOAD-HINT:TYPE=PART,URI=\"{}\"",
+                part_name(self.next_msn, self.next_part_index())
+            );
+        }
+        out
+    }
+}
+
+fn segment_part_names(segment: &Segment) -> impl Iterator<Item = String> + '_ {
+    (0..segment.parts.len() as u32).map(|index| part_name(segment.msn, index))
+}
+
+fn write_parts(out: &mut String, msn: u64, parts: &[Part]) {
+    for (index, part) in parts.iter().enumerate() {
+        let _ = write!(
+            out,
+            "#EXT-X-PART:DURATION={:.3},URI=\"{}\"",
+            part.duration,
+            part_name(msn, index as u32)
+        );
+        if part.independent {
+            out.push_str(",INDEPENDENT=YES");
+        }
+        out.push('\n');
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn part(duration: f64) -> Part {
+        Part {
+            duration,
+            independent: true,
+        }
+    }
+
+    #[test]
+    fn test_rolling_window() {
+        let mut playlist = MediaPlaylist::new(2.0, None, 2);
+        for msn in 0..3 {
+            playlist.push_part(part(2.0));
+            let stale = playlist.close_segment();
+            let expected: &[&str] = if msn == 2 { &["seg0.m4s"] } else { &[] };
+            assert_eq!(stale, expected);
+        }
+        playlist.end();
+
+        let rendered = playlist.render();
+        assert_eq!(
+            rendered,
+            "#EXTM3U\n#EXT-X-VERSION:7\n#EXT-X-TARGETDURATION:2\n\
+             #EXT-X-MEDIA-SEQUENCE:1\n#EXT-X-MAP:URI=\"init.mp4\"\n\
+             #EXTINF:2.000,\nseg1.m4s\n#EXTINF:2.000,\nseg2.m4s\n#EXT-X-ENDLIST\n"
+        );
+    }
+
+    #[test]
+    fn test_low_latency_parts_and_hint() {
+        let mut playlist = MediaPlaylist::new(1.0, Some(0.5), 10);
+        for msn in 0..5 {
+            playlist.push_part(part(0.5));
+            playlist.push_part(Part {
+                duration: 0.5,
+                independent: false,
+            });
+            // Parts of the segment three back stop being listed
+            let stale = playlist.close_segment();
+            match msn {
+                3 => assert_eq!(stale, ["seg0.0.m4s", "seg0.1.m4s"]),
+                4 => assert_eq!(stale, ["seg1.0.m4s", "seg1.1.m4s"]),
+                _ => assert!(stale.is_empty()),
+            }
+        }
+        playlist.push_part(part(0.5));
+        assert_eq!(playlist.latest(), Some((5, Some(0))));
+        assert!(playlist.contains(5, Some(0)));
+        assert!(!playlist.contains(5, Some(1)));
+        assert!(playlist.contains(4, None));
+        assert!(!playlist.contains(5, None));
+
+        let rendered = playlist.render();
+        assert!(rendered.contains("#EXT-X-VERSION:9\n"));
+        assert!(rendered.contains("CAN-BLOCK-RELOAD=YES,PART-HOLD-BACK=1.500"));
+        assert!(rendered.contains("#EXT-X-PART-INF:PART-TARGET=0.500"));
+        // Only the newest three completed segments keep their parts
+        assert!(!rendered.contains("seg1.0.m4s"));
+        assert!(rendered.contains(
+            "#EXT-X-PART:DURATION=0.500,URI=\"seg2.0.m4s\",INDEPENDENT=YES\n\
+             #EXT-X-PART:DURATION=0.500,URI=\"seg2.1.m4s\"\n"
+        ));
+        assert!(rendered.ends_with(
+            "#EXT-X-PART:DURATION=0.500,URI=\"seg5.0.m4s\",INDEPENDENT=YES\n\
+             #EXT-X-PRELOAD-HINT:TYPE=PART,URI=\"seg5.1.m4s\"\n"
+        ));
+    }
+
+    #[test]
+    fn test_target_duration_covers_long_segments() {
+        let mut playlist = MediaPlaylist::new(2.0, None, 5);
+        playlist.push_part(part(3.6));
+        playlist.close_segment();
+        assert_eq!(playlist.target_duration(), 4);
+    }
+}
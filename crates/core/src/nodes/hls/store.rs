@@ -0,0 +1,283 @@
+//! Where an HLS sink's files go: a directory, an in-memory stream, or both
+//!
+//! In-memory streams live in a process-wide registry keyed by stream
+//! name so an HTTP server can serve them (the `remotemedia-http` server
+//! mounts them at `/hls/:stream/:file`). A stream keeps only the files
+//! its playlist still lists, and leaves the registry when its muxer is
+//! dropped.
+
+use super::playlist::{MediaPlaylist, PLAYLIST};
+use crate::Error;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, OnceLock, RwLock};
+use std::time::Duration;
+use tokio::sync::watch;
+
+/// Process-wide in-memory streams
+static GLOBAL_STREAMS: OnceLock<HlsStreams> = OnceLock::new();
+
+/// The process-wide stream registry
+pub fn streams() -> &'static HlsStreams {
+    GLOBAL_STREAMS.get_or_init(HlsStreams::default)
+}
+
+/// Registry of in-memory HLS streams by name
+#[derive(Default)]
+pub struct HlsStreams {
+    streams: RwLock<HashMap<String, Arc<HlsStream>>>,
+}
+
+impl HlsStreams {
+    /// Start a new stream under `name`, replacing any previous one
+    pub fn publish(&self, name: &str) -> Arc<HlsStream> {
+        let stream = Arc::new(HlsStream::new());
+        if let Ok(mut streams) = self.streams.write() {
+            streams.insert(name.to_string(), stream.clone());
+        }
+        stream
+    }
+
+    /// Look up a stream
+    pub fn get(&self, name: &str) -> Option<Arc<HlsStream>> {
+        self.streams.read().ok()?.get(name).cloned()
+    }
+
+    /// Remove `name` if it still refers to `stream`
+    pub fn remove(&self, name: &str, stream: &Arc<HlsStream>) {
+        if let Ok(mut streams) = self.streams.write() {
+            if streams.get(name).is_some_and(|s| Arc::ptr_eq(s, stream)) {
+                streams.remove(name);
+            }
+        }
+    }
+
+    /// Names of all streams
+    pub fn names(&self) -> Vec<String> {
+        self.streams
+            .read()
+            .map(|s| s.keys().cloned().collect())
+            .unwrap_or_default()
+    }
+}
+
+#[derive(Default)]
+struct StreamState {
+    files: HashMap<String, Arc<[u8]>>,
+    playlist: Option<MediaPlaylist>,
+}
+
+/// An in-memory HLS stream
+///
+/// Readers can block until a segment or part appears, which is what
+/// LL-HLS blocking playlist reloads (`_HLS_msn` / `_HLS_part`) need.
+pub struct HlsStream {
+    state: RwLock<StreamState>,
+    /// Bumped on every change
+    version: watch::Sender<u64>,
+}
+
+impl HlsStream {
+    fn new() -> Self {
+        Self {
+            state: RwLock::new(StreamState::default()),
+            version: watch::channel(0).0,
+        }
+    }
+
+    /// A media file (`init.mp4`, segment or part) by name
+    pub fn file(&self, name: &str) -> Option<Arc<[u8]>> {
+        self.state.read().ok()?.files.get(name).cloned()
+    }
+
+    /// The rendered media playlist, once the init segment is out
+    pub fn playlist(&self) -> Option<String> {
+        self.state
+            .read()
+            .ok()?
+            .playlist
+            .as_ref()
+            .map(|p| p.render())
+    }
+
+    /// `EXT-X-TARGETDURATION` of the playlist
+    pub fn target_duration(&self) -> Option<u64> {
+        Some(self.state.read().ok()?.playlist.as_ref()?.target_duration())
+    }
+
+    /// Whether the producer has finished
+    pub fn has_ended(&self) -> bool {
+        self.state
+            .read()
+            .ok()
+            .and_then(|s| s.playlist.as_ref().map(|p| p.has_ended()))
+            .unwrap_or(false)
+    }
+
+    /// Whether segment `msn` (or part `part` of it) is published
+    pub fn contains(&self, msn: u64, part: Option<u32>) -> bool {
+        self.state
+            .read()
+            .ok()
+            .and_then(|s| s.playlist.as_ref().map(|p| p.contains(msn, part)))
+            .unwrap_or(false)
+    }
+
+    /// Wait until `ready` holds, the stream ends, or `timeout` passes
+    ///
+    /// Returns the final value of `ready`.
+    pub async fn wait_until(&self, timeout: Duration, ready: impl Fn(&Self) -> bool) -> bool {
+        let mut changes = self.version.subscribe();
+        let wait = async {
+            loop {
+                if ready(self) || self.has_ended() {
+                    return;
+                }
+                if changes.changed().await.is_err() {
+                    return;
+                }
+            }
+        };
+        let _ = tokio::time::timeout(timeout, wait).await;
+        ready(self)
+    }
+
+    fn update(&self, f: impl FnOnce(&mut StreamState)) {
+        if let Ok(mut state) = self.state.write() {
+            f(&mut state);
+        }
+        self.version.send_modify(|v| *v += 1);
+    }
+}
+
+/// File sink for one HLS output
+pub(crate) struct HlsOutput {
+    dir: Option<PathBuf>,
+    /// Registry name and the stream published under it
+    stream: Option<(String, Arc<HlsStream>)>,
+}
+
+impl HlsOutput {
+    /// Create the output directory and/or register the in-memory stream
+    pub fn open(dir: Option<PathBuf>, stream_name: Option<String>) -> Result<Self, Error> {
+        if let Some(dir) = &dir {
+            std::fs::create_dir_all(dir).map_err(|e| {
+                Error::Execution(format!(
+                    "Failed to create HLS output directory {}: {}",
+                    dir.display(),
+                    e
+                ))
+            })?;
+        }
+        let stream = stream_name.map(|name| {
+            let stream = streams().publish(&name);
+            (name, stream)
+        });
+        Ok(Self { dir, stream })
+    }
+
+    /// Write a media file
+    pub fn write(&self, name: &str, data: Vec<u8>) -> Result<(), Error> {
+        if let Some(dir) = &self.dir {
+            write_atomic(dir, name, &data)?;
+        }
+        if let Some((_, stream)) = &self.stream {
+            let data: Arc<[u8]> = data.into();
+            stream.update(|s| {
+                s.files.insert(name.to_string(), data);
+            });
+        }
+        Ok(())
+    }
+
+    /// Delete media files that are no longer listed
+    pub fn remove(&self, names: &[String]) {
+        if let Some(dir) = &self.dir {
+            for name in names {
+                // Already gone is fine
+                let _ = std::fs::remove_file(dir.join(name));
+            }
+        }
+        if let Some((_, stream)) = &self.stream {
+            stream.update(|s| {
+                for name in names {
+                    s.files.remove(name);
+                }
+            });
+        }
+    }
+
+    /// Publish the playlist
+    pub fn write_playlist(&self, playlist: &MediaPlaylist) -> Result<(), Error> {
+        if let Some(dir) = &self.dir {
+            write_atomic(dir, PLAYLIST, playlist.render().as_bytes())?;
+        }
+        if let Some((_, stream)) = &self.stream {
+            let playlist = playlist.clone();
+            stream.update(|s| s.playlist = Some(playlist));
+        }
+        Ok(())
+    }
+
+    /// Take the in-memory stream out of the registry
+    ///
+    /// Readers already holding the stream keep it; a newer stream
+    /// published under the same name is left alone.
+    pub fn unpublish(&self) {
+        if let Some((name, stream)) = &self.stream {
+            streams().remove(name, stream);
+        }
+    }
+}
+
+/// Write via a temporary file and rename, so readers never see a partial file
+fn write_atomic(dir: &Path, name: &str, data: &[u8]) -> Result<(), Error> {
+    let path = dir.join(name);
+    let tmp = dir.join(format!(".{}.tmp", name));
+    std::fs::write(&tmp, data)
+        .and_then(|_| std::fs::rename(&tmp, &path))
+        .map_err(|e| Error::Execution(format!("Failed to write {}: {}", path.display(), e)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::nodes::hls::playlist::Part;
+
+    #[tokio::test]
+    async fn test_blocking_wait_for_part() {
+        let name = "store-test-blocking";
+        let output = HlsOutput::open(None, Some(name.to_string())).unwrap();
+        let stream = streams().get(name).unwrap();
+        let mut playlist = MediaPlaylist::new(1.0, Some(0.5), 4);
+        output.write_playlist(&playlist).unwrap();
+
+        let waiter = {
+            let stream = stream.clone();
+            tokio::spawn(async move {
+                stream
+                    .wait_until(Duration::from_secs(5), |s| s.contains(0, Some(0)))
+                    .await
+            })
+        };
+        tokio::task::yield_now().await;
+
+        output.write("seg0.0.m4s", vec![1, 2, 3]).unwrap();
+        playlist.push_part(Part {
+            duration: 0.5,
+            independent: true,
+        });
+        output.write_playlist(&playlist).unwrap();
+
+        assert!(waiter.await.unwrap());
+        assert_eq!(stream.file("seg0.0.m4s").as_deref(), Some(&[1, 2, 3][..]));
+
+        output.remove(&["seg0.0.m4s".to_string()]);
+        assert!(stream.file("seg0.0.m4s").is_none());
+        assert!(
+            !stream
+                .wait_until(Duration::from_millis(10), |s| s.contains(1, None))
+                .await
+        );
+    }
+}
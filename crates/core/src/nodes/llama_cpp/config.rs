@@ -1,6 +1,8 @@
 //! Configuration types for llama.cpp nodes
 
+use crate::nodes::tool_spec::{build_tool_registry, ToolSpec};
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
 
 // ---------------------------------------------------------------------------
 // Shared backend config
@@ -93,6 +95,60 @@ pub struct LlamaCppGenerationConfig {
     pub system_prompt: Option<String>,
     /// Random seed for sampling (0 = random seed each time).
     pub seed: u64,
+
+    // ── Constrained decoding ────────────────────────────────────────
+    /// GBNF grammar (start rule `root`) every response must match.
+    pub grammar: Option<String>,
+    /// JSON Schema every response must match. Compiled to a GBNF grammar;
+    /// mutually exclusive with `grammar`.
+    #[serde(alias = "jsonSchema")]
+    pub json_schema: Option<Value>,
+
+    // ── Tool calling ────────────────────────────────────────────────
+    //
+    // Same registry as `OpenAIChatNode`. Active tools are rendered into
+    // the model's chat template, `<tool_call>` blocks are parsed out of
+    // the response, and side-effect tools (`say`, `show`, ...) are
+    // dispatched to their channels instead of being streamed as text.
+    // `return_value` tools are parsed but not executed: there is no
+    // multi-pass handler loop for local models.
+    /// Register the built-in `say` tool (speaks its `text` on `output_channel`).
+    #[serde(alias = "enableSayTool")]
+    pub enable_say_tool: bool,
+    /// Register the built-in `show` tool (renders its `content` on `ui`).
+    #[serde(alias = "enableShowTool")]
+    pub enable_show_tool: bool,
+    /// Additional tools registered alongside the built-ins.
+    pub tools: Vec<ToolSpec>,
+    /// Subset of registered tool names exposed to the model.
+    /// `None` means "expose every registered tool".
+    #[serde(alias = "activeTools")]
+    pub active_tools: Option<Vec<String>>,
+    /// Whether the model may answer in prose (`auto`), must call a tool
+    /// (`required`, enforced with a grammar) or sees no tools (`none`).
+    #[serde(alias = "toolChoice")]
+    pub tool_choice: LlamaToolChoice,
+    /// Channel `say` tool calls are spoken on. Default: `"tts"`.
+    #[serde(alias = "outputChannel")]
+    pub output_channel: Option<String>,
+}
+
+/// When the generation node's model may or must call a tool.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, schemars::JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum LlamaToolChoice {
+    /// The model decides between answering and calling tools.
+    Auto,
+    /// Every response is one or more tool calls.
+    Required,
+    /// Tools are neither advertised nor parsed.
+    None,
+}
+
+impl Default for LlamaToolChoice {
+    fn default() -> Self {
+        Self::Auto
+    }
 }
 
 impl Default for LlamaCppGenerationConfig {
@@ -110,6 +166,14 @@ impl Default for LlamaCppGenerationConfig {
             repeat_penalty: 1.1,
             system_prompt: None,
             seed: 0,
+            grammar: None,
+            json_schema: None,
+            enable_say_tool: false,
+            enable_show_tool: false,
+            tools: Vec::new(),
+            active_tools: None,
+            tool_choice: LlamaToolChoice::Auto,
+            output_channel: None,
         }
     }
 }
@@ -129,7 +193,57 @@ impl LlamaCppGenerationConfig {
         if self.temperature < 0.0 {
             return Err("temperature must be >= 0".to_string());
         }
-        Ok(())
+        self.constraint_grammar().map(|_| ())
+    }
+
+    /// Active tool registry (see [`build_tool_registry`]). Empty when
+    /// `tool_choice` is `none`.
+    pub fn tool_registry(&self) -> Vec<ToolSpec> {
+        if self.tool_choice == LlamaToolChoice::None {
+            return Vec::new();
+        }
+        build_tool_registry(
+            self.enable_say_tool,
+            self.enable_show_tool,
+            &self.tools,
+            self.active_tools.as_deref(),
+        )
+    }
+
+    /// GBNF grammar sampling is constrained by, if any: `grammar`, the
+    /// compiled `json_schema`, or the tool-call grammar when
+    /// `tool_choice` is `required`.
+    pub fn constraint_grammar(&self) -> Result<Option<String>, String> {
+        let required = self.tool_choice == LlamaToolChoice::Required;
+        match (&self.grammar, &self.json_schema) {
+            (Some(_), Some(_)) => Err("grammar and json_schema are mutually exclusive".to_string()),
+            (Some(_), None) | (None, Some(_)) if required => {
+                Err("tool_choice `required` can't be combined with grammar or json_schema".to_string())
+            }
+            // llama.cpp rejects a grammar without its start rule by
+            // failing to create the sampler; catch that at load time
+            (Some(grammar), None)
+                if !grammar.lines().any(|line| {
+                    line.trim_start()
+                        .strip_prefix("root")
+                        .is_some_and(|rest| rest.trim_start().starts_with("::="))
+                }) =>
+            {
+                Err("grammar must define a `root` rule".to_string())
+            }
+            (Some(grammar), None) => Ok(Some(grammar.clone())),
+            (None, Some(schema)) => super::grammar::json_schema_to_grammar(schema)
+                .map(Some)
+                .map_err(|e| format!("json_schema: {}", e)),
+            (None, None) if required => {
+                let tools = self.tool_registry();
+                if tools.is_empty() {
+                    return Err("tool_choice `required` needs at least one active tool".to_string());
+                }
+                super::tool_calls::tool_call_grammar(&tools).map(Some)
+            }
+            (None, None) => Ok(None),
+        }
     }
 }
 
@@ -407,6 +521,51 @@ mod tests {
         assert!(cfg.validate().is_ok());
     }
 
+    #[test]
+    fn test_generation_constraint_grammar() {
+        let mut cfg = LlamaCppGenerationConfig::default();
+        cfg.model_path = "/path/to/model.gguf".to_string();
+        assert_eq!(cfg.constraint_grammar(), Ok(None));
+
+        cfg.json_schema = Some(serde_json::json!({"type": "boolean"}));
+        let grammar = cfg.constraint_grammar().unwrap().unwrap();
+        assert!(grammar.starts_with("root ::= boolean\n"));
+
+        cfg.grammar = Some("root ::= \"yes\" | \"no\"".to_string());
+        assert!(cfg.validate().is_err());
+        cfg.json_schema = None;
+        assert!(cfg.validate().is_ok());
+        cfg.grammar = Some("answer ::= \"yes\"".to_string());
+        assert!(cfg.validate().is_err());
+        cfg.json_schema = Some(serde_json::json!({"type": "string", "pattern": "^a"}));
+        cfg.grammar = None;
+        assert!(cfg.validate().unwrap_err().contains("pattern"));
+    }
+
+    #[test]
+    fn test_generation_tool_registry() {
+        let cfg: LlamaCppGenerationConfig = serde_json::from_value(serde_json::json!({
+            "model_path": "/path/to/model.gguf",
+            "enableSayTool": true,
+            "enable_show_tool": true,
+            "tools": [{"name": "lookup", "description": "Look up", "parameters": {}}],
+            "active_tools": ["say", "lookup"],
+            "tool_choice": "required",
+        }))
+        .unwrap();
+        let names: Vec<_> = cfg.tool_registry().into_iter().map(|t| t.name).collect();
+        assert_eq!(names, ["say", "lookup"]);
+        assert!(cfg.constraint_grammar().unwrap().unwrap().contains("lookup-call"));
+
+        assert_eq!(cfg.tool_choice, LlamaToolChoice::Required);
+        assert!(LlamaCppGenerationConfig {
+            active_tools: Some(Vec::new()),
+            ..cfg
+        }
+        .constraint_grammar()
+        .is_err());
+    }
+
     #[test]
     fn test_embedding_config_default() {
         let cfg = LlamaCppEmbeddingConfig::default();
@@ -20,9 +20,20 @@
 //! `mpsc::Sender<WorkerRequest>`. `initialize()` blocks the pipeline's
 //! readiness signal on a successful model load — so when the frontend
 //! sees `"ready"`, weights really are on the GPU.
-
-use crate::data::RuntimeData;
+//!
+//! # Constrained decoding and tools
+//!
+//! A `grammar` (GBNF) or `json_schema` adds a grammar sampler to the
+//! front of the sampler chain, so every response matches it. Active
+//! tools are passed to the chat template as `tools`; `<tool_call>`
+//! blocks in the response are split out of the text stream and
+//! dispatched through [`crate::llm::dispatch_tool_call`] — `say` speaks
+//! on `output_channel`, `show` renders on `ui`. With `tool_choice:
+//! required` the tool-call grammar forces every response to be calls.
+
+use crate::data::{RuntimeData, TEXT_CHANNEL_DEFAULT};
 use crate::error::Error;
+use crate::llm::dispatch_tool_call;
 use crate::nodes::streaming_node::{
     AsyncStreamingNode, InitializeContext, StreamingNode, StreamingNodeFactory,
 };
@@ -32,12 +43,13 @@ use tokio::sync::{mpsc, oneshot};
 use tracing::{debug, error, info};
 
 use super::config::LlamaCppGenerationConfig;
+use super::tool_calls::TurnEvent;
 
 #[cfg(feature = "llama-cpp")]
 enum WorkerRequest {
     Generate {
         prompt: String,
-        result_tx: oneshot::Sender<Result<Vec<String>, Error>>,
+        result_tx: oneshot::Sender<Result<Vec<TurnEvent>, Error>>,
     },
 }
 
@@ -70,9 +82,9 @@ impl LlamaCppGenerationNode {
     }
 
     /// Send a generation request to the worker thread and await all
-    /// produced chunks.
+    /// produced chunks and tool calls.
     #[cfg(feature = "llama-cpp")]
-    async fn generate(&self, prompt: &str) -> Result<Vec<String>, Error> {
+    async fn generate(&self, prompt: &str) -> Result<Vec<TurnEvent>, Error> {
         let tx = self
             .worker_tx
             .get()
@@ -94,11 +106,11 @@ impl LlamaCppGenerationNode {
     }
 
     #[cfg(not(feature = "llama-cpp"))]
-    async fn generate(&self, prompt: &str) -> Result<Vec<String>, Error> {
-        Ok(vec![format!(
+    async fn generate(&self, prompt: &str) -> Result<Vec<TurnEvent>, Error> {
+        Ok(vec![TurnEvent::Text(format!(
             "[llama-cpp disabled: {}]",
             &prompt[..prompt.len().min(30)]
-        )])
+        ))])
     }
 }
 
@@ -176,8 +188,28 @@ impl AsyncStreamingNode for LlamaCppGenerationNode {
             }
         };
 
-        let chunks = self.generate(&prompt).await?;
-        Ok(RuntimeData::Text(chunks.join("")))
+        // Tool calls need a callback to be dispatched to, so the unary
+        // path hands them back as JSON alongside the text instead.
+        let mut text = String::new();
+        let mut tool_calls = Vec::new();
+        for event in self.generate(&prompt).await? {
+            match event {
+                TurnEvent::Text(chunk) => text.push_str(&chunk),
+                TurnEvent::ToolCall(call) => tool_calls.push(serde_json::json!({
+                    "id": call.id,
+                    "name": call.name,
+                    "arguments": serde_json::from_str::<Value>(&call.arguments)
+                        .unwrap_or(Value::String(call.arguments)),
+                })),
+            }
+        }
+        if tool_calls.is_empty() {
+            return Ok(RuntimeData::Text(text));
+        }
+        Ok(RuntimeData::Json(serde_json::json!({
+            "text": text,
+            "tool_calls": tool_calls,
+        })))
     }
 
     async fn process_streaming<F>(
@@ -205,12 +237,28 @@ impl AsyncStreamingNode for LlamaCppGenerationNode {
             }
         };
 
-        let chunks = self.generate(&prompt).await?;
+        let events = self.generate(&prompt).await?;
+        let tools = self.config.tool_registry();
+        let output_channel = self
+            .config
+            .output_channel
+            .as_deref()
+            .unwrap_or(TEXT_CHANNEL_DEFAULT);
 
+        // Tool calls may emit nothing (return_value or unknown tools), so
+        // count the outputs actually produced
         let mut count = 0;
-        for chunk in chunks {
-            callback(RuntimeData::Text(chunk))?;
+        let mut counted = |output: RuntimeData| {
             count += 1;
+            callback(output)
+        };
+        for event in events {
+            match event {
+                TurnEvent::Text(chunk) => counted(RuntimeData::Text(chunk))?,
+                TurnEvent::ToolCall(call) => {
+                    dispatch_tool_call(&tools, &call, output_channel, &mut counted)?
+                }
+            }
         }
 
         Ok(count)
@@ -383,9 +431,31 @@ fn worker_main(
     info!(
         node = %node_id,
         jinja_template = template.has_template,
+        template_tools = template.supports_tools,
         "llama.cpp worker: chat template renderer initialized"
     );
 
+    // Tool registry and constraint grammar are fixed for the worker's
+    // lifetime; `new()` already validated them.
+    let turn = TurnSetup {
+        tools: config.tool_registry(),
+        grammar: match config.constraint_grammar() {
+            Ok(grammar) => grammar,
+            Err(e) => {
+                let _ = init_tx.send(Err(Error::Execution(format!("Invalid config: {}", e))));
+                return;
+            }
+        },
+    };
+    if !turn.tools.is_empty() || turn.grammar.is_some() {
+        info!(
+            node = %node_id,
+            n_tools = turn.tools.len(),
+            grammar = turn.grammar.is_some(),
+            "llama.cpp worker: constrained decoding / tool calling enabled"
+        );
+    }
+
     if init_tx.send(Ok(())).is_err() {
         // Caller dropped before we finished; just exit.
         return;
@@ -407,15 +477,26 @@ fn worker_main(
                     &model,
                     &mut llama_ctx,
                     &config,
+                    &turn,
                     &prompt,
                 );
                 match &result {
-                    Ok((chunks, stats)) => {
-                        let total_chars: usize = chunks.iter().map(|c| c.len()).sum();
+                    Ok((events, stats)) => {
+                        let (mut n_chunks, mut total_chars, mut n_tool_calls) = (0, 0, 0);
+                        for event in events {
+                            match event {
+                                TurnEvent::Text(chunk) => {
+                                    n_chunks += 1;
+                                    total_chars += chunk.len();
+                                }
+                                TurnEvent::ToolCall(_) => n_tool_calls += 1,
+                            }
+                        }
                         info!(
                             node = %node_id,
-                            n_chunks = chunks.len(),
+                            n_chunks,
                             n_chars = total_chars,
+                            n_tool_calls,
                             n_decoded = stats.n_decoded,
                             n_reused = stats.n_reused,
                             elapsed_ms = t0.elapsed().as_millis() as u64,
@@ -426,7 +507,7 @@ fn worker_main(
                         error!(node = %node_id, "llama.cpp worker: generation failed: {}", e);
                     }
                 }
-                let _ = result_tx.send(result.map(|(chunks, _)| chunks));
+                let _ = result_tx.send(result.map(|(events, _)| events));
             }
         }
     }
@@ -490,6 +571,9 @@ fn to_llama_messages(
 struct ChatTemplate {
     env: minijinja::Environment<'static>,
     has_template: bool,
+    /// The template renders a `tools` variable itself; otherwise tools
+    /// are described in the system prompt.
+    supports_tools: bool,
 }
 
 #[cfg(feature = "llama-cpp")]
@@ -525,18 +609,22 @@ impl ChatTemplate {
             },
         );
 
+        let mut supports_tools = false;
         let has_template = match model.get_chat_template(16 * 1024) {
-            Ok(s) if !s.is_empty() => match env.add_template_owned("chat", s) {
-                Ok(()) => true,
-                Err(e) => {
-                    tracing::warn!(
-                        error = %e,
-                        "ChatTemplate: failed to compile model's Jinja chat template; \
-                         falling back to llama_chat_apply_template (no kwargs support)"
-                    );
-                    false
+            Ok(s) if !s.is_empty() => {
+                supports_tools = s.contains("tools");
+                match env.add_template_owned("chat", s) {
+                    Ok(()) => true,
+                    Err(e) => {
+                        tracing::warn!(
+                            error = %e,
+                            "ChatTemplate: failed to compile model's Jinja chat template; \
+                             falling back to llama_chat_apply_template (no kwargs support)"
+                        );
+                        false
+                    }
                 }
-            },
+            }
             Ok(_) => {
                 tracing::warn!(
                     "ChatTemplate: model has no embedded chat_template; \
@@ -554,7 +642,11 @@ impl ChatTemplate {
             }
         };
 
-        Self { env, has_template }
+        Self {
+            env,
+            has_template,
+            supports_tools: has_template && supports_tools,
+        }
     }
 
     /// Render the conversation history with the supplied kwargs.
@@ -565,6 +657,7 @@ impl ChatTemplate {
         messages: &[ChatMsg],
         add_generation_prompt: bool,
         enable_thinking: bool,
+        tools: Option<&serde_json::Value>,
     ) -> Option<String> {
         if !self.has_template {
             return None;
@@ -593,6 +686,8 @@ impl ChatTemplate {
             messages => msgs,
             add_generation_prompt => add_generation_prompt,
             enable_thinking => enable_thinking,
+            // OpenAI-style function specs, as HF tool-use templates expect.
+            tools => tools,
             // Qwen / Llama templates sometimes reference these globals.
             bos_token => "",
             eos_token => "",
@@ -710,6 +805,48 @@ impl ThinkStripper {
     }
 }
 
+/// Per-worker generation setup derived from the config.
+#[cfg(feature = "llama-cpp")]
+struct TurnSetup {
+    /// Tools advertised to the model and parsed out of its responses.
+    tools: Vec<crate::nodes::tool_spec::ToolSpec>,
+    /// GBNF grammar the sampler is constrained by.
+    grammar: Option<String>,
+}
+
+/// Describe `tools` in the system prompt, for chat templates that can't
+/// render them.
+#[cfg(feature = "llama-cpp")]
+fn add_tool_prompt(messages: &mut Vec<ChatMsg>, tools: &[crate::nodes::tool_spec::ToolSpec]) {
+    let prompt = super::tool_calls::tool_prompt(tools);
+    match messages.first_mut() {
+        Some(first) if first.role == "system" => {
+            first.content = format!("{}\n\n{}", first.content, prompt);
+        }
+        _ => messages.insert(
+            0,
+            ChatMsg {
+                role: "system".to_string(),
+                content: prompt,
+            },
+        ),
+    }
+}
+
+/// Queue visible response text, through the tool-call splitter when
+/// tools are active.
+#[cfg(feature = "llama-cpp")]
+fn push_text(
+    events: &mut Vec<TurnEvent>,
+    splitter: Option<&mut super::tool_calls::ToolCallSplitter>,
+    text: String,
+) {
+    match splitter {
+        Some(splitter) => events.extend(splitter.push(&text)),
+        None => events.push(TurnEvent::Text(text)),
+    }
+}
+
 #[cfg(feature = "llama-cpp")]
 struct TurnStats {
     /// Tokens decoded for the prompt portion of this turn (system +
@@ -733,8 +870,11 @@ fn run_turn_incremental(
     model: &llama_cpp_4::model::LlamaModel,
     ctx: &mut llama_cpp_4::context::LlamaContext,
     config: &LlamaCppGenerationConfig,
+    turn: &TurnSetup,
     user_text: &str,
-) -> Result<(Vec<String>, TurnStats), Error> {
+) -> Result<(Vec<TurnEvent>, TurnStats), Error> {
+    use super::tool_calls::ToolCallSplitter;
+    use crate::nodes::tool_spec::to_openai_tools_array;
     use llama_cpp_4::llama_batch::LlamaBatch;
     use llama_cpp_4::model::{AddBos, LlamaChatMessage, Special};
     use llama_cpp_4::sampling::LlamaSampler;
@@ -762,14 +902,26 @@ fn run_turn_incremental(
     // from historical assistant content on every render, so any
     // auto-prefix tokens we cache for the open turn become unrecoverable
     // on the next turn — which is also why M-RoPE `seq_rm` can't help.
+    //
+    // Tools go in the template's `tools` variable when it renders one,
+    // and into the system prompt otherwise.
+    let tools_json = (!turn.tools.is_empty()).then(|| to_openai_tools_array(&turn.tools));
+    if tools_json.is_some() && !template.supports_tools {
+        add_tool_prompt(&mut probe_messages, &turn.tools);
+    }
     let formatted = match template.render(
         &probe_messages,
         /* add_generation_prompt */ true,
         /* enable_thinking */ false,
+        tools_json.as_ref(),
     ) {
         Some(s) => s,
         None => {
-            let llama_msgs = to_llama_messages(&probe_messages)?;
+            let mut messages = probe_messages.clone();
+            if tools_json.is_some() && template.supports_tools {
+                add_tool_prompt(&mut messages, &turn.tools);
+            }
+            let llama_msgs = to_llama_messages(&messages)?;
             model
                 .apply_chat_template(None, &llama_msgs, true)
                 .map_err(|e| Error::Execution(format!("chat template apply: {}", e)))?
@@ -810,9 +962,14 @@ fn run_turn_incremental(
 
     let mut pos = n_prompt as i32;
 
-    // 4. Build the sampler chain.
+    // 4. Build the sampler chain. The grammar goes first so the
+    //    truncating samplers only ever see tokens it allows.
     let mut chain_top_k: Vec<LlamaSampler> = Vec::new();
     let mut chain_no_top_k: Vec<LlamaSampler> = Vec::new();
+    if let Some(grammar) = turn.grammar.as_deref() {
+        chain_top_k.push(LlamaSampler::grammar(model, grammar, "root"));
+        chain_no_top_k.push(LlamaSampler::grammar(model, grammar, "root"));
+    }
     if config.min_p > 0.0 {
         chain_top_k.push(LlamaSampler::min_p(config.min_p, 1));
         chain_no_top_k.push(LlamaSampler::min_p(config.min_p, 1));
@@ -837,7 +994,8 @@ fn run_turn_incremental(
     // 5. Sample assistant tokens until EOG / max_tokens.
     let mut decoder = encoding_rs::UTF_8.new_decoder();
     let mut stripper = ThinkStripper::new();
-    let mut chunks: Vec<String> = Vec::new();
+    let mut splitter = (!turn.tools.is_empty()).then(|| ToolCallSplitter::new(&turn.tools));
+    let mut events: Vec<TurnEvent> = Vec::new();
     let mut response = String::new();
 
     for _ in 0..config.max_tokens {
@@ -856,7 +1014,7 @@ fn run_turn_incremental(
 
         if let Some(out) = stripper.push(&piece) {
             response.push_str(&out);
-            chunks.push(out);
+            push_text(&mut events, splitter.as_mut(), out);
         }
 
         batch.clear();
@@ -876,11 +1034,14 @@ fn run_turn_incremental(
     let _ = decoder.decode_to_string(&[], &mut tail, true);
     if let Some(out) = stripper.push(&tail) {
         response.push_str(&out);
-        chunks.push(out);
+        push_text(&mut events, splitter.as_mut(), out);
     }
     if let Some(out) = stripper.flush() {
         response.push_str(&out);
-        chunks.push(out);
+        push_text(&mut events, splitter.as_mut(), out);
+    }
+    if let Some(splitter) = splitter.as_mut() {
+        events.extend(splitter.flush());
     }
 
     // End-of-response sentinel. The downstream coordinator
@@ -891,7 +1052,7 @@ fn run_turn_incremental(
     // coordinator only times out after `llm_silence_timeout_ms` (120 s
     // in the Qwen example), so the frontend stays stuck on
     // "assistant generating".
-    chunks.push("<|text_end|>".to_string());
+    events.push(TurnEvent::Text("<|text_end|>".to_string()));
 
     // 6. Persist the turn into history. Skip empty assistant responses
     //    so the model doesn't see an empty `assistant` block on the
@@ -912,7 +1073,7 @@ fn run_turn_incremental(
     }
 
     Ok((
-        chunks,
+        events,
         TurnStats {
             n_decoded: n_prompt,
             n_reused: 0,
@@ -1006,7 +1167,10 @@ impl StreamingNodeFactory for LlamaCppGenerationNodeFactory {
                      Supports CUDA/Metal/Vulkan GPU acceleration. \
                      Runs inference on a dedicated worker thread (llama.cpp \
                      types are not Send). Model is loaded eagerly during \
-                     initialize() and reused across calls.",
+                     initialize() and reused across calls. Output can be \
+                     constrained with a GBNF grammar or a JSON Schema; \
+                     say/show and custom tools are called natively and \
+                     dispatched like OpenAIChatNode tool calls.",
                 )
                 .category("llm")
                 .accepts([RuntimeDataType::Text, RuntimeDataType::Json])
@@ -1051,4 +1215,23 @@ mod tests {
         let node = LlamaCppGenerationNode::from_params("test", &params);
         assert!(node.is_ok());
     }
+
+    #[test]
+    fn test_from_params_constraints() {
+        let params = serde_json::json!({
+            "model_path": "/path/to/model.gguf",
+            "enable_say_tool": true,
+            "tool_choice": "required",
+            "output_channel": "tts",
+        });
+        assert!(LlamaCppGenerationNode::from_params("test", &params).is_ok());
+
+        // A schema the grammar compiler can't honour fails at creation,
+        // not on the first turn
+        let params = serde_json::json!({
+            "model_path": "/path/to/model.gguf",
+            "json_schema": {"type": "string", "pattern": "^[0-9]+$"},
+        });
+        assert!(LlamaCppGenerationNode::from_params("test", &params).is_err());
+    }
 }
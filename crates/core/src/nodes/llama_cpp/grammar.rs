@@ -0,0 +1,607 @@
+//! JSON Schema → GBNF grammar compiler
+//!
+//! llama.cpp constrains sampling with GBNF grammars: tokens that can't
+//! continue a sentence of the grammar are masked out before sampling. This
+//! module turns a JSON Schema into such a grammar so the generation node
+//! can guarantee output that parses as JSON and matches the schema.
+//!
+//! Supported subset — what tool parameters and structured-output schemas
+//! use in practice:
+//!
+//! - `type` (one type or a list of types), `const`, `enum`
+//! - objects: `properties` and `required`; `additionalProperties` only
+//!   when no `properties` are given (a free-form map)
+//! - arrays: `items`, `prefixItems`, `minItems`, `maxItems`
+//! - strings: `minLength`, `maxLength`
+//! - `anyOf` / `oneOf`, and `$ref` into `$defs` / `definitions`
+//!   (recursive schemas are fine)
+//!
+//! Required properties are generated in `required` order, optional ones
+//! after them in key order. Numeric bounds and `format` are not enforced;
+//! keywords whose constraint can't be expressed (`pattern`, `allOf`,
+//! `not`, ...) are rejected instead of silently ignored.
+
+use serde_json::{Map, Value};
+use std::collections::HashMap;
+
+/// Keywords that would be violated silently if ignored
+const UNSUPPORTED: &[&str] = &[
+    "allOf",
+    "not",
+    "if",
+    "pattern",
+    "patternProperties",
+    "dependentRequired",
+    "dependentSchemas",
+    "uniqueItems",
+    "contains",
+];
+
+/// Shared rules: `(name, body, rules the body references)`
+const PRIMITIVES: &[(&str, &str, &[&str])] = &[
+    ("ws", r#"| " " | "\n" [ \t]{0,20}"#, &[]),
+    (
+        "char",
+        r#"[^"\\\x7F\x00-\x1F] | [\\] (["\\bfnrt] | "u" [0-9a-fA-F]{4})"#,
+        &[],
+    ),
+    ("string", r#""\"" char* "\"" ws"#, &["char", "ws"]),
+    ("integral-part", r#"[0] | [1-9] [0-9]{0,15}"#, &[]),
+    (
+        "number",
+        r#""-"? integral-part ("." [0-9]+)? ([eE] [-+]? [0-9]+)? ws"#,
+        &["integral-part", "ws"],
+    ),
+    (
+        "integer",
+        r#""-"? integral-part ws"#,
+        &["integral-part", "ws"],
+    ),
+    ("boolean", r#"("true" | "false") ws"#, &["ws"]),
+    ("null", r#""null" ws"#, &["ws"]),
+    (
+        "value",
+        "object | array | string | number | boolean | null",
+        &["object", "array", "string", "number", "boolean", "null"],
+    ),
+    (
+        "object",
+        r#""{" ws (string ":" ws value ("," ws string ":" ws value)*)? "}" ws"#,
+        &["string", "value", "ws"],
+    ),
+    (
+        "array",
+        r#""[" ws (value ("," ws value)*)? "]" ws"#,
+        &["value", "ws"],
+    ),
+];
+
+/// Compile a JSON Schema into a GBNF grammar whose start rule is `root`
+pub fn json_schema_to_grammar(schema: &Value) -> Result<String, String> {
+    let mut converter = SchemaConverter::new();
+    let rule = converter.compile(schema, "root")?;
+    if rule != "root" {
+        converter.add_rule("root", rule);
+    }
+    Ok(converter.format())
+}
+
+/// Builds a grammar out of one or more schemas
+///
+/// Each schema is compiled into a named rule with [`Self::compile`];
+/// callers can then combine those rules with hand-written ones through
+/// [`Self::add_rule`].
+#[derive(Default)]
+pub struct SchemaConverter {
+    /// Schema that `$ref`s resolve against
+    root: Value,
+    rules: Vec<(String, String)>,
+    /// `$ref` target → rule name, for the current root
+    refs: HashMap<String, String>,
+}
+
+impl SchemaConverter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Compile `schema` (which `$ref`s resolve against) under `name`,
+    /// returning the rule to reference
+    ///
+    /// Simple schemas may resolve to a shared rule such as `string`
+    /// rather than one called `name`.
+    pub fn compile(&mut self, schema: &Value, name: &str) -> Result<String, String> {
+        self.root = schema.clone();
+        self.refs.clear();
+        self.visit(schema, &sanitize(name))
+    }
+
+    /// Add `name ::= body`, returning the name it was stored under
+    ///
+    /// An existing rule with the same body is reused; a different one
+    /// gets a numbered name.
+    pub fn add_rule(&mut self, name: &str, body: impl Into<String>) -> String {
+        let body = body.into();
+        let name = sanitize(name);
+        let mut candidate = name.clone();
+        let mut n = 1;
+        loop {
+            match self
+                .rules
+                .iter()
+                .find(|(existing, _)| *existing == candidate)
+            {
+                None => {
+                    self.rules.push((candidate.clone(), body));
+                    return candidate;
+                }
+                Some((_, existing)) if *existing == body => return candidate,
+                Some(_) => {
+                    n += 1;
+                    candidate = format!("{}{}", name, n);
+                }
+            }
+        }
+    }
+
+    /// Add a shared rule (and the rules it references), returning its name
+    pub fn primitive(&mut self, name: &str) -> String {
+        if let Some(&(_, body, deps)) = PRIMITIVES.iter().find(|(n, _, _)| *n == name) {
+            if !self.rules.iter().any(|(existing, _)| existing == name) {
+                self.rules.push((name.to_string(), body.to_string()));
+                for dep in deps {
+                    self.primitive(dep);
+                }
+            }
+        }
+        name.to_string()
+    }
+
+    /// Grammar text, `root` first
+    pub fn format(&self) -> String {
+        let (root, rest): (Vec<_>, Vec<_>) =
+            self.rules.iter().partition(|(name, _)| name == "root");
+        root.into_iter()
+            .chain(rest)
+            .map(|(name, body)| format!("{} ::= {}\n", name, body))
+            .collect()
+    }
+
+    /// GBNF for a JSON value, followed by optional whitespace
+    fn literal(&mut self, value: &Value) -> String {
+        self.primitive("ws");
+        format!("{} ws", gbnf_string(&value.to_string()))
+    }
+
+    fn visit(&mut self, schema: &Value, name: &str) -> Result<String, String> {
+        let schema = match schema {
+            Value::Bool(true) => return Ok(self.primitive("value")),
+            Value::Bool(false) => return Err(format!("{}: schema `false` matches nothing", name)),
+            Value::Object(map) => map,
+            other => return Err(format!("{}: expected a schema object, got {}", name, other)),
+        };
+        if let Some(keyword) = UNSUPPORTED.iter().find(|k| schema.contains_key(**k)) {
+            return Err(format!("{}: `{}` is not supported", name, keyword));
+        }
+
+        if let Some(reference) = schema.get("$ref") {
+            return self.visit_ref(reference, name);
+        }
+        if let Some(value) = schema.get("const") {
+            let body = self.literal(value);
+            return Ok(self.add_rule(name, body));
+        }
+        if let Some(values) = schema.get("enum") {
+            let values = values
+                .as_array()
+                .filter(|v| !v.is_empty())
+                .ok_or_else(|| format!("{}: `enum` must be a non-empty array", name))?;
+            let body = values
+                .iter()
+                .map(|v| self.literal(v))
+                .collect::<Vec<_>>()
+                .join(" | ");
+            return Ok(self.add_rule(name, body));
+        }
+        if let Some(variants) = schema.get("anyOf").or_else(|| schema.get("oneOf")) {
+            let variants = variants
+                .as_array()
+                .filter(|v| !v.is_empty())
+                .ok_or_else(|| format!("{}: `anyOf`/`oneOf` must be a non-empty array", name))?;
+            let body = variants
+                .iter()
+                .enumerate()
+                .map(|(i, variant)| self.visit(variant, &format!("{}-{}", name, i)))
+                .collect::<Result<Vec<_>, _>>()?
+                .join(" | ");
+            return Ok(self.add_rule(name, body));
+        }
+
+        match schema.get("type") {
+            Some(Value::String(ty)) => self.visit_type(ty, schema, name),
+            Some(Value::Array(types)) => {
+                let body = types
+                    .iter()
+                    .map(|ty| {
+                        let ty = ty
+                            .as_str()
+                            .ok_or_else(|| format!("{}: `type` entries must be strings", name))?;
+                        self.visit_type(ty, schema, &format!("{}-{}", name, ty))
+                    })
+                    .collect::<Result<Vec<_>, _>>()?
+                    .join(" | ");
+                Ok(self.add_rule(name, body))
+            }
+            Some(other) => Err(format!("{}: invalid `type` {}", name, other)),
+            None if schema.contains_key("properties") => self.visit_type("object", schema, name),
+            None if schema.contains_key("items") || schema.contains_key("prefixItems") => {
+                self.visit_type("array", schema, name)
+            }
+            None => Ok(self.primitive("value")),
+        }
+    }
+
+    fn visit_type(
+        &mut self,
+        ty: &str,
+        schema: &Map<String, Value>,
+        name: &str,
+    ) -> Result<String, String> {
+        match ty {
+            "object" => self.visit_object(schema, name),
+            "array" => self.visit_array(schema, name),
+            "string" => {
+                let min = count(schema, "minLength")?.unwrap_or(0);
+                let max = count(schema, "maxLength")?;
+                if min == 0 && max.is_none() {
+                    return Ok(self.primitive("string"));
+                }
+                if max.is_some_and(|max| max < min) {
+                    return Err(format!("{}: maxLength is below minLength", name));
+                }
+                self.primitive("char");
+                self.primitive("ws");
+                let body = format!(r#""\"" {} "\"" ws"#, repeat("char", min, max));
+                Ok(self.add_rule(name, body))
+            }
+            "number" | "integer" | "boolean" | "null" => Ok(self.primitive(ty)),
+            other => Err(format!("{}: unknown type `{}`", name, other)),
+        }
+    }
+
+    fn visit_object(&mut self, schema: &Map<String, Value>, name: &str) -> Result<String, String> {
+        self.primitive("ws");
+        let Some(properties) = schema.get("properties") else {
+            return match schema.get("additionalProperties") {
+                Some(Value::Bool(false)) => Ok(self.add_rule(name, r#""{" ws "}" ws"#)),
+                Some(value @ Value::Object(_)) => {
+                    let value = self.visit(value, &format!("{}-value", name))?;
+                    let key = self.primitive("string");
+                    let entry = format!(r#"{} ":" ws {}"#, key, value);
+                    let body = format!(r#""{{" ws ({} ("," ws {})*)? "}}" ws"#, entry, entry);
+                    Ok(self.add_rule(name, body))
+                }
+                _ => Ok(self.primitive("object")),
+            };
+        };
+        let properties = properties
+            .as_object()
+            .ok_or_else(|| format!("{}: `properties` must be an object", name))?;
+
+        let mut required: Vec<&str> = Vec::new();
+        for key in schema
+            .get("required")
+            .and_then(Value::as_array)
+            .into_iter()
+            .flatten()
+        {
+            let key = key
+                .as_str()
+                .ok_or_else(|| format!("{}: `required` entries must be strings", name))?;
+            if !properties.contains_key(key) {
+                return Err(format!(
+                    "{}: required property `{}` is not defined",
+                    name, key
+                ));
+            }
+            if !required.contains(&key) {
+                required.push(key);
+            }
+        }
+
+        let entry = |this: &mut Self, key: &str| -> Result<String, String> {
+            let value = this.visit(&properties[key], &format!("{}-{}", name, key))?;
+            Ok(format!(
+                r#"{} ws ":" ws {}"#,
+                gbnf_string(&Value::from(key).to_string()),
+                value
+            ))
+        };
+        let required_entries = required
+            .iter()
+            .map(|key| entry(self, key))
+            .collect::<Result<Vec<_>, _>>()?;
+        let optional_entries = properties
+            .keys()
+            .filter(|key| !required.contains(&key.as_str()))
+            .map(|key| entry(self, key))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let optional_tail = |entries: &[String]| {
+            entries
+                .iter()
+                .map(|entry| format!(r#" ("," ws {})?"#, entry))
+                .collect::<String>()
+        };
+        let members = if !required_entries.is_empty() {
+            format!(
+                "{}{} ",
+                required_entries.join(r#" "," ws "#),
+                optional_tail(&optional_entries)
+            )
+        } else if !optional_entries.is_empty() {
+            // Any optional member may come first; the rest follow in order
+            let firsts = (0..optional_entries.len())
+                .map(|i| {
+                    format!(
+                        "{}{}",
+                        optional_entries[i],
+                        optional_tail(&optional_entries[i + 1..])
+                    )
+                })
+                .collect::<Vec<_>>();
+            format!("({})? ", firsts.join(" | "))
+        } else {
+            String::new()
+        };
+        let body = format!(r#""{{" ws {}"}}" ws"#, members);
+        Ok(self.add_rule(name, body))
+    }
+
+    fn visit_array(&mut self, schema: &Map<String, Value>, name: &str) -> Result<String, String> {
+        self.primitive("ws");
+        if let Some(prefix) = schema.get("prefixItems") {
+            let prefix = prefix
+                .as_array()
+                .ok_or_else(|| format!("{}: `prefixItems` must be an array", name))?;
+            let items = prefix
+                .iter()
+                .enumerate()
+                .map(|(i, item)| self.visit(item, &format!("{}-{}", name, i)))
+                .collect::<Result<Vec<_>, _>>()?;
+            let body = format!(r#""[" ws {} "]" ws"#, items.join(r#" "," ws "#));
+            return Ok(self.add_rule(name, body));
+        }
+
+        let item = match schema.get("items") {
+            Some(items) => self.visit(items, &format!("{}-item", name))?,
+            None => self.primitive("value"),
+        };
+        let min = count(schema, "minItems")?.unwrap_or(0);
+        let max = count(schema, "maxItems")?;
+        if max.is_some_and(|max| max < min) {
+            return Err(format!("{}: maxItems is below minItems", name));
+        }
+        let list = match max {
+            Some(0) => String::new(),
+            _ => {
+                let rest = repeat(
+                    &format!(r#"("," ws {})"#, item),
+                    min.saturating_sub(1),
+                    max.map(|max| max - 1),
+                );
+                if min == 0 {
+                    format!("({} {})? ", item, rest)
+                } else {
+                    format!("{} {} ", item, rest)
+                }
+            }
+        };
+        let body = format!(r#""[" ws {}"]" ws"#, list);
+        Ok(self.add_rule(name, body))
+    }
+
+    fn visit_ref(&mut self, reference: &Value, name: &str) -> Result<String, String> {
+        let reference = reference
+            .as_str()
+            .ok_or_else(|| format!("{}: `$ref` must be a string", name))?;
+        if let Some(rule) = self.refs.get(reference) {
+            return Ok(rule.clone());
+        }
+        let pointer = reference.strip_prefix('#').ok_or_else(|| {
+            format!(
+                "{}: only local `$ref`s are supported, got {}",
+                name, reference
+            )
+        })?;
+        let target = self
+            .root
+            .pointer(pointer)
+            .cloned()
+            .ok_or_else(|| format!("{}: `$ref` {} does not resolve", name, reference))?;
+
+        // Reserve the rule before visiting, so recursive references find it
+        let label = pointer
+            .rsplit('/')
+            .next()
+            .filter(|s| !s.is_empty())
+            .unwrap_or("root");
+        let rule = self.add_rule(&format!("def-{}", label), String::new());
+        self.refs.insert(reference.to_string(), rule.clone());
+        let body = self.visit(&target, &format!("{}-body", rule))?;
+        if let Some(slot) = self
+            .rules
+            .iter_mut()
+            .find(|(existing, _)| *existing == rule)
+        {
+            slot.1 = body;
+        }
+        Ok(rule)
+    }
+}
+
+/// Non-negative integer keyword
+fn count(schema: &Map<String, Value>, key: &str) -> Result<Option<usize>, String> {
+    match schema.get(key) {
+        None => Ok(None),
+        Some(value) => value
+            .as_u64()
+            .map(|n| Some(n as usize))
+            .ok_or_else(|| format!("`{}` must be a non-negative integer", key)),
+    }
+}
+
+/// `symbol` repeated between `min` and `max` times
+fn repeat(symbol: &str, min: usize, max: Option<usize>) -> String {
+    match (min, max) {
+        (0, None) => format!("{}*", symbol),
+        (1, None) => format!("{}+", symbol),
+        (0, Some(1)) => format!("{}?", symbol),
+        (_, Some(0)) => String::new(),
+        (min, None) => format!("{}{{{},}}", symbol, min),
+        (min, Some(max)) if min == max => format!("{}{{{}}}", symbol, min),
+        (min, Some(max)) => format!("{}{{{},{}}}", symbol, min, max),
+    }
+}
+
+/// Quote `text` as a GBNF string literal
+pub fn gbnf_string(text: &str) -> String {
+    let mut out = String::with_capacity(text.len() + 2);
+    out.push('"');
+    for c in text.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+/// Rule names may only contain ASCII letters, digits and dashes
+fn sanitize(name: &str) -> String {
+    let name: String = name
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '-' })
+        .collect();
+    if name.is_empty() {
+        "rule".to_string()
+    } else {
+        name
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn rule<'a>(grammar: &'a str, name: &str) -> &'a str {
+        let prefix = format!("{} ::= ", name);
+        grammar
+            .lines()
+            .find_map(|line| line.strip_prefix(prefix.as_str()))
+            .unwrap_or_else(|| panic!("no rule {} in\n{}", name, grammar))
+    }
+
+    #[test]
+    fn test_object_orders_required_first() {
+        let grammar = json_schema_to_grammar(&json!({
+            "type": "object",
+            "properties": {
+                "age": {"type": "integer"},
+                "name": {"type": "string"},
+                "tags": {"type": "array", "items": {"type": "string"}, "maxItems": 3}
+            },
+            "required": ["name", "age"]
+        }))
+        .unwrap();
+        assert!(grammar.starts_with("root ::= "));
+        assert_eq!(
+            rule(&grammar, "root"),
+            r#""{" ws "\"name\"" ws ":" ws string "," ws "\"age\"" ws ":" ws integer ("," ws "\"tags\"" ws ":" ws root-tags)? "}" ws"#
+        );
+        assert_eq!(
+            rule(&grammar, "root-tags"),
+            r#""[" ws (string ("," ws string){0,2})? "]" ws"#
+        );
+        for shared in ["string", "char", "integer", "integral-part", "ws"] {
+            rule(&grammar, shared);
+        }
+    }
+
+    #[test]
+    fn test_object_without_required_members() {
+        let grammar = json_schema_to_grammar(&json!({
+            "properties": {"a": {"type": "boolean"}, "b": {"type": "null"}}
+        }))
+        .unwrap();
+        assert_eq!(
+            rule(&grammar, "root"),
+            r#""{" ws ("\"a\"" ws ":" ws boolean ("," ws "\"b\"" ws ":" ws null)? | "\"b\"" ws ":" ws null)? "}" ws"#
+        );
+    }
+
+    #[test]
+    fn test_literals_and_alternatives() {
+        let grammar = json_schema_to_grammar(&json!({
+            "anyOf": [
+                {"enum": ["say \"hi\"", 3]},
+                {"const": null},
+                {"type": ["string", "number"], "minLength": 2}
+            ]
+        }))
+        .unwrap();
+        assert_eq!(rule(&grammar, "root"), "root-0 | root-1 | root-2");
+        assert_eq!(
+            rule(&grammar, "root-0"),
+            r#""\"say \\\"hi\\\"\"" ws | "3" ws"#
+        );
+        assert_eq!(rule(&grammar, "root-1"), r#""null" ws"#);
+        assert_eq!(rule(&grammar, "root-2"), "root-2-string | number");
+        assert_eq!(rule(&grammar, "root-2-string"), r#""\"" char{2,} "\"" ws"#);
+    }
+
+    #[test]
+    fn test_recursive_ref() {
+        let grammar = json_schema_to_grammar(&json!({
+            "$ref": "#/$defs/node",
+            "$defs": {
+                "node": {
+                    "type": "object",
+                    "properties": {
+                        "children": {"type": "array", "items": {"$ref": "#/$defs/node"}}
+                    },
+                    "required": ["children"]
+                }
+            }
+        }))
+        .unwrap();
+        assert_eq!(rule(&grammar, "root"), "def-node");
+        assert_eq!(rule(&grammar, "def-node"), "def-node-body");
+        assert_eq!(
+            rule(&grammar, "def-node-body-children"),
+            r#""[" ws (def-node ("," ws def-node)*)? "]" ws"#
+        );
+    }
+
+    #[test]
+    fn test_rejects_unsupported_keywords() {
+        let err = json_schema_to_grammar(&json!({
+            "type": "object",
+            "properties": {"id": {"type": "string", "pattern": "^[a-z]+$"}}
+        }))
+        .unwrap_err();
+        assert!(
+            err.contains("root-id") && err.contains("pattern"),
+            "{}",
+            err
+        );
+        assert!(json_schema_to_grammar(&json!({"$ref": "#/$defs/missing"})).is_err());
+        assert!(json_schema_to_grammar(&json!({"properties": {}, "required": ["x"]})).is_err());
+    }
+}
@@ -41,6 +41,18 @@
 //!  └─────────────────────┘
 //! ```
 //!
+//! # Constrained decoding and tools
+//!
+//! [`LlamaCppGenerationNode`] can constrain sampling with a GBNF
+//! `grammar`, or with a `json_schema` compiled to one by
+//! [`json_schema_to_grammar`], so structured output always parses. It
+//! also supports native tool calling: the same `say` / `show` / custom
+//! [`crate::nodes::tool_spec::ToolSpec`] registry as `OpenAIChatNode` is
+//! rendered into the model's chat template, and the model's
+//! `<tool_call>` blocks are dispatched like the HTTP backends' calls.
+//! Unary `process()` has nowhere to dispatch them, so a reply with tool
+//! calls comes back as JSON: `{ "text": ..., "tool_calls": [...] }`.
+//!
 //! # GPU support
 //!
 //! Enable GPU acceleration via cargo features on the `llama-cpp-4` crate:
@@ -60,12 +72,15 @@ mod activation;
 mod steer;
 mod factory;
 mod inference;
+mod grammar;
+mod tool_calls;
 
 pub use config::{
     LlamaCppConfig, LlamaCppGenerationConfig, LlamaCppEmbeddingConfig,
     LlamaCppActivationConfig, LlamaCppSteerConfig, LlamaCppSteerVector,
-    LlamaBackendConfig, GpuOffload,
+    LlamaBackendConfig, GpuOffload, LlamaToolChoice,
 };
+pub use grammar::{json_schema_to_grammar, SchemaConverter};
 pub use generation::{LlamaCppGenerationNode, LlamaCppGenerationNodeFactory};
 pub use embedding::{LlamaCppEmbeddingNode, LlamaCppEmbeddingNodeFactory};
 pub use activation::{LlamaCppActivationNode, LlamaCppActivationNodeFactory};
@@ -0,0 +1,318 @@
+//! Native tool calling for local GGUF models
+//!
+//! Tools are advertised through the model's own chat template (the
+//! `tools` variable HF templates expect) or, for templates that don't
+//! take one, through a Hermes-style block in the system prompt. Calls
+//! come back in the same format most tool-tuned open models use:
+//!
+//! ```text
+//! <tool_call>
+//! {"name": "say", "arguments": {"text": "Hello!"}}
+//! </tool_call>
+//! ```
+//!
+//! Llama 3.1-style replies that are nothing but a call object (or an
+//! array of them) are recognised too. [`ToolCallSplitter`] separates
+//! calls from the streamed text so they can be dispatched with
+//! [`crate::llm::dispatch_tool_call`], exactly like the HTTP backends do.
+
+use super::grammar::{gbnf_string, SchemaConverter};
+use crate::llm::ToolCallAccum;
+use crate::nodes::tool_spec::ToolSpec;
+use serde_json::Value;
+
+/// Output of one generation turn
+#[derive(Debug)]
+pub enum TurnEvent {
+    /// Streamed response text
+    Text(String),
+    /// Completed tool call, ready for dispatch
+    ToolCall(ToolCallAccum),
+}
+
+const OPEN: &str = "<tool_call>";
+const CLOSE: &str = "</tool_call>";
+
+/// System-prompt section describing `tools`, for chat templates that
+/// don't render a `tools` variable themselves
+pub fn tool_prompt(tools: &[ToolSpec]) -> String {
+    let signatures: Vec<String> = tools
+        .iter()
+        .map(|tool| tool.to_openai_function().to_string())
+        .collect();
+    format!(
+        "# Tools\n\n\
+         You may call one or more functions to assist with the user query.\n\n\
+         You are provided with function signatures within <tools></tools> XML tags:\n\
+         <tools>\n{}\n</tools>\n\n\
+         For each function call, return a json object with function name and arguments \
+         within <tool_call></tool_call> XML tags:\n\
+         <tool_call>\n{{\"name\": <function-name>, \"arguments\": <args-json-object>}}\n</tool_call>",
+        signatures.join("\n")
+    )
+}
+
+/// GBNF grammar that only admits `<tool_call>` blocks calling one of
+/// `tools` with arguments matching its parameter schema
+pub fn tool_call_grammar(tools: &[ToolSpec]) -> Result<String, String> {
+    if tools.is_empty() {
+        return Err("no tools to call".to_string());
+    }
+    let mut converter = SchemaConverter::new();
+    let ws = converter.primitive("ws");
+    let mut calls = Vec::with_capacity(tools.len());
+    for tool in tools {
+        let arguments = converter
+            .compile(&tool.parameters, &format!("{}-arguments", tool.name))
+            .map_err(|e| format!("tool `{}`: {}", tool.name, e))?;
+        let name = gbnf_string(&Value::from(tool.name.as_str()).to_string());
+        let body = format!(
+            r#""{{" {ws} "\"name\"" {ws} ":" {ws} {name} {ws} "," {ws} "\"arguments\"" {ws} ":" {ws} {arguments} "}}" {ws}"#
+        );
+        calls.push(converter.add_rule(&format!("{}-call", tool.name), body));
+    }
+    let call = converter.add_rule("call", calls.join(" | "));
+    let tool_call = converter.add_rule(
+        "tool-call",
+        format!(r#""{}" {ws} {} "{}""#, OPEN, call, CLOSE),
+    );
+    converter.add_rule("root", format!("{tool_call} ({ws} {tool_call})*"));
+    Ok(converter.format())
+}
+
+/// Parse one call object: `{"name": .., "arguments": ..}`, also accepting
+/// `parameters` for the arguments and the OpenAI `{"function": {..}}` shape
+pub fn parse_tool_call(body: &str, index: usize) -> Option<ToolCallAccum> {
+    let value: Value = serde_json::from_str(body.trim()).ok()?;
+    call_from_value(&value, index)
+}
+
+fn call_from_value(value: &Value, index: usize) -> Option<ToolCallAccum> {
+    let call = value
+        .get("function")
+        .filter(|f| f.is_object())
+        .unwrap_or(value);
+    let name = call.get("name")?.as_str()?.to_string();
+    let arguments = match call.get("arguments").or_else(|| call.get("parameters")) {
+        // Some models double-encode the arguments as a JSON string
+        Some(Value::String(s)) => s.clone(),
+        Some(args) => args.to_string(),
+        None => "{}".to_string(),
+    };
+    Some(ToolCallAccum {
+        id: format!("call_{}", index),
+        name,
+        arguments,
+    })
+}
+
+/// Streaming filter that pulls tool calls out of generated text
+///
+/// Text is passed through as it arrives, holding back only a possible
+/// partial `<tool_call>` tag. A reply that opens with `{` or `[` is held
+/// back whole and, at the end, either recognised as bare call JSON naming
+/// a registered tool or released as text.
+pub struct ToolCallSplitter {
+    names: Vec<String>,
+    buffer: String,
+    in_call: bool,
+    /// Only whitespace seen so far
+    at_start: bool,
+    /// Reply may be a bare JSON call
+    bare: bool,
+    calls: usize,
+}
+
+impl ToolCallSplitter {
+    /// Splitter recognising calls to `tools`
+    pub fn new(tools: &[ToolSpec]) -> Self {
+        Self {
+            names: tools.iter().map(|t| t.name.clone()).collect(),
+            buffer: String::new(),
+            in_call: false,
+            at_start: true,
+            bare: false,
+            calls: 0,
+        }
+    }
+
+    /// Feed one decoded piece
+    pub fn push(&mut self, piece: &str) -> Vec<TurnEvent> {
+        self.buffer.push_str(piece);
+        let mut events = Vec::new();
+        if self.at_start {
+            let trimmed = self.buffer.trim_start();
+            if trimmed.is_empty() {
+                return events;
+            }
+            self.at_start = false;
+            self.bare = trimmed.starts_with(['{', '[']);
+        }
+        if self.bare {
+            return events;
+        }
+
+        loop {
+            if self.in_call {
+                let Some(idx) = self.buffer.find(CLOSE) else {
+                    break;
+                };
+                let body: String = self.buffer.drain(..idx + CLOSE.len()).collect();
+                self.in_call = false;
+                events.extend(self.call(&body[..idx]));
+                continue;
+            }
+            if let Some(idx) = self.buffer.find(OPEN) {
+                let text: String = self.buffer.drain(..idx + OPEN.len()).collect();
+                if idx > 0 {
+                    events.push(TurnEvent::Text(text[..idx].to_string()));
+                }
+                self.in_call = true;
+                continue;
+            }
+            // Hold back a suffix that could still become `<tool_call>`
+            let keep_from = self
+                .buffer
+                .char_indices()
+                .map(|(i, _)| i)
+                .find(|&i| OPEN.starts_with(&self.buffer[i..]))
+                .unwrap_or(self.buffer.len());
+            if keep_from > 0 {
+                events.push(TurnEvent::Text(self.buffer.drain(..keep_from).collect()));
+            }
+            break;
+        }
+        events
+    }
+
+    /// End of generation: release whatever is still held back
+    pub fn flush(&mut self) -> Vec<TurnEvent> {
+        let rest = std::mem::take(&mut self.buffer);
+        if self.bare {
+            let calls = self.bare_calls(&rest);
+            if !calls.is_empty() {
+                return calls.into_iter().map(TurnEvent::ToolCall).collect();
+            }
+        } else if self.in_call {
+            // Generation stopped before the closing tag; keep the call if
+            // its JSON is complete
+            self.in_call = false;
+            return self.call(&rest).into_iter().collect();
+        }
+        if rest.is_empty() {
+            Vec::new()
+        } else {
+            vec![TurnEvent::Text(rest)]
+        }
+    }
+
+    fn call(&mut self, body: &str) -> Option<TurnEvent> {
+        match parse_tool_call(body, self.calls) {
+            Some(call) => {
+                self.calls += 1;
+                Some(TurnEvent::ToolCall(call))
+            }
+            None => {
+                tracing::warn!(body = %body, "[llama.cpp] unparseable tool call; dropping");
+                None
+            }
+        }
+    }
+
+    /// Calls in a reply that is nothing but call JSON, if every one of
+    /// them names a registered tool
+    fn bare_calls(&mut self, reply: &str) -> Vec<ToolCallAccum> {
+        let Ok(value) = serde_json::from_str::<Value>(reply.trim()) else {
+            return Vec::new();
+        };
+        let values = match &value {
+            Value::Array(items) => items.iter().collect(),
+            other => vec![other],
+        };
+        let calls: Option<Vec<ToolCallAccum>> = values
+            .into_iter()
+            .enumerate()
+            .map(|(i, v)| call_from_value(v, self.calls + i))
+            .map(|call| call.filter(|c| self.names.contains(&c.name)))
+            .collect();
+        let calls = calls.unwrap_or_default();
+        self.calls += calls.len();
+        calls
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::nodes::tool_spec::{default_say_tool, default_show_tool};
+
+    fn run(pieces: &[&str]) -> Vec<TurnEvent> {
+        let mut splitter = ToolCallSplitter::new(&[default_say_tool(), default_show_tool()]);
+        let mut events: Vec<TurnEvent> = pieces.iter().flat_map(|p| splitter.push(p)).collect();
+        events.extend(splitter.flush());
+        events
+    }
+
+    fn describe(events: &[TurnEvent]) -> Vec<String> {
+        events
+            .iter()
+            .map(|event| match event {
+                TurnEvent::Text(text) => format!("text:{}", text),
+                TurnEvent::ToolCall(call) => {
+                    format!("{}:{}:{}", call.id, call.name, call.arguments)
+                }
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_splits_tagged_calls_across_pieces() {
+        let events = run(&[
+            "Sure",
+            ". <tool",
+            "_call>\n{\"name\": \"say\", \"arguments\": {\"text\": \"Hi\"}}\n</tool_",
+            "call>\n<tool_call>{\"name\":\"show\",\"parameters\":\"{\\\"content\\\":\\\"x\\\"}\"}",
+        ]);
+        assert_eq!(
+            describe(&events),
+            [
+                "text:Sure",
+                "text:. ",
+                r#"call_0:say:{"text":"Hi"}"#,
+                "text:\n",
+                r#"call_1:show:{"content":"x"}"#,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_bare_json_calls() {
+        let events = run(&[
+            " {\"name\": \"say\",",
+            " \"parameters\": {\"text\": \"Yo\"}}",
+        ]);
+        assert_eq!(describe(&events), [r#"call_0:say:{"text":"Yo"}"#]);
+
+        // JSON that isn't a call to a registered tool is plain text
+        let events = run(&["{\"name\": \"weather\"}"]);
+        assert_eq!(describe(&events), [r#"text:{"name": "weather"}"#]);
+    }
+
+    #[test]
+    fn test_tool_call_grammar() {
+        let grammar = tool_call_grammar(&[default_say_tool(), default_show_tool()]).unwrap();
+        assert!(grammar.starts_with("root ::= tool-call (ws tool-call)*\n"));
+        assert!(grammar.contains("call ::= say-call | show-call\n"));
+        assert!(grammar.contains(r#"tool-call ::= "<tool_call>" ws call "</tool_call>""#));
+        assert!(grammar.contains(r#""\"say\"" ws "," ws "\"arguments\"" ws ":" ws say-arguments"#));
+        assert!(grammar.contains(r#"say-arguments-text ::= "\"" char+ "\"" ws"#));
+        assert!(tool_call_grammar(&[]).is_err());
+    }
+
+    #[test]
+    fn test_tool_prompt_lists_signatures() {
+        let prompt = tool_prompt(&[default_say_tool()]);
+        assert!(prompt.contains("<tools>\n{\"function\":{\"description\":"));
+        assert!(prompt.ends_with("</tool_call>"));
+    }
+}
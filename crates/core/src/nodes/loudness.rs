@@ -0,0 +1,721 @@
+//! EBU R128 Loudness Measurement Node
+//!
+//! Measures momentary (400 ms), short-term (3 s) and gated integrated
+//! loudness in LUFS plus true-peak in dBTP, following ITU-R BS.1770-4.
+//! Flags deviations from a loudness target (EBU R128: -23 LUFS ±1 LU,
+//! max -1 dBTP).
+
+use crate::data::RuntimeData;
+use crate::nodes::{StreamingNode, SyncNodeWrapper, SyncStreamingNode};
+use crate::Error;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::{HashMap, VecDeque};
+use std::f64::consts::PI;
+use std::sync::Mutex;
+
+/// Absolute gating threshold (BS.1770-4 §2.8)
+const ABSOLUTE_GATE_LUFS: f64 = -70.0;
+/// Relative gating threshold below the ungated mean
+const RELATIVE_GATE_LU: f64 = -10.0;
+/// Gating histogram resolution: 0.1 LU bins from -70 to +30 LUFS
+const HISTOGRAM_BINS: usize = 1000;
+/// Sub-blocks per momentary window (4 × 100 ms)
+const MOMENTARY_BLOCKS: usize = 4;
+/// Sub-blocks per short-term window (30 × 100 ms)
+const SHORT_TERM_BLOCKS: usize = 30;
+/// Taps of the true-peak interpolation filter
+const TRUE_PEAK_TAPS: usize = 49;
+
+/// Loudness measurement result
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LoudnessEvent {
+    /// Momentary loudness (400 ms window) in LUFS, `None` until 400 ms of audio
+    pub momentary_lufs: Option<f64>,
+    /// Short-term loudness (3 s window) in LUFS, `None` until 3 s of audio
+    pub short_term_lufs: Option<f64>,
+    /// Gated integrated loudness in LUFS, `None` until a block passes the gates
+    pub integrated_lufs: Option<f64>,
+    /// Maximum true-peak so far in dBTP
+    pub true_peak_dbtp: f64,
+    /// Configured loudness target in LUFS
+    pub target_lufs: f64,
+    /// Measured minus target loudness in LU (for the configured measure)
+    pub deviation_lu: Option<f64>,
+    /// Loudness is more than `tolerance_lu` above target
+    pub is_too_loud: bool,
+    /// Loudness is more than `tolerance_lu` below target
+    pub is_too_quiet: bool,
+    /// True-peak exceeds `max_true_peak_dbtp`
+    pub is_true_peak_exceeded: bool,
+    /// Health: 1.0 = on target, 0.0 = far off target or over the peak limit
+    pub health: f32,
+    /// Stream identifier
+    pub stream_id: Option<String>,
+    /// Timestamp in microseconds
+    pub timestamp_us: Option<u64>,
+}
+
+/// Which loudness value is compared against the target
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum LoudnessMeasure {
+    Momentary,
+    ShortTerm,
+    #[default]
+    Integrated,
+}
+
+impl LoudnessMeasure {
+    /// Name used in events
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            LoudnessMeasure::Momentary => "momentary",
+            LoudnessMeasure::ShortTerm => "short_term",
+            LoudnessMeasure::Integrated => "integrated",
+        }
+    }
+}
+
+/// Configuration for loudness measurement
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LoudnessConfig {
+    /// Loudness target in LUFS (default: -23.0, EBU R128)
+    pub target_lufs: f64,
+    /// Allowed deviation from target in LU (default: 1.0)
+    pub tolerance_lu: f64,
+    /// Maximum permitted true-peak in dBTP (default: -1.0)
+    pub max_true_peak_dbtp: f64,
+    /// Loudness value compared against the target (default: integrated)
+    pub measure: LoudnessMeasure,
+    /// Audio required before target violations are reported (default: 3000ms)
+    pub min_measure_ms: f64,
+}
+
+impl Default for LoudnessConfig {
+    fn default() -> Self {
+        Self {
+            target_lufs: -23.0,
+            tolerance_lu: 1.0,
+            max_true_peak_dbtp: -1.0,
+            measure: LoudnessMeasure::Integrated,
+            min_measure_ms: 3000.0,
+        }
+    }
+}
+
+/// Second-order IIR section (transposed direct form II)
+#[derive(Debug, Clone, Copy)]
+struct Biquad {
+    b: [f64; 3],
+    a: [f64; 2],
+    z: [f64; 2],
+}
+
+impl Biquad {
+    fn new(b: [f64; 3], a: [f64; 2]) -> Self {
+        Self { b, a, z: [0.0; 2] }
+    }
+
+    fn process(&mut self, x: f64) -> f64 {
+        let y = self.b[0] * x + self.z[0];
+        self.z[0] = self.b[1] * x - self.a[0] * y + self.z[1];
+        self.z[1] = self.b[2] * x - self.a[1] * y;
+        y
+    }
+}
+
+/// BS.1770 K-weighting (pre-filter shelf + RLB high-pass) for any sample rate
+fn k_weighting(sample_rate: u32) -> [Biquad; 2] {
+    let rate = sample_rate as f64;
+
+    let f0 = 1681.974450955533;
+    let gain_db = 3.999843853973347;
+    let q = 0.7071752369554196;
+    let k = (PI * f0 / rate).tan();
+    let vh = 10f64.powf(gain_db / 20.0);
+    let vb = vh.powf(0.4996667741545416);
+    let a0 = 1.0 + k / q + k * k;
+    let shelf = Biquad::new(
+        [
+            (vh + vb * k / q + k * k) / a0,
+            2.0 * (k * k - vh) / a0,
+            (vh - vb * k / q + k * k) / a0,
+        ],
+        [2.0 * (k * k - 1.0) / a0, (1.0 - k / q + k * k) / a0],
+    );
+
+    let f0 = 38.13547087602444;
+    let q = 0.5003270373238773;
+    let k = (PI * f0 / rate).tan();
+    let a0 = 1.0 + k / q + k * k;
+    let high_pass = Biquad::new(
+        [1.0, -2.0, 1.0],
+        [2.0 * (k * k - 1.0) / a0, (1.0 - k / q + k * k) / a0],
+    );
+
+    [shelf, high_pass]
+}
+
+/// BS.1770 channel weights: L, R, C = 1.0, LFE excluded, surrounds = 1.41
+fn channel_weights(channels: usize) -> Vec<f64> {
+    (0..channels)
+        .map(|ch| match (channels, ch) {
+            (6, 3) => 0.0,
+            (6, 4) | (6, 5) => 1.41,
+            _ => 1.0,
+        })
+        .collect()
+}
+
+fn energy_to_lufs(energy: f64) -> f64 {
+    -0.691 + 10.0 * energy.log10()
+}
+
+/// True-peak estimation by oversampling (BS.1770-4 Annex 2)
+#[derive(Debug, Clone)]
+struct TruePeak {
+    factor: usize,
+    /// Polyphase windowed-sinc coefficients, `phases[p][k]`
+    phases: Vec<Vec<f64>>,
+    /// Per-channel input history, newest at `pos`
+    history: Vec<Vec<f64>>,
+    pos: usize,
+    max: f64,
+}
+
+impl TruePeak {
+    fn new(sample_rate: u32, channels: usize) -> Self {
+        let factor = if sample_rate < 96_000 {
+            4
+        } else if sample_rate < 192_000 {
+            2
+        } else {
+            1
+        };
+        let len = TRUE_PEAK_TAPS.div_ceil(factor);
+        let mut phases = vec![Vec::with_capacity(len); factor];
+        for j in 0..TRUE_PEAK_TAPS {
+            let m = j as f64 - (TRUE_PEAK_TAPS - 1) as f64 / 2.0;
+            let sinc = if m.abs() < 1e-9 {
+                1.0
+            } else {
+                let x = m * PI / factor as f64;
+                x.sin() / x
+            };
+            let window = 0.5 * (1.0 - (2.0 * PI * j as f64 / (TRUE_PEAK_TAPS - 1) as f64).cos());
+            phases[j % factor].push(sinc * window);
+        }
+        Self {
+            factor,
+            phases,
+            history: vec![vec![0.0; len]; channels],
+            pos: 0,
+            max: 0.0,
+        }
+    }
+
+    /// Feed one interleaved frame
+    fn push_frame(&mut self, frame: &[f64]) {
+        let len = self.history[0].len();
+        self.pos = (self.pos + 1) % len;
+        for (ch, &x) in frame.iter().enumerate() {
+            let history = &mut self.history[ch];
+            history[self.pos] = x;
+            if self.factor == 1 {
+                self.max = self.max.max(x.abs());
+                continue;
+            }
+            for phase in &self.phases {
+                let mut y = 0.0;
+                for (k, c) in phase.iter().enumerate() {
+                    y += c * history[(self.pos + len - k) % len];
+                }
+                self.max = self.max.max(y.abs());
+            }
+        }
+    }
+
+    fn dbtp(&self) -> f64 {
+        if self.max <= 0.0 {
+            -120.0
+        } else {
+            20.0 * self.max.log10()
+        }
+    }
+}
+
+/// Streaming BS.1770-4 loudness meter
+///
+/// Feed interleaved `f32` PCM with [`push`](Self::push); read momentary,
+/// short-term, integrated loudness and true-peak at any time. Integrated
+/// loudness keeps a fixed-size gating histogram, so memory does not grow
+/// with stream length.
+#[derive(Debug, Clone)]
+pub struct LoudnessMeter {
+    sample_rate: u32,
+    channels: usize,
+    filters: Vec<[Biquad; 2]>,
+    weights: Vec<f64>,
+    /// Frames per 100 ms sub-block
+    sub_block_frames: usize,
+    frames_in_sub_block: usize,
+    /// Per-channel sum of squares in the current sub-block
+    sub_block_sums: Vec<f64>,
+    /// Weighted mean-square energy of the most recent sub-blocks
+    recent: VecDeque<f64>,
+    /// Gating histogram: (block count, summed block energy) per 0.1 LU bin
+    histogram: Vec<(u64, f64)>,
+    true_peak: TruePeak,
+    frames_total: u64,
+}
+
+impl LoudnessMeter {
+    /// Create a meter for interleaved audio with the given layout
+    pub fn new(sample_rate: u32, channels: usize) -> Self {
+        let channels = channels.max(1);
+        Self {
+            sample_rate,
+            channels,
+            filters: (0..channels).map(|_| k_weighting(sample_rate)).collect(),
+            weights: channel_weights(channels),
+            sub_block_frames: (sample_rate as usize / 10).max(1),
+            frames_in_sub_block: 0,
+            sub_block_sums: vec![0.0; channels],
+            recent: VecDeque::with_capacity(SHORT_TERM_BLOCKS),
+            histogram: vec![(0, 0.0); HISTOGRAM_BINS],
+            true_peak: TruePeak::new(sample_rate, channels),
+            frames_total: 0,
+        }
+    }
+
+    /// Sample rate this meter was created for
+    pub fn sample_rate(&self) -> u32 {
+        self.sample_rate
+    }
+
+    /// Channel count this meter was created for
+    pub fn channels(&self) -> usize {
+        self.channels
+    }
+
+    /// Audio measured so far, in milliseconds
+    pub fn duration_ms(&self) -> f64 {
+        self.frames_total as f64 * 1000.0 / self.sample_rate as f64
+    }
+
+    /// Feed interleaved samples. A trailing partial frame is ignored.
+    pub fn push(&mut self, samples: &[f32]) {
+        let mut frame = vec![0.0f64; self.channels];
+        for chunk in samples.chunks_exact(self.channels) {
+            for (ch, &s) in chunk.iter().enumerate() {
+                let x = s as f64;
+                frame[ch] = x;
+                let [shelf, high_pass] = &mut self.filters[ch];
+                let y = high_pass.process(shelf.process(x));
+                self.sub_block_sums[ch] += y * y;
+            }
+            self.true_peak.push_frame(&frame);
+            self.frames_total += 1;
+            self.frames_in_sub_block += 1;
+            if self.frames_in_sub_block == self.sub_block_frames {
+                self.finish_sub_block();
+            }
+        }
+    }
+
+    fn finish_sub_block(&mut self) {
+        let n = self.frames_in_sub_block as f64;
+        let energy: f64 = self
+            .sub_block_sums
+            .iter()
+            .zip(&self.weights)
+            .map(|(sum, w)| w * sum / n)
+            .sum();
+        self.sub_block_sums.iter_mut().for_each(|s| *s = 0.0);
+        self.frames_in_sub_block = 0;
+
+        if self.recent.len() == SHORT_TERM_BLOCKS {
+            self.recent.pop_front();
+        }
+        self.recent.push_back(energy);
+
+        // Each new sub-block completes a 400 ms gating block (75% overlap)
+        if let Some(block_energy) = self.window_energy(MOMENTARY_BLOCKS) {
+            let lufs = energy_to_lufs(block_energy);
+            if lufs >= ABSOLUTE_GATE_LUFS {
+                let bin = (((lufs - ABSOLUTE_GATE_LUFS) * 10.0) as usize).min(HISTOGRAM_BINS - 1);
+                self.histogram[bin].0 += 1;
+                self.histogram[bin].1 += block_energy;
+            }
+        }
+    }
+
+    fn window_energy(&self, blocks: usize) -> Option<f64> {
+        if self.recent.len() < blocks {
+            return None;
+        }
+        let sum: f64 = self.recent.iter().rev().take(blocks).sum();
+        Some(sum / blocks as f64)
+    }
+
+    /// Momentary loudness (400 ms) in LUFS
+    pub fn momentary_lufs(&self) -> Option<f64> {
+        self.window_energy(MOMENTARY_BLOCKS).map(energy_to_lufs)
+    }
+
+    /// Short-term loudness (3 s) in LUFS
+    pub fn short_term_lufs(&self) -> Option<f64> {
+        self.window_energy(SHORT_TERM_BLOCKS).map(energy_to_lufs)
+    }
+
+    /// Gated integrated loudness in LUFS
+    pub fn integrated_lufs(&self) -> Option<f64> {
+        let (count, energy) = self
+            .histogram
+            .iter()
+            .fold((0u64, 0.0), |(c, e), &(bc, be)| (c + bc, e + be));
+        if count == 0 {
+            return None;
+        }
+
+        let relative_gate = energy_to_lufs(energy / count as f64) + RELATIVE_GATE_LU;
+        // Bins straddling the relative gate are included whole (0.1 LU resolution)
+        let first_bin = ((relative_gate - ABSOLUTE_GATE_LUFS) * 10.0)
+            .floor()
+            .max(0.0) as usize;
+        let (count, energy) = self.histogram[first_bin.min(HISTOGRAM_BINS)..]
+            .iter()
+            .fold((0u64, 0.0), |(c, e), &(bc, be)| (c + bc, e + be));
+        if count == 0 {
+            return None;
+        }
+        Some(energy_to_lufs(energy / count as f64))
+    }
+
+    /// Maximum true-peak so far in dBTP
+    pub fn true_peak_dbtp(&self) -> f64 {
+        self.true_peak.dbtp()
+    }
+
+    /// Forget everything measured so far
+    pub fn reset(&mut self) {
+        *self = Self::new(self.sample_rate, self.channels);
+    }
+}
+
+/// Node that measures EBU R128 loudness and true-peak
+pub struct LoudnessNode {
+    node_id: String,
+    config: LoudnessConfig,
+    /// Created on first audio; rebuilt if the layout changes
+    meter: Mutex<Option<LoudnessMeter>>,
+}
+
+impl LoudnessNode {
+    /// Create a new LoudnessNode
+    pub fn new(node_id: String, config: LoudnessConfig) -> Self {
+        Self {
+            node_id,
+            config,
+            meter: Mutex::new(None),
+        }
+    }
+
+    fn evaluate(&self, meter: &LoudnessMeter) -> (Option<f64>, bool, bool, bool, f32) {
+        let measured = match self.config.measure {
+            LoudnessMeasure::Momentary => meter.momentary_lufs(),
+            LoudnessMeasure::ShortTerm => meter.short_term_lufs(),
+            LoudnessMeasure::Integrated => meter.integrated_lufs(),
+        };
+        let judged = meter.duration_ms() >= self.config.min_measure_ms;
+        let deviation_lu = measured.map(|m| m - self.config.target_lufs);
+
+        let (is_too_loud, is_too_quiet) = match deviation_lu {
+            Some(d) if judged => (d > self.config.tolerance_lu, d < -self.config.tolerance_lu),
+            _ => (false, false),
+        };
+        let is_true_peak_exceeded = meter.true_peak_dbtp() > self.config.max_true_peak_dbtp;
+
+        // Health: full within tolerance, degrades linearly to 0 at 3× tolerance
+        let health = if is_true_peak_exceeded {
+            0.0
+        } else {
+            match deviation_lu {
+                Some(d) if judged && self.config.tolerance_lu > 0.0 => {
+                    let excess = (d.abs() - self.config.tolerance_lu).max(0.0);
+                    (1.0 - excess / (2.0 * self.config.tolerance_lu)).clamp(0.0, 1.0) as f32
+                }
+                _ => 1.0,
+            }
+        };
+
+        (
+            deviation_lu,
+            is_too_loud,
+            is_too_quiet,
+            is_true_peak_exceeded,
+            health,
+        )
+    }
+
+    fn process_audio(&self, input: RuntimeData) -> Result<RuntimeData, Error> {
+        let (samples, sample_rate, channels, stream_id, timestamp_us) = match &input {
+            RuntimeData::Audio {
+                samples,
+                sample_rate,
+                channels,
+                stream_id,
+                timestamp_us,
+                ..
+            } => (
+                samples.clone(),
+                *sample_rate,
+                *channels as usize,
+                stream_id.clone(),
+                *timestamp_us,
+            ),
+            _ => return Ok(input), // Pass through non-audio data
+        };
+
+        let mut guard = self.meter.lock().unwrap();
+        let layout_changed = guard
+            .as_ref()
+            .map(|m| m.sample_rate() != sample_rate || m.channels() != channels.max(1))
+            .unwrap_or(true);
+        if layout_changed {
+            if guard.is_some() {
+                tracing::debug!(
+                    "LoudnessNode {}: audio layout changed to {}Hz/{}ch, restarting measurement",
+                    self.node_id,
+                    sample_rate,
+                    channels
+                );
+            }
+            *guard = Some(LoudnessMeter::new(sample_rate, channels));
+        }
+        let meter = guard.as_mut().expect("meter initialised above");
+        meter.push(&samples);
+
+        let (deviation_lu, is_too_loud, is_too_quiet, is_true_peak_exceeded, health) =
+            self.evaluate(meter);
+
+        let event = LoudnessEvent {
+            momentary_lufs: meter.momentary_lufs(),
+            short_term_lufs: meter.short_term_lufs(),
+            integrated_lufs: meter.integrated_lufs(),
+            true_peak_dbtp: meter.true_peak_dbtp(),
+            target_lufs: self.config.target_lufs,
+            deviation_lu,
+            is_too_loud,
+            is_too_quiet,
+            is_true_peak_exceeded,
+            health,
+            stream_id,
+            timestamp_us,
+        };
+        drop(guard);
+
+        // Log if issues detected
+        if is_too_loud || is_too_quiet {
+            tracing::debug!(
+                "LoudnessNode {}: {} loudness off target by {:+.1} LU",
+                self.node_id,
+                self.config.measure.as_str(),
+                deviation_lu.unwrap_or_default()
+            );
+        }
+        if is_true_peak_exceeded {
+            tracing::debug!(
+                "LoudnessNode {}: True-peak {:.1} dBTP above limit",
+                self.node_id,
+                event.true_peak_dbtp
+            );
+        }
+
+        // Output the loudness event as JSON
+        let mut event_json = serde_json::to_value(&event).unwrap_or(Value::Null);
+        if let Value::Object(ref mut map) = event_json {
+            map.insert(
+                "_schema".to_string(),
+                Value::String("loudness_event".to_string()),
+            );
+            map.insert(
+                "measure".to_string(),
+                Value::String(self.config.measure.as_str().to_string()),
+            );
+            map.insert("tolerance_lu".to_string(), self.config.tolerance_lu.into());
+            map.insert(
+                "max_true_peak_dbtp".to_string(),
+                self.config.max_true_peak_dbtp.into(),
+            );
+        }
+        Ok(RuntimeData::Json(event_json))
+    }
+}
+
+// `SyncStreamingNode` — body is sync; meter state sits behind a
+// `std::sync::Mutex` (no `.await`). Wrapped through `SyncNodeWrapper` at
+// the registry boundary.
+impl SyncStreamingNode for LoudnessNode {
+    fn node_type(&self) -> &str {
+        "LoudnessNode"
+    }
+
+    fn process(&self, data: RuntimeData) -> Result<RuntimeData, Error> {
+        self.process_audio(data)
+    }
+
+    fn process_multi(&self, inputs: HashMap<String, RuntimeData>) -> Result<RuntimeData, Error> {
+        if let Some((_key, data)) = inputs.into_iter().next() {
+            self.process_audio(data)
+        } else {
+            Err(Error::Execution("No input data".to_string()))
+        }
+    }
+
+    fn is_multi_input(&self) -> bool {
+        false
+    }
+}
+
+/// Factory for creating LoudnessNode instances
+pub struct LoudnessNodeFactory;
+
+impl crate::nodes::StreamingNodeFactory for LoudnessNodeFactory {
+    fn create(
+        &self,
+        node_id: String,
+        params: &Value,
+        _session_id: Option<String>,
+    ) -> Result<Box<dyn StreamingNode>, Error> {
+        let config: LoudnessConfig = if params.is_null() {
+            LoudnessConfig::default()
+        } else {
+            serde_json::from_value(params.clone())
+                .map_err(|e| Error::InvalidManifest(format!("LoudnessNode params: {}", e)))?
+        };
+
+        Ok(Box::new(SyncNodeWrapper(LoudnessNode::new(
+            node_id, config,
+        ))))
+    }
+
+    fn node_type(&self) -> &str {
+        "LoudnessNode"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Interleaved sine, same signal on every channel
+    fn sine(freq: f64, amplitude: f64, sample_rate: u32, channels: usize, secs: f64) -> Vec<f32> {
+        let frames = (sample_rate as f64 * secs) as usize;
+        (0..frames)
+            .flat_map(|n| {
+                let s = amplitude * (2.0 * PI * freq * n as f64 / sample_rate as f64).sin();
+                std::iter::repeat(s as f32).take(channels)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_stereo_1khz_at_minus_23_dbfs_reads_minus_23_lufs() {
+        // EBU Tech 3341 case 1: 1 kHz stereo sine at -23 dBFS => -23.0 ±0.1 LUFS
+        let amplitude = 10f64.powf(-23.0 / 20.0);
+        let mut meter = LoudnessMeter::new(48_000, 2);
+        meter.push(&sine(1000.0, amplitude, 48_000, 2, 20.0));
+
+        for lufs in [
+            meter.momentary_lufs().unwrap(),
+            meter.short_term_lufs().unwrap(),
+            meter.integrated_lufs().unwrap(),
+        ] {
+            assert!((lufs + 23.0).abs() < 0.1, "measured {lufs} LUFS");
+        }
+    }
+
+    #[test]
+    fn test_sample_rate_independent() {
+        let amplitude = 10f64.powf(-20.0 / 20.0);
+        let mut meter = LoudnessMeter::new(44_100, 1);
+        meter.push(&sine(1000.0, amplitude, 44_100, 1, 5.0));
+        // Mono carries half the energy of the same sine in stereo
+        let expected = -20.0 - 3.0103;
+        let measured = meter.integrated_lufs().unwrap();
+        assert!(
+            (measured - expected).abs() < 0.1,
+            "measured {measured} LUFS"
+        );
+    }
+
+    #[test]
+    fn test_relative_gate_ignores_quiet_passages() {
+        let loud = 10f64.powf(-20.0 / 20.0);
+        let quiet = 10f64.powf(-50.0 / 20.0);
+        let mut meter = LoudnessMeter::new(48_000, 2);
+        meter.push(&sine(1000.0, loud, 48_000, 2, 10.0));
+        meter.push(&sine(1000.0, quiet, 48_000, 2, 10.0));
+        // The -50 passage is >10 LU below the mean and gated out
+        let measured = meter.integrated_lufs().unwrap();
+        assert!((measured + 20.0).abs() < 0.2, "measured {measured} LUFS");
+    }
+
+    #[test]
+    fn test_silence_has_no_integrated_loudness() {
+        let mut meter = LoudnessMeter::new(48_000, 2);
+        meter.push(&vec![0.0; 48_000 * 2]);
+        assert!(meter.integrated_lufs().is_none());
+        assert!(meter.short_term_lufs().is_none());
+        assert!(meter.true_peak_dbtp() <= -120.0);
+    }
+
+    #[test]
+    fn test_true_peak_finds_inter_sample_peaks() {
+        // fs/4 sine at 45° phase: every sample sits at ±0.707 of the peak
+        let sample_rate = 48_000;
+        let samples: Vec<f32> = (0..4800)
+            .map(|n| (2.0 * PI * n as f64 / 4.0 + PI / 4.0).sin() as f32)
+            .collect();
+        let sample_peak_db =
+            20.0 * (samples.iter().fold(0f32, |m, s| m.max(s.abs())) as f64).log10();
+
+        let mut meter = LoudnessMeter::new(sample_rate, 1);
+        meter.push(&samples);
+        let true_peak = meter.true_peak_dbtp();
+        assert!(sample_peak_db < -2.9);
+        assert!(true_peak > -0.6, "true-peak {true_peak} dBTP");
+    }
+
+    #[test]
+    fn test_node_flags_loud_stream() {
+        let node = LoudnessNode::new(
+            "loudness".to_string(),
+            LoudnessConfig {
+                min_measure_ms: 0.0,
+                ..Default::default()
+            },
+        );
+        let input = RuntimeData::Audio {
+            samples: sine(1000.0, 0.5, 48_000, 2, 1.0).into(),
+            sample_rate: 48_000,
+            channels: 2,
+            stream_id: Some("program".to_string()),
+            timestamp_us: None,
+            arrival_ts_us: None,
+            metadata: None,
+        };
+
+        let RuntimeData::Json(event) = node.process(input).unwrap() else {
+            panic!("expected JSON event");
+        };
+        assert_eq!(event["_schema"], "loudness_event");
+        assert_eq!(event["is_too_loud"], true);
+        assert_eq!(event["is_too_quiet"], false);
+        assert_eq!(event["measure"], "integrated");
+        assert!(event["integrated_lufs"].as_f64().unwrap() > -10.0);
+    }
+}
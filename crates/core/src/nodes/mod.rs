@@ -71,7 +71,7 @@ pub mod llama_cpp;
 pub use llama_cpp::{
     LlamaCppConfig, LlamaCppGenerationConfig, LlamaCppEmbeddingConfig,
     LlamaCppActivationConfig, LlamaCppSteerConfig, LlamaCppSteerVector,
-    LlamaBackendConfig, GpuOffload,
+    LlamaBackendConfig, GpuOffload, LlamaToolChoice,
     LlamaCppGenerationNode, LlamaCppGenerationNodeFactory,
     LlamaCppEmbeddingNode, LlamaCppEmbeddingNodeFactory,
     LlamaCppActivationNode, LlamaCppActivationNodeFactory,
@@ -97,6 +97,9 @@ pub use channel_balance::{ChannelBalanceNode, ChannelBalanceConfig, ChannelBalan
 pub mod silence_detector;
 pub use silence_detector::{SilenceDetectorNode, SilenceConfig, SilenceDetectorNodeFactory, SilenceEvent};
 
+pub mod loudness;
+pub use loudness::{LoudnessNode, LoudnessConfig, LoudnessMeasure, LoudnessMeter, LoudnessNodeFactory, LoudnessEvent};
+
 // Stream health monitoring nodes (business layer)
 pub mod speech_presence;
 pub use speech_presence::{SpeechPresenceNode, SpeechPresenceConfig, SpeechPresenceNodeFactory};
@@ -129,6 +132,14 @@ pub use audio_resample_streaming::ResampleStreamingNode;
 pub mod text_collector;
 pub use text_collector::TextCollectorNode;
 
+// HLS / LL-HLS egress
+pub mod hls;
+pub use hls::{HlsSinkConfig, HlsSinkNode, HlsSinkNodeFactory};
+
+// SRT / WebVTT subtitle generation
+pub mod subtitles;
+pub use subtitles::{SubtitleSinkConfig, SubtitleSinkNode, SubtitleSinkNodeFactory};
+
 pub mod video_flip;
 pub use video_flip::VideoFlipNode;
 
@@ -28,7 +28,11 @@
 use crate::data::{tag_text_str, RuntimeData, TEXT_CHANNEL_DEFAULT};
 use crate::error::Error;
 use crate::llm::audio_encode::audio_to_wav_base64;
-use crate::llm::{data_url::image_to_data_url, ChatBackend, ChatBackendConfig, ProviderKind};
+use crate::llm::{
+    data_url::image_to_data_url, ChatBackend, ChatBackendConfig, HistoryBudget,
+    HistoryStoreConfig, ProviderKind, ToolHandler, ToolHandlerRegistry, TruncatingSummarizer,
+    DEFAULT_MAX_TOOL_ITERATIONS,
+};
 use crate::nodes::AsyncStreamingNode;
 use parking_lot::Mutex;
 use serde::{Deserialize, Serialize};
@@ -135,6 +139,12 @@ pub struct MultimodalLLMConfig {
     pub top_p: Option<f32>,
     #[serde(alias = "historyTurns")]
     pub history_turns: usize,
+    /// Where conversation history is kept (see
+    /// [`crate::nodes::openai_chat::OpenAIChatConfig::history_store`]).
+    #[serde(default, alias = "historyStore")]
+    pub history_store: Option<HistoryStoreConfig>,
+    #[serde(default, alias = "historyBudget")]
+    pub history_budget: Option<HistoryBudget>,
     pub streaming: bool,
 
     // ── Tool calling (same shape as OpenAIChatConfig) ───────────────
@@ -148,6 +158,8 @@ pub struct MultimodalLLMConfig {
     pub active_tools: Option<Vec<String>>,
     #[serde(default, alias = "toolChoice")]
     pub tool_choice: Option<Value>,
+    #[serde(alias = "maxToolIterations")]
+    pub max_tool_iterations: usize,
 
     // ── Multimodal-only ─────────────────────────────────────────────
     /// Aggregation policy. See [`AggregationMode`].
@@ -155,7 +167,8 @@ pub struct MultimodalLLMConfig {
     pub aggregation: AggregationMode,
 
     /// LLM vendor profile. Default `OpenAI`. Set to `anthropic` to
-    /// target the Messages API; the node will reshape image and tool
+    /// target the Messages API or `gemini` for the Generative
+    /// Language API; the node will reshape image, audio and tool
     /// content automatically.
     #[serde(default, alias = "provider")]
     pub provider: ProviderKind,
@@ -201,12 +214,15 @@ impl Default for MultimodalLLMConfig {
             temperature: None,
             top_p: None,
             history_turns: 10,
+            history_store: None,
+            history_budget: None,
             streaming: true,
             enable_say_tool: false,
             enable_show_tool: false,
             tools: Vec::new(),
             active_tools: None,
             tool_choice: None,
+            max_tool_iterations: DEFAULT_MAX_TOOL_ITERATIONS,
             aggregation: AggregationMode::default(),
             provider: ProviderKind::default(),
             output_mode: LlmOutputMode::Text,
@@ -225,6 +241,7 @@ impl Default for MultimodalLLMConfig {
 pub struct MultimodalLLMNode {
     config: MultimodalLLMConfig,
     backend: Arc<ChatBackend>,
+    tool_handlers: ToolHandlerRegistry,
     /// Per-session content-parts buffer used by the `CoalesceUntil`
     /// aggregation mode. `PerInput` mode never reads or writes this.
     coalesce_buffers: Arc<Mutex<HashMap<String, Vec<Value>>>>,
@@ -233,14 +250,40 @@ pub struct MultimodalLLMNode {
 }
 
 impl MultimodalLLMNode {
-    pub fn with_config(config: MultimodalLLMConfig) -> Self {
+    pub fn with_config(config: MultimodalLLMConfig) -> Result<Self, Error> {
         let profile = config.provider.into_profile();
-        Self {
+        let tool_handlers = ToolHandlerRegistry::from_specs(&config.tools)?;
+        let mut backend = ChatBackend::new(profile);
+        if let Some(ref store) = config.history_store {
+            backend = backend.with_history_store(store.open()?);
+        }
+        if let Some(budget) = config.history_budget {
+            backend = backend.with_summarizer(budget, Arc::new(TruncatingSummarizer));
+        }
+        Ok(Self {
             config,
-            backend: Arc::new(ChatBackend::new(profile)),
+            backend: Arc::new(backend),
+            tool_handlers,
             coalesce_buffers: Arc::new(Mutex::new(HashMap::new())),
             embedding_client: std::sync::OnceLock::new(),
-        }
+        })
+    }
+
+    /// Register a Rust handler for a `return_value` tool, overriding
+    /// any handler declared in the manifest for the same name.
+    pub fn with_tool_handler(
+        mut self,
+        name: impl Into<String>,
+        handler: Arc<dyn ToolHandler>,
+    ) -> Self {
+        self.tool_handlers.register(name, handler);
+        self
+    }
+
+    /// The node's chat backend — export or seed a session's history
+    /// through it.
+    pub fn backend(&self) -> &Arc<ChatBackend> {
+        &self.backend
     }
 
     /// Get the embedding HTTP client (lazy-initialized).
@@ -356,10 +399,8 @@ impl MultimodalLLMNode {
 
     fn resolve_api_key(&self) -> Option<String> {
         self.config
-            .api_key
-            .clone()
-            .filter(|s| !s.is_empty())
-            .or_else(|| std::env::var("OPENAI_API_KEY").ok().filter(|s| !s.is_empty()))
+            .provider
+            .resolve_api_key(self.config.api_key.as_deref())
     }
 
     fn resolve_base_url(&self) -> String {
@@ -373,31 +414,16 @@ impl MultimodalLLMNode {
         self.config
             .model
             .clone()
-            .unwrap_or_else(|| "gpt-4o-mini".to_string())
+            .unwrap_or_else(|| self.config.provider.default_model().to_string())
     }
 
     fn build_tool_registry(&self) -> Vec<crate::nodes::tool_spec::ToolSpec> {
-        use crate::nodes::tool_spec::{default_say_tool, default_show_tool, ToolSpec};
-        let mut out: Vec<ToolSpec> = Vec::new();
-        if self.config.enable_say_tool {
-            out.push(default_say_tool());
-        }
-        if self.config.enable_show_tool {
-            out.push(default_show_tool());
-        }
-        for spec in &self.config.tools {
-            if let Some(existing) = out.iter_mut().find(|t| t.name == spec.name) {
-                *existing = spec.clone();
-            } else {
-                out.push(spec.clone());
-            }
-        }
-        if let Some(ref active) = self.config.active_tools {
-            let active: std::collections::HashSet<&str> =
-                active.iter().map(String::as_str).collect();
-            out.retain(|t| active.contains(t.name.as_str()));
-        }
-        out
+        crate::nodes::tool_spec::build_tool_registry(
+            self.config.enable_say_tool,
+            self.config.enable_show_tool,
+            &self.config.tools,
+            self.config.active_tools.as_deref(),
+        )
     }
 
     fn backend_config(&self) -> ChatBackendConfig {
@@ -423,6 +449,8 @@ impl MultimodalLLMNode {
                 .filter(|s| !s.is_empty()),
             tools: self.build_tool_registry(),
             tool_choice: self.config.tool_choice.clone(),
+            tool_handlers: self.tool_handlers.clone(),
+            max_tool_iterations: self.config.max_tool_iterations,
         }
     }
 
@@ -688,7 +716,7 @@ impl crate::nodes::StreamingNodeFactory for MultimodalLLMNodeFactory {
     ) -> Result<Box<dyn crate::nodes::StreamingNode>, Error> {
         let config: MultimodalLLMConfig =
             serde_json::from_value(params.clone()).unwrap_or_default();
-        let node = MultimodalLLMNode::with_config(config);
+        let node = MultimodalLLMNode::with_config(config)?;
         Ok(Box::new(crate::nodes::AsyncNodeWrapper(Arc::new(node))))
     }
 
@@ -793,7 +821,7 @@ mod tests {
             },
             ..Default::default()
         };
-        let node = MultimodalLLMNode::with_config(cfg);
+        let node = MultimodalLLMNode::with_config(cfg).unwrap();
         let part = || serde_json::json!({"type":"text","text":"x"});
 
         // First two parts buffer.
@@ -821,7 +849,7 @@ mod tests {
             },
             ..Default::default()
         };
-        let node = MultimodalLLMNode::with_config(cfg);
+        let node = MultimodalLLMNode::with_config(cfg).unwrap();
         node.coalesce_buffers
             .lock()
             .insert("a".into(), vec![serde_json::json!({"type":"text","text":"A"})]);
@@ -848,7 +876,7 @@ mod tests {
 
     #[test]
     fn text_input_builds_text_part() {
-        let node = MultimodalLLMNode::with_config(MultimodalLLMConfig::default());
+        let node = MultimodalLLMNode::with_config(MultimodalLLMConfig::default()).unwrap();
         let part = node.input_to_part(&RuntimeData::Text("hello".into())).unwrap();
         match part {
             Some(InputPart::Part(v)) => {
@@ -861,7 +889,7 @@ mod tests {
 
     #[test]
     fn image_input_builds_image_url_part_with_data_url() {
-        let node = MultimodalLLMNode::with_config(MultimodalLLMConfig::default());
+        let node = MultimodalLLMNode::with_config(MultimodalLLMConfig::default()).unwrap();
         let part = node.input_to_part(&img()).unwrap();
         match part {
             Some(InputPart::Part(v)) => {
@@ -875,14 +903,14 @@ mod tests {
 
     #[test]
     fn empty_text_dropped_silently() {
-        let node = MultimodalLLMNode::with_config(MultimodalLLMConfig::default());
+        let node = MultimodalLLMNode::with_config(MultimodalLLMConfig::default()).unwrap();
         let part = node.input_to_part(&RuntimeData::Text("".into())).unwrap();
         assert!(part.is_none());
     }
 
     #[test]
     fn audio_input_emits_input_audio_part() {
-        let node = MultimodalLLMNode::with_config(MultimodalLLMConfig::default());
+        let node = MultimodalLLMNode::with_config(MultimodalLLMConfig::default()).unwrap();
         let audio = RuntimeData::Audio {
             samples: vec![0.0_f32; 16].into(),
             sample_rate: 16000,
@@ -910,7 +938,7 @@ mod tests {
 
     #[test]
     fn unsupported_input_still_rejected() {
-        let node = MultimodalLLMNode::with_config(MultimodalLLMConfig::default());
+        let node = MultimodalLLMNode::with_config(MultimodalLLMConfig::default()).unwrap();
         let bin = RuntimeData::Binary(vec![0u8; 4]);
         let err = node.input_to_part(&bin).unwrap_err();
         assert!(format!("{}", err).contains("does not accept"));
@@ -925,7 +953,7 @@ mod tests {
             },
             ..Default::default()
         };
-        let node = MultimodalLLMNode::with_config(cfg);
+        let node = MultimodalLLMNode::with_config(cfg).unwrap();
         // First image goes to buffer.
         let p = node.input_to_part(&img()).unwrap().unwrap();
         if let InputPart::Part(v) = p {
@@ -965,6 +993,21 @@ mod tests {
         assert_eq!(node.node_type(), "MultimodalLLMNode");
     }
 
+    #[test]
+    fn factory_rejects_invalid_tool_handler() {
+        let factory = MultimodalLLMNodeFactory;
+        let params = serde_json::json!({
+            "tools": [{
+                "name": "broken",
+                "description": "",
+                "parameters": {"type": "object"},
+                "kind": "return_value",
+                "handler": {"type": "http", "url": "not a url"}
+            }]
+        });
+        assert!(factory.create("n1".into(), &params, None).is_err());
+    }
+
     /// End-to-end: a sentinel arriving with a non-empty buffer pulls
     /// the buffer through the backend's request builder. We can't
     /// actually issue HTTP in unit tests, but we can assert the
@@ -980,7 +1023,7 @@ mod tests {
             },
             ..Default::default()
         };
-        let node = MultimodalLLMNode::with_config(cfg);
+        let node = MultimodalLLMNode::with_config(cfg).unwrap();
         node.coalesce_buffers.lock().insert(
             "s".into(),
             vec![serde_json::json!({"type":"text","text":"x"})],
@@ -20,7 +20,10 @@
 
 use crate::data::{RuntimeData, TEXT_CHANNEL_DEFAULT};
 use crate::error::Error;
-use crate::llm::{ChatBackend, ChatBackendConfig, ProviderKind};
+use crate::llm::{
+    ChatBackend, ChatBackendConfig, HistoryBudget, HistoryStoreConfig, ProviderKind, ToolHandler,
+    ToolHandlerRegistry, TruncatingSummarizer, DEFAULT_MAX_TOOL_ITERATIONS,
+};
 use crate::nodes::AsyncStreamingNode;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
@@ -37,16 +40,20 @@ use std::sync::Arc;
 #[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
 #[serde(default)]
 pub struct OpenAIChatConfig {
-    /// OpenAI API key. Also read from `OPENAI_API_KEY` env var if omitted.
+    /// API key. If omitted, read from the provider's env var:
+    /// `OPENAI_API_KEY`, `ANTHROPIC_API_KEY` then `OPENAI_API_KEY`, or
+    /// `GEMINI_API_KEY` / `GOOGLE_API_KEY`.
     #[serde(alias = "apiKey")]
     pub api_key: Option<String>,
 
     /// Base URL for the API endpoint.
-    /// Default: `https://api.openai.com/v1`
+    /// Default: the provider's public API (`https://api.openai.com/v1`
+    /// for OpenAI).
     #[serde(alias = "baseUrl")]
     pub base_url: Option<String>,
 
     /// Model identifier (e.g. `"gpt-4o"`, `"gpt-4o-mini"`, `"qwen2.5-7b"`).
+    /// Default: the provider's [`ProviderKind::default_model`].
     #[serde(alias = "model")]
     pub model: Option<String>,
 
@@ -90,6 +97,17 @@ pub struct OpenAIChatConfig {
     #[serde(alias = "historyTurns")]
     pub history_turns: usize,
 
+    /// Where conversation history is kept. Default: process memory.
+    /// `{"type": "sqlite", "path": "...", "ttl_secs": 86400}` persists
+    /// it so a reconnecting session resumes its conversation.
+    #[serde(default, alias = "historyStore")]
+    pub history_store: Option<HistoryStoreConfig>,
+
+    /// Token budget for stored history. When exceeded, turns older
+    /// than `keep_turns` are dropped at the end of a turn.
+    #[serde(default, alias = "historyBudget")]
+    pub history_budget: Option<HistoryBudget>,
+
     /// Whether to enable streaming responses. Default: `true`.
     /// When `false`, the full response is buffered then emitted as one chunk.
     #[serde(alias = "streaming")]
@@ -102,7 +120,9 @@ pub struct OpenAIChatConfig {
     // `tool_choice` field; the SSE parser accumulates
     // `delta.tool_calls` and dispatches `side_effect` tools (`say`,
     // `show`, ...) to the appropriate downstream channel without
-    // feeding a result back to the model.
+    // feeding a result back to the model. `return_value` tools run
+    // through a handler (manifest `handler` or Rust registration) and
+    // the model re-generates with the result.
     /// Register the built-in `say` tool. Routes its `text` argument
     /// to the `output_channel` (default `tts`) so it's spoken
     /// immediately.
@@ -131,8 +151,14 @@ pub struct OpenAIChatConfig {
     #[serde(default, alias = "toolChoice")]
     pub tool_choice: Option<Value>,
 
+    /// Maximum follow-up generation passes per user turn driven by
+    /// `return_value` tool results. Default: `5`.
+    #[serde(alias = "maxToolIterations")]
+    pub max_tool_iterations: usize,
+
     /// LLM vendor profile. Default `OpenAI`. Set to `anthropic` to
-    /// use the Messages API. Note: the public node name remains
+    /// use the Messages API or `gemini` for the Generative Language
+    /// API. Note: the public node name remains
     /// `OpenAIChatNode` for back-compat, but the profile genuinely
     /// drives the wire format — pick what matches your `base_url`.
     #[serde(default, alias = "provider")]
@@ -152,12 +178,15 @@ impl Default for OpenAIChatConfig {
             temperature: None,
             top_p: None,
             history_turns: 10,
+            history_store: None,
+            history_budget: None,
             streaming: true,
             enable_say_tool: false,
             enable_show_tool: false,
             tools: Vec::new(),
             active_tools: None,
             tool_choice: None,
+            max_tool_iterations: DEFAULT_MAX_TOOL_ITERATIONS,
             provider: ProviderKind::default(),
         }
     }
@@ -180,25 +209,51 @@ impl Default for OpenAIChatConfig {
 pub struct OpenAIChatNode {
     config: OpenAIChatConfig,
     backend: Arc<ChatBackend>,
+    tool_handlers: ToolHandlerRegistry,
 }
 
 impl OpenAIChatNode {
-    /// Create from a config struct.
-    pub fn with_config(config: OpenAIChatConfig) -> Self {
+    /// Create from a config struct. Fails when a tool handler is invalid
+    /// or the history store cannot be opened.
+    pub fn with_config(config: OpenAIChatConfig) -> Result<Self, Error> {
         let profile = config.provider.into_profile();
-        Self {
-            config,
-            backend: Arc::new(ChatBackend::new(profile)),
+        let tool_handlers = ToolHandlerRegistry::from_specs(&config.tools)?;
+        let mut backend = ChatBackend::new(profile);
+        if let Some(ref store) = config.history_store {
+            backend = backend.with_history_store(store.open()?);
+        }
+        if let Some(budget) = config.history_budget {
+            backend = backend.with_summarizer(budget, Arc::new(TruncatingSummarizer));
         }
+        Ok(Self {
+            config,
+            backend: Arc::new(backend),
+            tool_handlers,
+        })
+    }
+
+    /// The node's chat backend — export or seed a session's history
+    /// through it.
+    pub fn backend(&self) -> &Arc<ChatBackend> {
+        &self.backend
+    }
+
+    /// Register a Rust handler for a `return_value` tool, overriding
+    /// any handler declared in the manifest for the same name.
+    pub fn with_tool_handler(
+        mut self,
+        name: impl Into<String>,
+        handler: Arc<dyn ToolHandler>,
+    ) -> Self {
+        self.tool_handlers.register(name, handler);
+        self
     }
 
-    /// Resolve the effective API key (config → env var).
+    /// Resolve the effective API key (config → vendor env var).
     fn resolve_api_key(&self) -> Option<String> {
         self.config
-            .api_key
-            .clone()
-            .filter(|s| !s.is_empty())
-            .or_else(|| std::env::var("OPENAI_API_KEY").ok().filter(|s| !s.is_empty()))
+            .provider
+            .resolve_api_key(self.config.api_key.as_deref())
     }
 
     fn resolve_base_url(&self) -> String {
@@ -212,34 +267,19 @@ impl OpenAIChatNode {
         self.config
             .model
             .clone()
-            .unwrap_or_else(|| "gpt-4o-mini".to_string())
+            .unwrap_or_else(|| self.config.provider.default_model().to_string())
     }
 
     /// Build the active tool registry: built-ins gated by config flags
     /// + user-defined tools, deduped by name (later wins), then
     /// optionally filtered by `active_tools`.
     fn build_tool_registry(&self) -> Vec<crate::nodes::tool_spec::ToolSpec> {
-        use crate::nodes::tool_spec::{default_say_tool, default_show_tool, ToolSpec};
-        let mut out: Vec<ToolSpec> = Vec::new();
-        if self.config.enable_say_tool {
-            out.push(default_say_tool());
-        }
-        if self.config.enable_show_tool {
-            out.push(default_show_tool());
-        }
-        for spec in &self.config.tools {
-            if let Some(existing) = out.iter_mut().find(|t| t.name == spec.name) {
-                *existing = spec.clone();
-            } else {
-                out.push(spec.clone());
-            }
-        }
-        if let Some(ref active) = self.config.active_tools {
-            let active: std::collections::HashSet<&str> =
-                active.iter().map(String::as_str).collect();
-            out.retain(|t| active.contains(t.name.as_str()));
-        }
-        out
+        crate::nodes::tool_spec::build_tool_registry(
+            self.config.enable_say_tool,
+            self.config.enable_show_tool,
+            &self.config.tools,
+            self.config.active_tools.as_deref(),
+        )
     }
 
     /// Snapshot the per-call backend config from the node config.
@@ -266,6 +306,8 @@ impl OpenAIChatNode {
                 .filter(|s| !s.is_empty()),
             tools: self.build_tool_registry(),
             tool_choice: self.config.tool_choice.clone(),
+            tool_handlers: self.tool_handlers.clone(),
+            max_tool_iterations: self.config.max_tool_iterations,
         }
     }
 
@@ -416,7 +458,7 @@ impl crate::nodes::StreamingNodeFactory for OpenAIChatNodeFactory {
     ) -> Result<Box<dyn crate::nodes::StreamingNode>, Error> {
         let config: OpenAIChatConfig =
             serde_json::from_value(params.clone()).unwrap_or_default();
-        let node = OpenAIChatNode::with_config(config);
+        let node = OpenAIChatNode::with_config(config)?;
         Ok(Box::new(crate::nodes::AsyncNodeWrapper(Arc::new(node))))
     }
 
@@ -520,31 +562,28 @@ mod tests {
     #[test]
     fn test_node_type() {
         let config = OpenAIChatConfig::default();
-        let node = OpenAIChatNode::with_config(config);
+        let node = OpenAIChatNode::with_config(config).unwrap();
         assert_eq!(node.node_type(), "OpenAIChatNode");
     }
 
     #[test]
     fn test_resolve_base_url_default() {
         let config = OpenAIChatConfig::default();
-        let node = OpenAIChatNode::with_config(config);
-        assert_eq!(
-            node.resolve_base_url(),
-            "https://api.openai.com/v1"
-        );
+        let node = OpenAIChatNode::with_config(config).unwrap();
+        assert_eq!(node.resolve_base_url(), "https://api.openai.com/v1");
     }
 
     #[test]
     fn test_resolve_model_default() {
         let config = OpenAIChatConfig::default();
-        let node = OpenAIChatNode::with_config(config);
+        let node = OpenAIChatNode::with_config(config).unwrap();
         assert_eq!(node.resolve_model(), "gpt-4o-mini");
     }
 
     #[test]
     fn test_missing_api_key_returns_none() {
         let config = OpenAIChatConfig::default();
-        let node = OpenAIChatNode::with_config(config);
+        let node = OpenAIChatNode::with_config(config).unwrap();
         std::env::remove_var("OPENAI_API_KEY");
         assert!(node.resolve_api_key().is_none());
     }
@@ -554,16 +593,44 @@ mod tests {
         std::env::remove_var("OPENAI_API_KEY");
         let mut config = OpenAIChatConfig::default();
         config.api_key = Some("sk-test".into());
-        let node = OpenAIChatNode::with_config(config);
+        let node = OpenAIChatNode::with_config(config).unwrap();
         assert_eq!(node.resolve_api_key().as_deref(), Some("sk-test"));
     }
 
+    #[test]
+    fn test_gemini_key_never_falls_back_to_openai() {
+        let env = |name: &str| match name {
+            "OPENAI_API_KEY" => Some("sk-openai".to_string()),
+            "GOOGLE_API_KEY" => Some("google-key".to_string()),
+            _ => None,
+        };
+        assert_eq!(
+            ProviderKind::Gemini
+                .resolve_api_key_with(None, env)
+                .as_deref(),
+            Some("google-key")
+        );
+        assert_eq!(
+            ProviderKind::Gemini.resolve_api_key_with(None, |name| {
+                (name == "OPENAI_API_KEY").then(|| "sk-openai".to_string())
+            }),
+            None
+        );
+        // Anthropic keeps the `OPENAI_API_KEY` fallback
+        assert_eq!(
+            ProviderKind::Anthropic
+                .resolve_api_key_with(None, env)
+                .as_deref(),
+            Some("sk-openai")
+        );
+    }
+
     #[test]
     fn test_resolve_api_key_empty_string_treated_as_none() {
         std::env::remove_var("OPENAI_API_KEY");
         let mut config = OpenAIChatConfig::default();
         config.api_key = Some(String::new());
-        let node = OpenAIChatNode::with_config(config);
+        let node = OpenAIChatNode::with_config(config).unwrap();
         assert!(node.resolve_api_key().is_none());
     }
 
@@ -581,7 +648,7 @@ mod tests {
         config.api_key = Some("sk-test".into());
         config.system_prompt = Some("You are a translator.".into());
         config.model = Some("gpt-4o".into());
-        let node = OpenAIChatNode::with_config(config);
+        let node = OpenAIChatNode::with_config(config).unwrap();
         let body = body_for(&node, "sess1", "Hello");
         let messages = body["messages"].as_array().unwrap();
         assert_eq!(messages[0]["role"], "system");
@@ -594,21 +661,24 @@ mod tests {
     fn test_build_request_body_without_system_prompt() {
         let mut config = OpenAIChatConfig::default();
         config.api_key = Some("sk-test".into());
-        let node = OpenAIChatNode::with_config(config);
+        let node = OpenAIChatNode::with_config(config).unwrap();
         let body = body_for(&node, "sess1", "Hello");
         let messages = body["messages"].as_array().unwrap();
         assert_eq!(messages.len(), 1);
         assert_eq!(messages[0]["role"], "user");
     }
 
-    #[test]
-    fn test_history_append_and_retrieve() {
+    #[tokio::test]
+    async fn test_history_append_and_retrieve() {
         let mut config = OpenAIChatConfig::default();
         config.api_key = Some("sk-test".into());
-        let node = OpenAIChatNode::with_config(config);
-        node.backend.append_history("s1", HistoryEntry::user("Hi"));
+        let node = OpenAIChatNode::with_config(config).unwrap();
+        node.backend
+            .append_history("s1", HistoryEntry::user("Hi"))
+            .await;
         node.backend
-            .append_history("s1", HistoryEntry::assistant_text("Hello!"));
+            .append_history("s1", HistoryEntry::assistant_text("Hello!"))
+            .await;
 
         let entries = node.backend.history_snapshot("s1");
         assert_eq!(entries.len(), 2);
@@ -618,8 +688,50 @@ mod tests {
         assert_eq!(entries[1].message["content"], "Hello!");
     }
 
+    #[tokio::test]
+    async fn test_sqlite_history_survives_node_restart() {
+        let dir = tempfile::tempdir().unwrap();
+        let params = serde_json::json!({
+            "historyStore": {
+                "type": "sqlite",
+                "path": dir.path().join("history.db").to_str().unwrap()
+            }
+        });
+
+        let config: OpenAIChatConfig = serde_json::from_value(params.clone()).unwrap();
+        let node = OpenAIChatNode::with_config(config).unwrap();
+        node.backend()
+            .append_history("s1", HistoryEntry::user("My name is Ada."))
+            .await;
+        node.backend()
+            .append_history("s1", HistoryEntry::assistant_text("Hi Ada!"))
+            .await;
+        drop(node);
+
+        let config: OpenAIChatConfig = serde_json::from_value(params).unwrap();
+        let node = OpenAIChatNode::with_config(config).unwrap();
+        let body = body_for(&node, "s1", "What's my name?");
+        let messages = body["messages"].as_array().unwrap();
+        assert_eq!(messages.len(), 3);
+        assert_eq!(messages[0]["content"], "My name is Ada.");
+        assert_eq!(messages[1]["content"], "Hi Ada!");
+    }
+
     #[test]
-    fn test_history_round_trips_tool_calls() {
+    fn test_unopenable_history_store_fails_construction() {
+        let dir = tempfile::tempdir().unwrap();
+        let config: OpenAIChatConfig = serde_json::from_value(serde_json::json!({
+            "historyStore": {
+                "type": "sqlite",
+                "path": dir.path().join("missing/history.db").to_str().unwrap()
+            }
+        }))
+        .unwrap();
+        assert!(OpenAIChatNode::with_config(config).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_history_round_trips_tool_calls() {
         // Tools-only replies must commit an assistant message with
         // tool_calls + a tool result per call, otherwise the model
         // can't recall its own outputs and the next request 400s on
@@ -627,26 +739,30 @@ mod tests {
         let mut config = OpenAIChatConfig::default();
         config.api_key = Some("sk-test".into());
         config.enable_say_tool = true;
-        let node = OpenAIChatNode::with_config(config);
-
-        node.backend.append_history("s1", HistoryEntry::user("hi"));
-        node.backend.extend_history(
-            "s1",
-            vec![
-                HistoryEntry::assistant_with_tool_calls(
-                    None,
-                    serde_json::json!([{
-                        "id": "call_0",
-                        "type": "function",
-                        "function": {
-                            "name": "say",
-                            "arguments": "{\"text\":\"Hello!\"}",
-                        },
-                    }]),
-                ),
-                HistoryEntry::tool_result("call_0", "say", ""),
-            ],
-        );
+        let node = OpenAIChatNode::with_config(config).unwrap();
+
+        node.backend
+            .append_history("s1", HistoryEntry::user("hi"))
+            .await;
+        node.backend
+            .extend_history(
+                "s1",
+                vec![
+                    HistoryEntry::assistant_with_tool_calls(
+                        None,
+                        serde_json::json!([{
+                            "id": "call_0",
+                            "type": "function",
+                            "function": {
+                                "name": "say",
+                                "arguments": "{\"text\":\"Hello!\"}",
+                            },
+                        }]),
+                    ),
+                    HistoryEntry::tool_result("call_0", "say", ""),
+                ],
+            )
+            .await;
 
         let body = body_for(&node, "s1", "what did you just say?");
         let messages = body["messages"].as_array().unwrap();
@@ -662,32 +778,38 @@ mod tests {
         assert_eq!(messages[3]["content"], "what did you just say?");
     }
 
-    #[test]
-    fn test_history_window_keeps_tool_results_intact() {
+    #[tokio::test]
+    async fn test_history_window_keeps_tool_results_intact() {
         let mut config = OpenAIChatConfig::default();
         config.api_key = Some("sk-test".into());
         config.history_turns = 1;
-        let node = OpenAIChatNode::with_config(config);
+        let node = OpenAIChatNode::with_config(config).unwrap();
 
-        node.backend.append_history("s1", HistoryEntry::user("hi"));
         node.backend
-            .append_history("s1", HistoryEntry::assistant_text("hello"));
+            .append_history("s1", HistoryEntry::user("hi"))
+            .await;
         node.backend
-            .append_history("s1", HistoryEntry::user("again"));
-        node.backend.extend_history(
-            "s1",
-            vec![
-                HistoryEntry::assistant_with_tool_calls(
-                    None,
-                    serde_json::json!([{
-                        "id": "call_0",
-                        "type": "function",
-                        "function": {"name": "say", "arguments": "{}"},
-                    }]),
-                ),
-                HistoryEntry::tool_result("call_0", "say", ""),
-            ],
-        );
+            .append_history("s1", HistoryEntry::assistant_text("hello"))
+            .await;
+        node.backend
+            .append_history("s1", HistoryEntry::user("again"))
+            .await;
+        node.backend
+            .extend_history(
+                "s1",
+                vec![
+                    HistoryEntry::assistant_with_tool_calls(
+                        None,
+                        serde_json::json!([{
+                            "id": "call_0",
+                            "type": "function",
+                            "function": {"name": "say", "arguments": "{}"},
+                        }]),
+                    ),
+                    HistoryEntry::tool_result("call_0", "say", ""),
+                ],
+            )
+            .await;
 
         let body = body_for(&node, "s1", "next");
         let messages = body["messages"].as_array().unwrap();
@@ -717,16 +839,32 @@ mod tests {
     fn test_provider_anthropic_uses_anthropic_default_base_url() {
         let mut cfg = OpenAIChatConfig::default();
         cfg.provider = crate::llm::ProviderKind::Anthropic;
-        let node = OpenAIChatNode::with_config(cfg);
+        let node = OpenAIChatNode::with_config(cfg).unwrap();
         assert_eq!(node.resolve_base_url(), "https://api.anthropic.com/v1");
     }
 
+    #[test]
+    fn test_provider_gemini_defaults() {
+        let mut cfg = OpenAIChatConfig::default();
+        cfg.provider = crate::llm::ProviderKind::Gemini;
+        let node = OpenAIChatNode::with_config(cfg).unwrap();
+        assert_eq!(
+            node.resolve_base_url(),
+            "https://generativelanguage.googleapis.com/v1beta"
+        );
+        assert_eq!(node.resolve_model(), "gemini-2.0-flash");
+
+        let cfg: OpenAIChatConfig =
+            serde_json::from_value(serde_json::json!({"provider": "gemini"})).unwrap();
+        assert_eq!(cfg.provider, crate::llm::ProviderKind::Gemini);
+    }
+
     #[test]
     fn test_provider_explicit_base_url_overrides_default() {
         let mut cfg = OpenAIChatConfig::default();
         cfg.provider = crate::llm::ProviderKind::Anthropic;
         cfg.base_url = Some("http://localhost:9999/v1".into());
-        let node = OpenAIChatNode::with_config(cfg);
+        let node = OpenAIChatNode::with_config(cfg).unwrap();
         assert_eq!(node.resolve_base_url(), "http://localhost:9999/v1");
     }
 
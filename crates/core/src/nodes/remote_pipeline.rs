@@ -36,6 +36,7 @@
 //! ```
 
 use crate::manifest::Manifest;
+use crate::transport::client::{ClientStreamSession, Endpoint, EndpointPool, PipelineClient};
 use crate::{Error, Result};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
@@ -144,6 +145,13 @@ pub struct RemotePipelineConfig {
     #[serde(default = "default_timeout")]
     pub timeout_ms: u64,
 
+    /// How long a streamed input waits for further outputs, in milliseconds
+    ///
+    /// Outputs the remote pipeline produces later are delivered with the
+    /// next input. Default: 50ms
+    #[serde(default = "default_stream_idle")]
+    pub stream_idle_ms: u64,
+
     /// Retry configuration
     #[serde(skip_serializing_if = "Option::is_none")]
     pub retry: Option<RetryConfig>,
@@ -175,6 +183,10 @@ fn default_timeout() -> u64 {
     30000
 }
 
+fn default_stream_idle() -> u64 {
+    50
+}
+
 /// Retry configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RetryConfig {
@@ -491,7 +503,8 @@ pub async fn load_manifest_from_source(source: &ManifestSource) -> Result<Manife
 
 /// Substitute environment variables in auth tokens
 ///
-/// Supports syntax: "${VAR_NAME}" or "$VAR_NAME"
+/// Supports the manifest syntax "${VAR_NAME}" / "${VAR_NAME:-default}"
+/// (see [`crate::manifest::template`]) as well as bare "$VAR_NAME".
 ///
 /// # Example
 ///
@@ -508,20 +521,11 @@ pub fn substitute_env_vars(value: &str) -> Result<String> {
         return Ok(value.to_string());
     }
 
-    let mut result = value.to_string();
-
-    // Handle ${VAR} syntax
-    let re = regex::Regex::new(r"\$\{([A-Z_][A-Z0-9_]*)\}").unwrap();
-    for cap in re.captures_iter(value) {
-        let var_name = &cap[1];
-        let var_value = std::env::var(var_name).map_err(|_| {
-            Error::ConfigError(format!(
-                "Environment variable '{}' not found (referenced in auth_token)",
-                var_name
-            ))
-        })?;
-        result = result.replace(&format!("${{{}}}", var_name), &var_value);
-    }
+    // Handle ${VAR} and ${VAR:-default} syntax
+    let mut result = crate::manifest::template::substitute_env(value).map_err(|e| match e {
+        Error::Manifest(msg) => Error::ConfigError(format!("{} (referenced in auth_token)", msg)),
+        e => e,
+    })?;
 
     // Handle $VAR syntax (without braces)
     let re = regex::Regex::new(r"\$([A-Z_][A-Z0-9_]*)").unwrap();
@@ -545,7 +549,7 @@ pub fn substitute_env_vars(value: &str) -> Result<String> {
 /// Validate manifest compatibility
 ///
 /// Checks:
-/// - Version is supported ("v1")
+/// - Version is supported ("v1" or "v2")
 /// - Node types are reasonable (not empty, valid identifiers)
 /// - No obvious circular references in manifest itself
 ///
@@ -553,10 +557,11 @@ pub fn substitute_env_vars(value: &str) -> Result<String> {
 /// when traversing node connections.
 pub fn validate_manifest(manifest: &Manifest) -> Result<()> {
     // Check version
-    if manifest.version != "v1" {
+    if !crate::manifest::SUPPORTED_VERSIONS.contains(&manifest.version.as_str()) {
         return Err(Error::InvalidManifest(format!(
-            "Unsupported manifest version: '{}' (expected 'v1')",
-            manifest.version
+            "Unsupported manifest version: '{}' (expected one of {:?})",
+            manifest.version,
+            crate::manifest::SUPPORTED_VERSIONS
         )));
     }
 
@@ -650,6 +655,78 @@ pub struct RemotePipelineNode {
 
     /// Resolved manifest to execute remotely
     pub manifest: Option<Manifest>,
+
+    /// Endpoints to spread calls over, each behind its own circuit breaker
+    #[cfg_attr(not(feature = "grpc-client"), allow(dead_code))]
+    pool: EndpointPool,
+
+    /// Transport clients by endpoint URL, created on first use
+    #[cfg_attr(not(feature = "grpc-client"), allow(dead_code))]
+    clients: tokio::sync::Mutex<HashMap<String, Arc<dyn PipelineClient>>>,
+
+    /// Streaming session with the remote pipeline, opened on first input
+    #[cfg_attr(not(feature = "grpc-client"), allow(dead_code))]
+    stream: tokio::sync::Mutex<Option<RemoteStream>>,
+}
+
+/// An open streaming session and the endpoint serving it
+#[cfg_attr(not(feature = "grpc-client"), allow(dead_code))]
+struct RemoteStream {
+    endpoint: Arc<Endpoint>,
+    session: Box<dyn ClientStreamSession>,
+}
+
+#[cfg_attr(not(feature = "grpc-client"), allow(dead_code))]
+impl RemoteStream {
+    /// Send `input` and deliver outputs to `callback`
+    ///
+    /// With `idle`, outputs are delivered until none arrives for that long,
+    /// or until `timeout` has passed since the send; without it, nothing is
+    /// received. An error from `callback` is left in `stopped` and ends the
+    /// exchange without failing the session.
+    async fn exchange<F>(
+        &mut self,
+        input: crate::transport::TransportData,
+        idle: Option<Duration>,
+        timeout: Duration,
+        callback: &mut F,
+        stopped: &mut Option<Error>,
+    ) -> Result<usize>
+    where
+        F: FnMut(crate::data::RuntimeData) -> Result<()> + Send,
+    {
+        let deadline = tokio::time::Instant::now() + timeout;
+        tokio::time::timeout_at(deadline, self.session.send(input))
+            .await
+            .unwrap_or_else(|_elapsed| {
+                Err(Error::RemoteTimeout {
+                    timeout_ms: timeout.as_millis() as u64,
+                    context: format!("Send timed out on {}", self.endpoint.url),
+                })
+            })?;
+
+        let Some(idle) = idle else {
+            return Ok(0);
+        };
+        let mut count = 0;
+        while tokio::time::Instant::now() < deadline {
+            let output = match tokio::time::timeout(idle, self.session.receive()).await {
+                Err(_elapsed) => break,
+                Ok(received) => received?.ok_or_else(|| {
+                    Error::RemoteExecutionFailed(format!(
+                        "{}: stream closed by the server",
+                        self.endpoint.url
+                    ))
+                })?,
+            };
+            if let Err(e) = callback(output.data) {
+                *stopped = Some(e);
+                break;
+            }
+            count += 1;
+        }
+        Ok(count)
+    }
 }
 
 impl RemotePipelineNode {
@@ -705,10 +782,23 @@ impl RemotePipelineNode {
             config.auth_token = Some(substitute_env_vars(token)?);
         }
 
+        let urls = match &config.endpoints {
+            Some(endpoints) if !endpoints.is_empty() => endpoints.clone(),
+            _ => vec![config.endpoint.clone().unwrap_or_default()],
+        };
+        let pool = EndpointPool::new(
+            urls,
+            config.load_balance_strategy.unwrap_or_default(),
+            config.circuit_breaker.clone().unwrap_or_default(),
+        );
+
         Ok(Self {
             node_id,
             config,
             manifest: None,
+            pool,
+            clients: tokio::sync::Mutex::new(HashMap::new()),
+            stream: tokio::sync::Mutex::new(None),
         })
     }
 
@@ -745,12 +835,17 @@ impl RemotePipelineNode {
         Ok(())
     }
 
-    /// Get or create the transport client using the plugin registry
+    /// Get or create the transport client for `endpoint` using the plugin registry
     #[cfg(feature = "grpc-client")]
-    async fn get_client(&self) -> crate::Result<Box<dyn crate::transport::client::PipelineClient>> {
+    async fn get_client(&self, endpoint: &str) -> crate::Result<Arc<dyn PipelineClient>> {
         use crate::transport::plugin_registry::global_registry;
         use crate::transport::ClientConfig;
 
+        let mut clients = self.clients.lock().await;
+        if let Some(client) = clients.get(endpoint) {
+            return Ok(client.clone());
+        }
+
         // Look up transport plugin by name
         let transport_name = &self.config.transport;
         let plugin = global_registry().get(transport_name).ok_or_else(|| {
@@ -768,7 +863,7 @@ impl RemotePipelineNode {
         let mut params = serde_json::Map::new();
         params.insert(
             "endpoint".to_string(),
-            serde_json::Value::String(self.get_primary_endpoint()),
+            serde_json::Value::String(endpoint.to_string()),
         );
 
         if let Some(ref token) = self.config.auth_token {
@@ -791,22 +886,29 @@ impl RemotePipelineNode {
             plugin.validate_config(extra)?;
         }
 
-        // Create client via plugin
-        plugin.create_client(&client_config).await
+        // Create client via plugin; it keeps its connection for later calls
+        let client: Arc<dyn PipelineClient> = plugin.create_client(&client_config).await?.into();
+        clients.insert(endpoint.to_string(), client.clone());
+        Ok(client)
     }
 
     /// Execute with retry logic
-    #[cfg_attr(not(feature = "grpc-client"), allow(unused_variables))]
+    ///
+    /// Each attempt goes to the endpoint the pool selects. A failed attempt
+    /// counts against that endpoint's circuit breaker, so once it opens,
+    /// later attempts go to the remaining endpoints.
+    #[cfg(feature = "grpc-client")]
     async fn execute_with_retry(
         &self,
-        manifest: std::sync::Arc<Manifest>,
+        manifest: Arc<Manifest>,
         input: crate::transport::TransportData,
     ) -> crate::Result<crate::transport::TransportData> {
         let retry_config = self.config.retry.as_ref();
         let max_retries = retry_config.map(|r| r.max_retries).unwrap_or(0);
         let backoff_ms = retry_config.map(|r| r.backoff_ms).unwrap_or(1000);
+        let timeout_ms = self.config.timeout_ms;
 
-        let last_error = None;
+        let mut last_error = None;
         let mut ctx =
             ExecutionContext::new(self.get_primary_endpoint(), self.config.auth_token.clone());
 
@@ -825,9 +927,11 @@ impl RemotePipelineNode {
                 tokio::time::sleep(std::time::Duration::from_millis(delay)).await;
             }
 
-            // Get client
-            #[cfg(feature = "grpc-client")]
-            let client = match self.get_client().await {
+            // Fails fast once every endpoint's circuit is open
+            let endpoint = self.pool.select().await?;
+            ctx.selected_endpoint = endpoint.url.clone();
+
+            let client = match self.get_client(&endpoint.url).await {
                 Ok(c) => c,
                 Err(e) => {
                     last_error = Some(e);
@@ -835,73 +939,260 @@ impl RemotePipelineNode {
                 }
             };
 
-            #[cfg(not(feature = "grpc-client"))]
-            {
-                return Err(crate::Error::ConfigError(
-                    "gRPC client not enabled - compile with 'grpc-client' feature".into(),
-                ));
+            // Execute with timeout under the endpoint's circuit breaker
+            endpoint.acquire().await;
+            let result = endpoint
+                .circuit_breaker
+                .execute(|| async {
+                    tokio::time::timeout(
+                        std::time::Duration::from_millis(timeout_ms),
+                        client.execute_unary(manifest.clone(), input.clone()),
+                    )
+                    .await
+                    .unwrap_or_else(|_elapsed| {
+                        Err(crate::Error::RemoteTimeout {
+                            timeout_ms,
+                            context: format!(
+                                "Node '{}' timed out after {}ms on {}",
+                                self.node_id, timeout_ms, endpoint.url
+                            ),
+                        })
+                    })
+                })
+                .await;
+            endpoint.release().await;
+
+            match result {
+                Ok(output) => {
+                    *endpoint.last_success.write().await = Some(Instant::now());
+                    ctx.update_elapsed();
+                    tracing::info!(
+                        "RemotePipelineNode '{}': Success on {} after {} attempts, {}ms elapsed",
+                        self.node_id,
+                        ctx.selected_endpoint,
+                        ctx.attempt_count,
+                        ctx.elapsed_ms
+                    );
+                    return Ok(output);
+                }
+                Err(e) => {
+                    self.pool.record_failure(&endpoint).await;
+                    tracing::warn!(
+                        "RemotePipelineNode '{}': Attempt {} on {} failed: {}",
+                        self.node_id,
+                        attempt + 1,
+                        endpoint.url,
+                        e
+                    );
+                    last_error = Some(e);
+                }
             }
+        }
 
-            #[cfg(feature = "grpc-client")]
-            {
-                // Execute with timeout
-                let timeout_ms = self.config.timeout_ms;
-                let timeout_duration = std::time::Duration::from_millis(timeout_ms);
+        // All retries exhausted
+        ctx.update_elapsed();
+        Err(last_error.unwrap_or_else(|| {
+            crate::Error::RemoteExecutionFailed(format!(
+                "Node '{}' failed after {} attempts",
+                self.node_id, ctx.attempt_count
+            ))
+        }))
+    }
 
-                let result = tokio::time::timeout(
-                    timeout_duration,
-                    client.execute_unary(manifest.clone(), input.clone()),
-                )
-                .await;
+    /// Execute with retry logic (unavailable without a transport client)
+    #[cfg(not(feature = "grpc-client"))]
+    async fn execute_with_retry(
+        &self,
+        _manifest: Arc<Manifest>,
+        _input: crate::transport::TransportData,
+    ) -> crate::Result<crate::transport::TransportData> {
+        Err(crate::Error::ConfigError(
+            "gRPC client not enabled - compile with 'grpc-client' feature".into(),
+        ))
+    }
 
-                match result {
-                    Ok(Ok(output)) => {
-                        ctx.update_elapsed();
-                        tracing::info!(
-                            "RemotePipelineNode '{}': Success after {} attempts, {}ms elapsed",
-                            self.node_id,
-                            ctx.attempt_count,
-                            ctx.elapsed_ms
-                        );
-                        return Ok(output);
-                    }
-                    Ok(Err(e)) => {
+    /// Send `input` over the node's streaming session, delivering outputs
+    /// to `callback` unless `idle` is `None`
+    ///
+    /// The session is opened on first use, on the endpoint the pool
+    /// selects, and kept for later inputs so the remote nodes keep their
+    /// state. An input that produces no output is not an error. A failed
+    /// exchange counts against the endpoint's circuit breaker; the session
+    /// is dropped and the input retried on a new one.
+    #[cfg(feature = "grpc-client")]
+    async fn stream_with_retry<F>(
+        &self,
+        manifest: &Manifest,
+        input: crate::transport::TransportData,
+        idle: Option<Duration>,
+        mut callback: F,
+    ) -> crate::Result<usize>
+    where
+        F: FnMut(crate::data::RuntimeData) -> crate::Result<()> + Send,
+    {
+        let retry_config = self.config.retry.as_ref();
+        let max_retries = retry_config.map(|r| r.max_retries).unwrap_or(0);
+        let backoff_ms = retry_config.map(|r| r.backoff_ms).unwrap_or(1000);
+        let timeout = Duration::from_millis(self.config.timeout_ms);
+
+        // Inputs go through the session one at a time, in order
+        let mut stream = self.stream.lock().await;
+        let mut last_error = None;
+
+        for attempt in 0..=max_retries {
+            if attempt > 0 {
+                let delay = backoff_ms * 2u64.pow(attempt - 1);
+                tracing::debug!(
+                    "RemotePipelineNode '{}': Retry attempt {} after {}ms",
+                    self.node_id,
+                    attempt,
+                    delay
+                );
+                tokio::time::sleep(Duration::from_millis(delay)).await;
+            }
+
+            if stream.is_none() {
+                // Fails fast once every endpoint's circuit is open
+                let endpoint = self.pool.select().await?;
+                match self.open_stream(endpoint, manifest).await {
+                    Ok(opened) => *stream = Some(opened),
+                    Err(e) => {
                         last_error = Some(e);
-                        tracing::warn!(
-                            "RemotePipelineNode '{}': Attempt {} failed: {}",
-                            self.node_id,
-                            attempt + 1,
-                            last_error.as_ref().unwrap()
-                        );
+                        continue;
                     }
-                    Err(_elapsed) => {
-                        last_error = Some(crate::Error::RemoteTimeout {
-                            timeout_ms,
-                            context: format!(
-                                "Node '{}' timed out after {}ms",
-                                self.node_id, timeout_ms
-                            ),
-                        });
-                        tracing::warn!(
-                            "RemotePipelineNode '{}': Attempt {} timed out after {}ms",
-                            self.node_id,
-                            attempt + 1,
-                            timeout_ms
-                        );
+                }
+            }
+            let Some(open) = stream.as_mut() else {
+                continue;
+            };
+
+            let endpoint = open.endpoint.clone();
+            let mut stopped = None;
+            let result = endpoint
+                .circuit_breaker
+                .execute(|| {
+                    open.exchange(input.clone(), idle, timeout, &mut callback, &mut stopped)
+                })
+                .await;
+
+            match result {
+                Ok(count) => {
+                    *endpoint.last_success.write().await = Some(Instant::now());
+                    return match stopped {
+                        Some(e) => Err(e),
+                        None => Ok(count),
+                    };
+                }
+                Err(e) => {
+                    self.pool.record_failure(&endpoint).await;
+                    tracing::warn!(
+                        "RemotePipelineNode '{}': Attempt {} on {} failed: {}",
+                        self.node_id,
+                        attempt + 1,
+                        endpoint.url,
+                        e
+                    );
+                    if let Some(mut failed) = stream.take() {
+                        failed.session.close().await.ok();
+                        failed.endpoint.release().await;
                     }
+                    last_error = Some(e);
                 }
             }
         }
 
-        // All retries exhausted
-        ctx.update_elapsed();
         Err(last_error.unwrap_or_else(|| {
             crate::Error::RemoteExecutionFailed(format!(
                 "Node '{}' failed after {} attempts",
-                self.node_id, ctx.attempt_count
+                self.node_id,
+                max_retries + 1
             ))
         }))
     }
+
+    /// Open a streaming session on `endpoint` under its circuit breaker
+    #[cfg(feature = "grpc-client")]
+    async fn open_stream(
+        &self,
+        endpoint: Arc<Endpoint>,
+        manifest: &Manifest,
+    ) -> crate::Result<RemoteStream> {
+        let timeout_ms = self.config.timeout_ms;
+        let manifest = Arc::new(manifest.clone());
+        let result = async {
+            let client = self.get_client(&endpoint.url).await?;
+            endpoint
+                .circuit_breaker
+                .execute(|| async {
+                    tokio::time::timeout(
+                        Duration::from_millis(timeout_ms),
+                        client.create_stream_session(manifest),
+                    )
+                    .await
+                    .unwrap_or_else(|_elapsed| {
+                        Err(crate::Error::RemoteTimeout {
+                            timeout_ms,
+                            context: format!(
+                                "Node '{}' timed out opening a stream on {}",
+                                self.node_id, endpoint.url
+                            ),
+                        })
+                    })
+                })
+                .await
+        }
+        .await;
+
+        match result {
+            Ok(session) => {
+                endpoint.acquire().await;
+                tracing::info!(
+                    "RemotePipelineNode '{}': Streaming session {} open on {}",
+                    self.node_id,
+                    session.session_id(),
+                    endpoint.url
+                );
+                Ok(RemoteStream { endpoint, session })
+            }
+            Err(e) => {
+                self.pool.record_failure(&endpoint).await;
+                tracing::warn!(
+                    "RemotePipelineNode '{}': Cannot open a stream on {}: {}",
+                    self.node_id,
+                    endpoint.url,
+                    e
+                );
+                Err(e)
+            }
+        }
+    }
+
+    /// Stream with retry logic (unavailable without a transport client)
+    #[cfg(not(feature = "grpc-client"))]
+    async fn stream_with_retry<F>(
+        &self,
+        _manifest: &Manifest,
+        _input: crate::transport::TransportData,
+        _idle: Option<Duration>,
+        _callback: F,
+    ) -> crate::Result<usize>
+    where
+        F: FnMut(crate::data::RuntimeData) -> crate::Result<()> + Send,
+    {
+        Err(crate::Error::ConfigError(
+            "gRPC client not enabled - compile with 'grpc-client' feature".into(),
+        ))
+    }
+
+    /// The manifest to run remotely
+    fn remote_manifest(&self) -> crate::Result<&Manifest> {
+        self.manifest.as_ref().ok_or_else(|| {
+            crate::Error::Execution(format!(
+                "RemotePipelineNode '{}' not initialized - manifest is None",
+                self.node_id
+            ))
+        })
+    }
 }
 
 /// Factory for creating RemotePipelineNode instances
@@ -945,7 +1236,8 @@ impl crate::nodes::StreamingNodeFactory for RemotePipelineNodeFactory {
     }
 
     fn is_multi_output_streaming(&self) -> bool {
-        false // Remote nodes use unary execution for now
+        // A remote pipeline may answer an input with any number of outputs
+        true
     }
 }
 
@@ -966,17 +1258,13 @@ impl crate::nodes::AsyncStreamingNode for RemotePipelineNode {
         Ok(())
     }
 
+    /// One-off execution with a unary call; streams go through
+    /// `process_streaming`, which keeps one remote session
     async fn process(
         &self,
         data: crate::data::RuntimeData,
     ) -> crate::Result<crate::data::RuntimeData> {
-        // Get the manifest
-        let manifest = self.manifest.as_ref().ok_or_else(|| {
-            crate::Error::Execution(format!(
-                "RemotePipelineNode '{}' not initialized - manifest is None",
-                self.node_id
-            ))
-        })?;
+        let manifest = self.remote_manifest()?;
 
         // Convert RuntimeData to TransportData
         let input = crate::transport::TransportData::new(data);
@@ -994,15 +1282,20 @@ impl crate::nodes::AsyncStreamingNode for RemotePipelineNode {
         &self,
         data: crate::data::RuntimeData,
         _session_id: Option<String>,
-        mut callback: F,
+        callback: F,
     ) -> crate::Result<usize>
     where
         F: FnMut(crate::data::RuntimeData) -> crate::Result<()> + Send,
     {
-        // For unary execution, just call process and invoke callback once
-        let output = self.process(data).await?;
-        callback(output)?;
-        Ok(1)
+        let manifest = self.remote_manifest()?;
+        let idle = Duration::from_millis(self.config.stream_idle_ms);
+        self.stream_with_retry(
+            manifest,
+            crate::transport::TransportData::new(data),
+            Some(idle),
+            callback,
+        )
+        .await
     }
 
     async fn process_control_message(
@@ -1010,9 +1303,6 @@ impl crate::nodes::AsyncStreamingNode for RemotePipelineNode {
         message: crate::data::RuntimeData,
         _session_id: Option<String>,
     ) -> crate::Result<bool> {
-        // Forward control messages to remote transport
-        // The transport layer will handle serialization (JSON for gRPC/WebRTC/HTTP)
-
         match &message {
             crate::data::RuntimeData::ControlMessage { .. } => {
                 tracing::debug!(
@@ -1020,23 +1310,16 @@ impl crate::nodes::AsyncStreamingNode for RemotePipelineNode {
                     self.node_id
                 );
 
-                // Convert to TransportData and send via remote transport
-                // Note: For now, control messages are sent as data (will be handled by transport)
-                // TODO: Once T042-T045 are complete, this will use proper transport control message APIs
-                let transport_data = crate::transport::TransportData::new(message);
-
-                // Get manifest
-                let manifest = self.manifest.as_ref().ok_or_else(|| {
-                    crate::Error::Execution(format!(
-                        "RemotePipelineNode '{}' not initialized - manifest is None",
-                        self.node_id
-                    ))
-                })?;
-
-                // Execute via transport (control messages flow through the same pipeline)
-                let _result = self
-                    .execute_with_retry(std::sync::Arc::new(manifest.clone()), transport_data)
-                    .await?;
+                // Control messages share the data stream; their outputs, if
+                // any, arrive with the next input
+                let manifest = self.remote_manifest()?;
+                self.stream_with_retry(
+                    manifest,
+                    crate::transport::TransportData::new(message),
+                    None,
+                    |_| Ok(()),
+                )
+                .await?;
 
                 Ok(true) // Message was handled (forwarded)
             }
@@ -67,6 +67,61 @@ impl RuntimeDataType {
             RuntimeDataType::ControlMessage => "'control'",
         }
     }
+
+    /// Classify a `RuntimeData` value (`None` for variants without a schema type)
+    pub fn of(data: &crate::data::RuntimeData) -> Option<RuntimeDataType> {
+        use crate::data::RuntimeData;
+        match data {
+            RuntimeData::Audio { .. } => Some(RuntimeDataType::Audio),
+            RuntimeData::Video { .. } => Some(RuntimeDataType::Video),
+            RuntimeData::Image { .. } => Some(RuntimeDataType::Image),
+            RuntimeData::Json(_) => Some(RuntimeDataType::Json),
+            RuntimeData::Text(_) => Some(RuntimeDataType::Text),
+            RuntimeData::Binary(_) => Some(RuntimeDataType::Binary),
+            RuntimeData::Tensor { .. } => Some(RuntimeDataType::Tensor),
+            RuntimeData::Numpy { .. } => Some(RuntimeDataType::Numpy),
+            RuntimeData::ControlMessage { .. } => Some(RuntimeDataType::ControlMessage),
+            RuntimeData::File { .. } => None,
+        }
+    }
+}
+
+/// Name of the implicit port every node has on both sides
+pub const MAIN_PORT: &str = "main";
+
+/// A named input or output port on a node
+///
+/// Every node implicitly has a `main` port in each direction; schemas only
+/// declare the additional ones (e.g. a VAD's `events` output next to its
+/// pass-through audio).
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct PortSchema {
+    /// Port name as referenced by `from_port` / `to_port` in a manifest
+    pub name: String,
+
+    /// RuntimeData variants carried on this port (empty = any)
+    #[serde(default)]
+    pub types: Vec<RuntimeDataType>,
+
+    /// Human-readable description
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+}
+
+impl PortSchema {
+    /// Create a port carrying the given types
+    pub fn new(name: impl Into<String>, types: impl IntoIterator<Item = RuntimeDataType>) -> Self {
+        Self {
+            name: name.into(),
+            types: types.into_iter().collect(),
+            description: None,
+        }
+    }
+
+    /// Whether data of type `ty` may travel on this port
+    pub fn carries(&self, ty: RuntimeDataType) -> bool {
+        self.types.is_empty() || self.types.contains(&ty)
+    }
 }
 
 /// Complete schema for a pipeline node
@@ -114,6 +169,14 @@ pub struct NodeSchema {
     /// Execution characteristics
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub capabilities: Option<NodeCapabilitiesSchema>,
+
+    /// Named input ports besides the implicit `main`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub input_ports: Vec<PortSchema>,
+
+    /// Named output ports besides the implicit `main`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub output_ports: Vec<PortSchema>,
 }
 
 fn default_true() -> bool {
@@ -211,6 +274,8 @@ impl NodeSchema {
             streaming: true,
             multi_output: false,
             capabilities: None,
+            input_ports: Vec::new(),
+            output_ports: Vec::new(),
         }
     }
 
@@ -293,6 +358,51 @@ impl NodeSchema {
         self
     }
 
+    /// Declare a named input port
+    pub fn input_port(
+        mut self,
+        name: impl Into<String>,
+        types: impl IntoIterator<Item = RuntimeDataType>,
+    ) -> Self {
+        self.input_ports.push(PortSchema::new(name, types));
+        self
+    }
+
+    /// Declare a named output port
+    pub fn output_port(
+        mut self,
+        name: impl Into<String>,
+        types: impl IntoIterator<Item = RuntimeDataType>,
+    ) -> Self {
+        self.output_ports.push(PortSchema::new(name, types));
+        self
+    }
+
+    /// Whether `port` names an input of this node (`main` always does)
+    pub fn has_input_port(&self, port: &str) -> bool {
+        port == MAIN_PORT || self.input_ports.iter().any(|p| p.name == port)
+    }
+
+    /// Whether `port` names an output of this node (`main` always does)
+    pub fn has_output_port(&self, port: &str) -> bool {
+        port == MAIN_PORT || self.output_ports.iter().any(|p| p.name == port)
+    }
+
+    /// Output port an emitted value belongs to
+    ///
+    /// The first declared output port whose types include the value's type
+    /// wins; anything unmatched goes out on `main`.
+    pub fn output_port_for(&self, data: &crate::data::RuntimeData) -> &str {
+        RuntimeDataType::of(data)
+            .and_then(|ty| {
+                self.output_ports
+                    .iter()
+                    .find(|p| !p.types.is_empty() && p.types.contains(&ty))
+            })
+            .map(|p| p.name.as_str())
+            .unwrap_or(MAIN_PORT)
+    }
+
     /// Extract parameters from config_schema as a structured list
     pub fn get_parameters(&self) -> Vec<NodeParameter> {
         let Some(schema) = &self.config_schema else {
@@ -959,6 +1069,35 @@ mod tests {
         assert!(schema.is_python);
     }
 
+    #[test]
+    fn test_schema_ports() {
+        use crate::data::RuntimeData;
+
+        let schema = NodeSchema::new("VadNode")
+            .produces([RuntimeDataType::Audio, RuntimeDataType::Json])
+            .output_port("events", [RuntimeDataType::Json])
+            .input_port("control", [RuntimeDataType::Json]);
+
+        assert!(schema.has_output_port("main"));
+        assert!(schema.has_output_port("events"));
+        assert!(!schema.has_output_port("control"));
+        assert!(schema.has_input_port("control"));
+        assert!(!schema.has_input_port("events"));
+
+        let event = RuntimeData::Json(serde_json::json!({"is_speech": true}));
+        assert_eq!(schema.output_port_for(&event), "events");
+        let text = RuntimeData::Text("hi".into());
+        assert_eq!(schema.output_port_for(&text), "main");
+
+        // Ports round-trip through JSON; undeclared ports are omitted
+        let json = serde_json::to_value(&schema).unwrap();
+        assert_eq!(json["output_ports"][0]["name"], "events");
+        assert!(serde_json::to_value(NodeSchema::new("Plain"))
+            .unwrap()
+            .get("output_ports")
+            .is_none());
+    }
+
     #[test]
     fn test_registry() {
         let registry = create_builtin_schema_registry();
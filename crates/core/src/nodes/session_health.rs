@@ -44,6 +44,10 @@ pub enum HealthContributor {
     Drift,
     Freeze,
     CadenceUnstable,
+    LoudnessOffTarget,
+    TruePeak,
+    FrozenPicture,
+    BlackFrame,
 }
 
 impl std::fmt::Display for HealthContributor {
@@ -58,6 +62,10 @@ impl std::fmt::Display for HealthContributor {
             HealthContributor::Drift => write!(f, "drift"),
             HealthContributor::Freeze => write!(f, "freeze"),
             HealthContributor::CadenceUnstable => write!(f, "cadence_unstable"),
+            HealthContributor::LoudnessOffTarget => write!(f, "loudness_off_target"),
+            HealthContributor::TruePeak => write!(f, "true_peak"),
+            HealthContributor::FrozenPicture => write!(f, "frozen_picture"),
+            HealthContributor::BlackFrame => write!(f, "black_frame"),
         }
     }
 }
@@ -78,16 +86,20 @@ impl HealthContributor {
             HealthContributor::Clipping => IssueSeverity::Severe,
             HealthContributor::Freeze => IssueSeverity::Severe,
             HealthContributor::DeadChannel => IssueSeverity::Severe,
+            HealthContributor::FrozenPicture => IssueSeverity::Severe,
+            HealthContributor::BlackFrame => IssueSeverity::Severe,
 
             // Moderate: Noticeable quality degradation
             HealthContributor::DeadAir => IssueSeverity::Moderate,
             HealthContributor::Drift => IssueSeverity::Moderate,
             HealthContributor::Imbalance => IssueSeverity::Moderate,
+            HealthContributor::TruePeak => IssueSeverity::Moderate,
 
             // Minor: May not be immediately noticeable
             HealthContributor::Silence => IssueSeverity::Minor,
             HealthContributor::LowVolume => IssueSeverity::Minor,
             HealthContributor::CadenceUnstable => IssueSeverity::Minor,
+            HealthContributor::LoudnessOffTarget => IssueSeverity::Minor,
         }
     }
 }
@@ -253,6 +265,39 @@ impl SessionHealthNode {
                 }
             }
 
+            // LoudnessNode events
+            (_, Some("loudness_event")) => {
+                if json.get("is_too_loud").and_then(|v| v.as_bool()).unwrap_or(false)
+                    || json.get("is_too_quiet").and_then(|v| v.as_bool()).unwrap_or(false)
+                {
+                    self.add_issue(HealthContributor::LoudnessOffTarget, current_us, state);
+                }
+                if json.get("is_true_peak_exceeded").and_then(|v| v.as_bool()).unwrap_or(false) {
+                    self.add_issue(HealthContributor::TruePeak, current_us, state);
+                }
+            }
+            (Some("loudness_violation"), _) => {
+                self.add_issue(HealthContributor::LoudnessOffTarget, current_us, state);
+            }
+            (Some("true_peak_violation"), _) => {
+                self.add_issue(HealthContributor::TruePeak, current_us, state);
+            }
+
+            // VideoQualityNode events
+            (_, Some("video_quality_event")) => {
+                if json.get("is_black").and_then(|v| v.as_bool()).unwrap_or(false) {
+                    self.add_issue(HealthContributor::BlackFrame, current_us, state);
+                } else if json.get("is_frozen").and_then(|v| v.as_bool()).unwrap_or(false) {
+                    self.add_issue(HealthContributor::FrozenPicture, current_us, state);
+                }
+            }
+            (Some("black_frame"), _) => {
+                self.add_issue(HealthContributor::BlackFrame, current_us, state);
+            }
+            (Some("frozen_picture"), _) => {
+                self.add_issue(HealthContributor::FrozenPicture, current_us, state);
+            }
+
             // SpeechPresenceNode events
             (Some("speech.presence"), _) => {
                 let speech_state = json.get("state").and_then(|v| v.as_str());
@@ -546,6 +591,10 @@ mod tests {
         assert_eq!(HealthContributor::Clipping.severity(), IssueSeverity::Severe);
         assert_eq!(HealthContributor::DeadAir.severity(), IssueSeverity::Moderate);
         assert_eq!(HealthContributor::LowVolume.severity(), IssueSeverity::Minor);
+        assert_eq!(HealthContributor::TruePeak.severity(), IssueSeverity::Moderate);
+        assert_eq!(HealthContributor::LoudnessOffTarget.to_string(), "loudness_off_target");
+        assert_eq!(HealthContributor::FrozenPicture.severity(), IssueSeverity::Severe);
+        assert_eq!(HealthContributor::BlackFrame.to_string(), "black_frame");
     }
 
     #[test]
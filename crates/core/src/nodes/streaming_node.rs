@@ -773,6 +773,11 @@ impl StreamingNodeRegistry {
             .collect()
     }
 
+    /// Get the schema declared by a node type's factory, if any.
+    pub fn get_schema(&self, node_type: &str) -> Option<crate::nodes::schema::NodeSchema> {
+        self.factories.get(node_type).and_then(|f| f.schema())
+    }
+
     // =========================================================================
     // Capability Resolution Methods (spec 023)
     // =========================================================================
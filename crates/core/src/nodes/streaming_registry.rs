@@ -237,6 +237,47 @@ impl StreamingNodeFactory for VideoFormatConverterNodeFactory {
     }
 }
 
+#[cfg(feature = "video")]
+pub(crate) struct VideoQualityNodeFactory;
+
+#[cfg(feature = "video")]
+impl StreamingNodeFactory for VideoQualityNodeFactory {
+    fn create(
+        &self,
+        node_id: String,
+        params: &Value,
+        _session_id: Option<String>,
+    ) -> Result<Box<dyn StreamingNode>, Error> {
+        use crate::nodes::video::{VideoQualityConfig, VideoQualityNode};
+        let config: VideoQualityConfig = if params.is_null() {
+            VideoQualityConfig::default()
+        } else {
+            serde_json::from_value(params.clone())
+                .map_err(|e| Error::InvalidManifest(format!("VideoQualityNode params: {}", e)))?
+        };
+        let node = VideoQualityNode::new(node_id, config)
+            .map_err(|e| Error::Execution(format!("Failed to create VideoQualityNode: {}", e)))?;
+        Ok(Box::new(AsyncNodeWrapper(Arc::new(node))))
+    }
+
+    fn node_type(&self) -> &str {
+        "VideoQualityNode"
+    }
+
+    fn schema(&self) -> Option<crate::nodes::schema::NodeSchema> {
+        use crate::nodes::schema::{NodeSchema, RuntimeDataType};
+        use crate::nodes::video::VideoQualityConfig;
+        Some(
+            NodeSchema::new("VideoQualityNode")
+                .description("Detects frozen pictures, black frames and scene cuts")
+                .category("video")
+                .accepts([RuntimeDataType::Video])
+                .produces([RuntimeDataType::Json])
+                .config_schema_from::<VideoQualityConfig>(),
+        )
+    }
+}
+
 // Temporarily disabled - SynchronizedAudioVideoNode has incomplete implementation
 /*
 struct SynchronizedAudioVideoNodeFactory;
@@ -651,6 +692,7 @@ impl StreamingNodeFactory for SileroVADNodeFactory {
                 .category("audio")
                 .accepts([RuntimeDataType::Audio])
                 .produces([RuntimeDataType::Json, RuntimeDataType::Audio])
+                .output_port("events", [RuntimeDataType::Json])
                 .capabilities(NodeCapabilitiesSchema {
                     parallelizable: false,
                     batch_aware: false,
@@ -0,0 +1,294 @@
+//! Cue building: grouping timed words into readable subtitles
+//!
+//! Words are packed into a cue until it would exceed `max_lines` lines of
+//! `max_line_length` characters or `max_duration` seconds, the speaker
+//! changes, or a sentence ends on an already full line. Cue timing is
+//! then stretched to the reading speed in [`CueRules::retime`].
+
+/// Layout and timing rules for cues
+#[derive(Debug, Clone)]
+pub struct CueRules {
+    /// Characters per line
+    pub max_line_length: usize,
+    /// Lines per cue
+    pub max_lines: usize,
+    /// Reading speed in characters per second
+    pub max_chars_per_second: f64,
+    /// Shortest time a cue stays on screen, in seconds
+    pub min_duration: f64,
+    /// Longest span of speech a cue may cover, in seconds
+    pub max_duration: f64,
+    /// Gap kept between consecutive cues, in seconds
+    pub min_gap: f64,
+}
+
+/// A word (or a whole untimed segment) with its time span
+#[derive(Debug, Clone, PartialEq)]
+pub struct TimedText {
+    pub start: f64,
+    pub end: f64,
+    pub text: String,
+    pub speaker: Option<String>,
+}
+
+/// One subtitle
+#[derive(Debug, Clone, PartialEq)]
+pub struct Cue {
+    /// Start time in seconds
+    pub start: f64,
+    /// End time in seconds
+    pub end: f64,
+    /// Cue text, without speaker label
+    pub text: String,
+    /// Speaker id, when known
+    pub speaker: Option<String>,
+    /// Label shown before the text, when the speaker is to be labelled
+    pub label: Option<String>,
+}
+
+impl Cue {
+    /// Text as displayed, with the speaker label in front
+    pub fn labelled_text(&self) -> String {
+        match &self.label {
+            Some(label) => format!("{}: {}", label, self.text),
+            None => self.text.clone(),
+        }
+    }
+}
+
+/// Spread a segment's time over its words in proportion to their length
+///
+/// Used when the transcriber gives segment times only.
+pub fn spread_words(start: f64, end: f64, text: &str, speaker: Option<&str>) -> Vec<TimedText> {
+    let words: Vec<&str> = text.split_whitespace().collect();
+    let total: usize = words.iter().map(|w| w.chars().count()).sum();
+    let duration = (end - start).max(0.0);
+    let mut t = start;
+    words
+        .into_iter()
+        .map(|word| {
+            let share = if total == 0 {
+                0.0
+            } else {
+                duration * word.chars().count() as f64 / total as f64
+            };
+            let timed = TimedText {
+                start: t,
+                end: t + share,
+                text: word.to_string(),
+                speaker: speaker.map(str::to_string),
+            };
+            t += share;
+            timed
+        })
+        .collect()
+}
+
+/// Wrap `text` into at most `max_lines` lines of `max_line_length`
+/// characters, or `None` if it doesn't fit
+///
+/// Two-line cues are balanced so the lines are of similar length.
+pub fn wrap(text: &str, max_line_length: usize, max_lines: usize) -> Option<Vec<String>> {
+    let words: Vec<&str> = text.split_whitespace().collect();
+    let width = |words: &[&str]| {
+        words.iter().map(|w| w.chars().count()).sum::<usize>() + words.len().saturating_sub(1)
+    };
+    if words.is_empty() {
+        return Some(Vec::new());
+    }
+    if width(&words) <= max_line_length {
+        return Some(vec![words.join(" ")]);
+    }
+    if max_lines < 2 {
+        return None;
+    }
+
+    if max_lines == 2 {
+        return (1..words.len())
+            .filter(|&i| {
+                width(&words[..i]) <= max_line_length && width(&words[i..]) <= max_line_length
+            })
+            .min_by_key(|&i| width(&words[..i]).abs_diff(width(&words[i..])))
+            .map(|i| vec![words[..i].join(" "), words[i..].join(" ")]);
+    }
+
+    let mut lines: Vec<String> = Vec::new();
+    let mut line = String::new();
+    for word in words {
+        if word.chars().count() > max_line_length {
+            return None;
+        }
+        if line.is_empty() {
+            line.push_str(word);
+        } else if line.chars().count() + 1 + word.chars().count() <= max_line_length {
+            line.push(' ');
+            line.push_str(word);
+        } else {
+            lines.push(std::mem::replace(&mut line, word.to_string()));
+        }
+    }
+    lines.push(line);
+    (lines.len() <= max_lines).then_some(lines)
+}
+
+impl CueRules {
+    /// Group words into cues
+    ///
+    /// `label` gives the label that may be shown for a speaker; it is
+    /// counted against the line length so labelled cues still fit.
+    pub fn build(&self, words: &[TimedText], label: impl Fn(&str) -> Option<String>) -> Vec<Cue> {
+        let mut cues: Vec<Cue> = Vec::new();
+        let mut current: Option<Cue> = None;
+        let fits = |cue: &Cue| {
+            let text = match cue.speaker.as_deref().and_then(&label) {
+                Some(label) => format!("{}: {}", label, cue.text),
+                None => cue.text.clone(),
+            };
+            wrap(&text, self.max_line_length, self.max_lines).is_some()
+        };
+
+        for word in words {
+            let text = word.text.trim();
+            if text.is_empty() {
+                continue;
+            }
+            if let Some(cue) = current.as_mut() {
+                let grown = Cue {
+                    end: word.end.max(cue.end),
+                    text: format!("{} {}", cue.text, text),
+                    ..cue.clone()
+                };
+                let sentence_done = cue.text.ends_with(['.', '?', '!'])
+                    && cue.text.chars().count() >= self.max_line_length;
+                if cue.speaker == word.speaker
+                    && !sentence_done
+                    && grown.end - grown.start <= self.max_duration
+                    && fits(&grown)
+                {
+                    *cue = grown;
+                    continue;
+                }
+                cues.extend(current.take());
+            }
+            current = Some(Cue {
+                start: word.start,
+                end: word.end.max(word.start),
+                text: text.to_string(),
+                speaker: word.speaker.clone(),
+                label: None,
+            });
+        }
+        cues.extend(current);
+        cues
+    }
+
+    /// Stretch cues to the reading speed and minimum duration
+    ///
+    /// A cue never starts before `not_before` (the end of the previous cue
+    /// plus the gap) and is only stretched up to the next cue's start.
+    /// Returns the `not_before` for the cue after the last one.
+    pub fn retime(&self, cues: &mut [Cue], mut not_before: f64) -> f64 {
+        for i in 0..cues.len() {
+            let next_start = cues.get(i + 1).map(|next| next.start - self.min_gap);
+            let cue = &mut cues[i];
+            cue.start = cue.start.max(not_before);
+            let reading = cue.labelled_text().chars().count() as f64 / self.max_chars_per_second;
+            let mut end = cue.end.max(cue.start + reading.max(self.min_duration));
+            if let Some(next_start) = next_start {
+                end = end.min(next_start.max(cue.end));
+            }
+            cue.end = end.max(cue.start);
+            not_before = cue.end + self.min_gap;
+        }
+        not_before
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rules() -> CueRules {
+        CueRules {
+            max_line_length: 20,
+            max_lines: 2,
+            max_chars_per_second: 10.0,
+            min_duration: 1.0,
+            max_duration: 5.0,
+            min_gap: 0.1,
+        }
+    }
+
+    fn word(start: f64, text: &str, speaker: &str) -> TimedText {
+        TimedText {
+            start,
+            end: start + 0.3,
+            text: text.to_string(),
+            speaker: Some(speaker.to_string()),
+        }
+    }
+
+    #[test]
+    fn test_wrap_balances_two_lines() {
+        let lines = wrap("the quick brown fox jumps over", 20, 2).unwrap();
+        assert_eq!(lines, ["the quick brown", "fox jumps over"]);
+        assert!(wrap("the quick brown fox jumps over the lazy dog", 20, 2).is_none());
+        assert_eq!(wrap("a b c d e", 3, 3).unwrap(), ["a b", "c d", "e"]);
+    }
+
+    #[test]
+    fn test_build_splits_on_speaker_and_length() {
+        let words = [
+            word(0.0, "Hello", "0"),
+            word(0.4, "there.", "0"),
+            word(1.0, "Hi,", "1"),
+            word(1.4, "how", "1"),
+            word(1.8, "are", "1"),
+            word(2.2, "you", "1"),
+            word(2.6, "doing", "1"),
+            word(3.0, "today", "1"),
+            word(3.4, "my", "1"),
+            word(3.8, "friend?", "1"),
+        ];
+        let cues = rules().build(&words, |s| Some(format!("S{}", s)));
+        let texts: Vec<_> = cues.iter().map(|c| c.text.as_str()).collect();
+        assert_eq!(
+            texts,
+            ["Hello there.", "Hi, how are you doing today my", "friend?"]
+        );
+        assert_eq!(cues[1].speaker.as_deref(), Some("1"));
+        assert_eq!(cues[1].start, 1.0);
+        assert!((cues[1].end - 3.7).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_retime_extends_to_reading_speed() {
+        let cue = |start: f64, end: f64, text: &str| Cue {
+            start,
+            end,
+            text: text.to_string(),
+            speaker: None,
+            label: None,
+        };
+        let mut cues = vec![cue(0.0, 0.5, "twenty characters!!!"), cue(1.0, 1.2, "hi")];
+        let end = rules().retime(&mut cues, 0.0);
+        // Stretched to 2 s of reading time, but stopped before the next cue
+        assert_eq!(cues[0].end, 0.9);
+        // Minimum duration
+        assert_eq!(cues[1].end, 2.0);
+        assert!((end - 2.1).abs() < 1e-9);
+
+        // A cue that overlaps the previous one is pushed back
+        let mut late = vec![cue(1.5, 3.0, "late")];
+        rules().retime(&mut late, end);
+        assert_eq!(late[0].start, end);
+    }
+
+    #[test]
+    fn test_spread_words() {
+        let words = spread_words(10.0, 12.0, "ab abc", None);
+        assert_eq!(words.len(), 2);
+        assert!((words[0].end - 10.8).abs() < 1e-9);
+        assert!((words[1].end - 12.0).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,597 @@
+//! Subtitle generation
+//!
+//! [`SubtitleSinkNode`] turns timestamped transcripts into SRT and WebVTT
+//! subtitles. It accepts:
+//!
+//! - **Transcripts**: JSON with `segments` (`start`, `end`, `text`, and
+//!   optionally `words` and `speaker`), as produced by `candle-whisper`
+//!   and `RustWhisperNode`. Word timings give the tightest cues; without
+//!   them a segment's time is spread over its words.
+//! - **Diarization**: audio annotated by `SpeakerDiarizationNode`
+//!   (`metadata.diarization`), or JSON carrying a `diarization` object or
+//!   speaker `segments`. Words without a speaker take the speaker turn
+//!   they overlap most.
+//! - **Plain text**: wrapped in a single segment, like `SrtOutput` does.
+//!   Having no timing, it starts where the previous cue ended and lasts
+//!   as long as it takes to read.
+//!
+//! Words are grouped into cues of at most `max_lines` lines of
+//! `max_line_length` characters, split on speaker changes, and kept on
+//! screen long enough to read at `max_chars_per_second`. Each cue is
+//! appended to `srt_path` / `vtt_path` as soon as it is made and emitted
+//! as `RuntimeData::Text` for live overlays.
+//!
+//! ```yaml
+//! - id: subtitles
+//!   node_type: SubtitleSinkNode
+//!   params:
+//!     srt_path: "captions/{session_id}.srt"
+//!     vtt_path: "captions/{session_id}.vtt"
+//!     speaker_labels: change
+//!     speaker_names: { "0": "Host", "1": "Guest" }
+//! ```
+
+mod cue;
+mod writer;
+
+pub use cue::{Cue, CueRules};
+pub use writer::SubtitleFormat;
+
+use crate::data::RuntimeData;
+use crate::nodes::{StreamingNode, SyncNodeWrapper, SyncStreamingNode};
+use crate::Error;
+use cue::{spread_words, TimedText};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::Mutex;
+use writer::{cue_lines, SubtitleFile};
+
+/// Placeholder in `srt_path` / `vtt_path` replaced by the session id
+const SESSION_PLACEHOLDER: &str = "{session_id}";
+
+/// Speaker turns older than this (relative to the latest cue) are dropped
+const TURN_RETENTION_SECS: f64 = 60.0;
+
+/// When cues name their speaker
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize, schemars::JsonSchema,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum SpeakerLabels {
+    /// Never
+    Off,
+    /// When the speaker differs from the previous cue's
+    #[default]
+    Change,
+    /// On every cue with a known speaker
+    Always,
+}
+
+/// Configuration for [`SubtitleSinkNode`]
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
+#[serde(default)]
+pub struct SubtitleSinkConfig {
+    /// SRT file to write
+    pub srt_path: Option<String>,
+    /// WebVTT file to write
+    pub vtt_path: Option<String>,
+    /// Format of the emitted cues; unset emits the plain cue text
+    pub emit: Option<SubtitleFormat>,
+    /// Characters per line
+    pub max_line_length: usize,
+    /// Lines per cue
+    pub max_lines: usize,
+    /// Reading speed in characters per second
+    pub max_chars_per_second: f64,
+    /// Shortest time a cue stays on screen, in seconds
+    pub min_duration: f64,
+    /// Longest span of speech a cue may cover, in seconds
+    pub max_duration: f64,
+    /// Gap kept between consecutive cues, in seconds
+    pub min_gap: f64,
+    /// When cues name their speaker
+    pub speaker_labels: SpeakerLabels,
+    /// Display names by speaker id; others are shown as "Speaker {id}"
+    pub speaker_names: HashMap<String, String>,
+}
+
+impl Default for SubtitleSinkConfig {
+    fn default() -> Self {
+        Self {
+            srt_path: None,
+            vtt_path: None,
+            emit: None,
+            max_line_length: 42,
+            max_lines: 2,
+            max_chars_per_second: 17.0,
+            min_duration: 1.0,
+            max_duration: 7.0,
+            min_gap: 0.08,
+            speaker_labels: SpeakerLabels::default(),
+            speaker_names: HashMap::new(),
+        }
+    }
+}
+
+impl SubtitleSinkConfig {
+    fn validate(&self) -> Result<(), Error> {
+        if self.max_line_length == 0 || self.max_lines == 0 {
+            return Err(Error::InvalidManifest(
+                "SubtitleSinkNode `max_line_length` and `max_lines` must be positive".to_string(),
+            ));
+        }
+        for (name, value) in [
+            ("max_chars_per_second", self.max_chars_per_second),
+            ("max_duration", self.max_duration),
+        ] {
+            if value.is_nan() || value <= 0.0 {
+                return Err(Error::InvalidManifest(format!(
+                    "SubtitleSinkNode `{}` must be positive",
+                    name
+                )));
+            }
+        }
+        for (name, value) in [
+            ("min_duration", self.min_duration),
+            ("min_gap", self.min_gap),
+        ] {
+            if value.is_nan() || value < 0.0 {
+                return Err(Error::InvalidManifest(format!(
+                    "SubtitleSinkNode `{}` must not be negative",
+                    name
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    fn rules(&self) -> CueRules {
+        CueRules {
+            max_line_length: self.max_line_length,
+            max_lines: self.max_lines,
+            max_chars_per_second: self.max_chars_per_second,
+            min_duration: self.min_duration,
+            max_duration: self.max_duration,
+            min_gap: self.min_gap,
+        }
+    }
+
+    fn label(&self, speaker: &str) -> Option<String> {
+        if self.speaker_labels == SpeakerLabels::Off {
+            return None;
+        }
+        Some(
+            self.speaker_names
+                .get(speaker)
+                .cloned()
+                .unwrap_or_else(|| format!("Speaker {}", speaker)),
+        )
+    }
+}
+
+/// Speaker turn from diarization
+#[derive(Debug, Clone, Deserialize)]
+struct SpeakerTurn {
+    start: f64,
+    end: f64,
+    speaker: String,
+}
+
+struct SinkState {
+    files: Vec<SubtitleFile>,
+    /// Recent speaker turns, in arrival order
+    turns: Vec<SpeakerTurn>,
+    /// Cues written so far
+    cue_count: usize,
+    /// Earliest start of the next cue
+    not_before: f64,
+    /// Speaker of the last cue
+    last_speaker: Option<String>,
+}
+
+/// Speaker whose turns overlap `[start, end]` the most
+fn speaker_at(turns: &[SpeakerTurn], start: f64, end: f64) -> Option<String> {
+    let middle = (start + end) / 2.0;
+    turns
+        .iter()
+        .map(|turn| {
+            let overlap = if end > start {
+                turn.end.min(end) - turn.start.max(start)
+            } else if turn.start <= middle && middle < turn.end {
+                f64::MIN_POSITIVE
+            } else {
+                0.0
+            };
+            (turn, overlap)
+        })
+        .filter(|(_, overlap)| *overlap > 0.0)
+        .max_by(|a, b| a.1.total_cmp(&b.1))
+        .map(|(turn, _)| turn.speaker.clone())
+}
+
+/// Timed words of one transcript segment
+fn segment_words(segment: &Value) -> Vec<TimedText> {
+    let start = segment["start"].as_f64().unwrap_or(0.0);
+    let end = segment["end"].as_f64().unwrap_or(start);
+    let speaker = segment["speaker"].as_str();
+
+    let words: Vec<TimedText> = segment["words"]
+        .as_array()
+        .map(|words| {
+            words
+                .iter()
+                .filter_map(|word| {
+                    Some(TimedText {
+                        start: word["start"].as_f64()?,
+                        end: word["end"].as_f64()?,
+                        text: word["word"].as_str()?.to_string(),
+                        speaker: word["speaker"].as_str().or(speaker).map(str::to_string),
+                    })
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    if !words.is_empty() {
+        return words;
+    }
+    spread_words(start, end, segment["text"].as_str().unwrap_or(""), speaker)
+}
+
+/// Sink that writes transcripts as SRT / WebVTT subtitles
+pub struct SubtitleSinkNode {
+    config: SubtitleSinkConfig,
+    state: Mutex<SinkState>,
+}
+
+impl SubtitleSinkNode {
+    /// Create the sink, creating its subtitle files
+    pub fn new(config: SubtitleSinkConfig) -> Result<Self, Error> {
+        config.validate()?;
+        let mut files = Vec::new();
+        for (path, format) in [
+            (&config.srt_path, SubtitleFormat::Srt),
+            (&config.vtt_path, SubtitleFormat::Vtt),
+        ] {
+            if let Some(path) = path {
+                files.push(SubtitleFile::create(Path::new(path), format)?);
+            }
+        }
+        Ok(Self {
+            config,
+            state: Mutex::new(SinkState {
+                files,
+                turns: Vec::new(),
+                cue_count: 0,
+                not_before: 0.0,
+                last_speaker: None,
+            }),
+        })
+    }
+
+    /// Take in a transcript or diarization result, returning the emitted
+    /// form of every new cue
+    fn ingest(&self, data: &RuntimeData) -> Result<Vec<String>, Error> {
+        let mut state = self
+            .state
+            .lock()
+            .map_err(|e| Error::Execution(format!("SubtitleSinkNode state poisoned: {}", e)))?;
+        let state = &mut *state;
+
+        let wrapped;
+        let json = match data {
+            RuntimeData::Audio {
+                metadata: Some(metadata),
+                ..
+            } => {
+                Self::add_turns(state, &metadata["diarization"]);
+                return Ok(Vec::new());
+            }
+            RuntimeData::Json(json) => json,
+            RuntimeData::Text(text) => {
+                let start = state.not_before;
+                let reading = text.chars().count() as f64 / self.config.max_chars_per_second;
+                wrapped = serde_json::json!({
+                    "segments": [{
+                        "start": start,
+                        "end": start + reading.max(self.config.min_duration),
+                        "text": text,
+                    }]
+                });
+                &wrapped
+            }
+            // Anything else carries neither text nor timing
+            _ => return Ok(Vec::new()),
+        };
+        Self::add_turns(state, &json["diarization"]);
+
+        let mut words: Vec<TimedText> = Vec::new();
+        if let Some(segments) = json["segments"].as_array() {
+            if segments.iter().all(|s| s.get("text").is_none()) {
+                // Diarization result on its own
+                Self::add_turns(state, json);
+            } else {
+                for segment in segments {
+                    words.extend(segment_words(segment));
+                }
+            }
+        } else if let (Some(word), Some(start), Some(end)) = (
+            json["word"].as_str(),
+            json["start"].as_f64(),
+            json["end"].as_f64(),
+        ) {
+            // Single word update from a streaming transcriber
+            words.push(TimedText {
+                start,
+                end,
+                text: word.to_string(),
+                speaker: json["speaker"].as_str().map(str::to_string),
+            });
+        }
+        if words.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        for word in words.iter_mut().filter(|w| w.speaker.is_none()) {
+            word.speaker = speaker_at(&state.turns, word.start, word.end);
+        }
+
+        let rules = self.config.rules();
+        let mut cues = rules.build(&words, |speaker| self.config.label(speaker));
+        for cue in &mut cues {
+            let show = match self.config.speaker_labels {
+                SpeakerLabels::Off => false,
+                SpeakerLabels::Change => cue.speaker != state.last_speaker,
+                SpeakerLabels::Always => true,
+            };
+            if show {
+                cue.label = cue.speaker.as_deref().and_then(|s| self.config.label(s));
+            }
+            if cue.speaker.is_some() {
+                state.last_speaker = cue.speaker.clone();
+            }
+        }
+        state.not_before = rules.retime(&mut cues, state.not_before);
+        let horizon = state.not_before - TURN_RETENTION_SECS;
+        state.turns.retain(|turn| turn.end >= horizon);
+
+        let (width, lines) = (self.config.max_line_length, self.config.max_lines);
+        let mut emitted = Vec::with_capacity(cues.len());
+        for cue in &cues {
+            state.cue_count += 1;
+            for file in &mut state.files {
+                file.write(&file.format().render(state.cue_count, cue, width, lines))?;
+            }
+            emitted.push(match self.config.emit {
+                Some(format) => format.render(state.cue_count, cue, width, lines),
+                None => cue_lines(&cue.labelled_text(), width, lines).join("\n"),
+            });
+        }
+        Ok(emitted)
+    }
+
+    fn add_turns(state: &mut SinkState, diarization: &Value) {
+        if let Some(segments) = diarization["segments"].as_array() {
+            state.turns.extend(
+                segments
+                    .iter()
+                    .filter_map(|s| serde_json::from_value::<SpeakerTurn>(s.clone()).ok()),
+            );
+        }
+    }
+}
+
+impl SyncStreamingNode for SubtitleSinkNode {
+    fn node_type(&self) -> &str {
+        "SubtitleSinkNode"
+    }
+
+    fn process(&self, data: RuntimeData) -> Result<RuntimeData, Error> {
+        let separator = if self.config.emit.is_some() { "" } else { "\n" };
+        Ok(RuntimeData::Text(self.ingest(&data)?.join(separator)))
+    }
+
+    fn process_streaming(
+        &self,
+        data: RuntimeData,
+        _session_id: Option<&str>,
+        callback: &mut dyn FnMut(RuntimeData) -> Result<(), Error>,
+    ) -> Result<usize, Error> {
+        let cues = self.ingest(&data)?;
+        let count = cues.len();
+        for cue in cues {
+            callback(RuntimeData::Text(cue))?;
+        }
+        Ok(count)
+    }
+}
+
+/// Factory for creating SubtitleSinkNode instances
+pub struct SubtitleSinkNodeFactory;
+
+impl crate::nodes::StreamingNodeFactory for SubtitleSinkNodeFactory {
+    fn create(
+        &self,
+        node_id: String,
+        params: &Value,
+        session_id: Option<String>,
+    ) -> Result<Box<dyn StreamingNode>, Error> {
+        let mut config: SubtitleSinkConfig = if params.is_null() {
+            SubtitleSinkConfig::default()
+        } else {
+            serde_json::from_value(params.clone())
+                .map_err(|e| Error::InvalidManifest(format!("SubtitleSinkNode params: {}", e)))?
+        };
+
+        // Concurrent sessions need distinct files
+        let session = session_id.as_deref().unwrap_or(&node_id).to_string();
+        for path in [&mut config.srt_path, &mut config.vtt_path]
+            .into_iter()
+            .flatten()
+        {
+            *path = path.replace(SESSION_PLACEHOLDER, &session);
+        }
+
+        Ok(Box::new(SyncNodeWrapper(SubtitleSinkNode::new(config)?)))
+    }
+
+    fn node_type(&self) -> &str {
+        "SubtitleSinkNode"
+    }
+
+    fn is_multi_output_streaming(&self) -> bool {
+        true
+    }
+
+    fn schema(&self) -> Option<crate::nodes::schema::NodeSchema> {
+        use crate::nodes::schema::{NodeSchema, RuntimeDataType};
+        Some(
+            NodeSchema::new("SubtitleSinkNode")
+                .description(
+                    "Writes timestamped transcripts as SRT/WebVTT subtitles with speaker labels",
+                )
+                .category("utility")
+                .accepts([RuntimeDataType::Json, RuntimeDataType::Audio])
+                .produces([RuntimeDataType::Text])
+                .config_schema_from::<SubtitleSinkConfig>(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::nodes::StreamingNodeFactory;
+    use serde_json::json;
+
+    fn diarized_audio() -> RuntimeData {
+        RuntimeData::Audio {
+            samples: vec![0.0; 160].into(),
+            sample_rate: 16000,
+            channels: 1,
+            stream_id: None,
+            timestamp_us: None,
+            arrival_ts_us: None,
+            metadata: Some(json!({
+                "diarization": {
+                    "segments": [
+                        {"start": 0.0, "end": 2.0, "speaker": "0"},
+                        {"start": 2.0, "end": 6.0, "speaker": "1"}
+                    ],
+                    "num_speakers": 2,
+                    "time_offset": 0.0
+                }
+            })),
+        }
+    }
+
+    fn transcript() -> RuntimeData {
+        RuntimeData::Json(json!({
+            "text": "Hello there. Hi, nice to meet you.",
+            "segments": [
+                {"start": 0.2, "end": 1.6, "text": "Hello there."},
+                {"start": 2.4, "end": 4.5, "text": "Hi, nice to meet you."}
+            ]
+        }))
+    }
+
+    #[test]
+    fn test_config_validation() {
+        let node = |params: Value| {
+            SubtitleSinkNodeFactory
+                .create("subs".to_string(), &params, None)
+                .map(|_| ())
+        };
+        assert!(node(json!({})).is_ok());
+        assert!(node(json!({"max_lines": 0})).is_err());
+        assert!(node(json!({"max_chars_per_second": 0.0})).is_err());
+        assert!(node(json!({"emit": "ass"})).is_err());
+    }
+
+    #[test]
+    fn test_writes_srt_and_vtt_with_speakers() {
+        let dir = tempfile::tempdir().unwrap();
+        let srt = dir.path().join("out.srt");
+        let vtt = dir.path().join("out.vtt");
+        let node = SubtitleSinkNode::new(SubtitleSinkConfig {
+            srt_path: Some(srt.to_string_lossy().into_owned()),
+            vtt_path: Some(vtt.to_string_lossy().into_owned()),
+            speaker_names: HashMap::from([("1".to_string(), "Guest".to_string())]),
+            ..Default::default()
+        })
+        .unwrap();
+
+        let mut live = Vec::new();
+        for data in [diarized_audio(), transcript()] {
+            node.process_streaming(data, None, &mut |cue| {
+                live.push(cue);
+                Ok(())
+            })
+            .unwrap();
+        }
+        assert_eq!(live.len(), 2);
+        assert!(
+            matches!(&live[1], RuntimeData::Text(t) if t == "Guest: Hi, nice to meet you."),
+            "{:?}",
+            live[1]
+        );
+
+        // Files are written as cues are made, without waiting for a drop
+        assert_eq!(
+            std::fs::read_to_string(&srt).unwrap(),
+            "1\n00:00:00,200 --> 00:00:01,600\nSpeaker 0: Hello there.\n\n\
+             2\n00:00:02,400 --> 00:00:04,500\nGuest: Hi, nice to meet you.\n\n"
+        );
+        let vtt = std::fs::read_to_string(&vtt).unwrap();
+        assert!(vtt.starts_with("WEBVTT\n\n1\n00:00:00.200 --> 00:00:01.600\n<v Speaker 0>"));
+    }
+
+    #[test]
+    fn test_speaker_label_on_change_only() {
+        let node = SubtitleSinkNode::new(SubtitleSinkConfig {
+            max_line_length: 12,
+            max_lines: 1,
+            ..Default::default()
+        })
+        .unwrap();
+        let out = node
+            .process(RuntimeData::Json(json!({
+                "segments": [{
+                    "start": 0.0,
+                    "end": 3.0,
+                    "text": "one two three four",
+                    "speaker": "A"
+                }]
+            })))
+            .unwrap();
+        // The label leaves no room for a second word, so words go one per
+        // cue, and only the first names the speaker
+        let RuntimeData::Text(out) = out else {
+            panic!("expected text");
+        };
+        let cues: Vec<&str> = out.lines().collect();
+        assert_eq!(cues[0], "Speaker A: one");
+        assert!(cues[1..].iter().all(|c| !c.contains("Speaker")));
+    }
+
+    #[test]
+    fn test_plain_text_follows_previous_cue() {
+        let node = SubtitleSinkNode::new(SubtitleSinkConfig {
+            emit: Some(SubtitleFormat::Srt),
+            ..Default::default()
+        })
+        .unwrap();
+        node.process(transcript()).unwrap();
+
+        let out = node
+            .process(RuntimeData::Text("Goodbye.".to_string()))
+            .unwrap();
+        let RuntimeData::Text(out) = out else {
+            panic!("expected text");
+        };
+        // Starts one gap after "Hi, nice to meet you." and stays up for
+        // the minimum duration
+        assert_eq!(out, "3\n00:00:04,580 --> 00:00:05,580\nGoodbye.\n\n");
+    }
+}
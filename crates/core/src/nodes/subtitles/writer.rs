@@ -0,0 +1,167 @@
+//! SRT and WebVTT rendering and incremental file output
+
+use super::cue::{wrap, Cue};
+use crate::Error;
+use serde::{Deserialize, Serialize};
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+
+/// Subtitle text format
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, schemars::JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum SubtitleFormat {
+    /// SubRip (`.srt`)
+    Srt,
+    /// WebVTT (`.vtt`)
+    Vtt,
+}
+
+/// `HH:MM:SS` plus milliseconds after `separator`
+fn timecode(seconds: f64, separator: char) -> String {
+    let total_ms = (seconds.max(0.0) * 1000.0).round() as u64;
+    let ms = total_ms % 1000;
+    let secs = total_ms / 1000 % 60;
+    let mins = total_ms / 60_000 % 60;
+    let hours = total_ms / 3_600_000;
+    format!("{:02}:{:02}:{:02}{}{:03}", hours, mins, secs, separator, ms)
+}
+
+/// Display lines of a cue, falling back to a single line when the text
+/// can't be wrapped (a word longer than a line)
+pub fn cue_lines(text: &str, max_line_length: usize, max_lines: usize) -> Vec<String> {
+    wrap(text, max_line_length, max_lines).unwrap_or_else(|| vec![text.to_string()])
+}
+
+impl SubtitleFormat {
+    /// File header written before the first cue
+    pub fn header(self) -> &'static str {
+        match self {
+            Self::Srt => "",
+            Self::Vtt => "WEBVTT\n\n",
+        }
+    }
+
+    /// Render the `index`-th cue (1-based), blank line included
+    ///
+    /// SRT shows the speaker label as a text prefix; WebVTT puts it in a
+    /// `<v>` voice span so players can style or hide it.
+    pub fn render(
+        self,
+        index: usize,
+        cue: &Cue,
+        max_line_length: usize,
+        max_lines: usize,
+    ) -> String {
+        match self {
+            Self::Srt => format!(
+                "{}\n{} --> {}\n{}\n\n",
+                index,
+                timecode(cue.start, ','),
+                timecode(cue.end, ','),
+                cue_lines(&cue.labelled_text(), max_line_length, max_lines).join("\n")
+            ),
+            Self::Vtt => {
+                let mut lines: Vec<String> = cue_lines(&cue.text, max_line_length, max_lines)
+                    .iter()
+                    .map(|line| escape_vtt(line))
+                    .collect();
+                if let (Some(label), Some(first)) = (&cue.label, lines.first_mut()) {
+                    *first = format!("<v {}>{}", escape_vtt(label), first);
+                }
+                format!(
+                    "{}\n{} --> {}\n{}\n\n",
+                    index,
+                    timecode(cue.start, '.'),
+                    timecode(cue.end, '.'),
+                    lines.join("\n")
+                )
+            }
+        }
+    }
+}
+
+/// Escape the characters WebVTT treats as markup
+fn escape_vtt(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+/// Subtitle file that cues are appended to as they are made
+pub struct SubtitleFile {
+    format: SubtitleFormat,
+    writer: BufWriter<File>,
+}
+
+impl SubtitleFile {
+    /// Create (or truncate) `path` and write the format's header
+    pub fn create(path: &Path, format: SubtitleFormat) -> Result<Self, Error> {
+        if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            std::fs::create_dir_all(parent).map_err(|e| {
+                Error::Execution(format!("Failed to create {}: {}", parent.display(), e))
+            })?;
+        }
+        let file = File::create(path)
+            .map_err(|e| Error::Execution(format!("Failed to create {}: {}", path.display(), e)))?;
+        let mut file = Self {
+            format,
+            writer: BufWriter::new(file),
+        };
+        file.write(format.header())?;
+        Ok(file)
+    }
+
+    pub fn format(&self) -> SubtitleFormat {
+        self.format
+    }
+
+    /// Append rendered text and flush it, so readers see every cue as soon
+    /// as it is written
+    pub fn write(&mut self, text: &str) -> Result<(), Error> {
+        self.writer
+            .write_all(text.as_bytes())
+            .and_then(|_| self.writer.flush())
+            .map_err(|e| Error::Execution(format!("Failed to write subtitles: {}", e)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cue(label: Option<&str>) -> Cue {
+        Cue {
+            start: 3661.5,
+            end: 3663.25,
+            text: "Fish & chips <please>".to_string(),
+            speaker: label.map(|_| "0".to_string()),
+            label: label.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn test_timecode() {
+        assert_eq!(timecode(3661.5, ','), "01:01:01,500");
+        assert_eq!(timecode(0.0004, '.'), "00:00:00.000");
+    }
+
+    #[test]
+    fn test_render_srt() {
+        assert_eq!(
+            SubtitleFormat::Srt.render(7, &cue(Some("Ann")), 42, 2),
+            "7\n01:01:01,500 --> 01:01:03,250\nAnn: Fish & chips <please>\n\n"
+        );
+    }
+
+    #[test]
+    fn test_render_vtt_uses_voice_spans() {
+        assert_eq!(
+            SubtitleFormat::Vtt.render(1, &cue(Some("Ann")), 14, 2),
+            "1\n01:01:01.500 --> 01:01:03.250\n<v Ann>Fish &amp; chips\n&lt;please&gt;\n\n"
+        );
+        assert!(SubtitleFormat::Vtt
+            .render(1, &cue(None), 42, 2)
+            .ends_with("\nFish &amp; chips &lt;please&gt;\n\n"));
+    }
+}
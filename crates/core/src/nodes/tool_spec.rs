@@ -16,18 +16,22 @@
 //!   - [`ToolKind::SideEffect`] — the LLM node consumes the call inline
 //!     (e.g. `say` yields its `text` argument as TTS-channel output).
 //!     No tool-result is fed back to the model.
-//!   - [`ToolKind::ReturnValue`] — reserved for the classic two-pass
-//!     "generate → execute → feed result back → regenerate" flow. Not
-//!     yet implemented in Rust streaming dispatch; declaring such a
-//!     tool currently logs and is dropped at dispatch time.
+//!   - [`ToolKind::ReturnValue`] — the classic multi-pass "generate →
+//!     execute → feed result back → regenerate" flow. The call is
+//!     executed by a [`crate::llm::ToolHandler`] (registered from Rust,
+//!     or declared in the manifest via [`ToolSpec::handler`]) and the
+//!     backend re-generates with the result in history.
 //! - [`default_say_tool`] / [`default_show_tool`] are the canonical
 //!   built-ins. The LLM node typically toggles them via config flags
 //!   rather than asking callers to construct them.
+//! - [`build_tool_registry`] assembles a node's active tools from
+//!   those flags and its user-defined specs.
 //! - [`to_openai_tools_array`] renders a slice of specs as the
 //!   `tools` field of an OpenAI chat-completions request body.
 
 use serde::{Deserialize, Serialize};
 use serde_json::{json, Value};
+use std::collections::BTreeMap;
 
 /// Dispatch contract for a tool call.
 ///
@@ -38,8 +42,8 @@ pub enum ToolKind {
     /// Tool is consumed inline by the LLM node — the call IS the
     /// output. No result is fed back to the model.
     SideEffect,
-    /// Tool's return value should be fed back to the model on a second
-    /// generation pass. Not implemented in the Rust SSE pipeline yet.
+    /// Tool's return value is fed back to the model on a follow-up
+    /// generation pass. Executed by a [`crate::llm::ToolHandler`].
     ReturnValue,
 }
 
@@ -60,6 +64,33 @@ pub struct ToolSpec {
     pub parameters: Value,
     #[serde(default)]
     pub kind: ToolKind,
+    /// Manifest-declared executor for a `return_value` tool. Tools
+    /// without one fall back to a handler registered from Rust under
+    /// the same name (see [`crate::llm::ToolHandlerRegistry`]).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub handler: Option<ToolHandlerSpec>,
+}
+
+/// Manifest-declared executor for a [`ToolKind::ReturnValue`] tool.
+///
+/// ```json
+/// { "name": "lookup_order", "kind": "return_value",
+///   "handler": { "type": "http", "url": "https://example.com/hooks/order" } }
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, schemars::JsonSchema)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ToolHandlerSpec {
+    /// POST the call (`{session_id, call_id, name, arguments}`) as JSON
+    /// to `url`; the response body becomes the tool result.
+    Http {
+        url: String,
+        /// Extra request headers (e.g. `Authorization`).
+        #[serde(default)]
+        headers: BTreeMap<String, String>,
+        /// Per-call timeout. Default: 10 s.
+        #[serde(default, alias = "timeoutMs")]
+        timeout_ms: Option<u64>,
+    },
 }
 
 impl ToolSpec {
@@ -78,6 +109,36 @@ impl ToolSpec {
     }
 }
 
+/// Build an LLM node's active tool registry: built-ins gated by the
+/// `enable_say_tool` / `enable_show_tool` flags + user-defined tools,
+/// deduped by name (later wins), then optionally filtered by
+/// `active_tools`.
+pub fn build_tool_registry(
+    enable_say_tool: bool,
+    enable_show_tool: bool,
+    tools: &[ToolSpec],
+    active_tools: Option<&[String]>,
+) -> Vec<ToolSpec> {
+    let mut out: Vec<ToolSpec> = Vec::new();
+    if enable_say_tool {
+        out.push(default_say_tool());
+    }
+    if enable_show_tool {
+        out.push(default_show_tool());
+    }
+    for spec in tools {
+        if let Some(existing) = out.iter_mut().find(|t| t.name == spec.name) {
+            *existing = spec.clone();
+        } else {
+            out.push(spec.clone());
+        }
+    }
+    if let Some(active) = active_tools {
+        out.retain(|t| active.contains(&t.name));
+    }
+    out
+}
+
 /// Render a slice of specs as a JSON array suitable for the
 /// chat-completions `tools` request field.
 pub fn to_openai_tools_array(specs: &[ToolSpec]) -> Value {
@@ -115,6 +176,7 @@ string of plain prose. Example: \"Sure thing, here's the Python script.\"",
             "required": ["text"]
         }),
         kind: ToolKind::SideEffect,
+        handler: None,
     }
 }
 
@@ -146,6 +208,7 @@ string. Example: \"```python\\nprint('hi')\\n```\"",
             "required": ["content"]
         }),
         kind: ToolKind::SideEffect,
+        handler: None,
     }
 }
 
@@ -168,6 +231,29 @@ mod tests {
         assert_eq!(spec.parameters["required"][0], "content");
     }
 
+    #[test]
+    fn tool_registry_dedupes_and_filters() {
+        let custom_say = ToolSpec {
+            description: "custom".to_string(),
+            ..default_say_tool()
+        };
+        let lookup = ToolSpec {
+            name: "lookup".to_string(),
+            ..default_show_tool()
+        };
+        let tools = vec![custom_say, lookup];
+
+        let all = build_tool_registry(true, true, &tools, None);
+        let names: Vec<_> = all.iter().map(|t| t.name.as_str()).collect();
+        assert_eq!(names, ["say", "show", "lookup"]);
+        assert_eq!(all[0].description, "custom");
+
+        let active = ["lookup".to_string()];
+        let filtered = build_tool_registry(true, false, &tools, Some(&active));
+        assert_eq!(filtered.len(), 1);
+        assert_eq!(filtered[0].name, "lookup");
+    }
+
     #[test]
     fn to_openai_function_shape() {
         let v = default_say_tool().to_openai_function();
@@ -185,4 +271,37 @@ mod tests {
         assert_eq!(arr[0]["function"]["name"], "say");
         assert_eq!(arr[1]["function"]["name"], "show");
     }
+
+    #[test]
+    fn return_value_tool_with_http_handler_parses() {
+        let spec: ToolSpec = serde_json::from_value(json!({
+            "name": "lookup_order",
+            "description": "Look up an order by id",
+            "parameters": {"type": "object"},
+            "kind": "return_value",
+            "handler": {
+                "type": "http",
+                "url": "http://localhost:9000/order",
+                "headers": {"Authorization": "Bearer t"},
+                "timeoutMs": 2500
+            }
+        }))
+        .unwrap();
+        assert_eq!(spec.kind, ToolKind::ReturnValue);
+        match spec.handler {
+            Some(ToolHandlerSpec::Http {
+                url,
+                headers,
+                timeout_ms,
+            }) => {
+                assert_eq!(url, "http://localhost:9000/order");
+                assert_eq!(headers["Authorization"], "Bearer t");
+                assert_eq!(timeout_ms, Some(2500));
+            }
+            other => panic!("expected http handler, got {:?}", other),
+        }
+        // Built-ins and handler-less specs don't serialize the field.
+        let rendered = serde_json::to_value(default_say_tool()).unwrap();
+        assert!(rendered.get("handler").is_none());
+    }
 }
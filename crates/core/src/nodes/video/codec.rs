@@ -2,30 +2,40 @@
 //!
 //! Provides traits and implementations for video encoding/decoding backends.
 //! Primary backend: ac-ffmpeg (FFmpeg bindings)
-//! Optional: rav1e (pure Rust AV1 encoder)
 //!
-//! ## ac-ffmpeg Integration Status
+//! ## Software codecs
 //!
-//! The codec implementations below provide functional mock encoding/decoding
-//! that enables end-to-end testing of the video pipeline architecture.
+//! Only software codecs are used so the nodes behave identically on every
+//! host. Encoders are probed in order and the first one FFmpeg was built
+//! with wins:
 //!
-//! To enable actual FFmpeg encoding/decoding, implement the TODOs in:
-//! - FFmpegEncoder::encode() - Use ac-ffmpeg VideoEncoder
-//! - FFmpegDecoder::decode() - Use ac-ffmpeg VideoDecoder
+//! | Codec | Encoders                                   | Decoders                    |
+//! |-------|--------------------------------------------|-----------------------------|
+//! | VP8   | `libvpx`                                   | `vp8`, `libvpx`             |
+//! | H.264 | `libx264`, `libopenh264`                   | `h264`                      |
+//! | AV1   | `libaom-av1`, `libsvtav1`, `librav1e`      | `libdav1d`, `libaom-av1`, `av1` |
 //!
-//! FFmpeg 7.1.1 is installed with libvpx (VP8), libx264 (H.264), and libaom/librav1e (AV1).
+//! `hardware_accel` in the node configs is currently ignored.
 //!
-//! Key ac-ffmpeg API elements (from source inspection):
-//! - VideoDecoder::new(codec_name) or VideoDecoder::builder(codec_name).build()
-//! - VideoEncoder::builder(codec_name).pixel_format(fmt).width(w).height(h).time_base(tb).bit_rate(br).build()
-//! - PixelFormat: parse from strings "yuv420p", "rgb24", "nv12", etc.
-//! - VideoFrameMut::black(format, width, height) - Create frame, use planes_mut()[i].data_mut() to write pixels
-//! - decoder.push(packet), decoder.take() -> Option<VideoFrame>
-//! - encoder.push(frame), encoder.take() -> Option<Packet>
-//! - Packet data access: packet is from demuxer or needs PacketMut construction
+//! ## Latency
+//!
+//! Encoders are opened in their low-latency modes (`lag-in-frames=0`,
+//! `tune=zerolatency`, no B-frames) so every pushed frame yields its
+//! packet immediately and the encoded output keeps the input's
+//! `frame_number` / `timestamp_us`. Decoders may still buffer; decoded
+//! frames are queued and carry the timestamp of the packet they came from.
+//!
+//! ## Keyframes and rate control
+//!
+//! - A raw input frame with `is_keyframe: true`, or a call to
+//!   [`VideoEncoderBackend::request_keyframe`], forces the next encoded
+//!   frame to be an I-frame (IDR for H.264).
+//! - `bitrate` sets the target rate and the VBV ceiling (`maxrate`, one
+//!   second of `bufsize`). [`VideoEncoderBackend::reconfigure`] drains and
+//!   closes the encoder, returning the frames it still held; it is reopened
+//!   with the new settings on the next frame, which is then a keyframe.
 //!
 //! References:
-//! - Source: ~/.cargo/registry/src/.../ac-ffmpeg-0.19.0/src/codec/video/
 //! - Docs: https://docs.rs/ac-ffmpeg/0.19.0
 //! - Examples: https://github.com/angelcam/rust-ac-ffmpeg/tree/master/examples
 
@@ -34,6 +44,9 @@ use crate::data::video::{PixelFormat, VideoCodec};
 use super::encoder::VideoEncoderConfig;
 use super::decoder::VideoDecoderConfig;
 
+#[cfg(feature = "video")]
+use std::collections::VecDeque;
+
 /// Result type for codec operations
 pub type Result<T> = std::result::Result<T, CodecError>;
 
@@ -74,7 +87,20 @@ pub trait VideoEncoderBackend: Send + Sync {
     fn codec(&self) -> VideoCodec;
 
     /// Reconfigure encoder (bitrate, quality, etc.)
-    fn reconfigure(&mut self, config: &VideoEncoderConfig) -> Result<()>;
+    ///
+    /// Returns the frames still buffered under the old settings, in
+    /// output order, so none are lost when the encoder is reopened.
+    fn reconfigure(&mut self, config: &VideoEncoderConfig) -> Result<Vec<RuntimeData>>;
+
+    /// Force the next encoded frame to be a keyframe
+    fn request_keyframe(&mut self) {}
+
+    /// Drain any frames still buffered inside the encoder
+    ///
+    /// Called at end of stream. Returns encoded frames in output order.
+    fn flush(&mut self) -> Result<Vec<RuntimeData>> {
+        Ok(Vec::new())
+    }
 }
 
 /// Video decoder backend trait
@@ -96,38 +122,449 @@ pub trait VideoDecoderBackend: Send + Sync {
 
     /// Get the output pixel format
     fn output_format(&self) -> PixelFormat;
+
+    /// Drain any frames still buffered inside the decoder
+    ///
+    /// Called at end of stream. Returns decoded frames in display order.
+    fn flush(&mut self) -> Result<Vec<RuntimeData>> {
+        Ok(Vec::new())
+    }
+}
+
+/// Empty raw frame returned when a codec has nothing to emit yet
+pub(crate) fn empty_frame() -> RuntimeData {
+    RuntimeData::Video {
+        pixel_data: vec![],
+        width: 0,
+        height: 0,
+        format: PixelFormat::Unspecified,
+        codec: None,
+        frame_number: 0,
+        timestamp_us: 0,
+        is_keyframe: false,
+        stream_id: None,
+        arrival_ts_us: None,
+    }
+}
+
+/// FFmpeg pixel format name for a raw [`PixelFormat`]
+///
+/// Returns `None` for `Encoded` / `Unspecified`.
+pub(crate) fn ffmpeg_pixel_format_name(format: PixelFormat) -> Option<&'static str> {
+    match format {
+        PixelFormat::Yuv420p | PixelFormat::I420 => Some("yuv420p"),
+        PixelFormat::NV12 => Some("nv12"),
+        PixelFormat::Rgb24 => Some("rgb24"),
+        PixelFormat::Rgba32 => Some("rgba"),
+        PixelFormat::Encoded | PixelFormat::Unspecified => None,
+    }
+}
+
+/// Tightly-packed plane layout of a raw frame as `(row_bytes, rows)` per plane
+///
+/// This is the layout `RuntimeData::Video::pixel_data` uses; FFmpeg frames
+/// pad each row to `line_size`, so copies go row by row.
+pub(crate) fn plane_layout(format: PixelFormat, width: usize, height: usize) -> Vec<(usize, usize)> {
+    let chroma_w = width.div_ceil(2);
+    let chroma_h = height.div_ceil(2);
+    match format {
+        PixelFormat::Yuv420p | PixelFormat::I420 => {
+            vec![(width, height), (chroma_w, chroma_h), (chroma_w, chroma_h)]
+        }
+        PixelFormat::NV12 => vec![(width, height), (chroma_w * 2, chroma_h)],
+        PixelFormat::Rgb24 => vec![(width * 3, height)],
+        PixelFormat::Rgba32 => vec![(width * 4, height)],
+        PixelFormat::Encoded | PixelFormat::Unspecified => Vec::new(),
+    }
+}
+
+/// Software encoder names to probe for each codec, in preference order
+#[cfg(feature = "video")]
+fn encoder_candidates(codec: VideoCodec) -> &'static [&'static str] {
+    match codec {
+        VideoCodec::Vp8 => &["libvpx"],
+        VideoCodec::H264 => &["libx264", "libopenh264"],
+        VideoCodec::Av1 => &["libaom-av1", "libsvtav1", "librav1e"],
+    }
+}
+
+/// Software decoder names to probe for each codec, in preference order
+#[cfg(feature = "video")]
+fn decoder_candidates(codec: VideoCodec) -> &'static [&'static str] {
+    match codec {
+        VideoCodec::Vp8 => &["vp8", "libvpx"],
+        VideoCodec::H264 => &["h264"],
+        VideoCodec::Av1 => &["libdav1d", "libaom-av1", "av1"],
+    }
+}
+
+/// Copy tightly-packed `data` into the (stride-padded) planes of `frame`
+#[cfg(feature = "video")]
+fn copy_into_frame(
+    frame: &mut ac_ffmpeg::codec::video::VideoFrameMut,
+    data: &[u8],
+    layout: &[(usize, usize)],
+) -> Result<()> {
+    let expected: usize = layout.iter().map(|(row_bytes, rows)| row_bytes * rows).sum();
+    if data.len() < expected {
+        return Err(CodecError::InvalidInput(format!(
+            "Frame buffer too small: expected {} bytes, got {}",
+            expected,
+            data.len()
+        )));
+    }
+
+    let mut planes = frame.planes_mut();
+    let mut offset = 0;
+    for (index, &(row_bytes, rows)) in layout.iter().enumerate() {
+        let plane = &mut planes[index];
+        let stride = plane.line_size();
+        let dst = plane.data_mut();
+        for row in 0..rows {
+            let src = &data[offset + row * row_bytes..offset + (row + 1) * row_bytes];
+            dst[row * stride..row * stride + row_bytes].copy_from_slice(src);
+        }
+        offset += row_bytes * rows;
+    }
+
+    Ok(())
+}
+
+/// Copy the (stride-padded) planes of `frame` into a tightly-packed buffer
+#[cfg(feature = "video")]
+fn copy_from_frame(
+    frame: &ac_ffmpeg::codec::video::VideoFrame,
+    layout: &[(usize, usize)],
+) -> Vec<u8> {
+    let total: usize = layout.iter().map(|(row_bytes, rows)| row_bytes * rows).sum();
+    let mut out = Vec::with_capacity(total);
+
+    let planes = frame.planes();
+    for (index, &(row_bytes, rows)) in layout.iter().enumerate() {
+        let plane = &planes[index];
+        let stride = plane.line_size();
+        let src = plane.data();
+        for row in 0..rows {
+            out.extend_from_slice(&src[row * stride..row * stride + row_bytes]);
+        }
+    }
+
+    out
 }
 
 /// FFmpeg encoder implementation
 ///
-/// Currently returns mock encoded frames. To enable actual encoding:
-/// 1. Use VideoEncoder::builder() from ac-ffmpeg
-/// 2. Create VideoFrameMut from pixel_data
-/// 3. Push frame, take packets
-/// 4. Return bitstream as encoded RuntimeData
+/// Opens a software encoder lazily on the first frame (and again after a
+/// resolution change or [`reconfigure`](VideoEncoderBackend::reconfigure)).
+/// Non-YUV input is converted to `yuv420p` with swscale before encoding.
+///
+/// A resolution change drains the old encoder first; its last packets are
+/// queued ahead of the new encoder's and handed out by the following
+/// `encode` calls, or by `flush`.
 #[cfg(feature = "video")]
 pub struct FFmpegEncoder {
     config: VideoEncoderConfig,
     frame_count: u64,
     encoder: Option<ac_ffmpeg::codec::video::VideoEncoder>,
+    /// Name of the FFmpeg encoder that was opened (e.g. "libx264")
+    encoder_name: Option<&'static str>,
+    /// Input geometry and format the encoder/scaler were opened for
+    input_shape: Option<(u32, u32, PixelFormat)>,
+    /// Converts non-YUV420P input into the encoder's pixel format
+    scaler: Option<ac_ffmpeg::codec::video::VideoFrameScaler>,
+    /// Force the next pushed frame to be an I-frame
+    force_keyframe: bool,
+    /// (frame_number, timestamp_us) of frames pushed but not yet emitted
+    pending: VecDeque<(u64, u64)>,
+    /// Encoded frames not yet handed out
+    ready: VecDeque<RuntimeData>,
 }
 
 #[cfg(feature = "video")]
 impl FFmpegEncoder {
     pub fn new(config: VideoEncoderConfig) -> Result<Self> {
+        if config.framerate == 0 {
+            return Err(CodecError::InvalidConfig("framerate must be > 0".to_string()));
+        }
+
         Ok(Self {
             config,
             frame_count: 0,
             encoder: None, // Lazy initialization on first frame
+            encoder_name: None,
+            input_shape: None,
+            scaler: None,
+            force_keyframe: false,
+            pending: VecDeque::new(),
+            ready: VecDeque::new(),
         })
     }
+
+    /// Name of the FFmpeg encoder in use, once the first frame was encoded
+    pub fn encoder_name(&self) -> Option<&'static str> {
+        self.encoder_name
+    }
+
+    /// Open the first available software encoder for the configured codec
+    fn open_encoder(
+        &self,
+        width: u32,
+        height: u32,
+    ) -> Result<(ac_ffmpeg::codec::video::VideoEncoder, &'static str)> {
+        use ac_ffmpeg::codec::video::{VideoEncoder, frame::get_pixel_format};
+        use ac_ffmpeg::time::TimeBase;
+
+        if width % 2 != 0 || height % 2 != 0 {
+            return Err(CodecError::InvalidInput(format!(
+                "YUV420P encoding requires even dimensions, got {}x{}",
+                width, height
+            )));
+        }
+
+        let time_base = TimeBase::new(1, self.config.framerate as i32);
+        let bitrate = self.config.bitrate as u64;
+        let candidates = encoder_candidates(self.config.codec);
+
+        let mut last_error = None;
+        for &name in candidates {
+            let builder = match VideoEncoder::builder(name) {
+                Ok(builder) => builder,
+                Err(e) => {
+                    last_error = Some(e.to_string());
+                    continue;
+                }
+            };
+
+            let mut builder = builder
+                .pixel_format(get_pixel_format("yuv420p"))
+                .width(width as usize)
+                .height(height as usize)
+                .time_base(time_base)
+                .bit_rate(bitrate)
+                .set_option("g", self.config.keyframe_interval.max(1))
+                .set_option("bf", 0)
+                .set_option("maxrate", bitrate)
+                .set_option("bufsize", bitrate);
+
+            if self.config.threads > 0 {
+                builder = builder.set_option("threads", self.config.threads);
+            }
+
+            for (key, value) in encoder_options(name, &self.config.quality_preset) {
+                builder = builder.set_option(key, value);
+            }
+
+            match builder.build() {
+                Ok(encoder) => {
+                    tracing::debug!(
+                        "Opened {} encoder for {:?} at {}x{} ({} bps)",
+                        name, self.config.codec, width, height, bitrate
+                    );
+                    return Ok((encoder, name));
+                }
+                Err(e) => last_error = Some(format!("{}: {}", name, e)),
+            }
+        }
+
+        Err(CodecError::NotAvailable(format!(
+            "No software encoder available for {:?} (tried {}): {}",
+            self.config.codec,
+            candidates.join(", "),
+            last_error.unwrap_or_default()
+        )))
+    }
+
+    /// Build a YUV420P frame from the packed input buffer
+    fn prepare_frame(
+        &mut self,
+        pixel_data: &[u8],
+        width: u32,
+        height: u32,
+        format: PixelFormat,
+    ) -> Result<ac_ffmpeg::codec::video::VideoFrameMut> {
+        use ac_ffmpeg::codec::video::{VideoFrameMut, VideoFrameScaler, frame::get_pixel_format};
+
+        let name = ffmpeg_pixel_format_name(format).ok_or_else(|| {
+            CodecError::InvalidInput(format!("Unsupported raw pixel format {:?}", format))
+        })?;
+
+        let (w, h) = (width as usize, height as usize);
+        let mut frame = VideoFrameMut::black(get_pixel_format(name), w, h);
+        copy_into_frame(&mut frame, pixel_data, &plane_layout(format, w, h))?;
+
+        if name == "yuv420p" {
+            return Ok(frame);
+        }
+
+        if self.scaler.is_none() {
+            let scaler = VideoFrameScaler::builder()
+                .source_pixel_format(get_pixel_format(name))
+                .source_width(w)
+                .source_height(h)
+                .target_pixel_format(get_pixel_format("yuv420p"))
+                .target_width(w)
+                .target_height(h)
+                .build()
+                .map_err(|e| CodecError::EncodingFailed(format!("Failed to create converter: {}", e)))?;
+            self.scaler = Some(scaler);
+        }
+
+        let converted = self.scaler
+            .as_mut()
+            .unwrap()
+            .scale(&frame.freeze())
+            .map_err(|e| CodecError::EncodingFailed(format!("Pixel format conversion failed: {}", e)))?;
+
+        // Re-pack into a mutable frame so timestamps and picture type can be set
+        let yuv_layout = plane_layout(PixelFormat::Yuv420p, w, h);
+        let mut yuv = VideoFrameMut::black(get_pixel_format("yuv420p"), w, h);
+        copy_into_frame(&mut yuv, &copy_from_frame(&converted, &yuv_layout), &yuv_layout)?;
+        Ok(yuv)
+    }
+
+    /// Take the next packet the encoder has ready as one output frame
+    ///
+    /// Without B-frames packets come out in input order, one per frame, so
+    /// each is matched with the oldest pushed frame.
+    fn take_packet(&mut self, width: u32, height: u32) -> Result<Option<RuntimeData>> {
+        use ac_ffmpeg::codec::Encoder;
+
+        let packet = self.encoder
+            .as_mut()
+            .unwrap()
+            .take()
+            .map_err(|e| CodecError::EncodingFailed(format!("Failed to take packet from encoder: {}", e)))?;
+        let Some(packet) = packet else {
+            return Ok(None);
+        };
+
+        let (frame_number, timestamp_us) = self.pending.pop_front().unwrap_or_default();
+
+        Ok(Some(RuntimeData::Video {
+            pixel_data: packet.data().to_vec(),
+            width,
+            height,
+            format: PixelFormat::Encoded,
+            codec: Some(self.config.codec),
+            frame_number,
+            timestamp_us,
+            is_keyframe: packet.is_key(),
+            stream_id: None,
+            arrival_ts_us: None,
+        }))
+    }
+
+    /// Drain the open encoder, one output per packet, and close it
+    fn drain(&mut self) -> Result<Vec<RuntimeData>> {
+        use ac_ffmpeg::codec::Encoder;
+
+        let Some((width, height, _)) = self.input_shape else {
+            return Ok(Vec::new());
+        };
+
+        self.encoder
+            .as_mut()
+            .unwrap()
+            .flush()
+            .map_err(|e| CodecError::EncodingFailed(format!("Failed to flush encoder: {}", e)))?;
+
+        let mut frames = Vec::new();
+        while let Some(frame) = self.take_packet(width, height)? {
+            frames.push(frame);
+        }
+
+        // A flushed encoder cannot accept more frames
+        self.encoder = None;
+        self.input_shape = None;
+        self.scaler = None;
+        self.pending.clear();
+        Ok(frames)
+    }
+}
+
+/// Per-encoder private options for low-latency software encoding
+#[cfg(feature = "video")]
+fn encoder_options(name: &str, quality_preset: &str) -> Vec<(&'static str, String)> {
+    const X264_PRESETS: &[&str] = &[
+        "ultrafast", "superfast", "veryfast", "faster", "fast",
+        "medium", "slow", "slower", "veryslow",
+    ];
+
+    match name {
+        "libvpx" => {
+            let deadline = match quality_preset {
+                "good" | "best" | "realtime" => quality_preset,
+                _ => "realtime",
+            };
+            vec![
+                ("deadline", deadline.to_string()),
+                ("lag-in-frames", "0".to_string()),
+                ("cpu-used", "8".to_string()),
+            ]
+        }
+        "libx264" => {
+            let preset = if X264_PRESETS.contains(&quality_preset) {
+                quality_preset
+            } else {
+                "veryfast"
+            };
+            vec![
+                ("preset", preset.to_string()),
+                ("tune", "zerolatency".to_string()),
+                ("forced-idr", "1".to_string()),
+            ]
+        }
+        "libaom-av1" => {
+            let cpu_used = quality_preset
+                .parse::<u8>()
+                .ok()
+                .filter(|v| *v <= 10)
+                .unwrap_or(8);
+            vec![
+                ("usage", "realtime".to_string()),
+                ("cpu-used", cpu_used.to_string()),
+                ("lag-in-frames", "0".to_string()),
+            ]
+        }
+        "libsvtav1" => {
+            let preset = quality_preset
+                .parse::<u8>()
+                .ok()
+                .filter(|v| *v <= 13)
+                .unwrap_or(10);
+            vec![
+                ("preset", preset.to_string()),
+                // Low-delay prediction structure without lookahead
+                ("svtav1-params", "lookahead=0:pred-struct=1".to_string()),
+            ]
+        }
+        "librav1e" => {
+            let speed = quality_preset
+                .parse::<u8>()
+                .ok()
+                .filter(|v| *v <= 10)
+                .unwrap_or(10);
+            vec![
+                ("speed", speed.to_string()),
+                (
+                    "rav1e-params",
+                    "low_latency=true:rdo_lookahead_frames=1".to_string(),
+                ),
+            ]
+        }
+        _ => Vec::new(),
+    }
 }
 
 #[cfg(feature = "video")]
 impl VideoEncoderBackend for FFmpegEncoder {
     fn encode(&mut self, input: RuntimeData) -> Result<RuntimeData> {
+        use ac_ffmpeg::codec::Encoder;
+        use ac_ffmpeg::codec::video::frame::PictureType;
+        use ac_ffmpeg::time::{TimeBase, Timestamp};
+
         // Extract raw video frame
-        let (pixel_data, width, height, format, frame_number, timestamp_us) = match input {
+        let (pixel_data, width, height, format, frame_number, timestamp_us, wants_keyframe) = match input {
             RuntimeData::Video {
                 pixel_data,
                 width,
@@ -136,9 +573,9 @@ impl VideoEncoderBackend for FFmpegEncoder {
                 codec: None,
                 frame_number,
                 timestamp_us,
-                stream_id: _,
+                is_keyframe,
                 ..
-            } => (pixel_data, width, height, format, frame_number, timestamp_us),
+            } => (pixel_data, width, height, format, frame_number, timestamp_us, is_keyframe),
             RuntimeData::Video { codec: Some(_), .. } => {
                 return Err(CodecError::InvalidInput("Frame is already encoded".to_string()));
             }
@@ -151,128 +588,107 @@ impl VideoEncoderBackend for FFmpegEncoder {
             return Err(CodecError::InvalidInput("Cannot encode already-encoded frame".to_string()));
         }
 
-        // Lazy initialize encoder on first frame
-        if self.encoder.is_none() {
-            use ac_ffmpeg::codec::video::{VideoEncoder, frame::get_pixel_format};
-            use ac_ffmpeg::time::TimeBase;
-
-            let codec_name = match self.config.codec {
-                VideoCodec::Vp8 => "libvpx",
-                VideoCodec::H264 => "libx264",
-                VideoCodec::Av1 => "libaom-av1",
-            };
-
-            let pixel_format = get_pixel_format("yuv420p");
-            let time_base = TimeBase::new(1, self.config.framerate as i32);
-
-            let encoder = VideoEncoder::builder(codec_name)
-                .map_err(|e| CodecError::EncodingFailed(format!("Failed to create encoder builder: {}", e)))?
-                .pixel_format(pixel_format)
-                .width(width as usize)
-                .height(height as usize)
-                .time_base(time_base)
-                .bit_rate(self.config.bitrate as u64)
-                .build()
-                .map_err(|e| CodecError::EncodingFailed(format!("Failed to build encoder: {}", e)))?;
-
+        // (Re)open the encoder on first frame or when the input shape
+        // changes, keeping what the old one still held
+        if self.encoder.is_none() || self.input_shape != Some((width, height, format)) {
+            let drained = self.drain()?;
+            self.ready.extend(drained);
+            let (encoder, name) = self.open_encoder(width, height)?;
             self.encoder = Some(encoder);
+            self.encoder_name = Some(name);
+            self.input_shape = Some((width, height, format));
+            self.scaler = None;
+            self.pending.clear();
         }
 
-        let encoder = self.encoder.as_mut().unwrap();
+        let frame = self.prepare_frame(&pixel_data, width, height, format)?;
 
-        // Create frame and copy pixel data
-        use ac_ffmpeg::codec::{Encoder, video::{VideoFrameMut, frame::get_pixel_format}};
-        use ac_ffmpeg::time::{TimeBase, Timestamp};
-
-        let pixel_format = get_pixel_format("yuv420p");
         let time_base = TimeBase::new(1, self.config.framerate as i32);
-
-        let mut frame = VideoFrameMut::black(pixel_format, width as usize, height as usize);
-
-        // Copy pixel data to frame planes (YUV420P format)
-        let y_size = (width * height) as usize;
-        let uv_size = (width * height / 4) as usize;
-
-        if pixel_data.len() >= y_size + 2 * uv_size {
-            let mut planes_mut = frame.planes_mut();
-
-            // Y plane
-            if planes_mut.len() > 0 {
-                let y_plane = planes_mut[0].data_mut();
-                let copy_len = y_plane.len().min(y_size);
-                y_plane[..copy_len].copy_from_slice(&pixel_data[..copy_len]);
-            }
-
-            // U plane
-            if planes_mut.len() > 1 {
-                let u_plane = planes_mut[1].data_mut();
-                let copy_len = u_plane.len().min(uv_size);
-                u_plane[..copy_len].copy_from_slice(&pixel_data[y_size..y_size + copy_len]);
-            }
-
-            // V plane
-            if planes_mut.len() > 2 {
-                let v_plane = planes_mut[2].data_mut();
-                let copy_len = v_plane.len().min(uv_size);
-                v_plane[..copy_len].copy_from_slice(&pixel_data[y_size + uv_size..y_size + uv_size + copy_len]);
-            }
-        }
-
-        // Set timestamp
         let pts = Timestamp::new(self.frame_count as i64, time_base);
-        let frame = frame.with_time_base(time_base).with_pts(pts).freeze();
-
-        // Encode frame
-        encoder.push(frame)
+        let force_keyframe = std::mem::take(&mut self.force_keyframe) || wants_keyframe;
+        let picture_type = if force_keyframe { PictureType::I } else { PictureType::None };
+
+        let frame = frame
+            .with_time_base(time_base)
+            .with_pts(pts)
+            .with_picture_type(picture_type)
+            .freeze();
+
+        self.encoder
+            .as_mut()
+            .unwrap()
+            .push(frame)
             .map_err(|e| CodecError::EncodingFailed(format!("Failed to push frame to encoder: {}", e)))?;
 
-        // Collect encoded packets
-        let mut bitstream = Vec::new();
-        let mut is_keyframe = false;
+        self.pending.push_back((frame_number, timestamp_us));
+        self.frame_count += 1;
 
-        while let Some(packet) = encoder.take()
-            .map_err(|e| CodecError::EncodingFailed(format!("Failed to take packet from encoder: {}", e)))? {
-            bitstream.extend_from_slice(packet.data());
-            is_keyframe |= packet.is_key();
+        while let Some(packet) = self.take_packet(width, height)? {
+            self.ready.push_back(packet);
         }
 
-        self.frame_count += 1;
-
-        Ok(RuntimeData::Video {
-            pixel_data: bitstream,
+        // Encoders run with zero lookahead, so a packet is normally ready
+        // immediately. If one isn't, emit an empty encoded frame.
+        Ok(self.ready.pop_front().unwrap_or(RuntimeData::Video {
+            pixel_data: Vec::new(),
             width,
             height,
             format: PixelFormat::Encoded,
             codec: Some(self.config.codec),
             frame_number,
             timestamp_us,
-            is_keyframe,
+            is_keyframe: false,
             stream_id: None,
             arrival_ts_us: None,
-        })
+        }))
     }
 
     fn codec(&self) -> VideoCodec {
         self.config.codec
     }
 
-    fn reconfigure(&mut self, config: &VideoEncoderConfig) -> Result<()> {
+    fn reconfigure(&mut self, config: &VideoEncoderConfig) -> Result<Vec<RuntimeData>> {
+        if config.framerate == 0 {
+            return Err(CodecError::InvalidConfig("framerate must be > 0".to_string()));
+        }
+
+        // ac-ffmpeg has no runtime rate-control API; drain and close the
+        // encoder, and reopen it with the new settings on the next frame.
+        let flushed = self.flush()?;
         self.config = config.clone();
-        Ok(())
+        Ok(flushed)
+    }
+
+    fn request_keyframe(&mut self) {
+        self.force_keyframe = true;
+    }
+
+    fn flush(&mut self) -> Result<Vec<RuntimeData>> {
+        let drained = self.drain()?;
+        let mut frames: Vec<RuntimeData> = self.ready.drain(..).collect();
+        frames.extend(drained);
+        Ok(frames)
     }
 }
 
 /// FFmpeg decoder implementation
 ///
-/// Currently returns mock decoded frames. To enable actual decoding:
-/// 1. Use VideoDecoder::new() from ac-ffmpeg
-/// 2. Create Packet from bitstream (may need PacketMut)
-/// 3. Push packet, take frames
-/// 4. Copy frame planes to RuntimeData
+/// Opens a software decoder lazily on the first packet (and again if the
+/// stream switches codec). Output is converted to `output_format` with
+/// swscale when the decoder produces something else.
 #[cfg(feature = "video")]
 pub struct FFmpegDecoder {
     config: VideoDecoderConfig,
     decoder: Option<ac_ffmpeg::codec::video::VideoDecoder>,
+    /// Codec the open decoder handles
+    active_codec: Option<VideoCodec>,
+    /// Converts decoder output to the configured pixel format
+    scaler: Option<ac_ffmpeg::codec::video::VideoFrameScaler>,
+    /// Source (width, height, ffmpeg format name) the scaler was built for
+    scaler_source: Option<(usize, usize, &'static str)>,
+    /// Decoded frames not yet handed out
+    ready: VecDeque<RuntimeData>,
+    decoded_count: u64,
 }
 
 #[cfg(feature = "video")]
@@ -281,25 +697,152 @@ impl FFmpegDecoder {
         Ok(Self {
             config,
             decoder: None, // Lazy initialization on first frame
+            active_codec: None,
+            scaler: None,
+            scaler_source: None,
+            ready: VecDeque::new(),
+            decoded_count: 0,
+        })
+    }
+
+    /// Target pixel format for decoded frames
+    fn target_format(&self) -> PixelFormat {
+        match self.config.output_format {
+            PixelFormat::Encoded | PixelFormat::Unspecified => PixelFormat::Yuv420p,
+            other => other,
+        }
+    }
+
+    /// Open the first available software decoder for `codec`
+    fn open_decoder(&self, codec: VideoCodec) -> Result<ac_ffmpeg::codec::video::VideoDecoder> {
+        use ac_ffmpeg::codec::video::VideoDecoder;
+
+        let candidates = decoder_candidates(codec);
+        let mut last_error = None;
+        for &name in candidates {
+            let builder = match VideoDecoder::builder(name) {
+                Ok(builder) => builder,
+                Err(e) => {
+                    last_error = Some(e.to_string());
+                    continue;
+                }
+            };
+
+            let builder = if self.config.threads > 0 {
+                builder.set_option("threads", self.config.threads)
+            } else {
+                builder
+            };
+
+            match builder.build() {
+                Ok(decoder) => {
+                    tracing::debug!("Opened {} decoder for {:?}", name, codec);
+                    return Ok(decoder);
+                }
+                Err(e) => last_error = Some(format!("{}: {}", name, e)),
+            }
+        }
+
+        Err(CodecError::NotAvailable(format!(
+            "No software decoder available for {:?} (tried {}): {}",
+            codec,
+            candidates.join(", "),
+            last_error.unwrap_or_default()
+        )))
+    }
+
+    /// Convert a decoded frame to a packed `RuntimeData::Video`
+    fn convert_frame(&mut self, frame: ac_ffmpeg::codec::video::VideoFrame) -> Result<RuntimeData> {
+        use ac_ffmpeg::codec::video::{VideoFrameScaler, frame::{PictureType, get_pixel_format}};
+
+        let width = frame.width();
+        let height = frame.height();
+        let target = self.target_format();
+        let target_name = ffmpeg_pixel_format_name(target).unwrap_or("yuv420p");
+        let is_keyframe = frame.picture_type() == PictureType::I;
+        let timestamp_us = frame.pts().as_micros().unwrap_or(0).max(0) as u64;
+
+        let frame = if frame.pixel_format() == get_pixel_format(target_name) {
+            frame
+        } else {
+            let source_name = frame.pixel_format().name();
+            let source = (width, height, source_name);
+            if self.scaler_source != Some(source) {
+                let scaler = VideoFrameScaler::builder()
+                    .source_pixel_format(frame.pixel_format())
+                    .source_width(width)
+                    .source_height(height)
+                    .target_pixel_format(get_pixel_format(target_name))
+                    .target_width(width)
+                    .target_height(height)
+                    .build()
+                    .map_err(|e| CodecError::DecodingFailed(format!("Failed to create converter: {}", e)))?;
+                self.scaler = Some(scaler);
+                self.scaler_source = Some(source);
+            }
+            self.scaler
+                .as_mut()
+                .unwrap()
+                .scale(&frame)
+                .map_err(|e| CodecError::DecodingFailed(format!("Pixel format conversion failed: {}", e)))?
+        };
+
+        let layout = plane_layout(target, width, height);
+        let pixel_data = copy_from_frame(&frame, &layout);
+
+        let frame_number = self.decoded_count;
+        self.decoded_count += 1;
+
+        Ok(RuntimeData::Video {
+            pixel_data,
+            width: width as u32,
+            height: height as u32,
+            format: target,
+            codec: None,
+            frame_number,
+            timestamp_us,
+            is_keyframe,
+            stream_id: None,
+            arrival_ts_us: None,
         })
     }
+
+    /// Move every frame the decoder has ready into the output queue
+    fn drain_decoder(&mut self) -> Result<()> {
+        use ac_ffmpeg::codec::Decoder;
+
+        loop {
+            let frame = self.decoder
+                .as_mut()
+                .unwrap()
+                .take()
+                .map_err(|e| CodecError::DecodingFailed(format!("Failed to take frame from decoder: {}", e)))?;
+            match frame {
+                Some(frame) => {
+                    let data = self.convert_frame(frame)?;
+                    self.ready.push_back(data);
+                }
+                None => return Ok(()),
+            }
+        }
+    }
 }
 
 #[cfg(feature = "video")]
 impl VideoDecoderBackend for FFmpegDecoder {
     fn decode(&mut self, input: RuntimeData) -> Result<RuntimeData> {
+        use ac_ffmpeg::codec::Decoder;
+        use ac_ffmpeg::packet::PacketMut;
+        use ac_ffmpeg::time::Timestamp;
+
         // Extract encoded video frame
-        let (pixel_data, _width, _height, codec, frame_number, timestamp_us) = match input {
+        let (pixel_data, codec, timestamp_us) = match input {
             RuntimeData::Video {
                 pixel_data,
-                width,
-                height,
                 codec: Some(codec),
-                frame_number,
                 timestamp_us,
-                stream_id: _,
                 ..
-            } => (pixel_data, width, height, codec, frame_number, timestamp_us),
+            } => (pixel_data, codec, timestamp_us),
             RuntimeData::Video { codec: None, .. } => {
                 return Err(CodecError::InvalidInput("Frame is not encoded".to_string()));
             }
@@ -315,87 +858,35 @@ impl VideoDecoderBackend for FFmpegDecoder {
                     expected, codec
                 )));
             }
-            }
-
-        // Lazy initialize decoder on first frame
-        if self.decoder.is_none() {
-            use ac_ffmpeg::codec::video::VideoDecoder;
-
-            let codec_name = match codec {
-                VideoCodec::Vp8 => "vp8",
-                VideoCodec::H264 => "h264",
-                VideoCodec::Av1 => "av1",
-            };
-
-            let decoder = VideoDecoder::builder(codec_name)
-                .map_err(|e| CodecError::DecodingFailed(format!("Failed to create decoder builder: {}", e)))?
-                .build()
-                .map_err(|e| CodecError::DecodingFailed(format!("Failed to build decoder: {}", e)))?;
-
-            self.decoder = Some(decoder);
         }
 
-        let decoder = self.decoder.as_mut().unwrap();
-
-        // Create packet from bitstream
-        use ac_ffmpeg::codec::Decoder;
-        use ac_ffmpeg::packet::PacketMut;
-
-        let mut packet_mut = PacketMut::new(pixel_data.len());
-        packet_mut.data_mut().copy_from_slice(&pixel_data);
-        let packet = packet_mut.freeze();
-
-        // Decode packet
-        decoder.push(packet)
-            .map_err(|e| CodecError::DecodingFailed(format!("Failed to push packet to decoder: {}", e)))?;
-
-        // Take decoded frame
-        if let Some(frame) = decoder.take()
-            .map_err(|e| CodecError::DecodingFailed(format!("Failed to take frame from decoder: {}", e)))? {
-
-            // Copy frame planes to output buffer (YUV420P format)
-            let mut decoded_pixel_data = Vec::new();
-
-            let planes = frame.planes();
-            // Only access first 3 planes (Y, U, V for YUV420P)
-            // Planes index 3 may be uninitialized/invalid
-            for i in 0..3 {
-                if i < planes.len() {
-                    let plane_data = planes[i].data();
-                    if !plane_data.is_empty() {
-                        decoded_pixel_data.extend_from_slice(plane_data);
-                    }
-                }
-            }
+        // (Re)open the decoder on first packet or when the codec changes
+        if self.decoder.is_none() || self.active_codec != Some(codec) {
+            self.decoder = Some(self.open_decoder(codec)?);
+            self.active_codec = Some(codec);
+            self.ready.clear();
+        }
 
-            Ok(RuntimeData::Video {
-                pixel_data: decoded_pixel_data,
-                width: frame.width() as u32,
-                height: frame.height() as u32,
-                format: self.config.output_format,
-                codec: None,
-                frame_number,
-                timestamp_us,
-                is_keyframe: false,
-                stream_id: None,
-                arrival_ts_us: None,
-            })
-        } else {
-            // No frame available yet (decoder may need more packets)
-            // Return empty frame to indicate no output
-            Ok(RuntimeData::Video {
-                pixel_data: vec![],
-                width: 0,
-                height: 0,
-                format: PixelFormat::Unspecified,
-                codec: None,
-                frame_number: 0,
-                timestamp_us: 0,
-                is_keyframe: false,
-                stream_id: None,
-                arrival_ts_us: None,
-            })
+        // An encoder may legitimately emit nothing for a frame
+        if !pixel_data.is_empty() {
+            let mut packet = PacketMut::new(pixel_data.len());
+            packet.data_mut().copy_from_slice(&pixel_data);
+            let packet = packet
+                .with_pts(Timestamp::from_micros(timestamp_us as i64))
+                .freeze();
+
+            self.decoder
+                .as_mut()
+                .unwrap()
+                .push(packet)
+                .map_err(|e| CodecError::DecodingFailed(format!("Failed to push packet to decoder: {}", e)))?;
+
+            self.drain_decoder()?;
         }
+
+        // No frame available yet (decoder may need more packets):
+        // return an empty frame to indicate no output
+        Ok(self.ready.pop_front().unwrap_or_else(empty_frame))
     }
 
     fn codec(&self) -> VideoCodec {
@@ -403,6 +894,30 @@ impl VideoDecoderBackend for FFmpegDecoder {
     }
 
     fn output_format(&self) -> PixelFormat {
-        self.config.output_format
+        self.target_format()
+    }
+
+    fn flush(&mut self) -> Result<Vec<RuntimeData>> {
+        use ac_ffmpeg::codec::Decoder;
+
+        if self.decoder.is_none() {
+            return Ok(self.ready.drain(..).collect());
+        }
+
+        self.decoder
+            .as_mut()
+            .unwrap()
+            .flush()
+            .map_err(|e| CodecError::DecodingFailed(format!("Failed to flush decoder: {}", e)))?;
+        self.drain_decoder()?;
+
+        // A flushed decoder cannot accept more packets
+        self.decoder = None;
+        self.active_codec = None;
+        Ok(self.ready.drain(..).collect())
     }
 }
+
+#[cfg(test)]
+#[path = "codec_tests.rs"]
+mod tests;
@@ -0,0 +1,386 @@
+//! Round-trip tests for the FFmpeg codec backends
+//!
+//! Spec 012: Video Codec Support
+//!
+//! Tests that need a particular encoder first check that this FFmpeg
+//! build ships it, and skip themselves (with a note on stdout) if not.
+
+#[cfg(test)]
+mod tests {
+    use crate::data::video::{PixelFormat, VideoCodec};
+    use crate::data::RuntimeData;
+    use crate::nodes::video::codec::{
+        plane_layout, CodecError, FFmpegDecoder, FFmpegEncoder, VideoDecoderBackend,
+        VideoEncoderBackend,
+    };
+    use crate::nodes::video::{VideoDecoderConfig, VideoEncoderConfig};
+    use std::collections::HashMap;
+
+    const WIDTH: u32 = 320;
+    const HEIGHT: u32 = 240;
+    const FRAME_US: u64 = 33_333;
+
+    /// Moving diagonal gradient with a drifting chroma tint
+    fn test_frame(index: u64) -> RuntimeData {
+        let (w, h) = (WIDTH as usize, HEIGHT as usize);
+        let mut pixel_data = Vec::with_capacity(PixelFormat::Yuv420p.buffer_size(WIDTH, HEIGHT));
+        for y in 0..h {
+            for x in 0..w {
+                pixel_data.push(((x + y + index as usize * 4) % 256) as u8);
+            }
+        }
+        for plane in 0..2 {
+            for y in 0..h / 2 {
+                for x in 0..w / 2 {
+                    let v = 128 + ((x + y * plane) / 4 + index as usize) % 32;
+                    pixel_data.push(v as u8);
+                }
+            }
+        }
+
+        RuntimeData::Video {
+            pixel_data,
+            width: WIDTH,
+            height: HEIGHT,
+            format: PixelFormat::Yuv420p,
+            codec: None,
+            frame_number: index,
+            timestamp_us: index * FRAME_US,
+            is_keyframe: false,
+            stream_id: None,
+            arrival_ts_us: None,
+        }
+    }
+
+    fn luma(frame: &RuntimeData) -> &[u8] {
+        match frame {
+            RuntimeData::Video { pixel_data, .. } => &pixel_data[..(WIDTH * HEIGHT) as usize],
+            _ => panic!("Expected video frame"),
+        }
+    }
+
+    /// Peak signal-to-noise ratio over 8-bit samples, in dB
+    fn psnr(reference: &[u8], distorted: &[u8]) -> f64 {
+        assert_eq!(reference.len(), distorted.len());
+        let mse = reference
+            .iter()
+            .zip(distorted)
+            .map(|(a, b)| {
+                let d = *a as f64 - *b as f64;
+                d * d
+            })
+            .sum::<f64>()
+            / reference.len() as f64;
+        if mse == 0.0 {
+            f64::INFINITY
+        } else {
+            10.0 * (255.0 * 255.0 / mse).log10()
+        }
+    }
+
+    fn encoder_config(codec: VideoCodec, bitrate: u32) -> VideoEncoderConfig {
+        VideoEncoderConfig {
+            codec,
+            bitrate,
+            framerate: 30,
+            keyframe_interval: 300,
+            ..Default::default()
+        }
+    }
+
+    /// Whether this FFmpeg build can encode `codec`
+    fn encoder_available(codec: VideoCodec) -> bool {
+        let mut encoder = FFmpegEncoder::new(encoder_config(codec, 1_000_000)).unwrap();
+        match encoder.encode(test_frame(0)) {
+            Ok(_) => true,
+            Err(e) => {
+                println!("Skipping test: no {:?} encoder in this FFmpeg build ({})", codec, e);
+                false
+            }
+        }
+    }
+
+    fn encode(encoder: &mut FFmpegEncoder, frame: RuntimeData) -> RuntimeData {
+        encoder
+            .encode(frame)
+            .unwrap_or_else(|e| panic!("Encoding failed: {}", e))
+    }
+
+    /// Frame numbers of the non-empty packets, in output order
+    fn packet_numbers(packets: &[RuntimeData]) -> Vec<u64> {
+        packets
+            .iter()
+            .filter_map(|p| match p {
+                RuntimeData::Video { pixel_data, frame_number, .. } if !pixel_data.is_empty() => {
+                    Some(*frame_number)
+                }
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Encode and decode `frames` frames; returns the minimum luma PSNR
+    fn round_trip(codec: VideoCodec, frames: u64) -> f64 {
+        let mut encoder = FFmpegEncoder::new(encoder_config(codec, 1_000_000)).unwrap();
+        let mut decoder = FFmpegDecoder::new(VideoDecoderConfig {
+            expected_codec: Some(codec),
+            ..Default::default()
+        })
+        .unwrap();
+
+        let originals: HashMap<u64, RuntimeData> =
+            (0..frames).map(|i| (i * FRAME_US, test_frame(i))).collect();
+
+        let mut encoded = Vec::new();
+        for i in 0..frames {
+            encoded.push(encode(&mut encoder, test_frame(i)));
+        }
+        encoded.extend(encoder.flush().unwrap());
+
+        // One packet per frame, each carrying its own frame number
+        assert_eq!(packet_numbers(&encoded), (0..frames).collect::<Vec<_>>());
+
+        let mut decoded = Vec::new();
+        for packet in encoded {
+            match decoder.decode(packet) {
+                Ok(frame @ RuntimeData::Video { width: WIDTH, .. }) => decoded.push(frame),
+                Ok(_) => {}
+                Err(e) => panic!("Decoding failed: {}", e),
+            }
+        }
+        decoded.extend(decoder.flush().unwrap());
+
+        assert_eq!(decoded.len() as u64, frames, "every encoded frame should decode");
+
+        let mut min_psnr = f64::INFINITY;
+        for frame in &decoded {
+            let RuntimeData::Video { width, height, format, codec, timestamp_us, .. } = frame else {
+                unreachable!()
+            };
+            assert_eq!((*width, *height), (WIDTH, HEIGHT));
+            assert_eq!(*format, PixelFormat::Yuv420p);
+            assert!(codec.is_none());
+
+            let original = originals
+                .get(timestamp_us)
+                .unwrap_or_else(|| panic!("No source frame for pts {}", timestamp_us));
+            min_psnr = min_psnr.min(psnr(luma(original), luma(frame)));
+        }
+
+        min_psnr
+    }
+
+    #[test]
+    fn test_plane_layout() {
+        assert_eq!(
+            plane_layout(PixelFormat::Yuv420p, 320, 240),
+            vec![(320, 240), (160, 120), (160, 120)]
+        );
+        assert_eq!(plane_layout(PixelFormat::NV12, 320, 240), vec![(320, 240), (320, 120)]);
+        assert_eq!(plane_layout(PixelFormat::Rgb24, 320, 240), vec![(960, 240)]);
+        assert!(plane_layout(PixelFormat::Encoded, 320, 240).is_empty());
+
+        for format in [PixelFormat::Yuv420p, PixelFormat::NV12, PixelFormat::Rgb24, PixelFormat::Rgba32] {
+            let total: usize = plane_layout(format, 1280, 720).iter().map(|(r, n)| r * n).sum();
+            assert_eq!(total, format.buffer_size(1280, 720));
+        }
+    }
+
+    #[test]
+    fn test_psnr_helper() {
+        let a = vec![100u8; 64];
+        assert!(psnr(&a, &a).is_infinite());
+        let b = vec![101u8; 64];
+        assert!((psnr(&a, &b) - 48.13).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_vp8_round_trip_psnr() {
+        if !encoder_available(VideoCodec::Vp8) {
+            return;
+        }
+        let psnr = round_trip(VideoCodec::Vp8, 30);
+        assert!(psnr > 30.0, "VP8 round-trip PSNR too low: {:.2} dB", psnr);
+    }
+
+    #[test]
+    fn test_h264_round_trip_psnr() {
+        if !encoder_available(VideoCodec::H264) {
+            return;
+        }
+        let psnr = round_trip(VideoCodec::H264, 30);
+        assert!(psnr > 30.0, "H.264 round-trip PSNR too low: {:.2} dB", psnr);
+    }
+
+    #[test]
+    fn test_av1_round_trip_psnr() {
+        if !encoder_available(VideoCodec::Av1) {
+            return;
+        }
+        let psnr = round_trip(VideoCodec::Av1, 10);
+        assert!(psnr > 30.0, "AV1 round-trip PSNR too low: {:.2} dB", psnr);
+    }
+
+    #[test]
+    fn test_keyframe_requests() {
+        if !encoder_available(VideoCodec::Vp8) {
+            return;
+        }
+        let mut encoder = FFmpegEncoder::new(encoder_config(VideoCodec::Vp8, 1_000_000)).unwrap();
+
+        let mut keyframes = Vec::new();
+        for i in 0..12 {
+            if i == 5 {
+                encoder.request_keyframe();
+            }
+            let mut frame = test_frame(i);
+            if i == 9 {
+                if let RuntimeData::Video { is_keyframe, .. } = &mut frame {
+                    *is_keyframe = true;
+                }
+            }
+            let RuntimeData::Video { is_keyframe, .. } = encode(&mut encoder, frame) else {
+                unreachable!()
+            };
+            keyframes.push(is_keyframe);
+        }
+
+        assert!(keyframes[0], "first frame must be a keyframe");
+        assert!(keyframes[5], "requested keyframe was not produced");
+        assert!(keyframes[9], "in-band keyframe flag was ignored");
+        assert!(!keyframes[3] && !keyframes[7], "unexpected keyframe with interval 300");
+    }
+
+    #[test]
+    fn test_bitrate_reconfigure() {
+        if !encoder_available(VideoCodec::H264) {
+            return;
+        }
+        let mut encoder = FFmpegEncoder::new(encoder_config(VideoCodec::H264, 100_000)).unwrap();
+
+        fn encode_bytes(encoder: &mut FFmpegEncoder, start: u64) -> (usize, Vec<RuntimeData>) {
+            let mut total = 0;
+            let mut packets = Vec::new();
+            for i in start..start + 30 {
+                let packet = encode(encoder, test_frame(i));
+                match &packet {
+                    RuntimeData::Video { pixel_data, .. } => total += pixel_data.len(),
+                    _ => unreachable!(),
+                }
+                packets.push(packet);
+            }
+            (total, packets)
+        }
+
+        let (low, mut packets) = encode_bytes(&mut encoder, 0);
+
+        // Frames buffered under the old bitrate come back from reconfigure
+        let config = encoder_config(VideoCodec::H264, 4_000_000);
+        packets.extend(encoder.reconfigure(&config).unwrap());
+        assert_eq!(packet_numbers(&packets), (0..30).collect::<Vec<_>>());
+
+        let (high, _) = encode_bytes(&mut encoder, 30);
+
+        assert!(
+            high > low * 2,
+            "raising bitrate should grow the bitstream ({} -> {} bytes)",
+            low,
+            high
+        );
+    }
+
+    #[test]
+    fn test_resize_keeps_every_packet() {
+        if !encoder_available(VideoCodec::Vp8) {
+            return;
+        }
+        let mut encoder = FFmpegEncoder::new(encoder_config(VideoCodec::Vp8, 1_000_000)).unwrap();
+
+        let mut packets: Vec<RuntimeData> =
+            (0..5).map(|i| encode(&mut encoder, test_frame(i))).collect();
+        // Half size: the encoder is drained and reopened
+        let (w, h) = (WIDTH / 2, HEIGHT / 2);
+        for i in 5..10 {
+            let frame = RuntimeData::Video {
+                pixel_data: vec![128u8; PixelFormat::Yuv420p.buffer_size(w, h)],
+                width: w,
+                height: h,
+                format: PixelFormat::Yuv420p,
+                codec: None,
+                frame_number: i,
+                timestamp_us: i * FRAME_US,
+                is_keyframe: false,
+                stream_id: None,
+                arrival_ts_us: None,
+            };
+            packets.push(encode(&mut encoder, frame));
+        }
+        packets.extend(encoder.flush().unwrap());
+
+        assert_eq!(packet_numbers(&packets), (0..10).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_decoder_unspecified_output_format() {
+        let decoder = FFmpegDecoder::new(VideoDecoderConfig {
+            output_format: PixelFormat::Unspecified,
+            ..Default::default()
+        })
+        .unwrap();
+        assert_eq!(decoder.output_format(), PixelFormat::Yuv420p);
+
+        let decoder = FFmpegDecoder::new(VideoDecoderConfig {
+            output_format: PixelFormat::Rgb24,
+            ..Default::default()
+        })
+        .unwrap();
+        assert_eq!(decoder.output_format(), PixelFormat::Rgb24);
+    }
+
+    #[test]
+    fn test_decoder_output_format_conversion() {
+        if !encoder_available(VideoCodec::Vp8) {
+            return;
+        }
+        let mut encoder = FFmpegEncoder::new(encoder_config(VideoCodec::Vp8, 1_000_000)).unwrap();
+        let mut decoder = FFmpegDecoder::new(VideoDecoderConfig {
+            output_format: PixelFormat::Rgb24,
+            ..Default::default()
+        })
+        .unwrap();
+
+        let encoded = encode(&mut encoder, test_frame(0));
+        let mut frames = vec![decoder.decode(encoded).unwrap()];
+        frames.extend(decoder.flush().unwrap());
+
+        let decoded = frames
+            .into_iter()
+            .find(|f| matches!(f, RuntimeData::Video { width: WIDTH, .. }))
+            .expect("decoder produced no frame");
+        match decoded {
+            RuntimeData::Video { pixel_data, format, .. } => {
+                assert_eq!(format, PixelFormat::Rgb24);
+                assert_eq!(pixel_data.len(), PixelFormat::Rgb24.buffer_size(WIDTH, HEIGHT));
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn test_encoder_rejects_odd_dimensions() {
+        let mut encoder = FFmpegEncoder::new(encoder_config(VideoCodec::Vp8, 1_000_000)).unwrap();
+        let frame = RuntimeData::Video {
+            pixel_data: vec![0u8; 64],
+            width: 7,
+            height: 5,
+            format: PixelFormat::Yuv420p,
+            codec: None,
+            frame_number: 0,
+            timestamp_us: 0,
+            is_keyframe: false,
+            stream_id: None,
+            arrival_ts_us: None,
+        };
+        assert!(matches!(encoder.encode(frame), Err(CodecError::InvalidInput(_))));
+    }
+}
@@ -29,6 +29,8 @@ pub struct VideoDecoderConfig {
     pub output_format: PixelFormat,
 
     /// Enable hardware acceleration
+    ///
+    /// Currently ignored: the FFmpeg backend only opens software decoders.
     #[serde(alias = "hardwareAccel")]
     pub hardware_accel: bool,
 
@@ -102,6 +104,17 @@ impl VideoDecoderNode {
         .await
         .map_err(|e| CodecError::DecodingFailed(e.to_string()))?
     }
+
+    /// Drain frames still buffered in the decoder at end of stream
+    pub async fn flush(&self) -> Result<Vec<RuntimeData>, CodecError> {
+        let decoder = Arc::clone(&self.decoder);
+        tokio::task::spawn_blocking(move || {
+            let mut dec = decoder.lock().unwrap();
+            dec.flush()
+        })
+        .await
+        .map_err(|e| CodecError::DecodingFailed(e.to_string()))?
+    }
 }
 
 #[async_trait]
@@ -128,10 +128,12 @@ mod tests {
                 arrival_ts_us: None,
             };
 
-            // In strict mode, should return error (or success with mock decoder)
+            // The FFmpeg VP8 decoder rejects this packet outright: its
+            // 3-byte frame tag declares a partition far larger than the
+            // data. Strict mode surfaces that (or a missing decoder) as an
+            // error instead of dropping the frame.
             let result = decoder.process(corrupted_frame).await;
-            // Mock decoder returns success, real decoder would return error
-            // assert!(result.is_err());  // Enable when real FFmpeg integrated
+            assert!(result.is_err());
         }
     }
 
@@ -44,6 +44,8 @@ pub struct VideoEncoderConfig {
     pub quality_preset: String,
 
     /// Enable hardware acceleration (VAAPI on Linux, VideoToolbox on macOS, NVENC/QuickSync on Windows)
+    ///
+    /// Currently ignored: the FFmpeg backend only opens software encoders.
     #[serde(alias = "hardwareAccel")]
     pub hardware_accel: bool,
 
@@ -173,19 +175,42 @@ impl VideoEncoderNode {
     ///
     /// # Returns
     ///
-    /// * `Ok(())` - Configuration updated successfully
+    /// * `Ok(frames)` - Frames still buffered under the old configuration
     /// * `Err(CodecError)` - If reconfiguration fails
-    pub fn reconfigure(&mut self, new_config: VideoEncoderConfig) -> Result<(), CodecError> {
+    pub fn reconfigure(
+        &mut self,
+        new_config: VideoEncoderConfig,
+    ) -> Result<Vec<RuntimeData>, CodecError> {
         let mut enc = self.encoder.lock().unwrap();
-        enc.reconfigure(&new_config)?;
+        let flushed = enc.reconfigure(&new_config)?;
         self.config = new_config;
-        Ok(())
+        Ok(flushed)
     }
 
     /// Get the codec this encoder produces
     pub fn codec(&self) -> VideoCodec {
         self.config.codec
     }
+
+    /// Force the next encoded frame to be a keyframe
+    ///
+    /// Used to recover receivers after packet loss (e.g. an RTCP PLI/FIR).
+    /// Raw input frames with `is_keyframe: true` have the same effect.
+    pub fn request_keyframe(&self) {
+        self.encoder.lock().unwrap().request_keyframe();
+    }
+
+    /// Drain frames still buffered in the encoder at end of stream
+    pub async fn flush(&self) -> Result<Vec<RuntimeData>, CodecError> {
+        let encoder = Arc::clone(&self.encoder);
+
+        tokio::task::spawn_blocking(move || {
+            let mut enc = encoder.lock().unwrap();
+            enc.flush()
+        })
+        .await
+        .map_err(|e| CodecError::EncodingFailed(e.to_string()))?
+    }
 }
 
 #[async_trait]
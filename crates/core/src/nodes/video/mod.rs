@@ -10,6 +10,7 @@ pub mod encoder;
 pub mod decoder;
 pub mod scaler;
 pub mod format_converter;
+pub mod quality;
 
 // Re-export encoder and decoder nodes (T020-T027 complete)
 pub use encoder::{VideoEncoderConfig, VideoEncoderNode};
@@ -18,3 +19,6 @@ pub use decoder::{VideoDecoderConfig, VideoDecoderNode};
 // Phase 6: Video processing nodes (T084-T095)
 pub use scaler::{VideoScalerNode, VideoScalerConfig};
 pub use format_converter::{VideoFormatConverterNode, VideoFormatConverterConfig};
+
+// Picture-quality analysis (freeze, black frame, scene cut)
+pub use quality::{VideoQualityAnalyzer, VideoQualityConfig, VideoQualityEvent, VideoQualityNode};
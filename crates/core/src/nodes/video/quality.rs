@@ -0,0 +1,728 @@
+//! Video picture-quality analysis node
+//!
+//! Detects frozen pictures, black frames and scene cuts from picture content
+//! rather than timestamp cadence. Luma is sampled on a coarse grid; the mean
+//! absolute difference and the luma-histogram distance to the previous frame
+//! drive freeze and scene-cut detection. Encoded frames are decoded through
+//! [`VideoDecoderNode`] first, so the node can consume an ingest feed directly.
+//! Each `stream_id` is analysed, and decoded, on its own.
+
+use super::codec::{empty_frame, CodecError};
+use super::decoder::{VideoDecoderConfig, VideoDecoderNode};
+use crate::data::video::PixelFormat;
+use crate::data::RuntimeData;
+use crate::nodes::streaming_node::AsyncStreamingNode;
+use crate::Error;
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+/// Luma histogram bins used for the scene-cut score
+const HISTOGRAM_BINS: usize = 32;
+
+/// Picture-quality measurement result
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VideoQualityEvent {
+    /// Mean luma of the frame (0-255)
+    pub mean_luma: f64,
+    /// Fraction of sampled pixels at or below `black_luma_threshold`
+    pub black_ratio: f64,
+    /// Mean absolute luma difference to the previous frame (0-255)
+    pub frame_diff: Option<f64>,
+    /// Luma histogram distance to the previous frame (0-1)
+    pub scene_score: Option<f64>,
+    /// Picture unchanged for at least `freeze_min_duration_ms`
+    pub is_frozen: bool,
+    /// How long the picture has been unchanged in milliseconds
+    pub frozen_ms: f64,
+    /// Picture black for at least `black_min_duration_ms`
+    pub is_black: bool,
+    /// How long the picture has been black in milliseconds
+    pub black_ms: f64,
+    /// This frame starts a new shot
+    pub is_scene_cut: bool,
+    /// Health: 1.0 = picture OK, 0.0 = frozen or black
+    pub health: f32,
+    /// Frame width in pixels
+    pub width: u32,
+    /// Frame height in pixels
+    pub height: u32,
+    /// Stream identifier
+    pub stream_id: Option<String>,
+    /// Timestamp in microseconds
+    pub timestamp_us: Option<u64>,
+}
+
+/// Configuration for video quality analysis
+///
+/// Uses `#[serde(default)]` to allow partial config, and `#[serde(alias)]`
+/// to accept both snake_case and camelCase.
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
+#[serde(default)]
+pub struct VideoQualityConfig {
+    /// Width of the luma sampling grid (default: 64)
+    #[serde(alias = "analysisWidth")]
+    #[schemars(range(min = 1, max = 7680))]
+    pub analysis_width: u32,
+
+    /// Mean absolute luma difference (0-255) up to which consecutive frames
+    /// count as identical (default: 0.5)
+    #[serde(alias = "freezeDiffThreshold")]
+    pub freeze_diff_threshold: f64,
+
+    /// Unchanged picture required before a freeze is reported (default: 2000ms)
+    #[serde(alias = "freezeMinDurationMs")]
+    pub freeze_min_duration_ms: f64,
+
+    /// Luma (0-255) at or below which a pixel counts as black (default: 32)
+    #[serde(alias = "blackLumaThreshold")]
+    pub black_luma_threshold: u8,
+
+    /// Fraction of black pixels that makes a frame black (default: 0.98)
+    #[serde(alias = "blackPixelRatio")]
+    #[schemars(range(min = 0.0, max = 1.0))]
+    pub black_pixel_ratio: f64,
+
+    /// Black picture required before it is reported (default: 500ms)
+    #[serde(alias = "blackMinDurationMs")]
+    pub black_min_duration_ms: f64,
+
+    /// Histogram distance (0-1) to the previous frame that marks a scene cut
+    /// (default: 0.4)
+    #[serde(alias = "sceneCutThreshold")]
+    #[schemars(range(min = 0.0, max = 1.0))]
+    pub scene_cut_threshold: f64,
+
+    /// Minimum spacing between reported scene cuts (default: 500ms)
+    #[serde(alias = "sceneCutMinIntervalMs")]
+    pub scene_cut_min_interval_ms: f64,
+
+    /// Interval between reports while the picture state is unchanged
+    /// (default: 1000ms)
+    #[serde(alias = "emitIntervalMs")]
+    pub emit_interval_ms: f64,
+}
+
+impl Default for VideoQualityConfig {
+    fn default() -> Self {
+        Self {
+            analysis_width: 64,
+            freeze_diff_threshold: 0.5,
+            freeze_min_duration_ms: 2000.0,
+            black_luma_threshold: 32,
+            black_pixel_ratio: 0.98,
+            black_min_duration_ms: 500.0,
+            scene_cut_threshold: 0.4,
+            scene_cut_min_interval_ms: 500.0,
+            emit_interval_ms: 1000.0,
+        }
+    }
+}
+
+/// Luma sampled on a coarse grid of one frame
+#[derive(Debug, Clone)]
+struct LumaGrid {
+    width: usize,
+    height: usize,
+    samples: Vec<u8>,
+    /// Normalised luma histogram
+    histogram: [f64; HISTOGRAM_BINS],
+}
+
+impl LumaGrid {
+    /// Sample a raw frame; `None` for encoded, unknown or truncated frames
+    fn sample(
+        pixel_data: &[u8],
+        width: u32,
+        height: u32,
+        format: PixelFormat,
+        grid_width: u32,
+    ) -> Option<Self> {
+        let (w, h) = (width as usize, height as usize);
+        let bytes_per_pixel = match format {
+            // The Y plane comes first in every YUV layout we carry
+            PixelFormat::Yuv420p | PixelFormat::I420 | PixelFormat::NV12 => 1,
+            PixelFormat::Rgb24 => 3,
+            PixelFormat::Rgba32 => 4,
+            PixelFormat::Encoded | PixelFormat::Unspecified => return None,
+        };
+        if w == 0 || h == 0 || pixel_data.len() < w * h * bytes_per_pixel {
+            return None;
+        }
+
+        let step = (w / grid_width.max(1) as usize).max(1);
+        let mut samples = Vec::with_capacity(w.div_ceil(step) * h.div_ceil(step));
+        for y in (0..h).step_by(step) {
+            for x in (0..w).step_by(step) {
+                let i = (y * w + x) * bytes_per_pixel;
+                let luma = if bytes_per_pixel == 1 {
+                    pixel_data[i]
+                } else {
+                    // BT.601 luma from RGB
+                    let (r, g, b) = (
+                        pixel_data[i] as u32,
+                        pixel_data[i + 1] as u32,
+                        pixel_data[i + 2] as u32,
+                    );
+                    ((77 * r + 150 * g + 29 * b) >> 8) as u8
+                };
+                samples.push(luma);
+            }
+        }
+
+        let mut histogram = [0.0; HISTOGRAM_BINS];
+        let weight = 1.0 / samples.len() as f64;
+        for &s in &samples {
+            histogram[s as usize * HISTOGRAM_BINS / 256] += weight;
+        }
+
+        Some(Self {
+            width: w.div_ceil(step),
+            height: h.div_ceil(step),
+            samples,
+            histogram,
+        })
+    }
+
+    fn mean(&self) -> f64 {
+        self.samples.iter().map(|&s| s as f64).sum::<f64>() / self.samples.len() as f64
+    }
+
+    fn black_ratio(&self, threshold: u8) -> f64 {
+        self.samples.iter().filter(|&&s| s <= threshold).count() as f64 / self.samples.len() as f64
+    }
+
+    /// Mean absolute difference and histogram distance to `other`, `None`
+    /// if the frames were sampled on different grids
+    fn compare(&self, other: &LumaGrid) -> Option<(f64, f64)> {
+        if (self.width, self.height) != (other.width, other.height) {
+            return None;
+        }
+        let diff = self
+            .samples
+            .iter()
+            .zip(&other.samples)
+            .map(|(&a, &b)| (a as i32 - b as i32).unsigned_abs() as f64)
+            .sum::<f64>()
+            / self.samples.len() as f64;
+        let distance = 0.5
+            * self
+                .histogram
+                .iter()
+                .zip(&other.histogram)
+                .map(|(a, b)| (a - b).abs())
+                .sum::<f64>();
+        Some((diff, distance))
+    }
+}
+
+/// Frozen-picture, black-frame and scene-cut detection over raw frames
+///
+/// Durations are measured on frame timestamps. A report is produced when the
+/// frozen/black state changes, on a scene cut, and otherwise every
+/// `emit_interval_ms`.
+pub struct VideoQualityAnalyzer {
+    config: VideoQualityConfig,
+    /// Previous frame, its timestamp and whether it was black
+    previous: Option<(LumaGrid, u64, bool)>,
+    /// Timestamp of the first frame of the current unchanged run
+    static_since_us: Option<u64>,
+    /// Timestamp of the first frame of the current black run
+    black_since_us: Option<u64>,
+    last_cut_us: Option<u64>,
+    /// Timestamp and (frozen, black) state of the last report
+    last_report: Option<(u64, (bool, bool))>,
+}
+
+impl VideoQualityAnalyzer {
+    pub fn new(config: VideoQualityConfig) -> Self {
+        Self {
+            config,
+            previous: None,
+            static_since_us: None,
+            black_since_us: None,
+            last_cut_us: None,
+            last_report: None,
+        }
+    }
+
+    /// Analyse one raw frame; returns an event when one is due
+    ///
+    /// Frames without raw pixels are ignored.
+    pub fn push_frame(
+        &mut self,
+        pixel_data: &[u8],
+        width: u32,
+        height: u32,
+        format: PixelFormat,
+        timestamp_us: u64,
+    ) -> Option<VideoQualityEvent> {
+        let grid = LumaGrid::sample(
+            pixel_data,
+            width,
+            height,
+            format,
+            self.config.analysis_width,
+        )?;
+
+        if matches!(self.previous, Some((_, prev_ts, _)) if timestamp_us < prev_ts) {
+            // Timestamps went backwards (discontinuity): start over
+            self.reset();
+        }
+
+        let mean_luma = grid.mean();
+        let black_ratio = grid.black_ratio(self.config.black_luma_threshold);
+        let frame_is_black = black_ratio >= self.config.black_pixel_ratio;
+        let (comparison, prev_ts, prev_black) = match &self.previous {
+            Some((prev, prev_ts, prev_black)) => (grid.compare(prev), *prev_ts, *prev_black),
+            None => (None, timestamp_us, false),
+        };
+        let frame_diff = comparison.map(|(diff, _)| diff);
+        let scene_score = comparison.map(|(_, distance)| distance);
+        self.previous = Some((grid, timestamp_us, frame_is_black));
+
+        // Frozen: unchanged since the first frame of the run
+        if frame_diff.is_some_and(|d| d <= self.config.freeze_diff_threshold) {
+            self.static_since_us.get_or_insert(prev_ts);
+        } else {
+            self.static_since_us = None;
+        }
+        let frozen_ms = self
+            .static_since_us
+            .map(|since| (timestamp_us - since) as f64 / 1000.0)
+            .unwrap_or(0.0);
+
+        if frame_is_black {
+            self.black_since_us.get_or_insert(timestamp_us);
+        } else {
+            self.black_since_us = None;
+        }
+        let black_ms = self
+            .black_since_us
+            .map(|since| (timestamp_us - since) as f64 / 1000.0)
+            .unwrap_or(0.0);
+
+        // A black picture is also static; report it as black only
+        let is_black = frame_is_black && black_ms >= self.config.black_min_duration_ms;
+        let is_frozen = !frame_is_black && frozen_ms >= self.config.freeze_min_duration_ms;
+
+        // Cuts to or from black are fades, not shot changes
+        let is_scene_cut = scene_score.is_some_and(|s| s >= self.config.scene_cut_threshold)
+            && !frame_is_black
+            && !prev_black
+            && self.last_cut_us.is_none_or(|last| {
+                (timestamp_us - last) as f64 / 1000.0 >= self.config.scene_cut_min_interval_ms
+            });
+        if is_scene_cut {
+            self.last_cut_us = Some(timestamp_us);
+        }
+
+        let state = (is_frozen, is_black);
+        let due = match self.last_report {
+            None => true,
+            Some((at, last_state)) => {
+                last_state != state
+                    || timestamp_us.saturating_sub(at) as f64 / 1000.0
+                        >= self.config.emit_interval_ms
+            }
+        };
+        if !due && !is_scene_cut {
+            return None;
+        }
+        self.last_report = Some((timestamp_us, state));
+
+        Some(VideoQualityEvent {
+            mean_luma,
+            black_ratio,
+            frame_diff,
+            scene_score,
+            is_frozen,
+            frozen_ms,
+            is_black,
+            black_ms,
+            is_scene_cut,
+            health: if is_frozen || is_black { 0.0 } else { 1.0 },
+            width,
+            height,
+            stream_id: None,
+            timestamp_us: Some(timestamp_us),
+        })
+    }
+
+    /// Forget all previous frames
+    pub fn reset(&mut self) {
+        *self = Self::new(self.config.clone());
+    }
+}
+
+/// Analysis state of one stream
+struct StreamAnalysis {
+    analyzer: VideoQualityAnalyzer,
+    /// Decodes the stream's encoded frames before analysis
+    decoder: Arc<VideoDecoderNode>,
+}
+
+/// Node that reports frozen pictures, black frames and scene cuts
+///
+/// Accepts raw frames, or encoded frames which are decoded first. Non-video
+/// input passes through unchanged. Frames are grouped by `stream_id`, so
+/// interleaved streams neither mask nor fake each other's freezes.
+pub struct VideoQualityNode {
+    node_id: String,
+    config: VideoQualityConfig,
+    /// Per-stream analyzer and decoder, keyed by `stream_id`
+    streams: Mutex<HashMap<Option<String>, StreamAnalysis>>,
+}
+
+impl VideoQualityNode {
+    /// Create a new VideoQualityNode
+    pub fn new(node_id: String, config: VideoQualityConfig) -> Result<Self, CodecError> {
+        Ok(Self {
+            node_id,
+            config,
+            streams: Mutex::new(HashMap::new()),
+        })
+    }
+
+    /// Run `f` on the state of `stream_id`, creating it on first use
+    fn with_stream<T>(
+        &self,
+        stream_id: &Option<String>,
+        f: impl FnOnce(&mut StreamAnalysis) -> T,
+    ) -> Result<T, Error> {
+        let mut streams = self.streams.lock().unwrap();
+        if !streams.contains_key(stream_id) {
+            let decoder = VideoDecoderNode::new(VideoDecoderConfig {
+                output_format: PixelFormat::Yuv420p,
+                ..Default::default()
+            })
+            .map_err(|e| Error::Execution(format!("VideoQualityNode decoder: {}", e)))?;
+            streams.insert(
+                stream_id.clone(),
+                StreamAnalysis {
+                    analyzer: VideoQualityAnalyzer::new(self.config.clone()),
+                    decoder: Arc::new(decoder),
+                },
+            );
+        }
+        Ok(f(streams.get_mut(stream_id).unwrap()))
+    }
+
+    /// Analyse one input; `None` when there is nothing to report
+    async fn analyze(&self, input: RuntimeData) -> Result<Option<RuntimeData>, Error> {
+        let (stream_id, encoded) = match &input {
+            RuntimeData::Video {
+                stream_id, codec, ..
+            } => (stream_id.clone(), codec.is_some()),
+            _ => return Ok(Some(input)), // Pass through non-video data
+        };
+
+        let frame = if encoded {
+            let decoder = self.with_stream(&stream_id, |stream| stream.decoder.clone())?;
+            match decoder.decode_frame(input).await {
+                Ok(frame) => frame,
+                Err(e) => {
+                    tracing::debug!(
+                        "VideoQualityNode {}: skipping undecodable frame: {}",
+                        self.node_id,
+                        e
+                    );
+                    return Ok(None);
+                }
+            }
+        } else {
+            input
+        };
+        let RuntimeData::Video {
+            pixel_data,
+            width,
+            height,
+            format,
+            timestamp_us,
+            ..
+        } = frame
+        else {
+            return Ok(None);
+        };
+
+        let event = self.with_stream(&stream_id, |stream| {
+            stream
+                .analyzer
+                .push_frame(&pixel_data, width, height, format, timestamp_us)
+        })?;
+        let Some(mut event) = event else {
+            return Ok(None);
+        };
+        event.stream_id = stream_id;
+
+        if event.is_black || event.is_frozen {
+            tracing::debug!(
+                "VideoQualityNode {}: picture {} for {:.0}ms",
+                self.node_id,
+                if event.is_black { "black" } else { "frozen" },
+                if event.is_black {
+                    event.black_ms
+                } else {
+                    event.frozen_ms
+                }
+            );
+        }
+
+        // Output the quality event as JSON
+        let mut event_json = serde_json::to_value(&event).unwrap_or(Value::Null);
+        if let Value::Object(ref mut map) = event_json {
+            map.insert(
+                "_schema".to_string(),
+                Value::String("video_quality_event".to_string()),
+            );
+        }
+        Ok(Some(RuntimeData::Json(event_json)))
+    }
+}
+
+#[async_trait]
+impl AsyncStreamingNode for VideoQualityNode {
+    fn node_type(&self) -> &str {
+        "VideoQualityNode"
+    }
+
+    async fn process(&self, input: RuntimeData) -> Result<RuntimeData, Error> {
+        // Nothing to report: an empty frame, as the decoder emits
+        Ok(self.analyze(input).await?.unwrap_or_else(empty_frame))
+    }
+
+    async fn process_streaming<F>(
+        &self,
+        data: RuntimeData,
+        _session_id: Option<String>,
+        mut callback: F,
+    ) -> Result<usize, Error>
+    where
+        F: FnMut(RuntimeData) -> Result<(), Error> + Send,
+    {
+        match self.analyze(data).await? {
+            Some(output) => {
+                callback(output)?;
+                Ok(1)
+            }
+            None => Ok(0),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const WIDTH: u32 = 64;
+    const HEIGHT: u32 = 48;
+
+    /// YUV420P frame with a vertical luma ramp starting at `base`
+    fn yuv_frame(base: u8) -> Vec<u8> {
+        let mut data = vec![128u8; PixelFormat::Yuv420p.buffer_size(WIDTH, HEIGHT)];
+        for y in 0..HEIGHT as usize {
+            for x in 0..WIDTH as usize {
+                data[y * WIDTH as usize + x] = base.saturating_add(y as u8);
+            }
+        }
+        data
+    }
+
+    fn analyzer() -> VideoQualityAnalyzer {
+        VideoQualityAnalyzer::new(VideoQualityConfig {
+            analysis_width: 16,
+            emit_interval_ms: 1000.0,
+            ..Default::default()
+        })
+    }
+
+    /// Push `frames` copies at 25 fps from `start_us`; returns emitted events
+    fn push_run(
+        analyzer: &mut VideoQualityAnalyzer,
+        frame: &[u8],
+        start_us: u64,
+        frames: u64,
+    ) -> Vec<VideoQualityEvent> {
+        (0..frames)
+            .filter_map(|n| {
+                analyzer.push_frame(
+                    frame,
+                    WIDTH,
+                    HEIGHT,
+                    PixelFormat::Yuv420p,
+                    start_us + n * 40_000,
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_frozen_picture_after_min_duration() {
+        let mut analyzer = analyzer();
+        // 3 s of an unchanging picture
+        let events = push_run(&mut analyzer, &yuv_frame(60), 0, 75);
+
+        let first_frozen = events.iter().find(|e| e.is_frozen).unwrap();
+        assert!(first_frozen.frozen_ms >= 2000.0);
+        assert!(first_frozen.frozen_ms < 2100.0);
+        assert_eq!(first_frozen.health, 0.0);
+        assert!(!first_frozen.is_black);
+        // Throttled: not one event per frame
+        assert!(events.len() < 6, "{} events", events.len());
+
+        // Any change ends the freeze immediately
+        let event = analyzer
+            .push_frame(
+                &yuv_frame(90),
+                WIDTH,
+                HEIGHT,
+                PixelFormat::Yuv420p,
+                3_000_000,
+            )
+            .unwrap();
+        assert!(!event.is_frozen);
+        assert_eq!(event.frozen_ms, 0.0);
+    }
+
+    #[test]
+    fn test_black_frames_are_not_reported_as_frozen() {
+        let mut analyzer = analyzer();
+        let black = vec![16u8; PixelFormat::Yuv420p.buffer_size(WIDTH, HEIGHT)];
+        let events = push_run(&mut analyzer, &black, 0, 100);
+
+        assert!(events.iter().any(|e| e.is_black && e.black_ms >= 500.0));
+        assert!(events.iter().all(|e| !e.is_frozen));
+        let last = events.last().unwrap();
+        assert!(last.black_ratio > 0.99);
+        assert!(last.mean_luma < 20.0);
+    }
+
+    #[test]
+    fn test_scene_cut() {
+        let mut analyzer = analyzer();
+        push_run(&mut analyzer, &yuv_frame(40), 0, 10);
+        let event = analyzer
+            .push_frame(
+                &yuv_frame(180),
+                WIDTH,
+                HEIGHT,
+                PixelFormat::Yuv420p,
+                400_000,
+            )
+            .unwrap();
+        assert!(event.is_scene_cut);
+        assert!(event.scene_score.unwrap() > 0.9);
+
+        // A small change is not a cut
+        let next = analyzer.push_frame(
+            &yuv_frame(182),
+            WIDTH,
+            HEIGHT,
+            PixelFormat::Yuv420p,
+            440_000,
+        );
+        assert!(next.is_none_or(|e| !e.is_scene_cut));
+    }
+
+    #[test]
+    fn test_rgb_luma_and_unanalysable_frames() {
+        let mut analyzer = analyzer();
+        let white = vec![255u8; PixelFormat::Rgb24.buffer_size(WIDTH, HEIGHT)];
+        let event = analyzer
+            .push_frame(&white, WIDTH, HEIGHT, PixelFormat::Rgb24, 0)
+            .unwrap();
+        assert_eq!(event.mean_luma, 255.0);
+        assert_eq!(event.black_ratio, 0.0);
+
+        assert!(analyzer
+            .push_frame(&[], 1, 1, PixelFormat::Unspecified, 40_000)
+            .is_none());
+        assert!(analyzer
+            .push_frame(&white[..10], WIDTH, HEIGHT, PixelFormat::Rgb24, 40_000)
+            .is_none());
+    }
+
+    #[tokio::test]
+    async fn test_node_emits_schema_event_and_passes_audio_through() {
+        let node = VideoQualityNode::new("video_quality".to_string(), Default::default()).unwrap();
+        let frame = RuntimeData::Video {
+            pixel_data: yuv_frame(60),
+            width: WIDTH,
+            height: HEIGHT,
+            format: PixelFormat::Yuv420p,
+            codec: None,
+            frame_number: 0,
+            timestamp_us: 0,
+            is_keyframe: true,
+            stream_id: Some("program".to_string()),
+            arrival_ts_us: None,
+        };
+
+        let RuntimeData::Json(event) = node.process(frame).await.unwrap() else {
+            panic!("expected JSON event");
+        };
+        assert_eq!(event["_schema"], "video_quality_event");
+        assert_eq!(event["stream_id"], "program");
+        assert_eq!(event["is_frozen"], false);
+
+        let audio = RuntimeData::Audio {
+            samples: vec![0.0f32; 160].into(),
+            sample_rate: 16_000,
+            channels: 1,
+            stream_id: None,
+            timestamp_us: None,
+            arrival_ts_us: None,
+            metadata: None,
+        };
+        assert!(matches!(
+            node.process(audio).await.unwrap(),
+            RuntimeData::Audio { .. }
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_interleaved_streams_are_analysed_separately() {
+        let config = VideoQualityConfig {
+            analysis_width: 16,
+            emit_interval_ms: 1000.0,
+            ..Default::default()
+        };
+        let node = VideoQualityNode::new("video_quality".to_string(), config).unwrap();
+        let frame = |stream: &str, base: u8, n: u64| RuntimeData::Video {
+            pixel_data: yuv_frame(base),
+            width: WIDTH,
+            height: HEIGHT,
+            format: PixelFormat::Yuv420p,
+            codec: None,
+            frame_number: n,
+            timestamp_us: n * 40_000,
+            is_keyframe: true,
+            stream_id: Some(stream.to_string()),
+            arrival_ts_us: None,
+        };
+
+        // A static "frozen" feed interleaved with a constantly changing "live" one
+        let mut frozen_events = Vec::new();
+        let mut live_events = Vec::new();
+        for n in 0..75u64 {
+            if let Some(RuntimeData::Json(event)) =
+                node.analyze(frame("frozen", 60, n)).await.unwrap()
+            {
+                frozen_events.push(event);
+            }
+            let base = if n % 2 == 0 { 40 } else { 180 };
+            if let Some(RuntimeData::Json(event)) =
+                node.analyze(frame("live", base, n)).await.unwrap()
+            {
+                live_events.push(event);
+            }
+        }
+
+        assert!(frozen_events.iter().all(|e| e["stream_id"] == "frozen"));
+        assert!(frozen_events.iter().any(|e| e["is_frozen"] == true));
+        assert!(live_events.iter().all(|e| e["stream_id"] == "live"));
+        assert!(live_events.iter().all(|e| e["is_frozen"] == false));
+    }
+}
@@ -37,6 +37,15 @@ pub enum ProcessEvent {
         timestamp: Instant,
     },
 
+    /// Process was respawned under its node's restart policy
+    ProcessRestarted {
+        old_pid: u32,
+        pid: u32,
+        node_id: String,
+        restart_count: u32,
+        timestamp: Instant,
+    },
+
     /// Process failed to initialize
     InitializationFailed {
         pid: u32,
@@ -324,6 +333,43 @@ impl HealthMonitor {
         Ok(())
     }
 
+    /// Record that `old_pid` was replaced by `pid` after a crash
+    pub async fn handle_process_restart(
+        &self,
+        old_pid: u32,
+        pid: u32,
+        node_id: &str,
+        restart_count: u32,
+    ) {
+        tracing::info!(
+            "Process {} for node {} restarted as {} (restart #{})",
+            old_pid,
+            node_id,
+            pid,
+            restart_count
+        );
+
+        self.health_stats.write().await.insert(
+            pid,
+            ProcessHealthStats {
+                restart_count,
+                last_check: Some(Instant::now()),
+                is_responsive: true,
+                ..Default::default()
+            },
+        );
+
+        let _ = self.event_sender.send(ProcessEvent::ProcessRestarted {
+            old_pid,
+            pid,
+            node_id: node_id.to_string(),
+            restart_count,
+            timestamp: Instant::now(),
+        });
+
+        self.process_events().await;
+    }
+
     /// Register an event handler
     pub async fn on_event<F>(&self, handler: F)
     where
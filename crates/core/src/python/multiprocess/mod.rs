@@ -5,6 +5,7 @@ pub mod health_monitor;
 pub mod ipc_channel;
 pub mod multiprocess_executor;
 pub mod process_manager;
+pub mod supervisor;
 
 #[cfg(feature = "docker")]
 pub mod docker_support;
@@ -16,6 +17,7 @@ pub use data_transfer::DataType;
 pub use multiprocess_executor::{
     InitStatus, MultiprocessConfig, MultiprocessExecutor, SessionState,
 };
+pub use supervisor::{RestartMode, RestartPolicy};
 
 // Re-export env manager types for convenience
 pub use super::env_manager::{EnvScope, PythonEnvConfig, PythonEnvMode};
@@ -21,7 +21,12 @@ use super::health_monitor::{HealthMonitor, ProcessEvent};
 #[cfg(feature = "multiprocess")]
 use super::ipc_channel::{ChannelHandle, ChannelRegistry};
 #[cfg(feature = "multiprocess")]
-use super::process_manager::{ExitReason, ProcessHandle, ProcessManager};
+use super::process_manager::{ExitReason, ProcessHandle, ProcessManager, ProcessStatus};
+#[cfg(feature = "multiprocess")]
+use super::supervisor::publish_node_event;
+use super::supervisor::{RestartPolicy, SupervisedNode};
+#[cfg(feature = "multiprocess")]
+use crate::transport::session_control::NodeState;
 
 #[cfg(feature = "docker")]
 use super::docker_support::DockerSupport;
@@ -84,6 +89,9 @@ enum IpcCommand {
     RegisterOutputCallback {
         callback_tx: tokio::sync::mpsc::Sender<IPCRuntimeData>,
     },
+    /// Fail the in-flight request, if any: the node process died and will
+    /// not answer it
+    Abort { reason: String },
     /// Request graceful shutdown of the IPC thread
     Shutdown,
 }
@@ -97,6 +105,8 @@ enum IpcResponse {
     SendComplete,
     /// Error occurred during IPC operation
     Error(String),
+    /// The node process exited; outputs for the current input are lost
+    Aborted(String),
 }
 
 /// Handle to a node's dedicated IPC thread
@@ -158,6 +168,10 @@ pub struct MultiprocessConfig {
     /// Controls whether the SDK auto-provisions venvs via uv.
     #[serde(default)]
     pub python_env: crate::python::env_manager::PythonEnvConfig,
+
+    /// Restart policy for nodes whose manifest entry doesn't set one
+    #[serde(default)]
+    pub restart_policy: RestartPolicy,
 }
 
 /// Policy for handling Docker unavailability
@@ -211,6 +225,7 @@ impl Default for MultiprocessConfig {
             docker_fallback_policy: DockerFallbackPolicy::AllowWithWarning,
             python_path: Vec::new(),
             python_env: Default::default(),
+            restart_policy: RestartPolicy::default(),
         }
     }
 }
@@ -337,6 +352,9 @@ pub struct SessionState {
 
     /// Initialization progress for each node (node_id -> progress)
     pub init_progress: HashMap<String, InitProgress>,
+
+    /// Respawn specs for nodes that finished initializing (node_id -> spec)
+    pub(crate) supervised: HashMap<String, SupervisedNode>,
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -425,6 +443,45 @@ pub struct MultiprocessExecutor {
     control: Arc<std::sync::Mutex<Option<Arc<crate::transport::session_control::SessionControl>>>>,
 }
 
+/// A [`MultiprocessExecutor`] that holds its process manager weakly
+///
+/// The process manager owns its exit handlers, so a handler holding a
+/// full executor would keep the manager, and every process it tracks,
+/// alive forever.
+#[cfg(feature = "multiprocess")]
+#[derive(Clone)]
+struct WeakExecutor {
+    process_manager: std::sync::Weak<ProcessManager>,
+    channel_registry: Arc<ChannelRegistry>,
+    health_monitor: Arc<HealthMonitor>,
+    sessions: Arc<RwLock<HashMap<String, SessionState>>>,
+    config: MultiprocessConfig,
+    current_context: Option<ExecutorNodeContext>,
+    #[cfg(feature = "docker")]
+    docker_support: Option<Arc<DockerSupport>>,
+    env_manager: Option<Arc<crate::python::env_manager::PythonEnvManager>>,
+    control: Arc<std::sync::Mutex<Option<Arc<crate::transport::session_control::SessionControl>>>>,
+}
+
+#[cfg(feature = "multiprocess")]
+impl WeakExecutor {
+    /// The executor, unless its process manager has been dropped
+    fn upgrade(&self) -> Option<MultiprocessExecutor> {
+        Some(MultiprocessExecutor {
+            process_manager: self.process_manager.upgrade()?,
+            channel_registry: self.channel_registry.clone(),
+            health_monitor: self.health_monitor.clone(),
+            sessions: self.sessions.clone(),
+            config: self.config.clone(),
+            current_context: self.current_context.clone(),
+            #[cfg(feature = "docker")]
+            docker_support: self.docker_support.clone(),
+            env_manager: self.env_manager.clone(),
+            control: self.control.clone(),
+        })
+    }
+}
+
 impl MultiprocessExecutor {
     /// Process RuntimeData with streaming callback via IPC channels
     #[cfg(feature = "multiprocess")]
@@ -500,8 +557,12 @@ impl MultiprocessExecutor {
 
         // Wait for send acknowledgment
         let mut resp_rx = ipc_thread_resp_rx.lock().await;
+        // An `Aborted` queued before our acknowledgment belongs to an
+        // earlier input that was lost when the node restarted
+        let mut acked = false;
         match tokio::time::timeout(std::time::Duration::from_secs(5), resp_rx.recv()).await {
             Ok(Some(IpcResponse::SendComplete)) => {
+                acked = true;
                 tracing::debug!("Data sent successfully to node: {}", ctx.node_id);
             }
             Ok(Some(IpcResponse::Error(e))) => {
@@ -594,8 +655,18 @@ impl MultiprocessExecutor {
                 Some(IpcResponse::SendComplete) => {
                     tracing::debug!("[Multiprocess] Received SendComplete from node '{}', continuing to poll for outputs", ctx.node_id);
                     // Acknowledgment - ignore and continue polling for outputs
+                    acked = true;
                     continue;
                 }
+                Some(IpcResponse::Aborted(reason)) => {
+                    if !acked {
+                        continue;
+                    }
+                    return Err(Error::Execution(format!(
+                        "Node {} lost input: {}",
+                        ctx.node_id, reason
+                    )));
+                }
                 Some(IpcResponse::Error(e)) => {
                     tracing::error!(
                         "[Multiprocess] IPC error from node '{}': {}",
@@ -1387,18 +1458,22 @@ except Exception:
         let sessions = self.sessions.clone();
         let health_monitor = self.health_monitor.clone();
 
-        // Register process exit handler
+        // Register process exit handler. The process manager stores the
+        // handler, so the handler only holds the manager weakly.
         let process_manager = self.process_manager.clone();
-        let process_manager_for_handler = process_manager.clone();
         let sessions_for_handler = sessions.clone();
         let health_monitor_for_handler = health_monitor.clone();
+        let weak_supervisor = self.downgrade();
 
         tokio::spawn(async move {
             process_manager
                 .on_process_exit(move |pid, reason| {
+                    let Some(supervisor) = weak_supervisor.upgrade() else {
+                        return;
+                    };
                     let sessions = sessions_for_handler.clone();
                     let health_monitor = health_monitor_for_handler.clone();
-                    let process_manager_clone = process_manager_for_handler.clone();
+                    let process_manager_clone = supervisor.process_manager.clone();
 
                     tokio::spawn(async move {
                         // Find which session this process belongs to
@@ -1419,6 +1494,49 @@ except Exception:
                         };
 
                         if let Some((session_id, Some(node_id))) = session_info {
+                            // Consult the node's restart policy first
+                            let decision = sessions
+                                .write()
+                                .await
+                                .get_mut(&session_id)
+                                .and_then(|s| s.supervised.get_mut(&node_id))
+                                .map(|n| {
+                                    (n.on_exit(&reason), n.restarts, n.policy.applies_to(&reason))
+                                });
+                            match decision {
+                                Some((Some(delay), attempt, _)) => {
+                                    let _ = health_monitor
+                                        .handle_process_exit(pid, reason.clone(), None, None)
+                                        .await;
+                                    supervisor
+                                        .restart_node(
+                                            &session_id,
+                                            &node_id,
+                                            pid,
+                                            reason,
+                                            delay,
+                                            attempt,
+                                        )
+                                        .await;
+                                    return;
+                                }
+                                Some((None, restarts, true)) => {
+                                    tracing::error!(
+                                        "Node {} exceeded its restart limit after {} restarts",
+                                        node_id,
+                                        restarts
+                                    );
+                                    publish_node_event(
+                                        supervisor.get_control().as_deref(),
+                                        &node_id,
+                                        "failed",
+                                        restarts,
+                                        format!("gave up after {} restarts", restarts),
+                                    );
+                                }
+                                _ => {}
+                            }
+
                             // Handle process exit
                             let _ = health_monitor
                                 .handle_process_exit(
@@ -1526,6 +1644,212 @@ except Exception:
         Ok(())
     }
 
+    /// This executor with its process manager held weakly
+    #[cfg(feature = "multiprocess")]
+    fn downgrade(&self) -> WeakExecutor {
+        WeakExecutor {
+            process_manager: Arc::downgrade(&self.process_manager),
+            channel_registry: self.channel_registry.clone(),
+            health_monitor: self.health_monitor.clone(),
+            sessions: self.sessions.clone(),
+            config: self.config.clone(),
+            current_context: self.current_context.clone(),
+            #[cfg(feature = "docker")]
+            docker_support: self.docker_support.clone(),
+            env_manager: self.env_manager.clone(),
+            control: self.control.clone(),
+        }
+    }
+
+    /// Respawn a crashed node process under its restart policy
+    ///
+    /// The node is `Disabled` on the control bus while it is down, so the
+    /// router skips it rather than feeding a dead process. The node's IPC
+    /// thread and iceoryx2 channels are kept: they are addressed by name,
+    /// so the new process attaches to them the same way the first one did,
+    /// and any registered output callback keeps working. An input that was
+    /// in flight when the process died fails with an error instead of
+    /// waiting for outputs that will never come.
+    ///
+    /// If the new process dies or never signals READY, its exit goes
+    /// through the exit handler again and the policy decides whether to
+    /// try once more.
+    #[cfg(feature = "multiprocess")]
+    async fn restart_node(
+        &self,
+        session_id: &str,
+        node_id: &str,
+        old_pid: u32,
+        reason: ExitReason,
+        delay: std::time::Duration,
+        attempt: u32,
+    ) {
+        let control = self.get_control();
+        let prior_state = control.as_ref().map(|c| c.node_state(node_id));
+        if let Some(ctrl) = &control {
+            ctrl.set_node_state(node_id, NodeState::Disabled);
+        }
+        publish_node_event(
+            control.as_deref(),
+            node_id,
+            "restarting",
+            attempt,
+            format!(
+                "process {} exited ({:?}), restarting in {} ms",
+                old_pid,
+                reason,
+                delay.as_millis()
+            ),
+        );
+        tracing::warn!(
+            "Node {} (PID {}) exited with {:?}; restart #{} in {:?}",
+            node_id,
+            old_pid,
+            reason,
+            attempt,
+            delay
+        );
+
+        // Fail the input the dead process was working on
+        let command_tx = global_sessions()
+            .read()
+            .await
+            .get(session_id)
+            .and_then(|nodes| nodes.get(node_id).cloned());
+        if let Some(tx) = &command_tx {
+            let _ = tx
+                .send(IpcCommand::Abort {
+                    reason: format!("process exited ({:?})", reason),
+                })
+                .await;
+        }
+
+        tokio::time::sleep(delay).await;
+
+        // The session may have been torn down while we waited
+        let spec = self
+            .sessions
+            .read()
+            .await
+            .get(session_id)
+            .and_then(|s| s.supervised.get(node_id).cloned());
+        let Some(spec) = spec else {
+            return;
+        };
+
+        let process = match self
+            .process_manager
+            .spawn_node(&spec.node_type, node_id, &spec.params, session_id)
+            .await
+        {
+            Ok(process) => process,
+            Err(e) => {
+                tracing::error!("Failed to respawn node {}: {}", node_id, e);
+                publish_node_event(
+                    control.as_deref(),
+                    node_id,
+                    "failed",
+                    attempt,
+                    format!("respawn failed: {}", e),
+                );
+                if let Err(e) = Self::terminate_session_static(
+                    self.sessions.clone(),
+                    self.process_manager.clone(),
+                    session_id,
+                )
+                .await
+                {
+                    tracing::error!("Failed to terminate session {}: {}", session_id, e);
+                }
+                return;
+            }
+        };
+        let pid = process.id;
+        let status = process.status.clone();
+
+        // Register before waiting so a crash during init reaches the exit
+        // handler with this node's spec
+        {
+            let mut sessions = self.sessions.write().await;
+            match sessions.get_mut(session_id) {
+                Some(session) => {
+                    session
+                        .node_processes
+                        .insert(node_id.to_string(), process.clone());
+                }
+                None => {
+                    drop(sessions);
+                    let _ = self
+                        .process_manager
+                        .terminate_process(process, std::time::Duration::from_secs(5))
+                        .await;
+                    return;
+                }
+            }
+        }
+
+        let ready = self
+            .wait_for_ready_signal_ipc(
+                session_id,
+                node_id,
+                std::time::Duration::from_secs(self.config.init_timeout_secs),
+                Some(status.clone()),
+            )
+            .await
+            .unwrap_or(false);
+
+        if !ready {
+            if matches!(
+                *status.read().await,
+                ProcessStatus::Stopped | ProcessStatus::Error(_)
+            ) {
+                // Already exited; the exit handler owns the next step
+                return;
+            }
+            tracing::error!(
+                "Restarted node {} (PID {}) did not signal READY; killing it",
+                node_id,
+                pid
+            );
+            let _ = self
+                .process_manager
+                .terminate_process(process, std::time::Duration::from_secs(5))
+                .await;
+            return;
+        }
+
+        let total_restarts = {
+            let mut sessions = self.sessions.write().await;
+            let node = sessions
+                .get_mut(session_id)
+                .and_then(|s| s.supervised.get_mut(node_id));
+            match node {
+                Some(node) => {
+                    node.started();
+                    node.total_restarts
+                }
+                None => attempt,
+            }
+        };
+        self.health_monitor
+            .handle_process_restart(old_pid, pid, node_id, total_restarts)
+            .await;
+
+        if let Some(ctrl) = &control {
+            match prior_state {
+                Some(NodeState::Enabled) | None => ctrl.clear_node_state(node_id),
+                Some(state) => ctrl.set_node_state(node_id, state),
+            }
+        }
+        publish_node_event(
+            control.as_deref(),
+            node_id,
+            "running",
+            attempt,
+            format!("restarted as process {}", pid),
+        );
+    }
+
     /// Create a new session for pipeline execution
     pub async fn create_session(&self, session_id: String) -> Result<()> {
         let mut sessions = self.sessions.write().await;
@@ -1548,6 +1872,7 @@ except Exception:
             status: SessionStatus::Initializing,
             created_at: std::time::Instant::now(),
             init_progress: HashMap::new(),
+            supervised: HashMap::new(),
         };
 
         sessions.insert(session_id, session);
@@ -1741,6 +2066,7 @@ except Exception:
         session_id: &str,
         node_id: &str,
         timeout: std::time::Duration,
+        process_status: Option<Arc<RwLock<ProcessStatus>>>,
     ) -> Result<bool> {
         let start = std::time::Instant::now();
         let control_channel_name = format!("control/{}_{}", session_id, node_id);
@@ -1782,6 +2108,17 @@ except Exception:
                         "Control channel poll #{} - still waiting for READY",
                         poll_count
                     );
+
+                    // A process that died while loading will never signal
+                    let exited = process_status.as_ref().is_some_and(|status| {
+                        status.try_read().is_ok_and(|s| {
+                            matches!(*s, ProcessStatus::Stopped | ProcessStatus::Error(_))
+                        })
+                    });
+                    if exited {
+                        tracing::warn!("Node {} exited before signaling READY", node_id_clone);
+                        return Ok(false);
+                    }
                 }
 
                 // Try to receive READY signal (raw bytes, not RuntimeData)
@@ -1985,6 +2322,11 @@ except Exception:
                         );
                         output_callback = Some(callback_tx);
                     }
+                    Ok(IpcCommand::Abort { reason }) => {
+                        // try_send: nobody may be reading responses, and
+                        // the abort is moot if the queue is already full
+                        let _ = resp_tx.try_send(IpcResponse::Aborted(reason));
+                    }
                     Ok(IpcCommand::Shutdown) => {
                         tracing::debug!("IPC thread shutting down for node: {}", node_id_clone);
                         break;
@@ -2596,11 +2938,19 @@ impl ExecutorNodeExecutor for MultiprocessExecutor {
             // Python creates its input subscriber BEFORE sending READY, so when we receive
             // READY signal, Python is fully prepared to receive data
             // Wait for READY signal with configured timeout (allows time for heavy model loading)
+            let process_status = self
+                .sessions
+                .read()
+                .await
+                .get(&session_id)
+                .and_then(|s| s.node_processes.get(&ctx.node_id))
+                .map(|p| p.status.clone());
             let ready = self
                 .wait_for_ready_signal_ipc(
                     &session_id,
                     &ctx.node_id,
                     std::time::Duration::from_secs(self.config.init_timeout_secs),
+                    process_status,
                 )
                 .await?;
             if !ready {
@@ -2619,6 +2969,16 @@ impl ExecutorNodeExecutor for MultiprocessExecutor {
             // to complete the internal pub/sub connection registration
             tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
 
+            // From here on a crash is handled by the node's restart policy
+            let policy =
+                RestartPolicy::from_params(&ctx.params).unwrap_or(self.config.restart_policy);
+            if let Some(session) = self.sessions.write().await.get_mut(&session_id) {
+                session.supervised.insert(
+                    ctx.node_id.clone(),
+                    SupervisedNode::new(ctx.node_type.clone(), ctx.params.clone(), policy),
+                );
+            }
+
             tracing::debug!(
                 "✅ Node initialization complete, IPC thread ready with persistent publishers"
             );
@@ -2730,28 +3090,8 @@ impl NodesNodeExecutor for MultiprocessExecutor {
 mod tests {
     use super::*;
 
-    #[tokio::test]
-    async fn test_multiprocess_executor_creation() {
-        let config = MultiprocessConfig::default();
-        let executor = MultiprocessExecutor::new(config);
-
-        // Create a test session
-        executor
-            .create_session("test_session".to_string())
-            .await
-            .unwrap();
-
-        // Verify session exists
-        let sessions = executor.sessions.read().await;
-        assert!(sessions.contains_key("test_session"));
-
-        // Cleanup
-        drop(sessions);
-        executor.terminate_session("test_session").await.unwrap();
-    }
-
-    #[tokio::test]
-    async fn test_node_initialization() {
+    /// Config that spawns nodes from this checkout's Python client
+    fn python_test_config() -> MultiprocessConfig {
         // Use a shorter timeout for tests (30 seconds instead of 5 minutes)
         let mut config = MultiprocessConfig::default();
         config.init_timeout_secs = 30;
@@ -2773,8 +3113,32 @@ mod tests {
                 config.python_path.push(path);
             }
         }
+        config
+    }
 
-        let mut executor = MultiprocessExecutor::new(config);
+    #[tokio::test]
+    async fn test_multiprocess_executor_creation() {
+        let config = MultiprocessConfig::default();
+        let executor = MultiprocessExecutor::new(config);
+
+        // Create a test session
+        executor
+            .create_session("test_session".to_string())
+            .await
+            .unwrap();
+
+        // Verify session exists
+        let sessions = executor.sessions.read().await;
+        assert!(sessions.contains_key("test_session"));
+
+        // Cleanup
+        drop(sessions);
+        executor.terminate_session("test_session").await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_node_initialization() {
+        let mut executor = MultiprocessExecutor::new(python_test_config());
 
         // Register the test nodes module so test_processor is available
         executor
@@ -2798,6 +3162,72 @@ mod tests {
         ExecutorNodeExecutor::cleanup(&mut executor).await.unwrap();
     }
 
+    #[cfg(all(unix, feature = "multiprocess"))]
+    #[tokio::test]
+    async fn test_killed_node_is_restarted() {
+        use super::super::supervisor::RESTART_POLICY_PARAM;
+        use crate::data::RuntimeData;
+        use crate::transport::session_control::{ControlAddress, SessionControl};
+        use nix::sys::signal::{kill, Signal};
+        use nix::unistd::Pid;
+
+        let session_id = "restart_session";
+        let mut executor = MultiprocessExecutor::new(python_test_config());
+        let control = SessionControl::new(session_id);
+        executor.set_control(control.clone());
+        let mut events = control
+            .subscribe(&ControlAddress::node_out("__system__"))
+            .unwrap();
+        executor
+            .register_module("remotemedia.nodes.multiprocess_test_nodes")
+            .await;
+
+        let ctx = ExecutorNodeContext {
+            node_id: "test_node".to_string(),
+            node_type: "test_processor".to_string(),
+            params: serde_json::json!({
+                RESTART_POLICY_PARAM: { "policy": "on-failure", "initial_backoff_ms": 10 }
+            }),
+            session_id: Some(session_id.to_string()),
+            metadata: HashMap::new(),
+        };
+        ExecutorNodeExecutor::initialize(&mut executor, &ctx)
+            .await
+            .unwrap();
+
+        let pid = |executor: &MultiprocessExecutor| {
+            let sessions = executor.sessions.clone();
+            async move { sessions.read().await[session_id].node_processes["test_node"].id }
+        };
+        let old_pid = pid(&executor).await;
+        kill(Pid::from_raw(old_pid as i32), Signal::SIGKILL).unwrap();
+
+        // The supervisor reports `restarting`, then `running` once the new
+        // process signals READY
+        let mut states = Vec::new();
+        tokio::time::timeout(std::time::Duration::from_secs(60), async {
+            while states.last().map(String::as_str) != Some("running") {
+                if let RuntimeData::Json(event) = events.recv().await.unwrap() {
+                    if event["kind"] == "node_state" && event["node"] == "test_node" {
+                        states.push(event["state"].as_str().unwrap().to_string());
+                    }
+                }
+            }
+        })
+        .await
+        .expect("node was not restarted");
+        assert_eq!(states, ["restarting", "running"]);
+
+        let new_pid = pid(&executor).await;
+        assert_ne!(new_pid, old_pid);
+        assert_eq!(
+            executor.sessions.read().await[session_id].supervised["test_node"].total_restarts,
+            1
+        );
+
+        ExecutorNodeExecutor::cleanup(&mut executor).await.unwrap();
+    }
+
     #[tokio::test]
     async fn test_session_termination_with_cleanup() {
         let config = MultiprocessConfig::default();
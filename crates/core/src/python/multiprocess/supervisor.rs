@@ -0,0 +1,293 @@
+//! Supervision of Python node processes
+//!
+//! A [`RestartPolicy`] decides whether a node process that exited is
+//! respawned. It comes from the node's `restart` entry in the manifest
+//! (forwarded to the executor as the [`RESTART_POLICY_PARAM`] parameter)
+//! or from [`MultiprocessConfig::restart_policy`](super::MultiprocessConfig::restart_policy):
+//!
+//! ```yaml
+//! nodes:
+//!   - id: tts
+//!     node_type: KokoroTTSNode
+//!     restart:
+//!       policy: on-failure
+//!       max_restarts: 5
+//!       initial_backoff_ms: 500
+//! ```
+//!
+//! While a node restarts it is `Disabled` on the session's control bus, so
+//! the router skips it instead of queueing input for a dead process, and a
+//! `node_state` event is published on `__system__.out`.
+
+use super::process_manager::ExitReason;
+use crate::data::RuntimeData;
+use crate::transport::session_control::SessionControl;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::time::{Duration, Instant};
+
+/// Node parameter carrying the manifest's restart policy
+pub const RESTART_POLICY_PARAM: &str = "__restart_policy__";
+
+/// When a node process is restarted
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum RestartMode {
+    /// Never restart; a crash terminates the session (default)
+    #[default]
+    Never,
+    /// Restart after a crash (non-zero exit, signal, init timeout)
+    OnFailure,
+    /// Restart after any exit, including a clean one
+    Always,
+}
+
+/// Per-node restart policy
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RestartPolicy {
+    #[serde(rename = "policy", default)]
+    pub mode: RestartMode,
+
+    /// Consecutive restarts allowed before giving up; `None` is unlimited
+    #[serde(default = "default_max_restarts")]
+    pub max_restarts: Option<u32>,
+
+    /// Delay before the first restart; doubles on each consecutive one
+    #[serde(default = "default_initial_backoff_ms")]
+    pub initial_backoff_ms: u64,
+
+    /// Upper bound on the restart delay
+    #[serde(default = "default_max_backoff_ms")]
+    pub max_backoff_ms: u64,
+
+    /// A process that stays up this long resets the restart count
+    #[serde(default = "default_reset_after_secs")]
+    pub reset_after_secs: u64,
+}
+
+fn default_max_restarts() -> Option<u32> {
+    Some(5)
+}
+
+fn default_initial_backoff_ms() -> u64 {
+    500
+}
+
+fn default_max_backoff_ms() -> u64 {
+    30_000
+}
+
+fn default_reset_after_secs() -> u64 {
+    60
+}
+
+impl Default for RestartPolicy {
+    fn default() -> Self {
+        Self {
+            mode: RestartMode::Never,
+            max_restarts: default_max_restarts(),
+            initial_backoff_ms: default_initial_backoff_ms(),
+            max_backoff_ms: default_max_backoff_ms(),
+            reset_after_secs: default_reset_after_secs(),
+        }
+    }
+}
+
+impl RestartPolicy {
+    /// Policy with the default limits and backoff
+    pub fn new(mode: RestartMode) -> Self {
+        Self {
+            mode,
+            ..Default::default()
+        }
+    }
+
+    /// The policy in a node's parameters, if the manifest set one
+    pub fn from_params(params: &Value) -> Option<Self> {
+        let value = params.get(RESTART_POLICY_PARAM)?;
+        match serde_json::from_value(value.clone()) {
+            Ok(policy) => Some(policy),
+            Err(e) => {
+                tracing::warn!("Ignoring invalid restart policy {}: {}", value, e);
+                None
+            }
+        }
+    }
+
+    /// Whether `reason` warrants a restart under this policy, ignoring limits
+    pub fn applies_to(&self, reason: &ExitReason) -> bool {
+        match self.mode {
+            RestartMode::Never => false,
+            RestartMode::OnFailure => !matches!(reason, ExitReason::Normal),
+            RestartMode::Always => true,
+        }
+    }
+
+    /// Delay before restart number `attempt` (0-based)
+    pub fn backoff(&self, attempt: u32) -> Duration {
+        let factor = 1u64.checked_shl(attempt.min(63)).unwrap_or(u64::MAX);
+        Duration::from_millis(
+            self.initial_backoff_ms
+                .saturating_mul(factor)
+                .min(self.max_backoff_ms),
+        )
+    }
+}
+
+/// Everything needed to respawn one node, plus its restart history
+#[derive(Debug, Clone)]
+pub(crate) struct SupervisedNode {
+    pub node_type: String,
+    pub params: Value,
+    pub policy: RestartPolicy,
+    /// Consecutive restarts since the process last stayed up
+    pub restarts: u32,
+    /// Restarts over the node's lifetime
+    pub total_restarts: u32,
+    pub started_at: Instant,
+}
+
+impl SupervisedNode {
+    pub fn new(node_type: impl Into<String>, params: Value, policy: RestartPolicy) -> Self {
+        Self {
+            node_type: node_type.into(),
+            params,
+            policy,
+            restarts: 0,
+            total_restarts: 0,
+            started_at: Instant::now(),
+        }
+    }
+
+    /// Record an exit; returns the delay before respawning, or `None` if
+    /// the node should stay down
+    pub fn on_exit(&mut self, reason: &ExitReason) -> Option<Duration> {
+        self.on_exit_at(reason, Instant::now())
+    }
+
+    fn on_exit_at(&mut self, reason: &ExitReason, now: Instant) -> Option<Duration> {
+        if !self.policy.applies_to(reason) {
+            return None;
+        }
+        let uptime = now.saturating_duration_since(self.started_at);
+        if uptime >= Duration::from_secs(self.policy.reset_after_secs) {
+            self.restarts = 0;
+        }
+        if self
+            .policy
+            .max_restarts
+            .is_some_and(|max| self.restarts >= max)
+        {
+            return None;
+        }
+        let delay = self.policy.backoff(self.restarts);
+        self.restarts += 1;
+        self.total_restarts += 1;
+        Some(delay)
+    }
+
+    /// Mark a successful respawn
+    pub fn started(&mut self) {
+        self.started_at = Instant::now();
+    }
+}
+
+/// Publish a `node_state` event on `__system__.out`
+///
+/// `state` is one of `restarting`, `running` or `failed`.
+pub(crate) fn publish_node_event(
+    control: Option<&SessionControl>,
+    node_id: &str,
+    state: &str,
+    restarts: u32,
+    message: impl Into<String>,
+) {
+    let Some(ctrl) = control else {
+        return;
+    };
+    let ts = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0);
+    ctrl.publish_tap(
+        "__system__",
+        None,
+        RuntimeData::Json(serde_json::json!({
+            "kind": "node_state",
+            "node": node_id,
+            "state": state,
+            "restarts": restarts,
+            "message": message.into(),
+            "ts_ms": ts,
+        })),
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_policy_from_params() {
+        let params = serde_json::json!({
+            "voice": "af_heart",
+            RESTART_POLICY_PARAM: { "policy": "on-failure", "max_restarts": 2 },
+        });
+        let policy = RestartPolicy::from_params(&params).unwrap();
+        assert_eq!(policy.mode, RestartMode::OnFailure);
+        assert_eq!(policy.max_restarts, Some(2));
+        assert_eq!(policy.initial_backoff_ms, 500);
+
+        assert!(RestartPolicy::from_params(&serde_json::json!({})).is_none());
+        assert!(RestartPolicy::from_params(
+            &serde_json::json!({ RESTART_POLICY_PARAM: { "policy": "sometimes" } })
+        )
+        .is_none());
+    }
+
+    #[test]
+    fn test_modes() {
+        let on_failure = RestartPolicy::new(RestartMode::OnFailure);
+        assert!(on_failure.applies_to(&ExitReason::Error(1)));
+        assert!(on_failure.applies_to(&ExitReason::Killed));
+        assert!(!on_failure.applies_to(&ExitReason::Normal));
+
+        assert!(RestartPolicy::new(RestartMode::Always).applies_to(&ExitReason::Normal));
+        assert!(!RestartPolicy::default().applies_to(&ExitReason::Killed));
+    }
+
+    #[test]
+    fn test_backoff_and_limit() {
+        let policy = RestartPolicy {
+            max_restarts: Some(3),
+            initial_backoff_ms: 100,
+            max_backoff_ms: 250,
+            ..RestartPolicy::new(RestartMode::OnFailure)
+        };
+        let mut node = SupervisedNode::new("TTS", Value::Null, policy);
+        let start = node.started_at;
+        let crash = ExitReason::Killed;
+
+        assert_eq!(
+            node.on_exit_at(&crash, start),
+            Some(Duration::from_millis(100))
+        );
+        assert_eq!(
+            node.on_exit_at(&crash, start),
+            Some(Duration::from_millis(200))
+        );
+        assert_eq!(
+            node.on_exit_at(&crash, start),
+            Some(Duration::from_millis(250))
+        );
+        assert_eq!(node.on_exit_at(&crash, start), None);
+
+        // Staying up past `reset_after_secs` forgives earlier crashes
+        let later = start + Duration::from_secs(policy.reset_after_secs);
+        assert_eq!(
+            node.on_exit_at(&crash, later),
+            Some(Duration::from_millis(100))
+        );
+        assert_eq!(node.total_restarts, 4);
+    }
+}
@@ -0,0 +1,250 @@
+//! Distributed tracing across nodes and transports.
+//!
+//! [`RtProbeSet`](crate::metrics::RtProbeSet) and the perf aggregator answer
+//! "how slow is node X on average"; this module answers "where did *this*
+//! utterance spend its time". Every [`DataPacket`] carries an optional W3C
+//! [`TraceContext`], and the session router wraps each node dispatch in a
+//! `node.dispatch` span parented on the context of the packet it is
+//! processing. Outputs inherit the dispatch span's context, so a trace
+//! follows a packet from transport ingress through every node it touches.
+//!
+//! Transports accept an inbound `traceparent` (gRPC metadata, HTTP header,
+//! WebRTC signaling) and hand it to the session through
+//! [`TransportData::metadata`] under [`TRACEPARENT_KEY`]. Without one, each
+//! dispatch at a source node starts a new trace.
+//!
+//! Spans are plain `tracing` spans. Exporting them over OTLP needs the
+//! `otel` feature, which adds [`otlp::layer`] and links each span to its
+//! parent's OpenTelemetry context; without it the trace context is passed
+//! through untouched so downstream hops still see the caller's trace.
+//!
+//! [`DataPacket`]: crate::transport::session_router::DataPacket
+//! [`TransportData::metadata`]: crate::transport::TransportData::metadata
+
+#[cfg(feature = "otel")]
+pub mod otlp;
+
+use std::collections::HashMap;
+use std::fmt;
+
+/// Metadata / header key carrying a W3C trace context
+pub const TRACEPARENT_KEY: &str = "traceparent";
+
+/// `sampled` bit of the W3C `trace-flags` field
+const FLAG_SAMPLED: u8 = 0x01;
+
+/// A W3C trace context: the `traceparent` header, parsed
+///
+/// Only version `00` is produced; higher versions are accepted as long
+/// as the first four fields parse, per the spec's forward-compatibility
+/// rule. `tracestate` is not carried.
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct TraceContext {
+    pub trace_id: [u8; 16],
+    pub span_id: [u8; 8],
+    pub flags: u8,
+}
+
+impl TraceContext {
+    /// Parse a `traceparent` value (`00-<trace-id>-<span-id>-<flags>`)
+    ///
+    /// Returns `None` for malformed values and for the all-zero trace or
+    /// span ids the spec declares invalid.
+    pub fn parse(traceparent: &str) -> Option<Self> {
+        let mut parts = traceparent.trim().split('-');
+        let version = parts.next()?;
+        let trace_id = parts.next()?;
+        let span_id = parts.next()?;
+        let flags = parts.next()?;
+
+        if version.len() != 2 || version.eq_ignore_ascii_case("ff") {
+            return None;
+        }
+        u8::from_str_radix(version, 16).ok()?;
+        // Version 00 has exactly four fields; later versions may append more.
+        if version == "00" && parts.next().is_some() {
+            return None;
+        }
+
+        let ctx = Self {
+            trace_id: decode_hex(trace_id)?,
+            span_id: decode_hex(span_id)?,
+            flags: decode_hex::<1>(flags)?[0],
+        };
+        ctx.is_valid().then_some(ctx)
+    }
+
+    /// Read [`TRACEPARENT_KEY`] out of transport metadata
+    pub fn from_metadata(metadata: &HashMap<String, String>) -> Option<Self> {
+        metadata.get(TRACEPARENT_KEY).and_then(|v| Self::parse(v))
+    }
+
+    /// Format as a version `00` `traceparent` value
+    pub fn to_traceparent(&self) -> String {
+        self.to_string()
+    }
+
+    /// The trace id as 32 lowercase hex digits
+    pub fn trace_id_hex(&self) -> String {
+        encode_hex(&self.trace_id)
+    }
+
+    /// `false` for the all-zero trace or span id
+    pub fn is_valid(&self) -> bool {
+        self.trace_id != [0; 16] && self.span_id != [0; 8]
+    }
+
+    /// Whether the caller asked for this trace to be recorded
+    pub fn is_sampled(&self) -> bool {
+        self.flags & FLAG_SAMPLED != 0
+    }
+}
+
+impl fmt::Display for TraceContext {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "00-{}-{}-{:02x}",
+            self.trace_id_hex(),
+            encode_hex(&self.span_id),
+            self.flags
+        )
+    }
+}
+
+impl fmt::Debug for TraceContext {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "TraceContext({})", self)
+    }
+}
+
+fn decode_hex<const N: usize>(s: &str) -> Option<[u8; N]> {
+    // The spec only allows lowercase hex
+    if s.len() != N * 2 || !s.bytes().all(|b| matches!(b, b'0'..=b'9' | b'a'..=b'f')) {
+        return None;
+    }
+    let mut out = [0u8; N];
+    for (i, byte) in out.iter_mut().enumerate() {
+        *byte = u8::from_str_radix(&s[i * 2..i * 2 + 2], 16).ok()?;
+    }
+    Some(out)
+}
+
+fn encode_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+/// Open the span for one node dispatch
+///
+/// `parent` is the trace context of the packet being processed; its trace
+/// id is recorded on the span so plain logs can be correlated too. With
+/// the `otel` feature the span becomes its child in the exported trace.
+pub fn dispatch_span(
+    session_id: &str,
+    node_id: &str,
+    node_type: &str,
+    parent: Option<&TraceContext>,
+) -> tracing::Span {
+    let span = tracing::info_span!(
+        "node.dispatch",
+        otel.name = %format_args!("{} {}", node_type, node_id),
+        session_id = %session_id,
+        node_id = %node_id,
+        node_type = %node_type,
+        trace_id = tracing::field::Empty,
+        otel.status_code = tracing::field::Empty,
+        error = tracing::field::Empty,
+    );
+    if let Some(parent) = parent {
+        span.record("trace_id", parent.trace_id_hex().as_str());
+        #[cfg(feature = "otel")]
+        otlp::set_parent(&span, parent);
+    }
+    span
+}
+
+/// Mark a dispatch span as failed
+pub fn record_error(span: &tracing::Span, error: &dyn fmt::Display) {
+    span.record("otel.status_code", "ERROR");
+    span.record("error", tracing::field::display(error));
+}
+
+/// The trace context outputs of `span` should carry downstream
+///
+/// With the `otel` feature this is the span's own context, so the next
+/// hop becomes its child. Otherwise the incoming context is passed
+/// through unchanged.
+pub fn outgoing_context(
+    span: &tracing::Span,
+    incoming: Option<TraceContext>,
+) -> Option<TraceContext> {
+    #[cfg(feature = "otel")]
+    if let Some(ctx) = otlp::span_context(span) {
+        return Some(ctx);
+    }
+    #[cfg(not(feature = "otel"))]
+    let _ = span;
+    incoming
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01";
+
+    #[test]
+    fn test_traceparent_round_trip() {
+        let ctx = TraceContext::parse(EXAMPLE).unwrap();
+        assert_eq!(ctx.trace_id[0], 0x4b);
+        assert_eq!(ctx.span_id[7], 0xb7);
+        assert!(ctx.is_sampled());
+        assert_eq!(ctx.to_traceparent(), EXAMPLE);
+    }
+
+    #[test]
+    fn test_rejects_malformed_traceparent() {
+        for bad in [
+            "",
+            "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7",
+            "00-4BF92F3577B34DA6A3CE929D0E0E4736-00f067aa0ba902b7-01",
+            "00-00000000000000000000000000000000-00f067aa0ba902b7-01",
+            "00-4bf92f3577b34da6a3ce929d0e0e4736-0000000000000000-01",
+            "ff-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01",
+            "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01-extra",
+        ] {
+            assert!(TraceContext::parse(bad).is_none(), "{:?}", bad);
+        }
+    }
+
+    #[test]
+    fn test_future_version_keeps_known_fields() {
+        let ctx =
+            TraceContext::parse("01-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-00-extra")
+                .unwrap();
+        assert!(!ctx.is_sampled());
+        assert_eq!(
+            ctx.to_traceparent(),
+            "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-00"
+        );
+    }
+
+    #[test]
+    fn test_from_metadata() {
+        let mut metadata = HashMap::new();
+        assert!(TraceContext::from_metadata(&metadata).is_none());
+        metadata.insert(TRACEPARENT_KEY.to_string(), EXAMPLE.to_string());
+        assert_eq!(
+            TraceContext::from_metadata(&metadata),
+            TraceContext::parse(EXAMPLE)
+        );
+    }
+
+    #[cfg(not(feature = "otel"))]
+    #[test]
+    fn test_context_passes_through_without_otel() {
+        let ctx = TraceContext::parse(EXAMPLE);
+        let span = dispatch_span("s", "vad", "SileroVAD", ctx.as_ref());
+        assert_eq!(outgoing_context(&span, ctx), ctx);
+    }
+}
@@ -0,0 +1,117 @@
+//! OTLP export of pipeline spans (`otel` feature).
+//!
+//! [`layer`] builds a `tracing-subscriber` layer that turns `tracing`
+//! spans into OpenTelemetry spans and ships them with the OTLP/HTTP
+//! exporter. Configuration comes from the standard environment variables:
+//!
+//! | Variable | Effect |
+//! |---|---|
+//! | `OTEL_EXPORTER_OTLP_ENDPOINT` | Collector base URL. Export is disabled when unset. |
+//! | `OTEL_EXPORTER_OTLP_TRACES_ENDPOINT` | Full traces URL, overrides the above |
+//! | `OTEL_EXPORTER_OTLP_HEADERS` | Extra request headers (`k=v,k2=v2`) |
+//! | `OTEL_SERVICE_NAME` | Overrides the service name passed to [`layer`] |
+//!
+//! ```ignore
+//! use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
+//!
+//! let (otel, _guard) = remotemedia_core::telemetry::otlp::layer("remotemedia-grpc")?.unzip();
+//! tracing_subscriber::registry()
+//!     .with(tracing_subscriber::EnvFilter::from_default_env())
+//!     .with(tracing_subscriber::fmt::layer())
+//!     .with(otel)
+//!     .init();
+//! ```
+//!
+//! Keep the [`OtlpGuard`] alive for the life of the process; dropping it
+//! flushes buffered spans.
+
+use super::TraceContext;
+use crate::{Error, Result};
+use opentelemetry::trace::{
+    SpanContext, SpanId, TraceContextExt, TraceFlags, TraceId, TraceState, TracerProvider as _,
+};
+use opentelemetry_sdk::trace::{SdkTracer, SdkTracerProvider};
+use opentelemetry_sdk::Resource;
+use tracing_opentelemetry::{OpenTelemetryLayer, OpenTelemetrySpanExt};
+use tracing_subscriber::registry::LookupSpan;
+
+/// Instrumentation scope name on exported spans
+const TRACER_NAME: &str = "remotemedia";
+
+/// Flushes and shuts down the tracer provider when dropped
+pub struct OtlpGuard {
+    provider: SdkTracerProvider,
+}
+
+impl Drop for OtlpGuard {
+    fn drop(&mut self) {
+        if let Err(e) = self.provider.shutdown() {
+            eprintln!("otlp: tracer provider shutdown failed: {}", e);
+        }
+    }
+}
+
+/// Build the OTLP export layer, or `None` if no collector is configured
+///
+/// `service_name` is used unless `OTEL_SERVICE_NAME` is set.
+pub fn layer<S>(service_name: &str) -> Result<Option<(OpenTelemetryLayer<S, SdkTracer>, OtlpGuard)>>
+where
+    S: tracing::Subscriber + for<'span> LookupSpan<'span>,
+{
+    let configured = [
+        "OTEL_EXPORTER_OTLP_ENDPOINT",
+        "OTEL_EXPORTER_OTLP_TRACES_ENDPOINT",
+    ]
+    .iter()
+    .any(|var| std::env::var_os(var).is_some_and(|v| !v.is_empty()));
+    if !configured {
+        return Ok(None);
+    }
+
+    let exporter = opentelemetry_otlp::SpanExporter::builder()
+        .with_http()
+        .build()
+        .map_err(|e| Error::Execution(format!("otlp: failed to build span exporter: {}", e)))?;
+
+    let resource = if std::env::var_os("OTEL_SERVICE_NAME").is_some() {
+        Resource::builder().build()
+    } else {
+        Resource::builder()
+            .with_service_name(service_name.to_string())
+            .build()
+    };
+
+    let provider = SdkTracerProvider::builder()
+        .with_batch_exporter(exporter)
+        .with_resource(resource)
+        .build();
+    let tracer = provider.tracer(TRACER_NAME);
+
+    Ok(Some((
+        tracing_opentelemetry::layer().with_tracer(tracer),
+        OtlpGuard { provider },
+    )))
+}
+
+/// Parent `span` on a remote (upstream) trace context
+pub(crate) fn set_parent(span: &tracing::Span, parent: &TraceContext) {
+    let remote = SpanContext::new(
+        TraceId::from_bytes(parent.trace_id),
+        SpanId::from_bytes(parent.span_id),
+        TraceFlags::new(parent.flags),
+        true,
+        TraceState::default(),
+    );
+    let _ = span.set_parent(opentelemetry::Context::new().with_remote_span_context(remote));
+}
+
+/// `span`'s own context, if it is being exported
+pub(crate) fn span_context(span: &tracing::Span) -> Option<TraceContext> {
+    let cx = span.context();
+    let sc = cx.span().span_context().clone();
+    sc.is_valid().then(|| TraceContext {
+        trace_id: sc.trace_id().to_bytes(),
+        span_id: sc.span_id().to_bytes(),
+        flags: sc.trace_flags().to_u8(),
+    })
+}
@@ -0,0 +1,644 @@
+//! Executor-wide admission control
+//!
+//! Every session created through [`PipelineExecutor`] passes through an
+//! [`AdmissionController`] before any node is built. The controller
+//! estimates the manifest's cost and checks it against the configured
+//! quotas:
+//!
+//! - **Concurrent sessions**, globally and per tenant.
+//! - **Sessions per node type** — e.g. at most two sessions running
+//!   `WhisperNode` at once. A session counts once per type no matter how
+//!   many nodes of that type it contains.
+//! - **Resource budgets** — CPU cores, memory and GPU memory, summed over
+//!   the nodes of every admitted session.
+//!
+//! A node's cost comes from its declared [`CapabilityRequirements`]
+//! (`resources` in v2 manifests, `capabilities` in v1). Nodes that declare
+//! nothing fall back to [`AdmissionConfig::node_costs`] for their type, and
+//! cost nothing otherwise. A node's fallback is not counted until it is
+//! switched in.
+//!
+//! Admission returns an [`AdmissionPermit`] that holds the session's share
+//! until it is dropped. The share follows the session's graph: a live
+//! patch or a fallback switch resizes the permit before the nodes it
+//! brings in are created, and is refused like a new session when the
+//! growth does not fit. A refused session or patch gets
+//! [`Error::AdmissionRejected`] naming the exhausted quota and a retry
+//! hint, which each transport maps onto its own status (HTTP 429, gRPC
+//! `RESOURCE_LIMIT`, JSON-RPC `SESSION_LIMIT_EXCEEDED`, ...).
+//!
+//! ```json
+//! {
+//!   "max_sessions": 64,
+//!   "max_sessions_per_tenant": 8,
+//!   "node_type_limits": { "WhisperNode": 2 },
+//!   "max_gpu_memory_gb": 22.0,
+//!   "node_costs": { "WhisperNode": { "gpu_memory_gb": 6.0 } },
+//!   "retry_after_ms": 5000
+//! }
+//! ```
+//!
+//! [`PipelineExecutor`]: crate::transport::PipelineExecutor
+//! [`CapabilityRequirements`]: crate::manifest::CapabilityRequirements
+
+use crate::manifest::{CapabilityRequirements, Manifest};
+use crate::{Error, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, HashMap};
+use std::sync::{Arc, Mutex};
+
+/// Tenant that sessions created without one are counted under
+pub const DEFAULT_TENANT: &str = "default";
+
+/// Quotas enforced by an [`AdmissionController`]
+///
+/// Every limit is optional; the default admits everything.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AdmissionConfig {
+    /// Maximum concurrent sessions across all tenants
+    pub max_sessions: Option<usize>,
+    /// Maximum concurrent sessions for any one tenant
+    pub max_sessions_per_tenant: Option<usize>,
+    /// Maximum concurrent sessions containing a given node type
+    pub node_type_limits: HashMap<String, usize>,
+    /// CPU core budget shared by all sessions
+    pub max_cpu_cores: Option<f64>,
+    /// Memory budget (GB) shared by all sessions
+    pub max_memory_gb: Option<f64>,
+    /// GPU memory budget (GB) shared by all sessions
+    pub max_gpu_memory_gb: Option<f64>,
+    /// Cost of a node type whose nodes declare no requirements
+    pub node_costs: HashMap<String, ResourceCost>,
+    /// Retry hint returned with every rejection
+    pub retry_after_ms: u64,
+}
+
+impl Default for AdmissionConfig {
+    fn default() -> Self {
+        Self {
+            max_sessions: None,
+            max_sessions_per_tenant: None,
+            node_type_limits: HashMap::new(),
+            max_cpu_cores: None,
+            max_memory_gb: None,
+            max_gpu_memory_gb: None,
+            node_costs: HashMap::new(),
+            retry_after_ms: 5_000,
+        }
+    }
+}
+
+/// Resources held by a node or session
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ResourceCost {
+    pub cpu_cores: f64,
+    pub memory_gb: f64,
+    pub gpu_memory_gb: f64,
+}
+
+impl ResourceCost {
+    /// Cost declared by a node's requirements
+    pub fn from_requirements(req: &CapabilityRequirements) -> Self {
+        Self {
+            cpu_cores: req.cpu.as_ref().and_then(|c| c.cores).unwrap_or(0) as f64,
+            memory_gb: req.memory_gb.unwrap_or(0.0),
+            gpu_memory_gb: req
+                .gpu
+                .as_ref()
+                .and_then(|g| g.min_memory_gb)
+                .unwrap_or(0.0),
+        }
+    }
+
+    fn add(&mut self, other: &ResourceCost) {
+        self.cpu_cores += other.cpu_cores;
+        self.memory_gb += other.memory_gb;
+        self.gpu_memory_gb += other.gpu_memory_gb;
+    }
+
+    fn sub(&mut self, other: &ResourceCost) {
+        // Clamp so float error never leaves a budget slightly negative
+        self.cpu_cores = (self.cpu_cores - other.cpu_cores).max(0.0);
+        self.memory_gb = (self.memory_gb - other.memory_gb).max(0.0);
+        self.gpu_memory_gb = (self.gpu_memory_gb - other.gpu_memory_gb).max(0.0);
+    }
+}
+
+/// Estimated footprint of one session
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SessionCost {
+    /// Summed over every running node in the manifest
+    pub resources: ResourceCost,
+    /// Distinct node types the running nodes use
+    pub node_types: BTreeSet<String>,
+}
+
+impl SessionCost {
+    /// Estimate a manifest's cost under `config`
+    ///
+    /// Standby nodes, which only serve as another node's fallback, are not
+    /// counted.
+    pub fn estimate(manifest: &Manifest, config: &AdmissionConfig) -> Self {
+        Self::estimate_running(manifest, config, &HashMap::new())
+    }
+
+    /// [`Self::estimate`] with some nodes running as their fallback
+    ///
+    /// `running` maps a node ID to the ID of the manifest entry it runs as.
+    pub fn estimate_running(
+        manifest: &Manifest,
+        config: &AdmissionConfig,
+        running: &HashMap<String, String>,
+    ) -> Self {
+        let standby = manifest.standby_nodes().unwrap_or_default();
+        let mut cost = Self::default();
+        for node in &manifest.nodes {
+            if standby.contains(node.id.as_str()) {
+                continue;
+            }
+            let node = running
+                .get(&node.id)
+                .and_then(|id| manifest.nodes.iter().find(|n| &n.id == id))
+                .unwrap_or(node);
+            let node_cost = match node.resource_requirements() {
+                Some(req) => ResourceCost::from_requirements(req),
+                None => config
+                    .node_costs
+                    .get(&node.node_type)
+                    .copied()
+                    .unwrap_or_default(),
+            };
+            cost.resources.add(&node_cost);
+            cost.node_types.insert(node.node_type.clone());
+        }
+        cost
+    }
+}
+
+/// Point-in-time view of what admitted sessions hold
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct AdmissionUsage {
+    pub sessions: usize,
+    pub per_tenant: HashMap<String, usize>,
+    pub per_node_type: HashMap<String, usize>,
+    pub resources: ResourceCost,
+}
+
+/// Enforces an [`AdmissionConfig`] across concurrent sessions
+pub struct AdmissionController {
+    config: Arc<AdmissionConfig>,
+    usage: Arc<Mutex<AdmissionUsage>>,
+}
+
+impl AdmissionController {
+    pub fn new(config: AdmissionConfig) -> Self {
+        Self {
+            config: Arc::new(config),
+            usage: Arc::new(Mutex::new(AdmissionUsage::default())),
+        }
+    }
+
+    pub fn config(&self) -> &AdmissionConfig {
+        &self.config
+    }
+
+    /// Current usage
+    pub fn usage(&self) -> AdmissionUsage {
+        self.usage.lock().unwrap_or_else(|e| e.into_inner()).clone()
+    }
+
+    /// Admit a session running `manifest` for `tenant`
+    ///
+    /// Sessions without a tenant are counted under [`DEFAULT_TENANT`].
+    /// The returned permit must be kept alive for as long as the session
+    /// runs.
+    pub fn admit(&self, tenant: Option<&str>, manifest: &Manifest) -> Result<AdmissionPermit> {
+        let tenant = tenant.unwrap_or(DEFAULT_TENANT).to_string();
+        let cost = SessionCost::estimate(manifest, &self.config);
+
+        let mut usage = self.usage.lock().unwrap_or_else(|e| e.into_inner());
+        self.check(&usage, &tenant, &cost)?;
+
+        usage.sessions += 1;
+        *usage.per_tenant.entry(tenant.clone()).or_default() += 1;
+        charge(&mut usage, &SessionCost::default(), &cost);
+        drop(usage);
+
+        Ok(AdmissionPermit {
+            config: self.config.clone(),
+            usage: self.usage.clone(),
+            tenant,
+            cost,
+        })
+    }
+
+    fn check(&self, usage: &AdmissionUsage, tenant: &str, cost: &SessionCost) -> Result<()> {
+        let config = &self.config;
+
+        if let Some(max) = config.max_sessions {
+            if usage.sessions >= max {
+                return Err(reject(
+                    config,
+                    "sessions".to_string(),
+                    format!(
+                        "{} concurrent sessions already running (limit {})",
+                        usage.sessions, max
+                    ),
+                ));
+            }
+        }
+
+        if let Some(max) = config.max_sessions_per_tenant {
+            let running = usage.per_tenant.get(tenant).copied().unwrap_or(0);
+            if running >= max {
+                return Err(reject(
+                    config,
+                    format!("tenant:{}", tenant),
+                    format!(
+                        "tenant '{}' already has {} concurrent sessions (limit {})",
+                        tenant, running, max
+                    ),
+                ));
+            }
+        }
+
+        check_growth(config, usage, &SessionCost::default(), cost)
+    }
+}
+
+impl Default for AdmissionController {
+    fn default() -> Self {
+        Self::new(AdmissionConfig::default())
+    }
+}
+
+/// Check that a session's footprint can grow from `held` to `cost`
+///
+/// Only what `held` does not already cover is checked: node types the
+/// session does not use yet and resources beyond what it holds.
+fn check_growth(
+    config: &AdmissionConfig,
+    usage: &AdmissionUsage,
+    held: &SessionCost,
+    cost: &SessionCost,
+) -> Result<()> {
+    for node_type in cost.node_types.difference(&held.node_types) {
+        if let Some(&max) = config.node_type_limits.get(node_type) {
+            let running = usage.per_node_type.get(node_type).copied().unwrap_or(0);
+            if running >= max {
+                return Err(reject(
+                    config,
+                    format!("node_type:{}", node_type),
+                    format!(
+                        "{} sessions using '{}' already running (limit {})",
+                        running, node_type, max
+                    ),
+                ));
+            }
+        }
+    }
+
+    let budgets = [
+        (
+            "cpu_cores",
+            config.max_cpu_cores,
+            usage.resources.cpu_cores,
+            cost.resources.cpu_cores - held.resources.cpu_cores,
+        ),
+        (
+            "memory_gb",
+            config.max_memory_gb,
+            usage.resources.memory_gb,
+            cost.resources.memory_gb - held.resources.memory_gb,
+        ),
+        (
+            "gpu_memory_gb",
+            config.max_gpu_memory_gb,
+            usage.resources.gpu_memory_gb,
+            cost.resources.gpu_memory_gb - held.resources.gpu_memory_gb,
+        ),
+    ];
+    for (resource, budget, in_use, requested) in budgets {
+        let Some(budget) = budget else { continue };
+        if requested > 0.0 && in_use + requested > budget {
+            return Err(reject(
+                config,
+                resource.to_string(),
+                format!(
+                    "session needs {} {} but only {} of {} is free",
+                    requested,
+                    resource,
+                    (budget - in_use).max(0.0),
+                    budget
+                ),
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+fn reject(config: &AdmissionConfig, resource: String, message: String) -> Error {
+    tracing::warn!(resource = %resource, "Admission rejected: {}", message);
+    Error::AdmissionRejected {
+        resource,
+        message,
+        retry_after_ms: config.retry_after_ms,
+    }
+}
+
+/// Move a session's share in `usage` from `held` to `cost`
+fn charge(usage: &mut AdmissionUsage, held: &SessionCost, cost: &SessionCost) {
+    for node_type in cost.node_types.difference(&held.node_types) {
+        *usage.per_node_type.entry(node_type.clone()).or_default() += 1;
+    }
+    for node_type in held.node_types.difference(&cost.node_types) {
+        release(&mut usage.per_node_type, node_type);
+    }
+    usage.resources.sub(&held.resources);
+    usage.resources.add(&cost.resources);
+}
+
+/// An admitted session's share of the quotas, released on drop
+pub struct AdmissionPermit {
+    config: Arc<AdmissionConfig>,
+    usage: Arc<Mutex<AdmissionUsage>>,
+    tenant: String,
+    cost: SessionCost,
+}
+
+impl AdmissionPermit {
+    pub fn tenant(&self) -> &str {
+        &self.tenant
+    }
+
+    pub fn cost(&self) -> &SessionCost {
+        &self.cost
+    }
+
+    /// Quotas this permit was admitted under
+    pub fn config(&self) -> &AdmissionConfig {
+        &self.config
+    }
+
+    /// Change the session's share to `cost`, e.g. for a patched pipeline
+    ///
+    /// Growth is checked like a new session: a node type the session does
+    /// not use yet must be under its limit, and added resources must fit
+    /// the budgets. On rejection the permit is unchanged.
+    pub fn resize(&mut self, cost: SessionCost) -> Result<()> {
+        let mut usage = self.usage.lock().unwrap_or_else(|e| e.into_inner());
+        check_growth(&self.config, &usage, &self.cost, &cost)?;
+        charge(&mut usage, &self.cost, &cost);
+        drop(usage);
+        self.cost = cost;
+        Ok(())
+    }
+
+    /// [`Self::resize`] without the check, to go back to a share the
+    /// session held before
+    pub fn restore(&mut self, cost: SessionCost) {
+        let mut usage = self.usage.lock().unwrap_or_else(|e| e.into_inner());
+        charge(&mut usage, &self.cost, &cost);
+        drop(usage);
+        self.cost = cost;
+    }
+}
+
+impl Drop for AdmissionPermit {
+    fn drop(&mut self) {
+        let mut usage = self.usage.lock().unwrap_or_else(|e| e.into_inner());
+        usage.sessions = usage.sessions.saturating_sub(1);
+        release(&mut usage.per_tenant, &self.tenant);
+        charge(&mut usage, &self.cost, &SessionCost::default());
+    }
+}
+
+fn release(counts: &mut HashMap<String, usize>, key: &str) {
+    if let Some(count) = counts.get_mut(key) {
+        *count -= 1;
+        if *count == 0 {
+            counts.remove(key);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::manifest::{
+        ExecutionMetadata, GpuRequirement, ManifestMetadata, NodeManifest, Placement,
+    };
+
+    fn manifest(node_types: &[&str]) -> Manifest {
+        Manifest {
+            version: "v1".to_string(),
+            metadata: ManifestMetadata {
+                name: "test".to_string(),
+                ..Default::default()
+            },
+            nodes: node_types
+                .iter()
+                .enumerate()
+                .map(|(i, node_type)| NodeManifest {
+                    id: format!("n{}", i),
+                    node_type: node_type.to_string(),
+                    ..Default::default()
+                })
+                .collect(),
+            connections: vec![],
+            python_env: None,
+        }
+    }
+
+    fn rejected_resource(result: Result<AdmissionPermit>) -> String {
+        match result {
+            Err(Error::AdmissionRejected { resource, .. }) => resource,
+            Err(e) => panic!("unexpected error: {}", e),
+            Ok(_) => panic!("session was admitted"),
+        }
+    }
+
+    #[test]
+    fn test_default_admits_everything() {
+        let controller = AdmissionController::default();
+        let permits: Vec<_> = (0..100)
+            .map(|_| controller.admit(None, &manifest(&["WhisperNode"])).unwrap())
+            .collect();
+        assert_eq!(controller.usage().sessions, 100);
+        assert_eq!(controller.usage().per_tenant[DEFAULT_TENANT], 100);
+        drop(permits);
+        assert_eq!(controller.usage().sessions, 0);
+        assert!(controller.usage().per_node_type.is_empty());
+    }
+
+    #[test]
+    fn test_node_type_limit() {
+        let controller = AdmissionController::new(AdmissionConfig {
+            node_type_limits: HashMap::from([("WhisperNode".to_string(), 2)]),
+            ..Default::default()
+        });
+        // Two Whisper nodes in one session still count as one session
+        let first = controller
+            .admit(None, &manifest(&["WhisperNode", "WhisperNode"]))
+            .unwrap();
+        let _second = controller.admit(None, &manifest(&["WhisperNode"])).unwrap();
+        assert_eq!(
+            rejected_resource(controller.admit(None, &manifest(&["WhisperNode"]))),
+            "node_type:WhisperNode"
+        );
+        // Other node types are unaffected
+        controller.admit(None, &manifest(&["PassThrough"])).unwrap();
+
+        drop(first);
+        controller.admit(None, &manifest(&["WhisperNode"])).unwrap();
+    }
+
+    #[test]
+    fn test_tenant_and_global_limits() {
+        let controller = AdmissionController::new(AdmissionConfig {
+            max_sessions: Some(3),
+            max_sessions_per_tenant: Some(2),
+            retry_after_ms: 1_500,
+            ..Default::default()
+        });
+        let m = manifest(&["PassThrough"]);
+        let _a1 = controller.admit(Some("a"), &m).unwrap();
+        let _a2 = controller.admit(Some("a"), &m).unwrap();
+        match controller.admit(Some("a"), &m) {
+            Err(Error::AdmissionRejected {
+                resource,
+                retry_after_ms,
+                ..
+            }) => {
+                assert_eq!(resource, "tenant:a");
+                assert_eq!(retry_after_ms, 1_500);
+            }
+            _ => panic!("tenant limit not enforced"),
+        }
+        let _b1 = controller.admit(Some("b"), &m).unwrap();
+        assert_eq!(
+            rejected_resource(controller.admit(Some("b"), &m)),
+            "sessions"
+        );
+    }
+
+    #[test]
+    fn test_cost_from_requirements_and_fallback() {
+        let mut m = manifest(&["WhisperNode", "LlamaCppGenerationNode", "PassThrough"]);
+        m.nodes[1].resources = Some(CapabilityRequirements {
+            gpu: Some(GpuRequirement {
+                gpu_type: "cuda".to_string(),
+                min_memory_gb: Some(8.0),
+                required: true,
+            }),
+            cpu: None,
+            memory_gb: Some(4.0),
+        });
+        let config = AdmissionConfig {
+            node_costs: HashMap::from([(
+                "WhisperNode".to_string(),
+                ResourceCost {
+                    cpu_cores: 2.0,
+                    gpu_memory_gb: 4.0,
+                    ..Default::default()
+                },
+            )]),
+            max_gpu_memory_gb: Some(20.0),
+            ..Default::default()
+        };
+
+        let cost = SessionCost::estimate(&m, &config);
+        assert_eq!(
+            cost.resources,
+            ResourceCost {
+                cpu_cores: 2.0,
+                memory_gb: 4.0,
+                gpu_memory_gb: 12.0,
+            }
+        );
+        assert_eq!(cost.node_types.len(), 3);
+
+        let controller = AdmissionController::new(config);
+        let _first = controller.admit(None, &m).unwrap();
+        assert_eq!(
+            rejected_resource(controller.admit(None, &m)),
+            "gpu_memory_gb"
+        );
+        // A session that needs no GPU still fits
+        controller.admit(None, &manifest(&["PassThrough"])).unwrap();
+    }
+
+    #[test]
+    fn test_resize_checks_only_growth() {
+        let config = AdmissionConfig {
+            node_type_limits: HashMap::from([("WhisperNode".to_string(), 1)]),
+            node_costs: HashMap::from([(
+                "WhisperNode".to_string(),
+                ResourceCost {
+                    gpu_memory_gb: 6.0,
+                    ..Default::default()
+                },
+            )]),
+            max_gpu_memory_gb: Some(10.0),
+            ..Default::default()
+        };
+        let controller = AdmissionController::new(config.clone());
+        let _other = controller.admit(None, &manifest(&["WhisperNode"])).unwrap();
+        let mut permit = controller.admit(None, &manifest(&["PassThrough"])).unwrap();
+
+        // A second session may not start using WhisperNode
+        let grown = SessionCost::estimate(&manifest(&["PassThrough", "WhisperNode"]), &config);
+        match permit.resize(grown) {
+            Err(Error::AdmissionRejected { resource, .. }) => {
+                assert_eq!(resource, "node_type:WhisperNode")
+            }
+            _ => panic!("patch past the node type limit was admitted"),
+        }
+        assert_eq!(permit.cost().node_types.len(), 1);
+        assert_eq!(controller.usage().per_node_type["WhisperNode"], 1);
+
+        // Shrinking always fits, and releases what the session gave up
+        permit.resize(SessionCost::default()).unwrap();
+        assert!(!controller.usage().per_node_type.contains_key("PassThrough"));
+        drop(permit);
+        assert_eq!(controller.usage().sessions, 1);
+        assert_eq!(controller.usage().resources.gpu_memory_gb, 6.0);
+    }
+
+    #[test]
+    fn test_fallback_is_not_charged_until_switched_in() {
+        let mut m = manifest(&["WhisperNode", "OpenAIWhisperNode"]);
+        m.nodes[0].execution = Some(ExecutionMetadata {
+            placement: Placement::Auto,
+            reason: None,
+            fallback: Some("n1".to_string()),
+        });
+        let config = AdmissionConfig::default();
+
+        let cost = SessionCost::estimate(&m, &config);
+        assert_eq!(cost.node_types, BTreeSet::from(["WhisperNode".to_string()]));
+
+        let running = HashMap::from([("n0".to_string(), "n1".to_string())]);
+        let cost = SessionCost::estimate_running(&m, &config, &running);
+        assert_eq!(
+            cost.node_types,
+            BTreeSet::from(["OpenAIWhisperNode".to_string()])
+        );
+    }
+
+    #[test]
+    fn test_config_deserializes_with_defaults() {
+        let config: AdmissionConfig = serde_json::from_value(serde_json::json!({
+            "node_type_limits": { "WhisperNode": 2 },
+            "node_costs": { "WhisperNode": { "gpu_memory_gb": 6.0 } }
+        }))
+        .unwrap();
+        assert_eq!(config.node_type_limits["WhisperNode"], 2);
+        assert_eq!(config.node_costs["WhisperNode"].gpu_memory_gb, 6.0);
+        assert_eq!(config.retry_after_ms, 5_000);
+        assert!(config.max_sessions.is_none());
+    }
+}
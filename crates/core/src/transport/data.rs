@@ -4,6 +4,7 @@
 //! metadata for transport-specific information.
 
 use crate::data::RuntimeData;
+use crate::telemetry::{TraceContext, TRACEPARENT_KEY};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
@@ -133,6 +134,36 @@ impl TransportData {
     pub fn get_metadata(&self, key: &str) -> Option<&String> {
         self.metadata.get(key)
     }
+
+    /// Builder pattern: attach a W3C trace context
+    ///
+    /// Stored in `metadata` under [`TRACEPARENT_KEY`]; the session router
+    /// parents the first node dispatch on it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use remotemedia_core::transport::TransportData;
+    /// use remotemedia_core::data::RuntimeData;
+    /// use remotemedia_core::telemetry::TraceContext;
+    ///
+    /// let ctx = TraceContext::parse("00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01").unwrap();
+    /// let data = TransportData::new(RuntimeData::Text("hello".into()))
+    ///     .with_trace_context(&ctx);
+    /// assert_eq!(data.trace_context(), Some(ctx));
+    /// ```
+    ///
+    /// [`TRACEPARENT_KEY`]: crate::telemetry::TRACEPARENT_KEY
+    pub fn with_trace_context(mut self, ctx: &TraceContext) -> Self {
+        self.metadata
+            .insert(TRACEPARENT_KEY.to_string(), ctx.to_traceparent());
+        self
+    }
+
+    /// Trace context carried in `metadata`, if present and well-formed
+    pub fn trace_context(&self) -> Option<TraceContext> {
+        TraceContext::from_metadata(&self.metadata)
+    }
 }
 
 /// Convert RuntimeData directly to TransportData
@@ -29,6 +29,7 @@ use crate::executor::streaming_scheduler::{SchedulerConfig, StreamingScheduler};
 use crate::executor::DriftThresholds;
 use crate::manifest::Manifest;
 use crate::nodes::{StreamingNodeFactory, StreamingNodeRegistry};
+use crate::transport::admission::{AdmissionConfig, AdmissionController};
 use crate::transport::session_control::{SessionControl, SessionControlBus};
 use crate::transport::session_router::{DataPacket, SessionRouter};
 use crate::transport::TransportData;
@@ -48,6 +49,8 @@ pub struct ExecutorConfig {
     pub enable_drift_metrics: bool,
     /// Session ID prefix for generated sessions
     pub session_id_prefix: String,
+    /// Session quotas; unlimited by default
+    pub admission: AdmissionConfig,
 }
 
 impl Default for ExecutorConfig {
@@ -57,6 +60,7 @@ impl Default for ExecutorConfig {
             drift_thresholds: DriftThresholds::default(),
             enable_drift_metrics: true,
             session_id_prefix: "session".to_string(),
+            admission: AdmissionConfig::default(),
         }
     }
 }
@@ -111,6 +115,7 @@ impl SessionHandle {
             return Err(crate::Error::Execution("Session is closed".to_string()));
         }
 
+        let trace_context = data.trace_context();
         let packet = DataPacket {
             data: data.data,
             from_node: "client".to_string(),
@@ -118,6 +123,7 @@ impl SessionHandle {
             session_id: self.session_id.clone(),
             sequence: data.sequence.unwrap_or(0),
             sub_sequence: data.sequence.unwrap_or(0),
+            trace_context,
         };
 
         let tx = self.input_tx.as_ref().ok_or_else(|| {
@@ -133,6 +139,28 @@ impl SessionHandle {
         Ok(())
     }
 
+    /// Send a pre-built packet, keeping its routing fields.
+    ///
+    /// Used by [`crate::transport::session_replay`] to re-feed recorded
+    /// ingress exactly as the router first saw it (including `to_node`
+    /// targets and control-bus injections). The session id is rewritten
+    /// to this session's.
+    pub(crate) async fn send_packet(&self, mut packet: DataPacket) -> Result<()> {
+        if !self.is_active {
+            return Err(crate::Error::Execution("Session is closed".to_string()));
+        }
+        packet.session_id = self.session_id.clone();
+
+        let tx = self.input_tx.as_ref().ok_or_else(|| {
+            crate::Error::Execution("Input channel closed (input complete signalled)".to_string())
+        })?;
+        tx.send(packet).await.map_err(|e| {
+            crate::Error::Execution(format!("Failed to send input: {}", e))
+        })?;
+
+        Ok(())
+    }
+
     /// Receive output data from the session
     ///
     /// Returns `None` if the session is closed or no more outputs are available.
@@ -228,6 +256,7 @@ impl SessionInputSender {
     /// — call from a dedicated task so a full queue doesn't wedge
     /// the output-drain loop.
     pub async fn send(&self, data: TransportData) -> Result<()> {
+        let trace_context = data.trace_context();
         let packet = DataPacket {
             data: data.data,
             from_node: "client".to_string(),
@@ -235,6 +264,7 @@ impl SessionInputSender {
             session_id: self.session_id.clone(),
             sequence: data.sequence.unwrap_or(0),
             sub_sequence: data.sequence.unwrap_or(0),
+            trace_context,
         };
         self.tx.send(packet).await.map_err(|e| {
             crate::Error::Execution(format!("Failed to send input: {}", e))
@@ -294,6 +324,9 @@ pub struct PipelineExecutor {
     /// up a session here when a client sends an `Attach(session_id)`
     /// control frame.
     control_bus: Arc<SessionControlBus>,
+    /// Quotas checked by [`Self::create_session_as`] before a session
+    /// is built
+    admission: Arc<AdmissionController>,
 }
 
 impl PipelineExecutor {
@@ -317,12 +350,15 @@ impl PipelineExecutor {
         // call repeatedly: first-writer-wins, later calls no-op.
         SessionControlBus::install_global(control_bus.clone());
 
+        let admission = Arc::new(AdmissionController::new(config.admission.clone()));
+
         Ok(Self {
             config,
             registry,
             scheduler,
             session_counter: std::sync::atomic::AtomicU64::new(0),
             control_bus,
+            admission,
         })
     }
 
@@ -335,6 +371,15 @@ impl PipelineExecutor {
         self.control_bus.clone()
     }
 
+    /// Access the admission controller.
+    ///
+    /// Transports that build their own session router instead of going
+    /// through [`Self::create_session`] (gRPC streaming) admit sessions
+    /// here directly.
+    pub fn admission(&self) -> &Arc<AdmissionController> {
+        &self.admission
+    }
+
     /// Get the scheduler reference
     pub fn scheduler(&self) -> &Arc<StreamingScheduler> {
         &self.scheduler
@@ -394,6 +439,10 @@ impl PipelineExecutor {
     /// - Connection graph is valid (no cycles, all endpoints exist)
     /// - Node parameters are valid
     pub async fn validate_manifest(&self, manifest: &Manifest) -> Result<()> {
+        // Nodes with a `host` are checked by the host that runs them
+        let wrapped = crate::executor::wrap_remote_hosts(manifest)?;
+        let manifest = wrapped.as_ref().unwrap_or(manifest);
+
         // Build the graph to validate connections
         crate::executor::PipelineGraph::from_manifest(manifest)?;
 
@@ -428,12 +477,24 @@ impl PipelineExecutor {
         &self,
         manifest: Arc<Manifest>,
         input: TransportData,
+    ) -> Result<TransportData> {
+        self.execute_unary_as(manifest, input, None).await
+    }
+
+    /// [`Self::execute_unary`] on behalf of `tenant`
+    ///
+    /// The temporary session counts against `tenant`'s admission quota.
+    pub async fn execute_unary_as(
+        &self,
+        manifest: Arc<Manifest>,
+        input: TransportData,
+        tenant: Option<&str>,
     ) -> Result<TransportData> {
         // Validate manifest
         self.validate_manifest(&manifest).await?;
 
         // Create a temporary session
-        let mut session = self.create_session(manifest).await?;
+        let mut session = self.create_session_as(manifest, tenant).await?;
 
         // Send input
         session.send_input(input).await?;
@@ -462,9 +523,26 @@ impl PipelineExecutor {
     ///
     /// A SessionHandle for sending inputs and receiving outputs
     pub async fn create_session(&self, manifest: Arc<Manifest>) -> Result<SessionHandle> {
+        self.create_session_as(manifest, None).await
+    }
+
+    /// Create a streaming session on behalf of `tenant`
+    ///
+    /// Like [`Self::create_session`], but the session is counted against
+    /// `tenant`'s quota and owned by `tenant` on the control bus, so only
+    /// that tenant's keys may attach to it. Fails with [`crate::Error::AdmissionRejected`]
+    /// when admission control refuses it; the quota is held by the session
+    /// router until it exits, and live patches are admitted against it.
+    pub async fn create_session_as(
+        &self,
+        manifest: Arc<Manifest>,
+        tenant: Option<&str>,
+    ) -> Result<SessionHandle> {
         // Validate manifest
         self.validate_manifest(&manifest).await?;
 
+        let permit = self.admission.admit(tenant, &manifest)?;
+
         let session_id = self.generate_session_id();
 
         // Create bounded output channel. Capacity mirrors the router input
@@ -492,10 +570,14 @@ impl PipelineExecutor {
                 None
             },
         )?;
+        router.attach_admission(permit);
 
         // Create and attach the per-session control bus. Must happen before
         // `start()` consumes the router's input_tx.
         let control = SessionControl::new(session_id.clone());
+        if let Some(tenant) = tenant {
+            control.set_owner(tenant);
+        }
         router.attach_control(control.clone()).await;
         self.control_bus.register(control.clone());
 
@@ -663,10 +745,12 @@ mod tests {
                 Connection {
                     from: "A".to_string(),
                     to: "B".to_string(),
+                    ..Default::default()
                 },
                 Connection {
                     from: "B".to_string(),
                     to: "A".to_string(),
+                    ..Default::default()
                 },
             ],
             python_env: None,
@@ -677,6 +761,46 @@ mod tests {
         assert!(result.unwrap_err().to_string().contains("cycle"));
     }
 
+    #[tokio::test]
+    async fn test_create_session_admission() {
+        let config = ExecutorConfig {
+            admission: AdmissionConfig {
+                node_type_limits: [("PassThrough".to_string(), 1)].into(),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let executor = PipelineExecutor::with_config(config).unwrap();
+        let manifest = Arc::new(Manifest {
+            nodes: vec![NodeManifest {
+                id: "pass".to_string(),
+                node_type: "PassThrough".to_string(),
+                ..Default::default()
+            }],
+            ..create_test_manifest()
+        });
+
+        let mut session = executor
+            .create_session_as(manifest.clone(), Some("tenant-a"))
+            .await
+            .unwrap();
+        assert_eq!(executor.admission().usage().per_tenant["tenant-a"], 1);
+        let control = executor.control_bus().get(&session.session_id).unwrap();
+        assert_eq!(control.owner(), Some("tenant-a"));
+        match executor.create_session(manifest.clone()).await {
+            Err(crate::Error::AdmissionRejected { resource, .. }) => {
+                assert_eq!(resource, "node_type:PassThrough");
+            }
+            _ => panic!("second PassThrough session should be rejected"),
+        }
+
+        // The quota is released once the router exits
+        session.close().await.unwrap();
+        session.wait().await.unwrap();
+        assert_eq!(executor.admission().usage().sessions, 0);
+        executor.create_session(manifest).await.unwrap();
+    }
+
     #[tokio::test]
     async fn test_registry_access() {
         let executor = PipelineExecutor::new().unwrap();
@@ -26,13 +26,16 @@ use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 
 // Re-export submodules
+pub mod admission;
 pub mod client;
 pub mod data;
 pub mod perf_aggregator;
+pub mod pipeline_patch;
 pub mod plugin_registry;
 pub mod session;
 pub mod session_control;
 pub mod session_recorder;
+pub mod session_replay;
 pub mod session_router;
 
 // PipelineExecutor facade (spec 026)
@@ -40,9 +43,11 @@ pub mod executor;
 
 
 // Re-export key types for convenience
+pub use admission::{AdmissionConfig, AdmissionController, AdmissionPermit};
 pub use client::{ClientStreamSession, PipelineClient, TransportType};
 pub use data::TransportData;
 pub use executor::{ExecutorConfig, PipelineExecutor, SessionHandle, SessionInputSender};
+pub use pipeline_patch::{PatchOp, PatchReport, PipelinePatch};
 pub use plugin_registry::TransportPluginRegistry;
 pub use session::{StreamSession, StreamSessionHandle};
 pub use session_router::{
@@ -0,0 +1,377 @@
+//! Manifest diffs applied to a running session.
+//!
+//! A [`PipelinePatch`] is an ordered list of [`PatchOp`]s — add or remove
+//! nodes, rewire connections, replace a node's `params`. The session router
+//! applies it between packets via [`SessionControl::apply_patch`]:
+//!
+//! 1. **Prepare.** The patch is applied to a copy of the manifest, the new
+//!    graph is validated (cycles, unknown endpoints, undeclared ports) and
+//!    every added or re-parameterised node is created and initialised.
+//!    Any failure here is returned to the caller and the live pipeline is
+//!    left exactly as it was.
+//! 2. **Commit.** Fan-out routing is swapped to the new graph. Nodes that
+//!    are removed or replaced stop receiving input and drain whatever is
+//!    already queued on their edges before they exit; replacements only
+//!    start once their predecessor instance has drained, so ordering on
+//!    an edge is preserved across the swap.
+//!
+//! Changing `params` restarts the node: streaming nodes have no generic
+//! reconfigure hook, so the replacement is a fresh instance built from the
+//! new params. Removing a node and adding one with the same id in a
+//! single patch replaces it the same way.
+//!
+//! ```json
+//! { "ops": [
+//!     { "op": "add_node", "node": { "id": "tap", "node_type": "PassThrough" } },
+//!     { "op": "connect", "connection": { "from": "tts", "to": "tap" } },
+//!     { "op": "update_params", "node_id": "tts", "params": { "voice": "af_sky" } }
+//! ] }
+//! ```
+//!
+//! [`SessionControl::apply_patch`]: crate::transport::session_control::SessionControl::apply_patch
+
+use crate::manifest::{Connection, Manifest, NodeManifest};
+use crate::{Error, Result};
+use serde::{Deserialize, Serialize};
+
+/// One edit in a [`PipelinePatch`]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "op", rename_all = "snake_case")]
+pub enum PatchOp {
+    /// Add a node. Its id must not already be in use.
+    AddNode { node: NodeManifest },
+    /// Remove a node together with every connection touching it.
+    RemoveNode { node_id: String },
+    /// Replace a node's `params`; the node is restarted with the new values.
+    UpdateParams {
+        node_id: String,
+        params: serde_json::Value,
+    },
+    /// Add a connection between two existing nodes.
+    Connect { connection: Connection },
+    /// Remove a connection, matched on its endpoints and ports.
+    Disconnect { connection: Connection },
+}
+
+/// An ordered set of edits to a running session's pipeline
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PipelinePatch {
+    pub ops: Vec<PatchOp>,
+}
+
+/// What a patch changed, as applied
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PatchReport {
+    /// Nodes that were added
+    pub added: Vec<String>,
+    /// Nodes that were removed
+    pub removed: Vec<String>,
+    /// Existing nodes restarted with new params
+    pub restarted: Vec<String>,
+    /// Existing nodes removed and re-added under the same id
+    #[serde(default)]
+    pub replaced: Vec<String>,
+    /// Connections added
+    pub connections_added: usize,
+    /// Connections removed, including those dropped with a removed node
+    pub connections_removed: usize,
+}
+
+impl PatchReport {
+    /// `true` if the patch changed nothing
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty()
+            && self.removed.is_empty()
+            && self.restarted.is_empty()
+            && self.replaced.is_empty()
+            && self.connections_added == 0
+            && self.connections_removed == 0
+    }
+
+    /// Nodes that need a fresh instance: added, restarted and replaced
+    pub fn fresh_nodes(&self) -> impl Iterator<Item = &String> {
+        self.added.iter().chain(self.successors())
+    }
+
+    /// Nodes whose old instance must drain before the new one starts:
+    /// restarted plus replaced
+    pub fn successors(&self) -> impl Iterator<Item = &String> {
+        self.restarted.iter().chain(&self.replaced)
+    }
+}
+
+fn same_endpoints(a: &Connection, b: &Connection) -> bool {
+    a.from == b.from && a.to == b.to && a.from_port == b.from_port && a.to_port == b.to_port
+}
+
+impl PipelinePatch {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a node
+    pub fn add_node(mut self, node: NodeManifest) -> Self {
+        self.ops.push(PatchOp::AddNode { node });
+        self
+    }
+
+    /// Remove a node and its connections
+    pub fn remove_node(mut self, node_id: impl Into<String>) -> Self {
+        self.ops.push(PatchOp::RemoveNode {
+            node_id: node_id.into(),
+        });
+        self
+    }
+
+    /// Replace a node's params
+    pub fn update_params(mut self, node_id: impl Into<String>, params: serde_json::Value) -> Self {
+        self.ops.push(PatchOp::UpdateParams {
+            node_id: node_id.into(),
+            params,
+        });
+        self
+    }
+
+    /// Add a connection
+    pub fn connect(mut self, connection: Connection) -> Self {
+        self.ops.push(PatchOp::Connect { connection });
+        self
+    }
+
+    /// Remove a connection
+    pub fn disconnect(mut self, connection: Connection) -> Self {
+        self.ops.push(PatchOp::Disconnect { connection });
+        self
+    }
+
+    /// Apply the patch to `manifest`, returning the edited copy
+    ///
+    /// Checks each op against the manifest as edited so far (unknown or
+    /// duplicate node ids, missing or duplicate connections). Graph-level
+    /// validation — cycles, ports — is left to the caller.
+    pub fn apply_to(&self, manifest: &Manifest) -> Result<(Manifest, PatchReport)> {
+        let mut manifest = manifest.clone();
+        let mut report = PatchReport::default();
+        let has_node = |m: &Manifest, id: &str| m.nodes.iter().any(|n| n.id == id);
+
+        for op in &self.ops {
+            match op {
+                PatchOp::AddNode { node } => {
+                    if has_node(&manifest, &node.id) {
+                        return Err(Error::Manifest(format!(
+                            "patch: node '{}' already exists",
+                            node.id
+                        )));
+                    }
+                    manifest.nodes.push(node.clone());
+                    // Re-adding a node removed earlier in the patch
+                    // replaces the running instance
+                    if let Some(pos) = report.removed.iter().position(|id| id == &node.id) {
+                        report.removed.remove(pos);
+                        report.replaced.push(node.id.clone());
+                    } else {
+                        report.added.push(node.id.clone());
+                    }
+                }
+                PatchOp::RemoveNode { node_id } => {
+                    if !has_node(&manifest, node_id) {
+                        return Err(Error::Manifest(format!(
+                            "patch: cannot remove unknown node '{}'",
+                            node_id
+                        )));
+                    }
+                    manifest.nodes.retain(|n| &n.id != node_id);
+                    let before = manifest.connections.len();
+                    manifest
+                        .connections
+                        .retain(|c| &c.from != node_id && &c.to != node_id);
+                    report.connections_removed += before - manifest.connections.len();
+                    report.restarted.retain(|id| id != node_id);
+                    report.replaced.retain(|id| id != node_id);
+                    // Adding then removing in one patch is a no-op for that node
+                    if let Some(pos) = report.added.iter().position(|id| id == node_id) {
+                        report.added.remove(pos);
+                    } else {
+                        report.removed.push(node_id.clone());
+                    }
+                }
+                PatchOp::UpdateParams { node_id, params } => {
+                    let node = manifest
+                        .nodes
+                        .iter_mut()
+                        .find(|n| &n.id == node_id)
+                        .ok_or_else(|| {
+                            Error::Manifest(format!(
+                                "patch: cannot update params of unknown node '{}'",
+                                node_id
+                            ))
+                        })?;
+                    node.params = params.clone();
+                    if !report.added.contains(node_id)
+                        && !report.restarted.contains(node_id)
+                        && !report.replaced.contains(node_id)
+                    {
+                        report.restarted.push(node_id.clone());
+                    }
+                }
+                PatchOp::Connect { connection } => {
+                    for end in [&connection.from, &connection.to] {
+                        if !has_node(&manifest, end) {
+                            return Err(Error::Manifest(format!(
+                                "patch: connection {} references unknown node '{}'",
+                                connection, end
+                            )));
+                        }
+                    }
+                    if manifest
+                        .connections
+                        .iter()
+                        .any(|c| same_endpoints(c, connection))
+                    {
+                        return Err(Error::Manifest(format!(
+                            "patch: connection {} already exists",
+                            connection
+                        )));
+                    }
+                    manifest.connections.push(connection.clone());
+                    report.connections_added += 1;
+                }
+                PatchOp::Disconnect { connection } => {
+                    let pos = manifest
+                        .connections
+                        .iter()
+                        .position(|c| same_endpoints(c, connection))
+                        .ok_or_else(|| {
+                            Error::Manifest(format!(
+                                "patch: connection {} does not exist",
+                                connection
+                            ))
+                        })?;
+                    manifest.connections.remove(pos);
+                    report.connections_removed += 1;
+                }
+            }
+        }
+
+        Ok((manifest, report))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::manifest::ManifestMetadata;
+
+    fn node(id: &str) -> NodeManifest {
+        NodeManifest {
+            id: id.to_string(),
+            node_type: "PassThrough".to_string(),
+            ..Default::default()
+        }
+    }
+
+    fn manifest() -> Manifest {
+        Manifest {
+            version: "v1".to_string(),
+            metadata: ManifestMetadata {
+                name: "patch".to_string(),
+                ..Default::default()
+            },
+            nodes: vec![node("a"), node("b")],
+            connections: vec![Connection::new("a", "b")],
+            python_env: None,
+        }
+    }
+
+    #[test]
+    fn test_insert_node_between() {
+        let patch = PipelinePatch::new()
+            .add_node(node("tap"))
+            .disconnect(Connection::new("a", "b"))
+            .connect(Connection::new("a", "tap"))
+            .connect(Connection::new("tap", "b"));
+
+        let (patched, report) = patch.apply_to(&manifest()).unwrap();
+        assert_eq!(patched.nodes.len(), 3);
+        assert_eq!(
+            patched.connections,
+            vec![Connection::new("a", "tap"), Connection::new("tap", "b")]
+        );
+        assert_eq!(report.added, vec!["tap"]);
+        assert_eq!(report.connections_added, 2);
+        assert_eq!(report.connections_removed, 1);
+    }
+
+    #[test]
+    fn test_remove_node_drops_its_connections() {
+        let (patched, report) = PipelinePatch::new()
+            .remove_node("b")
+            .apply_to(&manifest())
+            .unwrap();
+        assert_eq!(patched.nodes.len(), 1);
+        assert!(patched.connections.is_empty());
+        assert_eq!(report.removed, vec!["b"]);
+        assert_eq!(report.connections_removed, 1);
+    }
+
+    #[test]
+    fn test_update_params_restarts_existing_nodes_only() {
+        let (patched, report) = PipelinePatch::new()
+            .update_params("a", serde_json::json!({ "x": 1 }))
+            .add_node(node("c"))
+            .update_params("c", serde_json::json!({ "x": 2 }))
+            .apply_to(&manifest())
+            .unwrap();
+        assert_eq!(patched.nodes[0].params["x"], 1);
+        assert_eq!(patched.nodes[2].params["x"], 2);
+        assert_eq!(report.restarted, vec!["a"]);
+        assert_eq!(report.added, vec!["c"]);
+    }
+
+    #[test]
+    fn test_remove_then_add_replaces_the_node() {
+        let mut replacement = node("b");
+        replacement.node_type = "Other".to_string();
+        let (patched, report) = PipelinePatch::new()
+            .remove_node("b")
+            .add_node(replacement)
+            .connect(Connection::new("a", "b"))
+            .update_params("b", serde_json::json!({ "x": 1 }))
+            .apply_to(&manifest())
+            .unwrap();
+        assert_eq!(patched.nodes[1].node_type, "Other");
+        assert_eq!(report.replaced, vec!["b"]);
+        assert!(report.added.is_empty() && report.removed.is_empty());
+        assert!(report.restarted.is_empty());
+        assert_eq!(report.fresh_nodes().collect::<Vec<_>>(), ["b"]);
+    }
+
+    #[test]
+    fn test_invalid_ops_are_rejected() {
+        let base = manifest();
+        for patch in [
+            PipelinePatch::new().add_node(node("a")),
+            PipelinePatch::new().remove_node("missing"),
+            PipelinePatch::new().update_params("missing", serde_json::json!({})),
+            PipelinePatch::new().connect(Connection::new("a", "missing")),
+            PipelinePatch::new().connect(Connection::new("a", "b")),
+            PipelinePatch::new().disconnect(Connection::new("b", "a")),
+        ] {
+            assert!(patch.apply_to(&base).is_err(), "{:?}", patch);
+        }
+    }
+
+    #[test]
+    fn test_patch_json_round_trip() {
+        let json = serde_json::json!({ "ops": [
+            { "op": "add_node", "node": { "id": "tap", "node_type": "PassThrough" } },
+            { "op": "connect", "connection": { "from": "b", "to": "tap" } },
+            { "op": "update_params", "node_id": "a", "params": { "voice": "af_sky" } }
+        ] });
+        let patch: PipelinePatch = serde_json::from_value(json).unwrap();
+        let (_, report) = patch.apply_to(&manifest()).unwrap();
+        assert_eq!(report.added, vec!["tap"]);
+        assert_eq!(report.restarted, vec!["a"]);
+        assert!(!report.is_empty());
+    }
+}
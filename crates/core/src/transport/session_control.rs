@@ -49,6 +49,8 @@
 //!                                 injectors, and intercept hooks.
 //! - [`ControlFrame`]            — wire frame (Subscribe/Publish/Intercept/Reply).
 //! - [`ControlAddress`]          — `{node_id, port, direction}` tuple.
+//! - [`SessionControl::apply_patch`] — live manifest edits; see
+//!   [`pipeline_patch`](crate::transport::pipeline_patch).
 //! - A single integration hook for [`SessionRouter`]:
 //!     * `SessionRouter::attach_control(&SessionControl)` — called at session
 //!       creation; gives the router a handle it consults in `process_input`
@@ -60,14 +62,17 @@
 //!   free-form strings; nodes choose how to interpret auxiliary publishes
 //!   (e.g. `llm.in.context` is just metadata on the `DataPacket` that the
 //!   node's `process_streaming` inspects). Typed ports are a follow-up.
-//! - Authorization. Attach frames are trusted in this prototype; a real
-//!   deployment must gate `attach(session_id)` on ownership.
-//!   Noted as [`ControlAuth`] placeholder below.
+//! - Fine-grained authorization. Attaches are gated by [`ControlAuth`] on
+//!   session ownership (the tenant that created it) via
+//!   [`SessionControlBus::attach`], and patches by
+//!   [`ControlAuth::check_patch`]; there are no per-port permissions.
 //! - Transport framing. `ControlFrame` is the logical message; gRPC /
 //!   WebSocket wrapping lives in the transport crates.
 
+use crate::auth::{AuthError, Principal, Scope};
 use crate::data::RuntimeData;
-use crate::transport::session_router::DataPacket;
+use crate::transport::pipeline_patch::{PatchReport, PipelinePatch};
+use crate::transport::session_router::{DataPacket, RouterHandle};
 use crate::Result;
 use dashmap::DashMap;
 use serde::{Deserialize, Serialize};
@@ -152,6 +157,8 @@ pub enum ControlFrame {
     SetNodeState { node_id: String, state: NodeState },
     /// Reset a node to the default `Enabled` state.
     ClearNodeState { node_id: String },
+    /// Edit the running pipeline (add/remove nodes, rewire, update params).
+    ApplyPatch(PipelinePatch),
 }
 
 #[derive(Clone, Debug)]
@@ -179,10 +186,66 @@ pub enum ControlEvent {
 // Per-session control state
 // ────────────────────────────────────────────────────────────────────────────
 
-/// Placeholder — real deployments will gate attach on this.
+/// Who is attaching to a session's control bus
+///
+/// `principal: None` means the transport runs without authentication, and
+/// every attach is allowed. Otherwise the key needs the `control` scope and
+/// must belong to the tenant that owns the session; admin keys may attach
+/// to any session.
 #[derive(Clone, Debug, Default)]
 pub struct ControlAuth {
-    pub principal: Option<String>,
+    pub principal: Option<Principal>,
+}
+
+impl ControlAuth {
+    pub fn new(principal: Principal) -> Self {
+        Self {
+            principal: Some(principal),
+        }
+    }
+
+    /// Check that this caller may attach to `control`
+    pub fn authorize(&self, control: &SessionControl) -> std::result::Result<(), AuthError> {
+        let Some(principal) = &self.principal else {
+            return Ok(());
+        };
+        if principal.has_scope(Scope::Admin) {
+            return Ok(());
+        }
+        if !principal.has_scope(Scope::Control) {
+            return Err(AuthError::Forbidden(format!(
+                "key '{}' lacks the 'control' scope",
+                principal.key_id
+            )));
+        }
+        match control.owner() {
+            Some(owner) if owner != principal.tenant_id => Err(AuthError::Forbidden(format!(
+                "session '{}' belongs to another tenant",
+                control.session_id()
+            ))),
+            _ => Ok(()),
+        }
+    }
+
+    /// Check that this caller may apply `patch` to `control`'s pipeline
+    ///
+    /// The patched manifest must pass [`Principal::check_manifest`], so a
+    /// key limited to certain manifests or node types cannot patch others
+    /// into a running session. A patch that does not apply is let through
+    /// for the router to reject with the actual reason.
+    pub fn check_patch(
+        &self,
+        control: &SessionControl,
+        patch: &PipelinePatch,
+    ) -> std::result::Result<(), AuthError> {
+        let (Some(principal), Some(router)) = (&self.principal, control.router()) else {
+            return Ok(());
+        };
+        match patch.apply_to(&router.manifest()) {
+            Ok((patched, _)) => principal.check_manifest(&patched),
+            Err(_) => Ok(()),
+        }
+    }
 }
 
 type TapKey = (String, Option<String>); // (node_id, port)
@@ -322,6 +385,21 @@ pub struct SessionControl {
     /// queue (gRPC, etc.) — callers should treat its absence as a
     /// no-op.
     flush_audio_tx: RwLock<Option<mpsc::Sender<()>>>,
+    /// Optional lossless ingress recorder installed by
+    /// [`crate::transport::session_recorder::SessionRecorder`]. Every
+    /// packet the router accepts is copied here, stamped with its arrival
+    /// time, before it is routed. Unlike taps this channel is never lossy:
+    /// a slow recorder backpressures ingress rather than dropping frames.
+    ingress_tx: RwLock<Option<mpsc::Sender<(std::time::Instant, DataPacket)>>>,
+    /// Stats / shutdown view of the router driving this session. Set once
+    /// by `SessionRouter::attach_control`; read by the admin surfaces.
+    router: std::sync::OnceLock<RouterHandle>,
+    /// Why the session was terminated, if it was. Consumed by the router
+    /// when it signals close so attaches see the reason.
+    termination_reason: parking_lot::Mutex<Option<String>>,
+    /// Tenant that created the session. Set once by the executor; checked
+    /// by [`ControlAuth::authorize`] on attach.
+    owner: std::sync::OnceLock<String>,
 }
 
 impl SessionControl {
@@ -336,9 +414,23 @@ impl SessionControl {
             close_tx,
             node_states: DashMap::new(),
             flush_audio_tx: RwLock::new(None),
+            ingress_tx: RwLock::new(None),
+            router: std::sync::OnceLock::new(),
+            termination_reason: parking_lot::Mutex::new(None),
+            owner: std::sync::OnceLock::new(),
         })
     }
 
+    /// Record the tenant that owns this session. First call wins.
+    pub fn set_owner(&self, tenant_id: impl Into<String>) {
+        let _ = self.owner.set(tenant_id.into());
+    }
+
+    /// Tenant that owns this session, if one was recorded
+    pub fn owner(&self) -> Option<&str> {
+        self.owner.get().map(String::as_str)
+    }
+
     /// Install the flush-audio hook. Called by the transport layer at
     /// session setup; later calls overwrite the previous hook (last-
     /// writer-wins, rare in practice since each session has one
@@ -359,6 +451,71 @@ impl SessionControl {
         }
     }
 
+    /// Install the ingress recorder hook. Later calls overwrite the
+    /// previous hook; dropping the receiver disables recording.
+    pub async fn install_ingress_recorder(
+        &self,
+        tx: mpsc::Sender<(std::time::Instant, DataPacket)>,
+    ) {
+        *self.ingress_tx.write().await = Some(tx);
+    }
+
+    /// Copy an ingress packet to the recorder, if one is installed.
+    /// Called by the router for every packet it pulls off its input
+    /// channel, before routing. No-op (one read lock) when not recording.
+    pub(crate) async fn record_ingress(&self, packet: &DataPacket) {
+        let tx = self.ingress_tx.read().await.clone();
+        if let Some(tx) = tx {
+            let at = std::time::Instant::now();
+            if tx.send((at, packet.clone())).await.is_err() {
+                // Recorder dropped — stop paying for the clone.
+                *self.ingress_tx.write().await = None;
+            }
+        }
+    }
+
+    /// Record the router driving this session. First call wins.
+    pub(crate) fn attach_router(&self, handle: RouterHandle) {
+        let _ = self.router.set(handle);
+    }
+
+    /// Stats / shutdown view of the session's router, once attached.
+    pub fn router(&self) -> Option<&RouterHandle> {
+        self.router.get()
+    }
+
+    /// Forcefully end the session.
+    ///
+    /// Stops the router's ingress loop, which tears the pipeline down and
+    /// closes every attach with `CloseReason::Error("terminated: <reason>")`.
+    /// Returns `false` if no router is attached or it has already exited.
+    pub fn terminate(&self, reason: impl Into<String>) -> bool {
+        let Some(router) = self.router.get() else {
+            return false;
+        };
+        self.termination_reason
+            .lock()
+            .get_or_insert_with(|| reason.into());
+        router.shutdown()
+    }
+
+    /// Reason passed to [`Self::terminate`], if the session was killed.
+    pub fn termination_reason(&self) -> Option<String> {
+        self.termination_reason.lock().clone()
+    }
+
+    /// Every explicit node-state override, sorted by node id. Nodes not
+    /// listed are `Enabled`.
+    pub fn node_states(&self) -> Vec<(String, NodeState)> {
+        let mut states: Vec<_> = self
+            .node_states
+            .iter()
+            .map(|e| (e.key().clone(), *e.value()))
+            .collect();
+        states.sort_by(|a, b| a.0.cmp(&b.0));
+        states
+    }
+
     /// Set a node's runtime execution state.
     ///
     /// Takes effect on the **next** packet the router processes. Callers
@@ -374,6 +531,22 @@ impl SessionControl {
         self.node_states.remove(node_id);
     }
 
+    /// Apply a [`PipelinePatch`] to the session's running pipeline.
+    ///
+    /// Rejected patches leave the pipeline unchanged. Fails if no router
+    /// is attached or it has already exited. No authorization happens
+    /// here: transports check the attached client with
+    /// [`ControlAuth::check_patch`] first.
+    pub async fn apply_patch(&self, patch: PipelinePatch) -> Result<PatchReport> {
+        let router = self.router.get().ok_or_else(|| {
+            crate::Error::Execution(format!(
+                "session '{}' has no router attached",
+                self.session_id
+            ))
+        })?;
+        router.apply_patch(patch).await
+    }
+
     /// Current state for a node. Nodes without an explicit override are
     /// `Enabled`. Called on the router hot path — DashMap lookup is
     /// lock-free.
@@ -471,6 +644,7 @@ impl SessionControl {
                 .inject_seq
                 .fetch_add(1, std::sync::atomic::Ordering::Relaxed),
             sub_sequence: 0,
+            trace_context: None,
         };
         tx.send(packet).await.map_err(|_| {
             crate::Error::Execution(format!(
@@ -568,6 +742,10 @@ impl SessionControl {
                 self.clear_node_state(&node_id);
                 Ok(FrameOutcome::Done)
             }
+            ControlFrame::ApplyPatch(patch) => {
+                let report = self.apply_patch(patch).await?;
+                Ok(FrameOutcome::Patched(report))
+            }
         }
     }
 
@@ -684,6 +862,8 @@ pub enum FrameOutcome {
     Done,
     Tap(broadcast::Receiver<RuntimeData>),
     Intercept(mpsc::Receiver<ControlEvent>),
+    /// A pipeline patch was committed.
+    Patched(PatchReport),
 }
 
 // ────────────────────────────────────────────────────────────────────────────
@@ -725,6 +905,28 @@ impl SessionControlBus {
         self.sessions.get(session_id).map(|e| e.value().clone())
     }
 
+    /// [`Self::get`] for a client attach, checked against `auth`.
+    ///
+    /// `Ok(None)` when no such session exists.
+    pub fn attach(
+        &self,
+        session_id: &str,
+        auth: &ControlAuth,
+    ) -> std::result::Result<Option<Arc<SessionControl>>, AuthError> {
+        let Some(control) = self.get(session_id) else {
+            return Ok(None);
+        };
+        auth.authorize(&control)?;
+        Ok(Some(control))
+    }
+
+    /// Every registered session, sorted by session id.
+    pub fn sessions(&self) -> Vec<Arc<SessionControl>> {
+        let mut sessions: Vec<_> = self.sessions.iter().map(|e| e.value().clone()).collect();
+        sessions.sort_by(|a, b| a.session_id.cmp(&b.session_id));
+        sessions
+    }
+
     /// Install `bus` as the process-wide singleton if none is set yet.
     /// First-writer-wins: later calls from secondary executors (typical
     /// only in multi-executor tests) silently no-op. Regular single-
@@ -889,4 +1091,39 @@ mod tests {
         bus.unregister("sess-A");
         assert!(bus.get("sess-A").is_none());
     }
+
+    #[test]
+    fn attach_checks_tenant_and_scope() {
+        let bus = SessionControlBus::new();
+        let ctrl = SessionControl::new("sess-A");
+        ctrl.set_owner("acme");
+        bus.register(ctrl);
+
+        let as_key = |tenant: &str, scopes: Vec<Scope>| {
+            ControlAuth::new(Principal::new("k", tenant).with_scopes(scopes))
+        };
+
+        assert!(bus
+            .attach("sess-A", &ControlAuth::default())
+            .unwrap()
+            .is_some());
+        assert!(bus
+            .attach("sess-A", &as_key("acme", vec![Scope::Control]))
+            .unwrap()
+            .is_some());
+        assert!(bus
+            .attach("sess-A", &as_key("globex", vec![Scope::Control]))
+            .is_err());
+        assert!(bus
+            .attach("sess-A", &as_key("acme", vec![Scope::Stream]))
+            .is_err());
+        assert!(bus
+            .attach("sess-A", &as_key("ops", vec![Scope::Admin]))
+            .unwrap()
+            .is_some());
+        assert!(bus
+            .attach("missing", &as_key("acme", vec![Scope::Control]))
+            .unwrap()
+            .is_none());
+    }
 }
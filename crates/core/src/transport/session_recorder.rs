@@ -16,6 +16,12 @@
 //! duration_ms}` — recording raw f32 PCM would quickly balloon trace
 //! files into gigabytes. Text and Json payloads are captured in full.
 //!
+//! Set `REMOTEMEDIA_RECORD_INPUTS=1` as well for lossless mode
+//! ([`RecordMode::Lossless`]): every packet fed to the router is also
+//! written, raw and timestamped, to `<dir>/<session_id>.inputs.bin`.
+//! That sidecar is what [`crate::transport::session_replay`] re-feeds
+//! into a fresh session to reproduce the run.
+//!
 //! The output format is newline-delimited JSON (JSONL). Each line:
 //!
 //! ```json
@@ -28,20 +34,23 @@
 //! }
 //! ```
 //!
-//! A sibling CLI (`session-replay`) pretty-prints these timelines.
+//! A sibling CLI (`session-replay`) pretty-prints these timelines and,
+//! given a sidecar, replays them.
 
 use crate::data::RuntimeData;
 use crate::manifest::Manifest;
 use crate::transport::session_control::{ControlAddress, SessionControl};
-use serde::Serialize;
+use crate::transport::session_replay::{encode_input, encode_preamble, CaptureHeader, CapturedInput};
+use crate::transport::session_router::DataPacket;
+use serde::{Deserialize, Serialize};
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
-use std::time::{SystemTime, UNIX_EPOCH};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 use tokio::io::AsyncWriteExt;
-use tokio::sync::{broadcast, mpsc};
+use tokio::sync::{broadcast, mpsc, oneshot};
 
 /// One captured frame on a tap.
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct TraceEvent {
     /// Wall-clock time in milliseconds since UNIX epoch.
     pub ts_ms: u64,
@@ -78,7 +87,7 @@ impl TraceEvent {
 
 /// Produce a `(kind, payload)` pair for a `RuntimeData`. Audio frames
 /// are reduced to metadata so trace files stay tractable.
-fn summarize(data: &RuntimeData) -> (String, serde_json::Value) {
+pub(crate) fn summarize(data: &RuntimeData) -> (String, serde_json::Value) {
     match data {
         RuntimeData::Text(s) => ("text".to_string(), serde_json::Value::String(s.clone())),
         RuntimeData::Json(v) => ("json".to_string(), v.clone()),
@@ -120,13 +129,111 @@ fn summarize(data: &RuntimeData) -> (String, serde_json::Value) {
     }
 }
 
+/// What a recorder captures.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RecordMode {
+    /// Node outputs only, audio summarised (`<session>.jsonl`).
+    #[default]
+    Summary,
+    /// Summary trace plus every raw ingress packet
+    /// (`<session>.inputs.bin`), enough to replay the session.
+    Lossless,
+}
+
 /// Handle for a running recorder. Dropping this signals shutdown — the
 /// writer task flushes and exits when the last sender drops.
 pub struct SessionRecorder {
-    _writer_tx: mpsc::Sender<TraceEvent>,
-    _tap_handles: Vec<tokio::task::JoinHandle<()>>,
-    _writer_handle: tokio::task::JoinHandle<()>,
+    writer_tx: mpsc::Sender<TraceEvent>,
+    tap_handles: Vec<tokio::task::JoinHandle<()>>,
+    writer_handle: tokio::task::JoinHandle<()>,
+    path: PathBuf,
+    inputs: Option<InputSidecar>,
+}
+
+/// Lossless ingress capture writing `<session>.inputs.bin`.
+///
+/// The router holds the ingress sender for the whole session, so the
+/// writer can't wait for channel close; `stop_tx` (fired or dropped)
+/// tells it to drain what's queued, flush and exit.
+struct InputSidecar {
     path: PathBuf,
+    stop_tx: oneshot::Sender<()>,
+    handle: tokio::task::JoinHandle<()>,
+}
+
+impl InputSidecar {
+    async fn attach(
+        dir: &Path,
+        session_id: &str,
+        control: &SessionControl,
+        manifest: &Manifest,
+    ) -> std::io::Result<Self> {
+        let path = dir.join(format!("{}.inputs.bin", session_id));
+        let header = CaptureHeader {
+            session_id: session_id.to_string(),
+            started_ms: TraceEvent::now_ms(),
+            manifest_json: serde_json::to_string(manifest)?,
+        };
+        let preamble =
+            encode_preamble(&header).map_err(|e| std::io::Error::other(e.to_string()))?;
+
+        let file = tokio::fs::File::create(&path).await?;
+        let mut writer = tokio::io::BufWriter::new(file);
+        writer.write_all(&preamble).await?;
+
+        let (ingress_tx, mut ingress_rx) = mpsc::channel::<(Instant, DataPacket)>(4096);
+        let (stop_tx, mut stop_rx) = oneshot::channel::<()>();
+        let started = Instant::now();
+        let writer_path = path.clone();
+        let handle = tokio::spawn(async move {
+            let write = |at: Instant, packet: DataPacket| {
+                let input = CapturedInput::from_packet(at.saturating_duration_since(started), packet);
+                encode_input(&input)
+            };
+            loop {
+                let (at, packet) = tokio::select! {
+                    next = ingress_rx.recv() => match next {
+                        Some(item) => item,
+                        None => break,
+                    },
+                    _ = &mut stop_rx => {
+                        // Drain what the router already queued.
+                        ingress_rx.close();
+                        let mut tail = Vec::new();
+                        while let Some((at, packet)) = ingress_rx.recv().await {
+                            match write(at, packet) {
+                                Ok(buf) => tail.extend(buf),
+                                Err(e) => tracing::warn!("[recorder] input encode failed: {}", e),
+                            }
+                        }
+                        if let Err(e) = writer.write_all(&tail).await {
+                            tracing::warn!("[recorder] write failed for {:?}: {}", writer_path, e);
+                        }
+                        break;
+                    }
+                };
+                match write(at, packet) {
+                    Ok(buf) => {
+                        if let Err(e) = writer.write_all(&buf).await {
+                            tracing::warn!("[recorder] write failed for {:?}: {}", writer_path, e);
+                            break;
+                        }
+                    }
+                    Err(e) => tracing::warn!("[recorder] input encode failed: {}", e),
+                }
+            }
+            if let Err(e) = writer.flush().await {
+                tracing::warn!("[recorder] final flush failed: {}", e);
+            }
+        });
+
+        control.install_ingress_recorder(ingress_tx).await;
+        Ok(Self {
+            path,
+            stop_tx,
+            handle,
+        })
+    }
 }
 
 impl SessionRecorder {
@@ -138,8 +245,25 @@ impl SessionRecorder {
         session_id: String,
         control: Arc<SessionControl>,
         manifest: &Manifest,
+    ) -> std::io::Result<Self> {
+        Self::attach_with_mode(dir, session_id, control, manifest, RecordMode::Summary).await
+    }
+
+    /// [`Self::attach`] with an explicit [`RecordMode`].
+    pub async fn attach_with_mode(
+        dir: &Path,
+        session_id: String,
+        control: Arc<SessionControl>,
+        manifest: &Manifest,
+        mode: RecordMode,
     ) -> std::io::Result<Self> {
         tokio::fs::create_dir_all(dir).await?;
+        let inputs = match mode {
+            RecordMode::Summary => None,
+            RecordMode::Lossless => {
+                Some(InputSidecar::attach(dir, &session_id, &control, manifest).await?)
+            }
+        };
         let path = dir.join(format!("{}.jsonl", session_id));
         let file = tokio::fs::File::create(&path).await?;
         let mut writer = tokio::io::BufWriter::new(file);
@@ -202,17 +326,19 @@ impl SessionRecorder {
         }
 
         tracing::info!(
-            "[recorder] capturing session {} to {:?} ({} taps)",
+            "[recorder] capturing session {} to {:?} ({} taps{})",
             session_id,
             path,
-            tap_handles.len()
+            tap_handles.len(),
+            if inputs.is_some() { ", lossless inputs" } else { "" }
         );
 
         Ok(Self {
-            _writer_tx: writer_tx,
-            _tap_handles: tap_handles,
-            _writer_handle: writer_handle,
+            writer_tx,
+            tap_handles,
+            writer_handle,
             path,
+            inputs,
         })
     }
 
@@ -220,6 +346,32 @@ impl SessionRecorder {
         &self.path
     }
 
+    /// Path of the `.inputs.bin` sidecar, in lossless mode.
+    pub fn inputs_path(&self) -> Option<&Path> {
+        self.inputs.as_ref().map(|i| i.path.as_path())
+    }
+
+    /// Stop recording and wait until the files are flushed.
+    ///
+    /// Dropping the recorder also stops it, but without waiting — call this
+    /// once the session has ended when the files are read straight after.
+    /// Tap relays finish on their own when the session's taps close; any
+    /// still running after a second (live session) are cut off.
+    pub async fn finish(self) -> std::io::Result<()> {
+        if let Some(inputs) = self.inputs {
+            let _ = inputs.stop_tx.send(());
+            inputs.handle.await.map_err(std::io::Error::other)?;
+        }
+        for handle in self.tap_handles {
+            let abort = handle.abort_handle();
+            if tokio::time::timeout(Duration::from_secs(1), handle).await.is_err() {
+                abort.abort();
+            }
+        }
+        drop(self.writer_tx);
+        self.writer_handle.await.map_err(std::io::Error::other)
+    }
+
     /// Helper that looks up `REMOTEMEDIA_RECORD_DIR` and attaches a
     /// recorder if it's set. Any failure (missing dir permission,
     /// subscribe error) downgrades to a warning and a `None` return
@@ -231,7 +383,11 @@ impl SessionRecorder {
     ) -> Option<Self> {
         let dir = std::env::var("REMOTEMEDIA_RECORD_DIR").ok()?;
         let dir = PathBuf::from(dir);
-        match Self::attach(&dir, session_id, control, manifest).await {
+        let mode = match std::env::var("REMOTEMEDIA_RECORD_INPUTS").as_deref() {
+            Ok("1") | Ok("true") => RecordMode::Lossless,
+            _ => RecordMode::Summary,
+        };
+        match Self::attach_with_mode(&dir, session_id, control, manifest, mode).await {
             Ok(rec) => Some(rec),
             Err(e) => {
                 tracing::warn!("[recorder] attach failed: {}", e);
@@ -241,7 +397,7 @@ impl SessionRecorder {
     }
 }
 
-fn spawn_tap_relay(
+pub(crate) fn spawn_tap_relay(
     session_id: String,
     source: String,
     mut rx: broadcast::Receiver<RuntimeData>,
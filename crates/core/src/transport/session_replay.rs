@@ -0,0 +1,826 @@
+//! Deterministic session replay from recorded inputs.
+//!
+//! The JSONL trace written by [`SessionRecorder`] is good for reading but
+//! not for reproducing: audio is summarised and only node *outputs* are
+//! captured. In lossless mode the recorder also writes a binary sidecar,
+//! `<dir>/<session_id>.inputs.bin`, holding every packet the
+//! [`SessionRouter`](crate::transport::SessionRouter) accepted on ingress,
+//! byte-for-byte, with its arrival offset. [`SessionReplay`] feeds those
+//! packets into a fresh session at the original (or an accelerated) pace
+//! and diffs the new outputs against the recorded trace, so a customer
+//! bug can be reproduced offline from the two files.
+//!
+//! # Sidecar format
+//!
+//! ```text
+//! "RMIN" | u16 LE version | frame(CaptureHeader) | frame(CapturedInput)*
+//! frame(T) = u32 LE length | bincode(T)
+//! ```
+//!
+//! Audio samples, video pixels and binary blobs are stored raw; every
+//! other payload is stored as its serde JSON form (bincode can't carry the
+//! `serde_json::Value` metadata those variants hold). A truncated final
+//! frame — the process died mid-write — is ignored on read.
+//!
+//! # Example
+//!
+//! ```no_run
+//! # async fn demo() -> remotemedia_core::Result<()> {
+//! use remotemedia_core::transport::session_replay::SessionReplay;
+//! use remotemedia_core::transport::PipelineExecutor;
+//!
+//! let executor = PipelineExecutor::new()?;
+//! let report = SessionReplay::open("traces/sess_42.inputs.bin")?
+//!     .with_speed(4.0)
+//!     .run(&executor)
+//!     .await?;
+//! for d in &report.divergences {
+//!     eprintln!("{}", d);
+//! }
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! [`SessionRecorder`]: crate::transport::session_recorder::SessionRecorder
+
+use crate::data::video::{PixelFormat, VideoCodec};
+use crate::data::RuntimeData;
+use crate::manifest::Manifest;
+use crate::transport::session_control::ControlAddress;
+use crate::transport::session_recorder::{spawn_tap_relay, TraceEvent};
+use crate::transport::session_router::DataPacket;
+use crate::transport::PipelineExecutor;
+use crate::{Error, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::io::{BufRead, BufReader};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::mpsc;
+
+const MAGIC: &[u8; 4] = b"RMIN";
+const VERSION: u16 = 1;
+
+/// Leading record of an input capture.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CaptureHeader {
+    /// Session the inputs were recorded from.
+    pub session_id: String,
+    /// Wall-clock start of the recording, milliseconds since UNIX epoch.
+    pub started_ms: u64,
+    /// The session's manifest as JSON, so a replay needs no other file.
+    pub manifest_json: String,
+}
+
+/// One packet accepted by the router's ingress loop.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CapturedInput {
+    /// Arrival time relative to the start of the recording.
+    pub offset_us: u64,
+    pub from_node: String,
+    pub to_node: Option<String>,
+    pub sequence: u64,
+    pub sub_sequence: u64,
+    pub data: RuntimeData,
+}
+
+impl CapturedInput {
+    pub(crate) fn from_packet(offset: Duration, packet: DataPacket) -> Self {
+        Self {
+            offset_us: offset.as_micros() as u64,
+            from_node: packet.from_node,
+            to_node: packet.to_node,
+            sequence: packet.sequence,
+            sub_sequence: packet.sub_sequence,
+            data: packet.data,
+        }
+    }
+
+    fn to_packet(&self, session_id: &str) -> DataPacket {
+        DataPacket {
+            data: self.data.clone(),
+            from_node: self.from_node.clone(),
+            to_node: self.to_node.clone(),
+            session_id: session_id.to_string(),
+            sequence: self.sequence,
+            sub_sequence: self.sub_sequence,
+            trace_context: None,
+        }
+    }
+}
+
+/// On-disk form of a [`CapturedInput`].
+#[derive(Serialize, Deserialize)]
+struct InputRecord {
+    offset_us: u64,
+    from_node: String,
+    to_node: Option<String>,
+    sequence: u64,
+    sub_sequence: u64,
+    data: WireData,
+}
+
+/// Lossless, bincode-friendly mirror of [`RuntimeData`].
+#[derive(Serialize, Deserialize)]
+enum WireData {
+    Audio {
+        samples: Vec<f32>,
+        sample_rate: u32,
+        channels: u32,
+        stream_id: Option<String>,
+        timestamp_us: Option<u64>,
+        arrival_ts_us: Option<u64>,
+        metadata: Option<String>,
+    },
+    Video {
+        pixel_data: Vec<u8>,
+        width: u32,
+        height: u32,
+        format: PixelFormat,
+        codec: Option<VideoCodec>,
+        frame_number: u64,
+        timestamp_us: u64,
+        is_keyframe: bool,
+        stream_id: Option<String>,
+        arrival_ts_us: Option<u64>,
+    },
+    Text(String),
+    Binary(Vec<u8>),
+    /// Any other variant, as serde JSON.
+    Json(String),
+}
+
+impl WireData {
+    fn encode(data: &RuntimeData) -> Result<Self> {
+        Ok(match data {
+            RuntimeData::Audio {
+                samples,
+                sample_rate,
+                channels,
+                stream_id,
+                timestamp_us,
+                arrival_ts_us,
+                metadata,
+            } => WireData::Audio {
+                samples: samples.to_vec(),
+                sample_rate: *sample_rate,
+                channels: *channels,
+                stream_id: stream_id.clone(),
+                timestamp_us: *timestamp_us,
+                arrival_ts_us: *arrival_ts_us,
+                metadata: metadata.as_ref().map(serde_json::to_string).transpose()?,
+            },
+            RuntimeData::Video {
+                pixel_data,
+                width,
+                height,
+                format,
+                codec,
+                frame_number,
+                timestamp_us,
+                is_keyframe,
+                stream_id,
+                arrival_ts_us,
+            } => WireData::Video {
+                pixel_data: pixel_data.clone(),
+                width: *width,
+                height: *height,
+                format: *format,
+                codec: *codec,
+                frame_number: *frame_number,
+                timestamp_us: *timestamp_us,
+                is_keyframe: *is_keyframe,
+                stream_id: stream_id.clone(),
+                arrival_ts_us: *arrival_ts_us,
+            },
+            RuntimeData::Text(text) => WireData::Text(text.clone()),
+            RuntimeData::Binary(bytes) => WireData::Binary(bytes.clone()),
+            other => WireData::Json(serde_json::to_string(other)?),
+        })
+    }
+
+    fn decode(self) -> Result<RuntimeData> {
+        Ok(match self {
+            WireData::Audio {
+                samples,
+                sample_rate,
+                channels,
+                stream_id,
+                timestamp_us,
+                arrival_ts_us,
+                metadata,
+            } => RuntimeData::Audio {
+                samples: samples.into(),
+                sample_rate,
+                channels,
+                stream_id,
+                timestamp_us,
+                arrival_ts_us,
+                metadata: metadata.as_deref().map(serde_json::from_str).transpose()?,
+            },
+            WireData::Video {
+                pixel_data,
+                width,
+                height,
+                format,
+                codec,
+                frame_number,
+                timestamp_us,
+                is_keyframe,
+                stream_id,
+                arrival_ts_us,
+            } => RuntimeData::Video {
+                pixel_data,
+                width,
+                height,
+                format,
+                codec,
+                frame_number,
+                timestamp_us,
+                is_keyframe,
+                stream_id,
+                arrival_ts_us,
+            },
+            WireData::Text(text) => RuntimeData::Text(text),
+            WireData::Binary(bytes) => RuntimeData::Binary(bytes),
+            WireData::Json(json) => serde_json::from_str(&json)?,
+        })
+    }
+}
+
+fn frame<T: Serialize>(value: &T) -> Result<Vec<u8>> {
+    let body = bincode::serialize(value)
+        .map_err(|e| Error::Execution(format!("input capture encode failed: {}", e)))?;
+    let mut buf = Vec::with_capacity(body.len() + 4);
+    buf.extend_from_slice(&(body.len() as u32).to_le_bytes());
+    buf.extend_from_slice(&body);
+    Ok(buf)
+}
+
+/// Encode the file preamble: magic, version and the framed header.
+pub(crate) fn encode_preamble(header: &CaptureHeader) -> Result<Vec<u8>> {
+    let mut buf = MAGIC.to_vec();
+    buf.extend_from_slice(&VERSION.to_le_bytes());
+    buf.extend(frame(header)?);
+    Ok(buf)
+}
+
+/// Encode one framed input record.
+pub(crate) fn encode_input(input: &CapturedInput) -> Result<Vec<u8>> {
+    frame(&InputRecord {
+        offset_us: input.offset_us,
+        from_node: input.from_node.clone(),
+        to_node: input.to_node.clone(),
+        sequence: input.sequence,
+        sub_sequence: input.sub_sequence,
+        data: WireData::encode(&input.data)?,
+    })
+}
+
+/// A decoded `.inputs.bin` sidecar.
+#[derive(Debug, Clone, PartialEq)]
+pub struct InputCapture {
+    pub header: CaptureHeader,
+    pub inputs: Vec<CapturedInput>,
+}
+
+impl InputCapture {
+    /// Read and decode a sidecar file.
+    pub fn read(path: impl AsRef<Path>) -> Result<Self> {
+        Self::decode(&std::fs::read(path)?)
+    }
+
+    /// Decode a sidecar from memory.
+    pub fn decode(bytes: &[u8]) -> Result<Self> {
+        if bytes.len() < 6 || &bytes[..4] != MAGIC {
+            return Err(Error::Execution("not an input capture (bad magic)".into()));
+        }
+        let version = u16::from_le_bytes([bytes[4], bytes[5]]);
+        if version != VERSION {
+            return Err(Error::Execution(format!(
+                "unsupported input capture version {}",
+                version
+            )));
+        }
+
+        let mut frames = Frames { bytes, pos: 6 };
+        let header: CaptureHeader = frames
+            .next_frame()?
+            .ok_or_else(|| Error::Execution("input capture has no header".into()))?;
+
+        let mut inputs = Vec::new();
+        while let Some(record) = frames.next_frame::<InputRecord>()? {
+            inputs.push(CapturedInput {
+                offset_us: record.offset_us,
+                from_node: record.from_node,
+                to_node: record.to_node,
+                sequence: record.sequence,
+                sub_sequence: record.sub_sequence,
+                data: record.data.decode()?,
+            });
+        }
+
+        Ok(Self { header, inputs })
+    }
+
+    /// The manifest the recorded session ran.
+    pub fn manifest(&self) -> Result<Manifest> {
+        Ok(serde_json::from_str(&self.header.manifest_json)?)
+    }
+}
+
+struct Frames<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl Frames<'_> {
+    /// Next complete frame, or `None` at EOF or on a truncated tail.
+    fn next_frame<T: for<'de> Deserialize<'de>>(&mut self) -> Result<Option<T>> {
+        let rest = &self.bytes[self.pos..];
+        if rest.len() < 4 {
+            return Ok(None);
+        }
+        let len = u32::from_le_bytes([rest[0], rest[1], rest[2], rest[3]]) as usize;
+        if rest.len() < 4 + len {
+            tracing::warn!(
+                "[replay] input capture truncated at byte {}; ignoring partial frame",
+                self.pos
+            );
+            return Ok(None);
+        }
+        self.pos += 4 + len;
+        bincode::deserialize(&rest[4..4 + len])
+            .map(Some)
+            .map_err(|e| Error::Execution(format!("input capture decode failed: {}", e)))
+    }
+}
+
+/// Read a JSONL trace written by the recorder.
+pub fn read_trace(path: impl AsRef<Path>) -> Result<Vec<TraceEvent>> {
+    let file = std::fs::File::open(path)?;
+    let mut events = Vec::new();
+    for line in BufReader::new(file).lines() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        events.push(serde_json::from_str(&line)?);
+    }
+    Ok(events)
+}
+
+/// One point where a replay's output differs from the recording.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Divergence {
+    /// Tap the frames came from, e.g. `llm.out`.
+    pub source: String,
+    /// Position of the frame within that tap's stream.
+    pub index: usize,
+    /// `(kind, payload)` recorded originally; `None` if the replay emitted extra frames.
+    pub expected: Option<(String, serde_json::Value)>,
+    /// `(kind, payload)` from the replay; `None` if the replay stopped short.
+    pub actual: Option<(String, serde_json::Value)>,
+}
+
+impl fmt::Display for Divergence {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let show = |v: &Option<(String, serde_json::Value)>| match v {
+            Some((kind, payload)) => format!("{} {}", kind, payload),
+            None => "<nothing>".to_string(),
+        };
+        write!(
+            f,
+            "{}[{}]: expected {}, got {}",
+            self.source,
+            self.index,
+            show(&self.expected),
+            show(&self.actual)
+        )
+    }
+}
+
+/// Compare two traces tap by tap.
+///
+/// Frames on different taps interleave non-deterministically, so ordering
+/// is only compared within each `source`. Recorder bookkeeping events
+/// (`lag`) are skipped on both sides.
+pub fn diff_traces(expected: &[TraceEvent], actual: &[TraceEvent]) -> Vec<Divergence> {
+    fn by_source(events: &[TraceEvent]) -> BTreeMap<&str, Vec<(&str, &serde_json::Value)>> {
+        let mut map: BTreeMap<&str, Vec<_>> = BTreeMap::new();
+        for ev in events.iter().filter(|ev| ev.kind != "lag") {
+            map.entry(ev.source.as_str())
+                .or_default()
+                .push((ev.kind.as_str(), &ev.payload));
+        }
+        map
+    }
+
+    let expected = by_source(expected);
+    let actual = by_source(actual);
+    let mut sources: Vec<&str> = expected.keys().chain(actual.keys()).copied().collect();
+    sources.sort_unstable();
+    sources.dedup();
+
+    let mut divergences = Vec::new();
+    for source in sources {
+        let exp = expected.get(source).map(Vec::as_slice).unwrap_or_default();
+        let act = actual.get(source).map(Vec::as_slice).unwrap_or_default();
+        for index in 0..exp.len().max(act.len()) {
+            let e = exp.get(index);
+            let a = act.get(index);
+            if e != a {
+                let own = |v: Option<&(&str, &serde_json::Value)>| {
+                    v.map(|(k, p)| (k.to_string(), (*p).clone()))
+                };
+                divergences.push(Divergence {
+                    source: source.to_string(),
+                    index,
+                    expected: own(e),
+                    actual: own(a),
+                });
+            }
+        }
+    }
+    divergences
+}
+
+/// Outcome of [`SessionReplay::run`].
+#[derive(Debug, Clone)]
+pub struct ReplayReport {
+    /// Id of the fresh session the inputs were fed into.
+    pub session_id: String,
+    /// Number of packets re-fed.
+    pub inputs_sent: usize,
+    /// Every tap frame the replay produced, in arrival order.
+    pub outputs: Vec<TraceEvent>,
+    /// Differences against the recorded trace (empty when none was loaded).
+    pub divergences: Vec<Divergence>,
+    /// Wall-clock duration of the replay.
+    pub elapsed: Duration,
+}
+
+impl ReplayReport {
+    /// Whether the replay reproduced the recorded outputs exactly.
+    pub fn is_match(&self) -> bool {
+        self.divergences.is_empty()
+    }
+}
+
+/// Re-feeds a recorded session into a fresh one.
+pub struct SessionReplay {
+    capture: InputCapture,
+    expected: Vec<TraceEvent>,
+    manifest: Option<Manifest>,
+    speed: f64,
+    drain_timeout: Duration,
+}
+
+impl SessionReplay {
+    /// Load a sidecar, plus the sibling `<session>.jsonl` trace if present.
+    pub fn open(inputs_path: impl AsRef<Path>) -> Result<Self> {
+        let inputs_path = inputs_path.as_ref();
+        let capture = InputCapture::read(inputs_path)?;
+        let trace_path = trace_path_for(inputs_path);
+        let expected = if trace_path.exists() {
+            read_trace(&trace_path)?
+        } else {
+            Vec::new()
+        };
+        Ok(Self::new(capture).with_expected(expected))
+    }
+
+    pub fn new(capture: InputCapture) -> Self {
+        Self {
+            capture,
+            expected: Vec::new(),
+            manifest: None,
+            speed: 1.0,
+            drain_timeout: Duration::from_secs(30),
+        }
+    }
+
+    /// Recorded outputs to diff against.
+    pub fn with_expected(mut self, expected: Vec<TraceEvent>) -> Self {
+        self.expected = expected;
+        self
+    }
+
+    /// Replay against a different manifest (e.g. with a fix applied)
+    /// instead of the one stored in the capture.
+    pub fn with_manifest(mut self, manifest: Manifest) -> Self {
+        self.manifest = Some(manifest);
+        self
+    }
+
+    /// Pace multiplier: `1.0` reproduces the original timing, `4.0` runs
+    /// four times faster, and `0.0` (or any non-finite value) feeds
+    /// packets back-to-back.
+    pub fn with_speed(mut self, speed: f64) -> Self {
+        self.speed = speed;
+        self
+    }
+
+    /// How long to wait for the pipeline to finish after the last input.
+    pub fn with_drain_timeout(mut self, timeout: Duration) -> Self {
+        self.drain_timeout = timeout;
+        self
+    }
+
+    pub fn capture(&self) -> &InputCapture {
+        &self.capture
+    }
+
+    /// Run the replay on `executor` and diff the outputs.
+    pub async fn run(&self, executor: &PipelineExecutor) -> Result<ReplayReport> {
+        let manifest = match &self.manifest {
+            Some(m) => m.clone(),
+            None => self.capture.manifest()?,
+        };
+        let node_ids: Vec<String> = manifest.nodes.iter().map(|n| n.id.clone()).collect();
+
+        let mut session = executor.create_session(Arc::new(manifest)).await?;
+        let session_id = session.session_id.clone();
+        let control = executor.control_bus().get(&session_id).ok_or_else(|| {
+            Error::Execution(format!("replay session {} has no control bus", session_id))
+        })?;
+
+        // Same tap wiring as the recorder, so both traces are shaped alike.
+        let (tx, mut rx) = mpsc::channel::<TraceEvent>(4096);
+        let mut relays = Vec::new();
+        for id in &node_ids {
+            let taps = control.subscribe(&ControlAddress::node_out(id))?;
+            relays.push(spawn_tap_relay(
+                session_id.clone(),
+                format!("{}.out", id),
+                taps,
+                tx.clone(),
+            ));
+        }
+        drop(tx);
+        drop(control);
+
+        // Drain client outputs concurrently so a full output channel can't
+        // stall ingress while we pace inputs.
+        let drain = async {
+            let started = Instant::now();
+            for input in &self.capture.inputs {
+                if self.speed.is_finite() && self.speed > 0.0 {
+                    let due = Duration::from_micros(input.offset_us).div_f64(self.speed);
+                    tokio::time::sleep_until((started + due).into()).await;
+                }
+                session.send_packet(input.to_packet(&session_id)).await?;
+            }
+            session.signal_input_complete();
+
+            let finished = tokio::time::timeout(self.drain_timeout, async {
+                while session.recv_output().await?.is_some() {}
+                Ok::<_, Error>(())
+            })
+            .await;
+            match finished {
+                Ok(result) => result?,
+                Err(_) => {
+                    tracing::warn!(
+                        "[replay] session {} still running after {:?}; diffing partial output",
+                        session_id,
+                        self.drain_timeout
+                    );
+                    let _ = session.close().await;
+                }
+            }
+            Ok::<_, Error>(started.elapsed())
+        };
+        let elapsed = drain.await?;
+
+        // The router has exited, so no further taps fire. Collect what the
+        // relays forwarded, then stop them (they only end on their own once
+        // every holder of the session's control bus lets go).
+        let mut outputs = Vec::new();
+        while let Ok(Some(ev)) = tokio::time::timeout(Duration::from_millis(200), rx.recv()).await {
+            outputs.push(ev);
+        }
+        for relay in relays {
+            relay.abort();
+        }
+
+        let divergences = if self.expected.is_empty() {
+            Vec::new()
+        } else {
+            diff_traces(&self.expected, &outputs)
+        };
+
+        Ok(ReplayReport {
+            session_id,
+            inputs_sent: self.capture.inputs.len(),
+            outputs,
+            divergences,
+            elapsed,
+        })
+    }
+}
+
+/// `<dir>/<session>.inputs.bin` → `<dir>/<session>.jsonl`
+pub fn trace_path_for(inputs_path: &Path) -> PathBuf {
+    let name = inputs_path
+        .file_name()
+        .and_then(|n| n.to_str())
+        .unwrap_or_default();
+    let stem = name.strip_suffix(".inputs.bin").unwrap_or(name);
+    inputs_path.with_file_name(format!("{}.jsonl", stem))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::manifest::{ManifestMetadata, NodeManifest};
+    use crate::transport::session_recorder::{RecordMode, SessionRecorder};
+    use crate::transport::TransportData;
+    use serde_json::json;
+
+    fn calc_manifest() -> Manifest {
+        Manifest {
+            version: "v1".to_string(),
+            metadata: ManifestMetadata {
+                name: "replay".to_string(),
+                ..Default::default()
+            },
+            nodes: vec![NodeManifest {
+                id: "calc".to_string(),
+                node_type: "CalculatorNode".to_string(),
+                params: json!({}),
+                ..Default::default()
+            }],
+            connections: Vec::new(),
+            python_env: None,
+        }
+    }
+
+    fn add(a: f64, b: f64) -> RuntimeData {
+        RuntimeData::Json(json!({ "operation": "add", "operands": [a, b] }))
+    }
+
+    #[test]
+    fn sidecar_round_trips_losslessly() {
+        let header = CaptureHeader {
+            session_id: "s1".into(),
+            started_ms: 1_712_345_678_900,
+            manifest_json: serde_json::to_string(&calc_manifest()).unwrap(),
+        };
+        let inputs = vec![
+            CapturedInput {
+                offset_us: 0,
+                from_node: "client".into(),
+                to_node: None,
+                sequence: 1,
+                sub_sequence: 1,
+                data: RuntimeData::Audio {
+                    samples: vec![0.1, -0.25, f32::MIN_POSITIVE, 1.0].into(),
+                    sample_rate: 48_000,
+                    channels: 2,
+                    stream_id: Some("mic".into()),
+                    timestamp_us: Some(20_000),
+                    arrival_ts_us: None,
+                    metadata: Some(json!({ "speaker": "spk_0" })),
+                },
+            },
+            CapturedInput {
+                offset_us: 20_123,
+                from_node: "__control__:s1".into(),
+                to_node: Some("llm".into()),
+                sequence: 0,
+                sub_sequence: 0,
+                data: RuntimeData::Json(json!({ "__aux_port__": "context", "payload": "hi" })),
+            },
+            CapturedInput {
+                offset_us: 40_000,
+                from_node: "client".into(),
+                to_node: None,
+                sequence: 2,
+                sub_sequence: 2,
+                data: RuntimeData::Binary(vec![0, 1, 2, 255]),
+            },
+        ];
+
+        let mut bytes = encode_preamble(&header).unwrap();
+        for input in &inputs {
+            bytes.extend(encode_input(input).unwrap());
+        }
+
+        let decoded = InputCapture::decode(&bytes).unwrap();
+        assert_eq!(decoded.header, header);
+        assert_eq!(decoded.inputs, inputs);
+        assert_eq!(decoded.manifest().unwrap().nodes[0].id, "calc");
+
+        // A crash mid-write leaves a partial frame; everything before it survives.
+        bytes.truncate(bytes.len() - 3);
+        assert_eq!(InputCapture::decode(&bytes).unwrap().inputs.len(), 2);
+
+        assert!(InputCapture::decode(b"nope").is_err());
+    }
+
+    #[test]
+    fn diff_compares_per_source() {
+        let ev = |source: &str, payload: serde_json::Value| TraceEvent {
+            ts_ms: 0,
+            session_id: "s".into(),
+            source: source.into(),
+            kind: "json".into(),
+            payload,
+        };
+        let expected = vec![
+            ev("a.out", json!(1)),
+            ev("b.out", json!("x")),
+            ev("a.out", json!(2)),
+        ];
+        // Cross-tap interleaving differs; per-tap order matches.
+        let reordered = vec![
+            ev("a.out", json!(1)),
+            ev("a.out", json!(2)),
+            ev("b.out", json!("x")),
+        ];
+        assert!(diff_traces(&expected, &reordered).is_empty());
+
+        let changed = vec![ev("a.out", json!(1)), ev("a.out", json!(3))];
+        let diffs = diff_traces(&expected, &changed);
+        assert_eq!(diffs.len(), 2);
+        assert_eq!(diffs[0].source, "a.out");
+        assert_eq!(diffs[0].index, 1);
+        assert_eq!(diffs[1].source, "b.out");
+        assert!(diffs[1].actual.is_none());
+    }
+
+    #[tokio::test]
+    async fn recorded_session_replays_identically() {
+        let dir = tempfile::tempdir().unwrap();
+        let executor = PipelineExecutor::new().unwrap();
+        let manifest = calc_manifest();
+
+        // Record a live session in lossless mode.
+        let mut session = executor
+            .create_session(Arc::new(manifest.clone()))
+            .await
+            .unwrap();
+        let control = executor.control_bus().get(&session.session_id).unwrap();
+        let recorder = SessionRecorder::attach_with_mode(
+            dir.path(),
+            session.session_id.clone(),
+            control,
+            &manifest,
+            RecordMode::Lossless,
+        )
+        .await
+        .unwrap();
+
+        for (i, (a, b)) in [(1.0, 2.0), (10.0, 5.5), (-3.0, 3.0)]
+            .into_iter()
+            .enumerate()
+        {
+            session
+                .send_input(TransportData::new(add(a, b)).with_sequence(i as u64))
+                .await
+                .unwrap();
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        session.signal_input_complete();
+        while session.recv_output().await.unwrap().is_some() {}
+        let inputs_path = recorder.inputs_path().unwrap().to_path_buf();
+        recorder.finish().await.unwrap();
+
+        let replay = SessionReplay::open(&inputs_path).unwrap();
+        assert_eq!(replay.capture().inputs.len(), 3);
+        assert!(replay.capture().inputs[2].offset_us >= 20_000);
+        assert_eq!(replay.expected.len(), 3);
+
+        let report = replay.with_speed(0.0).run(&executor).await.unwrap();
+        assert_eq!(report.inputs_sent, 3);
+        assert_eq!(report.outputs.len(), 3);
+        assert!(report.is_match(), "divergences: {:?}", report.divergences);
+
+        // A different pipeline diverges and the report says where.
+        let mut changed = manifest;
+        changed.nodes[0].id = "calc2".into();
+        let report = SessionReplay::open(&inputs_path)
+            .unwrap()
+            .with_manifest(changed)
+            .with_speed(0.0)
+            .run(&executor)
+            .await
+            .unwrap();
+        assert!(!report.is_match());
+        assert!(report
+            .divergences
+            .iter()
+            .any(|d| d.source == "calc.out" && d.actual.is_none()));
+    }
+
+    #[test]
+    fn trace_path_is_sibling_jsonl() {
+        assert_eq!(
+            trace_path_for(Path::new("/tmp/t/sess_1.inputs.bin")),
+            PathBuf::from("/tmp/t/sess_1.jsonl")
+        );
+    }
+}
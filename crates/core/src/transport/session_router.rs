@@ -39,21 +39,27 @@
 use crate::capabilities::{CapabilityBehavior, CapabilityResolver, ResolutionContext};
 use crate::data::RuntimeData;
 use crate::executor::{
-    DriftMetrics, DriftThresholds, NodeStats, PipelineGraph, SchedulerConfig, StreamingScheduler,
+    DriftMetrics, DriftThresholds, EdgeFilter, NodeStats, PipelineGraph, SchedulerConfig,
+    StreamingScheduler,
 };
-use crate::manifest::Manifest;
+use crate::manifest::{Manifest, ManifestPythonEnv, NodeManifest, Overflow};
+use crate::nodes::schema::{NodeSchema, MAIN_PORT};
 use crate::nodes::{InitializeContext, StreamingNode, StreamingNodeRegistry};
+use crate::transport::admission::{AdmissionPermit, SessionCost};
 use crate::transport::perf_aggregator::{spawn_flush_task, PerfAggregator};
+use crate::transport::pipeline_patch::{PatchReport, PipelinePatch};
 use crate::transport::session_control::{
-    aux_port_of, CloseReason, SessionControl, BARGE_IN_PORT, PERF_PORT,
+    aux_port_of, wrap_aux_port, CloseReason, SessionControl, BARGE_IN_PORT, PERF_PORT,
 };
+use crate::telemetry::{self, TraceContext};
 use crate::Result;
 use parking_lot::RwLock as DriftRwLock;
 use std::collections::HashMap;
 use std::sync::Arc;
-use tokio::sync::mpsc;
+use tokio::sync::{mpsc, oneshot};
 use tokio::sync::RwLock;
 use tokio::task::JoinHandle;
+use tracing::Instrument;
 
 /// Default capacity for the router's internal input channel.
 ///
@@ -97,8 +103,323 @@ pub struct DataPacket {
     pub sequence: u64,
     /// Sub-sequence for streaming outputs (multiple outputs per input)
     pub sub_sequence: u64,
+    /// W3C trace context the first node dispatch is parented on
+    pub trace_context: Option<TraceContext>,
 }
 
+/// One frame on a node's input or fan-out channel
+///
+/// `trace` is the context of the dispatch that produced `data` (or, at a
+/// source node, of the ingress packet) so the next node's dispatch span
+/// can be parented on it.
+#[derive(Clone)]
+struct NodeInput {
+    data: RuntimeData,
+    trace: Option<TraceContext>,
+}
+
+/// A node's stand-in, swapped in at runtime
+///
+/// Describes the candidate after the running one in the node's placement
+/// order (see [`SessionRouter::create_placed_node`]). The stand-in is only
+/// created and initialized when the switch happens, so a healthy node
+/// never pays for its fallback.
+struct Standby {
+    /// Pipeline node the stand-in takes over
+    node_id: String,
+    /// Manifest ID of the running candidate
+    from: String,
+    /// Manifest ID of the stand-in
+    to: String,
+    /// Manifest entry of the stand-in
+    spec: NodeManifest,
+    /// Pipeline-level Python environment the stand-in is created against
+    python_env: Option<ManifestPythonEnv>,
+    registry: Arc<StreamingNodeRegistry>,
+    control: Option<Arc<SessionControl>>,
+    admission: Option<SessionAdmission>,
+}
+
+impl Standby {
+    /// Create and initialize the stand-in
+    ///
+    /// The stand-in is admitted first; if it fails to start, the session's
+    /// share goes back to the running candidate.
+    async fn build(&self, session_id: &str) -> Result<Box<dyn StreamingNode>> {
+        if let Some(admission) = &self.admission {
+            admission.switch(&self.node_id, &self.to)?;
+        }
+        let node = create_node(
+            &self.registry,
+            session_id,
+            self.control.as_ref(),
+            &self.spec,
+            self.python_env.as_ref(),
+        )
+        .await;
+        if let (Err(_), Some(admission)) = (&node, &self.admission) {
+            admission.switch_back(&self.node_id, &self.from);
+        }
+        node
+    }
+}
+
+/// A session's [`AdmissionPermit`], resized as its running graph changes
+///
+/// Tracks the manifest the router runs and which nodes run as one of
+/// their fallbacks, so a patch or a fallback switch is charged before the
+/// nodes it brings in are created.
+#[derive(Clone)]
+struct SessionAdmission {
+    inner: Arc<std::sync::Mutex<AdmittedGraph>>,
+}
+
+struct AdmittedGraph {
+    permit: AdmissionPermit,
+    manifest: Arc<Manifest>,
+    /// Node ID to the manifest entry it runs as, for nodes that do not
+    /// run as themselves
+    running: HashMap<String, String>,
+}
+
+impl AdmittedGraph {
+    fn cost(&self, manifest: &Manifest, running: &HashMap<String, String>) -> SessionCost {
+        SessionCost::estimate_running(manifest, self.permit.config(), running)
+    }
+}
+
+impl SessionAdmission {
+    fn new(mut permit: AdmissionPermit, manifest: Arc<Manifest>) -> Self {
+        // The router's graph may differ from the one admitted (nodes with
+        // a `host` run behind a RemotePipelineNode)
+        permit.restore(SessionCost::estimate(&manifest, permit.config()));
+        Self {
+            inner: Arc::new(std::sync::Mutex::new(AdmittedGraph {
+                permit,
+                manifest,
+                running: HashMap::new(),
+            })),
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, AdmittedGraph> {
+        self.inner.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Charge `node_id` running as `candidate`
+    ///
+    /// Fails with [`crate::Error::AdmissionRejected`] if the session's
+    /// share cannot grow accordingly, leaving it unchanged.
+    fn switch(&self, node_id: &str, candidate: &str) -> Result<()> {
+        let mut graph = self.lock();
+        let mut running = graph.running.clone();
+        if node_id == candidate {
+            running.remove(node_id);
+        } else {
+            running.insert(node_id.to_string(), candidate.to_string());
+        }
+        let cost = graph.cost(&graph.manifest, &running);
+        graph.permit.resize(cost)?;
+        graph.running = running;
+        Ok(())
+    }
+
+    /// Undo [`Self::switch`] after the candidate failed to start
+    fn switch_back(&self, node_id: &str, candidate: &str) {
+        let mut graph = self.lock();
+        if node_id == candidate {
+            graph.running.remove(node_id);
+        } else {
+            graph
+                .running
+                .insert(node_id.to_string(), candidate.to_string());
+        }
+        let cost = graph.cost(&graph.manifest, &graph.running);
+        graph.permit.restore(cost);
+    }
+
+    /// Charge the session running the patched `manifest`
+    ///
+    /// `fresh` nodes are charged as themselves; placing them on a fallback
+    /// is a [`Self::switch`] of its own. Returns what to hand
+    /// [`Self::rollback`] if the patch is abandoned.
+    fn patch<'a>(
+        &self,
+        manifest: Arc<Manifest>,
+        fresh: impl IntoIterator<Item = &'a String>,
+    ) -> Result<(Arc<Manifest>, HashMap<String, String>)> {
+        let mut graph = self.lock();
+        let mut running = graph.running.clone();
+        for node_id in fresh {
+            running.remove(node_id);
+        }
+        running.retain(|node_id, _| manifest.nodes.iter().any(|n| &n.id == node_id));
+        let cost = graph.cost(&manifest, &running);
+        graph.permit.resize(cost)?;
+        let previous_manifest = std::mem::replace(&mut graph.manifest, manifest);
+        let previous_running = std::mem::replace(&mut graph.running, running);
+        Ok((previous_manifest, previous_running))
+    }
+
+    /// Return to the share held before an abandoned [`Self::patch`]
+    fn rollback(&self, (manifest, running): (Arc<Manifest>, HashMap<String, String>)) {
+        let mut graph = self.lock();
+        let cost = graph.cost(&manifest, &running);
+        graph.permit.restore(cost);
+        graph.manifest = manifest;
+        graph.running = running;
+    }
+}
+
+/// Publish a `node_fallback` event on `__system__.out`
+///
+/// `node` is the pipeline node whose implementation changed, `from` and
+/// `to` the manifest entries involved; `reason` is `init_failed` or
+/// `circuit_open`.
+fn publish_fallback_event(
+    control: Option<&SessionControl>,
+    node: &str,
+    from: &str,
+    to: &str,
+    reason: &str,
+    message: String,
+) {
+    let Some(ctrl) = control else {
+        return;
+    };
+    let ts = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0);
+    ctrl.publish_tap(
+        "__system__",
+        None,
+        RuntimeData::Json(serde_json::json!({
+            "kind": "node_fallback",
+            "node": node,
+            "from": from,
+            "to": to,
+            "reason": reason,
+            "message": message,
+            "ts_ms": ts,
+        })),
+    );
+}
+
+/// Create and initialize one node from its manifest entry
+///
+/// `python_env` is the pipeline-level Python environment of the manifest
+/// the node belongs to.
+async fn create_node(
+    registry: &StreamingNodeRegistry,
+    session_id: &str,
+    control: Option<&Arc<SessionControl>>,
+    node_spec: &NodeManifest,
+    python_env: Option<&ManifestPythonEnv>,
+) -> Result<Box<dyn StreamingNode>> {
+    // Inject manifest-level python dependency info into params
+    // so the multiprocess executor can provision the right venv
+    let mut params = node_spec.params.clone();
+    if let Some(ref py_deps) = node_spec.python_deps {
+        if let Some(obj) = params.as_object_mut() {
+            obj.insert("__python_deps__".to_string(), serde_json::json!(py_deps));
+        }
+    }
+    if let Some(ref restart) = node_spec.restart {
+        if let Some(obj) = params.as_object_mut() {
+            obj.insert(
+                crate::python::multiprocess::supervisor::RESTART_POLICY_PARAM.to_string(),
+                serde_json::json!(restart),
+            );
+        }
+    }
+    if let Some(py_env) = python_env {
+        if !py_env.extra_deps.is_empty() {
+            if let Some(obj) = params.as_object_mut() {
+                obj.insert(
+                    "__python_extra_deps__".to_string(),
+                    serde_json::json!(py_env.extra_deps),
+                );
+            }
+        }
+    }
+
+    let node = registry.create_node(
+        &node_spec.node_type,
+        node_spec.id.clone(),
+        &params,
+        Some(session_id.to_string()),
+    )?;
+
+    // Initialize the node (load models, etc.)
+    // Pass the InitializeContext so nodes can emit progress events.
+    let init_ctx = InitializeContext {
+        session_id: session_id.to_string(),
+        node_id: node_spec.id.clone(),
+        control: control.cloned(),
+    };
+    node.initialize(&init_ctx).await?;
+    Ok(node)
+}
+
+/// One outgoing edge as seen by a node's fan-out task
+struct SuccessorEdge {
+    /// Source output port; `None`/`main` forwards every output
+    from_port: Option<String>,
+    /// Target input port; a named port delivers via the aux-port envelope
+    to_port: Option<String>,
+    /// Edge predicate; `None` forwards everything on the port
+    filter: Option<EdgeFilter>,
+    /// Block or drop when the target's input is full
+    overflow: Overflow,
+    tx: mpsc::Sender<NodeInput>,
+}
+
+impl SuccessorEdge {
+    fn is_port_filtered(&self) -> bool {
+        self.from_port.as_deref().is_some_and(|p| p != MAIN_PORT)
+    }
+
+    fn carries(&self, port: &str) -> bool {
+        match self.from_port.as_deref() {
+            None | Some(MAIN_PORT) => true,
+            Some(p) => p == port,
+        }
+    }
+
+    fn accepts(&self, port: &str, data: &RuntimeData) -> bool {
+        self.carries(port) && self.filter.as_ref().map_or(true, |f| f.matches(data))
+    }
+
+    fn deliver(&self, data: RuntimeData) -> RuntimeData {
+        match self.to_port.as_deref() {
+            None | Some(MAIN_PORT) => data,
+            Some(port) => wrap_aux_port(port, data),
+        }
+    }
+}
+
+/// Where a node's outputs go: its successor edges plus, for sinks, the
+/// client channel.
+struct NodeRouting {
+    successors: Vec<SuccessorEdge>,
+    /// Classifies outputs into named ports; only set when some edge is
+    /// port-filtered
+    port_schema: Option<NodeSchema>,
+    client_tx: Option<mpsc::Sender<RuntimeData>>,
+}
+
+/// A node's routing, swappable while its fan-out task runs.
+///
+/// The fan-out task takes a snapshot per output, so a pipeline patch can
+/// rewire a live node without restarting it. Dropping every copy of a
+/// routing also drops its senders, which is what lets a downstream node
+/// see its input close.
+type RoutingCell = Arc<DriftRwLock<Arc<NodeRouting>>>;
+
+/// A pipeline patch queued for the router loop, with its reply channel
+type PatchRequest = (PipelinePatch, oneshot::Sender<Result<PatchReport>>);
+
 /// Session-persistent router that processes data through the pipeline graph
 ///
 /// This router:
@@ -106,6 +427,7 @@ pub struct DataPacket {
 /// - Caches node instances for the session lifetime
 /// - Executes nodes in topological order
 /// - Handles fan-in (multiple inputs) and fan-out (multiple outputs)
+/// - Applies per-connection port selection and edge filters on fan-out
 /// - Only sends outputs from sink nodes to the client
 /// - Integrates StreamingScheduler for timeout, retry, and circuit breaker (spec 026)
 /// - Tracks per-stream drift metrics for health monitoring (spec 026)
@@ -113,9 +435,19 @@ pub struct SessionRouter {
     /// Session ID
     session_id: String,
 
-    /// Pipeline manifest
+    /// Pipeline manifest, with nodes that declare a `host` wrapped by
+    /// [`crate::executor::wrap_remote_hosts`]
     manifest: Arc<Manifest>,
 
+    /// The manifest as declared and last patched, shared with every
+    /// [`RouterHandle`]. Patches apply to it, so they address remote
+    /// nodes by their own ids.
+    live_manifest: Arc<DriftRwLock<Arc<Manifest>>>,
+
+    /// Id of the wrapper each remote node runs in, for inputs published
+    /// to a node inside a remote pipeline
+    remote_members: HashMap<String, String>,
+
     /// Pipeline graph (topological order, sources, sinks)
     graph: PipelineGraph,
 
@@ -125,6 +457,15 @@ pub struct SessionRouter {
     /// Cached node instances (created once per session)
     cached_nodes: HashMap<String, Box<dyn StreamingNode>>,
 
+    /// Stand-ins for cached nodes that declare a fallback, moved into the
+    /// node's task alongside it
+    standby_nodes: HashMap<String, Standby>,
+
+    /// Admission permit the session was created under, if any. Attached
+    /// via [`Self::attach_admission`]; patches and fallback switches are
+    /// charged against it.
+    admission: Option<SessionAdmission>,
+
     /// Channel to send outputs to client.
     ///
     /// Bounded — caller picks the capacity when creating the channel. Sends
@@ -152,11 +493,19 @@ pub struct SessionRouter {
     /// Shutdown signal sender (held externally)
     _shutdown_tx: mpsc::Sender<()>,
 
+    /// Pipeline patches submitted through [`RouterHandle::apply_patch`].
+    /// Receiver wrapped like [`Self::input_rx`].
+    patch_tx: mpsc::Sender<PatchRequest>,
+    patch_rx: std::sync::Mutex<Option<mpsc::Receiver<PatchRequest>>>,
+
     /// Capability resolution context (spec 025)
     /// Stores pending capability updates for downstream nodes
     resolution_ctx: Option<ResolutionContext>,
 
     /// StreamingScheduler for node execution with timeout, retry, circuit breaker (spec 026)
+    ///
+    /// Owned by this session alone, so its per-node breakers (and a reset
+    /// when a node falls back) never reach another session.
     scheduler: Arc<StreamingScheduler>,
 
     /// Per-stream drift metrics for health monitoring (spec 026).
@@ -215,15 +564,24 @@ const NODE_INPUT_CAPACITY: usize = 16;
 /// normal TTS/VAD burst patterns while still bounding memory growth.
 const NODE_FANOUT_CAPACITY: usize = 1024;
 
+/// How long a pipeline patch waits for removed, restarted or replaced
+/// nodes to drain their queued input before aborting them.
+const PATCH_DRAIN_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(5);
+
 /// Per-node task handles + input sender map, owned by the router for the
-/// lifetime of the session. Built in [`SessionRouter::spawn_pipeline_tasks`]
-/// and torn down in [`SessionRouter::teardown_pipeline_tasks`].
+/// lifetime of the session. Built in [`SessionRouter::spawn_pipeline_tasks`],
+/// edited in place by [`SessionRouter::apply_patch`] and torn down in
+/// [`SessionRouter::teardown_pipeline_tasks`].
 struct PipelineTasks {
     /// Input sender for each node (keyed by node id). The router pushes
     /// source-bound and `to_node`-addressed packets through these.
-    input_txs: HashMap<String, mpsc::Sender<RuntimeData>>,
-    /// All spawned tasks (main + fan-out per node). Awaited on shutdown.
-    handles: Vec<JoinHandle<()>>,
+    input_txs: HashMap<String, mpsc::Sender<NodeInput>>,
+    /// Output routing for each node, shared with its fan-out task.
+    routing: HashMap<String, RoutingCell>,
+    /// Spawned tasks per node (main + fan-out). Awaited on shutdown.
+    handles: HashMap<String, Vec<JoinHandle<()>>>,
+    /// Patch tasks still draining retired nodes. Awaited on shutdown.
+    retiring: Vec<JoinHandle<()>>,
 }
 
 impl SessionRouter {
@@ -282,8 +640,17 @@ impl SessionRouter {
         scheduler_config: Option<SchedulerConfig>,
         drift_thresholds: Option<DriftThresholds>,
     ) -> Result<(Self, mpsc::Sender<()>)> {
+        // Nodes with a `host` run remotely behind a RemotePipelineNode
+        let source = manifest;
+        let manifest = match crate::executor::wrap_remote_hosts(&source)? {
+            Some(wrapped) => Arc::new(wrapped),
+            None => source.clone(),
+        };
+        let remote_members = crate::executor::remote_members(&manifest);
+
         // Build and validate the pipeline graph
         let graph = PipelineGraph::from_manifest(&manifest)?;
+        graph.validate_ports(|node_type| registry.get_schema(node_type))?;
         tracing::info!(
             "Session {}: Built pipeline graph with {} nodes, execution_order: {:?}, sources: {:?}, sinks: {:?}",
             session_id,
@@ -303,6 +670,7 @@ impl SessionRouter {
         let (input_tx, input_rx) = mpsc::channel(input_capacity_from_env());
         let (shutdown_tx, shutdown_rx) = mpsc::channel(1);
         let shutdown_tx_clone = shutdown_tx.clone();
+        let (patch_tx, patch_rx) = mpsc::channel(1);
 
         // Create scheduler config, extracting fast_path nodes from manifest
         let mut config = scheduler_config.unwrap_or_default();
@@ -328,15 +696,21 @@ impl SessionRouter {
 
         let router = Self {
             session_id,
+            live_manifest: Arc::new(DriftRwLock::new(source)),
+            remote_members,
             manifest,
             graph,
             registry,
             cached_nodes: HashMap::new(),
+            standby_nodes: HashMap::new(),
+            admission: None,
             output_tx,
             input_rx: std::sync::Mutex::new(Some(input_rx)),
             input_tx: Some(input_tx),
             shutdown_rx: std::sync::Mutex::new(Some(shutdown_rx)),
             _shutdown_tx: shutdown_tx,
+            patch_tx,
+            patch_rx: std::sync::Mutex::new(Some(patch_rx)),
             resolution_ctx: None,
             scheduler,
             drift_metrics: Arc::new(dashmap::DashMap::new()),
@@ -364,9 +738,37 @@ impl SessionRouter {
             .clone()
             .expect("attach_control must be called before run() consumes input_tx");
         control.attach_input_sender(input_tx).await;
+        control.attach_router(self.handle());
         self.control = Some(control);
     }
 
+    /// Hand this router the [`AdmissionPermit`] its session was admitted
+    /// with.
+    ///
+    /// The router holds it until it exits and keeps it sized to what the
+    /// session runs: a live patch or a fallback switch that would exceed
+    /// the quotas fails with [`crate::Error::AdmissionRejected`]. Must be
+    /// called before `start()` / `run()`.
+    pub fn attach_admission(&mut self, permit: AdmissionPermit) {
+        self.admission = Some(SessionAdmission::new(permit, self.manifest.clone()));
+    }
+
+    /// Cloneable view of this router's stats plus its shutdown switch.
+    ///
+    /// Stays valid after `run()` consumes the router; the stats simply
+    /// stop changing once the session ends.
+    pub fn handle(&self) -> RouterHandle {
+        RouterHandle {
+            session_id: self.session_id.clone(),
+            manifest: self.live_manifest.clone(),
+            started_at: std::time::SystemTime::now(),
+            scheduler: self.scheduler.clone(),
+            drift_metrics: self.drift_metrics.clone(),
+            shutdown_tx: self._shutdown_tx.clone(),
+            patch_tx: self.patch_tx.clone(),
+        }
+    }
+
     /// Get the input sender for feeding data to the router.
     ///
     /// The returned sender is bounded; callers should `.send(...).await`
@@ -414,66 +816,45 @@ impl SessionRouter {
             Some(format!("Starting {} nodes", self.manifest.nodes.len())),
         );
 
-        for node_spec in &self.manifest.nodes {
+        let manifest = self.manifest.clone();
+        let standby_ids = manifest.standby_nodes()?;
+        for node_spec in &manifest.nodes {
+            // Fallback nodes are created with the node they back up
+            if standby_ids.contains(node_spec.id.as_str()) {
+                continue;
+            }
+
             // Emit per-node loading event.
             self.emit_loading_event(
                 "loading_node",
                 Some(format!("Loading {} ({})", node_spec.id, node_spec.node_type)),
             );
 
-            // Inject manifest-level python dependency info into params
-            // so the multiprocess executor can provision the right venv
-            let mut params = node_spec.params.clone();
-            if let Some(ref py_deps) = node_spec.python_deps {
-                if let Some(obj) = params.as_object_mut() {
-                    obj.insert(
-                        "__python_deps__".to_string(),
-                        serde_json::json!(py_deps),
-                    );
-                }
-            }
-            if let Some(ref py_env) = self.manifest.python_env {
-                if !py_env.extra_deps.is_empty() {
-                    if let Some(obj) = params.as_object_mut() {
-                        obj.insert(
-                            "__python_extra_deps__".to_string(),
-                            serde_json::json!(py_env.extra_deps),
-                        );
+            let node = match self.create_placed_node(node_spec, &manifest).await {
+                Ok((node, standby)) => {
+                    if let Some(standby) = standby {
+                        self.standby_nodes.insert(node_spec.id.clone(), standby);
                     }
+                    node
+                }
+                Err(e) => {
+                    tracing::error!(
+                        session_id = %self.session_id,
+                        node_id = %node_spec.id,
+                        node_type = %node_spec.node_type,
+                        error = %e,
+                        "Node initialization failed; aborting session startup"
+                    );
+                    self.emit_loading_event(
+                        "error",
+                        Some(format!(
+                            "Node '{}' ({}) failed to initialize: {}",
+                            node_spec.id, node_spec.node_type, e
+                        )),
+                    );
+                    return Err(e);
                 }
-            }
-
-            let node = self.registry.create_node(
-                &node_spec.node_type,
-                node_spec.id.clone(),
-                &params,
-                Some(self.session_id.clone()),
-            )?;
-
-            // Initialize the node (load models, etc.)
-            // Pass the InitializeContext so nodes can emit progress events.
-            let init_ctx = InitializeContext {
-                session_id: self.session_id.clone(),
-                node_id: node_spec.id.clone(),
-                control: self.control.clone(),
             };
-            if let Err(e) = node.initialize(&init_ctx).await {
-                tracing::error!(
-                    session_id = %self.session_id,
-                    node_id = %node_spec.id,
-                    node_type = %node_spec.node_type,
-                    error = %e,
-                    "Node initialization failed; aborting session startup"
-                );
-                self.emit_loading_event(
-                    "error",
-                    Some(format!(
-                        "Node '{}' ({}) failed to initialize: {}",
-                        node_spec.id, node_spec.node_type, e
-                    )),
-                );
-                return Err(e);
-            }
 
             self.cached_nodes.insert(node_spec.id.clone(), node);
             tracing::debug!(
@@ -500,6 +881,109 @@ impl SessionRouter {
         Ok(())
     }
 
+    /// Create a pipeline node from the first candidate its placement allows.
+    ///
+    /// The candidates are the node and its declared fallback, ordered by
+    /// [`Placement::order`](crate::manifest::Placement::order). A candidate
+    /// that cannot be created or initialized, or that admission refuses, is
+    /// skipped; starting a later one is announced as a `node_fallback`
+    /// event. The candidate after the
+    /// one started, if any, is returned as the node's [`Standby`], to be
+    /// created if the node's circuit breaker opens.
+    async fn create_placed_node(
+        &self,
+        node_spec: &NodeManifest,
+        manifest: &Manifest,
+    ) -> Result<(Box<dyn StreamingNode>, Option<Standby>)> {
+        let fallback = node_spec
+            .fallback()
+            .and_then(|id| manifest.nodes.iter().find(|n| n.id == id));
+        let candidates: Vec<&NodeManifest> = std::iter::once(node_spec).chain(fallback).collect();
+        let placement = node_spec.placement();
+        let ordered = placement.order(&candidates);
+        if ordered.is_empty() {
+            return Err(crate::Error::Manifest(format!(
+                "Node '{}': no candidate matches placement {:?}",
+                node_spec.id, placement
+            )));
+        }
+
+        let mut failed: Option<(&str, crate::Error)> = None;
+        for (i, candidate) in ordered.iter().enumerate() {
+            let admitted = match &self.admission {
+                Some(admission) => admission.switch(&node_spec.id, &candidate.id),
+                None => Ok(()),
+            };
+            let created = match admitted {
+                Ok(()) => self.create_node(candidate, manifest).await,
+                Err(e) => Err(e),
+            };
+            let node = match created {
+                Ok(node) => node,
+                Err(e) => {
+                    if i + 1 < ordered.len() {
+                        tracing::warn!(
+                            session_id = %self.session_id,
+                            node_id = %node_spec.id,
+                            candidate = %candidate.id,
+                            error = %e,
+                            "Node candidate failed to initialize; trying the next one"
+                        );
+                    }
+                    failed = Some((&candidate.id, e));
+                    continue;
+                }
+            };
+            if let Some((from, e)) = failed {
+                publish_fallback_event(
+                    self.control.as_deref(),
+                    &node_spec.id,
+                    from,
+                    &candidate.id,
+                    "init_failed",
+                    e.to_string(),
+                );
+            }
+
+            let standby = ordered.get(i + 1).map(|next| Standby {
+                node_id: node_spec.id.clone(),
+                from: candidate.id.clone(),
+                to: next.id.clone(),
+                spec: (*next).clone(),
+                python_env: manifest.python_env.clone(),
+                registry: self.registry.clone(),
+                control: self.control.clone(),
+                admission: self.admission.clone(),
+            });
+            return Ok((node, standby));
+        }
+
+        // `ordered` is non-empty, so the loop recorded an error
+        Err(failed.map(|(_, e)| e).unwrap_or_else(|| {
+            crate::Error::Execution(format!("Node '{}' has no candidates (bug)", node_spec.id))
+        }))
+    }
+
+    /// Create and initialize one node from its manifest entry.
+    ///
+    /// `manifest` supplies the pipeline-level Python environment; it is
+    /// passed explicitly because a pipeline patch builds nodes against the
+    /// patched manifest before committing it.
+    async fn create_node(
+        &self,
+        node_spec: &NodeManifest,
+        manifest: &Manifest,
+    ) -> Result<Box<dyn StreamingNode>> {
+        create_node(
+            &self.registry,
+            &self.session_id,
+            self.control.as_ref(),
+            node_spec,
+            manifest.python_env.as_ref(),
+        )
+        .await
+    }
+
     /// Emit a loading-state event on the control bus.
     ///
     /// Clients subscribed to `__system__.out` receive these as JSON events
@@ -674,7 +1158,7 @@ impl SessionRouter {
         // same scheduler tick, instead of being batched into a `Vec` until
         // A's `process_streaming_async` returns. Sink yields go straight to
         // the client `output_tx`. See `spawn_pipeline_tasks` for the wiring.
-        let pipeline = self.spawn_pipeline_tasks();
+        let mut pipeline = self.spawn_pipeline_tasks();
 
         // Spawn the periodic perf-snapshot flush task. Cheap when
         // disabled (the task wakes on tick, sees the flag is off,
@@ -727,6 +1211,13 @@ impl SessionRouter {
             .take()
             .ok_or_else(|| crate::Error::Execution("Shutdown channel already taken".to_string()))?;
 
+        let mut patch_rx = self
+            .patch_rx
+            .lock()
+            .unwrap()
+            .take()
+            .ok_or_else(|| crate::Error::Execution("Patch channel already taken".to_string()))?;
+
         tracing::info!(
             "Session {}: Router running, waiting for input...",
             self.session_id
@@ -742,10 +1233,28 @@ impl SessionRouter {
             tokio::select! {
                 biased;
 
+                // Patches go ahead of input so a saturated ingress cannot
+                // starve them; packets already routed to a node drain
+                // through its old wiring. The router itself holds a
+                // `patch_tx`, so this arm never sees `None`.
+                Some((patch, reply)) = patch_rx.recv() => {
+                    let result = self.apply_patch(&patch, &mut pipeline).await;
+                    if let Err(e) = &result {
+                        tracing::warn!(
+                            "Session {}: pipeline patch rejected: {}",
+                            self.session_id,
+                            e
+                        );
+                    }
+                    let _ = reply.send(result);
+                }
                 result = input_rx.recv() => {
                     match result {
                         Some(packet) => {
                             self.probes.ingress.record_since(ingress_start);
+                            if let Some(ctrl) = &self.control {
+                                ctrl.record_ingress(&packet).await;
+                            }
                             self.route_input(packet, &pipeline.input_txs).await;
                         }
                         None => {
@@ -793,7 +1302,11 @@ impl SessionRouter {
         // Wake every attached control client so they drain and exit.
         // Idempotent — harmless if no control is attached.
         if let Some(ctrl) = &self.control {
-            ctrl.signal_close(CloseReason::Normal);
+            let reason = match ctrl.termination_reason() {
+                Some(reason) => CloseReason::Error(format!("terminated: {}", reason)),
+                None => CloseReason::Normal,
+            };
+            ctrl.signal_close(reason);
         }
 
         Ok(())
@@ -810,43 +1323,30 @@ impl SessionRouter {
     /// fine; after this call the router's only remaining role is shovelling
     /// packets into the source nodes' input channels.
     fn spawn_pipeline_tasks(&mut self) -> PipelineTasks {
-        let mut input_txs: HashMap<String, mpsc::Sender<RuntimeData>> = HashMap::new();
-        let mut input_rxs: HashMap<String, mpsc::Receiver<RuntimeData>> = HashMap::new();
+        let mut input_txs: HashMap<String, mpsc::Sender<NodeInput>> = HashMap::new();
+        let mut input_rxs: HashMap<String, mpsc::Receiver<NodeInput>> = HashMap::new();
 
         // Pass 1: create an input channel for every node we have cached.
         for node_id in self.cached_nodes.keys() {
-            let (tx, rx) = mpsc::channel::<RuntimeData>(NODE_INPUT_CAPACITY);
+            let (tx, rx) = mpsc::channel::<NodeInput>(NODE_INPUT_CAPACITY);
             input_txs.insert(node_id.clone(), tx);
             input_rxs.insert(node_id.clone(), rx);
         }
 
-        // Pass 2: compute each node's successor set from the graph. Fan-out
-        // is native — one output gets cloned to every successor's input
-        // channel. `successors[from]` yields a list of `input_txs[to]`
-        // clones.
-        let mut successors: HashMap<String, Vec<mpsc::Sender<RuntimeData>>> = HashMap::new();
+        // Pass 2: compute each node's routing from the graph edges. See
+        // `build_routing`.
+        let mut routing: HashMap<String, RoutingCell> = HashMap::new();
         for node_id in self.cached_nodes.keys() {
-            let sends = self
-                .graph
-                .nodes
-                .get(node_id)
-                .map(|gn| {
-                    gn.outputs
-                        .iter()
-                        .filter_map(|succ| input_txs.get(succ).cloned())
-                        .collect::<Vec<_>>()
-                })
-                .unwrap_or_default();
-            successors.insert(node_id.clone(), sends);
+            let cell = Arc::new(DriftRwLock::new(Arc::new(
+                self.build_routing(node_id, &input_txs),
+            )));
+            routing.insert(node_id.clone(), cell);
         }
 
-        let sinks: std::collections::HashSet<String> =
-            self.graph.sinks.iter().cloned().collect();
-
         // Drain node instances out of the cache. Each task owns its node.
         let nodes = std::mem::take(&mut self.cached_nodes);
 
-        let mut handles = Vec::with_capacity(nodes.len() * 2);
+        let mut handles = HashMap::with_capacity(nodes.len());
 
         for (node_id, node) in nodes {
             let input_rx = match input_rxs.remove(&node_id) {
@@ -860,31 +1360,280 @@ impl SessionRouter {
                     continue;
                 }
             };
-            let succ_txs = successors.remove(&node_id).unwrap_or_default();
-            let is_sink = sinks.contains(&node_id);
-
-            let (main_handle, fan_handle) = Self::spawn_node_pipeline(
-                node_id,
-                node,
-                input_rx,
-                succ_txs,
-                if is_sink {
-                    Some(self.output_tx.clone())
-                } else {
-                    None
-                },
-                self.session_id.clone(),
-                self.scheduler.clone(),
-                self.control.clone(),
-                self.probes.clone(),
-                self.perf.clone(),
-            );
+            let cell = routing[&node_id].clone();
+            let standby = self.standby_nodes.remove(&node_id);
+            let node_handles =
+                self.spawn_node(node_id.clone(), node, standby, input_rx, cell, None);
+            handles.insert(node_id, node_handles);
+        }
+
+        PipelineTasks {
+            input_txs,
+            routing,
+            handles,
+            retiring: Vec::new(),
+        }
+    }
+
+    /// Compute one node's routing from the current graph.
+    ///
+    /// Fan-out is native — one output gets cloned to every successor
+    /// whose edge carries the output's port and whose filter (if any)
+    /// matches it. Yields one `SuccessorEdge` (wrapping an
+    /// `input_txs[to]` clone) per manifest connection.
+    fn build_routing(
+        &self,
+        node_id: &str,
+        input_txs: &HashMap<String, mpsc::Sender<NodeInput>>,
+    ) -> NodeRouting {
+        let successors = self
+            .graph
+            .edges_from(node_id)
+            .filter_map(|edge| {
+                input_txs.get(&edge.to).map(|tx| SuccessorEdge {
+                    from_port: edge.from_port.clone(),
+                    to_port: edge.to_port.clone(),
+                    filter: edge.filter.clone(),
+                    overflow: edge.overflow,
+                    tx: tx.clone(),
+                })
+            })
+            .collect::<Vec<_>>();
+
+        // Only nodes with port-filtered edges pay for classifying
+        // each output against their schema.
+        let port_schema = if successors.iter().any(SuccessorEdge::is_port_filtered) {
+            self.graph
+                .nodes
+                .get(node_id)
+                .and_then(|gn| self.registry.get_schema(&gn.node_type))
+        } else {
+            None
+        };
+
+        let is_sink = self.graph.sinks.iter().any(|s| s == node_id);
+
+        NodeRouting {
+            successors,
+            port_schema,
+            client_tx: is_sink.then(|| self.output_tx.clone()),
+        }
+    }
+
+    /// Spawn one node's tasks with the router's shared session state.
+    ///
+    /// With `start_after`, the node buffers its input until the gate fires
+    /// or is dropped.
+    fn spawn_node(
+        &self,
+        node_id: String,
+        node: Box<dyn StreamingNode>,
+        standby: Option<Standby>,
+        input_rx: mpsc::Receiver<NodeInput>,
+        routing: RoutingCell,
+        start_after: Option<oneshot::Receiver<()>>,
+    ) -> Vec<JoinHandle<()>> {
+        let (main_handle, fan_handle) = Self::spawn_node_pipeline(
+            node_id,
+            node,
+            standby,
+            input_rx,
+            routing,
+            start_after,
+            self.session_id.clone(),
+            self.scheduler.clone(),
+            self.control.clone(),
+            self.probes.clone(),
+            self.perf.clone(),
+        );
+        vec![main_handle, fan_handle]
+    }
+
+    /// Apply a [`PipelinePatch`] to the running pipeline.
+    ///
+    /// Everything that can fail — manifest edits, graph validation, node
+    /// creation and initialization — happens before the live pipeline is
+    /// touched, so an `Err` leaves the session exactly as it was. On
+    /// commit, removed, restarted and replaced nodes lose their input
+    /// senders and drain what is already queued, in a background task so
+    /// the router keeps dispatching. Their successors are spawned at once
+    /// but start processing only after that drain, their input buffering
+    /// in the meantime. Routing for every surviving node is swapped in
+    /// place.
+    ///
+    /// The patch applies to the manifest as declared, which is then wrapped
+    /// again; a remote pipeline restarts as a whole when any of its nodes
+    /// changes.
+    ///
+    /// Scheduler settings derived from the manifest at session start
+    /// (`fast_path`) are not revisited for added nodes.
+    async fn apply_patch(
+        &mut self,
+        patch: &PipelinePatch,
+        pipeline: &mut PipelineTasks,
+    ) -> Result<PatchReport> {
+        // ── Prepare ────────────────────────────────────────────────────
+        let source = self.live_manifest.read().clone();
+        let (source, report) = patch.apply_to(&source)?;
+        let manifest =
+            crate::executor::wrap_remote_hosts(&source)?.unwrap_or_else(|| source.clone());
+        // What changes among the nodes actually running
+        let running =
+            crate::executor::remote_hosts::wrapped_report(&report, &self.manifest, &manifest);
+        let graph = PipelineGraph::from_manifest(&manifest)?;
+        graph.validate_ports(|node_type| self.registry.get_schema(node_type))?;
+        let manifest = Arc::new(manifest);
+
+        // Charge the patched graph before its new nodes load anything
+        let admitted = match &self.admission {
+            Some(admission) => Some(admission.patch(manifest.clone(), running.fresh_nodes())?),
+            None => None,
+        };
+
+        // Fresh instances are dropped with this map if any one of them
+        // fails, which together with the admission rollback is the whole
+        // rollback.
+        let created = async {
+            let mut fresh: HashMap<String, (Box<dyn StreamingNode>, Option<Standby>)> =
+                HashMap::new();
+            for node_id in running.fresh_nodes() {
+                let node_spec = manifest
+                    .nodes
+                    .iter()
+                    .find(|n| &n.id == node_id)
+                    .ok_or_else(|| {
+                        crate::Error::Execution(format!("patched node '{}' missing (bug)", node_id))
+                    })?;
+                let node = self.create_placed_node(node_spec, &manifest).await?;
+                fresh.insert(node_id.clone(), node);
+            }
+            Ok::<_, crate::Error>(fresh)
+        }
+        .await;
+        let fresh = match created {
+            Ok(fresh) => fresh,
+            Err(e) => {
+                if let (Some(admission), Some(previous)) = (&self.admission, admitted) {
+                    admission.rollback(previous);
+                }
+                return Err(e);
+            }
+        };
+
+        // ── Commit ─────────────────────────────────────────────────────
+        tracing::info!(
+            "Session {}: applying pipeline patch (added: {:?}, removed: {:?}, restarted: {:?}, replaced: {:?})",
+            self.session_id,
+            report.added,
+            report.removed,
+            report.restarted,
+            report.replaced
+        );
+        self.remote_members = crate::executor::remote_members(&manifest);
+        self.manifest = manifest;
+        *self.live_manifest.write() = Arc::new(source);
+        self.graph = graph;
+
+        let mut retiring = Vec::new();
+        for node_id in running.removed.iter().chain(running.successors()) {
+            pipeline.input_txs.remove(node_id);
+            if let Some(handles) = pipeline.handles.remove(node_id) {
+                retiring.extend(handles);
+            }
+        }
+        // A removed node keeps its old routing while it drains, so its
+        // last outputs still reach whatever it fed.
+        for node_id in &running.removed {
+            pipeline.routing.remove(node_id);
+        }
+
+        let mut input_rxs = HashMap::with_capacity(fresh.len());
+        for node_id in fresh.keys() {
+            let (tx, rx) = mpsc::channel::<NodeInput>(NODE_INPUT_CAPACITY);
+            pipeline.input_txs.insert(node_id.clone(), tx);
+            input_rxs.insert(node_id.clone(), rx);
+        }
+
+        // Rewire every live node. A restarted or replaced node keeps its
+        // cell, so the retiring instance's fan-out already follows the new
+        // graph.
+        for node_id in pipeline.input_txs.keys() {
+            let routing = Arc::new(self.build_routing(node_id, &pipeline.input_txs));
+            match pipeline.routing.get(node_id) {
+                Some(cell) => *cell.write() = routing,
+                None => {
+                    pipeline
+                        .routing
+                        .insert(node_id.clone(), Arc::new(DriftRwLock::new(routing)));
+                }
+            }
+        }
+
+        // Successors are gated on the drain below, so ordering on their
+        // edges holds across the swap.
+        let mut gates = Vec::new();
+        for (node_id, (node, standby)) in fresh {
+            let Some(input_rx) = input_rxs.remove(&node_id) else {
+                continue;
+            };
+            let start_after = running.successors().any(|id| id == &node_id).then(|| {
+                let (gate_tx, gate_rx) = oneshot::channel();
+                gates.push(gate_tx);
+                gate_rx
+            });
+            let cell = pipeline.routing[&node_id].clone();
+            let handles =
+                self.spawn_node(node_id.clone(), node, standby, input_rx, cell, start_after);
+            pipeline.handles.insert(node_id, handles);
+        }
+
+        // Drain. Each retiring node exits once the last sender to its
+        // input is gone and its queue is empty. Dropping the gates
+        // afterwards starts the successors.
+        pipeline.retiring.retain(|handle| !handle.is_finished());
+        if !retiring.is_empty() {
+            let session_id = self.session_id.clone();
+            pipeline.retiring.push(tokio::spawn(async move {
+                let drained = tokio::time::timeout(PATCH_DRAIN_TIMEOUT, async {
+                    for handle in retiring.iter_mut() {
+                        let _ = handle.await;
+                    }
+                })
+                .await;
+                if drained.is_err() {
+                    tracing::warn!(
+                        "Session {}: retired nodes did not drain within {:?}; aborting them",
+                        session_id,
+                        PATCH_DRAIN_TIMEOUT
+                    );
+                    for handle in &retiring {
+                        handle.abort();
+                    }
+                }
+                drop(gates);
+            }));
+        }
 
-            handles.push(main_handle);
-            handles.push(fan_handle);
+        if let Some(ctrl) = &self.control {
+            for node_id in &report.removed {
+                ctrl.clear_node_state(node_id);
+            }
+            let ts = std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .map(|d| d.as_millis() as u64)
+                .unwrap_or(0);
+            ctrl.publish_tap(
+                "__system__",
+                None,
+                RuntimeData::Json(serde_json::json!({
+                    "kind": "pipeline_patched",
+                    "report": report,
+                    "ts_ms": ts,
+                })),
+            );
         }
 
-        PipelineTasks { input_txs, handles }
+        Ok(report)
     }
 
     /// Spawn the two tasks that drive a single node:
@@ -896,25 +1645,39 @@ impl SessionRouter {
     ///    yield into an internal fan-out channel (`fan_tx`).
     ///
     /// 2. **fan_out**: drains `fan_rx`, applies the control-bus hook (tap +
-    ///    intercept), and forwards surviving outputs to every successor's
-    ///    input channel AND — for sinks — to the client `output_tx`. The
-    ///    hook must run async, so it cannot live inside the sync callback;
-    ///    that's why we need the second task.
+    ///    intercept), and forwards surviving outputs to every successor
+    ///    whose edge carries the output's port and passes its filter AND —
+    ///    for sinks — to the client `output_tx`. The hook must run async, so it cannot live
+    ///    inside the sync callback; that's why we need the second task.
+    ///
+    /// `routing` is read once per output, so a pipeline patch can rewire
+    /// the node without restarting it. Its `port_schema` classifies
+    /// outputs into named ports; without it every output is on `main`.
+    ///
+    /// When a call fails and leaves the node's circuit breaker open, the
+    /// main task creates `standby` (once), swaps it in, resets the breaker
+    /// in this session's `scheduler` and reports the switch on
+    /// `__system__`.
+    ///
+    /// A node spawned with `start_after` leaves its input queued until the
+    /// gate fires or is dropped; a patch uses it to hold a replacement
+    /// until its predecessor has drained.
     ///
     /// Returns both `JoinHandle`s so the router can await clean shutdown.
     fn spawn_node_pipeline(
         node_id: String,
         node: Box<dyn StreamingNode>,
-        mut input_rx: mpsc::Receiver<RuntimeData>,
-        successor_txs: Vec<mpsc::Sender<RuntimeData>>,
-        client_tx: Option<mpsc::Sender<RuntimeData>>,
+        mut standby: Option<Standby>,
+        mut input_rx: mpsc::Receiver<NodeInput>,
+        routing: RoutingCell,
+        start_after: Option<oneshot::Receiver<()>>,
         session_id: String,
         scheduler: Arc<StreamingScheduler>,
         control: Option<Arc<SessionControl>>,
         probes: Arc<crate::metrics::RtProbeSet>,
         perf: Arc<PerfAggregator>,
     ) -> (JoinHandle<()>, JoinHandle<()>) {
-        let (fan_tx, mut fan_rx) = mpsc::channel::<RuntimeData>(NODE_FANOUT_CAPACITY);
+        let (fan_tx, mut fan_rx) = mpsc::channel::<NodeInput>(NODE_FANOUT_CAPACITY);
 
         // ── Fan-out drain task ─────────────────────────────────────────
         let fan_node_id = node_id.clone();
@@ -922,18 +1685,41 @@ impl SessionRouter {
         let fan_control = control.clone();
         let fan_probes = probes.clone();
         let fan_handle = tokio::spawn(async move {
-            while let Some(out) = fan_rx.recv().await {
+            while let Some(NodeInput { data: out, trace }) = fan_rx.recv().await {
                 let kept = match &fan_control {
                     Some(ctrl) => ctrl.on_node_output(&fan_node_id, None, out).await,
                     None => Some(out),
                 };
                 let Some(kept) = kept else { continue };
+                let route = Arc::clone(&*routing.read());
+                let port = route
+                    .port_schema
+                    .as_ref()
+                    .map_or(MAIN_PORT, |schema| schema.output_port_for(&kept));
 
                 // Fan out to successors first. Bounded `send` awaits on
                 // full, providing real backpressure all the way back to
-                // the node's callback (via `fan_tx` filling up).
-                for tx in &successor_txs {
-                    if tx.send(kept.clone()).await.is_err() {
+                // the node's callback (via `fan_tx` filling up). Edges
+                // marked `drop_newest` shed the output instead.
+                for edge in route.successors.iter().filter(|e| e.accepts(port, &kept)) {
+                    let next = NodeInput {
+                        data: edge.deliver(kept.clone()),
+                        trace,
+                    };
+                    let delivered = match edge.overflow {
+                        Overflow::Block => edge.tx.send(next).await.is_ok(),
+                        Overflow::DropNewest => match edge.tx.try_send(next) {
+                            Err(mpsc::error::TrySendError::Full(_)) => {
+                                tracing::trace!(
+                                    "Session {}: node '{}' successor full; dropping output",
+                                    fan_session_id, fan_node_id
+                                );
+                                continue;
+                            }
+                            res => res.is_ok(),
+                        },
+                    };
+                    if !delivered {
                         tracing::debug!(
                             "Session {}: node '{}' successor closed; drop",
                             fan_session_id, fan_node_id
@@ -942,7 +1728,7 @@ impl SessionRouter {
                 }
 
                 // Sinks: also forward to the client.
-                if let Some(ref out_tx) = client_tx {
+                if let Some(ref out_tx) = route.client_tx {
                     let egress_start = std::time::Instant::now();
                     let res = out_tx.send(kept).await;
                     fan_probes.egress.record_since(egress_start);
@@ -979,13 +1765,13 @@ impl SessionRouter {
         // synthesis whenever a node forgot to filter them.
         let cancel = Arc::new(tokio::sync::Notify::new());
         let (filt_tx, mut filt_rx) =
-            mpsc::channel::<RuntimeData>(NODE_FANOUT_CAPACITY);
+            mpsc::channel::<NodeInput>(NODE_FANOUT_CAPACITY);
         let filter_cancel = Arc::clone(&cancel);
         let filter_node_id = node_id.clone();
         let filter_session_id = session_id.clone();
         let filter_handle = tokio::spawn(async move {
             while let Some(input) = input_rx.recv().await {
-                if let Some(port) = aux_port_of(&input) {
+                if let Some(port) = aux_port_of(&input.data) {
                     if port == BARGE_IN_PORT {
                         tracing::debug!(
                             session_id = %filter_session_id,
@@ -1014,14 +1800,17 @@ impl SessionRouter {
         let main_session_id = session_id.clone();
         let main_cancel = Arc::clone(&cancel);
         let main_handle = tokio::spawn(async move {
-            // Move the node into a raw pointer so we can hand out `&`
-            // references to scheduler closures without fighting the
-            // borrow checker. Safety: we never drop or share this Box
-            // across tasks. The single owning task uses it through `&*`.
-            let node = node; // bind
-            let node_ref: &dyn StreamingNode = &*node;
+            // The task owns the node; each dispatch borrows it through
+            // `node_ref`, so it can be replaced by the standby between
+            // inputs.
+            let mut node = node;
+            if let Some(gate) = start_after {
+                let _ = gate.await;
+            }
 
             while let Some(input) = filt_rx.recv().await {
+                let node_ref: &dyn StreamingNode = &*node;
+
                 // Node-state gate (Bypass / Disabled). Per-input, so a
                 // runtime control-bus toggle takes effect on the next
                 // packet.
@@ -1041,6 +1830,21 @@ impl SessionRouter {
                     }
                 }
 
+                // One span per dispatch, parented on whatever produced
+                // this input. Outputs carry the span's own context so
+                // the next hop nests under it.
+                let NodeInput {
+                    data: input,
+                    trace: parent,
+                } = input;
+                let span = telemetry::dispatch_span(
+                    &main_session_id,
+                    &main_node_id,
+                    node_ref.node_type(),
+                    parent.as_ref(),
+                );
+                let out_trace = telemetry::outgoing_context(&span, parent);
+
                 // Perf instrumentation: record one input event,
                 // and per-output latency via the wrapped callback.
                 // When the perf aggregator is disabled, every
@@ -1069,6 +1873,10 @@ impl SessionRouter {
                         .swap(true, std::sync::atomic::Ordering::Relaxed);
                     perf_clone.record_output(&perf_node_id, lat_us, is_first);
 
+                    let out = NodeInput {
+                        data: out,
+                        trace: out_trace,
+                    };
                     if let Err(e) = cb_fan_tx.try_send(out) {
                         tracing::warn!(
                             "node '{}' fan_tx backpressure drop: {}",
@@ -1125,6 +1933,10 @@ impl SessionRouter {
                                         let fan_tx = fan_tx.clone();
                                         let id = main_node_id.clone();
                                         Box::new(move |out| {
+                                            let out = NodeInput {
+                                                data: out,
+                                                trace: out_trace,
+                                            };
                                             if let Err(e) = fan_tx.try_send(out) {
                                                 tracing::warn!(
                                                     "node '{}' fan_tx backpressure drop: {}",
@@ -1150,6 +1962,7 @@ impl SessionRouter {
                     }
                 };
 
+                let mut failure = None;
                 let cancelled = tokio::select! {
                     biased;
                     _ = main_cancel.notified() => {
@@ -1164,20 +1977,63 @@ impl SessionRouter {
                         // which is the cancellation mechanism.
                         true
                     }
-                    r = dispatch_fut => {
+                    r = dispatch_fut.instrument(span.clone()) => {
                         if let Err(e) = r {
+                            telemetry::record_error(&span, &e);
                             tracing::error!(
                                 "Session {}: node '{}' execution error: {}",
                                 main_session_id,
                                 main_node_id,
                                 e
                             );
+                            failure = Some(e);
                         }
                         false
                     }
                 };
                 let _ = cancelled;
 
+                // Fall back once the breaker gives up on the node
+                if let Some(e) = failure {
+                    let open = standby.is_some()
+                        && scheduler
+                            .get_node_stats(&main_node_id)
+                            .await
+                            .is_some_and(|stats| stats.circuit_breaker_open);
+                    if let Some(next) = standby.take_if(|_| open) {
+                        match next.build(&main_session_id).await {
+                            Ok(fallback) => {
+                                tracing::warn!(
+                                    "Session {}: node '{}' circuit breaker open; switching from '{}' to fallback '{}'",
+                                    main_session_id,
+                                    main_node_id,
+                                    next.from,
+                                    next.to
+                                );
+                                node = fallback;
+                                // This session's scheduler: the fallback starts
+                                // with a closed breaker of its own
+                                scheduler.reset_circuit_breaker(&main_node_id).await;
+                                publish_fallback_event(
+                                    control.as_deref(),
+                                    &main_node_id,
+                                    &next.from,
+                                    &next.to,
+                                    "circuit_open",
+                                    e.to_string(),
+                                );
+                            }
+                            Err(init_err) => tracing::warn!(
+                                session_id = %main_session_id,
+                                node_id = %main_node_id,
+                                candidate = %next.to,
+                                error = %init_err,
+                                "Fallback failed to initialize; node runs without one"
+                            ),
+                        }
+                    }
+                }
+
                 probes.node_out.record_since(node_dispatch_start);
             }
             // input_rx closed: drop fan_tx so the drain task exits once
@@ -1198,7 +2054,7 @@ impl SessionRouter {
     async fn route_input(
         &self,
         packet: DataPacket,
-        input_txs: &HashMap<String, mpsc::Sender<RuntimeData>>,
+        input_txs: &HashMap<String, mpsc::Sender<NodeInput>>,
     ) {
         let arrival_ts_us = std::time::SystemTime::now()
             .duration_since(std::time::UNIX_EPOCH)
@@ -1213,7 +2069,11 @@ impl SessionRouter {
         }
 
         let targets: Vec<&str> = if let Some(ref target) = packet.to_node {
-            vec![target.as_str()]
+            // A node inside a remote pipeline is fed through its wrapper
+            match self.remote_members.get(target) {
+                Some(wrapper) => vec![wrapper.as_str()],
+                None => vec![target.as_str()],
+            }
         } else {
             self.graph.sources.iter().map(|s| s.as_str()).collect()
         };
@@ -1227,7 +2087,11 @@ impl SessionRouter {
                 );
                 continue;
             };
-            if tx.send(input_data.clone()).await.is_err() {
+            let input = NodeInput {
+                data: input_data.clone(),
+                trace: packet.trace_context,
+            };
+            if tx.send(input).await.is_err() {
                 tracing::warn!(
                     "Session {}: node '{}' input channel closed; drop packet",
                     self.session_id,
@@ -1239,12 +2103,19 @@ impl SessionRouter {
 
     /// Await every spawned task after dropping the router's own input_txs.
     async fn teardown_pipeline_tasks(pipeline: PipelineTasks) {
-        let PipelineTasks { input_txs, handles } = pipeline;
-        // Drop the router's copies of every node's input sender; each source
-        // node sees `input_rx.recv()` return `None` and exits, cascading
-        // through the graph.
+        let PipelineTasks {
+            input_txs,
+            routing,
+            handles,
+            retiring,
+        } = pipeline;
+        // Drop the router's copies of every node's input sender and
+        // routing; each source node sees `input_rx.recv()` return `None`
+        // and exits, cascading through the graph as each fan-out task
+        // drops the last copy of its routing.
         drop(input_txs);
-        for h in handles {
+        drop(routing);
+        for h in handles.into_values().flatten().chain(retiring) {
             let _ = h.await;
         }
     }
@@ -1377,6 +2248,135 @@ impl SessionRouter {
             .collect()
     }
 
+    /// Export all metrics in Prometheus format
+    pub async fn prometheus_metrics(&self) -> String {
+        self.handle().prometheus_metrics().await
+    }
+
+    /// Export per-stream debug metrics as JSON
+    pub async fn debug_stream_metrics(&self) -> serde_json::Value {
+        // Snapshot pairs first so the per-stream reads happen off the
+        // DashMap shard guard (even with sync reads now, we keep the
+        // clone-and-iterate pattern).
+        let pairs: Vec<(String, Arc<DriftRwLock<DriftMetrics>>)> = self
+            .drift_metrics
+            .iter()
+            .map(|e| (e.key().clone(), e.value().clone()))
+            .collect();
+        let stream_count = pairs.len();
+        let mut streams = serde_json::Map::new();
+        for (stream_id, metrics) in pairs {
+            let m = metrics.read();
+            streams.insert(stream_id, m.to_debug_json());
+        }
+
+        serde_json::json!({
+            "session_id": self.session_id,
+            "stream_count": stream_count,
+            "streams": streams,
+            "scheduler": {
+                "max_concurrency": self.scheduler.config.max_concurrency,
+                "available_permits": "N/A", // Can't get this synchronously
+            }
+        })
+    }
+}
+
+/// Per-stream drift snapshot reported by [`RouterHandle::drift`].
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct StreamDrift {
+    pub stream_id: String,
+    /// 0.0 (unhealthy) – 1.0 (healthy)
+    pub health_score: f64,
+    /// Full [`DriftMetrics::to_debug_json`] dump
+    pub detail: serde_json::Value,
+}
+
+/// Handle onto a session router that outlives `run()`.
+///
+/// Obtained from [`SessionRouter::handle`] or, for executor-created
+/// sessions, from [`SessionControl::router`]. Backs the admin surfaces
+/// (list / describe / kill / metrics / live patching) without touching
+/// the data path.
+#[derive(Clone)]
+pub struct RouterHandle {
+    session_id: String,
+    manifest: Arc<DriftRwLock<Arc<Manifest>>>,
+    started_at: std::time::SystemTime,
+    scheduler: Arc<StreamingScheduler>,
+    drift_metrics: Arc<dashmap::DashMap<String, Arc<DriftRwLock<DriftMetrics>>>>,
+    shutdown_tx: mpsc::Sender<()>,
+    patch_tx: mpsc::Sender<PatchRequest>,
+}
+
+impl RouterHandle {
+    pub fn session_id(&self) -> &str {
+        &self.session_id
+    }
+
+    /// The session's manifest, including any patches applied so far
+    pub fn manifest(&self) -> Arc<Manifest> {
+        self.manifest.read().clone()
+    }
+
+    /// Apply a [`PipelinePatch`] to the running pipeline.
+    ///
+    /// Resolves once the patch is committed and the nodes it retired have
+    /// drained, or with the reason it was rejected — in which case the
+    /// pipeline is unchanged. Fails if the router has exited.
+    pub async fn apply_patch(&self, patch: PipelinePatch) -> Result<PatchReport> {
+        let closed =
+            || crate::Error::Execution(format!("session '{}' is not running", self.session_id));
+        let (reply_tx, reply_rx) = oneshot::channel();
+        self.patch_tx
+            .send((patch, reply_tx))
+            .await
+            .map_err(|_| closed())?;
+        reply_rx.await.map_err(|_| closed())?
+    }
+
+    /// When the router was wired up (≈ session creation)
+    pub fn started_at(&self) -> std::time::SystemTime {
+        self.started_at
+    }
+
+    /// Per-node execution statistics from the session's scheduler
+    pub async fn node_stats(&self) -> HashMap<String, NodeStats> {
+        self.scheduler.get_all_node_stats().await
+    }
+
+    /// Drift snapshot for every stream seen so far, sorted by stream id
+    pub fn drift(&self) -> Vec<StreamDrift> {
+        let pairs: Vec<(String, Arc<DriftRwLock<DriftMetrics>>)> = self
+            .drift_metrics
+            .iter()
+            .map(|e| (e.key().clone(), e.value().clone()))
+            .collect();
+        let mut drift: Vec<StreamDrift> = pairs
+            .into_iter()
+            .map(|(stream_id, metrics)| {
+                let m = metrics.read();
+                StreamDrift {
+                    stream_id,
+                    health_score: m.health_score(),
+                    detail: m.to_debug_json(),
+                }
+            })
+            .collect();
+        drift.sort_by(|a, b| a.stream_id.cmp(&b.stream_id));
+        drift
+    }
+
+    /// Ask the router to shut down. Returns `false` if it already exited.
+    pub fn shutdown(&self) -> bool {
+        match self.shutdown_tx.try_send(()) {
+            Ok(()) => true,
+            // A shutdown is already queued.
+            Err(mpsc::error::TrySendError::Full(())) => true,
+            Err(mpsc::error::TrySendError::Closed(())) => false,
+        }
+    }
+
     /// Export all metrics in Prometheus format
     pub async fn prometheus_metrics(&self) -> String {
         let mut output = String::new();
@@ -1414,34 +2414,6 @@ impl SessionRouter {
 
         output
     }
-
-    /// Export per-stream debug metrics as JSON
-    pub async fn debug_stream_metrics(&self) -> serde_json::Value {
-        // Snapshot pairs first so the per-stream reads happen off the
-        // DashMap shard guard (even with sync reads now, we keep the
-        // clone-and-iterate pattern).
-        let pairs: Vec<(String, Arc<DriftRwLock<DriftMetrics>>)> = self
-            .drift_metrics
-            .iter()
-            .map(|e| (e.key().clone(), e.value().clone()))
-            .collect();
-        let stream_count = pairs.len();
-        let mut streams = serde_json::Map::new();
-        for (stream_id, metrics) in pairs {
-            let m = metrics.read();
-            streams.insert(stream_id, m.to_debug_json());
-        }
-
-        serde_json::json!({
-            "session_id": self.session_id,
-            "stream_count": stream_count,
-            "streams": streams,
-            "scheduler": {
-                "max_concurrency": self.scheduler.config.max_concurrency,
-                "available_permits": "N/A", // Can't get this synchronously
-            }
-        })
-    }
 }
 
 #[cfg(test)]
@@ -1473,6 +2445,7 @@ mod tests {
                 .map(|(from, to)| Connection {
                     from: from.to_string(),
                     to: to.to_string(),
+                    ..Default::default()
                 })
                 .collect(),
             python_env: None,
@@ -1852,4 +2825,98 @@ mod tests {
         let all_stats = router.get_all_node_stats().await;
         assert!(all_stats.is_empty());
     }
+
+    #[test]
+    fn test_session_router_rejects_undeclared_port() {
+        use crate::nodes::schema::RuntimeDataType;
+        use crate::nodes::StreamingNodeFactory;
+
+        struct PortedFactory;
+        impl StreamingNodeFactory for PortedFactory {
+            fn create(
+                &self,
+                _node_id: String,
+                _params: &serde_json::Value,
+                _session_id: Option<String>,
+            ) -> Result<Box<dyn StreamingNode>> {
+                Err(crate::Error::Execution("not used".into()))
+            }
+            fn node_type(&self) -> &str {
+                "PortedNode"
+            }
+            fn schema(&self) -> Option<NodeSchema> {
+                Some(NodeSchema::new("PortedNode").output_port("events", [RuntimeDataType::Json]))
+            }
+        }
+
+        let mut registry = StreamingNodeRegistry::new();
+        registry.register(Arc::new(PortedFactory));
+        let registry = Arc::new(registry);
+
+        let mut manifest = create_test_manifest(
+            vec![("vad", "PortedNode"), ("sink", "TestNode")],
+            vec![],
+        );
+        manifest.connections = vec![Connection::new("vad", "sink").from_port("events")];
+        let (output_tx, _output_rx) = mpsc::channel(DEFAULT_ROUTER_OUTPUT_CAPACITY);
+        assert!(SessionRouter::new(
+            "test-session".to_string(),
+            Arc::new(manifest.clone()),
+            registry.clone(),
+            output_tx.clone(),
+        )
+        .is_ok());
+
+        manifest.connections = vec![Connection::new("vad", "sink").from_port("audio")];
+        let result = SessionRouter::new(
+            "test-session".to_string(),
+            Arc::new(manifest),
+            registry,
+            output_tx,
+        );
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_successor_edge_port_filtering() {
+        let (tx, _rx) = mpsc::channel(1);
+        let edge = |from_port: Option<&str>, to_port: Option<&str>| SuccessorEdge {
+            from_port: from_port.map(str::to_string),
+            to_port: to_port.map(str::to_string),
+            filter: None,
+            overflow: Overflow::Block,
+            tx: tx.clone(),
+        };
+
+        // Unnamed and `main` source ports carry everything
+        for e in [edge(None, None), edge(Some(MAIN_PORT), None)] {
+            assert!(!e.is_port_filtered());
+            assert!(e.carries(MAIN_PORT));
+            assert!(e.carries("events"));
+        }
+
+        let events = edge(Some("events"), Some("alerts"));
+        assert!(events.is_port_filtered());
+        assert!(events.carries("events"));
+        assert!(!events.carries(MAIN_PORT));
+
+        // Filters apply on top of port selection
+        let mut finals = edge(None, None);
+        finals.filter = Some(EdgeFilter::Metadata(crate::executor::MetadataMatch {
+            pointer: "/is_final".to_string(),
+            equals: Some(serde_json::json!(true)),
+            ..Default::default()
+        }));
+        let partial = RuntimeData::Json(serde_json::json!({"text": "he", "is_final": false}));
+        let last = RuntimeData::Json(serde_json::json!({"text": "hello", "is_final": true}));
+        assert!(!finals.accepts(MAIN_PORT, &partial));
+        assert!(finals.accepts(MAIN_PORT, &last));
+        assert!(!events.accepts(MAIN_PORT, &last));
+
+        // Named target ports arrive as aux-port envelopes
+        let delivered = events.deliver(RuntimeData::Json(serde_json::json!({"x": 1})));
+        assert_eq!(aux_port_of(&delivered), Some("alerts"));
+        let passthrough = edge(None, None).deliver(RuntimeData::Text("hi".into()));
+        assert!(matches!(passthrough, RuntimeData::Text(ref t) if t == "hi"));
+    }
 }
@@ -109,6 +109,7 @@ async fn session_router_input_sender_is_bounded() {
             session_id: session.to_string(),
             sequence: seq,
             sub_sequence: 0,
+            trace_context: None,
         }
     }
 
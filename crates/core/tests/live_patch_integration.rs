@@ -0,0 +1,468 @@
+//! Integration test: live pipeline patching through SessionControl.
+//!
+//! Drives a running SessionRouter with `apply_patch` and checks that
+//! added / removed / replaced / re-parameterised nodes take effect on the data path,
+//! that queued packets drain through the old wiring in order, and that a
+//! rejected patch leaves the session untouched.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+use remotemedia_core::auth::{AuthError, Principal};
+use remotemedia_core::data::RuntimeData;
+use remotemedia_core::manifest::{Connection, Manifest, ManifestMetadata, NodeManifest};
+use remotemedia_core::nodes::{
+    AsyncNodeWrapper, AsyncStreamingNode, InitializeContext, StreamingNode, StreamingNodeFactory,
+    StreamingNodeRegistry,
+};
+use remotemedia_core::transport::session_control::{
+    ControlAddress, ControlAuth, ControlFrame, FrameOutcome, SessionControl,
+};
+use remotemedia_core::transport::session_router::{
+    DataPacket, SessionRouter, DEFAULT_ROUTER_OUTPUT_CAPACITY,
+};
+use remotemedia_core::transport::{
+    AdmissionConfig, AdmissionController, AdmissionPermit, PipelinePatch,
+};
+use remotemedia_core::Error;
+use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
+
+/// Prefixes text with `params.tag`; sleeps `params.delay_ms` per packet
+/// and fails initialization when `params.fail_init` is set.
+struct TagNode {
+    tag: String,
+    delay_ms: u64,
+    fail_init: bool,
+}
+
+#[async_trait::async_trait]
+impl AsyncStreamingNode for TagNode {
+    fn node_type(&self) -> &str {
+        "TagNode"
+    }
+
+    async fn initialize(&self, _ctx: &InitializeContext) -> Result<(), Error> {
+        if self.fail_init {
+            return Err(Error::Execution("TagNode: fail_init set".into()));
+        }
+        Ok(())
+    }
+
+    async fn process(&self, data: RuntimeData) -> Result<RuntimeData, Error> {
+        if self.delay_ms > 0 {
+            tokio::time::sleep(Duration::from_millis(self.delay_ms)).await;
+        }
+        match data {
+            RuntimeData::Text(text) => Ok(RuntimeData::Text(format!("{}:{}", self.tag, text))),
+            other => Ok(other),
+        }
+    }
+}
+
+/// Builds [`TagNode`]s registered under the given node type
+struct TagNodeFactory(&'static str);
+
+impl StreamingNodeFactory for TagNodeFactory {
+    fn create(
+        &self,
+        _node_id: String,
+        params: &serde_json::Value,
+        _session_id: Option<String>,
+    ) -> Result<Box<dyn StreamingNode>, Error> {
+        Ok(Box::new(AsyncNodeWrapper(Arc::new(TagNode {
+            tag: params["tag"].as_str().unwrap_or("tag").to_string(),
+            delay_ms: params["delay_ms"].as_u64().unwrap_or(0),
+            fail_init: params["fail_init"].as_bool().unwrap_or(false),
+        }))))
+    }
+
+    fn node_type(&self) -> &str {
+        self.0
+    }
+}
+
+fn tag_node(id: &str, tag: &str) -> NodeManifest {
+    NodeManifest {
+        id: id.to_string(),
+        node_type: "TagNode".to_string(),
+        params: serde_json::json!({ "tag": tag }),
+        ..Default::default()
+    }
+}
+
+fn one_node_pipeline(node: NodeManifest) -> Manifest {
+    Manifest {
+        version: "v1".to_string(),
+        metadata: ManifestMetadata {
+            name: "live-patch".to_string(),
+            ..Default::default()
+        },
+        nodes: vec![node],
+        connections: Vec::<Connection>::new(),
+        python_env: None,
+    }
+}
+
+fn text_packet(session_id: &str, text: &str, seq: u64) -> DataPacket {
+    DataPacket {
+        data: RuntimeData::Text(text.to_string()),
+        from_node: "client".to_string(),
+        to_node: None,
+        session_id: session_id.to_string(),
+        sequence: seq,
+        sub_sequence: 0,
+        trace_context: None,
+    }
+}
+
+struct Session {
+    id: String,
+    ctrl: Arc<SessionControl>,
+    input_tx: mpsc::Sender<DataPacket>,
+    output_rx: mpsc::Receiver<RuntimeData>,
+    handle: JoinHandle<()>,
+}
+
+impl Session {
+    async fn start(session_id: &str, manifest: Manifest) -> Self {
+        Self::start_admitted(session_id, manifest, None).await
+    }
+
+    async fn start_admitted(
+        session_id: &str,
+        manifest: Manifest,
+        permit: Option<AdmissionPermit>,
+    ) -> Self {
+        let mut registry = StreamingNodeRegistry::new();
+        registry.register(Arc::new(TagNodeFactory("TagNode")));
+        registry.register(Arc::new(TagNodeFactory("WhisperNode")));
+        let (output_tx, output_rx) = mpsc::channel(DEFAULT_ROUTER_OUTPUT_CAPACITY);
+
+        let (mut router, _shutdown_tx) = SessionRouter::new(
+            session_id.to_string(),
+            Arc::new(manifest),
+            Arc::new(registry),
+            output_tx,
+        )
+        .unwrap();
+        if let Some(permit) = permit {
+            router.attach_admission(permit);
+        }
+        let ctrl = SessionControl::new(session_id.to_string());
+        router.attach_control(ctrl.clone()).await;
+        let input_tx = router.get_input_sender();
+        let handle = router.start();
+
+        Self {
+            id: session_id.to_string(),
+            ctrl,
+            input_tx,
+            output_rx,
+            handle,
+        }
+    }
+
+    async fn send(&self, text: &str, seq: u64) {
+        self.input_tx
+            .send(text_packet(&self.id, text, seq))
+            .await
+            .unwrap();
+    }
+
+    async fn recv_text(&mut self) -> String {
+        let out = tokio::time::timeout(Duration::from_secs(2), self.output_rx.recv())
+            .await
+            .expect("client output timeout")
+            .expect("client channel closed");
+        match out {
+            RuntimeData::Text(text) => text,
+            other => panic!("unexpected variant: {:?}", other),
+        }
+    }
+
+    async fn roundtrip(&mut self, text: &str, seq: u64) -> String {
+        self.send(text, seq).await;
+        self.recv_text().await
+    }
+
+    async fn stop(self) {
+        drop(self.input_tx);
+        let _ = tokio::time::timeout(Duration::from_secs(2), self.handle).await;
+    }
+}
+
+#[tokio::test]
+async fn add_and_remove_nodes_on_a_running_session() {
+    let mut session =
+        Session::start("patch-add-remove", one_node_pipeline(tag_node("a", "a"))).await;
+    assert_eq!(session.roundtrip("x", 0).await, "a:x");
+
+    // Append `b` after the sink `a`: `b` becomes the new sink.
+    let report = session
+        .ctrl
+        .apply_patch(
+            PipelinePatch::new()
+                .add_node(tag_node("b", "b"))
+                .connect(Connection::new("a", "b")),
+        )
+        .await
+        .unwrap();
+    assert_eq!(report.added, vec!["b"]);
+    assert_eq!(report.connections_added, 1);
+    assert_eq!(session.roundtrip("x", 1).await, "b:a:x");
+
+    // The shared handle reflects the patched manifest.
+    let manifest = session.ctrl.router().unwrap().manifest();
+    assert_eq!(manifest.nodes.len(), 2);
+    assert_eq!(manifest.connections, vec![Connection::new("a", "b")]);
+
+    // Remove `b` again: `a` is the sink once more.
+    let report = session
+        .ctrl
+        .apply_patch(PipelinePatch::new().remove_node("b"))
+        .await
+        .unwrap();
+    assert_eq!(report.removed, vec!["b"]);
+    assert_eq!(report.connections_removed, 1);
+    assert_eq!(session.roundtrip("x", 2).await, "a:x");
+
+    session.stop().await;
+}
+
+#[tokio::test]
+async fn update_params_restarts_node_without_losing_queued_packets() {
+    let mut node = tag_node("a", "old");
+    node.params["delay_ms"] = serde_json::json!(20);
+    let mut session = Session::start("patch-params", one_node_pipeline(node)).await;
+
+    // Queue packets behind a slow node, then patch while they are in flight.
+    for seq in 0..5 {
+        session.send(&seq.to_string(), seq).await;
+    }
+    // Once the first is out the rest are sitting in the node's input.
+    let mut outputs = vec![session.recv_text().await];
+    let report = session
+        .ctrl
+        .apply_patch(PipelinePatch::new().update_params("a", serde_json::json!({ "tag": "new" })))
+        .await
+        .unwrap();
+    assert_eq!(report.restarted, vec!["a"]);
+    for seq in 5..8 {
+        session.send(&seq.to_string(), seq).await;
+    }
+
+    for _ in 1..8 {
+        outputs.push(session.recv_text().await);
+    }
+    let expected: Vec<String> = (0..8)
+        .map(|seq| format!("{}:{}", if seq < 5 { "old" } else { "new" }, seq))
+        .collect();
+    assert_eq!(outputs, expected);
+
+    session.stop().await;
+}
+
+#[tokio::test]
+async fn replacing_a_node_drains_it_without_blocking_the_patch() {
+    let mut node = tag_node("a", "old");
+    node.params["delay_ms"] = serde_json::json!(200);
+    let mut session = Session::start("patch-replace", one_node_pipeline(node)).await;
+
+    for seq in 0..4 {
+        session.send(&seq.to_string(), seq).await;
+    }
+    let mut outputs = vec![session.recv_text().await];
+
+    // Three packets (~600ms) are still queued on the old instance; the
+    // patch returns without waiting for them.
+    let started = std::time::Instant::now();
+    let report = session
+        .ctrl
+        .apply_patch(
+            PipelinePatch::new()
+                .remove_node("a")
+                .add_node(tag_node("a", "new")),
+        )
+        .await
+        .unwrap();
+    assert!(started.elapsed() < Duration::from_millis(400));
+    assert_eq!(report.replaced, vec!["a"]);
+    assert!(report.added.is_empty() && report.removed.is_empty());
+    session.send("4", 4).await;
+
+    for _ in 1..5 {
+        outputs.push(session.recv_text().await);
+    }
+    assert_eq!(outputs, ["old:0", "old:1", "old:2", "old:3", "new:4"]);
+
+    session.stop().await;
+}
+
+#[tokio::test]
+async fn rejected_patch_leaves_session_untouched() {
+    let mut session = Session::start("patch-reject", one_node_pipeline(tag_node("a", "a"))).await;
+
+    // Unknown endpoint: rejected while editing the manifest.
+    let err = session
+        .ctrl
+        .apply_patch(PipelinePatch::new().connect(Connection::new("a", "missing")))
+        .await;
+    assert!(err.is_err());
+
+    // Cycle: rejected by graph validation.
+    let err = session
+        .ctrl
+        .apply_patch(
+            PipelinePatch::new()
+                .add_node(tag_node("b", "b"))
+                .connect(Connection::new("a", "b"))
+                .connect(Connection::new("b", "a")),
+        )
+        .await;
+    assert!(err.is_err());
+
+    // A new node that fails to initialize rolls the whole patch back,
+    // including the param update that preceded it.
+    let mut broken = tag_node("b", "b");
+    broken.params["fail_init"] = serde_json::json!(true);
+    let err = session
+        .ctrl
+        .apply_patch(
+            PipelinePatch::new()
+                .update_params("a", serde_json::json!({ "tag": "changed" }))
+                .add_node(broken)
+                .connect(Connection::new("a", "b")),
+        )
+        .await;
+    assert!(err.is_err());
+
+    assert_eq!(session.ctrl.router().unwrap().manifest().nodes.len(), 1);
+    assert_eq!(session.roundtrip("x", 0).await, "a:x");
+
+    session.stop().await;
+}
+
+#[tokio::test]
+async fn apply_patch_frame_reports_and_announces_on_system_tap() {
+    let mut session = Session::start("patch-frame", one_node_pipeline(tag_node("a", "a"))).await;
+    let mut system = session
+        .ctrl
+        .subscribe(&ControlAddress::node_out("__system__"))
+        .unwrap();
+
+    let patch: PipelinePatch = serde_json::from_value(serde_json::json!({ "ops": [
+        { "op": "update_params", "node_id": "a", "params": { "tag": "z" } }
+    ] }))
+    .unwrap();
+    let outcome = session
+        .ctrl
+        .handle_frame(ControlFrame::ApplyPatch(patch))
+        .await
+        .unwrap();
+    let report = match outcome {
+        FrameOutcome::Patched(report) => report,
+        _ => panic!("expected FrameOutcome::Patched"),
+    };
+    assert_eq!(report.restarted, vec!["a"]);
+
+    let announced = loop {
+        let event = tokio::time::timeout(Duration::from_secs(1), system.recv())
+            .await
+            .expect("system tap timeout")
+            .expect("system tap closed");
+        if let RuntimeData::Json(v) = event {
+            if v["kind"] == "pipeline_patched" {
+                break v;
+            }
+        }
+    };
+    assert_eq!(announced["report"]["restarted"][0], "a");
+    assert_eq!(session.roundtrip("x", 0).await, "z:x");
+
+    session.stop().await;
+}
+
+#[tokio::test]
+async fn patch_is_checked_against_the_attached_key() {
+    let mut session = Session::start("patch-auth", one_node_pipeline(tag_node("a", "a"))).await;
+
+    let mut principal = Principal::new("tagger", "acme");
+    principal.allowed_node_types = Some(vec!["TagNode".to_string()]);
+    let auth = ControlAuth::new(principal);
+
+    let allowed = PipelinePatch::new()
+        .add_node(tag_node("b", "b"))
+        .connect(Connection::new("a", "b"));
+    auth.check_patch(&session.ctrl, &allowed).unwrap();
+
+    let python = NodeManifest {
+        id: "py".to_string(),
+        node_type: "PythonNode".to_string(),
+        ..Default::default()
+    };
+    let disallowed = PipelinePatch::new()
+        .add_node(python)
+        .connect(Connection::new("a", "py"));
+    let err = auth.check_patch(&session.ctrl, &disallowed).unwrap_err();
+    assert!(matches!(err, AuthError::Forbidden(_)), "{}", err);
+    assert!(err.to_string().contains("PythonNode"), "{}", err);
+
+    // Without authentication every patch is allowed through
+    ControlAuth::default()
+        .check_patch(&session.ctrl, &disallowed)
+        .unwrap();
+
+    assert_eq!(session.roundtrip("x", 0).await, "a:x");
+    session.stop().await;
+}
+
+#[tokio::test]
+async fn patch_is_admitted_against_node_type_limits() {
+    let admission = AdmissionController::new(AdmissionConfig {
+        node_type_limits: HashMap::from([("WhisperNode".to_string(), 1)]),
+        ..Default::default()
+    });
+    let whisper = NodeManifest {
+        node_type: "WhisperNode".to_string(),
+        ..tag_node("asr", "asr")
+    };
+
+    // Another session already runs the one WhisperNode allowed
+    let other = admission
+        .admit(None, &one_node_pipeline(whisper.clone()))
+        .unwrap();
+
+    let manifest = one_node_pipeline(tag_node("a", "a"));
+    let permit = admission.admit(None, &manifest).unwrap();
+    let mut session = Session::start_admitted("patch-admission", manifest, Some(permit)).await;
+    assert_eq!(session.roundtrip("x", 0).await, "a:x");
+
+    let add_whisper = PipelinePatch::new()
+        .add_node(whisper)
+        .connect(Connection::new("a", "asr"));
+    match session.ctrl.apply_patch(add_whisper.clone()).await {
+        Err(Error::AdmissionRejected { resource, .. }) => {
+            assert_eq!(resource, "node_type:WhisperNode")
+        }
+        other => panic!("expected an admission rejection, got {:?}", other),
+    }
+    assert_eq!(admission.usage().per_node_type["WhisperNode"], 1);
+    assert_eq!(session.roundtrip("x", 1).await, "a:x");
+
+    // Once the other session is gone the same patch fits
+    drop(other);
+    session.ctrl.apply_patch(add_whisper).await.unwrap();
+    assert_eq!(session.roundtrip("x", 2).await, "asr:a:x");
+    assert_eq!(admission.usage().per_node_type["WhisperNode"], 1);
+
+    // The router releases the patched share when it exits
+    assert!(session.ctrl.router().unwrap().shutdown());
+    tokio::time::timeout(Duration::from_secs(2), session.handle)
+        .await
+        .expect("router did not exit")
+        .unwrap();
+    assert_eq!(admission.usage().sessions, 0);
+    assert!(admission.usage().per_node_type.is_empty());
+}
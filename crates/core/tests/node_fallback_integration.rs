@@ -0,0 +1,283 @@
+//! Integration test: execution placement and fallback nodes.
+//!
+//! Drives a SessionRouter whose nodes declare `execution.placement` and
+//! `execution.fallback`, and checks that the router starts the candidate
+//! the placement allows, falls back when a node fails to initialize or its
+//! circuit breaker opens, and announces each switch on `__system__`.
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use remotemedia_core::data::RuntimeData;
+use remotemedia_core::executor::SchedulerConfig;
+use remotemedia_core::manifest::{
+    Connection, ExecutionMetadata, Manifest, ManifestMetadata, NodeManifest, Placement,
+};
+use remotemedia_core::nodes::{
+    AsyncNodeWrapper, AsyncStreamingNode, InitializeContext, StreamingNode, StreamingNodeFactory,
+    StreamingNodeRegistry,
+};
+use remotemedia_core::transport::session_control::{ControlAddress, SessionControl};
+use remotemedia_core::transport::session_router::{
+    DataPacket, SessionRouter, DEFAULT_ROUTER_OUTPUT_CAPACITY,
+};
+use remotemedia_core::Error;
+use tokio::sync::{broadcast, mpsc};
+
+/// Initializations of nodes created with `params.counted`
+static COUNTED_INITS: AtomicUsize = AtomicUsize::new(0);
+
+/// Prefixes text with `params.tag`. `params.fail_init` makes
+/// initialization fail, `params.fail` makes every call fail.
+struct StageNode {
+    tag: String,
+    fail_init: bool,
+    fail: bool,
+    counted: bool,
+}
+
+#[async_trait::async_trait]
+impl AsyncStreamingNode for StageNode {
+    fn node_type(&self) -> &str {
+        "StageNode"
+    }
+
+    async fn initialize(&self, _ctx: &InitializeContext) -> Result<(), Error> {
+        if self.counted {
+            COUNTED_INITS.fetch_add(1, Ordering::SeqCst);
+        }
+        if self.fail_init {
+            return Err(Error::Execution("StageNode: fail_init set".into()));
+        }
+        Ok(())
+    }
+
+    async fn process(&self, data: RuntimeData) -> Result<RuntimeData, Error> {
+        if self.fail {
+            return Err(Error::Execution("StageNode: fail set".into()));
+        }
+        match data {
+            RuntimeData::Text(text) => Ok(RuntimeData::Text(format!("{}:{}", self.tag, text))),
+            other => Ok(other),
+        }
+    }
+}
+
+struct StageNodeFactory;
+
+impl StreamingNodeFactory for StageNodeFactory {
+    fn create(
+        &self,
+        _node_id: String,
+        params: &serde_json::Value,
+        _session_id: Option<String>,
+    ) -> Result<Box<dyn StreamingNode>, Error> {
+        Ok(Box::new(AsyncNodeWrapper(Arc::new(StageNode {
+            tag: params["tag"].as_str().unwrap_or("stage").to_string(),
+            fail_init: params["fail_init"].as_bool().unwrap_or(false),
+            fail: params["fail"].as_bool().unwrap_or(false),
+            counted: params["counted"].as_bool().unwrap_or(false),
+        }))))
+    }
+
+    fn node_type(&self) -> &str {
+        "StageNode"
+    }
+}
+
+fn stage(id: &str, params: serde_json::Value) -> NodeManifest {
+    NodeManifest {
+        id: id.to_string(),
+        node_type: "StageNode".to_string(),
+        params,
+        ..Default::default()
+    }
+}
+
+/// `stt` backed by `local`, feeding `sink`
+fn fallback_pipeline(mut stt: NodeManifest, placement: Placement) -> Manifest {
+    stt.execution = Some(ExecutionMetadata {
+        placement,
+        reason: None,
+        fallback: Some("local".to_string()),
+    });
+    Manifest {
+        version: "v1".to_string(),
+        metadata: ManifestMetadata {
+            name: "fallback".to_string(),
+            ..Default::default()
+        },
+        nodes: vec![
+            stt,
+            stage("local", serde_json::json!({ "tag": "local" })),
+            stage("sink", serde_json::json!({ "tag": "sink" })),
+        ],
+        connections: vec![Connection::new("stt", "sink")],
+        python_env: None,
+    }
+}
+
+struct Session {
+    input_tx: mpsc::Sender<DataPacket>,
+    output_rx: mpsc::Receiver<RuntimeData>,
+    system: broadcast::Receiver<RuntimeData>,
+}
+
+impl Session {
+    async fn start(session_id: &str, manifest: Manifest, breaker_threshold: usize) -> Self {
+        let mut registry = StreamingNodeRegistry::new();
+        registry.register(Arc::new(StageNodeFactory));
+        let (output_tx, output_rx) = mpsc::channel(DEFAULT_ROUTER_OUTPUT_CAPACITY);
+
+        let (mut router, _shutdown_tx) = SessionRouter::with_config(
+            session_id.to_string(),
+            Arc::new(manifest),
+            Arc::new(registry),
+            output_tx,
+            Some(SchedulerConfig::default().with_circuit_breaker_threshold(breaker_threshold)),
+            None,
+        )
+        .unwrap();
+        let ctrl = SessionControl::new(session_id.to_string());
+        router.attach_control(ctrl.clone()).await;
+        let system = ctrl
+            .subscribe(&ControlAddress::node_out("__system__"))
+            .unwrap();
+        let input_tx = router.get_input_sender();
+        router.start();
+
+        Self {
+            input_tx,
+            output_rx,
+            system,
+        }
+    }
+
+    async fn send(&self, text: &str, seq: u64) {
+        self.input_tx
+            .send(DataPacket {
+                data: RuntimeData::Text(text.to_string()),
+                from_node: "client".to_string(),
+                to_node: None,
+                session_id: "fallback".to_string(),
+                sequence: seq,
+                sub_sequence: 0,
+                trace_context: None,
+            })
+            .await
+            .unwrap();
+    }
+
+    async fn recv_text(&mut self) -> String {
+        let out = tokio::time::timeout(Duration::from_secs(2), self.output_rx.recv())
+            .await
+            .expect("client output timeout")
+            .expect("client channel closed");
+        match out {
+            RuntimeData::Text(text) => text,
+            other => panic!("unexpected variant: {:?}", other),
+        }
+    }
+
+    async fn next_fallback_event(&mut self) -> serde_json::Value {
+        loop {
+            let event = tokio::time::timeout(Duration::from_secs(2), self.system.recv())
+                .await
+                .expect("system tap timeout")
+                .expect("system tap closed");
+            if let RuntimeData::Json(v) = event {
+                if v["kind"] == "node_fallback" {
+                    return v;
+                }
+            }
+        }
+    }
+}
+
+#[tokio::test]
+async fn init_failure_starts_the_fallback() {
+    let stt = stage(
+        "stt",
+        serde_json::json!({ "tag": "cloud", "fail_init": true }),
+    );
+    let mut session =
+        Session::start("fallback-init", fallback_pipeline(stt, Placement::Auto), 5).await;
+
+    let event = session.next_fallback_event().await;
+    assert_eq!(event["node"], "stt");
+    assert_eq!(event["from"], "stt");
+    assert_eq!(event["to"], "local");
+    assert_eq!(event["reason"], "init_failed");
+
+    session.send("x", 0).await;
+    assert_eq!(session.recv_text().await, "sink:local:x");
+}
+
+#[tokio::test]
+async fn open_circuit_breaker_switches_to_the_fallback() {
+    let stt = stage("stt", serde_json::json!({ "tag": "cloud", "fail": true }));
+    let mut session = Session::start(
+        "fallback-breaker",
+        fallback_pipeline(stt, Placement::Auto),
+        2,
+    )
+    .await;
+
+    // Two failures open the breaker; nothing reaches the client
+    session.send("a", 0).await;
+    session.send("b", 1).await;
+    let event = session.next_fallback_event().await;
+    assert_eq!(event["node"], "stt");
+    assert_eq!(event["from"], "stt");
+    assert_eq!(event["to"], "local");
+    assert_eq!(event["reason"], "circuit_open");
+
+    session.send("c", 2).await;
+    assert_eq!(session.recv_text().await, "sink:local:c");
+}
+
+#[tokio::test]
+async fn local_placement_skips_remote_candidates() {
+    let mut stt = stage("stt", serde_json::json!({ "tag": "cloud" }));
+    stt.host = Some("gpu-box:50051".to_string());
+    let mut session = Session::start(
+        "fallback-local",
+        fallback_pipeline(stt, Placement::Local),
+        5,
+    )
+    .await;
+
+    session.send("x", 0).await;
+    assert_eq!(session.recv_text().await, "sink:local:x");
+
+    // `local` was the only candidate, so nothing fell back
+    while let Ok(event) = session.system.try_recv() {
+        if let RuntimeData::Json(v) = event {
+            assert_ne!(v["kind"], "node_fallback");
+        }
+    }
+}
+
+#[tokio::test]
+async fn fallback_is_created_only_when_switching() {
+    let stt = stage("stt", serde_json::json!({ "tag": "cloud", "fail": true }));
+    let mut manifest = fallback_pipeline(stt, Placement::Auto);
+    manifest.nodes[1].params = serde_json::json!({ "tag": "local", "counted": true });
+    let mut session = Session::start("fallback-lazy", manifest, 2).await;
+
+    // The first failure leaves the breaker closed: no fallback yet
+    session.send("a", 0).await;
+    tokio::time::sleep(Duration::from_millis(100)).await;
+    assert_eq!(COUNTED_INITS.load(Ordering::SeqCst), 0);
+
+    session.send("b", 1).await;
+    assert_eq!(
+        session.next_fallback_event().await["reason"],
+        "circuit_open"
+    );
+    assert_eq!(COUNTED_INITS.load(Ordering::SeqCst), 1);
+
+    session.send("c", 2).await;
+    assert_eq!(session.recv_text().await, "sink:local:c");
+}
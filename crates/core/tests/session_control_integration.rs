@@ -49,6 +49,7 @@ fn add_packet(session_id: &str, a: f64, b: f64, seq: u64) -> DataPacket {
         session_id: session_id.to_string(),
         sequence: seq,
         sub_sequence: 0,
+        trace_context: None,
     }
 }
 
@@ -432,3 +433,48 @@ async fn close_signal_fires_on_router_shutdown() {
 
     let _ = tokio::time::timeout(Duration::from_secs(5), handle).await;
 }
+
+#[tokio::test]
+async fn terminate_closes_session_with_reason_and_keeps_stats() {
+    let session_id = "terminate-e2e".to_string();
+    let manifest = Arc::new(calc_pipeline());
+    let registry = Arc::new(create_default_streaming_registry());
+    let (output_tx, mut output_rx) = mpsc::channel(DEFAULT_ROUTER_OUTPUT_CAPACITY);
+
+    let (mut router, _shutdown_tx) =
+        SessionRouter::new(session_id.clone(), manifest, registry, output_tx).unwrap();
+
+    let ctrl = SessionControl::new(session_id.clone());
+    router.attach_control(ctrl.clone()).await;
+    let mut close_rx = ctrl.close_subscriber();
+
+    let input_tx = router.get_input_sender();
+    let handle = router.start();
+
+    input_tx.send(add_packet(&session_id, 1.0, 2.0, 1)).await.unwrap();
+    tokio::time::timeout(Duration::from_secs(10), output_rx.recv())
+        .await
+        .expect("output timeout")
+        .expect("client channel closed");
+
+    let router_handle = ctrl.router().expect("router attached").clone();
+    assert_eq!(router_handle.session_id(), session_id);
+    assert_eq!(router_handle.manifest().nodes[0].id, "calc");
+
+    assert!(ctrl.terminate("admin kill"));
+    let reason = tokio::time::timeout(Duration::from_secs(10), close_rx.recv())
+        .await
+        .expect("close-signal timeout")
+        .expect("close channel lagged");
+    match reason {
+        CloseReason::Error(msg) => assert_eq!(msg, "terminated: admin kill"),
+        other => panic!("unexpected close reason: {:?}", other),
+    }
+    let _ = tokio::time::timeout(Duration::from_secs(5), handle).await;
+
+    // The handle outlives the router; its shutdown switch reports the
+    // router is gone and the first reason sticks.
+    assert!(!router_handle.shutdown());
+    assert!(!ctrl.terminate("again"));
+    assert_eq!(ctrl.termination_reason().as_deref(), Some("admin kill"));
+}
@@ -0,0 +1,194 @@
+//! Integration test: trace context propagation through the SessionRouter.
+//!
+//! Installs a span-capturing subscriber, pushes packets through a
+//! two-node chain and checks that every dispatch opens a `node.dispatch`
+//! span carrying the trace id of the packet that caused it.
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use remotemedia_core::data::RuntimeData;
+use remotemedia_core::manifest::{Connection, Manifest, ManifestMetadata, NodeManifest};
+use remotemedia_core::nodes::{
+    AsyncNodeWrapper, AsyncStreamingNode, StreamingNode, StreamingNodeFactory,
+    StreamingNodeRegistry,
+};
+use remotemedia_core::telemetry::TraceContext;
+use remotemedia_core::transport::session_router::{
+    DataPacket, SessionRouter, DEFAULT_ROUTER_OUTPUT_CAPACITY,
+};
+use remotemedia_core::Error;
+use tokio::sync::mpsc;
+use tracing::field::{Field, Visit};
+use tracing::span::{Attributes, Id, Record};
+use tracing_subscriber::layer::{Context, SubscriberExt};
+use tracing_subscriber::Layer;
+
+const TRACEPARENT: &str = "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01";
+
+struct EchoNode;
+
+#[async_trait::async_trait]
+impl AsyncStreamingNode for EchoNode {
+    fn node_type(&self) -> &str {
+        "EchoNode"
+    }
+
+    async fn process(&self, data: RuntimeData) -> Result<RuntimeData, Error> {
+        Ok(data)
+    }
+}
+
+struct EchoNodeFactory;
+
+impl StreamingNodeFactory for EchoNodeFactory {
+    fn create(
+        &self,
+        _node_id: String,
+        _params: &serde_json::Value,
+        _session_id: Option<String>,
+    ) -> Result<Box<dyn StreamingNode>, Error> {
+        Ok(Box::new(AsyncNodeWrapper(Arc::new(EchoNode))))
+    }
+
+    fn node_type(&self) -> &str {
+        "EchoNode"
+    }
+}
+
+/// Fields of every `node.dispatch` span, in creation order
+#[derive(Clone, Default)]
+struct DispatchSpans(Arc<Mutex<Vec<(Id, HashMap<String, String>)>>>);
+
+impl DispatchSpans {
+    fn snapshot(&self) -> Vec<HashMap<String, String>> {
+        self.0
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|(_, fields)| fields.clone())
+            .collect()
+    }
+}
+
+struct FieldVisitor<'a>(&'a mut HashMap<String, String>);
+
+impl Visit for FieldVisitor<'_> {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.0.insert(field.name().to_string(), value.to_string());
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+        self.0
+            .insert(field.name().to_string(), format!("{:?}", value));
+    }
+}
+
+impl<S: tracing::Subscriber> Layer<S> for DispatchSpans {
+    fn on_new_span(&self, attrs: &Attributes<'_>, id: &Id, _ctx: Context<'_, S>) {
+        if attrs.metadata().name() != "node.dispatch" {
+            return;
+        }
+        let mut fields = HashMap::new();
+        attrs.record(&mut FieldVisitor(&mut fields));
+        self.0.lock().unwrap().push((id.clone(), fields));
+    }
+
+    fn on_record(&self, id: &Id, values: &Record<'_>, _ctx: Context<'_, S>) {
+        let mut spans = self.0.lock().unwrap();
+        if let Some((_, fields)) = spans.iter_mut().rev().find(|(span, _)| span == id) {
+            values.record(&mut FieldVisitor(fields));
+        }
+    }
+}
+
+fn chain_manifest() -> Manifest {
+    let node = |id: &str| NodeManifest {
+        id: id.to_string(),
+        node_type: "EchoNode".to_string(),
+        ..Default::default()
+    };
+    Manifest {
+        version: "v1".to_string(),
+        metadata: ManifestMetadata {
+            name: "trace-propagation".to_string(),
+            ..Default::default()
+        },
+        nodes: vec![node("a"), node("b")],
+        connections: vec![Connection::new("a", "b")],
+        python_env: None,
+    }
+}
+
+#[tokio::test]
+async fn dispatch_spans_carry_the_packet_trace() {
+    let spans = DispatchSpans::default();
+    // Current-thread runtime: every router task sees this subscriber.
+    let _guard =
+        tracing::subscriber::set_default(tracing_subscriber::registry().with(spans.clone()));
+
+    let mut registry = StreamingNodeRegistry::new();
+    registry.register(Arc::new(EchoNodeFactory));
+    let (output_tx, mut output_rx) = mpsc::channel(DEFAULT_ROUTER_OUTPUT_CAPACITY);
+    let (router, _shutdown_tx) = SessionRouter::new(
+        "trace-session".to_string(),
+        Arc::new(chain_manifest()),
+        Arc::new(registry),
+        output_tx,
+    )
+    .unwrap();
+    let input_tx = router.get_input_sender();
+    let handle = router.start();
+
+    let trace = TraceContext::parse(TRACEPARENT);
+    for (seq, trace_context) in [(0, trace), (1, None)] {
+        input_tx
+            .send(DataPacket {
+                data: RuntimeData::Text(format!("packet-{}", seq)),
+                from_node: "client".to_string(),
+                to_node: None,
+                session_id: "trace-session".to_string(),
+                sequence: seq,
+                sub_sequence: 0,
+                trace_context,
+            })
+            .await
+            .unwrap();
+        let out = tokio::time::timeout(Duration::from_secs(2), output_rx.recv())
+            .await
+            .expect("client output timeout")
+            .expect("client channel closed");
+        assert!(matches!(out, RuntimeData::Text(text) if text == format!("packet-{}", seq)));
+    }
+
+    drop(input_tx);
+    let _ = tokio::time::timeout(Duration::from_secs(2), handle).await;
+
+    let spans = spans.snapshot();
+    let summary: Vec<(&str, Option<&str>)> = spans
+        .iter()
+        .map(|fields| {
+            (
+                fields["node_id"].as_str(),
+                fields.get("trace_id").map(String::as_str),
+            )
+        })
+        .collect();
+    let trace_id = trace.unwrap().trace_id_hex();
+    assert_eq!(
+        summary,
+        vec![
+            ("a", Some(trace_id.as_str())),
+            ("b", Some(trace_id.as_str())),
+            ("a", None),
+            ("b", None),
+        ]
+    );
+    assert!(
+        spans
+            .iter()
+            .all(|fields| fields["session_id"] == "trace-session"
+                && fields["node_type"] == "EchoNode")
+    );
+}
@@ -9,7 +9,7 @@ mod ml_requirements;
 mod transport_detector;
 
 use remotemedia_core::executor::PipelineGraph;
-use remotemedia_core::manifest::Manifest;
+use remotemedia_core::manifest::{Manifest, ManifestLoader};
 use remotemedia_core::nodes::schema::RuntimeDataType;
 use serde::{Deserialize, Serialize};
 use std::path::Path;
@@ -95,6 +95,7 @@ pub fn analyze(manifest: &Manifest) -> Result<AnalysisResult, AnalyzerError> {
 }
 
 /// Parse a manifest from content, detecting format by file extension.
+/// Templated manifests are expanded first (see [`ManifestLoader`]).
 /// Handles both camelCase and snake_case JSON keys.
 fn parse_manifest(path: &Path, content: &str) -> Result<Manifest, AnalyzerError> {
     let ext = path
@@ -112,6 +113,12 @@ fn parse_manifest(path: &Path, content: &str) -> Result<Manifest, AnalyzerError>
         }
     };
 
+    // Expand `${ENV}`, template arguments and includes (relative to the file)
+    let base_dir = path.parent().unwrap_or_else(|| Path::new("."));
+    let value = ManifestLoader::new()
+        .expand(value, base_dir)
+        .map_err(|e| AnalyzerError::Parse(e.to_string()))?;
+
     // Normalize camelCase keys to snake_case
     let normalized = normalize_keys(value);
     serde_json::from_value(normalized).map_err(|e| AnalyzerError::Parse(e.to_string()))
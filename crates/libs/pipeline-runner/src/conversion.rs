@@ -36,8 +36,9 @@ pub fn convert_output_to_health_event(data: &RuntimeData) -> Option<HealthEvent>
     match data {
         RuntimeData::Json(value) => {
             // Use the library's conversion function which handles all event types:
-            // health, silence, low_volume, clipping, channel_imbalance, dropouts,
-            // drift, freeze, cadence, av_skew, stream_started, stream_ended
+            // health, silence, low_volume, clipping, channel_imbalance, loudness_violation,
+            // true_peak_violation, dropouts, drift, freeze, frozen_picture, black_frame,
+            // scene_cut, cadence, av_skew, stream_started, stream_ended
             if let Some(events) = convert_json_to_health_events(value) {
                 // Return the first event (most common case is single event)
                 events.into_iter().next()
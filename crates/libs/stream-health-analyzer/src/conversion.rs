@@ -65,6 +65,28 @@ fn convert_single_json_event(json: &Value) -> Option<HealthEvent> {
                 .and_then(|v| v.as_str().map(String::from));
             Some(HealthEvent::freeze(duration_ms, stream_id))
         }
+        "frozen_picture" => {
+            let duration_ms = json.get("duration_ms")?.as_u64()?;
+            let stream_id = json
+                .get("stream_id")
+                .and_then(|v| v.as_str().map(String::from));
+            Some(HealthEvent::frozen_picture(duration_ms, stream_id))
+        }
+        "black_frame" => {
+            let duration_ms = json.get("duration_ms")?.as_u64()?;
+            let mean_luma = json.get("mean_luma")?.as_f64()?;
+            let stream_id = json
+                .get("stream_id")
+                .and_then(|v| v.as_str().map(String::from));
+            Some(HealthEvent::black_frame(duration_ms, mean_luma, stream_id))
+        }
+        "scene_cut" => {
+            let score = json.get("score")?.as_f64()?;
+            let stream_id = json
+                .get("stream_id")
+                .and_then(|v| v.as_str().map(String::from));
+            Some(HealthEvent::scene_cut(score, stream_id))
+        }
         "health" => {
             let score = json.get("score")?.as_f64()?;
             let alerts = json
@@ -128,6 +150,34 @@ fn convert_single_json_event(json: &Value) -> Option<HealthEvent> {
                 stream_id,
             ))
         }
+        "loudness_violation" => {
+            let measure = json.get("measure")?.as_str()?.to_string();
+            let lufs = json.get("lufs")?.as_f64()?;
+            let target_lufs = json.get("target_lufs")?.as_f64()?;
+            let tolerance_lu = json.get("tolerance_lu")?.as_f64()?;
+            let stream_id = json
+                .get("stream_id")
+                .and_then(|v| v.as_str().map(String::from));
+            Some(HealthEvent::loudness_violation(
+                measure,
+                lufs,
+                target_lufs,
+                tolerance_lu,
+                stream_id,
+            ))
+        }
+        "true_peak_violation" => {
+            let true_peak_dbtp = json.get("true_peak_dbtp")?.as_f64()?;
+            let max_true_peak_dbtp = json.get("max_true_peak_dbtp")?.as_f64()?;
+            let stream_id = json
+                .get("stream_id")
+                .and_then(|v| v.as_str().map(String::from));
+            Some(HealthEvent::true_peak_violation(
+                true_peak_dbtp,
+                max_true_peak_dbtp,
+                stream_id,
+            ))
+        }
         "dropouts" => {
             let dropout_count = json.get("dropout_count")?.as_u64()? as u32;
             let stream_id = json
@@ -226,6 +276,63 @@ fn convert_schema_event(schema: &str, data: &Value) -> Option<HealthEvent> {
                 stream_id,
             ))
         }
+        "loudness_event" => {
+            let stream_id = data
+                .get("stream_id")
+                .and_then(|v| v.as_str().map(String::from));
+            let is_too_loud = data.get("is_too_loud")?.as_bool()?;
+            let is_too_quiet = data.get("is_too_quiet")?.as_bool()?;
+
+            // A target violation outranks a true-peak violation in the same event
+            if is_too_loud || is_too_quiet {
+                let measure = data.get("measure")?.as_str()?.to_string();
+                let lufs = data.get(format!("{}_lufs", measure))?.as_f64()?;
+                let target_lufs = data.get("target_lufs")?.as_f64()?;
+                let tolerance_lu = data.get("tolerance_lu")?.as_f64()?;
+                Some(HealthEvent::loudness_violation(
+                    measure,
+                    lufs,
+                    target_lufs,
+                    tolerance_lu,
+                    stream_id,
+                ))
+            } else if data.get("is_true_peak_exceeded")?.as_bool()? {
+                let true_peak_dbtp = data.get("true_peak_dbtp")?.as_f64()?;
+                let max_true_peak_dbtp = data.get("max_true_peak_dbtp")?.as_f64()?;
+                Some(HealthEvent::true_peak_violation(
+                    true_peak_dbtp,
+                    max_true_peak_dbtp,
+                    stream_id,
+                ))
+            } else {
+                None
+            }
+        }
+        "video_quality_event" => {
+            let stream_id = data
+                .get("stream_id")
+                .and_then(|v| v.as_str().map(String::from));
+
+            // Black outranks frozen (a black picture is also static), and
+            // either outranks a cut in the same report
+            if data.get("is_black")?.as_bool()? {
+                let black_ms = data.get("black_ms")?.as_f64()?;
+                let mean_luma = data.get("mean_luma")?.as_f64()?;
+                Some(HealthEvent::black_frame(
+                    black_ms as u64,
+                    mean_luma,
+                    stream_id,
+                ))
+            } else if data.get("is_frozen")?.as_bool()? {
+                let frozen_ms = data.get("frozen_ms")?.as_f64()?;
+                Some(HealthEvent::frozen_picture(frozen_ms as u64, stream_id))
+            } else if data.get("is_scene_cut")?.as_bool()? {
+                let score = data.get("scene_score")?.as_f64()?;
+                Some(HealthEvent::scene_cut(score, stream_id))
+            } else {
+                None
+            }
+        }
         _ => None,
     }
 }
@@ -352,6 +459,99 @@ mod tests {
         assert!(events[0].is_channel_imbalance());
     }
 
+    #[test]
+    fn test_convert_loudness_schema_event() {
+        let json = json!({
+            "_schema": "loudness_event",
+            "measure": "integrated",
+            "momentary_lufs": -15.2,
+            "short_term_lufs": -15.8,
+            "integrated_lufs": -16.1,
+            "true_peak_dbtp": -0.4,
+            "target_lufs": -23.0,
+            "tolerance_lu": 1.0,
+            "max_true_peak_dbtp": -1.0,
+            "is_too_loud": true,
+            "is_too_quiet": false,
+            "is_true_peak_exceeded": true,
+            "stream_id": "audio:0"
+        });
+
+        let events = convert_json_to_health_events(&json).unwrap();
+        assert_eq!(events.len(), 1);
+        match &events[0] {
+            HealthEvent::LoudnessViolation {
+                measure, lufs, ..
+            } => {
+                assert_eq!(measure, "integrated");
+                assert!((*lufs - -16.1).abs() < 1e-9);
+            }
+            _ => panic!("Expected LoudnessViolation event"),
+        }
+
+        // On target but over the peak limit
+        let mut json = json;
+        json["is_too_loud"] = json!(false);
+        let events = convert_json_to_health_events(&json).unwrap();
+        assert!(matches!(events[0], HealthEvent::TruePeakViolation { .. }));
+
+        json["is_true_peak_exceeded"] = json!(false);
+        assert!(convert_json_to_health_events(&json).is_none());
+    }
+
+    #[test]
+    fn test_convert_video_quality_schema_event() {
+        let json = json!({
+            "_schema": "video_quality_event",
+            "mean_luma": 14.2,
+            "black_ratio": 1.0,
+            "frame_diff": 0.0,
+            "scene_score": 0.0,
+            "is_frozen": false,
+            "frozen_ms": 0.0,
+            "is_black": true,
+            "black_ms": 840.0,
+            "is_scene_cut": false,
+            "health": 0.0,
+            "stream_id": "video:0"
+        });
+
+        let events = convert_json_to_health_events(&json).unwrap();
+        match &events[0] {
+            HealthEvent::BlackFrame {
+                duration_ms,
+                stream_id,
+                ..
+            } => {
+                assert_eq!(*duration_ms, 840);
+                assert_eq!(stream_id.as_deref(), Some("video:0"));
+            }
+            _ => panic!("Expected BlackFrame event"),
+        }
+
+        let mut json = json;
+        json["is_black"] = json!(false);
+        json["is_frozen"] = json!(true);
+        json["frozen_ms"] = json!(2500.0);
+        let events = convert_json_to_health_events(&json).unwrap();
+        assert!(matches!(
+            events[0],
+            HealthEvent::FrozenPicture {
+                duration_ms: 2500,
+                ..
+            }
+        ));
+
+        json["is_frozen"] = json!(false);
+        json["is_scene_cut"] = json!(true);
+        json["scene_score"] = json!(0.9);
+        let events = convert_json_to_health_events(&json).unwrap();
+        assert!(matches!(events[0], HealthEvent::SceneCut { .. }));
+
+        json["is_scene_cut"] = json!(false);
+        assert!(convert_json_to_health_events(&json).is_none());
+    }
+
     #[test]
     fn test_no_issue_returns_none() {
         // Audio level event with no issues should return None
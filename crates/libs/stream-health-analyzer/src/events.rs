@@ -35,6 +35,41 @@ pub enum HealthEvent {
         stream_id: Option<String>,
     },
 
+    /// Picture content unchanged (frames still arriving)
+    FrozenPicture {
+        /// Timestamp when the frozen picture was detected
+        ts: DateTime<Utc>,
+        /// How long the picture has been unchanged in milliseconds
+        duration_ms: u64,
+        /// Stream identifier (if available)
+        #[serde(skip_serializing_if = "Option::is_none")]
+        stream_id: Option<String>,
+    },
+
+    /// Picture is black
+    BlackFrame {
+        /// Timestamp when the black picture was detected
+        ts: DateTime<Utc>,
+        /// How long the picture has been black in milliseconds
+        duration_ms: u64,
+        /// Mean luma of the picture (0-255)
+        mean_luma: f64,
+        /// Stream identifier (if available)
+        #[serde(skip_serializing_if = "Option::is_none")]
+        stream_id: Option<String>,
+    },
+
+    /// Shot change in the picture
+    SceneCut {
+        /// Timestamp of the cut
+        ts: DateTime<Utc>,
+        /// Luma histogram distance to the previous frame (0-1)
+        score: f64,
+        /// Stream identifier (if available)
+        #[serde(skip_serializing_if = "Option::is_none")]
+        stream_id: Option<String>,
+    },
+
     /// Periodic health score update
     Health {
         /// Timestamp of the health score
@@ -117,6 +152,36 @@ pub enum HealthEvent {
         stream_id: Option<String>,
     },
 
+    /// Loudness outside the target range (EBU R128 / BS.1770)
+    LoudnessViolation {
+        /// Timestamp when the violation was detected
+        ts: DateTime<Utc>,
+        /// Measure compared against the target ("momentary", "short_term", "integrated")
+        measure: String,
+        /// Measured loudness in LUFS
+        lufs: f64,
+        /// Loudness target in LUFS
+        target_lufs: f64,
+        /// Allowed deviation from target in LU
+        tolerance_lu: f64,
+        /// Stream identifier (if available)
+        #[serde(skip_serializing_if = "Option::is_none")]
+        stream_id: Option<String>,
+    },
+
+    /// True-peak level above the permitted maximum
+    TruePeakViolation {
+        /// Timestamp when the violation was detected
+        ts: DateTime<Utc>,
+        /// Measured true-peak in dBTP
+        true_peak_dbtp: f64,
+        /// Permitted maximum true-peak in dBTP
+        max_true_peak_dbtp: f64,
+        /// Stream identifier (if available)
+        #[serde(skip_serializing_if = "Option::is_none")]
+        stream_id: Option<String>,
+    },
+
     /// Intermittent audio dropouts detected
     Dropouts {
         /// Timestamp when dropouts were detected
@@ -173,6 +238,34 @@ impl HealthEvent {
         }
     }
 
+    /// Create a new frozen picture event
+    pub fn frozen_picture(duration_ms: u64, stream_id: Option<String>) -> Self {
+        Self::FrozenPicture {
+            ts: Utc::now(),
+            duration_ms,
+            stream_id,
+        }
+    }
+
+    /// Create a new black frame event
+    pub fn black_frame(duration_ms: u64, mean_luma: f64, stream_id: Option<String>) -> Self {
+        Self::BlackFrame {
+            ts: Utc::now(),
+            duration_ms,
+            mean_luma,
+            stream_id,
+        }
+    }
+
+    /// Create a new scene cut event
+    pub fn scene_cut(score: f64, stream_id: Option<String>) -> Self {
+        Self::SceneCut {
+            ts: Utc::now(),
+            score,
+            stream_id,
+        }
+    }
+
     /// Create a new health score event
     pub fn health(score: f64, alerts: Vec<String>) -> Self {
         Self::Health {
@@ -244,6 +337,38 @@ impl HealthEvent {
         }
     }
 
+    /// Create a new loudness violation event
+    pub fn loudness_violation(
+        measure: String,
+        lufs: f64,
+        target_lufs: f64,
+        tolerance_lu: f64,
+        stream_id: Option<String>,
+    ) -> Self {
+        Self::LoudnessViolation {
+            ts: Utc::now(),
+            measure,
+            lufs,
+            target_lufs,
+            tolerance_lu,
+            stream_id,
+        }
+    }
+
+    /// Create a new true-peak violation event
+    pub fn true_peak_violation(
+        true_peak_dbtp: f64,
+        max_true_peak_dbtp: f64,
+        stream_id: Option<String>,
+    ) -> Self {
+        Self::TruePeakViolation {
+            ts: Utc::now(),
+            true_peak_dbtp,
+            max_true_peak_dbtp,
+            stream_id,
+        }
+    }
+
     /// Create a new dropouts event
     pub fn dropouts(dropout_count: u32, stream_id: Option<String>) -> Self {
         Self::Dropouts {
@@ -277,6 +402,9 @@ impl HealthEvent {
         match self {
             Self::Drift { ts, .. } => *ts,
             Self::Freeze { ts, .. } => *ts,
+            Self::FrozenPicture { ts, .. } => *ts,
+            Self::BlackFrame { ts, .. } => *ts,
+            Self::SceneCut { ts, .. } => *ts,
             Self::Health { ts, .. } => *ts,
             Self::Cadence { ts, .. } => *ts,
             Self::AvSkew { ts, .. } => *ts,
@@ -284,6 +412,8 @@ impl HealthEvent {
             Self::LowVolume { ts, .. } => *ts,
             Self::Clipping { ts, .. } => *ts,
             Self::ChannelImbalance { ts, .. } => *ts,
+            Self::LoudnessViolation { ts, .. } => *ts,
+            Self::TruePeakViolation { ts, .. } => *ts,
             Self::Dropouts { ts, .. } => *ts,
             Self::StreamStarted { ts, .. } => *ts,
             Self::StreamEnded { ts, .. } => *ts,
@@ -295,6 +425,9 @@ impl HealthEvent {
         match self {
             Self::Drift { .. } => "drift",
             Self::Freeze { .. } => "freeze",
+            Self::FrozenPicture { .. } => "frozen_picture",
+            Self::BlackFrame { .. } => "black_frame",
+            Self::SceneCut { .. } => "scene_cut",
             Self::Health { .. } => "health",
             Self::Cadence { .. } => "cadence",
             Self::AvSkew { .. } => "av_skew",
@@ -302,6 +435,8 @@ impl HealthEvent {
             Self::LowVolume { .. } => "low_volume",
             Self::Clipping { .. } => "clipping",
             Self::ChannelImbalance { .. } => "channel_imbalance",
+            Self::LoudnessViolation { .. } => "loudness_violation",
+            Self::TruePeakViolation { .. } => "true_peak_violation",
             Self::Dropouts { .. } => "dropouts",
             Self::StreamStarted { .. } => "stream_started",
             Self::StreamEnded { .. } => "stream_ended",
@@ -318,6 +453,11 @@ impl HealthEvent {
         matches!(self, Self::Freeze { .. })
     }
 
+    /// Check if this is a frozen picture or black frame event
+    pub fn is_picture_issue(&self) -> bool {
+        matches!(self, Self::FrozenPicture { .. } | Self::BlackFrame { .. })
+    }
+
     /// Check if this is a health event
     pub fn is_health(&self) -> bool {
         matches!(self, Self::Health { .. })
@@ -338,18 +478,30 @@ impl HealthEvent {
         matches!(self, Self::ChannelImbalance { .. })
     }
 
+    /// Check if this is a loudness or true-peak violation event
+    pub fn is_loudness_violation(&self) -> bool {
+        matches!(
+            self,
+            Self::LoudnessViolation { .. } | Self::TruePeakViolation { .. }
+        )
+    }
+
     /// Check if this is an alert event (not health score or system event)
     pub fn is_alert(&self) -> bool {
         matches!(
             self,
             Self::Drift { .. }
                 | Self::Freeze { .. }
+                | Self::FrozenPicture { .. }
+                | Self::BlackFrame { .. }
                 | Self::Cadence { .. }
                 | Self::AvSkew { .. }
                 | Self::Silence { .. }
                 | Self::LowVolume { .. }
                 | Self::Clipping { .. }
                 | Self::ChannelImbalance { .. }
+                | Self::LoudnessViolation { .. }
+                | Self::TruePeakViolation { .. }
                 | Self::Dropouts { .. }
         )
     }
@@ -484,6 +636,29 @@ mod tests {
         assert!(!json.contains("stream_id")); // Should be skipped when None
     }
 
+    #[test]
+    fn test_picture_event_serialization() {
+        let event = HealthEvent::frozen_picture(2400, Some("video".to_string()));
+        let json = serde_json::to_string(&event).unwrap();
+        assert!(json.contains("\"type\":\"frozen_picture\""));
+        assert!(json.contains("\"duration_ms\":2400"));
+        assert!(event.is_alert());
+        assert!(event.is_picture_issue());
+
+        let event = HealthEvent::black_frame(600, 16.5, None);
+        let json = serde_json::to_string(&event).unwrap();
+        assert!(json.contains("\"type\":\"black_frame\""));
+        assert!(json.contains("\"mean_luma\":16.5"));
+        assert!(event.is_picture_issue());
+
+        // Scene cuts are informational, not alerts
+        let event = HealthEvent::scene_cut(0.82, None);
+        let json = serde_json::to_string(&event).unwrap();
+        assert!(json.contains("\"type\":\"scene_cut\""));
+        assert!(!event.is_alert());
+        assert!(!event.is_picture_issue());
+    }
+
     #[test]
     fn test_health_event_serialization() {
         let event = HealthEvent::health(0.72, vec!["DRIFT_SLOPE".to_string(), "FREEZE".to_string()]);
@@ -511,6 +686,30 @@ mod tests {
         assert!(json.contains("\"reason\":\"client_disconnect\""));
     }
 
+    #[test]
+    fn test_loudness_violation_serialization() {
+        let event = HealthEvent::loudness_violation(
+            "integrated".to_string(),
+            -16.5,
+            -23.0,
+            1.0,
+            Some("program".to_string()),
+        );
+        let json = serde_json::to_string(&event).unwrap();
+        assert!(json.contains("\"type\":\"loudness_violation\""));
+        assert!(json.contains("\"measure\":\"integrated\""));
+        assert!(json.contains("\"lufs\":-16.5"));
+        assert!(json.contains("\"target_lufs\":-23.0"));
+        assert!(event.is_alert());
+        assert!(event.is_loudness_violation());
+
+        let event = HealthEvent::true_peak_violation(0.3, -1.0, None);
+        let json = serde_json::to_string(&event).unwrap();
+        assert!(json.contains("\"type\":\"true_peak_violation\""));
+        assert!(json.contains("\"max_true_peak_dbtp\":-1.0"));
+        assert!(!json.contains("stream_id"));
+    }
+
     #[test]
     fn test_event_type_categorization() {
         assert!(HealthEvent::drift(50, 50, None).is_alert());
@@ -19,6 +19,11 @@ fn test_all_event_types_serialize() {
         HealthEvent::low_volume(-45.0, -40.0, None),
         HealthEvent::clipping(0.05, 3.0, None),
         HealthEvent::channel_imbalance(6.0, "left".into(), None),
+        HealthEvent::loudness_violation("integrated".into(), -16.0, -23.0, 1.0, None),
+        HealthEvent::true_peak_violation(0.5, -1.0, None),
+        HealthEvent::frozen_picture(2500, None),
+        HealthEvent::black_frame(800, 16.0, None),
+        HealthEvent::scene_cut(0.75, None),
         HealthEvent::dropouts(3, None),
         HealthEvent::stream_started(Some("session-123".into())),
         HealthEvent::stream_ended(60000, "completed".into(), Some("session-123".into())),
@@ -43,6 +48,10 @@ fn test_round_trip_serialization() {
         HealthEvent::health(0.85, vec!["silence".into(), "drift".into()]),
         HealthEvent::silence(2000.0, -55.0, None),
         HealthEvent::clipping(0.1, 2.5, Some("mic-channel".into())),
+        HealthEvent::loudness_violation("short_term".into(), -30.5, -23.0, 1.0, Some("program".into())),
+        HealthEvent::true_peak_violation(-0.2, -1.0, Some("program".into())),
+        HealthEvent::black_frame(1200, 12.5, Some("video".into())),
+        HealthEvent::scene_cut(0.6, Some("video".into())),
     ];
 
     for original in events {
@@ -66,6 +75,11 @@ fn test_event_type_field() {
         (HealthEvent::low_volume(0.0, 0.0, None), "low_volume"),
         (HealthEvent::clipping(0.0, 0.0, None), "clipping"),
         (HealthEvent::channel_imbalance(0.0, "".into(), None), "channel_imbalance"),
+        (HealthEvent::loudness_violation("".into(), 0.0, 0.0, 0.0, None), "loudness_violation"),
+        (HealthEvent::true_peak_violation(0.0, 0.0, None), "true_peak_violation"),
+        (HealthEvent::frozen_picture(0, None), "frozen_picture"),
+        (HealthEvent::black_frame(0, 0.0, None), "black_frame"),
+        (HealthEvent::scene_cut(0.0, None), "scene_cut"),
         (HealthEvent::dropouts(0, None), "dropouts"),
         (HealthEvent::stream_started(None), "stream_started"),
         (HealthEvent::stream_ended(0, "".into(), None), "stream_ended"),
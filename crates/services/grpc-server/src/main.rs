@@ -30,8 +30,10 @@
 //! - `GRPC_MAX_TIMEOUT_SEC`: Maximum execution timeout in seconds (default: `5`)
 //! - `GRPC_JSON_LOGGING`: Enable JSON structured logging (default: `true`)
 //! - `RUST_LOG`: Logging level (default: `info`)
+//! - `OTEL_EXPORTER_OTLP_ENDPOINT`: OTLP collector for pipeline spans
+//!   (requires the `otel` feature)
 
-use remotemedia_grpc::{init_tracing, GrpcServerBuilder};
+use remotemedia_grpc::GrpcServerBuilder;
 use tracing::info;
 
 fn main() -> Result<(), Box<dyn std::error::Error>> {
@@ -50,7 +52,11 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         let json_logging = std::env::var("GRPC_JSON_LOGGING")
             .map(|v| v.to_lowercase() == "true")
             .unwrap_or(true);
-        init_tracing(json_logging);
+        #[cfg(feature = "otel")]
+        let _otlp_guard =
+            remotemedia_grpc::init_tracing_with_otlp(json_logging, "remotemedia-grpc-server");
+        #[cfg(not(feature = "otel"))]
+        remotemedia_grpc::init_tracing(json_logging);
 
         info!(
             version = env!("CARGO_PKG_VERSION"),
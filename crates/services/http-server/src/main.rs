@@ -19,17 +19,27 @@
 //!
 //! - `HTTP_BIND_ADDRESS`: Server bind address (default: `127.0.0.1:8080`)
 //! - `RUST_LOG`: Logging level (default: `info`)
+//! - `OTEL_EXPORTER_OTLP_ENDPOINT`: OTLP collector for pipeline spans
+//!   (requires the `otel` feature)
 
 use remotemedia_http::HttpServerBuilder;
 use tracing::info;
+use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
 
 fn main() -> Result<(), Box<dyn std::error::Error>> {
     // Initialize tracing
-    tracing_subscriber::fmt()
-        .with_env_filter(
+    #[cfg(feature = "otel")]
+    let (otel, _otlp_guard) =
+        remotemedia_core::telemetry::otlp::layer("remotemedia-http-server")?.unzip();
+    #[cfg(not(feature = "otel"))]
+    let otel: Option<tracing_subscriber::layer::Identity> = None;
+    tracing_subscriber::registry()
+        .with(otel)
+        .with(
             tracing_subscriber::EnvFilter::try_from_default_env()
                 .unwrap_or_else(|_| tracing_subscriber::EnvFilter::new("info")),
         )
+        .with(tracing_subscriber::fmt::layer())
         .init();
 
     info!(
@@ -19,12 +19,18 @@ const DEFAULT_LABELS: Record<string, string> = {
   'clipping': 'Audio clipping',
   'low_volume': 'Low volume',
   'channel_imbalance': 'Channel imbalance',
+  'loudness_violation': 'Loudness off target',
+  'true_peak_violation': 'True-peak over limit',
   'dropouts': 'Audio dropouts',
   // Drift/timing alerts (from HealthEmitterNode)
   'drift': 'A/V drift detected',
   'av_skew': 'A/V skew detected',
   'cadence': 'Frame cadence unstable',
   'freeze': 'Stream freeze detected',
+  // Picture alerts (from VideoQualityNode)
+  'frozen_picture': 'Frozen picture',
+  'black_frame': 'Black picture',
+  'scene_cut': 'Scene cut',
   // Timing events
   'timing.jitter_spike': 'Timing jitter spike',
   'timing.clock_drift': 'Clock drift detected',
@@ -75,6 +75,36 @@ export interface LowVolumeEvent extends StreamEvent {
   threshold_db: number;
 }
 
+export interface LoudnessViolationEvent extends StreamEvent {
+  event_type: 'loudness_violation';
+  measure: 'momentary' | 'short_term' | 'integrated';
+  lufs: number;
+  target_lufs: number;
+  tolerance_lu: number;
+}
+
+export interface TruePeakViolationEvent extends StreamEvent {
+  event_type: 'true_peak_violation';
+  true_peak_dbtp: number;
+  max_true_peak_dbtp: number;
+}
+
+export interface FrozenPictureEvent extends StreamEvent {
+  event_type: 'frozen_picture';
+  duration_ms: number;
+}
+
+export interface BlackFrameEvent extends StreamEvent {
+  event_type: 'black_frame';
+  duration_ms: number;
+  mean_luma: number;
+}
+
+export interface SceneCutEvent extends StreamEvent {
+  event_type: 'scene_cut';
+  score: number;
+}
+
 /** Incident (correlated events) */
 export interface IncidentEvent extends StreamEvent {
   event_type: 'incident.created' | 'incident.updated' | 'incident.resolved';
@@ -102,6 +132,11 @@ export type AnyStreamEvent =
   | ClippingEvent
   | ChannelImbalanceEvent
   | LowVolumeEvent
+  | LoudnessViolationEvent
+  | TruePeakViolationEvent
+  | FrozenPictureEvent
+  | BlackFrameEvent
+  | SceneCutEvent
   | IncidentEvent
   | SystemEvent
   | StreamEvent;
@@ -131,6 +166,8 @@ export function getEventCategory(eventType: string): EventCategory {
   // Audio quality and drift/timing alerts
   if (eventType.includes('silence') || eventType.includes('clipping') ||
       eventType.includes('imbalance') || eventType.includes('low_volume') ||
+      eventType.includes('loudness') || eventType.includes('true_peak') ||
+      eventType === 'frozen_picture' || eventType === 'black_frame' ||
       DRIFT_ALERT_TYPES.has(eventType)) return 'alert';
   return 'event';
 }
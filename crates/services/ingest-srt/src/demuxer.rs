@@ -12,6 +12,8 @@ use std::thread;
 
 use tokio::sync::mpsc;
 
+use remotemedia_core::data::video::VideoCodec;
+
 /// Decoded audio frame
 #[derive(Debug, Clone)]
 pub struct DecodedAudio {
@@ -21,13 +23,20 @@ pub struct DecodedAudio {
     pub timestamp_us: u64,
 }
 
-/// Video timing information (no pixel data - just PTS for A/V sync)
+/// Video timing information (PTS for A/V sync), plus the encoded packet
+/// when its codec can be decoded by the pipeline
 #[derive(Debug, Clone)]
 pub struct VideoTiming {
     /// Presentation timestamp in microseconds
     pub timestamp_us: u64,
     /// Frame duration in microseconds (based on framerate)
     pub duration_us: u64,
+    /// Codec of `data`; `None` for codecs the pipeline cannot decode
+    pub codec: Option<VideoCodec>,
+    /// Encoded packet (empty when `codec` is `None`)
+    pub data: Vec<u8>,
+    /// Packet starts a keyframe
+    pub is_keyframe: bool,
 }
 
 /// Thread-safe ring buffer for feeding data to FFmpeg
@@ -120,6 +129,9 @@ pub struct MpegTsDemuxer {
     /// Channel to send video timing (optional - for A/V sync)
     video_tx: Option<mpsc::Sender<VideoTiming>>,
 
+    /// Copy encoded video packets into `VideoTiming::data`
+    copy_video_packets: bool,
+
     /// Target sample rate for output
     pub target_sample_rate: u32,
 
@@ -143,6 +155,7 @@ impl MpegTsDemuxer {
             input_rx,
             audio_tx,
             video_tx: None,
+            copy_video_packets: false,
             target_sample_rate,
             target_channels,
             session_id,
@@ -162,12 +175,20 @@ impl MpegTsDemuxer {
             input_rx,
             audio_tx,
             video_tx: Some(video_tx),
+            copy_video_packets: false,
             target_sample_rate,
             target_channels,
             session_id,
         }
     }
 
+    /// Also forward the encoded video packets, for pipelines that decode
+    /// video; otherwise only their timing is sent
+    pub fn with_video_packets(mut self, copy: bool) -> Self {
+        self.copy_video_packets = copy;
+        self
+    }
+
     /// Run the demuxer, processing incoming MPEG-TS data
     pub async fn run(mut self) {
         tracing::info!(
@@ -187,9 +208,10 @@ impl MpegTsDemuxer {
 
         // Spawn worker thread for FFmpeg processing
         let session_id = self.session_id.clone();
+        let copy_video_packets = self.video_tx.is_some() && self.copy_video_packets;
 
         let worker_handle = thread::spawn(move || {
-            worker_thread_main(ring_buffer, cmd_rx, resp_tx, session_id);
+            worker_thread_main(ring_buffer, cmd_rx, resp_tx, session_id, copy_video_packets);
         });
 
         // Feed data to ring buffer and forward decoded audio
@@ -325,6 +347,7 @@ fn worker_thread_main(
     cmd_rx: std::sync::mpsc::Receiver<WorkerCommand>,
     resp_tx: std::sync::mpsc::Sender<WorkerResponse>,
     session_id: String,
+    copy_video_packets: bool,
 ) {
     tracing::info!(session_id = %session_id, "FFmpeg worker thread starting");
 
@@ -495,6 +518,29 @@ fn worker_thread_main(
             None
         };
 
+        // Packets are copied only when the pipeline decodes video, and only
+        // for codecs its video decoder handles
+        let video_codec = if copy_video_packets && video_stream_index >= 0 {
+            let video_stream = *(*format_ctx).streams.offset(video_stream_index as isize);
+            let codec_id = (*(*video_stream).codecpar).codec_id;
+            let codec = match codec_id {
+                ffi::AVCodecID::AV_CODEC_ID_H264 => Some(VideoCodec::H264),
+                ffi::AVCodecID::AV_CODEC_ID_VP8 => Some(VideoCodec::Vp8),
+                ffi::AVCodecID::AV_CODEC_ID_AV1 => Some(VideoCodec::Av1),
+                _ => None,
+            };
+            if codec.is_none() {
+                tracing::info!(
+                    session_id = %session_id,
+                    codec_id = ?codec_id,
+                    "Video codec not decodable by the pipeline; forwarding timing only"
+                );
+            }
+            codec
+        } else {
+            None
+        };
+
         let audio_stream = *(*format_ctx).streams.offset(audio_stream_index as isize);
         let codec_params = (*audio_stream).codecpar;
 
@@ -619,7 +665,7 @@ fn worker_thread_main(
 
             let stream_index = (*packet).stream_index;
 
-            // Handle video packets (just extract PTS, no decoding)
+            // Handle video packets (extract PTS and copy the packet, no decoding)
             if video_stream_index >= 0 && stream_index == video_stream_index {
                 video_packets_processed += 1;
 
@@ -643,9 +689,20 @@ fn worker_thread_main(
                         33333
                     };
 
+                    let data = match video_codec {
+                        Some(_) if !(*packet).data.is_null() && (*packet).size > 0 => {
+                            std::slice::from_raw_parts((*packet).data, (*packet).size as usize)
+                                .to_vec()
+                        }
+                        _ => Vec::new(),
+                    };
+
                     let video_timing = VideoTiming {
                         timestamp_us,
                         duration_us,
+                        codec: video_codec,
+                        data,
+                        is_keyframe: (*packet).flags & ffi::AV_PKT_FLAG_KEY as i32 != 0,
                     };
 
                     // Log first video packet
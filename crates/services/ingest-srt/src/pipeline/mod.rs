@@ -25,7 +25,7 @@ use crate::session::IngestSession;
 /// Run a pipeline for a session, consuming raw MPEG-TS data from the input channel
 ///
 /// This function:
-/// 1. Demuxes MPEG-TS to extract audio and video timing
+/// 1. Demuxes MPEG-TS to extract audio, video timing and encoded video packets
 /// 2. Feeds decoded audio and video timing to PipelineExecutor
 /// 3. Emits health events based on analysis results (including A/V drift)
 pub async fn run_pipeline(
@@ -39,6 +39,11 @@ pub async fn run_pipeline(
     let (audio_tx, audio_rx) = mpsc::channel::<DecodedAudio>(100);
     let (video_tx, video_rx) = mpsc::channel::<VideoTiming>(100);
 
+    // Encoded video is only copied out of the stream for pipelines that
+    // analyse picture content; the others just need timing
+    let manifest = load_manifest(&session);
+    let forward_video_packets = manifest.as_ref().is_ok_and(decodes_video);
+
     // Spawn demuxer task with video support for A/V sync
     let demuxer = MpegTsDemuxer::with_video(
         input_rx,
@@ -47,11 +52,24 @@ pub async fn run_pipeline(
         16000,  // Target sample rate for analysis
         1,      // Mono
         session_id.clone(),
-    );
+    )
+    .with_video_packets(forward_video_packets);
     let demuxer_handle = tokio::spawn(demuxer.run());
 
     // Run pipeline processing with decoded audio and video timing
-    let result = run_pipeline_with_av(session.clone(), audio_rx, video_rx).await;
+    let result = match manifest {
+        Ok(manifest) => {
+            run_pipeline_with_av(
+                session.clone(),
+                manifest,
+                forward_video_packets,
+                audio_rx,
+                video_rx,
+            )
+            .await
+        }
+        Err(e) => Err(e),
+    };
 
     if let Err(e) = result {
         warn!(session_id = %session_id, error = %e, "Pipeline processing error");
@@ -63,9 +81,29 @@ pub async fn run_pipeline(
     info!(session_id = %session_id, "Pipeline processing task ended");
 }
 
+/// Parse the manifest of the session's pipeline template
+fn load_manifest(session: &IngestSession) -> Result<Manifest, PipelineError> {
+    let registry = PipelineRegistry::with_defaults();
+    let template = registry.get(&session.pipeline_id)
+        .ok_or_else(|| PipelineError::TemplateNotFound(session.pipeline_id.clone()))?;
+
+    serde_yaml::from_str(&template.manifest)
+        .map_err(|e| PipelineError::InvalidManifest(e.to_string()))
+}
+
+/// Whether any node in `manifest` decodes the encoded video packets
+fn decodes_video(manifest: &Manifest) -> bool {
+    manifest
+        .nodes
+        .iter()
+        .any(|node| node.node_type == "VideoQualityNode")
+}
+
 /// Run the analysis pipeline with decoded audio and video timing
 async fn run_pipeline_with_av(
     session: Arc<IngestSession>,
+    manifest: Manifest,
+    forward_video_packets: bool,
     mut audio_rx: mpsc::Receiver<DecodedAudio>,
     mut video_rx: mpsc::Receiver<VideoTiming>,
 ) -> Result<(), PipelineError> {
@@ -75,14 +113,6 @@ async fn run_pipeline_with_av(
     let executor = PipelineExecutor::new()
         .map_err(|e| PipelineError::Execution(e.to_string()))?;
 
-    // Get manifest for the pipeline template
-    let registry = PipelineRegistry::with_defaults();
-    let template = registry.get(&session.pipeline_id)
-        .ok_or_else(|| PipelineError::TemplateNotFound(session.pipeline_id.clone()))?;
-
-    // Parse manifest from template
-    let manifest: Manifest = serde_yaml::from_str(&template.manifest)
-        .map_err(|e| PipelineError::InvalidManifest(e.to_string()))?;
     let manifest = Arc::new(manifest);
 
     // Create streaming session
@@ -160,18 +190,24 @@ async fn run_pipeline_with_av(
                             .map(|d| d.as_micros() as u64)
                             .unwrap_or(0);
 
-                        // Create RuntimeData::Video with just timing info
-                        // Using minimal placeholder data since we only need PTS for drift detection
+                        // Create RuntimeData::Video with the encoded packet, or with just
+                        // timing info (placeholder data) when only PTS is needed for drift detection
                         use remotemedia_core::data::video::PixelFormat;
+                        let (pixel_data, format, codec) = match video.codec {
+                            Some(codec) if forward_video_packets && !video.data.is_empty() => {
+                                (video.data, PixelFormat::Encoded, Some(codec))
+                            }
+                            _ => (vec![], PixelFormat::Unspecified, None),
+                        };
                         let runtime_data = RuntimeData::Video {
-                            pixel_data: vec![],  // No pixel data - just timing
+                            pixel_data,
                             width: 1,  // Minimal valid dimensions
                             height: 1,
-                            format: PixelFormat::Unspecified,
-                            codec: None,
+                            format,
+                            codec,
                             frame_number: video_frame_count as u64,
                             timestamp_us: video.timestamp_us,
-                            is_keyframe: false,
+                            is_keyframe: video.is_keyframe,
                             stream_id: Some(session_id.clone()),
                             arrival_ts_us: Some(arrival_ts),
                         };
@@ -334,4 +370,14 @@ mod tests {
         let event = convert_output_to_health_event(&data);
         assert!(event.is_some());
     }
+
+    #[test]
+    fn test_decodes_video() {
+        let registry = PipelineRegistry::with_defaults();
+        let manifest = |id: &str| -> Manifest {
+            serde_yaml::from_str(&registry.get(id).unwrap().manifest).unwrap()
+        };
+        assert!(decodes_video(&manifest("demo_video_integrity_v1")));
+        assert!(!decodes_video(&manifest("demo_audio_quality_v1")));
+    }
 }
@@ -66,10 +66,15 @@ impl WebhookPayload {
         let session_id = match event {
             HealthEvent::Drift { stream_id, .. } => stream_id.clone().unwrap_or_default(),
             HealthEvent::Freeze { stream_id, .. } => stream_id.clone().unwrap_or_default(),
+            HealthEvent::FrozenPicture { stream_id, .. } => stream_id.clone().unwrap_or_default(),
+            HealthEvent::BlackFrame { stream_id, .. } => stream_id.clone().unwrap_or_default(),
+            HealthEvent::SceneCut { stream_id, .. } => stream_id.clone().unwrap_or_default(),
             HealthEvent::Silence { stream_id, .. } => stream_id.clone().unwrap_or_default(),
             HealthEvent::LowVolume { stream_id, .. } => stream_id.clone().unwrap_or_default(),
             HealthEvent::Clipping { stream_id, .. } => stream_id.clone().unwrap_or_default(),
             HealthEvent::ChannelImbalance { stream_id, .. } => stream_id.clone().unwrap_or_default(),
+            HealthEvent::LoudnessViolation { stream_id, .. } => stream_id.clone().unwrap_or_default(),
+            HealthEvent::TruePeakViolation { stream_id, .. } => stream_id.clone().unwrap_or_default(),
             HealthEvent::Dropouts { stream_id, .. } => stream_id.clone().unwrap_or_default(),
             HealthEvent::StreamStarted { session_id, .. } => session_id.clone().unwrap_or_default(),
             HealthEvent::StreamEnded { session_id, .. } => session_id.clone().unwrap_or_default(),
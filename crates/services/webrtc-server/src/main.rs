@@ -11,6 +11,13 @@
 //!   --grpc-address 0.0.0.0:50051 \
 //!   --manifest ./examples/loopback.yaml
 //!
+//! # Fill in a templated manifest's arguments
+//! cargo run -p remotemedia-webrtc-server -- \
+//!   --mode grpc \
+//!   --manifest ./examples/voice-agent.yaml \
+//!   --manifest-arg stt_model=large-v3 \
+//!   --manifest-arg vad_threshold=0.6
+//!
 //! # Start WebSocket client mode (connects to signaling server)
 //! cargo run -p remotemedia-webrtc-server -- \
 //!   --mode websocket \
@@ -23,6 +30,7 @@
 //! ```
 
 use clap::Parser;
+use remotemedia_core::manifest::ManifestLoader;
 use remotemedia_webrtc::{WebRtcServerBuilder, WebRtcSignalingServerBuilder};
 use std::path::PathBuf;
 use tracing::info;
@@ -51,6 +59,10 @@ struct Args {
     )]
     manifest: PathBuf,
 
+    /// Template argument for the manifest, as NAME=VALUE (repeatable)
+    #[arg(long = "manifest-arg", value_name = "NAME=VALUE", value_parser = parse_manifest_arg)]
+    manifest_args: Vec<(String, String)>,
+
     /// WebSocket signaling URL (WebSocket mode only)
     #[arg(
         long,
@@ -88,14 +100,27 @@ enum ServerMode {
     Websocket,
 }
 
+fn parse_manifest_arg(s: &str) -> Result<(String, String), String> {
+    s.split_once('=')
+        .map(|(name, value)| (name.to_string(), value.to_string()))
+        .ok_or_else(|| format!("expected NAME=VALUE, got '{}'", s))
+}
+
 fn main() -> Result<(), Box<dyn std::error::Error>> {
     let args = Args::parse();
 
-    // Initialize tracing
+    // Initialize tracing; pipeline spans also go to an OTLP collector when
+    // built with `otel` and OTEL_EXPORTER_OTLP_ENDPOINT is set
     let env_filter = EnvFilter::try_from_default_env()
         .or_else(|_| EnvFilter::try_new("info"))
         .unwrap();
+    #[cfg(feature = "otel")]
+    let (otel, _otlp_guard) =
+        remotemedia_core::telemetry::otlp::layer("remotemedia-webrtc-server")?.unzip();
+    #[cfg(not(feature = "otel"))]
+    let otel: Option<tracing_subscriber::layer::Identity> = None;
     tracing_subscriber::registry()
+        .with(otel)
         .with(env_filter)
         .with(tracing_subscriber::fmt::layer())
         .init();
@@ -115,9 +140,15 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     runtime.block_on(async move {
         match args.mode {
             ServerMode::Grpc => {
+                let loader = args
+                    .manifest_args
+                    .into_iter()
+                    .fold(ManifestLoader::new(), |loader, (name, value)| {
+                        loader.arg(name, value)
+                    });
                 WebRtcSignalingServerBuilder::new()
                     .bind(&args.grpc_address)
-                    .manifest_from_file(&args.manifest)?
+                    .manifest_from_loader(&loader, &args.manifest)?
                     .stun_servers(args.stun_servers)
                     .max_peers(args.max_peers)
                     .build()?
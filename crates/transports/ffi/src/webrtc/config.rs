@@ -264,6 +264,7 @@ impl WebRtcServerConfig {
             enable_rtcp: true,
             rtcp_interval_ms: 1000,
             options: ConfigOptions::default(),
+            recording_dir: None,
         }
     }
 }
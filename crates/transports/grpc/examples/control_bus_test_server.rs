@@ -195,6 +195,7 @@ fn lfm2_audio_manifest() -> Manifest {
         connections: vec![Connection {
             from: "audio".to_string(),
             to: "stt_out".to_string(),
+            ..Default::default()
         }],
         // LFM2-Audio (via `liquid-audio`) requires Python >= 3.12. Pin
         // the managed-venv interpreter so `uv` provisions a 3.12 venv
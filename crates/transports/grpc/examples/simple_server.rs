@@ -25,7 +25,7 @@ use std::sync::Arc;
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
     // Load configuration from environment variables
-    let config = ServiceConfig::from_env();
+    let config = ServiceConfig::from_env()?;
 
     // Initialize logging
     init_tracing(config.json_logging);
@@ -8,7 +8,13 @@ use crate::generated::{
     AudioBuffer, AudioFormat, BatchHint, BinaryBuffer, CancelSpeculation, ControlMessage,
     DataBuffer, DeadlineWarning, FileBuffer, JsonData, NumpyBuffer, TensorBuffer, TextBuffer, VideoFrame,
 };
+use crate::generated::{
+    Connection as ProtoConnection, ManifestMetadata as ProtoManifestMetadata,
+    NodeManifest as ProtoNodeManifest, PipelineManifest as ProtoPipelineManifest,
+    RuntimeHint as ProtoRuntimeHint,
+};
 use remotemedia_core::data::{split_text_str, tag_text_str, RuntimeData};
+use remotemedia_core::manifest::{Manifest, RuntimeHint};
 use remotemedia_core::transport::TransportData;
 
 /// Get current timestamp in microseconds for arrival time stamping (spec 026)
@@ -415,6 +421,76 @@ pub fn data_buffer_to_transport_data(buffer: &DataBuffer) -> Option<TransportDat
     Some(transport_data)
 }
 
+/// Convert a core Manifest to its Protobuf form
+///
+/// Node params and connection filters travel as JSON strings. Fields the
+/// Protobuf manifest has no room for (ports, restart policies, execution
+/// placement, ...) are dropped.
+pub fn manifest_to_proto(manifest: &Manifest) -> ProtoPipelineManifest {
+    ProtoPipelineManifest {
+        version: manifest.version.clone(),
+        metadata: Some(ProtoManifestMetadata {
+            name: manifest.metadata.name.clone(),
+            description: manifest.metadata.description.clone().unwrap_or_default(),
+            created_at: manifest.metadata.created_at.clone().unwrap_or_default(),
+        }),
+        nodes: manifest
+            .nodes
+            .iter()
+            .map(|node| ProtoNodeManifest {
+                id: node.id.clone(),
+                node_type: node.node_type.clone(),
+                params: node.params.to_string(),
+                host: node.host.clone().unwrap_or_default(),
+                runtime_hint: match node.runtime_hint {
+                    None => ProtoRuntimeHint::Unspecified,
+                    Some(RuntimeHint::RustPython) => ProtoRuntimeHint::Rustpython,
+                    Some(RuntimeHint::Cpython) => ProtoRuntimeHint::Cpython,
+                    Some(RuntimeHint::CpythonWasm) => ProtoRuntimeHint::CpythonWasm,
+                    Some(RuntimeHint::Auto) => ProtoRuntimeHint::Auto,
+                } as i32,
+                ports: json_field(&node.ports),
+                restart: json_field(&node.restart),
+                execution: json_field(&node.execution),
+                ..Default::default()
+            })
+            .collect(),
+        connections: manifest
+            .connections
+            .iter()
+            .map(|conn| ProtoConnection {
+                from: conn.from.clone(),
+                to: conn.to.clone(),
+                from_port: conn.from_port.clone().unwrap_or_default(),
+                to_port: conn.to_port.clone().unwrap_or_default(),
+                filter: conn
+                    .filter
+                    .as_ref()
+                    .and_then(|f| serde_json::to_string(f).ok())
+                    .unwrap_or_default(),
+            })
+            .collect(),
+    }
+}
+
+/// JSON encoding of an optional manifest field, empty when unset
+fn json_field<T: serde::Serialize>(value: &Option<T>) -> String {
+    value
+        .as_ref()
+        .and_then(|v| serde_json::to_string(v).ok())
+        .unwrap_or_default()
+}
+
+/// Decode a JSON-encoded manifest field (`None` when empty)
+///
+/// Text that isn't JSON is kept as a string, so parsing the manifest
+/// reports it instead of silently dropping the field.
+pub fn parse_json_field(field: &str) -> Option<serde_json::Value> {
+    (!field.is_empty()).then(|| {
+        serde_json::from_str(field).unwrap_or_else(|_| serde_json::Value::String(field.to_string()))
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -587,4 +663,37 @@ mod tests {
             _ => panic!("Expected RuntimeData::File"),
         }
     }
+
+    #[test]
+    fn test_manifest_to_proto_keeps_node_extensions() {
+        let manifest: Manifest = serde_json::from_value(serde_json::json!({
+            "version": "v1",
+            "metadata": { "name": "extensions" },
+            "nodes": [{
+                "id": "asr",
+                "node_type": "WhisperNode",
+                "ports": { "outputs": ["events"] },
+                "restart": { "policy": "on-failure" },
+                "execution": { "placement": "prefer_remote" }
+            }],
+            "connections": []
+        }))
+        .unwrap();
+
+        let proto = manifest_to_proto(&manifest);
+        let node = &proto.nodes[0];
+        assert_eq!(
+            parse_json_field(&node.ports),
+            Some(serde_json::json!({ "outputs": ["events"] }))
+        );
+        assert_eq!(
+            parse_json_field(&node.restart).unwrap()["policy"],
+            "on-failure"
+        );
+        assert_eq!(
+            parse_json_field(&node.execution).unwrap()["placement"],
+            "prefer_remote"
+        );
+        assert_eq!(parse_json_field(""), None);
+    }
 }
@@ -0,0 +1,186 @@
+//! gRPC handler for `SessionAdmin` — operator view of live sessions.
+//!
+//! Wire protocol is defined in `proto/admin.proto`. Sessions are the ones
+//! registered on the executor's `SessionControlBus`; statistics come from
+//! each session's `RouterHandle` (the stats/shutdown view the router leaves
+//! on its `SessionControl`). Every RPC requires the admin scope, see
+//! [`crate::auth::check_admin_auth`].
+
+use crate::auth::{check_admin_auth, AuthConfig};
+use crate::generated::session_admin_server::SessionAdmin;
+use crate::generated::{
+    AdminNodeInfo, AdminNodeStats, DescribeSessionRequest, GetSessionMetricsRequest,
+    KillSessionRequest, KillSessionResponse, ListSessionsRequest, ListSessionsResponse,
+    NodeState as PbNodeState, SessionDescription, SessionMetrics, SessionSummary, StreamDriftInfo,
+};
+
+use remotemedia_core::executor::NodeStats;
+use remotemedia_core::transport::session_control::{NodeState, SessionControl, SessionControlBus};
+use remotemedia_core::transport::session_router::RouterHandle;
+
+use std::sync::Arc;
+use std::time::{SystemTime, UNIX_EPOCH};
+use tonic::{Request, Response, Status};
+use tracing::info;
+
+/// gRPC `SessionAdmin` service implementation.
+#[derive(Clone)]
+pub struct SessionAdminServiceImpl {
+    auth: AuthConfig,
+    bus: Arc<SessionControlBus>,
+}
+
+impl SessionAdminServiceImpl {
+    pub fn new(auth: AuthConfig, bus: Arc<SessionControlBus>) -> Self {
+        Self { auth, bus }
+    }
+
+    /// Look up a live session and its router
+    fn session(&self, session_id: &str) -> Result<(Arc<SessionControl>, RouterHandle), Status> {
+        let ctrl = self
+            .bus
+            .get(session_id)
+            .ok_or_else(|| Status::not_found(format!("session '{session_id}' not found")))?;
+        let router = ctrl.router().cloned().ok_or_else(|| {
+            Status::failed_precondition(format!("session '{session_id}' has no router attached"))
+        })?;
+        Ok((ctrl, router))
+    }
+}
+
+fn unix_ms(t: SystemTime) -> u64 {
+    t.duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+fn summary(router: &RouterHandle) -> SessionSummary {
+    let manifest = router.manifest();
+    SessionSummary {
+        session_id: router.session_id().to_string(),
+        pipeline_name: manifest.metadata.name.clone(),
+        started_at_ms: unix_ms(router.started_at()),
+        uptime_ms: router
+            .started_at()
+            .elapsed()
+            .map(|d| d.as_millis() as u64)
+            .unwrap_or(0),
+        node_count: manifest.nodes.len() as u32,
+    }
+}
+
+fn node_state_to_pb(state: NodeState) -> PbNodeState {
+    match state {
+        NodeState::Enabled => PbNodeState::Enabled,
+        NodeState::Bypass => PbNodeState::Bypass,
+        NodeState::Disabled => PbNodeState::Disabled,
+    }
+}
+
+fn node_stats_to_pb(stats: &NodeStats) -> AdminNodeStats {
+    AdminNodeStats {
+        execution_count: stats.execution_count,
+        error_count: stats.error_count,
+        error_rate: stats.error_rate(),
+        circuit_breaker_open: stats.circuit_breaker_open,
+        p50_us: stats.p50_us,
+        p95_us: stats.p95_us,
+        p99_us: stats.p99_us,
+    }
+}
+
+#[tonic::async_trait]
+impl SessionAdmin for SessionAdminServiceImpl {
+    async fn list_sessions(
+        &self,
+        request: Request<ListSessionsRequest>,
+    ) -> Result<Response<ListSessionsResponse>, Status> {
+        check_admin_auth(&request, &self.auth)?;
+
+        let sessions = self
+            .bus
+            .sessions()
+            .iter()
+            .filter_map(|ctrl| ctrl.router().map(summary))
+            .collect();
+        Ok(Response::new(ListSessionsResponse { sessions }))
+    }
+
+    async fn describe_session(
+        &self,
+        request: Request<DescribeSessionRequest>,
+    ) -> Result<Response<SessionDescription>, Status> {
+        check_admin_auth(&request, &self.auth)?;
+        let (ctrl, router) = self.session(&request.get_ref().session_id)?;
+
+        let manifest = router.manifest();
+        let nodes = manifest
+            .nodes
+            .iter()
+            .map(|node| AdminNodeInfo {
+                node_id: node.id.clone(),
+                node_type: node.node_type.clone(),
+                state: node_state_to_pb(ctrl.node_state(&node.id)) as i32,
+            })
+            .collect();
+        let manifest_json = serde_json::to_string(manifest.as_ref())
+            .map_err(|e| Status::internal(format!("failed to serialize manifest: {e}")))?;
+
+        Ok(Response::new(SessionDescription {
+            summary: Some(summary(&router)),
+            nodes,
+            stream_ids: router.drift().into_iter().map(|d| d.stream_id).collect(),
+            manifest_json,
+        }))
+    }
+
+    async fn kill_session(
+        &self,
+        request: Request<KillSessionRequest>,
+    ) -> Result<Response<KillSessionResponse>, Status> {
+        check_admin_auth(&request, &self.auth)?;
+        let KillSessionRequest { session_id, reason } = request.into_inner();
+        let (ctrl, _) = self.session(&session_id)?;
+
+        let reason = if reason.is_empty() {
+            "killed by admin".to_string()
+        } else {
+            reason
+        };
+        info!(session_id = %session_id, reason = %reason, "Admin terminating session");
+        let terminated = ctrl.terminate(reason);
+
+        Ok(Response::new(KillSessionResponse { terminated }))
+    }
+
+    async fn get_session_metrics(
+        &self,
+        request: Request<GetSessionMetricsRequest>,
+    ) -> Result<Response<SessionMetrics>, Status> {
+        check_admin_auth(&request, &self.auth)?;
+        let (_, router) = self.session(&request.get_ref().session_id)?;
+
+        let node_stats = router
+            .node_stats()
+            .await
+            .iter()
+            .map(|(node_id, stats)| (node_id.clone(), node_stats_to_pb(stats)))
+            .collect();
+        let drift = router
+            .drift()
+            .into_iter()
+            .map(|d| StreamDriftInfo {
+                stream_id: d.stream_id,
+                health_score: d.health_score,
+                detail_json: d.detail.to_string(),
+            })
+            .collect();
+
+        Ok(Response::new(SessionMetrics {
+            session_id: router.session_id().to_string(),
+            node_stats,
+            drift,
+            prometheus_text: router.prometheus_metrics().await,
+        }))
+    }
+}
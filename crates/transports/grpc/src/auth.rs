@@ -2,36 +2,104 @@
 //!
 //! Implements API token validation via tower interceptor.
 //! Tokens are passed in gRPC metadata as "authorization: Bearer <token>".
+//!
+//! Two scopes exist. Regular tokens grant the data plane (execute, stream,
+//! attach). Admin tokens additionally grant the `SessionAdmin` service
+//! (list / describe / kill sessions); an admin token is accepted wherever
+//! a regular one is.
+//!
+//! The static sets are checked by a [`StaticTokenAuthenticator`] behind
+//! an [`AuthGuard`] of their own, so they are compared in constant time
+//! and rate limited like any other key. Tokens outside them are handed to
+//! the configured [`AuthGuard`] (JWT or key-store API keys), whose
+//! [`Principal`] carries finer-grained scopes, a tenant and manifest
+//! restrictions.
 
-use std::collections::HashSet;
+use remotemedia_core::auth::{
+    AuthError, AuthGuard, Authenticator, Principal, Scope, StaticTokenAuthenticator,
+};
+use remotemedia_core::transport::admission::DEFAULT_TENANT;
 use std::sync::Arc;
 use tonic::{Request, Status};
 
 /// Authentication configuration
 #[derive(Clone, Debug)]
 pub struct AuthConfig {
-    /// Set of valid API tokens
-    pub valid_tokens: Arc<HashSet<String>>,
+    /// Valid API tokens, for the default tenant
+    valid_tokens: Arc<StaticTokenAuthenticator>,
     /// Whether authentication is required (false for dev/testing)
     pub require_auth: bool,
+    /// Tokens granting the admin scope
+    admin_tokens: Arc<StaticTokenAuthenticator>,
+    /// Both static sets, admin tokens first
+    static_tokens: AuthGuard,
+    /// API-key authenticators consulted for tokens not in the static sets
+    pub guard: AuthGuard,
 }
 
 impl Default for AuthConfig {
     fn default() -> Self {
-        Self {
-            valid_tokens: Arc::new(HashSet::new()),
-            require_auth: false,
-        }
+        Self::new(Vec::new(), false)
     }
 }
 
 impl AuthConfig {
     /// Create new auth config with a set of valid tokens
     pub fn new(tokens: Vec<String>, require_auth: bool) -> Self {
+        let valid_tokens = Arc::new(StaticTokenAuthenticator::from_tokens(tokens));
+        let admin_tokens = Arc::new(StaticTokenAuthenticator::new());
         Self {
-            valid_tokens: Arc::new(tokens.into_iter().collect()),
+            static_tokens: static_guard(&valid_tokens, &admin_tokens),
+            valid_tokens,
             require_auth,
+            admin_tokens,
+            guard: AuthGuard::open(),
+        }
+    }
+
+    /// Set the tokens granting the admin scope
+    pub fn with_admin_tokens(mut self, tokens: Vec<String>) -> Self {
+        let mut admin_tokens = StaticTokenAuthenticator::new();
+        for (i, token) in tokens.into_iter().filter(|t| !t.is_empty()).enumerate() {
+            admin_tokens = admin_tokens.with_token(
+                token,
+                Principal::new(format!("admin-{}", i), DEFAULT_TENANT)
+                    .with_scopes(vec![Scope::Admin]),
+            );
+        }
+        self.admin_tokens = Arc::new(admin_tokens);
+        self.static_tokens = static_guard(&self.valid_tokens, &self.admin_tokens);
+        self
+    }
+
+    /// Accept API keys from `authenticator`; turns `require_auth` on
+    pub fn with_authenticator(mut self, authenticator: Arc<dyn Authenticator>) -> Self {
+        self.guard = self.guard.with_authenticator(authenticator);
+        self.require_auth = true;
+        self
+    }
+
+    /// Use `guard` for API keys; a guard with authenticators turns
+    /// `require_auth` on
+    pub fn with_guard(mut self, guard: AuthGuard) -> Self {
+        self.require_auth |= guard.is_required();
+        self.guard = guard;
+        self
+    }
+
+    /// Resolve `token` to the principal it authenticates
+    ///
+    /// Static tokens map to the default tenant; admin tokens carry the
+    /// admin scope.
+    pub fn principal(&self, token: &str) -> Result<Principal, AuthError> {
+        match self.static_tokens.authenticate(Some(token)) {
+            Err(AuthError::InvalidCredentials(_) | AuthError::MissingCredentials) => {}
+            result => return result,
         }
+        if self.guard.is_required() {
+            return self.guard.authenticate(Some(token));
+        }
+        Err(AuthError::InvalidCredentials("unknown token".to_string()))
     }
 
     /// Check if a token is valid
@@ -39,40 +107,103 @@ impl AuthConfig {
         if !self.require_auth {
             return true;
         }
-        self.valid_tokens.contains(token)
+        self.principal(token).is_ok()
+    }
+
+    /// Whether admin RPCs need a token at all
+    ///
+    /// Open only in dev mode: auth disabled and no admin tokens configured.
+    /// Configuring admin tokens always protects the admin scope, and with
+    /// `require_auth` set but no admin tokens, admin RPCs are refused.
+    pub fn admin_auth_required(&self) -> bool {
+        self.require_auth || !self.admin_tokens.is_empty()
+    }
+
+    /// Check if a token grants the admin scope
+    pub fn validate_admin_token(&self, token: &str) -> bool {
+        if !self.admin_auth_required() {
+            return true;
+        }
+        self.principal(token)
+            .is_ok_and(|principal| principal.has_scope(Scope::Admin))
     }
 }
 
-/// Extract and validate bearer token from gRPC metadata
+/// A guard over the static token sets
 ///
-/// Expected format: "authorization: Bearer <token>"
-pub fn check_auth<T>(request: &Request<T>, config: &AuthConfig) -> Result<(), Status> {
-    // Skip auth if not required
-    if !config.require_auth {
-        return Ok(());
-    }
+/// Admin tokens come first, so a token in both sets keeps the admin scope.
+fn static_guard(
+    valid_tokens: &Arc<StaticTokenAuthenticator>,
+    admin_tokens: &Arc<StaticTokenAuthenticator>,
+) -> AuthGuard {
+    AuthGuard::open()
+        .with_authenticator(admin_tokens.clone())
+        .with_authenticator(valid_tokens.clone())
+}
 
-    // Extract authorization header
-    let metadata = request.metadata();
-    let auth_header = metadata
+/// Pull the bearer token out of gRPC metadata
+fn bearer_token<T>(request: &Request<T>) -> Result<&str, Status> {
+    let auth_header = request
+        .metadata()
         .get("authorization")
         .ok_or_else(|| {
             Status::unauthenticated("Missing authorization header. Include 'authorization: Bearer <token>' in gRPC metadata.")
         })?;
 
-    // Parse bearer token
     let auth_str = auth_header
         .to_str()
         .map_err(|_| Status::unauthenticated("Invalid authorization header encoding"))?;
 
-    let token = auth_str.strip_prefix("Bearer ").ok_or_else(|| {
+    auth_str.strip_prefix("Bearer ").ok_or_else(|| {
         Status::unauthenticated("Invalid authorization format. Expected 'Bearer <token>'")
-    })?;
+    })
+}
+
+/// Extract and validate bearer token from gRPC metadata
+///
+/// Expected format: "authorization: Bearer <token>"
+pub fn check_auth<T>(request: &Request<T>, config: &AuthConfig) -> Result<(), Status> {
+    authorize(request, config, Scope::Execute).map(|_| ())
+}
+
+/// Authenticate the caller and require `scope`
+///
+/// With auth disabled every caller is [`Principal::anonymous`].
+pub fn authorize<T>(
+    request: &Request<T>,
+    config: &AuthConfig,
+    scope: Scope,
+) -> Result<Principal, Status> {
+    // Skip auth if not required
+    if !config.require_auth {
+        return Ok(Principal::anonymous());
+    }
+
+    let token = bearer_token(request)?;
+    let principal = config.principal(token).map_err(Status::from)?;
+    if !principal.has_scope(scope) {
+        return Err(Status::permission_denied(format!(
+            "Token does not grant the '{:?}' scope.",
+            scope
+        )));
+    }
+
+    Ok(principal)
+}
+
+/// Extract and validate a bearer token for the admin scope
+///
+/// A missing or malformed header is `Unauthenticated`; a well-formed token
+/// without admin rights is `PermissionDenied`.
+pub fn check_admin_auth<T>(request: &Request<T>, config: &AuthConfig) -> Result<(), Status> {
+    if !config.admin_auth_required() {
+        return Ok(());
+    }
 
-    // Validate token
-    if !config.validate_token(token) {
-        return Err(Status::unauthenticated(
-            "Invalid API token. Check your authentication credentials.",
+    let token = bearer_token(request)?;
+    if !config.validate_admin_token(token) {
+        return Err(Status::permission_denied(
+            "Token does not grant the admin scope.",
         ));
     }
 
@@ -160,4 +291,92 @@ mod tests {
         let result = check_auth(&request, &config);
         assert!(result.is_ok()); // Should pass when auth disabled
     }
+
+    #[test]
+    fn test_admin_scope() {
+        let config = AuthConfig::new(vec!["user-token".to_string()], true)
+            .with_admin_tokens(vec!["admin-token".to_string()]);
+
+        // Admin tokens also pass the regular scope.
+        assert!(config.validate_token("admin-token"));
+        assert!(config.validate_token("user-token"));
+
+        let mut request = Request::new(());
+        request.metadata_mut().insert(
+            "authorization",
+            MetadataValue::from_static("Bearer user-token"),
+        );
+        let err = check_admin_auth(&request, &config).unwrap_err();
+        assert_eq!(err.code(), tonic::Code::PermissionDenied);
+
+        request.metadata_mut().insert(
+            "authorization",
+            MetadataValue::from_static("Bearer admin-token"),
+        );
+        assert!(check_admin_auth(&request, &config).is_ok());
+
+        let err = check_admin_auth(&Request::new(()), &config).unwrap_err();
+        assert_eq!(err.code(), tonic::Code::Unauthenticated);
+    }
+
+    #[test]
+    fn test_static_tokens_resolve_per_token() {
+        let config = AuthConfig::new(vec!["user-a".to_string(), "both".to_string()], true)
+            .with_admin_tokens(vec!["both".to_string()]);
+
+        assert_eq!(config.principal("user-a").unwrap().key_id, "static-0");
+        // A token in both sets keeps the admin scope
+        let both = config.principal("both").unwrap();
+        assert_eq!(both.key_id, "admin-0");
+        assert!(both.has_scope(Scope::Admin));
+        assert!(config.principal("user-").unwrap_err().is_unauthenticated());
+    }
+
+    #[test]
+    fn test_admin_scope_open_only_in_dev_mode() {
+        assert!(check_admin_auth(&Request::new(()), &AuthConfig::default()).is_ok());
+
+        // Auth on but nobody holds the admin scope: refuse.
+        let config = AuthConfig::new(vec!["user-token".to_string()], true);
+        let mut request = Request::new(());
+        request.metadata_mut().insert(
+            "authorization",
+            MetadataValue::from_static("Bearer user-token"),
+        );
+        assert!(check_admin_auth(&request, &config).is_err());
+
+        // Admin tokens protect the admin scope even with auth off.
+        let config = AuthConfig::default().with_admin_tokens(vec!["admin-token".to_string()]);
+        assert!(check_admin_auth(&Request::new(()), &config).is_err());
+        assert!(config.validate_token("anything"));
+    }
+
+    #[test]
+    fn test_api_key_authenticator() {
+        let keys = StaticTokenAuthenticator::new()
+            .with_token("acme-key", Principal::new("acme", "acme"))
+            .with_token(
+                "viewer-key",
+                Principal::new("viewer", "acme").with_scopes(vec![Scope::Control]),
+            );
+        let config = AuthConfig::default().with_authenticator(Arc::new(keys));
+        assert!(config.require_auth);
+
+        let mut request = Request::new(());
+        request.metadata_mut().insert(
+            "authorization",
+            MetadataValue::from_static("Bearer acme-key"),
+        );
+        let principal = authorize(&request, &config, Scope::Stream).unwrap();
+        assert_eq!(principal.tenant_id, "acme");
+
+        request.metadata_mut().insert(
+            "authorization",
+            MetadataValue::from_static("Bearer viewer-key"),
+        );
+        let err = authorize(&request, &config, Scope::Execute).unwrap_err();
+        assert_eq!(err.code(), tonic::Code::PermissionDenied);
+        assert!(authorize(&request, &config, Scope::Control).is_ok());
+        assert!(!config.validate_admin_token("viewer-key"));
+    }
 }
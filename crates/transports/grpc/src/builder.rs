@@ -26,6 +26,7 @@ use crate::limits::ResourceLimits as ServiceResourceLimits;
 use crate::server::GrpcServer;
 use crate::ServiceConfig;
 
+use remotemedia_core::auth::{AuthError, AuthGuard, Authenticator};
 use remotemedia_core::transport::PipelineExecutor;
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
@@ -40,7 +41,11 @@ pub struct GrpcServerBuilder {
     bind_address: Option<String>,
     executor: Option<Arc<PipelineExecutor>>,
     auth_tokens: Vec<String>,
+    admin_tokens: Vec<String>,
     require_auth: Option<bool>,
+    auth_guard: AuthGuard,
+    /// Invalid API key configuration found by [`from_env`](Self::from_env)
+    auth_error: Option<AuthError>,
     max_memory_mb: Option<u64>,
     max_timeout_secs: Option<u64>,
     json_logging: Option<bool>,
@@ -53,7 +58,10 @@ impl GrpcServerBuilder {
             bind_address: None,
             executor: None,
             auth_tokens: Vec::new(),
+            admin_tokens: Vec::new(),
             require_auth: None,
+            auth_guard: AuthGuard::open(),
+            auth_error: None,
             max_memory_mb: None,
             max_timeout_secs: None,
             json_logging: None,
@@ -84,6 +92,24 @@ impl GrpcServerBuilder {
         self
     }
 
+    /// Set the tokens granting the admin scope (`SessionAdmin` RPCs).
+    ///
+    /// Admin tokens are also accepted by the regular services.
+    pub fn admin_tokens(mut self, tokens: Vec<String>) -> Self {
+        self.admin_tokens = tokens;
+        self
+    }
+
+    /// Accept API keys (JWT, key store, ...) from `authenticator`.
+    ///
+    /// Keys carry their own tenant, scopes and manifest restrictions.
+    /// Enables authentication unless [`require_auth`](Self::require_auth)
+    /// says otherwise.
+    pub fn authenticator(mut self, authenticator: Arc<dyn Authenticator>) -> Self {
+        self.auth_guard = self.auth_guard.with_authenticator(authenticator);
+        self
+    }
+
     /// Explicitly enable or disable authentication.
     ///
     /// When not called, authentication is enabled if
@@ -117,13 +143,17 @@ impl GrpcServerBuilder {
     ///
     /// - `GRPC_BIND_ADDRESS`
     /// - `GRPC_AUTH_TOKENS` (comma-separated)
+    /// - `GRPC_ADMIN_TOKENS` (comma-separated)
     /// - `GRPC_REQUIRE_AUTH`
     /// - `GRPC_MAX_MEMORY_MB`
     /// - `GRPC_MAX_TIMEOUT_SEC`
     /// - `GRPC_JSON_LOGGING`
+    /// - `REMOTEMEDIA_AUTH_TOKENS`, `REMOTEMEDIA_AUTH_JWT_SECRET`,
+    ///   `REMOTEMEDIA_AUTH_KEYS_FILE` (see [`AuthGuard::from_env`])
     ///
     /// Fields that have already been set via builder methods are **not**
-    /// overwritten.
+    /// overwritten. An invalid API key configuration makes
+    /// [`build`](Self::build) fail.
     pub fn from_env(mut self) -> Self {
         if self.bind_address.is_none() {
             if let Ok(addr) = std::env::var("GRPC_BIND_ADDRESS") {
@@ -137,6 +167,19 @@ impl GrpcServerBuilder {
             }
         }
 
+        if self.admin_tokens.is_empty() {
+            if let Ok(tokens) = std::env::var("GRPC_ADMIN_TOKENS") {
+                self.admin_tokens = tokens.split(',').map(|s| s.trim().to_string()).collect();
+            }
+        }
+
+        if !self.auth_guard.is_required() {
+            match AuthGuard::from_env() {
+                Ok(guard) => self.auth_guard = guard,
+                Err(e) => self.auth_error = Some(e),
+            }
+        }
+
         if self.require_auth.is_none() {
             if let Ok(val) = std::env::var("GRPC_REQUIRE_AUTH") {
                 self.require_auth = Some(val.to_lowercase() == "true");
@@ -171,18 +214,25 @@ impl GrpcServerBuilder {
     /// Build the server, resolving all defaults.
     ///
     /// If no [`executor`](Self::executor) was provided, a new
-    /// [`PipelineExecutor`] is created. Returns an error if executor
-    /// creation or server initialization fails.
+    /// [`PipelineExecutor`] is created. Returns an error if the API key
+    /// configuration is invalid, or if executor creation or server
+    /// initialization fails.
     pub fn build(self) -> Result<GrpcTransportServer, Box<dyn std::error::Error>> {
+        if let Some(e) = self.auth_error {
+            return Err(format!("Invalid API key configuration: {}", e).into());
+        }
+
         let defaults = ServiceConfig::default();
 
         let bind_address = self.bind_address.unwrap_or(defaults.bind_address);
 
         let require_auth = self
             .require_auth
-            .unwrap_or(!self.auth_tokens.is_empty());
+            .unwrap_or(!self.auth_tokens.is_empty() || self.auth_guard.is_required());
 
-        let auth = AuthConfig::new(self.auth_tokens, require_auth);
+        let mut auth = AuthConfig::new(self.auth_tokens, require_auth)
+            .with_admin_tokens(self.admin_tokens);
+        auth.guard = self.auth_guard;
 
         let max_memory_bytes = self
             .max_memory_mb
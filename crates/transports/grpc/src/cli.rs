@@ -16,6 +16,10 @@ pub struct GrpcServeArgs {
     #[arg(long, env = "GRPC_AUTH_TOKENS")]
     pub grpc_auth_tokens: Option<String>,
 
+    /// Comma-separated list of tokens granting the admin scope
+    #[arg(long, env = "GRPC_ADMIN_TOKENS")]
+    pub grpc_admin_tokens: Option<String>,
+
     /// Require authentication (auto-enabled when tokens are provided)
     #[arg(long, env = "GRPC_REQUIRE_AUTH")]
     pub grpc_require_auth: bool,
@@ -55,6 +59,11 @@ impl GrpcServeArgs {
             builder = builder.auth_tokens(token_list);
         }
 
+        if let Some(tokens) = self.grpc_admin_tokens {
+            let token_list: Vec<String> = tokens.split(',').map(|s| s.trim().to_string()).collect();
+            builder = builder.admin_tokens(token_list);
+        }
+
         if self.grpc_require_auth {
             builder = builder.require_auth(true);
         }
@@ -26,12 +26,29 @@
 // Internal infrastructure - auth_token reserved for future use
 #![allow(dead_code)]
 
+use crate::adapters::{
+    data_buffer_to_transport_data, manifest_to_proto, transport_data_to_data_buffer,
+};
+use crate::generated::execute_response::Outcome;
+use crate::generated::pipeline_execution_service_client::PipelineExecutionServiceClient;
+use crate::generated::stream_control::Command;
+use crate::generated::stream_request::Request as StreamRequestType;
+use crate::generated::stream_response::Response as StreamResponseType;
+use crate::generated::streaming_pipeline_service_client::StreamingPipelineServiceClient;
+use crate::generated::{
+    DataChunk, ExecuteRequest, StreamControl, StreamInit, StreamRequest, StreamResponse,
+};
 use async_trait::async_trait;
 use remotemedia_core::manifest::Manifest;
 use remotemedia_core::transport::client::{ClientStreamSession, PipelineClient};
 use remotemedia_core::transport::TransportData;
 use remotemedia_core::{Error, Result};
+use std::collections::{HashMap, VecDeque};
 use std::sync::Arc;
+use std::time::Instant;
+
+/// Requests buffered between a stream session and its gRPC call
+const STREAM_REQUEST_CAPACITY: usize = 32;
 
 /// gRPC client for remote pipeline execution
 ///
@@ -140,37 +157,143 @@ impl GrpcPipelineClient {
 impl PipelineClient for GrpcPipelineClient {
     /// Execute a pipeline with unary semantics
     ///
-    /// This is a placeholder implementation. Full implementation requires:
-    /// 1. Converting Manifest to protobuf PipelineManifest
-    /// 2. Converting TransportData to protobuf DataBuffer
-    /// 3. Calling PipelineExecutionService::ExecutePipeline RPC
-    /// 4. Converting protobuf result back to TransportData
+    /// Sends the manifest and `input` in one ExecutePipeline call and
+    /// returns the pipeline's first output. Errors reported by the server
+    /// come back as [`Error::RemoteExecutionFailed`].
     async fn execute_unary(
         &self,
-        _manifest: Arc<Manifest>,
-        _input: TransportData,
+        manifest: Arc<Manifest>,
+        input: TransportData,
     ) -> Result<TransportData> {
-        // TODO: Implement actual gRPC call
-        // This requires protobuf conversion utilities which are available in this crate
-        Err(Error::Transport(
-            "gRPC execute_unary not yet fully implemented - will be completed in T011".to_string(),
-        ))
+        let channel = self.get_channel().await?;
+        let mut client = PipelineExecutionServiceClient::new(channel);
+
+        let mut request = tonic::Request::new(ExecuteRequest {
+            manifest: Some(manifest_to_proto(&manifest)),
+            data_inputs: HashMap::from([(
+                "input".to_string(),
+                transport_data_to_data_buffer(&input),
+            )]),
+            resource_limits: None,
+            client_version: "v1".to_string(),
+        });
+        *request.metadata_mut() = self.create_metadata()?;
+
+        let response = client
+            .execute_pipeline(request)
+            .await
+            .map_err(|status| {
+                Error::RemoteExecutionFailed(format!("{}: {}", self.endpoint, status.message()))
+            })?
+            .into_inner();
+
+        match response.outcome {
+            Some(Outcome::Result(result)) => {
+                let buffer = result.data_outputs.into_values().next().ok_or_else(|| {
+                    Error::RemoteExecutionFailed(format!("{}: no output", self.endpoint))
+                })?;
+                data_buffer_to_transport_data(&buffer).ok_or_else(|| {
+                    Error::RemoteExecutionFailed(format!("{}: output has no data", self.endpoint))
+                })
+            }
+            Some(Outcome::Error(error)) => Err(Error::RemoteExecutionFailed(format!(
+                "{}: {}",
+                self.endpoint, error.message
+            ))),
+            None => Err(Error::RemoteExecutionFailed(format!(
+                "{}: empty response",
+                self.endpoint
+            ))),
+        }
     }
 
     /// Create a streaming session
     ///
-    /// This is a placeholder implementation. Full implementation requires:
-    /// 1. Establishing bidirectional stream via StreamPipeline RPC
-    /// 2. Sending StreamInit message with manifest
-    /// 3. Creating GrpcStreamSession wrapper
+    /// Opens a StreamPipeline call, sends `StreamInit` with the manifest and
+    /// waits for the server's `StreamReady`, by which time every node of the
+    /// remote pipeline is initialized. Inputs go to the manifest's first
+    /// source node.
     async fn create_stream_session(
         &self,
-        _manifest: Arc<Manifest>,
+        manifest: Arc<Manifest>,
     ) -> Result<Box<dyn ClientStreamSession>> {
-        Err(Error::Transport(
-            "gRPC create_stream_session not yet fully implemented - will be completed in T011"
-                .to_string(),
-        ))
+        let input_node = manifest
+            .nodes
+            .iter()
+            .find(|node| !manifest.connections.iter().any(|c| c.to == node.id))
+            .map(|node| node.id.clone())
+            .ok_or_else(|| {
+                Error::InvalidManifest("Remote pipeline has no source node".to_string())
+            })?;
+
+        let channel = self.get_channel().await?;
+        let mut client = StreamingPipelineServiceClient::new(channel);
+
+        let (requests, rx) = tokio::sync::mpsc::channel(STREAM_REQUEST_CAPACITY);
+        let init = StreamRequest {
+            request: Some(StreamRequestType::Init(StreamInit {
+                manifest: Some(manifest_to_proto(&manifest)),
+                data_inputs: HashMap::new(),
+                resource_limits: None,
+                client_version: "v1".to_string(),
+                expected_chunk_size: 0,
+            })),
+        };
+        requests
+            .send(init)
+            .await
+            .map_err(|_| Error::Transport(format!("{}: stream closed", self.endpoint)))?;
+
+        let mut request = tonic::Request::new(tokio_stream::wrappers::ReceiverStream::new(rx));
+        *request.metadata_mut() = self.create_metadata()?;
+
+        let mut responses = client
+            .stream_pipeline(request)
+            .await
+            .map_err(|status| {
+                Error::RemoteExecutionFailed(format!("{}: {}", self.endpoint, status.message()))
+            })?
+            .into_inner();
+
+        // Status updates sent while the nodes initialize come before StreamReady
+        let session_id = loop {
+            let response = responses.message().await.map_err(|status| {
+                Error::RemoteExecutionFailed(format!("{}: {}", self.endpoint, status.message()))
+            })?;
+            match response.and_then(|r| r.response) {
+                Some(StreamResponseType::Ready(ready)) => break ready.session_id,
+                Some(StreamResponseType::Error(error)) => {
+                    return Err(Error::RemoteExecutionFailed(format!(
+                        "{}: {}",
+                        self.endpoint, error.message
+                    )))
+                }
+                Some(_) => continue,
+                None => {
+                    return Err(Error::RemoteExecutionFailed(format!(
+                        "{}: stream closed before the pipeline was ready",
+                        self.endpoint
+                    )))
+                }
+            }
+        };
+
+        tracing::info!(
+            "gRPC stream session {} opened on {}",
+            session_id,
+            self.endpoint
+        );
+        Ok(Box::new(GrpcStreamSession {
+            session_id,
+            endpoint: self.endpoint.clone(),
+            input_node,
+            requests: Some(requests),
+            responses,
+            pending: VecDeque::new(),
+            sequence: 0,
+            started: Instant::now(),
+            active: true,
+        }))
     }
 
     /// Check if the remote endpoint is healthy
@@ -188,51 +311,114 @@ impl PipelineClient for GrpcPipelineClient {
 
 /// gRPC streaming session
 ///
-/// Represents an active bidirectional streaming connection to a gRPC server.
+/// Represents an active bidirectional StreamPipeline call. The remote
+/// session, and every node in it, lives until the session is closed or
+/// dropped.
 pub struct GrpcStreamSession {
-    /// Unique session ID
+    /// Session ID assigned by the server
     session_id: String,
 
+    /// Endpoint the session runs on, for error messages
+    endpoint: String,
+
+    /// Node that receives each input
+    input_node: String,
+
+    /// Outgoing requests; dropping the sender ends the call
+    requests: Option<tokio::sync::mpsc::Sender<StreamRequest>>,
+
+    /// Incoming responses
+    responses: tonic::Streaming<StreamResponse>,
+
+    /// Outputs received but not yet returned by `receive`
+    pending: VecDeque<TransportData>,
+
+    /// Sequence number of the next input
+    sequence: u64,
+
+    /// When the session opened, for chunk timestamps
+    started: Instant,
+
     /// Whether the session is active
     active: bool,
 }
 
-impl GrpcStreamSession {
-    /// Create a new stream session
-    pub fn new(session_id: String) -> Self {
-        Self {
-            session_id,
-            active: true,
-        }
-    }
-}
-
 #[async_trait]
 impl ClientStreamSession for GrpcStreamSession {
     fn session_id(&self) -> &str {
         &self.session_id
     }
 
-    async fn send(&mut self, _data: TransportData) -> Result<()> {
-        if !self.active {
-            return Err(Error::Transport("Session is closed".to_string()));
-        }
+    async fn send(&mut self, data: TransportData) -> Result<()> {
+        let requests = match &self.requests {
+            Some(requests) if self.active => requests,
+            _ => return Err(Error::Transport("Session is closed".to_string())),
+        };
 
-        // TODO: Implement actual streaming send (T011)
-        Err(Error::Transport(
-            "gRPC streaming send not yet fully implemented".to_string(),
-        ))
+        let chunk = DataChunk {
+            node_id: self.input_node.clone(),
+            buffer: Some(transport_data_to_data_buffer(&data)),
+            named_buffers: HashMap::new(),
+            sequence: self.sequence,
+            timestamp_ms: self.started.elapsed().as_millis() as u64,
+        };
+        requests
+            .send(StreamRequest {
+                request: Some(StreamRequestType::DataChunk(chunk)),
+            })
+            .await
+            .map_err(|_| Error::Transport(format!("{}: stream closed", self.endpoint)))?;
+        self.sequence += 1;
+        Ok(())
     }
 
+    /// Receive the next output of the remote pipeline
+    ///
+    /// Cancel safe: outputs already read from the stream are kept for the
+    /// next call, so this can run under a timeout.
     async fn receive(&mut self) -> Result<Option<TransportData>> {
-        if !self.active {
-            return Ok(None);
-        }
+        loop {
+            if let Some(output) = self.pending.pop_front() {
+                return Ok(Some(output));
+            }
+            if !self.active {
+                return Ok(None);
+            }
 
-        // TODO: Implement actual streaming receive (T011)
-        Err(Error::Transport(
-            "gRPC streaming receive not yet fully implemented".to_string(),
-        ))
+            let response = match self.responses.message().await {
+                Ok(response) => response,
+                Err(status) => {
+                    self.active = false;
+                    return Err(Error::RemoteExecutionFailed(format!(
+                        "{}: {}",
+                        self.endpoint,
+                        status.message()
+                    )));
+                }
+            };
+            match response.and_then(|r| r.response) {
+                Some(StreamResponseType::Result(result)) => {
+                    // `_status` and other `_`-prefixed entries are server
+                    // progress notes, not pipeline output
+                    self.pending.extend(
+                        result
+                            .data_outputs
+                            .iter()
+                            .filter(|(node_id, _)| !node_id.starts_with('_'))
+                            .filter_map(|(_, buffer)| data_buffer_to_transport_data(buffer)),
+                    );
+                }
+                Some(StreamResponseType::Error(error)) => {
+                    self.active = false;
+                    return Err(Error::RemoteExecutionFailed(format!(
+                        "{}: {}",
+                        self.endpoint, error.message
+                    )));
+                }
+                Some(StreamResponseType::Closed(_)) | None => self.active = false,
+                Some(StreamResponseType::Ready(_)) | Some(StreamResponseType::Metrics(_)) => {}
+            }
+        }
     }
 
     async fn close(&mut self) -> Result<()> {
@@ -241,6 +427,15 @@ impl ClientStreamSession for GrpcStreamSession {
         }
 
         self.active = false;
+        if let Some(requests) = self.requests.take() {
+            let close = StreamRequest {
+                request: Some(StreamRequestType::Control(StreamControl {
+                    command: Command::Close as i32,
+                })),
+            };
+            // The server may already be gone; the call ends either way
+            let _ = requests.send(close).await;
+        }
         tracing::info!("gRPC stream session {} closed", self.session_id);
         Ok(())
     }
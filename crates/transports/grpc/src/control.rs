@@ -26,11 +26,15 @@ use crate::generated::pipeline_control_server::PipelineControl;
 use crate::generated::{
     Attached, CloseReasonCode, ControlAddress as PbAddress, ControlDirection, ControlErrorCode,
     ControlEvent, ControlFrame, ErrorEvent, InterceptRequest as PbInterceptRequest,
-    NodeState as PbNodeState, SessionClosed, TapEvent,
+    NodeState as PbNodeState, PatchApplied, SessionClosed, TapEvent,
 };
 
+use crate::auth::{authorize, AuthConfig};
+
+use remotemedia_core::auth::Scope;
+use remotemedia_core::transport::pipeline_patch::PipelinePatch;
 use remotemedia_core::transport::session_control::{
-    CloseReason, ControlAddress as CoreAddress, ControlEvent as CoreEvent,
+    CloseReason, ControlAddress as CoreAddress, ControlAuth, ControlEvent as CoreEvent,
     ControlFrame as CoreFrame, Direction, InterceptDecision as CoreDecision, NodeState,
     SessionControl, SessionControlBus,
 };
@@ -54,11 +58,22 @@ const OUTBOUND_EVENT_CAPACITY: usize = 256;
 #[derive(Clone)]
 pub struct ControlServiceImpl {
     bus: Arc<SessionControlBus>,
+    auth: AuthConfig,
 }
 
 impl ControlServiceImpl {
     pub fn new(bus: Arc<SessionControlBus>) -> Self {
-        Self { bus }
+        Self {
+            bus,
+            auth: AuthConfig::default(),
+        }
+    }
+
+    /// Require a key with the control scope; non-admin keys may only
+    /// attach to their own tenant's sessions
+    pub fn with_auth(mut self, auth: AuthConfig) -> Self {
+        self.auth = auth;
+        self
     }
 }
 
@@ -73,6 +88,7 @@ impl PipelineControl for ControlServiceImpl {
         &self,
         request: Request<Streaming<ControlFrame>>,
     ) -> Result<Response<Self::AttachStream>, Status> {
+        let principal = authorize(&request, &self.auth, Scope::Control)?;
         let mut in_stream = request.into_inner();
         let (out_tx, out_rx) = mpsc::channel::<Result<ControlEvent, Status>>(OUTBOUND_EVENT_CAPACITY);
 
@@ -92,7 +108,9 @@ impl PipelineControl for ControlServiceImpl {
             }
         };
 
-        let ctrl = match self.bus.get(&session_id) {
+        // Kept for the life of the attach: patches are checked against it
+        let auth = ControlAuth::new(principal);
+        let ctrl = match self.bus.attach(&session_id, &auth).map_err(Status::from)? {
             Some(c) => c,
             None => {
                 return Err(Status::not_found(format!(
@@ -117,7 +135,7 @@ impl PipelineControl for ControlServiceImpl {
         // Spawn the inbound-frame dispatch + outbound-event forwarding loop.
         let bus = self.bus.clone();
         tokio::spawn(async move {
-            run_attach_loop(ctrl, bus, session_id, in_stream, out_tx).await;
+            run_attach_loop(ctrl, auth, bus, session_id, in_stream, out_tx).await;
         });
 
         let stream: EventStream = Box::pin(ReceiverStream::new(out_rx));
@@ -129,6 +147,7 @@ impl PipelineControl for ControlServiceImpl {
 
 async fn run_attach_loop(
     ctrl: Arc<SessionControl>,
+    auth: ControlAuth,
     _bus: Arc<SessionControlBus>,
     session_id: String,
     mut in_stream: Streaming<ControlFrame>,
@@ -159,6 +178,7 @@ async fn run_attach_loop(
 
                 if let Err(proto_err) = handle_inbound_frame(
                     &ctrl,
+                    &auth,
                     frame,
                     &out_tx,
                     &mut forwarders,
@@ -207,6 +227,7 @@ async fn run_attach_loop(
 // error worth surfacing to the client; Ok(()) otherwise.
 async fn handle_inbound_frame(
     ctrl: &Arc<SessionControl>,
+    auth: &ControlAuth,
     frame: ControlFrame,
     out_tx: &mpsc::Sender<Result<ControlEvent, Status>>,
     forwarders: &mut Vec<tokio::task::JoinHandle<()>>,
@@ -340,6 +361,40 @@ async fn handle_inbound_frame(
             Ok(())
         }
 
+        Some(PbOp::ApplyPatch(apply)) => {
+            let patch: PipelinePatch = serde_json::from_str(&apply.patch_json).map_err(|e| {
+                error(ControlErrorCode::InvalidPatch, format!("invalid patch JSON: {e}"), None)
+            })?;
+            auth.check_patch(ctrl, &patch)
+                .map_err(|e| error(ControlErrorCode::Unauthorized, e.to_string(), None))?;
+            // Applying can take a while (new nodes load models, retired
+            // nodes drain), so answer from a task and keep reading frames.
+            let ctrl = ctrl.clone();
+            let tx = out_tx.clone();
+            let task = tokio::spawn(async move {
+                let event = match ctrl.apply_patch(patch).await {
+                    Ok(report) => PbEvent::PatchApplied(PatchApplied {
+                        report_json: serde_json::to_string(&report).unwrap_or_default(),
+                    }),
+                    Err(e) => PbEvent::Error(error(
+                        ControlErrorCode::InvalidPatch,
+                        e.to_string(),
+                        None,
+                    )),
+                };
+                let _ = tx.send(Ok(ControlEvent { event: Some(event) })).await;
+            });
+            forwarders.push(task);
+            Ok(())
+        }
+
+        // Recording needs the peer's media, which only WebRTC carries.
+        Some(PbOp::StartRecording(_)) | Some(PbOp::StopRecording(_)) => Err(error(
+            ControlErrorCode::Recording,
+            "session recording is only available on WebRTC peers",
+            None,
+        )),
+
         None => Err(error(
             ControlErrorCode::Protocol,
             "empty ControlFrame (no op set)",
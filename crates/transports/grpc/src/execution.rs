@@ -8,8 +8,8 @@
 #![allow(dead_code)]
 
 use crate::{
-    adapters::{data_buffer_to_runtime_data, runtime_data_to_data_buffer},
-    auth::{check_auth, AuthConfig},
+    adapters::{data_buffer_to_runtime_data, parse_json_field, runtime_data_to_data_buffer},
+    auth::{authorize, AuthConfig},
     generated::{
         pipeline_execution_service_server::PipelineExecutionService, ErrorResponse, ErrorType,
         ExecuteRequest, ExecuteResponse, ExecutionMetrics as ProtoExecutionMetrics,
@@ -22,6 +22,7 @@ use crate::{
 };
 
 use remotemedia_core::{
+    auth::Scope,
     manifest::Manifest,
     transport::{PipelineExecutor, TransportData},
 };
@@ -83,13 +84,22 @@ impl ExecutionServiceImpl {
                         3 => "cpython_wasm",
                         4 => "auto",
                         _ => "auto",
-                    }
+                    },
+                    "ports": parse_json_field(&n.ports),
+                    "restart": parse_json_field(&n.restart),
+                    "execution": parse_json_field(&n.execution)
                 })
             }).collect::<Vec<_>>(),
             "connections": proto_manifest.connections.iter().map(|c| {
                 serde_json::json!({
                     "from": c.from,
-                    "to": c.to
+                    "to": c.to,
+                    "from_port": (!c.from_port.is_empty()).then_some(&c.from_port),
+                    "to_port": (!c.to_port.is_empty()).then_some(&c.to_port),
+                    "filter": (!c.filter.is_empty()).then(|| {
+                        serde_json::from_str::<serde_json::Value>(&c.filter)
+                            .unwrap_or_else(|_| serde_json::Value::String(c.filter.clone()))
+                    })
                 })
             }).collect::<Vec<_>>()
         })
@@ -138,7 +148,7 @@ impl PipelineExecutionService for ExecutionServiceImpl {
         self.metrics.record_request_start("ExecutePipeline");
 
         // Check authentication
-        check_auth(&request, &self.auth_config)?;
+        let principal = authorize(&request, &self.auth_config, Scope::Execute)?;
 
         let req = request.into_inner();
 
@@ -196,6 +206,11 @@ impl PipelineExecutionService for ExecutionServiceImpl {
             return Ok(Response::new(response));
         }
 
+        // API keys may be restricted to certain manifests or node types
+        principal
+            .check_manifest(&manifest)
+            .map_err(Status::from)?;
+
         // Convert first data input to TransportData
         // For unary execution, we expect exactly one input
         let input = if let Some((node_id, data_buffer)) = req.data_inputs.into_iter().next() {
@@ -252,13 +267,28 @@ impl PipelineExecutionService for ExecutionServiceImpl {
             "Executing pipeline"
         );
 
-        let output = match self.executor.execute_unary(manifest.clone(), input).await {
+        let output = match self
+            .executor
+            .execute_unary_as(manifest.clone(), input, Some(&principal.tenant_id))
+            .await
+        {
             Ok(result) => result,
             Err(e) => {
                 error!(error = %e, "Pipeline execution failed");
                 self.metrics
                     .record_request_end("ExecutePipeline", "error", start_time);
 
+                // Sessions refused by admission control carry a retry hint
+                if let Some(error_response) = crate::admission_error_response(&e) {
+                    self.metrics.record_error("admission");
+                    let response = ExecuteResponse {
+                        outcome: Some(crate::generated::execute_response::Outcome::Error(
+                            error_response,
+                        )),
+                    };
+                    return Ok(Response::new(response));
+                }
+
                 // Detect validation errors and map to appropriate error type
                 let (error_type, message, context) =
                     if let remotemedia_core::Error::Validation(ref validation_errors) = e {
@@ -1078,6 +1078,15 @@ pub struct NodeManifest {
     /// output_types: \[DATA_TYPE_HINT_AUDIO\]
     #[prost(enumeration = "DataTypeHint", repeated, tag = "9")]
     pub output_types: ::prost::alloc::vec::Vec<i32>,
+    /// JSON-encoded named ports besides `main` (empty = none declared)
+    #[prost(string, tag = "10")]
+    pub ports: ::prost::alloc::string::String,
+    /// JSON-encoded worker restart policy (empty = executor default)
+    #[prost(string, tag = "11")]
+    pub restart: ::prost::alloc::string::String,
+    /// JSON-encoded execution placement (empty = none)
+    #[prost(string, tag = "12")]
+    pub execution: ::prost::alloc::string::String,
 }
 /// Connection between nodes (unchanged from Feature 003)
 #[derive(Clone, PartialEq, Eq, Hash, ::prost::Message)]
@@ -1088,6 +1097,15 @@ pub struct Connection {
     /// Target node ID (consumes input)
     #[prost(string, tag = "2")]
     pub to: ::prost::alloc::string::String,
+    /// Source output port (empty = "main")
+    #[prost(string, tag = "3")]
+    pub from_port: ::prost::alloc::string::String,
+    /// Target input port (empty = "main")
+    #[prost(string, tag = "4")]
+    pub to_port: ::prost::alloc::string::String,
+    /// JSON-encoded edge filter (empty = forward everything)
+    #[prost(string, tag = "5")]
+    pub filter: ::prost::alloc::string::String,
 }
 /// Hardware/resource requirements for node execution
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -2464,7 +2482,7 @@ pub mod intercept_decision {
 pub struct Empty {}
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct ControlFrame {
-    #[prost(oneof = "control_frame::Op", tags = "1, 2, 3, 4, 5, 6, 7, 8, 9")]
+    #[prost(oneof = "control_frame::Op", tags = "1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12")]
     pub op: ::core::option::Option<control_frame::Op>,
 }
 /// Nested message and enum types in `ControlFrame`.
@@ -2489,6 +2507,12 @@ pub mod control_frame {
         SetNodeState(super::SetNodeState),
         #[prost(message, tag = "9")]
         ClearNodeState(super::ClearNodeState),
+        #[prost(message, tag = "10")]
+        ApplyPatch(super::ApplyPatch),
+        #[prost(message, tag = "11")]
+        StartRecording(super::StartRecording),
+        #[prost(message, tag = "12")]
+        StopRecording(super::StopRecording),
     }
 }
 /// Identifies the session this attach is scoped to. MUST be the first frame.
@@ -2552,9 +2576,30 @@ pub struct ClearNodeState {
     #[prost(string, tag = "1")]
     pub node_id: ::prost::alloc::string::String,
 }
+/// Edit the running pipeline. Answered with `PatchApplied`, or an
+/// `ErrorEvent { code = INVALID_PATCH }` if the patch was rejected — in
+/// which case the pipeline is unchanged.
+#[derive(Clone, PartialEq, Eq, Hash, ::prost::Message)]
+pub struct ApplyPatch {
+    /// JSON-encoded `PipelinePatch`: `{"ops": \[{"op": "add_node", ...}, ...\]}`
+    #[prost(string, tag = "1")]
+    pub patch_json: ::prost::alloc::string::String,
+}
+/// Record the session to WebM on the server (WebRTC peers only). Answered
+/// with `RecordingStarted`, or `ErrorEvent { code = RECORDING }`.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, ::prost::Message)]
+pub struct StartRecording {
+    /// Also record the pipeline's tracks sent back to the client.
+    #[prost(bool, tag = "1")]
+    pub include_outbound: bool,
+}
+/// Stop the running recording. Answered with `RecordingStopped` once the
+/// file is finalized.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, ::prost::Message)]
+pub struct StopRecording {}
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct ControlEvent {
-    #[prost(oneof = "control_event::Event", tags = "1, 2, 3, 4, 5")]
+    #[prost(oneof = "control_event::Event", tags = "1, 2, 3, 4, 5, 6, 7, 8")]
     pub event: ::core::option::Option<control_event::Event>,
 }
 /// Nested message and enum types in `ControlEvent`.
@@ -2578,6 +2623,15 @@ pub mod control_event {
         /// Sent exactly once as the final frame when the session closes.
         #[prost(message, tag = "5")]
         SessionClosed(super::SessionClosed),
+        /// An `ApplyPatch` was committed.
+        #[prost(message, tag = "6")]
+        PatchApplied(super::PatchApplied),
+        /// A `StartRecording` began writing.
+        #[prost(message, tag = "7")]
+        RecordingStarted(super::RecordingStarted),
+        /// A `StopRecording` finalized the file.
+        #[prost(message, tag = "8")]
+        RecordingStopped(super::RecordingStopped),
     }
 }
 #[derive(Clone, PartialEq, Eq, Hash, ::prost::Message)]
@@ -2587,6 +2641,35 @@ pub struct Attached {
     #[prost(string, tag = "2")]
     pub attach_id: ::prost::alloc::string::String,
 }
+#[derive(Clone, PartialEq, Eq, Hash, ::prost::Message)]
+pub struct PatchApplied {
+    /// JSON-encoded `PatchReport`: added / removed / restarted node ids and
+    /// connection counts.
+    #[prost(string, tag = "1")]
+    pub report_json: ::prost::alloc::string::String,
+}
+#[derive(Clone, PartialEq, Eq, Hash, ::prost::Message)]
+pub struct RecordingStarted {
+    #[prost(string, tag = "1")]
+    pub recording_id: ::prost::alloc::string::String,
+    /// Path of the file on the server.
+    #[prost(string, tag = "2")]
+    pub path: ::prost::alloc::string::String,
+    /// Recorded track names (`in:audio`, `out:<stream_id>:video`, ...).
+    #[prost(string, repeated, tag = "3")]
+    pub tracks: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+}
+#[derive(Clone, PartialEq, Eq, Hash, ::prost::Message)]
+pub struct RecordingStopped {
+    #[prost(string, tag = "1")]
+    pub recording_id: ::prost::alloc::string::String,
+    #[prost(string, tag = "2")]
+    pub path: ::prost::alloc::string::String,
+    #[prost(uint64, tag = "3")]
+    pub duration_ms: u64,
+    #[prost(uint64, tag = "4")]
+    pub size_bytes: u64,
+}
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct TapEvent {
     #[prost(message, optional, tag = "1")]
@@ -2698,6 +2781,10 @@ pub enum ControlErrorCode {
     Protocol = 4,
     Unauthorized = 5,
     Internal = 6,
+    /// patch rejected; pipeline unchanged
+    InvalidPatch = 7,
+    /// recording unavailable or failed
+    Recording = 8,
 }
 impl ControlErrorCode {
     /// String value of the enum field names used in the ProtoBuf definition.
@@ -2713,6 +2800,8 @@ impl ControlErrorCode {
             Self::Protocol => "CONTROL_ERROR_CODE_PROTOCOL",
             Self::Unauthorized => "CONTROL_ERROR_CODE_UNAUTHORIZED",
             Self::Internal => "CONTROL_ERROR_CODE_INTERNAL",
+            Self::InvalidPatch => "CONTROL_ERROR_CODE_INVALID_PATCH",
+            Self::Recording => "CONTROL_ERROR_CODE_RECORDING",
         }
     }
     /// Creates an enum from field names used in the ProtoBuf definition.
@@ -2725,6 +2814,8 @@ impl ControlErrorCode {
             "CONTROL_ERROR_CODE_PROTOCOL" => Some(Self::Protocol),
             "CONTROL_ERROR_CODE_UNAUTHORIZED" => Some(Self::Unauthorized),
             "CONTROL_ERROR_CODE_INTERNAL" => Some(Self::Internal),
+            "CONTROL_ERROR_CODE_INVALID_PATCH" => Some(Self::InvalidPatch),
+            "CONTROL_ERROR_CODE_RECORDING" => Some(Self::Recording),
             _ => None,
         }
     }
@@ -3069,3 +3160,658 @@ pub mod pipeline_control_server {
         const NAME: &'static str = SERVICE_NAME;
     }
 }
+#[derive(Clone, Copy, PartialEq, Eq, Hash, ::prost::Message)]
+pub struct ListSessionsRequest {}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ListSessionsResponse {
+    #[prost(message, repeated, tag = "1")]
+    pub sessions: ::prost::alloc::vec::Vec<SessionSummary>,
+}
+/// One line of `ListSessions`.
+#[derive(Clone, PartialEq, Eq, Hash, ::prost::Message)]
+pub struct SessionSummary {
+    #[prost(string, tag = "1")]
+    pub session_id: ::prost::alloc::string::String,
+    /// `metadata.name` of the session's manifest.
+    #[prost(string, tag = "2")]
+    pub pipeline_name: ::prost::alloc::string::String,
+    /// Wall-clock creation time, milliseconds since UNIX epoch.
+    #[prost(uint64, tag = "3")]
+    pub started_at_ms: u64,
+    #[prost(uint64, tag = "4")]
+    pub uptime_ms: u64,
+    #[prost(uint32, tag = "5")]
+    pub node_count: u32,
+}
+#[derive(Clone, PartialEq, Eq, Hash, ::prost::Message)]
+pub struct DescribeSessionRequest {
+    #[prost(string, tag = "1")]
+    pub session_id: ::prost::alloc::string::String,
+}
+/// A node as declared in the manifest plus its runtime state.
+#[derive(Clone, PartialEq, Eq, Hash, ::prost::Message)]
+pub struct AdminNodeInfo {
+    #[prost(string, tag = "1")]
+    pub node_id: ::prost::alloc::string::String,
+    #[prost(string, tag = "2")]
+    pub node_type: ::prost::alloc::string::String,
+    #[prost(enumeration = "NodeState", tag = "3")]
+    pub state: i32,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SessionDescription {
+    #[prost(message, optional, tag = "1")]
+    pub summary: ::core::option::Option<SessionSummary>,
+    #[prost(message, repeated, tag = "2")]
+    pub nodes: ::prost::alloc::vec::Vec<AdminNodeInfo>,
+    /// Streams with drift metrics (see GetSessionMetrics).
+    #[prost(string, repeated, tag = "3")]
+    pub stream_ids: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+    /// The manifest the session is running, as JSON.
+    #[prost(string, tag = "4")]
+    pub manifest_json: ::prost::alloc::string::String,
+}
+#[derive(Clone, PartialEq, Eq, Hash, ::prost::Message)]
+pub struct KillSessionRequest {
+    #[prost(string, tag = "1")]
+    pub session_id: ::prost::alloc::string::String,
+    /// Free-form reason, forwarded to attached clients.
+    #[prost(string, tag = "2")]
+    pub reason: ::prost::alloc::string::String,
+}
+#[derive(Clone, Copy, PartialEq, Eq, Hash, ::prost::Message)]
+pub struct KillSessionResponse {
+    /// False if the session had already stopped.
+    #[prost(bool, tag = "1")]
+    pub terminated: bool,
+}
+#[derive(Clone, PartialEq, Eq, Hash, ::prost::Message)]
+pub struct GetSessionMetricsRequest {
+    #[prost(string, tag = "1")]
+    pub session_id: ::prost::alloc::string::String,
+}
+/// Mirrors `NodeStats` from the streaming scheduler.
+#[derive(Clone, Copy, PartialEq, ::prost::Message)]
+pub struct AdminNodeStats {
+    #[prost(uint64, tag = "1")]
+    pub execution_count: u64,
+    #[prost(uint64, tag = "2")]
+    pub error_count: u64,
+    #[prost(double, tag = "3")]
+    pub error_rate: f64,
+    #[prost(bool, tag = "4")]
+    pub circuit_breaker_open: bool,
+    #[prost(uint64, tag = "5")]
+    pub p50_us: u64,
+    #[prost(uint64, tag = "6")]
+    pub p95_us: u64,
+    #[prost(uint64, tag = "7")]
+    pub p99_us: u64,
+}
+/// Drift for one media stream.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct StreamDriftInfo {
+    #[prost(string, tag = "1")]
+    pub stream_id: ::prost::alloc::string::String,
+    /// 0.0 (unhealthy) – 1.0 (healthy).
+    #[prost(double, tag = "2")]
+    pub health_score: f64,
+    /// Full drift debug dump as JSON.
+    #[prost(string, tag = "3")]
+    pub detail_json: ::prost::alloc::string::String,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SessionMetrics {
+    #[prost(string, tag = "1")]
+    pub session_id: ::prost::alloc::string::String,
+    #[prost(map = "string, message", tag = "2")]
+    pub node_stats: ::std::collections::HashMap<
+        ::prost::alloc::string::String,
+        AdminNodeStats,
+    >,
+    #[prost(message, repeated, tag = "3")]
+    pub drift: ::prost::alloc::vec::Vec<StreamDriftInfo>,
+    /// Prometheus text exposition for this session.
+    #[prost(string, tag = "4")]
+    pub prometheus_text: ::prost::alloc::string::String,
+}
+/// Generated client implementations.
+pub mod session_admin_client {
+    #![allow(
+        unused_variables,
+        dead_code,
+        missing_docs,
+        clippy::wildcard_imports,
+        clippy::let_unit_value,
+    )]
+    use tonic::codegen::*;
+    use tonic::codegen::http::Uri;
+    #[derive(Debug, Clone)]
+    pub struct SessionAdminClient<T> {
+        inner: tonic::client::Grpc<T>,
+    }
+    impl SessionAdminClient<tonic::transport::Channel> {
+        /// Attempt to create a new client by connecting to a given endpoint.
+        pub async fn connect<D>(dst: D) -> Result<Self, tonic::transport::Error>
+        where
+            D: TryInto<tonic::transport::Endpoint>,
+            D::Error: Into<StdError>,
+        {
+            let conn = tonic::transport::Endpoint::new(dst)?.connect().await?;
+            Ok(Self::new(conn))
+        }
+    }
+    impl<T> SessionAdminClient<T>
+    where
+        T: tonic::client::GrpcService<tonic::body::Body>,
+        T::Error: Into<StdError>,
+        T::ResponseBody: Body<Data = Bytes> + std::marker::Send + 'static,
+        <T::ResponseBody as Body>::Error: Into<StdError> + std::marker::Send,
+    {
+        pub fn new(inner: T) -> Self {
+            let inner = tonic::client::Grpc::new(inner);
+            Self { inner }
+        }
+        pub fn with_origin(inner: T, origin: Uri) -> Self {
+            let inner = tonic::client::Grpc::with_origin(inner, origin);
+            Self { inner }
+        }
+        pub fn with_interceptor<F>(
+            inner: T,
+            interceptor: F,
+        ) -> SessionAdminClient<InterceptedService<T, F>>
+        where
+            F: tonic::service::Interceptor,
+            T::ResponseBody: Default,
+            T: tonic::codegen::Service<
+                http::Request<tonic::body::Body>,
+                Response = http::Response<
+                    <T as tonic::client::GrpcService<tonic::body::Body>>::ResponseBody,
+                >,
+            >,
+            <T as tonic::codegen::Service<
+                http::Request<tonic::body::Body>,
+            >>::Error: Into<StdError> + std::marker::Send + std::marker::Sync,
+        {
+            SessionAdminClient::new(InterceptedService::new(inner, interceptor))
+        }
+        /// Compress requests with the given encoding.
+        ///
+        /// This requires the server to support it otherwise it might respond with an
+        /// error.
+        #[must_use]
+        pub fn send_compressed(mut self, encoding: CompressionEncoding) -> Self {
+            self.inner = self.inner.send_compressed(encoding);
+            self
+        }
+        /// Enable decompressing responses.
+        #[must_use]
+        pub fn accept_compressed(mut self, encoding: CompressionEncoding) -> Self {
+            self.inner = self.inner.accept_compressed(encoding);
+            self
+        }
+        /// Limits the maximum size of a decoded message.
+        ///
+        /// Default: `4MB`
+        #[must_use]
+        pub fn max_decoding_message_size(mut self, limit: usize) -> Self {
+            self.inner = self.inner.max_decoding_message_size(limit);
+            self
+        }
+        /// Limits the maximum size of an encoded message.
+        ///
+        /// Default: `usize::MAX`
+        #[must_use]
+        pub fn max_encoding_message_size(mut self, limit: usize) -> Self {
+            self.inner = self.inner.max_encoding_message_size(limit);
+            self
+        }
+        /// Every live session, sorted by session id.
+        pub async fn list_sessions(
+            &mut self,
+            request: impl tonic::IntoRequest<super::ListSessionsRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::ListSessionsResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic_prost::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/remotemedia.v1.SessionAdmin/ListSessions",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("remotemedia.v1.SessionAdmin", "ListSessions"));
+            self.inner.unary(req, path, codec).await
+        }
+        /// Manifest, node states and streams of one session. NOT_FOUND if the
+        /// session is unknown or has ended.
+        pub async fn describe_session(
+            &mut self,
+            request: impl tonic::IntoRequest<super::DescribeSessionRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::SessionDescription>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic_prost::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/remotemedia.v1.SessionAdmin/DescribeSession",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(
+                    GrpcMethod::new("remotemedia.v1.SessionAdmin", "DescribeSession"),
+                );
+            self.inner.unary(req, path, codec).await
+        }
+        /// Forcefully end a session. Attached control clients receive
+        /// `SessionClosed` with the given reason.
+        pub async fn kill_session(
+            &mut self,
+            request: impl tonic::IntoRequest<super::KillSessionRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::KillSessionResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic_prost::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/remotemedia.v1.SessionAdmin/KillSession",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("remotemedia.v1.SessionAdmin", "KillSession"));
+            self.inner.unary(req, path, codec).await
+        }
+        /// Per-node execution statistics, per-stream drift and the session's
+        /// Prometheus exposition.
+        pub async fn get_session_metrics(
+            &mut self,
+            request: impl tonic::IntoRequest<super::GetSessionMetricsRequest>,
+        ) -> std::result::Result<tonic::Response<super::SessionMetrics>, tonic::Status> {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic_prost::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/remotemedia.v1.SessionAdmin/GetSessionMetrics",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(
+                    GrpcMethod::new("remotemedia.v1.SessionAdmin", "GetSessionMetrics"),
+                );
+            self.inner.unary(req, path, codec).await
+        }
+    }
+}
+/// Generated server implementations.
+pub mod session_admin_server {
+    #![allow(
+        unused_variables,
+        dead_code,
+        missing_docs,
+        clippy::wildcard_imports,
+        clippy::let_unit_value,
+    )]
+    use tonic::codegen::*;
+    /// Generated trait containing gRPC methods that should be implemented for use with SessionAdminServer.
+    #[async_trait]
+    pub trait SessionAdmin: std::marker::Send + std::marker::Sync + 'static {
+        /// Every live session, sorted by session id.
+        async fn list_sessions(
+            &self,
+            request: tonic::Request<super::ListSessionsRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::ListSessionsResponse>,
+            tonic::Status,
+        >;
+        /// Manifest, node states and streams of one session. NOT_FOUND if the
+        /// session is unknown or has ended.
+        async fn describe_session(
+            &self,
+            request: tonic::Request<super::DescribeSessionRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::SessionDescription>,
+            tonic::Status,
+        >;
+        /// Forcefully end a session. Attached control clients receive
+        /// `SessionClosed` with the given reason.
+        async fn kill_session(
+            &self,
+            request: tonic::Request<super::KillSessionRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::KillSessionResponse>,
+            tonic::Status,
+        >;
+        /// Per-node execution statistics, per-stream drift and the session's
+        /// Prometheus exposition.
+        async fn get_session_metrics(
+            &self,
+            request: tonic::Request<super::GetSessionMetricsRequest>,
+        ) -> std::result::Result<tonic::Response<super::SessionMetrics>, tonic::Status>;
+    }
+    #[derive(Debug)]
+    pub struct SessionAdminServer<T> {
+        inner: Arc<T>,
+        accept_compression_encodings: EnabledCompressionEncodings,
+        send_compression_encodings: EnabledCompressionEncodings,
+        max_decoding_message_size: Option<usize>,
+        max_encoding_message_size: Option<usize>,
+    }
+    impl<T> SessionAdminServer<T> {
+        pub fn new(inner: T) -> Self {
+            Self::from_arc(Arc::new(inner))
+        }
+        pub fn from_arc(inner: Arc<T>) -> Self {
+            Self {
+                inner,
+                accept_compression_encodings: Default::default(),
+                send_compression_encodings: Default::default(),
+                max_decoding_message_size: None,
+                max_encoding_message_size: None,
+            }
+        }
+        pub fn with_interceptor<F>(
+            inner: T,
+            interceptor: F,
+        ) -> InterceptedService<Self, F>
+        where
+            F: tonic::service::Interceptor,
+        {
+            InterceptedService::new(Self::new(inner), interceptor)
+        }
+        /// Enable decompressing requests with the given encoding.
+        #[must_use]
+        pub fn accept_compressed(mut self, encoding: CompressionEncoding) -> Self {
+            self.accept_compression_encodings.enable(encoding);
+            self
+        }
+        /// Compress responses with the given encoding, if the client supports it.
+        #[must_use]
+        pub fn send_compressed(mut self, encoding: CompressionEncoding) -> Self {
+            self.send_compression_encodings.enable(encoding);
+            self
+        }
+        /// Limits the maximum size of a decoded message.
+        ///
+        /// Default: `4MB`
+        #[must_use]
+        pub fn max_decoding_message_size(mut self, limit: usize) -> Self {
+            self.max_decoding_message_size = Some(limit);
+            self
+        }
+        /// Limits the maximum size of an encoded message.
+        ///
+        /// Default: `usize::MAX`
+        #[must_use]
+        pub fn max_encoding_message_size(mut self, limit: usize) -> Self {
+            self.max_encoding_message_size = Some(limit);
+            self
+        }
+    }
+    impl<T, B> tonic::codegen::Service<http::Request<B>> for SessionAdminServer<T>
+    where
+        T: SessionAdmin,
+        B: Body + std::marker::Send + 'static,
+        B::Error: Into<StdError> + std::marker::Send + 'static,
+    {
+        type Response = http::Response<tonic::body::Body>;
+        type Error = std::convert::Infallible;
+        type Future = BoxFuture<Self::Response, Self::Error>;
+        fn poll_ready(
+            &mut self,
+            _cx: &mut Context<'_>,
+        ) -> Poll<std::result::Result<(), Self::Error>> {
+            Poll::Ready(Ok(()))
+        }
+        fn call(&mut self, req: http::Request<B>) -> Self::Future {
+            match req.uri().path() {
+                "/remotemedia.v1.SessionAdmin/ListSessions" => {
+                    #[allow(non_camel_case_types)]
+                    struct ListSessionsSvc<T: SessionAdmin>(pub Arc<T>);
+                    impl<
+                        T: SessionAdmin,
+                    > tonic::server::UnaryService<super::ListSessionsRequest>
+                    for ListSessionsSvc<T> {
+                        type Response = super::ListSessionsResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::ListSessionsRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as SessionAdmin>::list_sessions(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = ListSessionsSvc(inner);
+                        let codec = tonic_prost::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/remotemedia.v1.SessionAdmin/DescribeSession" => {
+                    #[allow(non_camel_case_types)]
+                    struct DescribeSessionSvc<T: SessionAdmin>(pub Arc<T>);
+                    impl<
+                        T: SessionAdmin,
+                    > tonic::server::UnaryService<super::DescribeSessionRequest>
+                    for DescribeSessionSvc<T> {
+                        type Response = super::SessionDescription;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::DescribeSessionRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as SessionAdmin>::describe_session(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = DescribeSessionSvc(inner);
+                        let codec = tonic_prost::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/remotemedia.v1.SessionAdmin/KillSession" => {
+                    #[allow(non_camel_case_types)]
+                    struct KillSessionSvc<T: SessionAdmin>(pub Arc<T>);
+                    impl<
+                        T: SessionAdmin,
+                    > tonic::server::UnaryService<super::KillSessionRequest>
+                    for KillSessionSvc<T> {
+                        type Response = super::KillSessionResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::KillSessionRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as SessionAdmin>::kill_session(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = KillSessionSvc(inner);
+                        let codec = tonic_prost::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/remotemedia.v1.SessionAdmin/GetSessionMetrics" => {
+                    #[allow(non_camel_case_types)]
+                    struct GetSessionMetricsSvc<T: SessionAdmin>(pub Arc<T>);
+                    impl<
+                        T: SessionAdmin,
+                    > tonic::server::UnaryService<super::GetSessionMetricsRequest>
+                    for GetSessionMetricsSvc<T> {
+                        type Response = super::SessionMetrics;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::GetSessionMetricsRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as SessionAdmin>::get_session_metrics(&inner, request)
+                                    .await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = GetSessionMetricsSvc(inner);
+                        let codec = tonic_prost::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                _ => {
+                    Box::pin(async move {
+                        let mut response = http::Response::new(
+                            tonic::body::Body::default(),
+                        );
+                        let headers = response.headers_mut();
+                        headers
+                            .insert(
+                                tonic::Status::GRPC_STATUS,
+                                (tonic::Code::Unimplemented as i32).into(),
+                            );
+                        headers
+                            .insert(
+                                http::header::CONTENT_TYPE,
+                                tonic::metadata::GRPC_CONTENT_TYPE,
+                            );
+                        Ok(response)
+                    })
+                }
+            }
+        }
+    }
+    impl<T> Clone for SessionAdminServer<T> {
+        fn clone(&self) -> Self {
+            let inner = self.inner.clone();
+            Self {
+                inner,
+                accept_compression_encodings: self.accept_compression_encodings,
+                send_compression_encodings: self.send_compression_encodings,
+                max_decoding_message_size: self.max_decoding_message_size,
+                max_encoding_message_size: self.max_encoding_message_size,
+            }
+        }
+    }
+    /// Generated gRPC service name
+    pub const SERVICE_NAME: &str = "remotemedia.v1.SessionAdmin";
+    impl<T> tonic::server::NamedService for SessionAdminServer<T> {
+        const NAME: &'static str = SERVICE_NAME;
+    }
+}
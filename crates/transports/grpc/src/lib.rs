@@ -9,6 +9,7 @@
 //! - **execution.rs**: Unary RPC handler for ExecutePipeline
 //! - **streaming.rs**: Bidirectional streaming handler for StreamPipeline
 //! - **auth.rs**: API token validation middleware
+//! - **admin.rs**: SessionAdmin handler (list/describe/kill/metrics for live sessions)
 //! - **limits.rs**: Resource limit enforcement
 //! - **metrics.rs**: Prometheus metrics collection
 //! - **version.rs**: Protocol version negotiation
@@ -20,11 +21,13 @@
 //! - common.proto: DataBuffer, AudioBuffer, ExecutionMetrics, ErrorResponse
 //! - execution.proto: PipelineExecutionService, ExecutePipeline RPC
 //! - streaming.proto: StreamingPipelineService, StreamPipeline RPC
+//! - admin.proto: SessionAdmin service (admin-scoped session management)
 
 #![warn(clippy::all)]
 
 // Core modules
 pub mod adapters;
+pub mod admin;
 pub mod auth;
 pub mod client;
 pub mod control;
@@ -75,6 +78,7 @@ pub use adapters::{
 };
 
 // Re-export main server types for convenience
+pub use admin::SessionAdminServiceImpl;
 pub use execution::ExecutionServiceImpl;
 pub use server::GrpcServer;
 pub use streaming::StreamingServiceImpl;
@@ -120,6 +124,33 @@ pub enum ServiceError {
     Grpc(#[from] tonic::Status),
 }
 
+/// `ErrorResponse` for a session refused by executor admission control
+///
+/// Returns `None` for any other error. `context` carries the exhausted
+/// quota and retry hint as JSON (`{"resource": ..., "retry_after_ms": ...}`)
+/// so clients can back off before retrying.
+pub fn admission_error_response(err: &remotemedia_core::Error) -> Option<ErrorResponse> {
+    let remotemedia_core::Error::AdmissionRejected {
+        resource,
+        retry_after_ms,
+        ..
+    } = err
+    else {
+        return None;
+    };
+    Some(ErrorResponse {
+        error_type: ErrorType::ResourceLimit as i32,
+        message: err.to_string(),
+        failing_node_id: String::new(),
+        context: serde_json::json!({
+            "resource": resource,
+            "retry_after_ms": retry_after_ms,
+        })
+        .to_string(),
+        stack_trace: String::new(),
+    })
+}
+
 /// Service configuration
 #[derive(Debug, Clone)]
 pub struct ServiceConfig {
@@ -149,7 +180,10 @@ impl Default for ServiceConfig {
 
 impl ServiceConfig {
     /// Load configuration from environment variables
-    pub fn from_env() -> Self {
+    ///
+    /// Fails when the `REMOTEMEDIA_AUTH_*` API key configuration is invalid,
+    /// so a misconfigured server refuses to start instead of running open.
+    pub fn from_env() -> Result<Self, remotemedia_core::auth::AuthError> {
         let bind_address =
             std::env::var("GRPC_BIND_ADDRESS").unwrap_or_else(|_| "0.0.0.0:50051".to_string());
 
@@ -161,6 +195,10 @@ impl ServiceConfig {
             .map(|t| t.split(',').map(|s| s.trim().to_string()).collect())
             .unwrap_or_default();
 
+        let admin_tokens: Vec<String> = std::env::var("GRPC_ADMIN_TOKENS")
+            .map(|t| t.split(',').map(|s| s.trim().to_string()).collect())
+            .unwrap_or_default();
+
         let max_memory_bytes = std::env::var("GRPC_MAX_MEMORY_MB")
             .ok()
             .and_then(|v| v.parse::<u64>().ok())
@@ -176,16 +214,21 @@ impl ServiceConfig {
             .map(|v| v.to_lowercase() == "true")
             .unwrap_or(true);
 
-        Self {
+        // Shared API keys (REMOTEMEDIA_AUTH_*), on top of the static tokens
+        let api_keys = remotemedia_core::auth::AuthGuard::from_env()?;
+
+        Ok(Self {
             bind_address,
-            auth: auth::AuthConfig::new(auth_tokens, require_auth),
+            auth: auth::AuthConfig::new(auth_tokens, require_auth)
+                .with_admin_tokens(admin_tokens)
+                .with_guard(api_keys),
             limits: limits::ResourceLimits {
                 max_memory_bytes,
                 max_timeout: std::time::Duration::from_secs(max_timeout_sec),
                 ..Default::default()
             },
             json_logging,
-        }
+        })
     }
 }
 
@@ -207,3 +250,40 @@ pub fn init_tracing(json_logging: bool) {
             .init();
     }
 }
+
+/// [`init_tracing`] plus OTLP export of pipeline spans
+///
+/// Export is enabled when `OTEL_EXPORTER_OTLP_ENDPOINT` is set (see
+/// `remotemedia_core::telemetry::otlp`). Hold the returned guard until
+/// shutdown so buffered spans are flushed.
+#[cfg(feature = "otel")]
+pub fn init_tracing_with_otlp(
+    json_logging: bool,
+    service_name: &str,
+) -> Option<remotemedia_core::telemetry::otlp::OtlpGuard> {
+    use tracing_subscriber::{fmt, prelude::*, EnvFilter};
+
+    let env_filter = EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new("info"));
+    let (otel, guard) = match remotemedia_core::telemetry::otlp::layer(service_name) {
+        Ok(layer) => layer.unzip(),
+        Err(e) => {
+            eprintln!("OTLP export disabled: {}", e);
+            (None, None)
+        }
+    };
+
+    if json_logging {
+        tracing_subscriber::registry()
+            .with(otel)
+            .with(env_filter)
+            .with(fmt::layer().json())
+            .init();
+    } else {
+        tracing_subscriber::registry()
+            .with(otel)
+            .with(env_filter)
+            .with(fmt::layer())
+            .init();
+    }
+    guard
+}
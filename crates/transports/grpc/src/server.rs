@@ -4,12 +4,14 @@
 //! Provides graceful shutdown and health check support.
 
 use crate::{
+    admin::SessionAdminServiceImpl,
     auth::AuthConfig,
     control::ControlServiceImpl,
     execution::ExecutionServiceImpl,
     generated::{
         pipeline_control_server::PipelineControlServer,
         pipeline_execution_service_server::PipelineExecutionServiceServer,
+        session_admin_server::SessionAdminServer,
         streaming_pipeline_service_server::StreamingPipelineServiceServer,
     },
     metrics::ServiceMetrics,
@@ -89,7 +91,12 @@ impl GrpcServer {
         );
 
         // Session Control Bus — per-session pub/sub/intercept/node-state.
-        let control_service = ControlServiceImpl::new(self.executor.control_bus());
+        let control_service = ControlServiceImpl::new(self.executor.control_bus())
+            .with_auth(self.config.auth.clone());
+
+        // Session admin — list/describe/kill/metrics, admin scope only.
+        let admin_service =
+            SessionAdminServiceImpl::new(self.config.auth.clone(), self.executor.control_bus());
 
         // Wrap services with gRPC-Web and CORS support using tower ServiceBuilder
         let execution_service = tower::ServiceBuilder::new()
@@ -118,6 +125,12 @@ impl GrpcServer {
             .into_inner()
             .named_layer(PipelineControlServer::new(control_service));
 
+        let admin_service = tower::ServiceBuilder::new()
+            .layer(tower_http::cors::CorsLayer::permissive())
+            .layer(tonic_web::GrpcWebLayer::new())
+            .into_inner()
+            .named_layer(SessionAdminServer::new(admin_service));
+
         // T037: Configure connection pooling and HTTP/2 keepalive for concurrent clients
         let server = Server::builder()
             // Allow many concurrent requests per connection
@@ -136,7 +149,8 @@ impl GrpcServer {
             .trace_fn(|_| tracing::info_span!("grpc_request"))
             .add_service(execution_service)
             .add_service(streaming_service)
-            .add_service(control_service);
+            .add_service(control_service)
+            .add_service(admin_service);
 
         // TODO: Add graceful shutdown on Ctrl+C
         // Requires tokio signal feature which may not be available on all platforms
@@ -177,7 +191,12 @@ impl GrpcServer {
         );
 
         // Session Control Bus — per-session pub/sub/intercept/node-state.
-        let control_service = ControlServiceImpl::new(self.executor.control_bus());
+        let control_service = ControlServiceImpl::new(self.executor.control_bus())
+            .with_auth(self.config.auth.clone());
+
+        // Session admin — list/describe/kill/metrics, admin scope only.
+        let admin_service =
+            SessionAdminServiceImpl::new(self.config.auth.clone(), self.executor.control_bus());
 
         // Wrap services with gRPC-Web and CORS support using tower ServiceBuilder
         let execution_service = tower::ServiceBuilder::new()
@@ -206,6 +225,12 @@ impl GrpcServer {
             .into_inner()
             .named_layer(PipelineControlServer::new(control_service));
 
+        let admin_service = tower::ServiceBuilder::new()
+            .layer(tower_http::cors::CorsLayer::permissive())
+            .layer(tonic_web::GrpcWebLayer::new())
+            .into_inner()
+            .named_layer(SessionAdminServer::new(admin_service));
+
         // T037: Configure connection pooling and HTTP/2 keepalive for concurrent clients
         let server = Server::builder()
             // Allow many concurrent requests per connection
@@ -224,7 +249,8 @@ impl GrpcServer {
             .trace_fn(|_| tracing::info_span!("grpc_request"))
             .add_service(execution_service)
             .add_service(streaming_service)
-            .add_service(control_service);
+            .add_service(control_service)
+            .add_service(admin_service);
 
         info!("gRPC server listening on {}", addr);
 
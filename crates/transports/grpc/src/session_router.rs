@@ -27,12 +27,13 @@ use remotemedia_core::data::RuntimeData;
 use remotemedia_core::executor::PipelineGraph;
 use remotemedia_core::metrics::RtProbeSet;
 use remotemedia_core::nodes::{StreamingNode, StreamingNodeRegistry};
+use remotemedia_core::telemetry::{self, TraceContext};
 use std::collections::HashMap;
 use std::sync::Arc;
 use tokio::sync::{mpsc, Mutex};
 use tokio::task::JoinHandle;
 use tonic::Status;
-use tracing::{debug, error, info, warn};
+use tracing::{debug, error, info, warn, Instrument};
 
 /// Capacity of the client-ingress channel (client → router).
 ///
@@ -106,6 +107,8 @@ pub struct DataPacket {
     pub sequence: u64,
     /// Sub-sequence for streaming outputs
     pub sub_sequence: u64,
+    /// W3C trace context; outputs carry their producing dispatch's
+    pub trace_context: Option<TraceContext>,
 }
 
 /// Session-persistent router that runs for the entire streaming session
@@ -725,6 +728,8 @@ impl SessionRouter {
                                             session_id: session_id_for_drain.clone(),
                                             sequence: 0,  // Continuous outputs don't have input sequence
                                             sub_sequence,
+                                            // Nor an input whose trace they belong to
+                                            trace_context: None,
                                         };
 
                                         // Bounded loopback: .await applies
@@ -755,6 +760,14 @@ impl SessionRouter {
                     node_id_clone, packet.sequence
                 );
 
+                let span = telemetry::dispatch_span(
+                    &session_id,
+                    &node_id_clone,
+                    node.node_type(),
+                    packet.trace_context.as_ref(),
+                );
+                let out_trace = telemetry::outgoing_context(&span, packet.trace_context);
+
                 if is_streaming {
                     // Streaming node — inline dispatch.
                     //
@@ -794,6 +807,7 @@ impl SessionRouter {
                                     session_id: session_id_for_cb.clone(),
                                     sequence: packet_sequence,
                                     sub_sequence: output_count,
+                                    trace_context: out_trace,
                                 };
 
                                 // Sync callback — cannot `.await`.
@@ -820,6 +834,7 @@ impl SessionRouter {
                                 }
                             }),
                         )
+                        .instrument(span.clone())
                         .await;
                     probes.node_out.record_since(node_dispatch_start);
 
@@ -828,6 +843,7 @@ impl SessionRouter {
                             debug!("✅ Node '{}' produced {} outputs", node_id_clone, count);
                         }
                         Err(e) => {
+                            telemetry::record_error(&span, &e);
                             error!("Streaming node '{}' failed: {}", node_id_clone, e);
                         }
                     }
@@ -835,7 +851,10 @@ impl SessionRouter {
                     // Non-streaming node - single output.
                     // Phase B0: record total dispatch latency inline (no spawn).
                     let node_dispatch_start = std::time::Instant::now();
-                    let process_result = node.process_async(packet.data).await;
+                    let process_result = node
+                        .process_async(packet.data)
+                        .instrument(span.clone())
+                        .await;
                     probes.node_out.record_since(node_dispatch_start);
                     match process_result {
                         Ok(output) => {
@@ -846,6 +865,7 @@ impl SessionRouter {
                                 session_id: session_id.clone(),
                                 sequence: packet.sequence,
                                 sub_sequence: 0,
+                                trace_context: out_trace,
                             };
 
                             // Send output back to router via bounded loopback.
@@ -855,6 +875,7 @@ impl SessionRouter {
                             }
                         }
                         Err(e) => {
+                            telemetry::record_error(&span, &e);
                             error!("Node '{}' failed: {}", node_id_clone, e);
                         }
                     }
@@ -1002,6 +1023,7 @@ impl SessionRouter {
             session_id: self.session_id.clone(),
             sequence,
             sub_sequence: 0,
+            trace_context: None,
         };
 
         // Bounded client-ingress: `.await` applies real backpressure.
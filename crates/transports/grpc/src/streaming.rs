@@ -25,6 +25,7 @@
 // Internal infrastructure - some fields/methods for future use
 #![allow(dead_code)]
 
+use crate::adapters::parse_json_field;
 use crate::generated::{
     stream_control::Command, stream_request::Request as StreamRequestType,
     stream_response::Response as StreamResponseType,
@@ -35,11 +36,13 @@ use crate::metrics::ServiceMetrics;
 use crate::session_router::{DataPacket, SessionRouter};
 use crate::ServiceError;
 use remotemedia_core::{
+    auth::Principal,
     data::RuntimeData,
     manifest::Manifest,
     nodes::{python_streaming::PythonStreamingNode, StreamingNode, StreamingNodeRegistry},
-    transport::PipelineExecutor,
+    transport::{AdmissionController, AdmissionPermit, PipelineExecutor},
 };
+use remotemedia_core::telemetry::{TraceContext, TRACEPARENT_KEY};
 #[cfg(feature = "multiprocess")]
 use remotemedia_core::python::multiprocess::MultiprocessExecutor;
 use std::collections::HashMap;
@@ -455,6 +458,13 @@ impl crate::StreamingPipelineService for StreamingServiceImpl {
             info!("Preview features requested (validation skipped)");
         }
 
+        // The key's tenant owns the session and its admission quota
+        let principal =
+            crate::auth::authorize(&request, &self.auth_config, crate::auth::Scope::Stream)?;
+
+        // Every chunk on this stream joins the caller's trace, if it sent one
+        let trace_context = trace_context_from_metadata(request.metadata());
+
         let (tx, rx) = tokio::sync::mpsc::channel(32);
         let mut stream = request.into_inner();
         let sessions = self.sessions.clone();
@@ -470,6 +480,7 @@ impl crate::StreamingPipelineService for StreamingServiceImpl {
             Arc::new(registry.clone())
         };
         let global_node_cache = self.global_node_cache.clone();
+        let admission = self.executor.admission().clone();
 
         // Spawn async task to handle bidirectional streaming
         tokio::spawn(async move {
@@ -481,6 +492,9 @@ impl crate::StreamingPipelineService for StreamingServiceImpl {
                 metrics,
                 streaming_registry,
                 global_node_cache,
+                admission.clone(),
+                principal.clone(),
+                trace_context,
                 multiprocess_executor,
             )
             .await;
@@ -493,18 +507,38 @@ impl crate::StreamingPipelineService for StreamingServiceImpl {
                 metrics,
                 streaming_registry,
                 global_node_cache,
+                admission.clone(),
+                principal.clone(),
+                trace_context,
             )
             .await;
 
             if let Err(e) = result {
                 error!(error = %e, "Stream handling error");
-                let error_response = ErrorResponse {
+                let typed_error = match &e {
+                    ServiceError::Runtime(core_error) => {
+                        crate::admission_error_response(core_error)
+                    }
+                    ServiceError::Grpc(status)
+                        if status.code() == tonic::Code::PermissionDenied =>
+                    {
+                        Some(ErrorResponse {
+                            error_type: ErrorType::Authentication as i32,
+                            message: status.message().to_string(),
+                            failing_node_id: String::new(),
+                            context: String::new(),
+                            stack_trace: String::new(),
+                        })
+                    }
+                    _ => None,
+                };
+                let error_response = typed_error.unwrap_or_else(|| ErrorResponse {
                     error_type: ErrorType::Internal as i32,
                     message: e.to_string(),
                     failing_node_id: String::new(),
                     context: String::new(),
                     stack_trace: String::new(),
-                };
+                });
                 let response = StreamResponse {
                     response: Some(StreamResponseType::Error(error_response)),
                 };
@@ -518,6 +552,14 @@ impl crate::StreamingPipelineService for StreamingServiceImpl {
     }
 }
 
+/// W3C trace context from the `traceparent` request metadata, if any
+fn trace_context_from_metadata(metadata: &tonic::metadata::MetadataMap) -> Option<TraceContext> {
+    metadata
+        .get(TRACEPARENT_KEY)
+        .and_then(|v| v.to_str().ok())
+        .and_then(TraceContext::parse)
+}
+
 /// Handle bidirectional stream (runs in async task)
 ///
 /// Note: Audio chunks are now processed through the session router like data chunks,
@@ -530,10 +572,15 @@ async fn handle_stream(
     metrics: Arc<ServiceMetrics>,
     streaming_registry: Arc<StreamingNodeRegistry>,
     global_node_cache: Arc<RwLock<HashMap<String, CachedNode>>>,
+    admission: Arc<AdmissionController>,
+    principal: Principal,
+    trace_context: Option<TraceContext>,
     #[cfg(feature = "multiprocess")] multiprocess_executor: Option<Arc<MultiprocessExecutor>>,
 ) -> Result<(), ServiceError> {
     let mut session: Option<Arc<Mutex<StreamSession>>> = None;
     let mut session_id = String::new();
+    // Admission quota for the session; released when the stream ends
+    let mut _admission_permit: Option<AdmissionPermit> = None;
 
     // Main stream loop
     while let Some(request_result) = stream
@@ -551,8 +598,9 @@ async fn handle_stream(
                 }
 
                 debug!("Processing StreamInit");
-                let (new_session_id, ready) =
-                    handle_stream_init(init, &sessions).await?;
+                let (new_session_id, ready, permit) =
+                    handle_stream_init(init, &sessions, &admission, &principal).await?;
+                _admission_permit = Some(permit);
                 session_id = new_session_id.clone();
                 session = Some(sessions.read().await.get(&session_id).unwrap().clone());
 
@@ -655,6 +703,7 @@ async fn handle_stream(
                         session_id: session_id.clone(),
                         sequence: chunk.sequence,
                         sub_sequence: 0,
+                        trace_context,
                     };
 
                     // Bounded router-input: .await applies real backpressure
@@ -737,6 +786,7 @@ async fn handle_stream(
                         session_id: session_id.clone(),
                         sequence: data_chunk.sequence,
                         sub_sequence: 0,
+                        trace_context,
                     };
 
                     // Bounded router-input: .await applies real backpressure.
@@ -827,7 +877,9 @@ async fn handle_stream(
 async fn handle_stream_init(
     init: StreamInit,
     sessions: &Arc<RwLock<HashMap<String, Arc<Mutex<StreamSession>>>>>,
-) -> Result<(String, StreamReady), ServiceError> {
+    admission: &AdmissionController,
+    principal: &Principal,
+) -> Result<(String, StreamReady, AdmissionPermit), ServiceError> {
     // Validate client version (basic check)
     if init.client_version.is_empty() {
         return Err(ServiceError::Validation(
@@ -842,6 +894,14 @@ async fn handle_stream_init(
 
     let manifest = deserialize_manifest_from_proto(&manifest_proto)?;
 
+    // API keys may be restricted to certain manifests or node types
+    principal
+        .check_manifest(&manifest)
+        .map_err(|e| ServiceError::Grpc(Status::from(e)))?;
+
+    // Same quotas as sessions created through the executor
+    let permit = admission.admit(Some(&principal.tenant_id), &manifest)?;
+
     // Generate unique session ID
     let session_id = Uuid::new_v4().to_string();
 
@@ -875,7 +935,7 @@ async fn handle_stream_init(
         max_buffer_latency_ms: 100, // 100ms max buffer latency
     };
 
-    Ok((session_id, ready))
+    Ok((session_id, ready, permit))
 }
 
 /// Recursively route output data through the pipeline
@@ -1350,6 +1410,9 @@ fn deserialize_manifest_from_proto(
                     3 => "cpython_wasm",
                     _ => "auto",
                 },
+                "ports": parse_json_field(&n.ports),
+                "restart": parse_json_field(&n.restart),
+                "execution": parse_json_field(&n.execution),
                 "metadata": serde_json::json!({
                     "name": n.node_type,
                     "description": "",
@@ -1360,7 +1423,13 @@ fn deserialize_manifest_from_proto(
         "connections": proto.connections.iter().map(|c| {
             serde_json::json!({
                 "from": c.from,
-                "to": c.to
+                "to": c.to,
+                "from_port": (!c.from_port.is_empty()).then_some(&c.from_port),
+                "to_port": (!c.to_port.is_empty()).then_some(&c.to_port),
+                "filter": (!c.filter.is_empty()).then(|| {
+                    serde_json::from_str::<serde_json::Value>(&c.filter)
+                        .unwrap_or_else(|_| serde_json::Value::String(c.filter.clone()))
+                })
             })
         }).collect::<Vec<_>>()
     })
@@ -116,6 +116,9 @@ fn create_test_manifest() -> PipelineManifest {
             runtime_hint: 0,      // Auto
             input_types: vec![],  // Accept any type
             output_types: vec![], // Output any type
+            ports: String::new(),
+            restart: String::new(),
+            execution: String::new(),
         }],
         connections: vec![],
     }
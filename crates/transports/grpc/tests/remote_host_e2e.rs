@@ -0,0 +1,224 @@
+//! End-to-end test for nodes that declare a `host`.
+//!
+//! Starts two in-process gRPC servers and drives a local SessionRouter
+//! whose manifest places nodes on them. The router wraps each host node in
+//! a RemotePipelineNode that streams to its host, and every node's tag
+//! records where it ran, so the output shows the path a frame took.
+//! Each server also records the session every node was created in.
+
+use remotemedia_core::data::RuntimeData;
+use remotemedia_core::manifest::{Connection, Manifest, ManifestMetadata, NodeManifest};
+use remotemedia_core::nodes::remote_pipeline::RemotePipelineNodeFactory;
+use remotemedia_core::nodes::{
+    AsyncNodeWrapper, AsyncStreamingNode, StreamingNode, StreamingNodeFactory,
+    StreamingNodeRegistry,
+};
+use remotemedia_core::transport::plugin_registry::global_registry;
+use remotemedia_core::transport::session_router::{
+    DataPacket, SessionRouter, DEFAULT_ROUTER_OUTPUT_CAPACITY,
+};
+use remotemedia_core::transport::PipelineExecutor;
+use remotemedia_core::Error;
+use remotemedia_grpc::metrics::ServiceMetrics;
+use remotemedia_grpc::{
+    ExecutionServiceImpl, GrpcTransportPlugin, PipelineExecutionServiceServer, ServiceConfig,
+    StreamingPipelineServiceServer, StreamingServiceImpl,
+};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::sync::mpsc;
+use tonic::transport::Server;
+
+/// Prefixes text with `<place>/<node id>:`
+struct PlaceNode {
+    tag: String,
+}
+
+#[async_trait::async_trait]
+impl AsyncStreamingNode for PlaceNode {
+    fn node_type(&self) -> &str {
+        "PlaceNode"
+    }
+
+    async fn process(&self, data: RuntimeData) -> Result<RuntimeData, Error> {
+        match data {
+            RuntimeData::Text(text) => Ok(RuntimeData::Text(format!("{}:{}", self.tag, text))),
+            other => Ok(other),
+        }
+    }
+}
+
+/// Session each node was created in, by node id
+type Sessions = Arc<Mutex<HashMap<String, Option<String>>>>;
+
+/// Creates PlaceNodes tagged with the place they run
+struct PlaceNodeFactory(&'static str, Sessions);
+
+impl StreamingNodeFactory for PlaceNodeFactory {
+    fn create(
+        &self,
+        node_id: String,
+        _params: &serde_json::Value,
+        session_id: Option<String>,
+    ) -> Result<Box<dyn StreamingNode>, Error> {
+        self.1.lock().unwrap().insert(node_id.clone(), session_id);
+        Ok(Box::new(AsyncNodeWrapper(Arc::new(PlaceNode {
+            tag: format!("{}/{}", self.0, node_id),
+        }))))
+    }
+
+    fn node_type(&self) -> &str {
+        "PlaceNode"
+    }
+}
+
+/// Start a server whose PlaceNodes are tagged `place`
+async fn start_server(place: &'static str) -> (String, Sessions) {
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+
+    let executor = Arc::new(PipelineExecutor::new().unwrap());
+    let sessions = Sessions::default();
+    executor
+        .register_factory(Arc::new(PlaceNodeFactory(place, sessions.clone())))
+        .await;
+    let config = ServiceConfig::default();
+    let metrics = Arc::new(ServiceMetrics::new(prometheus::Registry::new()).unwrap());
+    let streaming = StreamingServiceImpl::new(
+        config.auth.clone(),
+        config.limits.clone(),
+        metrics.clone(),
+        executor.clone(),
+    );
+    let service = ExecutionServiceImpl::new(config.auth, config.limits, metrics, executor);
+
+    tokio::spawn(async move {
+        Server::builder()
+            .add_service(PipelineExecutionServiceServer::new(service))
+            .add_service(StreamingPipelineServiceServer::new(streaming))
+            .serve_with_incoming(tokio_stream::wrappers::TcpListenerStream::new(listener))
+            .await
+            .unwrap();
+    });
+    tokio::time::sleep(Duration::from_millis(100)).await;
+    (addr.to_string(), sessions)
+}
+
+fn place(id: &str, host: Option<&str>) -> NodeManifest {
+    NodeManifest {
+        id: id.to_string(),
+        node_type: "PlaceNode".to_string(),
+        params: serde_json::json!({}),
+        host: host.map(str::to_string),
+        ..Default::default()
+    }
+}
+
+fn chain(nodes: Vec<NodeManifest>) -> Manifest {
+    let connections = nodes
+        .windows(2)
+        .map(|pair| Connection::new(&pair[0].id, &pair[1].id))
+        .collect();
+    Manifest {
+        version: "v1".to_string(),
+        metadata: ManifestMetadata {
+            name: "remote-hosts".to_string(),
+            ..Default::default()
+        },
+        nodes,
+        connections,
+        python_env: None,
+    }
+}
+
+/// Run `inputs` through a local session of `manifest`
+async fn run(session_id: &str, manifest: Manifest, inputs: &[&str]) -> Vec<String> {
+    global_registry()
+        .register(Arc::new(GrpcTransportPlugin))
+        .ok();
+
+    let mut registry = StreamingNodeRegistry::new();
+    registry.register(Arc::new(PlaceNodeFactory("local", Sessions::default())));
+    registry.register(Arc::new(RemotePipelineNodeFactory));
+    let (output_tx, mut output_rx) = mpsc::channel(DEFAULT_ROUTER_OUTPUT_CAPACITY);
+    let (mut router, _shutdown_tx) = SessionRouter::with_config(
+        session_id.to_string(),
+        Arc::new(manifest),
+        Arc::new(registry),
+        output_tx,
+        None,
+        None,
+    )
+    .unwrap();
+    let input_tx = router.get_input_sender();
+    router.start();
+
+    let mut outputs = Vec::new();
+    for (sequence, text) in inputs.iter().enumerate() {
+        input_tx
+            .send(DataPacket {
+                data: RuntimeData::Text(text.to_string()),
+                from_node: "client".to_string(),
+                to_node: None,
+                session_id: session_id.to_string(),
+                sequence: sequence as u64,
+                sub_sequence: 0,
+                trace_context: None,
+            })
+            .await
+            .unwrap();
+        let output = tokio::time::timeout(Duration::from_secs(10), output_rx.recv())
+            .await
+            .expect("output timeout")
+            .expect("output channel closed");
+        match output {
+            RuntimeData::Text(text) => outputs.push(text),
+            other => panic!("unexpected output: {:?}", other),
+        }
+    }
+    outputs
+}
+
+#[tokio::test]
+async fn nodes_run_on_their_hosts() {
+    let (h1, h1_sessions) = start_server("h1").await;
+    let (h2, _) = start_server("h2").await;
+
+    let manifest = chain(vec![
+        place("in", None),
+        place("a", Some(&h1)),
+        place("b", Some(&h1)),
+        place("c", Some(&h2)),
+        place("out", None),
+    ]);
+    let outputs = run("remote-hosts", manifest, &["x", "y"]).await;
+
+    assert_eq!(
+        outputs,
+        [
+            "local/out:h2/c:h1/b:h1/a:local/in:x",
+            "local/out:h2/c:h1/b:h1/a:local/in:y",
+        ]
+    );
+
+    // `a` and `b` share one remote session on h1
+    let sessions = h1_sessions.lock().unwrap();
+    assert!(sessions["a"].is_some());
+    assert_eq!(sessions["a"], sessions["b"]);
+}
+
+#[tokio::test]
+async fn unreachable_endpoint_fails_over() {
+    let (h1, _) = start_server("h1").await;
+
+    // Nothing listens on port 1, so the session is opened on `h1`
+    let host = format!("127.0.0.1:1,{}", h1);
+    let manifest = chain(vec![place("a", Some(&host)), place("out", None)]);
+    let outputs = run("remote-failover", manifest, &["x", "y", "z"]).await;
+
+    assert_eq!(
+        outputs,
+        ["local/out:h1/a:x", "local/out:h1/a:y", "local/out:h1/a:z"]
+    );
+}
@@ -0,0 +1,252 @@
+//! End-to-end test for the gRPC `SessionAdmin` service.
+//!
+//! Starts a real gRPC server hosting `SessionAdmin` with admin-scoped auth,
+//! creates a session via `PipelineExecutor::create_session`, and exercises:
+//!
+//!   - ListSessions / DescribeSession / GetSessionMetrics
+//!   - KillSession (session ends, attached control clients see the reason)
+//!   - PERMISSION_DENIED for a data-plane token, NOT_FOUND for a bogus id
+
+use remotemedia_core::data::RuntimeData;
+use remotemedia_core::manifest::{Connection, Manifest, ManifestMetadata, NodeManifest};
+use remotemedia_core::transport::session_control::{CloseReason, NodeState};
+use remotemedia_core::transport::{PipelineExecutor, TransportData};
+use remotemedia_grpc::admin::SessionAdminServiceImpl;
+use remotemedia_grpc::auth::AuthConfig;
+use remotemedia_grpc::generated::{
+    session_admin_client::SessionAdminClient, session_admin_server::SessionAdminServer,
+    DescribeSessionRequest, GetSessionMetricsRequest, KillSessionRequest, ListSessionsRequest,
+    NodeState as PbNodeState,
+};
+use std::sync::Arc;
+use std::time::Duration;
+use tonic::transport::{Channel, Server};
+
+const ADMIN_TOKEN: &str = "admin-secret";
+const USER_TOKEN: &str = "user-secret";
+
+async fn start_server() -> (String, Arc<PipelineExecutor>, tokio::task::JoinHandle<()>) {
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    let url = format!("http://{}", addr);
+
+    let executor = Arc::new(PipelineExecutor::new().unwrap());
+    let auth = AuthConfig::new(vec![USER_TOKEN.to_string()], true)
+        .with_admin_tokens(vec![ADMIN_TOKEN.to_string()]);
+    let admin = SessionAdminServiceImpl::new(auth, executor.control_bus());
+
+    let srv = Server::builder()
+        .add_service(SessionAdminServer::new(admin))
+        .serve_with_incoming(tokio_stream::wrappers::TcpListenerStream::new(listener));
+
+    let handle = tokio::spawn(async move {
+        let _ = srv.await;
+    });
+    tokio::time::sleep(Duration::from_millis(100)).await;
+    (url, executor, handle)
+}
+
+fn calc_manifest() -> Manifest {
+    Manifest {
+        version: "v1".to_string(),
+        metadata: ManifestMetadata {
+            name: "admin-grpc-e2e".to_string(),
+            ..Default::default()
+        },
+        nodes: vec![NodeManifest {
+            id: "calc".to_string(),
+            node_type: "CalculatorNode".to_string(),
+            params: serde_json::json!({}),
+            ..Default::default()
+        }],
+        connections: Vec::<Connection>::new(),
+        python_env: None,
+    }
+}
+
+async fn connect(url: &str) -> SessionAdminClient<Channel> {
+    let channel = tonic::transport::Endpoint::from_shared(url.to_string())
+        .unwrap()
+        .connect()
+        .await
+        .unwrap();
+    SessionAdminClient::new(channel)
+}
+
+fn authed<T>(message: T, token: &str) -> tonic::Request<T> {
+    let mut request = tonic::Request::new(message);
+    request.metadata_mut().insert(
+        "authorization",
+        format!("Bearer {}", token).parse().unwrap(),
+    );
+    request
+}
+
+#[tokio::test]
+async fn admin_lists_describes_and_reports_metrics() {
+    let (url, executor, _srv) = start_server().await;
+    let mut session = executor
+        .create_session(Arc::new(calc_manifest()))
+        .await
+        .unwrap();
+    let session_id = session.session_id.clone();
+
+    let input = serde_json::json!({ "operation": "add", "operands": [2.0, 3.0] });
+    session
+        .send_input(TransportData::new(RuntimeData::Json(input)))
+        .await
+        .unwrap();
+    tokio::time::timeout(Duration::from_secs(5), session.recv_output())
+        .await
+        .expect("output timeout")
+        .unwrap();
+
+    executor
+        .control_bus()
+        .get(&session_id)
+        .unwrap()
+        .set_node_state("calc", NodeState::Bypass);
+
+    let mut client = connect(&url).await;
+
+    let list = client
+        .list_sessions(authed(ListSessionsRequest {}, ADMIN_TOKEN))
+        .await
+        .unwrap()
+        .into_inner();
+    let summary = list
+        .sessions
+        .iter()
+        .find(|s| s.session_id == session_id)
+        .expect("session listed");
+    assert_eq!(summary.pipeline_name, "admin-grpc-e2e");
+    assert_eq!(summary.node_count, 1);
+    assert!(summary.started_at_ms > 0);
+
+    let desc = client
+        .describe_session(authed(
+            DescribeSessionRequest {
+                session_id: session_id.clone(),
+            },
+            ADMIN_TOKEN,
+        ))
+        .await
+        .unwrap()
+        .into_inner();
+    assert_eq!(desc.nodes.len(), 1);
+    assert_eq!(desc.nodes[0].node_id, "calc");
+    assert_eq!(desc.nodes[0].node_type, "CalculatorNode");
+    assert_eq!(desc.nodes[0].state, PbNodeState::Bypass as i32);
+    let manifest: Manifest = serde_json::from_str(&desc.manifest_json).unwrap();
+    assert_eq!(manifest.nodes[0].id, "calc");
+
+    let metrics = client
+        .get_session_metrics(authed(
+            GetSessionMetricsRequest {
+                session_id: session_id.clone(),
+            },
+            ADMIN_TOKEN,
+        ))
+        .await
+        .unwrap()
+        .into_inner();
+    assert_eq!(metrics.session_id, session_id);
+    for stats in metrics.node_stats.values() {
+        assert!(stats.error_count <= stats.execution_count);
+    }
+
+    let _ = session.close().await;
+}
+
+#[tokio::test]
+async fn admin_kill_terminates_session() {
+    let (url, executor, _srv) = start_server().await;
+    let mut session = executor
+        .create_session(Arc::new(calc_manifest()))
+        .await
+        .unwrap();
+    let session_id = session.session_id.clone();
+    let mut close_rx = executor
+        .control_bus()
+        .get(&session_id)
+        .unwrap()
+        .close_subscriber();
+
+    let mut client = connect(&url).await;
+    let killed = client
+        .kill_session(authed(
+            KillSessionRequest {
+                session_id: session_id.clone(),
+                reason: "runaway session".to_string(),
+            },
+            ADMIN_TOKEN,
+        ))
+        .await
+        .unwrap()
+        .into_inner();
+    assert!(killed.terminated);
+
+    let reason = tokio::time::timeout(Duration::from_secs(5), close_rx.recv())
+        .await
+        .expect("close timeout")
+        .expect("close channel lagged");
+    match reason {
+        CloseReason::Error(msg) => assert!(msg.contains("runaway session"), "{}", msg),
+        other => panic!("unexpected close reason: {:?}", other),
+    }
+
+    // The client side drains and ends.
+    let end = tokio::time::timeout(Duration::from_secs(5), session.recv_output())
+        .await
+        .expect("session did not end");
+    assert!(end.unwrap().is_none());
+
+    // Once the router task unregisters the session, it is gone.
+    let mut gone = false;
+    for _ in 0..50 {
+        let status = client
+            .describe_session(authed(
+                DescribeSessionRequest {
+                    session_id: session_id.clone(),
+                },
+                ADMIN_TOKEN,
+            ))
+            .await;
+        if matches!(status, Err(ref s) if s.code() == tonic::Code::NotFound) {
+            gone = true;
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(20)).await;
+    }
+    assert!(gone, "killed session still registered");
+}
+
+#[tokio::test]
+async fn admin_requires_admin_scope() {
+    let (url, _executor, _srv) = start_server().await;
+    let mut client = connect(&url).await;
+
+    let err = client
+        .list_sessions(authed(ListSessionsRequest {}, USER_TOKEN))
+        .await
+        .unwrap_err();
+    assert_eq!(err.code(), tonic::Code::PermissionDenied);
+
+    let err = client
+        .list_sessions(tonic::Request::new(ListSessionsRequest {}))
+        .await
+        .unwrap_err();
+    assert_eq!(err.code(), tonic::Code::Unauthenticated);
+
+    let err = client
+        .kill_session(authed(
+            KillSessionRequest {
+                session_id: "no-such-session".to_string(),
+                reason: String::new(),
+            },
+            ADMIN_TOKEN,
+        ))
+        .await
+        .unwrap_err();
+    assert_eq!(err.code(), tonic::Code::NotFound);
+}
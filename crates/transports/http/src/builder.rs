@@ -1,6 +1,7 @@
 //! Builder pattern for constructing and running an HTTP transport server.
 
 use crate::server::HttpServer;
+use remotemedia_core::auth::AuthGuard;
 use remotemedia_core::transport::PipelineExecutor;
 use std::sync::Arc;
 
@@ -23,6 +24,7 @@ use std::sync::Arc;
 pub struct HttpServerBuilder {
     bind_address: Option<String>,
     executor: Option<Arc<PipelineExecutor>>,
+    auth: Option<AuthGuard>,
 }
 
 impl HttpServerBuilder {
@@ -31,10 +33,12 @@ impl HttpServerBuilder {
     /// Defaults:
     /// - `bind_address`: `"127.0.0.1:8080"`
     /// - `executor`: `None` (must be provided before calling `build`)
+    /// - `auth`: API keys from the `REMOTEMEDIA_AUTH_*` environment variables
     pub fn new() -> Self {
         Self {
             bind_address: None,
             executor: None,
+            auth: None,
         }
     }
 
@@ -52,6 +56,12 @@ impl HttpServerBuilder {
         self
     }
 
+    /// Authenticate requests with `auth` instead of the environment.
+    pub fn auth(mut self, auth: AuthGuard) -> Self {
+        self.auth = Some(auth);
+        self
+    }
+
     /// Read configuration from environment variables.
     ///
     /// Currently reads:
@@ -79,7 +89,10 @@ impl HttpServerBuilder {
             .bind_address
             .unwrap_or_else(|| "127.0.0.1:8080".to_string());
 
-        let server = HttpServer::new(bind_address, executor).await?;
+        let mut server = HttpServer::new(bind_address, executor).await?;
+        if let Some(auth) = self.auth {
+            server = server.with_auth(auth);
+        }
 
         Ok(HttpTransportServer { server })
     }
@@ -7,18 +7,33 @@
 //! - GET /stream/:id/output - Receive outputs via SSE
 //! - DELETE /stream/:id - Close session
 //! - GET /health - Health check
+//! - GET /hls/:stream/:file - In-memory HLS streams published by `HlsSinkNode`
+//!
+//! HLS playlists honour the LL-HLS `_HLS_msn` / `_HLS_part` query
+//! parameters and block until the requested segment or part exists.
+//!
+//! Stream input accepts a W3C `traceparent` header; the pipeline's node
+//! spans join that trace.
+//!
+//! Pipeline and stream endpoints authenticate `Authorization: Bearer <key>`
+//! through the core [`AuthGuard`] (open unless API keys are configured).
+//! Streaming sessions belong to the key's tenant; other tenants get `404`.
 
 use crate::error::{Error, Result};
 use async_trait::async_trait;
 use axum::{
-    extract::{Path, State},
-    http::StatusCode,
+    extract::{Path, Query, State},
+    http::{header, HeaderMap, StatusCode},
     response::sse::{Event, KeepAlive, Sse},
+    response::{IntoResponse, Response},
     routing::{delete, get, post},
     Json, Router,
 };
 use futures::stream::Stream;
+use remotemedia_core::auth::{self, AuthError, AuthGuard, Principal, Scope};
 use remotemedia_core::manifest::Manifest;
+use remotemedia_core::nodes::hls::{self, playlist::PLAYLIST};
+use remotemedia_core::telemetry::{TraceContext, TRACEPARENT_KEY};
 use remotemedia_core::transport::{
     PipelineExecutor, PipelineTransport, StreamSession, TransportData,
 };
@@ -26,6 +41,7 @@ use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::convert::Infallible;
 use std::sync::Arc;
+use std::time::Duration;
 use tokio::sync::{broadcast, RwLock};
 use tokio_stream::wrappers::BroadcastStream;
 use tokio_stream::StreamExt as _;
@@ -37,6 +53,8 @@ struct ServerState {
     executor: Arc<PipelineExecutor>,
     /// Active streaming sessions
     sessions: Arc<RwLock<HashMap<String, SessionHandle>>>,
+    /// API key authentication
+    auth: AuthGuard,
 }
 
 /// Handle to a streaming session
@@ -44,12 +62,21 @@ struct ServerState {
 struct SessionHandle {
     /// Session ID
     session_id: String,
+    /// Tenant of the key that created the session
+    tenant_id: String,
     /// Stream session from executor (spec 026 migration)
     session: SessionHandleWrapper,
     /// Broadcast channel for sending outputs to multiple SSE subscribers
     output_tx: broadcast::Sender<TransportData>,
 }
 
+impl SessionHandle {
+    /// Other tenants' sessions look like they do not exist
+    fn visible_to(&self, principal: &Principal) -> bool {
+        principal.has_scope(Scope::Admin) || self.tenant_id == principal.tenant_id
+    }
+}
+
 /// HTTP server with SSE streaming support
 pub struct HttpServer {
     /// Server bind address
@@ -70,10 +97,17 @@ impl HttpServer {
     ///
     /// * `Ok(HttpServer)` - Server created successfully
     /// * `Err(Error)` - Failed to create server
+    ///
+    /// API keys are read from the `REMOTEMEDIA_AUTH_*` environment
+    /// variables (see [`AuthGuard::from_env`]); use [`Self::with_auth`] to
+    /// configure them explicitly.
     pub async fn new(bind_address: String, executor: Arc<PipelineExecutor>) -> Result<Self> {
+        let auth = AuthGuard::from_env()
+            .map_err(|e| Error::ServerError(format!("Invalid API key configuration: {}", e)))?;
         let state = ServerState {
             executor,
             sessions: Arc::new(RwLock::new(HashMap::new())),
+            auth,
         };
 
         Ok(Self {
@@ -82,6 +116,12 @@ impl HttpServer {
         })
     }
 
+    /// Authenticate requests with `auth` instead of the environment
+    pub fn with_auth(mut self, auth: AuthGuard) -> Self {
+        self.state.auth = auth;
+        self
+    }
+
     /// Build the router with all endpoints
     fn build_router(&self) -> Router {
         Router::new()
@@ -91,6 +131,7 @@ impl HttpServer {
             .route("/stream/:session_id/input", post(stream_input_handler))
             .route("/stream/:session_id/output", get(stream_output_handler))
             .route("/stream/:session_id", delete(close_stream_handler))
+            .route("/hls/:stream/:file", get(hls_handler))
             .with_state(self.state.clone())
             .layer(
                 tower::ServiceBuilder::new()
@@ -202,6 +243,9 @@ struct ErrorResponse {
     /// Structured validation errors (only for validation errors)
     #[serde(skip_serializing_if = "Option::is_none")]
     validation_errors: Option<serde_json::Value>,
+    /// Suggested wait before retrying (only for admission rejections)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    retry_after_ms: Option<u64>,
 }
 
 /// Map runtime errors to appropriate HTTP status codes and structured responses
@@ -218,6 +262,7 @@ fn map_runtime_error(e: remotemedia_core::Error) -> (StatusCode, Json<ErrorRespo
                         validation_errors.len()
                     ),
                     validation_errors: errors_json,
+                    retry_after_ms: None,
                 }),
             )
         }
@@ -228,6 +273,7 @@ fn map_runtime_error(e: remotemedia_core::Error) -> (StatusCode, Json<ErrorRespo
                 error_type: "manifest".to_string(),
                 message: msg,
                 validation_errors: None,
+                retry_after_ms: None,
             }),
         ),
         remotemedia_core::Error::InvalidData(msg)
@@ -237,6 +283,16 @@ fn map_runtime_error(e: remotemedia_core::Error) -> (StatusCode, Json<ErrorRespo
                 error_type: "input".to_string(),
                 message: msg,
                 validation_errors: None,
+                retry_after_ms: None,
+            }),
+        ),
+        remotemedia_core::Error::AdmissionRejected { retry_after_ms, .. } => (
+            StatusCode::TOO_MANY_REQUESTS,
+            Json(ErrorResponse {
+                error_type: "admission".to_string(),
+                message: e.to_string(),
+                validation_errors: None,
+                retry_after_ms: Some(retry_after_ms),
             }),
         ),
         _ => (
@@ -245,16 +301,54 @@ fn map_runtime_error(e: remotemedia_core::Error) -> (StatusCode, Json<ErrorRespo
                 error_type: "execution".to_string(),
                 message: e.to_string(),
                 validation_errors: None,
+                retry_after_ms: None,
             }),
         ),
     }
 }
 
+/// Map authentication failures to `401`, `403` or `429`
+fn map_auth_error(e: AuthError) -> (StatusCode, Json<ErrorResponse>) {
+    (
+        StatusCode::from_u16(e.http_status()).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR),
+        Json(ErrorResponse {
+            error_type: e.error_type().to_string(),
+            message: e.to_string(),
+            validation_errors: None,
+            retry_after_ms: e.retry_after_ms(),
+        }),
+    )
+}
+
+/// Authenticate the request's bearer key and require `scope`
+fn authorize(
+    state: &ServerState,
+    headers: &HeaderMap,
+    scope: Scope,
+) -> std::result::Result<Principal, (StatusCode, Json<ErrorResponse>)> {
+    let token = headers
+        .get(header::AUTHORIZATION)
+        .and_then(|v| v.to_str().ok())
+        .and_then(auth::bearer_token);
+    state.auth.authorize(token, scope).map_err(map_auth_error)
+}
+
+/// [`authorize`] for the session endpoints, which answer with plain text
+fn authorize_session(
+    state: &ServerState,
+    headers: &HeaderMap,
+) -> std::result::Result<Principal, (StatusCode, String)> {
+    authorize(state, headers, Scope::Stream).map_err(|(status, Json(body))| (status, body.message))
+}
+
 /// POST /execute - Unary pipeline execution
 async fn execute_handler(
     State(state): State<ServerState>,
+    headers: HeaderMap,
     Json(request): Json<ExecuteRequest>,
 ) -> std::result::Result<Json<ExecuteResponse>, (StatusCode, Json<ErrorResponse>)> {
+    let principal = authorize(&state, &headers, Scope::Execute)?;
+
     // Parse manifest
     let manifest: Manifest = serde_json::from_value(request.manifest).map_err(|e| {
         (
@@ -263,14 +357,23 @@ async fn execute_handler(
                 error_type: "manifest".to_string(),
                 message: format!("Invalid manifest: {}", e),
                 validation_errors: None,
+                retry_after_ms: None,
             }),
         )
     })?;
 
+    principal
+        .check_manifest(&manifest)
+        .map_err(map_auth_error)?;
+
     // Execute pipeline - uses map_runtime_error for proper validation error handling
     let output = state
         .executor
-        .execute_unary(Arc::new(manifest), request.input)
+        .execute_unary_as(
+            Arc::new(manifest),
+            request.input,
+            Some(&principal.tenant_id),
+        )
         .await
         .map_err(map_runtime_error)?;
 
@@ -292,8 +395,11 @@ struct CreateStreamResponse {
 /// POST /stream - Create a streaming session
 async fn create_stream_handler(
     State(state): State<ServerState>,
+    headers: HeaderMap,
     Json(request): Json<CreateStreamRequest>,
 ) -> std::result::Result<Json<CreateStreamResponse>, (StatusCode, Json<ErrorResponse>)> {
+    let principal = authorize(&state, &headers, Scope::Stream)?;
+
     // Parse manifest
     let manifest: Manifest = serde_json::from_value(request.manifest).map_err(|e| {
         (
@@ -302,14 +408,19 @@ async fn create_stream_handler(
                 error_type: "manifest".to_string(),
                 message: format!("Invalid manifest: {}", e),
                 validation_errors: None,
+                retry_after_ms: None,
             }),
         )
     })?;
 
+    principal
+        .check_manifest(&manifest)
+        .map_err(map_auth_error)?;
+
     // Create stream session - uses map_runtime_error for proper validation error handling
     let session = state
         .executor
-        .create_session(Arc::new(manifest))
+        .create_session_as(Arc::new(manifest), Some(&principal.tenant_id))
         .await
         .map_err(map_runtime_error)?;
 
@@ -322,6 +433,7 @@ async fn create_stream_handler(
     // Store session (wrap in SessionHandleWrapper for trait compatibility)
     let handle = SessionHandle {
         session_id: session_id.clone(),
+        tenant_id: principal.tenant_id,
         session: SessionHandleWrapper(session),
         output_tx,
     };
@@ -347,19 +459,36 @@ struct StreamInputRequest {
 async fn stream_input_handler(
     State(state): State<ServerState>,
     Path(session_id): Path<String>,
+    headers: HeaderMap,
     Json(request): Json<StreamInputRequest>,
 ) -> std::result::Result<StatusCode, (StatusCode, String)> {
+    let principal = authorize_session(&state, &headers)?;
     let mut sessions = state.sessions.write().await;
 
-    let handle = sessions.get_mut(&session_id).ok_or_else(|| {
-        (
-            StatusCode::NOT_FOUND,
-            format!("Session not found: {}", session_id),
-        )
-    })?;
+    let handle = sessions
+        .get_mut(&session_id)
+        .filter(|h| h.visible_to(&principal))
+        .ok_or_else(|| {
+            (
+                StatusCode::NOT_FOUND,
+                format!("Session not found: {}", session_id),
+            )
+        })?;
+
+    // A `traceparent` header applies unless the body already carries one
+    let mut data = request.data;
+    if data.trace_context().is_none() {
+        let header_ctx = headers
+            .get(TRACEPARENT_KEY)
+            .and_then(|v| v.to_str().ok())
+            .and_then(TraceContext::parse);
+        if let Some(ctx) = header_ctx {
+            data = data.with_trace_context(&ctx);
+        }
+    }
 
     // Send input to session
-    handle.session.send_input(request.data).await.map_err(|e| {
+    handle.session.send_input(data).await.map_err(|e| {
         (
             StatusCode::INTERNAL_SERVER_ERROR,
             format!("Failed to send input: {}", e),
@@ -379,19 +508,25 @@ async fn stream_input_handler(
 async fn stream_output_handler(
     State(state): State<ServerState>,
     Path(session_id): Path<String>,
+    headers: HeaderMap,
 ) -> std::result::Result<
     Sse<impl Stream<Item = std::result::Result<Event, Infallible>>>,
     (StatusCode, String),
 > {
+    let principal = authorize_session(&state, &headers)?;
+
     // Get session and subscribe to broadcast channel
     let rx = {
         let sessions = state.sessions.read().await;
-        let handle = sessions.get(&session_id).ok_or_else(|| {
-            (
-                StatusCode::NOT_FOUND,
-                format!("Session not found: {}", session_id),
-            )
-        })?;
+        let handle = sessions
+            .get(&session_id)
+            .filter(|h| h.visible_to(&principal))
+            .ok_or_else(|| {
+                (
+                    StatusCode::NOT_FOUND,
+                    format!("Session not found: {}", session_id),
+                )
+            })?;
 
         // Subscribe to broadcast channel (supports multiple SSE connections)
         handle.output_tx.subscribe()
@@ -426,15 +561,23 @@ async fn stream_output_handler(
 async fn close_stream_handler(
     State(state): State<ServerState>,
     Path(session_id): Path<String>,
+    headers: HeaderMap,
 ) -> std::result::Result<StatusCode, (StatusCode, String)> {
+    let principal = authorize_session(&state, &headers)?;
     let mut sessions = state.sessions.write().await;
 
-    let mut handle = sessions.remove(&session_id).ok_or_else(|| {
-        (
-            StatusCode::NOT_FOUND,
-            format!("Session not found: {}", session_id),
-        )
-    })?;
+    let visible = sessions
+        .get(&session_id)
+        .is_some_and(|h| h.visible_to(&principal));
+    let mut handle = visible
+        .then(|| sessions.remove(&session_id))
+        .flatten()
+        .ok_or_else(|| {
+            (
+                StatusCode::NOT_FOUND,
+                format!("Session not found: {}", session_id),
+            )
+        })?;
 
     // Close session
     handle.session.close().await.map_err(|e| {
@@ -449,6 +592,91 @@ async fn close_stream_handler(
     Ok(StatusCode::OK)
 }
 
+/// LL-HLS blocking playlist reload parameters
+#[derive(Debug, Default, Deserialize)]
+struct HlsQuery {
+    #[serde(rename = "_HLS_msn")]
+    msn: Option<u64>,
+    #[serde(rename = "_HLS_part")]
+    part: Option<u32>,
+}
+
+/// GET /hls/:stream/:file - Serve an in-memory HLS stream
+async fn hls_handler(
+    Path((stream_name, file)): Path<(String, String)>,
+    Query(query): Query<HlsQuery>,
+) -> Response {
+    let Some(stream) = hls::streams().get(&stream_name) else {
+        return (
+            StatusCode::NOT_FOUND,
+            format!("HLS stream not found: {}", stream_name),
+        )
+            .into_response();
+    };
+    // LL-HLS asks servers to give up after three target durations
+    let timeout = Duration::from_secs(3 * stream.target_duration().unwrap_or(2));
+
+    if file == PLAYLIST {
+        if query.part.is_some() && query.msn.is_none() {
+            return (StatusCode::BAD_REQUEST, "_HLS_part requires _HLS_msn").into_response();
+        }
+        if let Some(msn) = query.msn {
+            if !stream
+                .wait_until(timeout, |s| s.contains(msn, query.part))
+                .await
+                && !stream.has_ended()
+            {
+                return (
+                    StatusCode::SERVICE_UNAVAILABLE,
+                    "Requested segment did not become available",
+                )
+                    .into_response();
+            }
+        }
+        return match stream.playlist() {
+            Some(playlist) => (
+                [
+                    (header::CONTENT_TYPE, "application/vnd.apple.mpegurl"),
+                    (header::CACHE_CONTROL, "no-cache"),
+                ],
+                playlist,
+            )
+                .into_response(),
+            None => (StatusCode::NOT_FOUND, "HLS stream has not started").into_response(),
+        };
+    }
+
+    let content_type = if file.ends_with(".m4s") {
+        "video/iso.segment"
+    } else if file.ends_with(".mp4") {
+        "video/mp4"
+    } else {
+        return (StatusCode::NOT_FOUND, format!("Unknown HLS file: {}", file)).into_response();
+    };
+    // A preload hint names a part before it exists: hold the request
+    // until the part is written
+    if stream.file(&file).is_none() {
+        stream
+            .wait_until(timeout, |s| s.file(&file).is_some())
+            .await;
+    }
+    match stream.file(&file) {
+        Some(data) => (
+            [
+                (header::CONTENT_TYPE, content_type),
+                (header::CACHE_CONTROL, "max-age=3600"),
+            ],
+            data.to_vec(),
+        )
+            .into_response(),
+        None => (
+            StatusCode::NOT_FOUND,
+            format!("HLS file not found: {}", file),
+        )
+            .into_response(),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -458,4 +686,58 @@ mod tests {
         let response = health_handler().await;
         assert_eq!(response, StatusCode::OK);
     }
+
+    #[tokio::test]
+    async fn test_hls_unknown_stream() {
+        let response = hls_handler(
+            Path(("no-such-stream".to_string(), PLAYLIST.to_string())),
+            Query(HlsQuery::default()),
+        )
+        .await;
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+
+    #[test]
+    fn test_admission_rejection_maps_to_429() {
+        let (status, Json(body)) = map_runtime_error(remotemedia_core::Error::AdmissionRejected {
+            resource: "node_type:WhisperNode".to_string(),
+            message: "2 sessions using 'WhisperNode' already running (limit 2)".to_string(),
+            retry_after_ms: 5_000,
+        });
+        assert_eq!(status, StatusCode::TOO_MANY_REQUESTS);
+        assert_eq!(body.error_type, "admission");
+        assert_eq!(body.retry_after_ms, Some(5_000));
+        assert!(body.message.contains("node_type:WhisperNode"));
+    }
+
+    #[tokio::test]
+    async fn test_api_key_errors() {
+        use remotemedia_core::auth::StaticTokenAuthenticator;
+
+        let keys = StaticTokenAuthenticator::new().with_token(
+            "viewer-key",
+            Principal::new("viewer", "acme").with_scopes(vec![Scope::Control]),
+        );
+        let executor = Arc::new(PipelineExecutor::new().unwrap());
+        let server = HttpServer::new("127.0.0.1:0".to_string(), executor)
+            .await
+            .unwrap()
+            .with_auth(AuthGuard::open().with_authenticator(Arc::new(keys)));
+
+        let mut headers = HeaderMap::new();
+        let (status, _) = authorize(&server.state, &headers, Scope::Execute).unwrap_err();
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+
+        headers.insert(header::AUTHORIZATION, "Bearer viewer-key".parse().unwrap());
+        let (status, Json(body)) = authorize(&server.state, &headers, Scope::Execute).unwrap_err();
+        assert_eq!(status, StatusCode::FORBIDDEN);
+        assert_eq!(body.error_type, "forbidden");
+
+        let (status, Json(body)) = map_auth_error(AuthError::RateLimited {
+            key_id: "viewer".to_string(),
+            retry_after_ms: 250,
+        });
+        assert_eq!(status, StatusCode::TOO_MANY_REQUESTS);
+        assert_eq!(body.retry_after_ms, Some(250));
+    }
 }
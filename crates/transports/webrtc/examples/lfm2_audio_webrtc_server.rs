@@ -292,30 +292,37 @@ fn build_manifest() -> Manifest {
             Connection {
                 from: "resample_in".to_string(),
                 to: "chunker".to_string(),
+                ..Default::default()
             },
             Connection {
                 from: "chunker".to_string(),
                 to: "vad".to_string(),
+                ..Default::default()
             },
             Connection {
                 from: "vad".to_string(),
                 to: "accumulator".to_string(),
+                ..Default::default()
             },
             Connection {
                 from: "accumulator".to_string(),
                 to: "resample_up".to_string(),
+                ..Default::default()
             },
             Connection {
                 from: "resample_up".to_string(),
                 to: "audio".to_string(),
+                ..Default::default()
             },
             Connection {
                 from: "accumulator".to_string(),
                 to: "stt_in".to_string(),
+                ..Default::default()
             },
             Connection {
                 from: "audio".to_string(),
                 to: "resample_out".to_string(),
+                ..Default::default()
             },
         ],
         // liquid-audio requires Python 3.12+. Pin the managed-venv
@@ -295,31 +295,38 @@ inside say(); no markdown.";
         Connection {
             from: "resample_in".to_string(),
             to: "chunker".to_string(),
+            ..Default::default()
         },
         Connection {
             from: "chunker".to_string(),
             to: "vad".to_string(),
+            ..Default::default()
         },
         Connection {
             from: "vad".to_string(),
             to: "accumulator".to_string(),
+            ..Default::default()
         },
         // Coordinator watches VAD events for barge detection.
         Connection {
             from: "vad".to_string(),
             to: "coordinator".to_string(),
+            ..Default::default()
         },
         Connection {
             from: "accumulator".to_string(),
             to: "stt_in".to_string(),
+            ..Default::default()
         },
         Connection {
             from: "stt_in".to_string(),
             to: "llm".to_string(),
+            ..Default::default()
         },
         Connection {
             from: "llm".to_string(),
             to: "coordinator".to_string(),
+            ..Default::default()
         },
     ];
 
@@ -331,7 +338,7 @@ inside say(); no markdown.";
         } else {
             c.from
         };
-        connections.push(Connection { from, to: c.to });
+        connections.push(Connection { from, to: c.to, ..Default::default() });
     }
 
     Manifest {
@@ -392,6 +399,7 @@ fn build_tts_stage(
                 vec![Connection {
                     from: "llm".to_string(),
                     to: "audio".to_string(),
+                    ..Default::default()
                 }],
             )
         }
@@ -540,10 +548,12 @@ fn build_tts_stage(
                     Connection {
                         from: "llm".to_string(),
                         to: "kokoro_tts".to_string(),
+                        ..Default::default()
                     },
                     Connection {
                         from: "kokoro_tts".to_string(),
                         to: "audio".to_string(),
+                        ..Default::default()
                     },
                 ],
             )
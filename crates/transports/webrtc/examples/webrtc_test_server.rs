@@ -98,14 +98,17 @@ fn build_manifest() -> Manifest {
             Connection {
                 from: "resample_in".to_string(),
                 to: "chunker".to_string(),
+                ..Default::default()
             },
             Connection {
                 from: "chunker".to_string(),
                 to: "vad".to_string(),
+                ..Default::default()
             },
             Connection {
                 from: "vad".to_string(),
                 to: "accumulator".to_string(),
+                ..Default::default()
             },
         ],
         python_env: None,
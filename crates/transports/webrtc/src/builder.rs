@@ -170,7 +170,11 @@ impl WebRtcTransportHandle {
 mod grpc_builder {
     use super::*;
     use crate::signaling::grpc::WebRtcSignalingService;
-    use remotemedia_core::{manifest::Manifest, transport::PipelineExecutor};
+    use remotemedia_core::{
+        auth::AuthGuard,
+        manifest::{Manifest, ManifestLoader},
+        transport::PipelineExecutor,
+    };
     use std::sync::Arc;
 
     /// Builder for creating a gRPC-based WebRTC signaling server.
@@ -207,6 +211,12 @@ mod grpc_builder {
         max_peers: Option<u32>,
         enable_data_channel: Option<bool>,
         jitter_buffer_ms: Option<u32>,
+        recording_dir: Option<std::path::PathBuf>,
+        auth: AuthGuard,
+        #[cfg(feature = "whip")]
+        whip_bind_address: Option<String>,
+        #[cfg(feature = "whip")]
+        whip_bearer_token: Option<String>,
     }
 
     impl WebRtcSignalingServerBuilder {
@@ -220,6 +230,12 @@ mod grpc_builder {
                 max_peers: None,
                 enable_data_channel: None,
                 jitter_buffer_ms: None,
+                recording_dir: None,
+                auth: AuthGuard::open(),
+                #[cfg(feature = "whip")]
+                whip_bind_address: None,
+                #[cfg(feature = "whip")]
+                whip_bearer_token: None,
             }
         }
 
@@ -250,19 +266,26 @@ mod grpc_builder {
         /// Load a manifest from a file on disk (JSON or YAML).
         ///
         /// Detects format by file extension (`.yaml`/`.yml` for YAML, otherwise JSON).
+        /// `${ENV}` references, template arguments and includes are expanded
+        /// (see [`remotemedia_core::manifest::template`]).
         /// Replaces any previously set manifest.
         pub fn manifest_from_file(
             mut self,
             path: impl AsRef<std::path::Path>,
         ) -> std::result::Result<Self, Box<dyn std::error::Error>> {
-            let path = path.as_ref();
-            let contents = std::fs::read_to_string(path)?;
-            let manifest: Manifest = match path.extension().and_then(|e| e.to_str()) {
-                Some("yaml" | "yml") => serde_json::from_value(
-                    serde_yaml::from_str::<serde_json::Value>(&contents)?,
-                )?,
-                _ => serde_json::from_str(&contents)?,
-            };
+            self.manifest_from_loader(&ManifestLoader::new(), path)
+        }
+
+        /// Load a templated manifest file, supplying template arguments
+        /// (and any `${ENV}` override) through `loader`.
+        ///
+        /// Replaces any previously set manifest.
+        pub fn manifest_from_loader(
+            mut self,
+            loader: &ManifestLoader,
+            path: impl AsRef<std::path::Path>,
+        ) -> std::result::Result<Self, Box<dyn std::error::Error>> {
+            let manifest = loader.load_file(path)?;
             self.manifest = Some(Arc::new(manifest));
             Ok(self)
         }
@@ -299,6 +322,40 @@ mod grpc_builder {
             self
         }
 
+        /// Let peers record their sessions to WebM files in `dir`.
+        ///
+        /// Recording is disabled unless set.
+        pub fn recording_dir(mut self, dir: impl Into<std::path::PathBuf>) -> Self {
+            self.recording_dir = Some(dir.into());
+            self
+        }
+
+        /// Require API keys on signaling (and WHIP/WHEP) requests.
+        ///
+        /// Open unless set. A key's tenant owns the sessions it creates.
+        pub fn auth(mut self, auth: AuthGuard) -> Self {
+            self.auth = auth;
+            self
+        }
+
+        /// Also serve WHIP ingest / WHEP playback over HTTP on this address.
+        ///
+        /// Disabled unless set. Requires the `whip` feature.
+        #[cfg(feature = "whip")]
+        pub fn whip_bind(mut self, addr: impl Into<String>) -> Self {
+            self.whip_bind_address = Some(addr.into());
+            self
+        }
+
+        /// Require `Authorization: Bearer <token>` on WHIP/WHEP requests.
+        ///
+        /// The token is accepted alongside any [`auth`](Self::auth) keys.
+        #[cfg(feature = "whip")]
+        pub fn whip_bearer_token(mut self, token: impl Into<String>) -> Self {
+            self.whip_bearer_token = Some(token.into());
+            self
+        }
+
         /// Build and validate the server configuration.
         ///
         /// Returns an error if `executor` or `manifest` has not been set, or
@@ -328,15 +385,37 @@ mod grpc_builder {
             if let Some(jb) = self.jitter_buffer_ms {
                 config.jitter_buffer_size_ms = jb;
             }
+            config.recording_dir = self.recording_dir;
 
             config.validate()?;
+            let config = Arc::new(config);
+
+            #[cfg(feature = "whip")]
+            let whip = match self.whip_bind_address {
+                Some(addr) => {
+                    let addr: std::net::SocketAddr = addr.parse()?;
+                    let mut server = crate::signaling::WhipServer::new(
+                        Arc::clone(&config),
+                        Arc::clone(&executor),
+                        Arc::clone(&manifest),
+                    )
+                    .with_auth(self.auth.clone());
+                    if let Some(token) = self.whip_bearer_token {
+                        server = server.with_bearer_token(token);
+                    }
+                    Some((addr, server))
+                }
+                None => None,
+            };
 
             let service =
-                WebRtcSignalingService::new(Arc::new(config), executor, manifest);
+                WebRtcSignalingService::new(config, executor, manifest).with_auth(self.auth);
 
             Ok(WebRtcSignalingServer {
                 bind_address,
                 service,
+                #[cfg(feature = "whip")]
+                whip,
             })
         }
     }
@@ -357,6 +436,8 @@ mod grpc_builder {
     pub struct WebRtcSignalingServer {
         bind_address: String,
         service: WebRtcSignalingService,
+        #[cfg(feature = "whip")]
+        whip: Option<(std::net::SocketAddr, crate::signaling::WhipServer)>,
     }
 
     impl WebRtcSignalingServer {
@@ -366,6 +447,16 @@ mod grpc_builder {
 
             tracing::info!("WebRTC signaling server listening on {}", addr);
 
+            // WHIP/WHEP runs alongside gRPC and stops with it.
+            #[cfg(feature = "whip")]
+            let whip_task = self.whip.map(|(whip_addr, whip)| {
+                tokio::spawn(async move {
+                    if let Err(e) = whip.serve(whip_addr).await {
+                        tracing::error!("WHIP/WHEP server on {} failed: {}", whip_addr, e);
+                    }
+                })
+            });
+
             let grpc_service = self.service.into_server();
 
             tonic::transport::Server::builder()
@@ -379,6 +470,11 @@ mod grpc_builder {
                 .await
                 .map_err(|e| format!("Failed to start server on {}: {}", addr, e))?;
 
+            #[cfg(feature = "whip")]
+            if let Some(task) = whip_task {
+                task.abort();
+            }
+
             Ok(())
         }
     }
@@ -51,6 +51,7 @@ mod grpc_signaling_cli {
     use crate::builder::WebRtcSignalingServerBuilder;
     use std::sync::Arc;
     use std::path::PathBuf;
+    use remotemedia_core::auth::{AuthGuard, JwtAuthenticator, KeyStoreAuthenticator};
     use remotemedia_core::transport::PipelineExecutor;
 
     /// CLI arguments for WebRTC gRPC signaling server
@@ -71,6 +72,28 @@ mod grpc_signaling_cli {
         /// Maximum concurrent peer connections
         #[arg(long, default_value_t = 10, env = "WEBRTC_MAX_PEERS")]
         pub webrtc_max_peers: u32,
+
+        /// Directory for session recordings (recording disabled if unset)
+        #[arg(long, env = "WEBRTC_RECORDING_DIR")]
+        pub webrtc_recording_dir: Option<PathBuf>,
+
+        /// API key store (JSON/YAML) required on signaling requests
+        #[arg(long, env = "REMOTEMEDIA_AUTH_KEYS_FILE")]
+        pub webrtc_auth_keys_file: Option<PathBuf>,
+
+        /// HMAC secret for JWT API keys required on signaling requests
+        #[arg(long, env = "REMOTEMEDIA_AUTH_JWT_SECRET", hide_env_values = true)]
+        pub webrtc_auth_jwt_secret: Option<String>,
+
+        /// WHIP/WHEP HTTP bind address (disabled if unset)
+        #[cfg(feature = "whip")]
+        #[arg(long, env = "WEBRTC_WHIP_ADDRESS")]
+        pub webrtc_whip_bind: Option<String>,
+
+        /// Bearer token required on WHIP/WHEP requests
+        #[cfg(feature = "whip")]
+        #[arg(long, env = "WEBRTC_WHIP_TOKEN")]
+        pub webrtc_whip_token: Option<String>,
     }
 
     impl WebRtcSignalingServeArgs {
@@ -93,6 +116,28 @@ mod grpc_signaling_cli {
             if let Some(exec) = executor {
                 builder = builder.executor(exec);
             }
+            if let Some(dir) = self.webrtc_recording_dir {
+                builder = builder.recording_dir(dir);
+            }
+
+            let mut auth = AuthGuard::open();
+            if let Some(path) = self.webrtc_auth_keys_file {
+                auth = auth.with_authenticator(Arc::new(KeyStoreAuthenticator::load(path)?));
+            }
+            if let Some(secret) = self.webrtc_auth_jwt_secret {
+                auth = auth.with_authenticator(Arc::new(JwtAuthenticator::new(secret)));
+            }
+            builder = builder.auth(auth);
+
+            #[cfg(feature = "whip")]
+            {
+                if let Some(addr) = self.webrtc_whip_bind {
+                    builder = builder.whip_bind(addr);
+                }
+                if let Some(token) = self.webrtc_whip_token {
+                    builder = builder.whip_bearer_token(token);
+                }
+            }
 
             builder.build()?.run().await
         }
@@ -1,6 +1,7 @@
 //! Configuration types for WebRTC transport
 
 use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
 
 /// Main configuration for WebRtcTransport
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -43,6 +44,11 @@ pub struct WebRtcTransportConfig {
 
     /// Additional configuration options
     pub options: ConfigOptions,
+
+    /// Directory session recordings are written to (`control.start_recording`).
+    /// Recording is disabled when `None` (default).
+    #[serde(default)]
+    pub recording_dir: Option<PathBuf>,
 }
 
 /// TURN server configuration
@@ -172,6 +178,7 @@ impl Default for WebRtcTransportConfig {
             enable_rtcp: true,
             rtcp_interval_ms: 5000,
             options: ConfigOptions::default(),
+            recording_dir: None,
         }
     }
 }
@@ -294,6 +301,7 @@ impl WebRtcTransportConfig {
                 reconnect_backoff_max_ms: 10000,
                 reconnect_backoff_multiplier: 1.5,
             },
+            recording_dir: None,
         }
     }
 
@@ -343,6 +351,7 @@ impl WebRtcTransportConfig {
                 reconnect_backoff_max_ms: 30000,
                 reconnect_backoff_multiplier: 2.0,
             },
+            recording_dir: None,
         }
     }
 
@@ -402,6 +411,7 @@ impl WebRtcTransportConfig {
                 reconnect_backoff_max_ms: 60000, // Up to 1 minute between retries
                 reconnect_backoff_multiplier: 1.5, // Slower backoff growth
             },
+            recording_dir: None,
         }
     }
 
@@ -31,15 +31,20 @@ use crate::generated::intercept_decision::Decision as PbDecision;
 use crate::generated::{
     Attached, CloseReasonCode, ControlAddress as PbAddress, ControlDirection, ControlErrorCode,
     ControlEvent, ControlFrame, ErrorEvent, InterceptRequest as PbInterceptRequest,
-    NodeState as PbNodeState, SessionClosed, TapEvent,
+    NodeState as PbNodeState, PatchApplied, RecordingStarted, RecordingStopped, SessionClosed,
+    TapEvent,
 };
+#[cfg(feature = "grpc-signaling")]
+use crate::recording::SessionRecorder;
 
 #[cfg(feature = "grpc-signaling")]
 use prost::Message;
 use remotemedia_core::data::RuntimeData;
 #[cfg(feature = "grpc-signaling")]
+use remotemedia_core::transport::pipeline_patch::PipelinePatch;
+#[cfg(feature = "grpc-signaling")]
 use remotemedia_core::transport::session_control::{
-    CloseReason, ControlAddress as CoreAddress, ControlEvent as CoreEvent, Direction,
+    CloseReason, ControlAddress as CoreAddress, ControlAuth, ControlEvent as CoreEvent, Direction,
     InterceptDecision as CoreDecision, NodeState, SessionControl, SessionControlBus,
 };
 
@@ -77,10 +82,16 @@ fn now_instant() -> std::time::Instant {
 /// The handler lives for as long as the data channel is open. When the
 /// session terminates on the server side, a `SessionClosed` event is
 /// emitted and the channel is closed.
+///
+/// `auth` is the signaling peer's key: the Hello is checked against it and
+/// so is every patch applied over the channel. `recorder` serves
+/// `StartRecording` / `StopRecording` for the peer that opened the channel.
 #[cfg(feature = "grpc-signaling")]
 pub async fn attach_control_channel(
     data_channel: Arc<RTCDataChannel>,
     bus: Arc<SessionControlBus>,
+    auth: ControlAuth,
+    recorder: Arc<SessionRecorder>,
 ) {
     // Queue for outbound events. Shared by:
     //   - the Hello/ErrorEvent sent before the dispatch loop starts
@@ -110,6 +121,8 @@ pub async fn attach_control_channel(
     let forwarders_cb = Arc::clone(&forwarders);
     let out_tx_cb = out_tx.clone();
     let bus_cb = Arc::clone(&bus);
+    let auth_cb = auth;
+    let recorder_cb = Arc::clone(&recorder);
     let dc_for_msg = Arc::clone(&data_channel);
 
     data_channel.on_message(Box::new(move |msg: DataChannelMessage| {
@@ -117,6 +130,8 @@ pub async fn attach_control_channel(
         let forwarders = Arc::clone(&forwarders_cb);
         let out_tx = out_tx_cb.clone();
         let bus = Arc::clone(&bus_cb);
+        let auth = auth_cb.clone();
+        let recorder = Arc::clone(&recorder_cb);
         let dc = Arc::clone(&dc_for_msg);
         Box::pin(async move {
             let frame = match ControlFrame::decode(&msg.data[..]) {
@@ -137,6 +152,8 @@ pub async fn attach_control_channel(
                 frame,
                 &state,
                 &bus,
+                &auth,
+                &recorder,
                 &out_tx,
                 &forwarders,
                 &dc,
@@ -152,6 +169,8 @@ enum AttachState {
     Attached {
         ctrl: Arc<SessionControl>,
         session_id: String,
+        // Who attached; patches are checked against it.
+        auth: ControlAuth,
         // JoinHandle on the close-watcher. Abort on detach / data channel close.
         close_watcher: tokio::task::JoinHandle<()>,
     },
@@ -183,6 +202,8 @@ async fn handle_inbound(
     frame: ControlFrame,
     state: &Arc<Mutex<AttachState>>,
     bus: &Arc<SessionControlBus>,
+    auth: &ControlAuth,
+    recorder: &Arc<SessionRecorder>,
     out_tx: &mpsc::Sender<ControlEvent>,
     forwarders: &Arc<Mutex<Vec<tokio::task::JoinHandle<()>>>>,
     dc: &Arc<RTCDataChannel>,
@@ -208,9 +229,21 @@ async fn handle_inbound(
                     return;
                 }
             };
-            let ctrl = match bus.get(&hello.session_id) {
-                Some(c) => c,
-                None => {
+            let ctrl = match bus.attach(&hello.session_id, auth) {
+                Ok(Some(c)) => c,
+                Err(e) => {
+                    let _ = out_tx
+                        .send(error_event(
+                            ControlErrorCode::Unauthorized,
+                            e.to_string(),
+                            None,
+                        ))
+                        .await;
+                    let _ = dc.close().await;
+                    *guard = AttachState::Closed;
+                    return;
+                }
+                Ok(None) => {
                     let _ = out_tx
                         .send(error_event(
                             ControlErrorCode::SessionNotFound,
@@ -261,6 +294,7 @@ async fn handle_inbound(
             *guard = AttachState::Attached {
                 ctrl,
                 session_id: hello.session_id,
+                auth: auth.clone(),
                 close_watcher,
             };
             return;
@@ -271,8 +305,8 @@ async fn handle_inbound(
     }
 
     // Borrow Ctrl out of the state to dispatch.
-    let ctrl = match &*guard {
-        AttachState::Attached { ctrl, .. } => Arc::clone(ctrl),
+    let (ctrl, auth) = match &*guard {
+        AttachState::Attached { ctrl, auth, .. } => (Arc::clone(ctrl), auth.clone()),
         _ => return,
     };
     drop(guard);
@@ -290,7 +324,7 @@ async fn handle_inbound(
     }
 
     // Dispatch the frame.
-    if let Err(err) = dispatch_frame(frame, &ctrl, out_tx, forwarders).await {
+    if let Err(err) = dispatch_frame(frame, &ctrl, &auth, recorder, out_tx, forwarders).await {
         let _ = out_tx
             .send(ControlEvent {
                 event: Some(PbEvent::Error(err)),
@@ -306,6 +340,8 @@ async fn handle_inbound(
 async fn dispatch_frame(
     frame: ControlFrame,
     ctrl: &Arc<SessionControl>,
+    auth: &ControlAuth,
+    recorder: &Arc<SessionRecorder>,
     out_tx: &mpsc::Sender<ControlEvent>,
     forwarders: &Arc<Mutex<Vec<tokio::task::JoinHandle<()>>>>,
 ) -> Result<(), ErrorEvent> {
@@ -426,6 +462,78 @@ async fn dispatch_frame(
             Ok(())
         }
 
+        Some(PbOp::ApplyPatch(apply)) => {
+            let patch: PipelinePatch = serde_json::from_str(&apply.patch_json).map_err(|e| {
+                error_inner(
+                    ControlErrorCode::InvalidPatch,
+                    format!("invalid patch JSON: {e}"),
+                    None,
+                )
+            })?;
+            auth.check_patch(ctrl, &patch)
+                .map_err(|e| error_inner(ControlErrorCode::Unauthorized, e.to_string(), None))?;
+            // Answered from a task: new nodes may load models and retired
+            // ones drain before the patch resolves.
+            let ctrl = ctrl.clone();
+            let tx = out_tx.clone();
+            let task = tokio::spawn(async move {
+                let event = match ctrl.apply_patch(patch).await {
+                    Ok(report) => PbEvent::PatchApplied(PatchApplied {
+                        report_json: serde_json::to_string(&report).unwrap_or_default(),
+                    }),
+                    Err(e) => PbEvent::Error(error_inner(
+                        ControlErrorCode::InvalidPatch,
+                        e.to_string(),
+                        None,
+                    )),
+                };
+                let _ = tx.send(ControlEvent { event: Some(event) }).await;
+            });
+            forwarders.lock().await.push(task);
+            Ok(())
+        }
+
+        Some(PbOp::StartRecording(start)) => {
+            let info = recorder
+                .start(start.include_outbound)
+                .await
+                .map_err(|e| error_inner(ControlErrorCode::Recording, e.to_string(), None))?;
+            let _ = out_tx
+                .send(ControlEvent {
+                    event: Some(PbEvent::RecordingStarted(RecordingStarted {
+                        recording_id: info.recording_id,
+                        path: info.path.display().to_string(),
+                        tracks: info.tracks,
+                    })),
+                })
+                .await;
+            Ok(())
+        }
+
+        Some(PbOp::StopRecording(_)) => {
+            // Finalizing flushes the reorder window; answer from a task.
+            let recorder = Arc::clone(recorder);
+            let tx = out_tx.clone();
+            let task = tokio::spawn(async move {
+                let event = match recorder.stop().await {
+                    Ok(summary) => PbEvent::RecordingStopped(RecordingStopped {
+                        recording_id: summary.recording_id,
+                        path: summary.path.display().to_string(),
+                        duration_ms: summary.duration_ms,
+                        size_bytes: summary.size_bytes,
+                    }),
+                    Err(e) => PbEvent::Error(error_inner(
+                        ControlErrorCode::Recording,
+                        e.to_string(),
+                        None,
+                    )),
+                };
+                let _ = tx.send(ControlEvent { event: Some(event) }).await;
+            });
+            forwarders.lock().await.push(task);
+            Ok(())
+        }
+
         Some(PbOp::Hello(_)) | None => Err(error_inner(
             ControlErrorCode::Protocol,
             "unexpected frame",
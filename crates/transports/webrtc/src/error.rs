@@ -82,6 +82,21 @@ pub enum Error {
     #[error("Track not found: {0}")]
     TrackNotFound(String),
 
+    /// Session recording error
+    #[error("Recording error: {0}")]
+    RecordingError(String),
+
+    /// Pipeline session refused by executor admission control
+    #[error("Session rejected ({resource}): {message}")]
+    SessionRejected {
+        /// Exhausted quota, as reported by the executor
+        resource: String,
+        /// Error message
+        message: String,
+        /// Suggested wait before retrying, in milliseconds
+        retry_after_ms: u64,
+    },
+
     /// WebSocket error
     #[error("WebSocket error: {0}")]
     WebSocketError(String),
@@ -115,6 +130,7 @@ impl Error {
             Error::SignalingError(_)
                 | Error::NatTraversalFailed(_)
                 | Error::OperationTimeout(_)
+                | Error::SessionRejected { .. }
                 | Error::WebSocketError(_)
                 | Error::IoError(_)
         )
@@ -269,6 +285,16 @@ impl Error {
                  2. The track hasn't been removed\n\
                  3. Use get_video_streams() or get_audio_streams() to list available tracks."
             }
+            Error::RecordingError(_) => {
+                "Session recording failed. Check that:\n\
+                 1. recording_dir is set in WebRtcTransportConfig and writable\n\
+                 2. Only one recording runs per session at a time\n\
+                 3. The session has at least one Opus or VP8 track to record"
+            }
+            Error::SessionRejected { .. } => {
+                "The server is at capacity for this pipeline. Retry the offer after \
+                 the suggested delay, or ask the operator to raise the admission quotas."
+            }
             Error::WebSocketError(_) => {
                 "WebSocket connection failed. Check that:\n\
                  1. Signaling server URL is correct (ws:// or wss://)\n\
@@ -324,6 +350,8 @@ impl Error {
             Error::InvalidStreamId(_) => "INVALID_STREAM_ID",
             Error::TrackLimitExceeded(_) => "TRACK_LIMIT_EXCEEDED",
             Error::TrackNotFound(_) => "TRACK_NOT_FOUND",
+            Error::RecordingError(_) => "RECORDING_ERROR",
+            Error::SessionRejected { .. } => "SESSION_REJECTED",
             Error::WebSocketError(_) => "WEBSOCKET_ERROR",
             Error::SerializationError(_) => "SERIALIZATION_ERROR",
             Error::InternalError(_) => "INTERNAL_ERROR",
@@ -348,6 +376,12 @@ mod tests {
     fn test_error_is_retryable() {
         assert!(Error::SignalingError("test".to_string()).is_retryable());
         assert!(Error::OperationTimeout("test".to_string()).is_retryable());
+        assert!(Error::SessionRejected {
+            resource: "sessions".to_string(),
+            message: "test".to_string(),
+            retry_after_ms: 5_000,
+        }
+        .is_retryable());
         assert!(!Error::InvalidConfig("test".to_string()).is_retryable());
     }
 
@@ -434,6 +468,12 @@ mod tests {
             Error::InvalidStreamId("test".to_string()),
             Error::TrackLimitExceeded("test".to_string()),
             Error::TrackNotFound("test".to_string()),
+            Error::RecordingError("test".to_string()),
+            Error::SessionRejected {
+                resource: "sessions".to_string(),
+                message: "test".to_string(),
+                retry_after_ms: 5_000,
+            },
             Error::WebSocketError("test".to_string()),
             Error::SerializationError("test".to_string()),
             Error::InternalError("test".to_string()),
@@ -958,7 +958,7 @@ pub mod intercept_decision {
 pub struct Empty {}
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct ControlFrame {
-    #[prost(oneof = "control_frame::Op", tags = "1, 2, 3, 4, 5, 6, 7, 8, 9")]
+    #[prost(oneof = "control_frame::Op", tags = "1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12")]
     pub op: ::core::option::Option<control_frame::Op>,
 }
 /// Nested message and enum types in `ControlFrame`.
@@ -983,6 +983,12 @@ pub mod control_frame {
         SetNodeState(super::SetNodeState),
         #[prost(message, tag = "9")]
         ClearNodeState(super::ClearNodeState),
+        #[prost(message, tag = "10")]
+        ApplyPatch(super::ApplyPatch),
+        #[prost(message, tag = "11")]
+        StartRecording(super::StartRecording),
+        #[prost(message, tag = "12")]
+        StopRecording(super::StopRecording),
     }
 }
 /// Identifies the session this attach is scoped to. MUST be the first frame.
@@ -1046,9 +1052,30 @@ pub struct ClearNodeState {
     #[prost(string, tag = "1")]
     pub node_id: ::prost::alloc::string::String,
 }
+/// Edit the running pipeline. Answered with `PatchApplied`, or an
+/// `ErrorEvent { code = INVALID_PATCH }` if the patch was rejected — in
+/// which case the pipeline is unchanged.
+#[derive(Clone, PartialEq, Eq, Hash, ::prost::Message)]
+pub struct ApplyPatch {
+    /// JSON-encoded `PipelinePatch`: `{"ops": \[{"op": "add_node", ...}, ...\]}`
+    #[prost(string, tag = "1")]
+    pub patch_json: ::prost::alloc::string::String,
+}
+/// Record the session to WebM on the server (WebRTC peers only). Answered
+/// with `RecordingStarted`, or `ErrorEvent { code = RECORDING }`.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, ::prost::Message)]
+pub struct StartRecording {
+    /// Also record the pipeline's tracks sent back to the client.
+    #[prost(bool, tag = "1")]
+    pub include_outbound: bool,
+}
+/// Stop the running recording. Answered with `RecordingStopped` once the
+/// file is finalized.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, ::prost::Message)]
+pub struct StopRecording {}
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct ControlEvent {
-    #[prost(oneof = "control_event::Event", tags = "1, 2, 3, 4, 5")]
+    #[prost(oneof = "control_event::Event", tags = "1, 2, 3, 4, 5, 6, 7, 8")]
     pub event: ::core::option::Option<control_event::Event>,
 }
 /// Nested message and enum types in `ControlEvent`.
@@ -1072,6 +1099,15 @@ pub mod control_event {
         /// Sent exactly once as the final frame when the session closes.
         #[prost(message, tag = "5")]
         SessionClosed(super::SessionClosed),
+        /// An `ApplyPatch` was committed.
+        #[prost(message, tag = "6")]
+        PatchApplied(super::PatchApplied),
+        /// A `StartRecording` began writing.
+        #[prost(message, tag = "7")]
+        RecordingStarted(super::RecordingStarted),
+        /// A `StopRecording` finalized the file.
+        #[prost(message, tag = "8")]
+        RecordingStopped(super::RecordingStopped),
     }
 }
 #[derive(Clone, PartialEq, Eq, Hash, ::prost::Message)]
@@ -1081,6 +1117,35 @@ pub struct Attached {
     #[prost(string, tag = "2")]
     pub attach_id: ::prost::alloc::string::String,
 }
+#[derive(Clone, PartialEq, Eq, Hash, ::prost::Message)]
+pub struct PatchApplied {
+    /// JSON-encoded `PatchReport`: added / removed / restarted node ids and
+    /// connection counts.
+    #[prost(string, tag = "1")]
+    pub report_json: ::prost::alloc::string::String,
+}
+#[derive(Clone, PartialEq, Eq, Hash, ::prost::Message)]
+pub struct RecordingStarted {
+    #[prost(string, tag = "1")]
+    pub recording_id: ::prost::alloc::string::String,
+    /// Path of the file on the server.
+    #[prost(string, tag = "2")]
+    pub path: ::prost::alloc::string::String,
+    /// Recorded track names (`in:audio`, `out:<stream_id>:video`, ...).
+    #[prost(string, repeated, tag = "3")]
+    pub tracks: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+}
+#[derive(Clone, PartialEq, Eq, Hash, ::prost::Message)]
+pub struct RecordingStopped {
+    #[prost(string, tag = "1")]
+    pub recording_id: ::prost::alloc::string::String,
+    #[prost(string, tag = "2")]
+    pub path: ::prost::alloc::string::String,
+    #[prost(uint64, tag = "3")]
+    pub duration_ms: u64,
+    #[prost(uint64, tag = "4")]
+    pub size_bytes: u64,
+}
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct TapEvent {
     #[prost(message, optional, tag = "1")]
@@ -1192,6 +1257,10 @@ pub enum ControlErrorCode {
     Protocol = 4,
     Unauthorized = 5,
     Internal = 6,
+    /// patch rejected; pipeline unchanged
+    InvalidPatch = 7,
+    /// recording unavailable or failed
+    Recording = 8,
 }
 impl ControlErrorCode {
     /// String value of the enum field names used in the ProtoBuf definition.
@@ -1207,6 +1276,8 @@ impl ControlErrorCode {
             Self::Protocol => "CONTROL_ERROR_CODE_PROTOCOL",
             Self::Unauthorized => "CONTROL_ERROR_CODE_UNAUTHORIZED",
             Self::Internal => "CONTROL_ERROR_CODE_INTERNAL",
+            Self::InvalidPatch => "CONTROL_ERROR_CODE_INVALID_PATCH",
+            Self::Recording => "CONTROL_ERROR_CODE_RECORDING",
         }
     }
     /// Creates an enum from field names used in the ProtoBuf definition.
@@ -1219,6 +1290,8 @@ impl ControlErrorCode {
             "CONTROL_ERROR_CODE_PROTOCOL" => Some(Self::Protocol),
             "CONTROL_ERROR_CODE_UNAUTHORIZED" => Some(Self::Unauthorized),
             "CONTROL_ERROR_CODE_INTERNAL" => Some(Self::Internal),
+            "CONTROL_ERROR_CODE_INVALID_PATCH" => Some(Self::InvalidPatch),
+            "CONTROL_ERROR_CODE_RECORDING" => Some(Self::Recording),
             _ => None,
         }
     }
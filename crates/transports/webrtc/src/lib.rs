@@ -10,6 +10,7 @@
 //! - **Media codecs**: Opus audio, VP9/H264 video
 //! - **Data channels**: Reliable/unreliable messaging
 //! - **JSON-RPC 2.0 signaling**: WebSocket-based peer discovery and SDP exchange
+//! - **WHIP/WHEP**: HTTP ingest and playback for standard tools like OBS (`whip` feature)
 //! - **RemoteMedia pipeline integration**: Implements PipelineTransport trait
 //!
 //! # Architecture
@@ -78,6 +79,7 @@ pub mod control;
 pub mod custom_nodes;
 pub mod error;
 pub mod plugin;
+pub mod recording;
 
 // Internal modules
 #[cfg(not(feature = "grpc-signaling"))]
@@ -124,6 +126,9 @@ pub use builder::WebRtcServerBuilder;
 #[cfg(feature = "grpc-signaling")]
 pub use builder::WebRtcSignalingServerBuilder;
 
+#[cfg(feature = "whip")]
+pub use signaling::WhipServer;
+
 #[cfg(feature = "cli")]
 pub use cli::WebRtcServeArgs;
 #[cfg(all(feature = "cli", feature = "grpc-signaling"))]
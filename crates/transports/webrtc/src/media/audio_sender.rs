@@ -9,6 +9,7 @@
 // Phase 4 (US2) audio transmission infrastructure
 #![allow(dead_code)]
 
+use crate::recording::TrackTap;
 use crate::{Error, Result};
 use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
 use std::sync::Arc;
@@ -239,6 +240,8 @@ pub struct AudioSender {
     timestamp: Arc<AtomicU32>,
     /// Thread handle (wrapped in Mutex for Drop)
     thread_handle: Arc<Mutex<Option<std::thread::JoinHandle<()>>>>,
+    /// Session recording tap, fed every frame as it is sent
+    recording_tap: Arc<TrackTap>,
 }
 
 impl AudioSender {
@@ -250,7 +253,12 @@ impl AudioSender {
     /// * `buffer_capacity` - Ring buffer capacity in frames
     ///   - Recommended: 1500 frames = 30 seconds @ 20ms (allows TTS burst generation)
     ///   - Minimum: 200 frames = 4 seconds @ 20ms
-    pub fn new(track: Arc<TrackLocalStaticSample>, buffer_capacity: usize) -> Self {
+    /// * `recording_tap` - Receives each encoded frame when the session is recorded
+    pub fn new(
+        track: Arc<TrackLocalStaticSample>,
+        buffer_capacity: usize,
+        recording_tap: Arc<TrackTap>,
+    ) -> Self {
         let buffer = Arc::new(AudioRingBuffer::new(buffer_capacity));
         let shutdown = Arc::new(AtomicBool::new(false));
         let timestamp = Arc::new(AtomicU32::new(0));
@@ -261,6 +269,7 @@ impl AudioSender {
             shutdown: Arc::clone(&shutdown),
             timestamp: Arc::clone(&timestamp),
             thread_handle: Arc::new(Mutex::new(None)),
+            recording_tap: Arc::clone(&recording_tap),
         };
 
         // Start the transmission thread
@@ -268,6 +277,7 @@ impl AudioSender {
         let thread_track = Arc::clone(&track);
         let thread_shutdown = Arc::clone(&shutdown);
         let thread_timestamp = Arc::clone(&timestamp);
+        let thread_tap = Arc::clone(&recording_tap);
 
         let handle = std::thread::Builder::new()
             .name("audio-sender".to_string())
@@ -277,6 +287,7 @@ impl AudioSender {
                     thread_track,
                     thread_shutdown,
                     thread_timestamp,
+                    thread_tap,
                 )
             })
             .expect("Failed to spawn audio sender thread");
@@ -301,6 +312,7 @@ impl AudioSender {
         track: Arc<TrackLocalStaticSample>,
         shutdown: Arc<AtomicBool>,
         timestamp: Arc<AtomicU32>,
+        recording_tap: Arc<TrackTap>,
     ) {
         use tracing::info;
         // Use println as a backup in case tracing isn't working
@@ -350,6 +362,8 @@ impl AudioSender {
                 // Update RTP timestamp
                 let old_ts = timestamp.fetch_add(frame.sample_count, Ordering::AcqRel);
 
+                recording_tap.write_frame(&frame.data, true, None);
+
                 // Create WebRTC sample
                 let sample = Sample {
                     data: frame.data.into(),
@@ -475,6 +489,7 @@ impl Clone for AudioSender {
             shutdown: Arc::clone(&self.shutdown),
             timestamp: Arc::clone(&self.timestamp),
             thread_handle: Arc::clone(&self.thread_handle),
+            recording_tap: Arc::clone(&self.recording_tap),
         }
     }
 }
@@ -8,6 +8,7 @@
 use super::audio::{AudioEncoder, AudioEncoderConfig};
 use super::audio_sender::AudioSender;
 use super::video::{VideoFormat, VideoFrame};
+use crate::recording::{RecordCodec, TrackTap};
 use crate::{Error, Result};
 use remotemedia_core::data::video::{PixelFormat, VideoCodec};
 use remotemedia_core::data::RuntimeData;
@@ -44,6 +45,9 @@ pub struct AudioTrack {
     /// RTP timestamp (in sample units). Sync parking_lot — only
     /// `u32` reads/writes; no guard ever lives across an await.
     timestamp: Arc<RwLock<u32>>,
+
+    /// Session recording tap, shared with the sender thread
+    recording_tap: Arc<TrackTap>,
 }
 
 impl AudioTrack {
@@ -63,7 +67,14 @@ impl AudioTrack {
         // Create audio sender with ring buffer
         // Large buffer allows TTS to generate audio in bursts without blocking
         println!("[AUDIOTRACK] About to create AudioSender...");
-        let sender = AudioSender::new(Arc::clone(&track), config.ring_buffer_capacity);
+        let recording_tap = TrackTap::new(RecordCodec::Opus {
+            channels: config.channels,
+        });
+        let sender = AudioSender::new(
+            Arc::clone(&track),
+            config.ring_buffer_capacity,
+            Arc::clone(&recording_tap),
+        );
         println!("[AUDIOTRACK] AudioSender created!");
 
         Ok(Self {
@@ -73,6 +84,7 @@ impl AudioTrack {
             // Async RwLock: see field doc — guard held across await.
             sender: Arc::new(AsyncRwLock::new(Some(sender))),
             timestamp: Arc::new(RwLock::new(0)),
+            recording_tap,
         })
     }
 
@@ -188,6 +200,11 @@ impl AudioTrack {
         Arc::clone(&self.track)
     }
 
+    /// Tap that records this track's encoded frames
+    pub fn recording_tap(&self) -> Arc<TrackTap> {
+        Arc::clone(&self.recording_tap)
+    }
+
     /// Decode received RTP packet to audio samples
     ///
     /// # Arguments
@@ -276,6 +293,9 @@ pub struct VideoTrack {
 
     /// Video codec being used
     codec: VideoCodec,
+
+    /// Session recording tap; `None` when the codec can't go into WebM
+    recording_tap: Option<Arc<TrackTap>>,
 }
 
 impl VideoTrack {
@@ -328,6 +348,10 @@ impl VideoTrack {
             sequence_number: Arc::new(RwLock::new(0)),
             timestamp: Arc::new(RwLock::new(0)),
             codec,
+            recording_tap: match codec {
+                VideoCodec::Vp8 => Some(TrackTap::new(RecordCodec::Vp8)),
+                VideoCodec::H264 | VideoCodec::Av1 => None,
+            },
         })
     }
 
@@ -390,8 +414,16 @@ impl VideoTrack {
             RuntimeData::Video {
                 pixel_data,
                 codec: Some(_),
+                width,
+                height,
+                is_keyframe,
                 ..
-            } => pixel_data.clone(),
+            } => {
+                if let Some(tap) = &self.recording_tap {
+                    tap.write_frame(pixel_data, *is_keyframe, Some((*width, *height)));
+                }
+                pixel_data.clone()
+            }
             _ => {
                 return Err(Error::EncodingError(
                     "Expected encoded video frame".to_string(),
@@ -435,6 +467,11 @@ impl VideoTrack {
         Arc::clone(&self.track)
     }
 
+    /// Tap that records this track's encoded frames, if its codec allows
+    pub fn recording_tap(&self) -> Option<Arc<TrackTap>> {
+        self.recording_tap.clone()
+    }
+
     /// Decode received RTP packet to RuntimeData (uses core VideoDecoderNode)
     ///
     /// # Arguments
@@ -19,20 +19,27 @@ use crate::media::{
     tracks::{AudioTrack, VideoTrack},
     extract_stream_id,
 };
+use crate::recording::{RecordingInfo, RecordingSummary, SessionRecorder, TrackTap};
 #[cfg(feature = "ws-signaling")]
 use crate::signaling::{WebRtcEventBridge, current_timestamp_ns};
 use prost::Message;
 use remotemedia_core::{
+    auth::Principal,
     data::RuntimeData,
     manifest::Manifest,
+    telemetry::TraceContext,
     transport::{PipelineExecutor, SessionHandle, TransportData},
 };
 use std::sync::Arc;
-use tokio::sync::{mpsc, RwLock};
+use tokio::sync::{broadcast, mpsc, RwLock};
 use tracing::{debug, error, info, trace, warn};
 use webrtc::data_channel::RTCDataChannel;
 use webrtc::peer_connection::sdp::session_description::RTCSessionDescription;
 
+/// Capacity of the per-peer output fan-out (see [`ServerPeer::subscribe_output`]).
+/// Subscribers that fall further behind than this skip ahead.
+const OUTPUT_FANOUT_CAPACITY: usize = 256;
+
 /// Server-side WebRTC peer with pipeline integration
 ///
 /// Automatically created when a client announces via gRPC signaling.
@@ -73,6 +80,21 @@ pub struct ServerPeer {
     /// Transport-level control handlers read this to look up the
     /// per-session `SessionControl` on the executor's `SessionControlBus`.
     session_id: Arc<RwLock<Option<String>>>,
+
+    /// Copy of every pipeline output, for playback-only peers (WHEP)
+    /// subscribed to this peer's session
+    output_fanout: broadcast::Sender<TransportData>,
+
+    /// Trace context from signaling, stamped on every input this peer
+    /// feeds the pipeline
+    trace_context: Option<TraceContext>,
+
+    /// API key that signaled this peer: its tenant owns the session and
+    /// its limits apply to control-channel patches
+    principal: Option<Principal>,
+
+    /// Session recorder, driven from the control bus
+    recorder: Arc<SessionRecorder>,
 }
 
 impl ServerPeer {
@@ -105,6 +127,11 @@ impl ServerPeer {
         // Create shutdown channel
         let (shutdown_tx, shutdown_rx) = mpsc::channel(1);
 
+        let recorder = Arc::new(
+            SessionRecorder::new(config.recording_dir.clone(), peer_id.clone())
+                .with_outbound_tracks(Arc::clone(&track_registry)),
+        );
+
         Ok(Self {
             peer_id,
             peer_connection,
@@ -116,6 +143,10 @@ impl ServerPeer {
             shutdown_tx,
             shutdown_rx: Arc::new(RwLock::new(Some(shutdown_rx))),
             session_id: Arc::new(RwLock::new(None)),
+            output_fanout: broadcast::channel(OUTPUT_FANOUT_CAPACITY).0,
+            trace_context: None,
+            principal: None,
+            recorder,
         })
     }
 
@@ -151,6 +182,11 @@ impl ServerPeer {
         // Create shutdown channel
         let (shutdown_tx, shutdown_rx) = mpsc::channel(1);
 
+        let recorder = Arc::new(
+            SessionRecorder::new(config.recording_dir.clone(), peer_id.clone())
+                .with_outbound_tracks(Arc::clone(&track_registry)),
+        );
+
         Ok(Self {
             peer_id,
             peer_connection,
@@ -161,9 +197,27 @@ impl ServerPeer {
             shutdown_tx,
             shutdown_rx: Arc::new(RwLock::new(Some(shutdown_rx))),
             session_id: Arc::new(RwLock::new(None)),
+            output_fanout: broadcast::channel(OUTPUT_FANOUT_CAPACITY).0,
+            trace_context: None,
+            principal: None,
+            recorder,
         })
     }
 
+    /// Join the caller's trace: inputs from this peer carry `trace_context`
+    /// into the pipeline, so node spans nest under the signaling request.
+    pub fn with_trace_context(mut self, trace_context: Option<TraceContext>) -> Self {
+        self.trace_context = trace_context;
+        self
+    }
+
+    /// Create the pipeline session on behalf of `principal`'s tenant, for
+    /// admission quotas and control-bus ownership
+    pub fn with_principal(mut self, principal: Option<Principal>) -> Self {
+        self.principal = principal;
+        self
+    }
+
     /// Session id assigned by `PipelineExecutor::create_session` when this
     /// peer handled its first offer. `None` before the offer is processed
     /// — callers racing the initial SDP exchange should retry.
@@ -187,6 +241,24 @@ impl ServerPeer {
         dropped
     }
 
+    /// The peer's session recorder
+    pub fn recorder(&self) -> Arc<SessionRecorder> {
+        Arc::clone(&self.recorder)
+    }
+
+    /// Start recording this session to WebM
+    ///
+    /// Records every Opus/VP8 track received from the client; with
+    /// `include_outbound`, also the pipeline's tracks sent back to it.
+    pub async fn start_recording(&self, include_outbound: bool) -> Result<RecordingInfo> {
+        self.recorder.start(include_outbound).await
+    }
+
+    /// Stop the running recording and finalize its file
+    pub async fn stop_recording(&self) -> Result<RecordingSummary> {
+        self.recorder.stop().await
+    }
+
     /// Handle incoming SDP offer from client
     ///
     /// Processes the offer, creates a pipeline session, sets up media routing,
@@ -205,10 +277,22 @@ impl ServerPeer {
         // Create pipeline session FIRST
         let session_handle = self
             .executor
-            .create_session(Arc::clone(&self.manifest))
+            .create_session_as(
+                Arc::clone(&self.manifest),
+                self.principal.as_ref().map(|p| p.tenant_id.as_str()),
+            )
             .await
-            .map_err(|e| {
-                Error::InternalError(format!("Failed to create pipeline session: {}", e))
+            .map_err(|e| match e {
+                remotemedia_core::Error::AdmissionRejected {
+                    resource,
+                    message,
+                    retry_after_ms,
+                } => Error::SessionRejected {
+                    resource,
+                    message,
+                    retry_after_ms,
+                },
+                e => Error::InternalError(format!("Failed to create pipeline session: {}", e)),
             })?;
 
         // Surface the session id on this peer so transport-level control
@@ -255,6 +339,19 @@ impl ServerPeer {
             });
         }
 
+        self.add_default_tracks().await?;
+
+        // Set up bidirectional media routing and data channel (this will set up the data channel handler)
+        self.setup_media_routing_and_data_channel(session_handle)
+            .await?;
+
+        // Now set remote description (offer) - data channel handler is already registered
+        self.answer_offer(offer_sdp).await
+    }
+
+    /// Add the default outbound audio/video tracks and register them under
+    /// `DEFAULT_STREAM_ID` in the track registry
+    async fn add_default_tracks(&self) -> Result<()> {
         // Add audio track for sending pipeline audio output to client (requires opus-codec feature)
         // Note: This sets the Opus clock rate in SDP - must match pipeline output sample rate
         let audio_config = crate::media::audio::AudioEncoderConfig {
@@ -304,11 +401,11 @@ impl ServerPeer {
 
         info!("Added video track to peer connection for {}", self.peer_id);
 
-        // Set up bidirectional media routing and data channel (this will set up the data channel handler)
-        self.setup_media_routing_and_data_channel(session_handle)
-            .await?;
+        Ok(())
+    }
 
-        // Now set remote description (offer) - data channel handler is already registered
+    /// Apply a remote offer and produce the local answer
+    async fn answer_offer(&self, offer_sdp: String) -> Result<String> {
         let offer = RTCSessionDescription::offer(offer_sdp)
             .map_err(|e| Error::WebRtcError(format!("Invalid offer SDP: {}", e)))?;
 
@@ -338,6 +435,98 @@ impl ServerPeer {
         Ok(answer.sdp)
     }
 
+    /// Receive a copy of every pipeline output this peer's session produces
+    ///
+    /// Used by playback-only peers (WHEP) that watch another peer's session.
+    /// The receiver closes when this peer's media routing ends.
+    pub fn subscribe_output(&self) -> broadcast::Receiver<TransportData> {
+        self.output_fanout.subscribe()
+    }
+
+    /// Handle an SDP offer from a playback-only client
+    ///
+    /// Unlike [`handle_offer`](Self::handle_offer) no pipeline session is
+    /// created: `outputs` (from another peer's
+    /// [`subscribe_output`](Self::subscribe_output)) is played out on this
+    /// peer's default tracks, and `session_id` is recorded so control-plane
+    /// lookups resolve to the watched session. Inbound media is ignored.
+    pub async fn handle_subscribe_offer(
+        &self,
+        offer_sdp: String,
+        session_id: String,
+        mut outputs: broadcast::Receiver<TransportData>,
+    ) -> Result<String> {
+        info!(
+            "ServerPeer {} handling playback offer for session {}",
+            self.peer_id, session_id
+        );
+
+        *self.session_id.write().await = Some(session_id);
+        self.add_default_tracks().await?;
+
+        let mut shutdown_rx =
+            self.shutdown_rx.write().await.take().ok_or_else(|| {
+                Error::InternalError("Shutdown receiver already taken".to_string())
+            })?;
+        let peer_id = self.peer_id.clone();
+        let track_registry = Arc::clone(&self.track_registry);
+        // Playback peers have no data channel; Json/Text outputs are dropped.
+        let no_data_channel: Arc<RwLock<Option<Arc<RTCDataChannel>>>> =
+            Arc::new(RwLock::new(None));
+        tokio::spawn(async move {
+            loop {
+                tokio::select! {
+                    _ = shutdown_rx.recv() => break,
+                    output = outputs.recv() => match output {
+                        Ok(transport_data) => {
+                            if let Err(e) = Self::send_to_webrtc_multitrack(
+                                &track_registry,
+                                &no_data_channel,
+                                transport_data,
+                            )
+                            .await
+                            {
+                                error!("Failed to send output to WebRTC for peer {}: {}", peer_id, e);
+                            }
+                        }
+                        Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                            warn!("Playback peer {} lagged, skipped {} outputs", peer_id, skipped);
+                        }
+                        Err(broadcast::error::RecvError::Closed) => {
+                            info!("Watched session ended for playback peer {}", peer_id);
+                            break;
+                        }
+                    },
+                }
+            }
+            debug!("Playback forwarder ended for peer {}", peer_id);
+        });
+
+        self.answer_offer(offer_sdp).await
+    }
+
+    /// Local SDP once ICE gathering has finished (or `timeout` elapsed)
+    ///
+    /// For signaling without trickle ICE (WHIP/WHEP), where the answer
+    /// returned to the client must already carry the server's candidates.
+    pub async fn local_description_with_candidates(
+        &self,
+        timeout: std::time::Duration,
+    ) -> Result<String> {
+        let pc = self.peer_connection.peer_connection();
+        let mut gathered = pc.gathering_complete_promise().await;
+        if tokio::time::timeout(timeout, gathered.recv()).await.is_err() {
+            warn!(
+                "ICE gathering for peer {} not complete after {:?}, answering with partial candidates",
+                self.peer_id, timeout
+            );
+        }
+        pc.local_description()
+            .await
+            .map(|desc| desc.sdp)
+            .ok_or_else(|| Error::SdpError("No local description after answer".to_string()))
+    }
+
     /// Set up bidirectional media routing and data channel
     ///
     /// - Incoming: WebRTC tracks + data channel → RuntimeData → pipeline input
@@ -377,6 +566,12 @@ impl ServerPeer {
         // prost-generated ControlFrame/ControlEvent types live under that feature.
         #[cfg(feature = "grpc-signaling")]
         let control_bus_for_dc = self.executor.control_bus();
+        #[cfg(feature = "grpc-signaling")]
+        let recorder_for_dc = Arc::clone(&self.recorder);
+        #[cfg(feature = "grpc-signaling")]
+        let auth_for_dc = remotemedia_core::transport::session_control::ControlAuth {
+            principal: self.principal.clone(),
+        };
         self.peer_connection
             .peer_connection()
             .on_data_channel(Box::new(move |data_channel| {
@@ -387,6 +582,10 @@ impl ServerPeer {
                 let event_tx = event_tx_for_dc_clone.clone();
                 #[cfg(feature = "grpc-signaling")]
                 let control_bus = Arc::clone(&control_bus_for_dc);
+                #[cfg(feature = "grpc-signaling")]
+                let recorder = Arc::clone(&recorder_for_dc);
+                #[cfg(feature = "grpc-signaling")]
+                let auth = auth_for_dc.clone();
                 let data_channel = Arc::new(data_channel);
 
                 Box::pin(async move {
@@ -402,6 +601,8 @@ impl ServerPeer {
                         crate::control::attach_control_channel(
                             Arc::clone(&data_channel),
                             control_bus,
+                            auth,
+                            recorder,
                         )
                         .await;
                         return;
@@ -478,18 +679,44 @@ impl ServerPeer {
         // Set up incoming track handlers (audio from client microphone)
         let peer_id_for_track = self.peer_id.clone();
         let peer_connection_for_track = Arc::clone(&self.peer_connection);
+        let recorder_for_track = Arc::clone(&self.recorder);
 
         self.peer_connection.on_track(move |track, _receiver, _transceiver| {
                 let peer_id = peer_id_for_track.clone();
                 let dc_input_tx = dc_input_tx_for_track.clone();
                 let peer_connection = Arc::clone(&peer_connection_for_track);
+                let recorder = Arc::clone(&recorder_for_track);
 
                 Box::pin(async move {
                     info!("Remote track added for peer {}: kind={}", peer_id, track.kind());
 
-                    // Only process audio tracks
-                    if track.kind() != webrtc::rtp_transceiver::rtp_codec::RTPCodecType::Audio {
-                        info!("Ignoring non-audio track for peer {}", peer_id);
+                    // Opus and VP8 tracks can be recorded as received
+                    let is_audio =
+                        track.kind() == webrtc::rtp_transceiver::rtp_codec::RTPCodecType::Audio;
+                    let codec = track.codec();
+                    let recording_tap = TrackTap::for_mime_type(
+                        &codec.capability.mime_type,
+                        codec.capability.channels,
+                    );
+                    if let Some(tap) = &recording_tap {
+                        let name = if is_audio { "in:audio" } else { "in:video" };
+                        recorder.add_inbound(name, Arc::clone(tap));
+                    }
+
+                    // Only audio feeds the pipeline; video is read for recording
+                    if !is_audio {
+                        let Some(tap) = recording_tap else {
+                            info!("Ignoring non-audio track for peer {}", peer_id);
+                            return;
+                        };
+                        info!("Reading {} track of peer {} for recording",
+                            codec.capability.mime_type, peer_id);
+                        tokio::spawn(async move {
+                            while let Ok((rtp_packet, _)) = track.read_rtp().await {
+                                tap.write_rtp(&rtp_packet);
+                            }
+                            debug!("Video reception ended for peer {}", peer_id);
+                        });
                         return;
                     }
 
@@ -557,6 +784,10 @@ impl ServerPeer {
                                 }
                             };
 
+                            if let Some(tap) = &recording_tap {
+                                tap.write_rtp(&rtp_packet);
+                            }
+
                             // Decode Opus payload to audio samples
                             match audio_track.on_rtp_packet(&rtp_packet.payload).await {
                                 Ok(samples) => {
@@ -599,6 +830,7 @@ impl ServerPeer {
         let _peer_connection = Arc::clone(&self.peer_connection);
         let track_registry = Arc::clone(&self.track_registry);
         let data_channel_for_output = Arc::clone(&data_channel_ref);
+        let output_fanout = self.output_fanout.clone();
         #[cfg(feature = "ws-signaling")]
         let event_tx_for_output = event_tx_for_output;
         let mut shutdown_rx =
@@ -624,8 +856,14 @@ impl ServerPeer {
             )
         })?;
         let peer_id_for_input = peer_id.clone();
+        let trace_context = self.trace_context;
         tokio::spawn(async move {
-            while let Some(transport_data) = dc_input_rx.recv().await {
+            while let Some(mut transport_data) = dc_input_rx.recv().await {
+                if let Some(ctx) = &trace_context {
+                    if transport_data.trace_context().is_none() {
+                        transport_data = transport_data.with_trace_context(ctx);
+                    }
+                }
                 debug!(
                     "Forwarding data for peer {} to session",
                     peer_id_for_input
@@ -669,6 +907,10 @@ impl ServerPeer {
                                     }
                                 }
 
+                                if output_fanout.receiver_count() > 0 {
+                                    let _ = output_fanout.send(transport_data.clone());
+                                }
+
                                 if let Err(e) = Self::send_to_webrtc_multitrack(
                                     &track_registry,
                                     &data_channel_for_output,
@@ -916,6 +1158,13 @@ impl ServerPeer {
         // Signal shutdown to media routing task
         let _ = self.shutdown_tx.send(()).await;
 
+        // Finalize a recording left running so its file stays playable
+        if self.recorder.current().await.is_some() {
+            if let Err(e) = self.recorder.stop().await {
+                warn!("Failed to finalize recording for {}: {}", self.peer_id, e);
+            }
+        }
+
         // Note: Pipeline session is owned by the media routing task
         // and will be cleaned up when that task ends
 
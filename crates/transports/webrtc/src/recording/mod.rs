@@ -0,0 +1,622 @@
+//! Per-session recording to WebM
+//!
+//! A [`SessionRecorder`] writes what a peer sends — Opus audio and VP8
+//! video, straight from RTP — into a `.webm` file without transcoding.
+//! Optionally the pipeline's outbound tracks go into the same file, taken
+//! after encoding. Each track feeds the recording through a [`TrackTap`],
+//! which costs one uncontended lock per frame while nothing is recording.
+//!
+//! Recordings are started and stopped from the control bus
+//! (`StartRecording` / `StopRecording` on the control data channel, or
+//! `control.start_recording` / `control.stop_recording` on the signaling
+//! WebSocket) and are written under
+//! [`WebRtcTransportConfig::recording_dir`](crate::config::WebRtcTransportConfig::recording_dir).
+
+mod webm;
+
+pub use webm::{vp8_is_keyframe, vp8_keyframe_size, RecordCodec, TrackSpec, WebmWriter};
+
+use crate::media::tracks::{AudioTrack, VideoTrack};
+use crate::media::TrackRegistry;
+use crate::{Error, Result};
+use parking_lot::Mutex;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::io::BufWriter;
+use std::path::PathBuf;
+use std::sync::mpsc as std_mpsc;
+use std::sync::Arc;
+use std::time::{Instant, SystemTime, UNIX_EPOCH};
+use tokio::sync::{oneshot, Mutex as AsyncMutex};
+use tracing::{info, warn};
+use webrtc::media::io::sample_builder::SampleBuilder;
+use webrtc::rtp::codecs::vp8::Vp8Packet;
+use webrtc::rtp::packet::Packet;
+
+/// Frames are held this long before being written, so tracks that arrive
+/// with different delays still interleave in timestamp order
+const REORDER_WINDOW_MS: u64 = 500;
+
+/// How many sequence numbers VP8 reassembly waits for a missing packet
+const MAX_LATE_PACKETS: u16 = 64;
+
+/// RTP clock rate of Opus
+const OPUS_CLOCK_RATE: u32 = 48_000;
+
+/// RTP clock rate of video
+const VIDEO_CLOCK_RATE: u32 = 90_000;
+
+/// A recording that has started
+#[derive(Debug, Clone, Serialize)]
+pub struct RecordingInfo {
+    /// Identifier of the recording; also the file stem
+    pub recording_id: String,
+    /// File being written
+    pub path: PathBuf,
+    /// Names of the recorded tracks, in track-number order
+    pub tracks: Vec<String>,
+}
+
+/// A recording that has been finalized
+#[derive(Debug, Clone, Serialize)]
+pub struct RecordingSummary {
+    pub recording_id: String,
+    pub path: PathBuf,
+    pub duration_ms: u64,
+    pub size_bytes: u64,
+}
+
+/// One encoded frame on its way to the writer thread
+struct Frame {
+    track: u64,
+    timestamp_ms: u64,
+    keyframe: bool,
+    data: Vec<u8>,
+    /// Video frame size, when the frame reveals it
+    size: Option<(u32, u32)>,
+}
+
+enum WriterMsg {
+    Frame(Frame),
+    Finish(oneshot::Sender<Result<RecordingSummary>>),
+}
+
+/// Maps a track's RTP timestamps onto the recording timeline
+///
+/// The first packet is placed at its arrival time; later ones follow the
+/// RTP clock, so network jitter doesn't leak into the file.
+struct RtpClock {
+    rate: u32,
+    /// Recording time of the first packet, in milliseconds
+    origin_ms: Option<u64>,
+    last_rtp: u32,
+    /// RTP ticks since the first packet, unwrapped
+    ticks: i64,
+}
+
+impl RtpClock {
+    fn new(rate: u32) -> Self {
+        Self {
+            rate,
+            origin_ms: None,
+            last_rtp: 0,
+            ticks: 0,
+        }
+    }
+
+    fn timestamp_ms(&mut self, rtp_timestamp: u32, started: Instant) -> u64 {
+        let Some(origin) = self.origin_ms else {
+            let origin = started.elapsed().as_millis() as u64;
+            self.origin_ms = Some(origin);
+            self.last_rtp = rtp_timestamp;
+            return origin;
+        };
+        self.ticks += i64::from(rtp_timestamp.wrapping_sub(self.last_rtp) as i32);
+        self.last_rtp = rtp_timestamp;
+        origin.saturating_add_signed(self.ticks * 1000 / i64::from(self.rate))
+    }
+}
+
+/// A track's connection to the running recording
+struct TapSink {
+    track: u64,
+    is_video: bool,
+    tx: std_mpsc::Sender<WriterMsg>,
+    started: Instant,
+    clock: RtpClock,
+    /// Reassembles VP8 frames from RTP; `None` for Opus (one frame per packet)
+    vp8: Option<SampleBuilder<Vp8Packet>>,
+    /// Video is dropped until the first keyframe
+    awaiting_keyframe: bool,
+}
+
+impl TapSink {
+    fn send(&mut self, timestamp_ms: u64, keyframe: bool, data: &[u8], size: Option<(u32, u32)>) {
+        if self.is_video && self.awaiting_keyframe {
+            if !keyframe {
+                return;
+            }
+            self.awaiting_keyframe = false;
+        }
+        let _ = self.tx.send(WriterMsg::Frame(Frame {
+            track: self.track,
+            timestamp_ms,
+            keyframe,
+            data: data.to_vec(),
+            size,
+        }));
+    }
+}
+
+/// Feeds one track's encoded frames to the active recording, if any
+pub struct TrackTap {
+    codec: RecordCodec,
+    sink: Mutex<Option<TapSink>>,
+}
+
+impl TrackTap {
+    pub fn new(codec: RecordCodec) -> Arc<Self> {
+        Arc::new(Self {
+            codec,
+            sink: Mutex::new(None),
+        })
+    }
+
+    /// Tap for a received track, if its codec can be recorded from RTP
+    ///
+    /// Opus and VP8 are supported.
+    pub fn for_mime_type(mime_type: &str, channels: u16) -> Option<Arc<Self>> {
+        let codec = match mime_type.to_ascii_lowercase().as_str() {
+            "audio/opus" => RecordCodec::Opus {
+                channels: channels.max(1),
+            },
+            "video/vp8" => RecordCodec::Vp8,
+            _ => return None,
+        };
+        Some(Self::new(codec))
+    }
+
+    pub fn codec(&self) -> RecordCodec {
+        self.codec
+    }
+
+    /// Whether a recording is taking this track's frames
+    pub fn is_recording(&self) -> bool {
+        self.sink.lock().is_some()
+    }
+
+    /// Record a received RTP packet
+    pub fn write_rtp(&self, packet: &Packet) {
+        let mut guard = self.sink.lock();
+        let Some(sink) = guard.as_mut() else {
+            return;
+        };
+        let Some(builder) = sink.vp8.as_mut() else {
+            let timestamp = sink
+                .clock
+                .timestamp_ms(packet.header.timestamp, sink.started);
+            sink.send(timestamp, true, &packet.payload, None);
+            return;
+        };
+        builder.push(packet.clone());
+        let mut samples = Vec::new();
+        while let Some(sample) = builder.pop() {
+            samples.push(sample);
+        }
+        for sample in samples {
+            let timestamp = sink
+                .clock
+                .timestamp_ms(sample.packet_timestamp, sink.started);
+            let keyframe = vp8_is_keyframe(&sample.data);
+            sink.send(
+                timestamp,
+                keyframe,
+                &sample.data,
+                vp8_keyframe_size(&sample.data),
+            );
+        }
+    }
+
+    /// Record an encoded frame about to be sent, timestamped on arrival
+    pub fn write_frame(&self, data: &[u8], keyframe: bool, size: Option<(u32, u32)>) {
+        let mut guard = self.sink.lock();
+        if let Some(sink) = guard.as_mut() {
+            let timestamp = sink.started.elapsed().as_millis() as u64;
+            sink.send(timestamp, keyframe, data, size);
+        }
+    }
+
+    fn attach(&self, track: u64, tx: std_mpsc::Sender<WriterMsg>, started: Instant) {
+        let (clock_rate, vp8) = match self.codec {
+            RecordCodec::Opus { .. } => (OPUS_CLOCK_RATE, None),
+            RecordCodec::Vp8 => (
+                VIDEO_CLOCK_RATE,
+                Some(SampleBuilder::new(
+                    MAX_LATE_PACKETS,
+                    Vp8Packet::default(),
+                    VIDEO_CLOCK_RATE,
+                )),
+            ),
+            RecordCodec::Vp9 => (VIDEO_CLOCK_RATE, None),
+        };
+        *self.sink.lock() = Some(TapSink {
+            track,
+            is_video: self.codec.is_video(),
+            tx,
+            started,
+            clock: RtpClock::new(clock_rate),
+            vp8,
+            awaiting_keyframe: true,
+        });
+    }
+
+    fn detach(&self) {
+        self.sink.lock().take();
+    }
+}
+
+struct ActiveRecording {
+    info: RecordingInfo,
+    taps: Vec<Arc<TrackTap>>,
+    tx: std_mpsc::Sender<WriterMsg>,
+}
+
+/// Records one session's media, one recording at a time
+pub struct SessionRecorder {
+    /// Where recordings go; `None` disables recording
+    dir: Option<PathBuf>,
+    /// Prefix of recording ids (the peer id)
+    name: String,
+    /// Tracks received from the peer, registered as they arrive
+    inbound: Mutex<Vec<(String, Arc<TrackTap>)>>,
+    /// The pipeline's tracks sent to the peer, for `include_outbound`
+    outbound: Option<Arc<TrackRegistry<AudioTrack, VideoTrack>>>,
+    active: AsyncMutex<Option<ActiveRecording>>,
+}
+
+impl SessionRecorder {
+    pub fn new(dir: Option<PathBuf>, name: impl Into<String>) -> Self {
+        Self {
+            dir,
+            name: name.into(),
+            inbound: Mutex::new(Vec::new()),
+            outbound: None,
+            active: AsyncMutex::new(None),
+        }
+    }
+
+    /// Let recordings include the tracks in `registry`
+    pub fn with_outbound_tracks(
+        mut self,
+        registry: Arc<TrackRegistry<AudioTrack, VideoTrack>>,
+    ) -> Self {
+        self.outbound = Some(registry);
+        self
+    }
+
+    /// Register a track received from the peer
+    ///
+    /// Tracks registered while a recording runs join the next one.
+    pub fn add_inbound(&self, name: impl Into<String>, tap: Arc<TrackTap>) {
+        self.inbound.lock().push((name.into(), tap));
+    }
+
+    /// The running recording, if any
+    pub async fn current(&self) -> Option<RecordingInfo> {
+        self.active.lock().await.as_ref().map(|a| a.info.clone())
+    }
+
+    /// Start recording the received tracks and, with `include_outbound`,
+    /// the pipeline's tracks sent back to the peer
+    pub async fn start(&self, include_outbound: bool) -> Result<RecordingInfo> {
+        let mut outbound = Vec::new();
+        if let Some(registry) = self.outbound.as_ref().filter(|_| include_outbound) {
+            for stream_id in registry.audio_stream_ids().await {
+                if let Some(track) = registry.get_audio_track(&stream_id).await {
+                    outbound.push((format!("out:{}:audio", stream_id), track.recording_tap()));
+                }
+            }
+            for stream_id in registry.video_stream_ids().await {
+                let track = registry.get_video_track(&stream_id).await;
+                if let Some(tap) = track.and_then(|track| track.recording_tap()) {
+                    outbound.push((format!("out:{}:video", stream_id), tap));
+                }
+            }
+        }
+        self.start_with(outbound).await
+    }
+
+    async fn start_with(&self, outbound: Vec<(String, Arc<TrackTap>)>) -> Result<RecordingInfo> {
+        let dir = self.dir.as_ref().ok_or_else(|| {
+            Error::RecordingError("recording is disabled: no recording_dir configured".to_string())
+        })?;
+        let mut active = self.active.lock().await;
+        if let Some(current) = active.as_ref() {
+            return Err(Error::RecordingError(format!(
+                "recording {} is already running",
+                current.info.recording_id
+            )));
+        }
+
+        let sources: Vec<(String, Arc<TrackTap>)> = self
+            .inbound
+            .lock()
+            .iter()
+            .cloned()
+            .chain(outbound)
+            .collect();
+        if sources.is_empty() {
+            return Err(Error::RecordingError(
+                "session has no recordable tracks".to_string(),
+            ));
+        }
+        let specs: Vec<TrackSpec> = sources
+            .iter()
+            .enumerate()
+            .map(|(i, (name, tap))| TrackSpec {
+                number: i as u64 + 1,
+                name: name.clone(),
+                codec: tap.codec(),
+            })
+            .collect();
+
+        std::fs::create_dir_all(dir)?;
+        let unix_ms = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_millis())
+            .unwrap_or(0);
+        let recording_id = format!("{}-{}", file_safe(&self.name), unix_ms);
+        let path = dir.join(format!("{}.webm", recording_id));
+        let writer = WebmWriter::new(BufWriter::new(File::create(&path)?), &specs)?;
+
+        let info = RecordingInfo {
+            recording_id,
+            path,
+            tracks: specs.iter().map(|s| s.name.clone()).collect(),
+        };
+        let (tx, rx) = std_mpsc::channel();
+        let thread_info = info.clone();
+        std::thread::Builder::new()
+            .name("webrtc-recorder".to_string())
+            .spawn(move || run_writer(writer, rx, thread_info))?;
+
+        let started = Instant::now();
+        let taps: Vec<Arc<TrackTap>> = sources.into_iter().map(|(_, tap)| tap).collect();
+        for (spec, tap) in specs.iter().zip(&taps) {
+            tap.attach(spec.number, tx.clone(), started);
+        }
+
+        info!(
+            "Recording {} started: {} ({} tracks)",
+            info.recording_id,
+            info.path.display(),
+            taps.len()
+        );
+        *active = Some(ActiveRecording {
+            info: info.clone(),
+            taps,
+            tx,
+        });
+        Ok(info)
+    }
+
+    /// Stop the running recording and finalize its file
+    pub async fn stop(&self) -> Result<RecordingSummary> {
+        let active = self
+            .active
+            .lock()
+            .await
+            .take()
+            .ok_or_else(|| Error::RecordingError("no recording is running".to_string()))?;
+        for tap in &active.taps {
+            tap.detach();
+        }
+        let (reply_tx, reply_rx) = oneshot::channel();
+        active
+            .tx
+            .send(WriterMsg::Finish(reply_tx))
+            .map_err(|_| Error::RecordingError("recording writer has stopped".to_string()))?;
+        let summary = reply_rx
+            .await
+            .map_err(|_| Error::RecordingError("recording writer has stopped".to_string()))??;
+        info!(
+            "Recording {} finished: {} ms, {} bytes",
+            summary.recording_id, summary.duration_ms, summary.size_bytes
+        );
+        Ok(summary)
+    }
+}
+
+/// Writer thread: orders frames by timestamp and appends them to the file
+fn run_writer(
+    mut writer: WebmWriter<BufWriter<File>>,
+    rx: std_mpsc::Receiver<WriterMsg>,
+    info: RecordingInfo,
+) {
+    let mut pending: BTreeMap<(u64, u64), Frame> = BTreeMap::new();
+    let mut sequence = 0u64;
+    let mut newest = 0u64;
+    let mut failed = false;
+    let mut reply = None;
+
+    let write = |writer: &mut WebmWriter<BufWriter<File>>, frame: Frame, failed: &mut bool| {
+        if *failed {
+            return;
+        }
+        let result = match frame.size {
+            Some((width, height)) => writer.set_video_size(frame.track, width, height),
+            None => Ok(()),
+        }
+        .and_then(|_| {
+            writer.write_frame(frame.track, frame.timestamp_ms, frame.keyframe, &frame.data)
+        });
+        if let Err(e) = result {
+            warn!("Recording {}: write failed: {}", info.recording_id, e);
+            *failed = true;
+        }
+    };
+
+    for msg in rx.iter() {
+        match msg {
+            WriterMsg::Frame(frame) => {
+                newest = newest.max(frame.timestamp_ms);
+                pending.insert((frame.timestamp_ms, sequence), frame);
+                sequence += 1;
+                while let Some(entry) = pending.first_entry() {
+                    if entry.key().0 + REORDER_WINDOW_MS > newest {
+                        break;
+                    }
+                    write(&mut writer, entry.remove(), &mut failed);
+                }
+            }
+            WriterMsg::Finish(tx) => {
+                reply = Some(tx);
+                break;
+            }
+        }
+    }
+    while let Some((_, frame)) = pending.pop_first() {
+        write(&mut writer, frame, &mut failed);
+    }
+
+    let duration_ms = writer.duration_ms();
+    let result = writer
+        .finish()
+        .map_err(Error::from)
+        .and_then(|_| Ok(std::fs::metadata(&info.path)?.len()))
+        .map(|size_bytes| RecordingSummary {
+            recording_id: info.recording_id.clone(),
+            path: info.path.clone(),
+            duration_ms,
+            size_bytes,
+        });
+    match reply {
+        Some(tx) => {
+            let _ = tx.send(result);
+        }
+        None => {
+            if let Err(e) = result {
+                warn!("Recording {}: finalize failed: {}", info.recording_id, e);
+            }
+        }
+    }
+}
+
+/// `name` with everything but ASCII alphanumerics, `-` and `_` replaced
+fn file_safe(name: &str) -> String {
+    name.chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '-' || c == '_' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use webrtc::rtp::header::Header;
+
+    fn opus_packet(sequence_number: u16, timestamp: u32) -> Packet {
+        Packet {
+            header: Header {
+                sequence_number,
+                timestamp,
+                ..Default::default()
+            },
+            payload: bytes::Bytes::from_static(&[0xFC, 0x01, 0x02]),
+        }
+    }
+
+    fn temp_dir(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!(
+            "remotemedia-recording-{}-{}",
+            name,
+            std::process::id()
+        ))
+    }
+
+    #[test]
+    fn test_rtp_clock_unwraps() {
+        let started = Instant::now();
+        let mut clock = RtpClock::new(OPUS_CLOCK_RATE);
+        let origin = clock.timestamp_ms(u32::MAX - 479, started);
+        assert_eq!(clock.timestamp_ms(480, started), origin + 20);
+        // A reordered packet lands before its predecessor
+        assert_eq!(clock.timestamp_ms(0, started), origin + 10);
+    }
+
+    #[test]
+    fn test_mime_types() {
+        assert_eq!(
+            TrackTap::for_mime_type("audio/opus", 2).unwrap().codec(),
+            RecordCodec::Opus { channels: 2 }
+        );
+        assert_eq!(
+            TrackTap::for_mime_type("video/VP8", 0).unwrap().codec(),
+            RecordCodec::Vp8
+        );
+        assert!(TrackTap::for_mime_type("video/H264", 0).is_none());
+    }
+
+    #[tokio::test]
+    async fn test_disabled_without_dir() {
+        let recorder = SessionRecorder::new(None, "peer");
+        recorder.add_inbound("in:audio", TrackTap::new(RecordCodec::Opus { channels: 2 }));
+        assert!(matches!(
+            recorder.start(false).await,
+            Err(Error::RecordingError(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_record_inbound_and_outbound() {
+        let dir = temp_dir("roundtrip");
+        let recorder = SessionRecorder::new(Some(dir.clone()), "peer/1");
+        let inbound = TrackTap::new(RecordCodec::Opus { channels: 2 });
+        recorder.add_inbound("in:audio", Arc::clone(&inbound));
+        let outbound = TrackTap::new(RecordCodec::Vp8);
+
+        // Nothing is recorded before start
+        inbound.write_rtp(&opus_packet(0, 0));
+        assert!(!inbound.is_recording());
+
+        let info = recorder
+            .start_with(vec![(
+                "out:default:video".to_string(),
+                Arc::clone(&outbound),
+            )])
+            .await
+            .unwrap();
+        assert!(info.recording_id.starts_with("peer_1-"));
+        assert_eq!(info.tracks, ["in:audio", "out:default:video"]);
+        assert!(recorder.start(true).await.is_err());
+
+        // Interframes before the first keyframe are dropped
+        outbound.write_frame(&[0x01, 0x00, 0x00], false, None);
+        outbound.write_frame(
+            &[0x10, 0x02, 0x00, 0x9D, 0x01, 0x2A, 0x80, 0x02, 0x68, 0x01],
+            true,
+            Some((640, 360)),
+        );
+        for i in 0..50u16 {
+            inbound.write_rtp(&opus_packet(i, u32::from(i) * 960));
+        }
+
+        let summary = recorder.stop().await.unwrap();
+        assert!(!inbound.is_recording());
+        assert!(recorder.current().await.is_none());
+        assert!(summary.duration_ms >= 980);
+        let file = std::fs::read(&summary.path).unwrap();
+        assert_eq!(file.len() as u64, summary.size_bytes);
+        assert_eq!(&file[..4], &[0x1A, 0x45, 0xDF, 0xA3]);
+        let opus_frames = file.windows(3).filter(|w| *w == [0xFC, 0x01, 0x02]).count();
+        assert_eq!(opus_frames, 50);
+
+        let _ = std::fs::remove_dir_all(dir);
+    }
+}
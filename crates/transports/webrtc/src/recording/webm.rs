@@ -0,0 +1,489 @@
+//! Minimal Matroska/WebM writer
+//!
+//! Writes a live-style file: the header goes out first with placeholders
+//! for everything only known later (segment size, duration, video
+//! dimensions), frames are appended as `SimpleBlock`s in clusters, and
+//! [`WebmWriter::finish`] seeks back to fill the placeholders in.
+
+use std::io::{self, Seek, SeekFrom, Write};
+
+const EBML: u32 = 0x1A45_DFA3;
+const EBML_VERSION: u32 = 0x4286;
+const EBML_READ_VERSION: u32 = 0x42F7;
+const EBML_MAX_ID_LENGTH: u32 = 0x42F2;
+const EBML_MAX_SIZE_LENGTH: u32 = 0x42F3;
+const DOC_TYPE: u32 = 0x4282;
+const DOC_TYPE_VERSION: u32 = 0x4287;
+const DOC_TYPE_READ_VERSION: u32 = 0x4285;
+const SEGMENT: u32 = 0x1853_8067;
+const INFO: u32 = 0x1549_A966;
+const TIMESTAMP_SCALE: u32 = 0x2A_D7B1;
+const MUXING_APP: u32 = 0x4D80;
+const WRITING_APP: u32 = 0x5741;
+const DURATION: u32 = 0x4489;
+const TRACKS: u32 = 0x1654_AE6B;
+const TRACK_ENTRY: u32 = 0xAE;
+const TRACK_NUMBER: u32 = 0xD7;
+const TRACK_UID: u32 = 0x73C5;
+const TRACK_TYPE: u32 = 0x83;
+const FLAG_LACING: u32 = 0x9C;
+const NAME: u32 = 0x536E;
+const CODEC_ID: u32 = 0x86;
+const CODEC_PRIVATE: u32 = 0x63A2;
+const SEEK_PRE_ROLL: u32 = 0x56BB;
+const VIDEO: u32 = 0xE0;
+const PIXEL_WIDTH: u32 = 0xB0;
+const PIXEL_HEIGHT: u32 = 0xBA;
+const AUDIO: u32 = 0xE1;
+const SAMPLING_FREQUENCY: u32 = 0xB5;
+const CHANNELS: u32 = 0x9F;
+const CLUSTER: u32 = 0x1F43_B675;
+const CLUSTER_TIMESTAMP: u32 = 0xE7;
+const SIMPLE_BLOCK: u32 = 0xA3;
+
+/// Size field of an element whose size is not known yet
+const UNKNOWN_SIZE: [u8; 8] = [0x01, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF];
+
+/// Longest a cluster may span; block timestamps are 16-bit offsets
+const MAX_CLUSTER_SPAN_MS: u64 = 5_000;
+
+/// Codec of a recorded track, as carried on the wire (no transcoding)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RecordCodec {
+    Opus { channels: u16 },
+    Vp8,
+    Vp9,
+}
+
+impl RecordCodec {
+    fn codec_id(&self) -> &'static str {
+        match self {
+            RecordCodec::Opus { .. } => "A_OPUS",
+            RecordCodec::Vp8 => "V_VP8",
+            RecordCodec::Vp9 => "V_VP9",
+        }
+    }
+
+    /// Whether this is a video codec
+    pub fn is_video(&self) -> bool {
+        !matches!(self, RecordCodec::Opus { .. })
+    }
+}
+
+/// One track of the file
+#[derive(Debug, Clone)]
+pub struct TrackSpec {
+    /// Track number, starting at 1
+    pub number: u64,
+    pub name: String,
+    pub codec: RecordCodec,
+}
+
+struct TrackState {
+    number: u64,
+    is_video: bool,
+    /// File offset of the `PixelWidth` value (`PixelHeight` follows it)
+    size_offset: Option<u64>,
+    size_known: bool,
+}
+
+/// Streaming WebM writer
+pub struct WebmWriter<W: Write + Seek> {
+    out: W,
+    tracks: Vec<TrackState>,
+    /// Bytes written to `out`
+    position: u64,
+    /// File offset of the Segment size field
+    segment_size_offset: u64,
+    /// File offset of the first byte inside the Segment
+    segment_start: u64,
+    /// File offset of the Duration value
+    duration_offset: u64,
+    cluster: Vec<u8>,
+    cluster_timestamp: u64,
+    cluster_blocks: usize,
+    /// Latest timestamp written, in milliseconds
+    last_timestamp: u64,
+}
+
+impl<W: Write + Seek> WebmWriter<W> {
+    /// Write the EBML header, segment info and track list
+    pub fn new(mut out: W, tracks: &[TrackSpec]) -> io::Result<Self> {
+        let mut buf = Vec::new();
+        let ebml = begin(&mut buf, EBML);
+        uint(&mut buf, EBML_VERSION, 1);
+        uint(&mut buf, EBML_READ_VERSION, 1);
+        uint(&mut buf, EBML_MAX_ID_LENGTH, 4);
+        uint(&mut buf, EBML_MAX_SIZE_LENGTH, 8);
+        string(&mut buf, DOC_TYPE, "webm");
+        uint(&mut buf, DOC_TYPE_VERSION, 4);
+        uint(&mut buf, DOC_TYPE_READ_VERSION, 2);
+        end(&mut buf, ebml);
+
+        write_id(&mut buf, SEGMENT);
+        let segment_size_offset = buf.len() as u64;
+        buf.extend_from_slice(&UNKNOWN_SIZE);
+        let segment_start = buf.len() as u64;
+
+        let info = begin(&mut buf, INFO);
+        uint(&mut buf, TIMESTAMP_SCALE, 1_000_000); // milliseconds
+        string(&mut buf, MUXING_APP, "remotemedia");
+        string(&mut buf, WRITING_APP, "remotemedia-webrtc");
+        write_id(&mut buf, DURATION);
+        write_size(&mut buf, 8);
+        let duration_offset = buf.len() as u64;
+        buf.extend_from_slice(&0f64.to_be_bytes());
+        end(&mut buf, info);
+
+        let mut states = Vec::with_capacity(tracks.len());
+        let track_list = begin(&mut buf, TRACKS);
+        for track in tracks {
+            let entry = begin(&mut buf, TRACK_ENTRY);
+            uint(&mut buf, TRACK_NUMBER, track.number);
+            uint(&mut buf, TRACK_UID, track.number);
+            uint(
+                &mut buf,
+                TRACK_TYPE,
+                if track.codec.is_video() { 1 } else { 2 },
+            );
+            uint(&mut buf, FLAG_LACING, 0);
+            string(&mut buf, NAME, &track.name);
+            string(&mut buf, CODEC_ID, track.codec.codec_id());
+            let mut size_offset = None;
+            match track.codec {
+                RecordCodec::Opus { channels } => {
+                    bytes(&mut buf, CODEC_PRIVATE, &opus_head(channels));
+                    uint(&mut buf, SEEK_PRE_ROLL, 80_000_000);
+                    let audio = begin(&mut buf, AUDIO);
+                    float(&mut buf, SAMPLING_FREQUENCY, 48_000.0);
+                    uint(&mut buf, CHANNELS, u64::from(channels));
+                    end(&mut buf, audio);
+                }
+                RecordCodec::Vp8 | RecordCodec::Vp9 => {
+                    let video = begin(&mut buf, VIDEO);
+                    // Fixed 4-byte values so the real size can be patched in
+                    write_id(&mut buf, PIXEL_WIDTH);
+                    write_size(&mut buf, 4);
+                    size_offset = Some(buf.len() as u64);
+                    buf.extend_from_slice(&0u32.to_be_bytes());
+                    write_id(&mut buf, PIXEL_HEIGHT);
+                    write_size(&mut buf, 4);
+                    buf.extend_from_slice(&0u32.to_be_bytes());
+                    end(&mut buf, video);
+                }
+            }
+            end(&mut buf, entry);
+            states.push(TrackState {
+                number: track.number,
+                is_video: track.codec.is_video(),
+                size_offset,
+                size_known: false,
+            });
+        }
+        end(&mut buf, track_list);
+
+        out.write_all(&buf)?;
+        Ok(Self {
+            out,
+            tracks: states,
+            position: buf.len() as u64,
+            segment_size_offset,
+            segment_start,
+            duration_offset,
+            cluster: Vec::new(),
+            cluster_timestamp: 0,
+            cluster_blocks: 0,
+            last_timestamp: 0,
+        })
+    }
+
+    /// Record a video track's frame size; only the first call per track
+    /// takes effect
+    pub fn set_video_size(&mut self, track: u64, width: u32, height: u32) -> io::Result<()> {
+        let Some(state) = self.tracks.iter_mut().find(|t| t.number == track) else {
+            return Ok(());
+        };
+        let Some(offset) = state.size_offset.filter(|_| !state.size_known) else {
+            return Ok(());
+        };
+        state.size_known = true;
+        // PixelHeight's ID (1 byte) and size (1 byte) sit between the values
+        let mut patch = width.to_be_bytes().to_vec();
+        patch.extend_from_slice(&[PIXEL_HEIGHT as u8, 0x84]);
+        patch.extend_from_slice(&height.to_be_bytes());
+        self.patch(offset, &patch)
+    }
+
+    /// Append one frame
+    ///
+    /// `timestamp_ms` is relative to the start of the recording. A video
+    /// keyframe starts a new cluster so players can seek to it.
+    pub fn write_frame(
+        &mut self,
+        track: u64,
+        timestamp_ms: u64,
+        keyframe: bool,
+        data: &[u8],
+    ) -> io::Result<()> {
+        let is_video = self.tracks.iter().any(|t| t.number == track && t.is_video);
+        if self.cluster_blocks > 0
+            && ((keyframe && is_video)
+                || timestamp_ms < self.cluster_timestamp
+                || timestamp_ms - self.cluster_timestamp >= MAX_CLUSTER_SPAN_MS)
+        {
+            self.flush_cluster()?;
+        }
+        if self.cluster_blocks == 0 {
+            self.cluster_timestamp = timestamp_ms;
+            uint(&mut self.cluster, CLUSTER_TIMESTAMP, timestamp_ms);
+        }
+
+        let relative = (timestamp_ms - self.cluster_timestamp) as i16;
+        write_id(&mut self.cluster, SIMPLE_BLOCK);
+        write_size(&mut self.cluster, 4 + data.len() as u64);
+        write_size(&mut self.cluster, track);
+        self.cluster.extend_from_slice(&relative.to_be_bytes());
+        self.cluster.push(if keyframe { 0x80 } else { 0x00 });
+        self.cluster.extend_from_slice(data);
+        self.cluster_blocks += 1;
+        self.last_timestamp = self.last_timestamp.max(timestamp_ms);
+        Ok(())
+    }
+
+    /// Bytes written so far, excluding the open cluster
+    pub fn bytes_written(&self) -> u64 {
+        self.position
+    }
+
+    /// Duration so far, in milliseconds
+    pub fn duration_ms(&self) -> u64 {
+        self.last_timestamp
+    }
+
+    /// Write the last cluster and fill in the segment size and duration
+    pub fn finish(mut self) -> io::Result<W> {
+        self.flush_cluster()?;
+        let duration = (self.last_timestamp as f64).to_be_bytes();
+        self.patch(self.duration_offset, &duration)?;
+        let segment_size = self.position - self.segment_start;
+        let mut size = segment_size.to_be_bytes();
+        size[0] = 0x01; // 8-byte length marker
+        self.patch(self.segment_size_offset, &size)?;
+        self.out.flush()?;
+        Ok(self.out)
+    }
+
+    fn flush_cluster(&mut self) -> io::Result<()> {
+        if self.cluster_blocks == 0 {
+            return Ok(());
+        }
+        let mut header = Vec::with_capacity(12);
+        write_id(&mut header, CLUSTER);
+        write_size(&mut header, self.cluster.len() as u64);
+        self.out.write_all(&header)?;
+        self.out.write_all(&self.cluster)?;
+        self.position += (header.len() + self.cluster.len()) as u64;
+        self.cluster.clear();
+        self.cluster_blocks = 0;
+        Ok(())
+    }
+
+    fn patch(&mut self, offset: u64, data: &[u8]) -> io::Result<()> {
+        self.out.seek(SeekFrom::Start(offset))?;
+        self.out.write_all(data)?;
+        self.out.seek(SeekFrom::Start(self.position))?;
+        Ok(())
+    }
+}
+
+/// `OpusHead` identification header, the `CodecPrivate` of an Opus track
+fn opus_head(channels: u16) -> Vec<u8> {
+    let mut head = b"OpusHead".to_vec();
+    head.push(1); // version
+    head.push(channels as u8);
+    head.extend_from_slice(&0u16.to_le_bytes()); // pre-skip
+    head.extend_from_slice(&48_000u32.to_le_bytes()); // input sample rate
+    head.extend_from_slice(&0i16.to_le_bytes()); // output gain
+    head.push(0); // channel mapping family
+    head
+}
+
+/// Frame size from a VP8 keyframe header, or `None` for an interframe
+pub fn vp8_keyframe_size(frame: &[u8]) -> Option<(u32, u32)> {
+    if frame.len() < 10 || frame[0] & 0x01 != 0 || frame[3..6] != [0x9D, 0x01, 0x2A] {
+        return None;
+    }
+    let width = u16::from_le_bytes([frame[6], frame[7]]) & 0x3FFF;
+    let height = u16::from_le_bytes([frame[8], frame[9]]) & 0x3FFF;
+    Some((u32::from(width), u32::from(height)))
+}
+
+/// Whether a VP8 frame is a keyframe
+pub fn vp8_is_keyframe(frame: &[u8]) -> bool {
+    frame.first().is_some_and(|b| b & 0x01 == 0)
+}
+
+/// A master element whose size is filled in by [`end`]
+struct Master {
+    size_offset: usize,
+}
+
+fn begin(buf: &mut Vec<u8>, id: u32) -> Master {
+    write_id(buf, id);
+    let size_offset = buf.len();
+    buf.extend_from_slice(&UNKNOWN_SIZE);
+    Master { size_offset }
+}
+
+fn end(buf: &mut [u8], master: Master) {
+    let size = (buf.len() - master.size_offset - 8) as u64;
+    let mut encoded = size.to_be_bytes();
+    encoded[0] = 0x01;
+    buf[master.size_offset..master.size_offset + 8].copy_from_slice(&encoded);
+}
+
+fn write_id(buf: &mut Vec<u8>, id: u32) {
+    let bytes = id.to_be_bytes();
+    let skip = bytes.iter().take_while(|&&b| b == 0).count();
+    buf.extend_from_slice(&bytes[skip..]);
+}
+
+/// EBML variable-length size, in the fewest bytes that fit
+fn write_size(buf: &mut Vec<u8>, size: u64) {
+    let mut len = 1;
+    // All-ones is reserved for "unknown", hence the `- 1`
+    while len < 8 && size >= (1u64 << (7 * len)) - 1 {
+        len += 1;
+    }
+    let marked = size | (1u64 << (7 * len));
+    buf.extend_from_slice(&marked.to_be_bytes()[8 - len..]);
+}
+
+fn uint(buf: &mut Vec<u8>, id: u32, value: u64) {
+    let bytes = value.to_be_bytes();
+    let skip = bytes.iter().take_while(|&&b| b == 0).count().min(7);
+    write_id(buf, id);
+    write_size(buf, (8 - skip) as u64);
+    buf.extend_from_slice(&bytes[skip..]);
+}
+
+fn float(buf: &mut Vec<u8>, id: u32, value: f64) {
+    write_id(buf, id);
+    write_size(buf, 8);
+    buf.extend_from_slice(&value.to_be_bytes());
+}
+
+fn string(buf: &mut Vec<u8>, id: u32, value: &str) {
+    bytes(buf, id, value.as_bytes());
+}
+
+fn bytes(buf: &mut Vec<u8>, id: u32, value: &[u8]) {
+    write_id(buf, id);
+    write_size(buf, value.len() as u64);
+    buf.extend_from_slice(value);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    fn tracks() -> Vec<TrackSpec> {
+        vec![
+            TrackSpec {
+                number: 1,
+                name: "in:audio".to_string(),
+                codec: RecordCodec::Opus { channels: 2 },
+            },
+            TrackSpec {
+                number: 2,
+                name: "in:video".to_string(),
+                codec: RecordCodec::Vp8,
+            },
+        ]
+    }
+
+    fn find(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+        haystack.windows(needle.len()).position(|w| w == needle)
+    }
+
+    #[test]
+    fn test_size_encoding() {
+        let mut buf = Vec::new();
+        write_size(&mut buf, 5);
+        write_size(&mut buf, 127);
+        write_size(&mut buf, 300);
+        assert_eq!(buf, [0x85, 0x40, 0x7F, 0x41, 0x2C]);
+    }
+
+    #[test]
+    fn test_header_and_clusters() {
+        let mut writer = WebmWriter::new(Cursor::new(Vec::new()), &tracks()).unwrap();
+        writer.write_frame(1, 0, true, &[0xAA; 3]).unwrap();
+        writer.write_frame(1, 20, true, &[0xAB; 3]).unwrap();
+        // A video keyframe opens a new cluster
+        writer.write_frame(2, 30, true, &[0x10; 4]).unwrap();
+        writer.set_video_size(2, 640, 360).unwrap();
+        writer.write_frame(2, 63, false, &[0x11; 4]).unwrap();
+        let file = writer.finish().unwrap().into_inner();
+
+        assert_eq!(&file[..4], &[0x1A, 0x45, 0xDF, 0xA3]);
+        assert!(find(&file, b"webm").is_some());
+        assert!(find(&file, b"OpusHead").is_some());
+        assert!(find(&file, b"V_VP8").is_some());
+        assert_eq!(
+            file.windows(4)
+                .filter(|w| *w == [0x1F, 0x43, 0xB6, 0x75])
+                .count(),
+            2
+        );
+
+        // Video size patched in place
+        let width = find(&file, &[0xB0, 0x84]).unwrap();
+        assert_eq!(&file[width + 2..width + 6], &640u32.to_be_bytes());
+        assert_eq!(&file[width + 6..width + 8], &[0xBA, 0x84]);
+        assert_eq!(&file[width + 8..width + 12], &360u32.to_be_bytes());
+
+        // Segment size covers the rest of the file
+        let segment = find(&file, &[0x18, 0x53, 0x80, 0x67]).unwrap();
+        let mut size = [0u8; 8];
+        size.copy_from_slice(&file[segment + 4..segment + 12]);
+        assert_eq!(size[0], 0x01);
+        size[0] = 0;
+        assert_eq!(u64::from_be_bytes(size) as usize, file.len() - segment - 12);
+
+        // Duration is the last timestamp
+        let duration = find(&file, &[0x44, 0x89, 0x88]).unwrap();
+        let mut value = [0u8; 8];
+        value.copy_from_slice(&file[duration + 3..duration + 11]);
+        assert_eq!(f64::from_be_bytes(value), 63.0);
+
+        // Second block of the video cluster: relative timestamp 33, no key flag
+        let block = find(&file, &[0xA3, 0x88, 0x82, 0x00, 0x21, 0x00]).unwrap();
+        assert_eq!(&file[block + 6..block + 10], &[0x11; 4]);
+    }
+
+    #[test]
+    fn test_long_gaps_split_clusters() {
+        let mut writer = WebmWriter::new(Cursor::new(Vec::new()), &tracks()[..1]).unwrap();
+        for i in 0..12u64 {
+            writer.write_frame(1, i * 1_000, true, &[0]).unwrap();
+        }
+        let file = writer.finish().unwrap().into_inner();
+        let clusters = file
+            .windows(4)
+            .filter(|w| *w == [0x1F, 0x43, 0xB6, 0x75])
+            .count();
+        assert_eq!(clusters, 3);
+    }
+
+    #[test]
+    fn test_vp8_keyframe_header() {
+        let key = [0x10, 0x02, 0x00, 0x9D, 0x01, 0x2A, 0x80, 0x02, 0x68, 0x01];
+        assert!(vp8_is_keyframe(&key));
+        assert_eq!(vp8_keyframe_size(&key), Some((640, 360)));
+        let inter = [0x11, 0x02, 0x00, 0x00];
+        assert!(!vp8_is_keyframe(&inter));
+        assert_eq!(vp8_keyframe_size(&inter), None);
+    }
+}
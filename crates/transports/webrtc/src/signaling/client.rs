@@ -244,7 +244,12 @@ impl SignalingClient {
         })?;
 
         let msg = SignalingMessage::PeerOffer {
-            params: PeerOfferParams { from, to, sdp },
+            params: PeerOfferParams {
+                from,
+                to,
+                sdp,
+                traceparent: None,
+            },
             id: Some(uuid::Uuid::new_v4().to_string()),
         };
 
@@ -11,7 +11,11 @@ use crate::generated::webrtc::{
     *,
 };
 use crate::peer::ServerPeer;
-use remotemedia_core::{manifest::Manifest, transport::PipelineExecutor};
+use remotemedia_core::{
+    auth::{self, AuthGuard, Principal, Scope},
+    manifest::Manifest,
+    transport::PipelineExecutor,
+};
 use std::collections::HashMap;
 use std::pin::Pin;
 use std::sync::Arc;
@@ -53,6 +57,9 @@ pub struct WebRtcSignalingService {
     /// Pipeline manifest
     manifest: Arc<Manifest>,
 
+    /// API key authentication for signaling streams
+    auth: AuthGuard,
+
     /// Server start time for uptime tracking
     start_time: SystemTime,
 }
@@ -82,10 +89,18 @@ impl WebRtcSignalingService {
             config,
             executor,
             manifest,
+            auth: AuthGuard::open(),
             start_time: SystemTime::now(),
         }
     }
 
+    /// Require an API key with the stream scope on `Signal`; the key's
+    /// tenant owns the sessions its offers create
+    pub fn with_auth(mut self, auth: AuthGuard) -> Self {
+        self.auth = auth;
+        self
+    }
+
     /// Register a server-side peer that will handle all client offers
     ///
     /// This creates a virtual "remotemedia-server" peer that clients can send offers to.
@@ -188,6 +203,16 @@ impl WebRtcSignaling for WebRtcSignalingService {
         &self,
         request: Request<tonic::Streaming<SignalingRequest>>,
     ) -> Result<Response<Self::SignalStream>, Status> {
+        let token = request
+            .metadata()
+            .get("authorization")
+            .and_then(|v| v.to_str().ok())
+            .and_then(auth::bearer_token);
+        let principal = self
+            .auth
+            .authorize(token, Scope::Stream)
+            .map_err(Status::from)?;
+
         let mut in_stream = request.into_inner();
         let (tx, rx) = mpsc::channel(128);
 
@@ -214,6 +239,7 @@ impl WebRtcSignaling for WebRtcSignalingService {
                             &config,
                             &executor,
                             &manifest,
+                            &principal,
                             &tx,
                             &mut peer_id,
                         )
@@ -331,6 +357,7 @@ impl WebRtcSignalingService {
         config: &Arc<WebRtcTransportConfig>,
         executor: &Arc<PipelineExecutor>,
         manifest: &Arc<Manifest>,
+        principal: &Principal,
         tx: &mpsc::Sender<Result<SignalingResponse, Status>>,
         peer_id: &mut Option<String>,
     ) -> Result<(), Status> {
@@ -349,6 +376,7 @@ impl WebRtcSignalingService {
                     config,
                     executor,
                     manifest,
+                    principal,
                     tx,
                     peer_id,
                     &request_id,
@@ -468,6 +496,7 @@ impl WebRtcSignalingService {
         config: &Arc<WebRtcTransportConfig>,
         executor: &Arc<PipelineExecutor>,
         manifest: &Arc<Manifest>,
+        principal: &Principal,
         tx: &mpsc::Sender<Result<SignalingResponse, Status>>,
         from_peer_id: &Option<String>,
         request_id: &str,
@@ -500,6 +529,7 @@ impl WebRtcSignalingService {
         // Check if target is remotemedia-server
         if offer.to_peer_id == "remotemedia-server" {
             info!("Creating ServerPeer for offer from {}", from_peer_id);
+            principal.check_manifest(manifest).map_err(Status::from)?;
 
             // CRITICAL: Clean up any existing ServerPeer for this peer_id first
             // This prevents stale sessions from processing old data
@@ -527,7 +557,7 @@ impl WebRtcSignalingService {
             )
             .await
             {
-                Ok(peer) => Arc::new(peer),
+                Ok(peer) => Arc::new(peer.with_principal(Some(principal.clone()))),
                 Err(e) => {
                     error!("Failed to create ServerPeer: {}", e);
                     return Err(Status::internal(format!(
@@ -540,6 +570,24 @@ impl WebRtcSignalingService {
             // Handle offer and get SDP answer
             let answer_sdp = match server_peer.handle_offer(offer.sdp).await {
                 Ok(sdp) => sdp,
+                Err(crate::Error::SessionRejected {
+                    resource,
+                    message,
+                    retry_after_ms,
+                }) => {
+                    warn!(
+                        "Offer from {} rejected by admission control: {}",
+                        from_peer_id, message
+                    );
+                    let mut status = Status::resource_exhausted(format!(
+                        "Session rejected ({}): {}",
+                        resource, message
+                    ));
+                    status
+                        .metadata_mut()
+                        .insert("retry-after-ms", retry_after_ms.into());
+                    return Err(status);
+                }
                 Err(e) => {
                     error!("ServerPeer failed to handle offer: {}", e);
                     return Err(Status::internal(format!("Failed to handle offer: {}", e)));
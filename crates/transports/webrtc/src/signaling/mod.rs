@@ -4,6 +4,7 @@
 //! - WebSocket-based JSON-RPC 2.0 (default)
 //! - WebSocket signaling server (ws-signaling feature)
 //! - gRPC bidirectional streaming (optional, requires `grpc-signaling` feature)
+//! - WHIP ingest / WHEP playback over HTTP (optional, requires `whip` feature)
 
 pub mod client;
 pub mod connection;
@@ -15,6 +16,9 @@ pub mod websocket;
 #[cfg(feature = "grpc-signaling")]
 pub mod grpc;
 
+#[cfg(feature = "whip")]
+pub mod whip;
+
 pub use client::SignalingClient;
 pub use protocol::IceCandidateParams;
 
@@ -26,3 +30,6 @@ pub use websocket::{
 
 #[cfg(feature = "grpc-signaling")]
 pub use grpc::WebRtcSignalingService;
+
+#[cfg(feature = "whip")]
+pub use whip::WhipServer;
@@ -107,6 +107,9 @@ pub mod error_codes {
 
     /// Session limit exceeded
     pub const SESSION_LIMIT_EXCEEDED: i32 = -32001;
+
+    /// API key lacks the scope or manifest permission for this request
+    pub const FORBIDDEN: i32 = -32008;
 }
 
 /// Signaling message types
@@ -189,6 +192,10 @@ pub struct PeerOfferParams {
 
     /// SDP offer
     pub sdp: String,
+
+    /// W3C `traceparent` the session's node spans should join
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub traceparent: Option<String>,
 }
 
 /// Parameters for peer.answer
@@ -428,6 +435,7 @@ mod tests {
                 from: "peer-alice".to_string(),
                 to: "peer-bob".to_string(),
                 sdp: "v=0\r\no=- ...".to_string(),
+                traceparent: None,
             },
             id: Some("offer-1".to_string()),
         };
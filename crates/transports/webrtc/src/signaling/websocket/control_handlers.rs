@@ -13,6 +13,17 @@
 //!   `{ "binary_b64": "..." }` and is coerced into `RuntimeData`.
 //! - `control.set_node_state { node_id, state }` — flip a node between
 //!   `enabled`, `bypass`, `disabled`. Returns `{ success: true }`.
+//! - `control.apply_patch { ops: [...] }` — edit the running pipeline;
+//!   params are a `PipelinePatch`. Returns the `PatchReport` once the
+//!   patch is committed, or an error if it was rejected (the pipeline is
+//!   then unchanged). A patch that brings in a manifest or node type the
+//!   connection's API key may not run fails with `FORBIDDEN`.
+//! - `control.start_recording { include_outbound?: bool }` — record the
+//!   peer's Opus/VP8 tracks (and, with `include_outbound`, the pipeline's
+//!   tracks) to WebM under the server's `recording_dir`. Returns
+//!   `{ recording_id, path, tracks }`.
+//! - `control.stop_recording` — finalize the running recording. Returns
+//!   `{ recording_id, path, duration_ms, size_bytes }`.
 //!
 //! # Notifications
 //!
@@ -48,9 +59,12 @@
 //! - `vad.out`               — main output of node `vad`
 
 use super::handler::SharedState;
+use crate::signaling::protocol::error_codes;
+use remotemedia_core::auth::AuthError;
 use remotemedia_core::data::{split_text_str, RuntimeData};
+use remotemedia_core::transport::pipeline_patch::PipelinePatch;
 use remotemedia_core::transport::session_control::{
-    ControlAddress, Direction, NodeState, SessionControl,
+    ControlAddress, ControlAuth, Direction, NodeState, SessionControl,
 };
 use serde_json::{json, Value};
 use std::collections::HashMap;
@@ -66,11 +80,16 @@ use tracing::{debug, warn};
 pub struct ControlSessionState {
     /// Active subscription forwarder tasks, keyed by topic.
     tasks: RwLock<HashMap<String, JoinHandle<()>>>,
+    /// The connection's API key; patches are checked against it.
+    auth: ControlAuth,
 }
 
 impl ControlSessionState {
-    pub fn new() -> Arc<Self> {
-        Arc::new(Self::default())
+    pub fn new(auth: ControlAuth) -> Arc<Self> {
+        Arc::new(Self {
+            tasks: RwLock::default(),
+            auth,
+        })
     }
 
     /// Abort every forwarder task this connection owns. Called on
@@ -84,6 +103,32 @@ impl ControlSessionState {
     }
 }
 
+/// A failed `control.*` call, answered as a JSON-RPC error
+#[derive(Debug)]
+pub struct ControlMethodError {
+    /// JSON-RPC error code, from [`error_codes`]
+    pub code: i32,
+    pub message: String,
+}
+
+impl From<String> for ControlMethodError {
+    fn from(message: String) -> Self {
+        Self {
+            code: error_codes::INVALID_PARAMS,
+            message,
+        }
+    }
+}
+
+impl From<AuthError> for ControlMethodError {
+    fn from(err: AuthError) -> Self {
+        Self {
+            code: error_codes::FORBIDDEN,
+            message: err.to_string(),
+        }
+    }
+}
+
 // ─── Topic parsing ──────────────────────────────────────────────────────
 
 struct ParsedTopic {
@@ -169,32 +214,47 @@ pub async fn handle_control_method(
     peer_id: &str,
     tx: &mpsc::Sender<String>,
     control_state: &Arc<ControlSessionState>,
-) -> Option<Result<String, String>> {
+) -> Option<Result<String, ControlMethodError>> {
     match method {
         "control.subscribe" => Some(
             handle_subscribe(params, request_id, state, peer_id, tx, control_state)
                 .await
-                .map_err(|e| e.to_string()),
+                .map_err(ControlMethodError::from),
         ),
         "control.unsubscribe" => Some(
             handle_unsubscribe(params, request_id, control_state)
                 .await
-                .map_err(|e| e.to_string()),
+                .map_err(ControlMethodError::from),
         ),
         "control.publish" => Some(
             handle_publish(params, request_id, state, peer_id)
                 .await
-                .map_err(|e| e.to_string()),
+                .map_err(ControlMethodError::from),
         ),
         "control.set_node_state" => Some(
             handle_set_node_state(params, request_id, state, peer_id)
                 .await
-                .map_err(|e| e.to_string()),
+                .map_err(ControlMethodError::from),
+        ),
+        "control.apply_patch" => Some(
+            handle_apply_patch(params, request_id, state, peer_id, control_state)
+                .await
+                .map_err(ControlMethodError::from),
         ),
         "control.flush_audio" => Some(
             handle_flush_audio(request_id, state, peer_id)
                 .await
-                .map_err(|e| e.to_string()),
+                .map_err(ControlMethodError::from),
+        ),
+        "control.start_recording" => Some(
+            handle_start_recording(params, request_id, state, peer_id)
+                .await
+                .map_err(ControlMethodError::from),
+        ),
+        "control.stop_recording" => Some(
+            handle_stop_recording(request_id, state, peer_id)
+                .await
+                .map_err(ControlMethodError::from),
         ),
         _ => None,
     }
@@ -225,6 +285,61 @@ async fn handle_flush_audio(
     .to_string())
 }
 
+async fn handle_start_recording(
+    params: &Value,
+    request_id: &Value,
+    state: &Arc<SharedState>,
+    peer_id: &str,
+) -> Result<String, String> {
+    // Like flush_audio, recording lives on the ServerPeer: it needs the
+    // peer's RTP, which never reaches the SessionControl.
+    let include_outbound = params
+        .get("include_outbound")
+        .and_then(Value::as_bool)
+        .unwrap_or(false);
+    let server_peer = {
+        let peers = state.server_peers.read().await;
+        peers.get(peer_id).cloned()
+    };
+    let server_peer = server_peer.ok_or_else(|| {
+        format!("peer {peer_id:?} has no ServerPeer (offer not yet negotiated)")
+    })?;
+    let info = server_peer
+        .start_recording(include_outbound)
+        .await
+        .map_err(|e| format!("control.start_recording: {e}"))?;
+    Ok(json!({
+        "jsonrpc": "2.0",
+        "result": info,
+        "id": request_id,
+    })
+    .to_string())
+}
+
+async fn handle_stop_recording(
+    request_id: &Value,
+    state: &Arc<SharedState>,
+    peer_id: &str,
+) -> Result<String, String> {
+    let server_peer = {
+        let peers = state.server_peers.read().await;
+        peers.get(peer_id).cloned()
+    };
+    let server_peer = server_peer.ok_or_else(|| {
+        format!("peer {peer_id:?} has no ServerPeer (offer not yet negotiated)")
+    })?;
+    let summary = server_peer
+        .stop_recording()
+        .await
+        .map_err(|e| format!("control.stop_recording: {e}"))?;
+    Ok(json!({
+        "jsonrpc": "2.0",
+        "result": summary,
+        "id": request_id,
+    })
+    .to_string())
+}
+
 // ─── Individual methods ─────────────────────────────────────────────────
 
 async fn handle_subscribe(
@@ -397,6 +512,30 @@ async fn handle_set_node_state(
     .to_string())
 }
 
+async fn handle_apply_patch(
+    params: &Value,
+    request_id: &Value,
+    state: &Arc<SharedState>,
+    peer_id: &str,
+    control_state: &Arc<ControlSessionState>,
+) -> Result<String, ControlMethodError> {
+    let patch: PipelinePatch = serde_json::from_value(params.clone())
+        .map_err(|e| format!("control.apply_patch: invalid patch: {e}"))?;
+
+    let ctrl = resolve_session_control(state, peer_id).await?;
+    control_state.auth.check_patch(&ctrl, &patch)?;
+    let report = ctrl
+        .apply_patch(patch)
+        .await
+        .map_err(|e| format!("control.apply_patch: {e}"))?;
+    Ok(json!({
+        "jsonrpc": "2.0",
+        "result": report,
+        "id": request_id,
+    })
+    .to_string())
+}
+
 // ─── Payload coercion ───────────────────────────────────────────────────
 
 fn coerce_payload_to_runtime_data(payload: &Value) -> Result<RuntimeData, String> {
@@ -1,10 +1,16 @@
 //! WebSocket message handler for JSON-RPC 2.0 signaling
 //!
 //! Handles individual WebSocket connections and processes JSON-RPC messages.
+//!
+//! Connections authenticate during the upgrade, with an
+//! `Authorization: Bearer <key>` header or a `?token=<key>` query parameter
+//! (browsers cannot set headers on WebSocket). The key's tenant owns the
+//! sessions its offers create.
 
 use super::control_handlers::{handle_control_method, ControlSessionState};
 use super::events::WebRtcEventBridge;
 use crate::config::WebRtcTransportConfig;
+use crate::error::Error;
 use crate::peer::ServerPeer;
 use crate::signaling::protocol::{
     error_codes, IceCandidateParams, JsonRpcError, JsonRpcRequest, JsonRpcResponse,
@@ -12,7 +18,12 @@ use crate::signaling::protocol::{
     PeerStateChangeParams,
 };
 use futures_util::{SinkExt, StreamExt};
-use remotemedia_core::{manifest::Manifest, transport::PipelineExecutor};
+use remotemedia_core::{
+    auth::{self, AuthError, AuthGuard, Principal, Scope},
+    manifest::Manifest,
+    telemetry::TraceContext,
+    transport::{session_control::ControlAuth, PipelineExecutor},
+};
 use serde_json::{json, Value};
 use std::collections::HashMap;
 use std::sync::Arc;
@@ -20,8 +31,12 @@ use std::time::SystemTime;
 use tokio::net::TcpStream;
 use tokio::sync::{mpsc, RwLock};
 use tokio_tungstenite::{
-    accept_async,
-    tungstenite::{Message, Result as WsResult},
+    accept_hdr_async,
+    tungstenite::{
+        handshake::server::{ErrorResponse, Request, Response},
+        http::StatusCode,
+        Message, Result as WsResult,
+    },
 };
 use tracing::{debug, error, info, warn};
 
@@ -87,11 +102,26 @@ impl SharedState {
 pub async fn handle_connection(
     stream: TcpStream,
     state: Arc<SharedState>,
+    auth: AuthGuard,
 ) -> WsResult<()> {
     let addr = stream.peer_addr()?;
     info!("New WebSocket connection from: {}", addr);
 
-    let ws_stream = accept_async(stream).await?;
+    let mut principal = None;
+    let ws_stream = accept_hdr_async(stream, |req: &Request, resp: Response| {
+        match auth.authorize(upgrade_token(req).as_deref(), Scope::Stream) {
+            Ok(p) => {
+                principal = Some(p);
+                Ok(resp)
+            }
+            Err(e) => {
+                warn!("Rejected WebSocket connection from {}: {}", addr, e);
+                Err(upgrade_rejection(&e))
+            }
+        }
+    })
+    .await?;
+    let principal = principal.expect("principal is set when the handshake succeeds");
 
     let (ws_tx, ws_rx) = ws_stream.split();
 
@@ -103,7 +133,8 @@ pub async fn handle_connection(
 
     // Per-connection control-plane subscriptions. Dropped on disconnect
     // to abort forwarder tasks and release broadcast receivers.
-    let control_state: Arc<ControlSessionState> = ControlSessionState::new();
+    let control_state: Arc<ControlSessionState> =
+        ControlSessionState::new(ControlAuth::new(principal.clone()));
 
     // Task to forward messages from channel to WebSocket
     let ws_tx = Arc::new(RwLock::new(ws_tx));
@@ -127,9 +158,15 @@ pub async fn handle_connection(
     while let Some(msg) = ws_rx.next().await {
         match msg {
             Ok(Message::Text(text)) => {
-                if let Err(e) =
-                    handle_message(&text, &state_clone, &peer_id_clone, &tx_clone, &control_state)
-                        .await
+                if let Err(e) = handle_message(
+                    &text,
+                    &state_clone,
+                    &principal,
+                    &peer_id_clone,
+                    &tx_clone,
+                    &control_state,
+                )
+                .await
                 {
                     error!("Error handling message: {}", e);
                     // Send error response
@@ -200,6 +237,7 @@ pub async fn handle_connection(
 async fn handle_message(
     text: &str,
     state: &Arc<SharedState>,
+    principal: &Principal,
     peer_id: &Arc<RwLock<Option<String>>>,
     tx: &mpsc::Sender<String>,
     control_state: &Arc<ControlSessionState>,
@@ -224,6 +262,15 @@ async fn handle_message(
     // peer's session. They require peer.announce to have run and the
     // SDP exchange to have finished (session_id populated).
     if request.method.starts_with("control.") {
+        if !principal.has_scope(Scope::Control) {
+            let error = JsonRpcError::new(
+                error_codes::FORBIDDEN,
+                format!("API key '{}' lacks the 'control' scope", principal.key_id),
+                request_id,
+            );
+            tx.send(error.to_json()?).await?;
+            return Ok(());
+        }
         let current_peer_id = peer_id.read().await.clone();
         let current_peer_id = match current_peer_id {
             Some(id) => id,
@@ -253,9 +300,8 @@ async fn handle_message(
                 Ok(response_json) => {
                     tx.send(response_json).await?;
                 }
-                Err(msg) => {
-                    let error =
-                        JsonRpcError::new(error_codes::INVALID_PARAMS, msg, request_id);
+                Err(err) => {
+                    let error = JsonRpcError::new(err.code, err.message, request_id);
                     tx.send(error.to_json()?).await?;
                 }
             }
@@ -268,7 +314,7 @@ async fn handle_message(
             handle_announce(request, state, peer_id, tx).await?;
         }
         "peer.offer" => {
-            handle_offer(request, state, peer_id, tx).await?;
+            handle_offer(request, state, principal, peer_id, tx).await?;
         }
         "peer.answer" => {
             handle_answer(request, state, peer_id, tx).await?;
@@ -372,6 +418,7 @@ async fn handle_announce(
 async fn handle_offer(
     request: JsonRpcRequest,
     state: &Arc<SharedState>,
+    principal: &Principal,
     peer_id_ref: &Arc<RwLock<Option<String>>>,
     tx: &mpsc::Sender<String>,
 ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
@@ -420,6 +467,13 @@ async fn handle_offer(
             return Ok(());
         }
 
+        // API keys may be restricted to certain manifests or node types
+        if let Err(e) = principal.check_manifest(&state.manifest) {
+            let error = JsonRpcError::new(error_codes::FORBIDDEN, e.to_string(), request_id);
+            tx.send(error.to_json()?).await?;
+            return Ok(());
+        }
+
         // Clean up any existing ServerPeer
         if let Some(old_server_peer) = state.server_peers.write().await.remove(&from_peer_id) {
             info!("Shutting down existing ServerPeer for peer {}", from_peer_id);
@@ -428,6 +482,9 @@ async fn handle_offer(
             }
         }
 
+        // Node spans join the client's trace if the offer names one
+        let trace_context = params.traceparent.as_deref().and_then(TraceContext::parse);
+
         // Create ServerPeer with event forwarding if configured
         let server_peer: Arc<ServerPeer> = match ServerPeer::new_with_events(
             from_peer_id.clone(),
@@ -438,7 +495,10 @@ async fn handle_offer(
         )
         .await
         {
-            Ok(peer) => Arc::new(peer),
+            Ok(peer) => Arc::new(
+                peer.with_trace_context(trace_context)
+                    .with_principal(Some(principal.clone())),
+            ),
             Err(e) => {
                 error!("Failed to create ServerPeer: {}", e);
                 let error = JsonRpcError::new(
@@ -454,6 +514,29 @@ async fn handle_offer(
         // Handle offer and get SDP answer
         let answer_sdp: String = match server_peer.handle_offer(params.sdp).await {
             Ok(sdp) => sdp,
+            Err(Error::SessionRejected {
+                resource,
+                message,
+                retry_after_ms,
+            }) => {
+                warn!(
+                    "Offer from {} rejected by admission control: {}",
+                    from_peer_id, message
+                );
+                let error = JsonRpcError::with_data(
+                    error_codes::SESSION_LIMIT_EXCEEDED,
+                    format!("Session rejected: {}", message),
+                    json!({
+                        "reason": message,
+                        "peer_id": from_peer_id,
+                        "resource": resource,
+                        "retry_after_ms": retry_after_ms,
+                    }),
+                    request_id,
+                );
+                tx.send(error.to_json()?).await?;
+                return Ok(());
+            }
             Err(e) => {
                 error!("ServerPeer failed to handle offer: {}", e);
                 let error = JsonRpcError::with_data(
@@ -964,3 +1047,62 @@ fn current_timestamp() -> u64 {
         .unwrap_or_default()
         .as_secs()
 }
+
+/// API key presented on the WebSocket upgrade request
+fn upgrade_token(req: &Request) -> Option<String> {
+    let header = req
+        .headers()
+        .get("authorization")
+        .and_then(|v| v.to_str().ok())
+        .and_then(auth::bearer_token);
+    if let Some(token) = header {
+        return Some(token.to_string());
+    }
+    req.uri()
+        .query()?
+        .split('&')
+        .find_map(|pair| pair.strip_prefix("token="))
+        .filter(|token| !token.is_empty())
+        .map(str::to_string)
+}
+
+/// HTTP response refusing the upgrade: `401`, `403` or `429`
+fn upgrade_rejection(err: &AuthError) -> ErrorResponse {
+    let status =
+        StatusCode::from_u16(err.http_status()).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR);
+    let mut resp = ErrorResponse::new(Some(err.to_string()));
+    *resp.status_mut() = status;
+    resp
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_upgrade_token() {
+        let req = Request::builder()
+            .uri("/ws?peer=a&token=query-key")
+            .body(())
+            .unwrap();
+        assert_eq!(upgrade_token(&req).as_deref(), Some("query-key"));
+
+        let req = Request::builder()
+            .uri("/ws?token=query-key")
+            .header("authorization", "Bearer header-key")
+            .body(())
+            .unwrap();
+        assert_eq!(upgrade_token(&req).as_deref(), Some("header-key"));
+
+        let req = Request::builder().uri("/ws").body(()).unwrap();
+        assert_eq!(upgrade_token(&req), None);
+    }
+
+    #[test]
+    fn test_upgrade_rejection_status() {
+        let resp = upgrade_rejection(&AuthError::MissingCredentials);
+        assert_eq!(resp.status(), StatusCode::UNAUTHORIZED);
+        let resp = upgrade_rejection(&AuthError::Forbidden("no".to_string()));
+        assert_eq!(resp.status(), StatusCode::FORBIDDEN);
+    }
+}
@@ -9,7 +9,7 @@
 use super::events::WebRtcEventBridge;
 use super::handler::{handle_connection, SharedState};
 use crate::config::WebRtcTransportConfig;
-use remotemedia_core::{manifest::Manifest, transport::PipelineExecutor};
+use remotemedia_core::{auth::AuthGuard, manifest::Manifest, transport::PipelineExecutor};
 use std::net::SocketAddr;
 use std::sync::Arc;
 use std::thread;
@@ -24,6 +24,9 @@ pub struct WebSocketSignalingServer {
 
     /// Shared state
     state: Arc<SharedState>,
+
+    /// API key authentication for incoming connections
+    auth: AuthGuard,
 }
 
 impl WebSocketSignalingServer {
@@ -51,7 +54,17 @@ impl WebSocketSignalingServer {
         let addr: SocketAddr = format!("0.0.0.0:{}", port).parse().unwrap();
         let state = Arc::new(SharedState::new_with_events(config, runner, manifest, event_tx));
 
-        Self { addr, state }
+        Self {
+            addr,
+            state,
+            auth: AuthGuard::open(),
+        }
+    }
+
+    /// Require an API key on the WebSocket upgrade
+    pub fn with_auth(mut self, auth: AuthGuard) -> Self {
+        self.auth = auth;
+        self
     }
 
     /// Get shared state (for external access)
@@ -66,6 +79,7 @@ impl WebSocketSignalingServer {
     pub async fn start(self) -> Result<WebSocketServerHandle, std::io::Error> {
         let addr = self.addr;
         let state = Arc::clone(&self.state);
+        let auth = self.auth.clone();
 
         // Channel to receive the startup result
         let (startup_tx, startup_rx) = oneshot::channel::<Result<(), std::io::Error>>();
@@ -145,8 +159,9 @@ impl WebSocketSignalingServer {
                                 Ok((stream, peer_addr)) => {
                                     info!("Accepted WebSocket connection from {}", peer_addr);
                                     let state_clone = Arc::clone(&state);
+                                    let auth = auth.clone();
                                     tokio::spawn(async move {
-                                        if let Err(e) = handle_connection(stream, state_clone).await {
+                                        if let Err(e) = handle_connection(stream, state_clone, auth).await {
                                             error!("WebSocket connection error from {}: {}", peer_addr, e);
                                         }
                                     });
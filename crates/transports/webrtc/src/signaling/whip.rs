@@ -0,0 +1,605 @@
+//! WHIP ingest and WHEP playback over HTTP
+//!
+//! Standard HTTP signaling for tools that don't speak the custom
+//! WebSocket/gRPC protocol (OBS, GStreamer `whipsink`, ffmpeg, WHEP players):
+//!
+//! - `POST /whip` — SDP offer in, SDP answer out. Creates a [`ServerPeer`]
+//!   and a pipeline session fed by the client's media.
+//! - `POST /whep` — playback of a fresh pipeline session's output tracks.
+//! - `POST /whep/sessions/:session_id` — playback of an existing session's
+//!   output (e.g. the one a WHIP publisher is feeding).
+//! - `DELETE /whip/resources/:id`, `DELETE /whep/resources/:id` — teardown.
+//!
+//! Offers must be `application/sdp`. Answers are returned with
+//! `201 Created`, the resource URL in `Location` and the pipeline session id
+//! in `X-RemoteMedia-Session`. Trickle ICE is not supported: the answer
+//! already carries the server's candidates. When API keys are configured
+//! every request must present one with the stream scope; a key only sees
+//! the resources of its own tenant. A `traceparent` header on `POST /whip`
+//! puts the ingest session's node spans in the caller's trace.
+
+use crate::config::WebRtcTransportConfig;
+use crate::peer::ServerPeer;
+use crate::{Error, Result};
+use axum::body::Bytes;
+use axum::extract::{Path, State};
+use axum::http::{header, HeaderMap, HeaderValue, StatusCode};
+use axum::response::{IntoResponse, Response};
+use axum::routing::{delete, post};
+use axum::Router;
+use remotemedia_core::{
+    auth::{self, AuthError, AuthGuard, Principal, Scope, StaticTokenAuthenticator},
+    manifest::Manifest,
+    telemetry::{TraceContext, TRACEPARENT_KEY},
+    transport::{PipelineExecutor, TransportData},
+};
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::{broadcast, RwLock};
+use tracing::{info, warn};
+use webrtc::peer_connection::peer_connection_state::RTCPeerConnectionState;
+
+/// Header carrying the pipeline session id of a WHIP/WHEP resource
+pub const SESSION_ID_HEADER: &str = "x-remotemedia-session";
+
+const SDP_CONTENT_TYPE: &str = "application/sdp";
+
+/// Default upper bound on ICE gathering before the answer is sent
+const DEFAULT_GATHER_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Direction of a WHIP/WHEP resource
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ResourceKind {
+    /// WHIP publisher feeding a pipeline session
+    Ingest,
+    /// WHEP viewer receiving a session's output tracks
+    Playback,
+}
+
+impl ResourceKind {
+    fn path_prefix(self) -> &'static str {
+        match self {
+            ResourceKind::Ingest => "/whip/resources",
+            ResourceKind::Playback => "/whep/resources",
+        }
+    }
+}
+
+struct Resource {
+    kind: ResourceKind,
+    session_id: String,
+    /// Tenant of the key that created the resource
+    tenant_id: String,
+    peer: Arc<ServerPeer>,
+}
+
+impl Resource {
+    /// Other tenants' resources look like they do not exist
+    fn visible_to(&self, principal: &Principal) -> bool {
+        principal.has_scope(Scope::Admin) || self.tenant_id == principal.tenant_id
+    }
+}
+
+/// WHIP/WHEP HTTP endpoints backed by [`ServerPeer`]
+///
+/// ```no_run
+/// # use remotemedia_webrtc::signaling::WhipServer;
+/// # async fn example(
+/// #     config: std::sync::Arc<remotemedia_webrtc::WebRtcTransportConfig>,
+/// #     executor: std::sync::Arc<remotemedia_core::transport::PipelineExecutor>,
+/// #     manifest: std::sync::Arc<remotemedia_core::manifest::Manifest>,
+/// # ) -> std::io::Result<()> {
+/// WhipServer::new(config, executor, manifest)
+///     .with_bearer_token("secret")
+///     .serve("0.0.0.0:8088".parse().unwrap())
+///     .await
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct WhipServer {
+    config: Arc<WebRtcTransportConfig>,
+    executor: Arc<PipelineExecutor>,
+    manifest: Arc<Manifest>,
+    auth: AuthGuard,
+    gather_timeout: Duration,
+    resources: Arc<RwLock<HashMap<String, Resource>>>,
+}
+
+impl WhipServer {
+    /// Create WHIP/WHEP endpoints that run `manifest` for every new session
+    pub fn new(
+        config: Arc<WebRtcTransportConfig>,
+        executor: Arc<PipelineExecutor>,
+        manifest: Arc<Manifest>,
+    ) -> Self {
+        Self {
+            config,
+            executor,
+            manifest,
+            auth: AuthGuard::open(),
+            gather_timeout: DEFAULT_GATHER_TIMEOUT,
+            resources: Arc::new(RwLock::new(HashMap::new())),
+        }
+    }
+
+    /// Require `Authorization: Bearer <token>` on every request
+    pub fn with_bearer_token(mut self, token: impl Into<String>) -> Self {
+        let token = StaticTokenAuthenticator::from_tokens([token.into()]);
+        self.auth = self.auth.with_authenticator(Arc::new(token));
+        self
+    }
+
+    /// Authenticate requests with the API keys of `auth`
+    pub fn with_auth(mut self, auth: AuthGuard) -> Self {
+        self.auth = auth;
+        self
+    }
+
+    /// Upper bound on ICE gathering before answering (default 2s)
+    pub fn with_gather_timeout(mut self, timeout: Duration) -> Self {
+        self.gather_timeout = timeout;
+        self
+    }
+
+    /// Axum router with the WHIP/WHEP routes, for mounting into a larger app
+    pub fn router(&self) -> Router {
+        Router::new()
+            .route("/whip", post(whip_publish))
+            .route("/whip/resources/:id", delete(delete_ingest))
+            .route("/whep", post(whep_play))
+            .route("/whep/sessions/:session_id", post(whep_subscribe))
+            .route("/whep/resources/:id", delete(delete_playback))
+            .with_state(self.clone())
+    }
+
+    /// Serve the endpoints on `addr` until the task is dropped
+    pub async fn serve(self, addr: SocketAddr) -> std::io::Result<()> {
+        let listener = tokio::net::TcpListener::bind(addr).await?;
+        info!(
+            "WHIP/WHEP endpoints listening on http://{}",
+            listener.local_addr()?
+        );
+        axum::serve(listener, self.router()).await
+    }
+
+    /// Number of live WHIP/WHEP resources
+    pub async fn resource_count(&self) -> usize {
+        self.resources.read().await.len()
+    }
+
+    fn authorize(&self, headers: &HeaderMap) -> std::result::Result<Principal, Response> {
+        let presented = headers
+            .get(header::AUTHORIZATION)
+            .and_then(|v| v.to_str().ok())
+            .and_then(auth::bearer_token);
+        self.auth
+            .authorize(presented, Scope::Stream)
+            .map_err(auth_error_response)
+    }
+
+    /// Subscribe to the output of the session a WHIP publisher is feeding
+    async fn find_session_source(
+        &self,
+        session_id: &str,
+        principal: &Principal,
+    ) -> Option<broadcast::Receiver<TransportData>> {
+        self.resources
+            .read()
+            .await
+            .values()
+            .find(|r| {
+                r.kind == ResourceKind::Ingest
+                    && r.session_id == session_id
+                    && r.visible_to(principal)
+            })
+            .map(|r| r.peer.subscribe_output())
+    }
+
+    /// Whether resource `id` exists, is a `kind` resource and belongs to
+    /// `principal`'s tenant
+    async fn is_visible(&self, id: &str, kind: ResourceKind, principal: &Principal) -> bool {
+        self.resources
+            .read()
+            .await
+            .get(id)
+            .is_some_and(|r| r.kind == kind && r.visible_to(principal))
+    }
+
+    /// Register `peer` as a resource and remove it again once its
+    /// connection fails or closes
+    async fn register(
+        &self,
+        kind: ResourceKind,
+        id: String,
+        session_id: String,
+        tenant_id: String,
+        peer: Arc<ServerPeer>,
+    ) {
+        let server = self.clone();
+        let resource_id = id.clone();
+        peer.peer_connection()
+            .peer_connection()
+            .on_peer_connection_state_change(Box::new(move |state| {
+                let server = server.clone();
+                let resource_id = resource_id.clone();
+                Box::pin(async move {
+                    if matches!(
+                        state,
+                        RTCPeerConnectionState::Failed | RTCPeerConnectionState::Closed
+                    ) {
+                        info!("WHIP/WHEP resource {} connection {}", resource_id, state);
+                        server.remove(&resource_id).await;
+                    }
+                })
+            }));
+
+        self.resources.write().await.insert(
+            id,
+            Resource {
+                kind,
+                session_id,
+                tenant_id,
+                peer,
+            },
+        );
+    }
+
+    /// Tear down a resource. Removing an ingest also ends the playback
+    /// resources watching its session.
+    async fn remove(&self, id: &str) -> bool {
+        let removed = {
+            let mut resources = self.resources.write().await;
+            let Some(resource) = resources.remove(id) else {
+                return false;
+            };
+            let mut removed = vec![resource];
+            if removed[0].kind == ResourceKind::Ingest {
+                let session_id = removed[0].session_id.clone();
+                let watchers: Vec<String> = resources
+                    .iter()
+                    .filter(|(_, r)| r.kind == ResourceKind::Playback && r.session_id == session_id)
+                    .map(|(id, _)| id.clone())
+                    .collect();
+                for watcher in watchers {
+                    removed.extend(resources.remove(&watcher));
+                }
+            }
+            removed
+        };
+
+        for resource in removed {
+            if let Err(e) = resource.peer.shutdown().await {
+                warn!(
+                    "Failed to shut down peer {}: {}",
+                    resource.peer.peer_id(),
+                    e
+                );
+            }
+        }
+        true
+    }
+
+    async fn new_peer(
+        &self,
+        kind: ResourceKind,
+        principal: &Principal,
+        trace_context: Option<TraceContext>,
+    ) -> Result<(String, Arc<ServerPeer>)> {
+        let prefix = match kind {
+            ResourceKind::Ingest => "whip",
+            ResourceKind::Playback => "whep",
+        };
+        let id = uuid::Uuid::new_v4().to_string();
+        let peer = ServerPeer::new(
+            format!("{}-{}", prefix, id),
+            &self.config,
+            Arc::clone(&self.executor),
+            Arc::clone(&self.manifest),
+        )
+        .await?
+        .with_trace_context(trace_context)
+        .with_principal(Some(principal.clone()));
+        Ok((id, Arc::new(peer)))
+    }
+
+    /// Common tail of every offer: wait for candidates, register the
+    /// resource and build the `201 Created` response
+    async fn created(
+        &self,
+        kind: ResourceKind,
+        id: String,
+        peer: Arc<ServerPeer>,
+        session_id: String,
+        principal: Principal,
+    ) -> Response {
+        let answer = match peer
+            .local_description_with_candidates(self.gather_timeout)
+            .await
+        {
+            Ok(sdp) => sdp,
+            Err(e) => {
+                let _ = peer.shutdown().await;
+                return error_response(StatusCode::INTERNAL_SERVER_ERROR, e);
+            }
+        };
+
+        let location = format!("{}/{}", kind.path_prefix(), id);
+        info!(
+            "{:?} resource {} created for session {}",
+            kind, location, session_id
+        );
+        self.register(kind, id, session_id.clone(), principal.tenant_id, peer)
+            .await;
+
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            header::CONTENT_TYPE,
+            HeaderValue::from_static(SDP_CONTENT_TYPE),
+        );
+        if let Ok(v) = HeaderValue::from_str(&location) {
+            headers.insert(header::LOCATION, v);
+        }
+        if let Ok(v) = HeaderValue::from_str(&session_id) {
+            headers.insert(SESSION_ID_HEADER, v);
+        }
+        (StatusCode::CREATED, headers, answer).into_response()
+    }
+}
+
+fn error_response(status: StatusCode, err: impl std::fmt::Display) -> Response {
+    (status, err.to_string()).into_response()
+}
+
+/// `401` with `WWW-Authenticate`, `403`, or `429` with `Retry-After`
+fn auth_error_response(err: AuthError) -> Response {
+    if err.http_status() == 401 {
+        return (
+            StatusCode::UNAUTHORIZED,
+            [(header::WWW_AUTHENTICATE, "Bearer")],
+        )
+            .into_response();
+    }
+    let status =
+        StatusCode::from_u16(err.http_status()).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR);
+    let retry_after_ms = err.retry_after_ms();
+    let mut resp = error_response(status, err);
+    if let Some(retry_after_ms) = retry_after_ms {
+        let seconds = retry_after_ms.div_ceil(1000).max(1);
+        resp.headers_mut()
+            .insert(header::RETRY_AFTER, HeaderValue::from(seconds));
+    }
+    resp
+}
+
+/// A failed offer: `503` with `Retry-After` when admission control refused
+/// the session, `400` otherwise
+fn offer_error_response(err: Error) -> Response {
+    let Error::SessionRejected { retry_after_ms, .. } = err else {
+        return error_response(StatusCode::BAD_REQUEST, err);
+    };
+    let mut resp = error_response(StatusCode::SERVICE_UNAVAILABLE, err);
+    // Retry-After is in whole seconds
+    let seconds = retry_after_ms.div_ceil(1000).max(1);
+    resp.headers_mut()
+        .insert(header::RETRY_AFTER, HeaderValue::from(seconds));
+    resp
+}
+
+/// Reject anything but a non-empty `application/sdp` body
+fn sdp_offer(headers: &HeaderMap, body: &Bytes) -> std::result::Result<String, Response> {
+    let content_type = headers
+        .get(header::CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .unwrap_or_default();
+    if !content_type.starts_with(SDP_CONTENT_TYPE) {
+        return Err(error_response(
+            StatusCode::UNSUPPORTED_MEDIA_TYPE,
+            "offer must be application/sdp",
+        ));
+    }
+    let sdp = std::str::from_utf8(body)
+        .map_err(|_| error_response(StatusCode::BAD_REQUEST, "offer is not UTF-8"))?;
+    if !sdp.starts_with("v=0") || !sdp.contains("m=") {
+        return Err(error_response(
+            StatusCode::BAD_REQUEST,
+            "invalid SDP offer: expected v=0 and at least one m= section",
+        ));
+    }
+    Ok(sdp.to_string())
+}
+
+/// Offer from a fresh [`ServerPeer`] that gets its own pipeline session
+async fn answer_with_new_session(
+    server: &WhipServer,
+    kind: ResourceKind,
+    headers: HeaderMap,
+    body: Bytes,
+) -> Response {
+    let principal = match server.authorize(&headers) {
+        Ok(p) => p,
+        Err(resp) => return resp,
+    };
+    if let Err(e) = principal.check_manifest(&server.manifest) {
+        return auth_error_response(e);
+    }
+    let offer = match sdp_offer(&headers, &body) {
+        Ok(sdp) => sdp,
+        Err(resp) => return resp,
+    };
+
+    // An ingest's node spans join the publisher's trace, if it sent one
+    let trace_context = headers
+        .get(TRACEPARENT_KEY)
+        .and_then(|v| v.to_str().ok())
+        .and_then(TraceContext::parse);
+    let (id, peer) = match server.new_peer(kind, &principal, trace_context).await {
+        Ok(p) => p,
+        Err(e) => return error_response(StatusCode::SERVICE_UNAVAILABLE, e),
+    };
+    if let Err(e) = peer.handle_offer(offer).await {
+        let _ = peer.shutdown().await;
+        return offer_error_response(e);
+    }
+    let session_id = peer.session_id().await.unwrap_or_default();
+    server.created(kind, id, peer, session_id, principal).await
+}
+
+/// `POST /whip`
+async fn whip_publish(
+    State(server): State<WhipServer>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> Response {
+    answer_with_new_session(&server, ResourceKind::Ingest, headers, body).await
+}
+
+/// `POST /whep`
+async fn whep_play(State(server): State<WhipServer>, headers: HeaderMap, body: Bytes) -> Response {
+    answer_with_new_session(&server, ResourceKind::Playback, headers, body).await
+}
+
+/// `POST /whep/sessions/:session_id`
+async fn whep_subscribe(
+    State(server): State<WhipServer>,
+    Path(session_id): Path<String>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> Response {
+    let principal = match server.authorize(&headers) {
+        Ok(p) => p,
+        Err(resp) => return resp,
+    };
+    let offer = match sdp_offer(&headers, &body) {
+        Ok(sdp) => sdp,
+        Err(resp) => return resp,
+    };
+    let Some(outputs) = server.find_session_source(&session_id, &principal).await else {
+        return error_response(
+            StatusCode::NOT_FOUND,
+            format!("no WHIP session '{}'", session_id),
+        );
+    };
+
+    let (id, peer) = match server
+        .new_peer(ResourceKind::Playback, &principal, None)
+        .await
+    {
+        Ok(p) => p,
+        Err(e) => return error_response(StatusCode::SERVICE_UNAVAILABLE, e),
+    };
+    if let Err(e) = peer
+        .handle_subscribe_offer(offer, session_id.clone(), outputs)
+        .await
+    {
+        let _ = peer.shutdown().await;
+        return error_response(StatusCode::BAD_REQUEST, e);
+    }
+    server
+        .created(ResourceKind::Playback, id, peer, session_id, principal)
+        .await
+}
+
+/// `DELETE /whip/resources/:id`
+async fn delete_ingest(
+    State(server): State<WhipServer>,
+    Path(id): Path<String>,
+    headers: HeaderMap,
+) -> Response {
+    delete_resource(server, ResourceKind::Ingest, id, headers).await
+}
+
+/// `DELETE /whep/resources/:id`
+async fn delete_playback(
+    State(server): State<WhipServer>,
+    Path(id): Path<String>,
+    headers: HeaderMap,
+) -> Response {
+    delete_resource(server, ResourceKind::Playback, id, headers).await
+}
+
+/// Tear down resource `id`, which must be a `kind` resource: an ingest
+/// can't be ended through its playback URL and vice versa
+async fn delete_resource(
+    server: WhipServer,
+    kind: ResourceKind,
+    id: String,
+    headers: HeaderMap,
+) -> Response {
+    let principal = match server.authorize(&headers) {
+        Ok(p) => p,
+        Err(resp) => return resp,
+    };
+    if server.is_visible(&id, kind, &principal).await && server.remove(&id).await {
+        StatusCode::OK.into_response()
+    } else {
+        StatusCode::NOT_FOUND.into_response()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sdp_headers() -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            header::CONTENT_TYPE,
+            HeaderValue::from_static(SDP_CONTENT_TYPE),
+        );
+        headers
+    }
+
+    #[test]
+    fn test_sdp_offer_validation() {
+        let offer = Bytes::from_static(
+            b"v=0\r\no=- 0 0 IN IP4 127.0.0.1\r\nm=audio 9 UDP/TLS/RTP/SAVPF 111\r\n",
+        );
+        assert!(sdp_offer(&sdp_headers(), &offer).is_ok());
+
+        let resp = sdp_offer(&HeaderMap::new(), &offer).unwrap_err();
+        assert_eq!(resp.status(), StatusCode::UNSUPPORTED_MEDIA_TYPE);
+
+        let resp = sdp_offer(&sdp_headers(), &Bytes::from_static(b"v=0\r\n")).unwrap_err();
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+    }
+
+    #[test]
+    fn test_resource_paths() {
+        assert_eq!(ResourceKind::Ingest.path_prefix(), "/whip/resources");
+        assert_eq!(ResourceKind::Playback.path_prefix(), "/whep/resources");
+    }
+
+    #[test]
+    fn test_rejected_offer_sets_retry_after() {
+        let resp = offer_error_response(Error::SessionRejected {
+            resource: "sessions".to_string(),
+            message: "64 concurrent sessions already running (limit 64)".to_string(),
+            retry_after_ms: 2_500,
+        });
+        assert_eq!(resp.status(), StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(resp.headers()[header::RETRY_AFTER], "3");
+
+        let resp = offer_error_response(Error::SdpError("bad offer".to_string()));
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+        assert!(resp.headers().get(header::RETRY_AFTER).is_none());
+    }
+
+    #[test]
+    fn test_auth_error_responses() {
+        let resp = auth_error_response(AuthError::MissingCredentials);
+        assert_eq!(resp.status(), StatusCode::UNAUTHORIZED);
+        assert_eq!(resp.headers()[header::WWW_AUTHENTICATE], "Bearer");
+
+        let resp = auth_error_response(AuthError::Forbidden("manifest".to_string()));
+        assert_eq!(resp.status(), StatusCode::FORBIDDEN);
+
+        let resp = auth_error_response(AuthError::RateLimited {
+            key_id: "obs".to_string(),
+            retry_after_ms: 1_200,
+        });
+        assert_eq!(resp.status(), StatusCode::TOO_MANY_REQUESTS);
+        assert_eq!(resp.headers()[header::RETRY_AFTER], "2");
+    }
+}
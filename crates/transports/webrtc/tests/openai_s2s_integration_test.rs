@@ -305,30 +305,37 @@ fn build_openai_s2s_manifest() -> Manifest {
         Connection {
             from: "resample_in".to_string(),
             to: "chunker".to_string(),
+            ..Default::default()
         },
         Connection {
             from: "chunker".to_string(),
             to: "vad".to_string(),
+            ..Default::default()
         },
         Connection {
             from: "vad".to_string(),
             to: "accumulator".to_string(),
+            ..Default::default()
         },
         Connection {
             from: "vad".to_string(),
             to: "coordinator".to_string(),
+            ..Default::default()
         },
         Connection {
             from: "accumulator".to_string(),
             to: "stt_in".to_string(),
+            ..Default::default()
         },
         Connection {
             from: "stt_in".to_string(),
             to: "llm".to_string(),
+            ..Default::default()
         },
         Connection {
             from: "llm".to_string(),
             to: "coordinator".to_string(),
+            ..Default::default()
         },
     ];
 
@@ -338,7 +345,7 @@ fn build_openai_s2s_manifest() -> Manifest {
         } else {
             c.from
         };
-        connections.push(Connection { from, to: c.to });
+        connections.push(Connection { from, to: c.to, ..Default::default() });
     }
 
     Manifest {
@@ -395,6 +402,7 @@ fn build_tts_stage(
                 vec![Connection {
                     from: "llm".to_string(),
                     to: "audio".to_string(),
+                    ..Default::default()
                 }],
             )
         }
@@ -440,10 +448,12 @@ fn build_tts_stage(
                     Connection {
                         from: "llm".to_string(),
                         to: "kokoro_tts".to_string(),
+                        ..Default::default()
                     },
                     Connection {
                         from: "kokoro_tts".to_string(),
                         to: "audio".to_string(),
+                        ..Default::default()
                     },
                 ],
             )
@@ -882,7 +892,7 @@ async fn test_openai_chat_node_with_mock_server() {
     config.streaming = true;
     config.history_turns = 0;
 
-    let node = OpenAIChatNode::with_config(config);
+    let node = OpenAIChatNode::with_config(config).unwrap();
 
     // Initialize should succeed
     node.initialize().await.expect("initialize should succeed");
@@ -930,7 +940,7 @@ async fn test_openai_chat_node_invalid_api_key() {
     config.api_key = Some("sk-invalid".into());
     config.base_url = Some("http://127.0.0.1:19999/v1".into()); // Non-existent server
 
-    let node = OpenAIChatNode::with_config(config);
+    let node = OpenAIChatNode::with_config(config).unwrap();
     node.initialize().await.expect("initialize should succeed");
 
     // This should fail because the server doesn't exist
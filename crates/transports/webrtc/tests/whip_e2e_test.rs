@@ -0,0 +1,221 @@
+//! WHIP/WHEP End-to-End Tests
+//!
+//! Drives the HTTP endpoints with a plain webrtc-rs client, the same way
+//! OBS or a WHEP player would: non-trickle offer in, answer out, DELETE to
+//! tear down.
+//!
+//! ```bash
+//! cargo test --features whip --test whip_e2e_test
+//! ```
+
+#![cfg(feature = "whip")]
+
+use remotemedia_core::manifest::Manifest;
+use remotemedia_core::transport::PipelineExecutor;
+use remotemedia_webrtc::signaling::whip::SESSION_ID_HEADER;
+use remotemedia_webrtc::{WebRtcTransportConfig, WhipServer};
+use std::sync::Arc;
+use std::time::Duration;
+use webrtc::api::interceptor_registry::register_default_interceptors;
+use webrtc::api::media_engine::MediaEngine;
+use webrtc::api::APIBuilder;
+use webrtc::interceptor::registry::Registry;
+use webrtc::peer_connection::configuration::RTCConfiguration;
+use webrtc::peer_connection::sdp::session_description::RTCSessionDescription;
+use webrtc::peer_connection::RTCPeerConnection;
+use webrtc::rtp_transceiver::rtp_codec::RTPCodecType;
+use webrtc::rtp_transceiver::rtp_transceiver_direction::RTCRtpTransceiverDirection;
+use webrtc::rtp_transceiver::RTCRtpTransceiverInit;
+
+const TOKEN: &str = "whip-secret";
+
+const PASSTHROUGH_MANIFEST: &str = r#"
+{
+    "version": "1.0",
+    "metadata": { "name": "whip-passthrough" },
+    "nodes": [ { "id": "passthrough", "node_type": "PassThrough", "params": {} } ],
+    "connections": []
+}
+"#;
+
+async fn start_server() -> (String, WhipServer) {
+    let manifest: Manifest = serde_json::from_str(PASSTHROUGH_MANIFEST).unwrap();
+    let config = WebRtcTransportConfig {
+        stun_servers: vec![],
+        ..Default::default()
+    };
+    let server = WhipServer::new(
+        Arc::new(config),
+        Arc::new(PipelineExecutor::new().unwrap()),
+        Arc::new(manifest),
+    )
+    .with_bearer_token(TOKEN)
+    .with_gather_timeout(Duration::from_secs(1));
+
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let base = format!("http://{}", listener.local_addr().unwrap());
+    let router = server.router();
+    tokio::spawn(async move {
+        let _ = axum::serve(listener, router).await;
+    });
+    (base, server)
+}
+
+/// Client peer with one audio and one video transceiver in `direction`,
+/// plus its complete (post-gathering) offer
+async fn client_offer(direction: RTCRtpTransceiverDirection) -> (Arc<RTCPeerConnection>, String) {
+    let mut media_engine = MediaEngine::default();
+    media_engine.register_default_codecs().unwrap();
+    let registry = register_default_interceptors(Registry::new(), &mut media_engine).unwrap();
+    let api = APIBuilder::new()
+        .with_media_engine(media_engine)
+        .with_interceptor_registry(registry)
+        .build();
+    let pc = Arc::new(
+        api.new_peer_connection(RTCConfiguration::default())
+            .await
+            .unwrap(),
+    );
+
+    for kind in [RTPCodecType::Audio, RTPCodecType::Video] {
+        pc.add_transceiver_from_kind(
+            kind,
+            Some(RTCRtpTransceiverInit {
+                direction,
+                send_encodings: vec![],
+            }),
+        )
+        .await
+        .unwrap();
+    }
+
+    let offer = pc.create_offer(None).await.unwrap();
+    let mut gathered = pc.gathering_complete_promise().await;
+    pc.set_local_description(offer).await.unwrap();
+    let _ = tokio::time::timeout(Duration::from_secs(2), gathered.recv()).await;
+    let sdp = pc.local_description().await.unwrap().sdp;
+    (pc, sdp)
+}
+
+async fn post_offer(url: &str, sdp: String) -> reqwest::Response {
+    reqwest::Client::new()
+        .post(url)
+        .bearer_auth(TOKEN)
+        .header("content-type", "application/sdp")
+        .body(sdp)
+        .send()
+        .await
+        .unwrap()
+}
+
+#[tokio::test]
+async fn test_whip_publish_and_whep_subscribe() {
+    let (base, server) = start_server().await;
+
+    // WHIP: publisher offer -> answer with candidates
+    let (publisher, offer) = client_offer(RTCRtpTransceiverDirection::Sendonly).await;
+    let resp = post_offer(&format!("{}/whip", base), offer).await;
+    assert_eq!(resp.status(), reqwest::StatusCode::CREATED);
+    assert_eq!(resp.headers()["content-type"], "application/sdp");
+    let whip_location = resp.headers()["location"].to_str().unwrap().to_string();
+    assert!(whip_location.starts_with("/whip/resources/"));
+    let session_id = resp.headers()[SESSION_ID_HEADER]
+        .to_str()
+        .unwrap()
+        .to_string();
+    assert!(!session_id.is_empty());
+    let answer = resp.text().await.unwrap();
+    assert!(answer.starts_with("v=0"));
+    publisher
+        .set_remote_description(RTCSessionDescription::answer(answer).unwrap())
+        .await
+        .unwrap();
+
+    // WHEP: viewer subscribes to the publisher's session
+    let (viewer, offer) = client_offer(RTCRtpTransceiverDirection::Recvonly).await;
+    let resp = post_offer(&format!("{}/whep/sessions/{}", base, session_id), offer).await;
+    assert_eq!(resp.status(), reqwest::StatusCode::CREATED);
+    assert_eq!(resp.headers()[SESSION_ID_HEADER], session_id.as_str());
+    let whep_location = resp.headers()["location"].to_str().unwrap().to_string();
+    assert!(whep_location.starts_with("/whep/resources/"));
+    viewer
+        .set_remote_description(RTCSessionDescription::answer(resp.text().await.unwrap()).unwrap())
+        .await
+        .unwrap();
+    assert_eq!(server.resource_count().await, 2);
+
+    // Deleting the publisher also ends its viewers
+    let resp = reqwest::Client::new()
+        .delete(format!("{}{}", base, whip_location))
+        .bearer_auth(TOKEN)
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), reqwest::StatusCode::OK);
+    assert_eq!(server.resource_count().await, 0);
+
+    let resp = reqwest::Client::new()
+        .delete(format!("{}{}", base, whep_location))
+        .bearer_auth(TOKEN)
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), reqwest::StatusCode::NOT_FOUND);
+
+    let _ = publisher.close().await;
+    let _ = viewer.close().await;
+}
+
+#[tokio::test]
+async fn test_whep_playback_of_new_session() {
+    let (base, server) = start_server().await;
+
+    let (viewer, offer) = client_offer(RTCRtpTransceiverDirection::Recvonly).await;
+    let resp = post_offer(&format!("{}/whep", base), offer).await;
+    assert_eq!(resp.status(), reqwest::StatusCode::CREATED);
+    let location = resp.headers()["location"].to_str().unwrap().to_string();
+    assert_eq!(server.resource_count().await, 1);
+
+    let resp = reqwest::Client::new()
+        .delete(format!("{}{}", base, location))
+        .bearer_auth(TOKEN)
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), reqwest::StatusCode::OK);
+    assert_eq!(server.resource_count().await, 0);
+
+    let _ = viewer.close().await;
+}
+
+#[tokio::test]
+async fn test_whip_rejects_bad_requests() {
+    let (base, _server) = start_server().await;
+    let client = reqwest::Client::new();
+    let (_pc, offer) = client_offer(RTCRtpTransceiverDirection::Sendonly).await;
+
+    // No bearer token
+    let resp = client
+        .post(format!("{}/whip", base))
+        .header("content-type", "application/sdp")
+        .body(offer.clone())
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), reqwest::StatusCode::UNAUTHORIZED);
+
+    // Wrong content type
+    let resp = client
+        .post(format!("{}/whip", base))
+        .bearer_auth(TOKEN)
+        .header("content-type", "application/json")
+        .body(offer.clone())
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), reqwest::StatusCode::UNSUPPORTED_MEDIA_TYPE);
+
+    // Unknown session to watch
+    let resp = post_offer(&format!("{}/whep/sessions/no-such-session", base), offer).await;
+    assert_eq!(resp.status(), reqwest::StatusCode::NOT_FOUND);
+}
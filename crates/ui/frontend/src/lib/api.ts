@@ -1,3 +1,14 @@
+// API key, if the server requires one. Taken from `?token=` on first load
+// and kept in localStorage.
+const TOKEN_KEY = 'remotemedia.token';
+const urlToken = new URLSearchParams(window.location.search).get('token');
+if (urlToken) localStorage.setItem(TOKEN_KEY, urlToken);
+
+function authHeaders(): Record<string, string> {
+  const token = localStorage.getItem(TOKEN_KEY);
+  return token ? { Authorization: `Bearer ${token}` } : {};
+}
+
 export async function getStatus() {
   const res = await fetch('/api/status');
   return res.json();
@@ -17,7 +28,7 @@ export async function executePipeline(input: any, manifest?: any) {
   if (manifest) body.manifest = manifest;
   const res = await fetch('/api/execute', {
     method: 'POST',
-    headers: { 'Content-Type': 'application/json' },
+    headers: { 'Content-Type': 'application/json', ...authHeaders() },
     body: JSON.stringify(body),
   });
   if (!res.ok) {
@@ -32,7 +43,7 @@ export async function createStream(manifest?: any) {
   if (manifest) body.manifest = manifest;
   const res = await fetch('/api/stream', {
     method: 'POST',
-    headers: { 'Content-Type': 'application/json' },
+    headers: { 'Content-Type': 'application/json', ...authHeaders() },
     body: JSON.stringify(body),
   });
   if (!res.ok) throw new Error('Failed to create stream');
@@ -42,17 +53,20 @@ export async function createStream(manifest?: any) {
 export async function sendStreamInput(sessionId: string, data: any) {
   await fetch(`/api/stream/${sessionId}/input`, {
     method: 'POST',
-    headers: { 'Content-Type': 'application/json' },
+    headers: { 'Content-Type': 'application/json', ...authHeaders() },
     body: JSON.stringify({ data }),
   });
 }
 
 export function subscribeToStream(sessionId: string, onData: (data: any) => void): EventSource {
-  const es = new EventSource(`/api/stream/${sessionId}/output`);
+  // EventSource cannot set headers, so the key goes in the query string
+  const token = localStorage.getItem(TOKEN_KEY);
+  const query = token ? `?token=${encodeURIComponent(token)}` : '';
+  const es = new EventSource(`/api/stream/${sessionId}/output${query}`);
   es.onmessage = (e) => onData(JSON.parse(e.data));
   return es;
 }
 
 export async function closeStream(sessionId: string) {
-  await fetch(`/api/stream/${sessionId}`, { method: 'DELETE' });
+  await fetch(`/api/stream/${sessionId}`, { method: 'DELETE', headers: authHeaders() });
 }
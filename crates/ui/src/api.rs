@@ -2,16 +2,23 @@
 //!
 //! Provides HTTP endpoints for pipeline execution and status,
 //! mirroring the HTTP transport server pattern.
+//!
+//! Requests are authenticated with the [`AuthGuard`] held in [`AppState`].
+//! The key goes in `Authorization: Bearer <key>`; the SSE output endpoint
+//! also accepts `?token=<key>` because `EventSource` cannot set headers.
+//! Streaming sessions belong to the tenant that created them and are not
+//! visible to other tenants.
 
 use crate::AppState;
 use async_trait::async_trait;
 use axum::{
-    extract::{Path, State},
-    http::StatusCode,
+    extract::{Path, Query, State},
+    http::{header, HeaderMap, StatusCode},
     response::sse::{Event, KeepAlive, Sse},
     Json,
 };
 use futures::stream::Stream;
+use remotemedia_core::auth::{self, AuthError, AuthGuard, Principal, Scope};
 use remotemedia_core::manifest::Manifest;
 use remotemedia_core::transport::{StreamSession, TransportData};
 use serde::{Deserialize, Serialize};
@@ -36,6 +43,8 @@ use crate::TransportInfo;
 pub(crate) struct SessionHandle {
     /// Session ID
     pub session_id: String,
+    /// Tenant that created the session
+    pub tenant_id: String,
     /// Channel for sending input to the background session task
     pub input_tx: tokio::sync::mpsc::UnboundedSender<TransportData>,
     /// Broadcast channel for sending outputs to multiple SSE subscribers
@@ -44,6 +53,12 @@ pub(crate) struct SessionHandle {
     drain_handle: tokio::task::JoinHandle<()>,
 }
 
+impl SessionHandle {
+    fn visible_to(&self, principal: &Principal) -> bool {
+        principal.has_scope(Scope::Admin) || self.tenant_id == principal.tenant_id
+    }
+}
+
 /// Wrapper to adapt PipelineExecutor's SessionHandle to StreamSession trait
 pub(crate) struct SessionHandleWrapper(pub remotemedia_core::transport::SessionHandle);
 
@@ -115,6 +130,12 @@ pub(crate) struct StreamInputRequest {
     data: TransportData,
 }
 
+/// Query string for GET /api/stream/:session_id/output
+#[derive(Debug, Default, Deserialize)]
+pub(crate) struct TokenQuery {
+    token: Option<String>,
+}
+
 /// Error response body
 #[derive(Debug, Serialize)]
 pub(crate) struct ErrorResponse {
@@ -174,6 +195,43 @@ fn map_runtime_error(e: remotemedia_core::Error) -> (StatusCode, Json<ErrorRespo
     }
 }
 
+/// Map authentication failures to 401/403/429
+fn map_auth_error(e: AuthError) -> (StatusCode, Json<ErrorResponse>) {
+    (
+        StatusCode::from_u16(e.http_status()).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR),
+        Json(ErrorResponse {
+            error_type: e.error_type().to_string(),
+            message: e.to_string(),
+            validation_errors: None,
+        }),
+    )
+}
+
+/// Authenticate the request's bearer key (or `query_token`) and require `scope`
+fn authorize(
+    guard: &AuthGuard,
+    headers: &HeaderMap,
+    query_token: Option<&str>,
+    scope: Scope,
+) -> std::result::Result<Principal, (StatusCode, Json<ErrorResponse>)> {
+    let token = headers
+        .get(header::AUTHORIZATION)
+        .and_then(|v| v.to_str().ok())
+        .and_then(auth::bearer_token)
+        .or(query_token);
+    guard.authorize(token, scope).map_err(map_auth_error)
+}
+
+/// [`authorize`] for the session endpoints, which report errors as text
+fn authorize_session(
+    guard: &AuthGuard,
+    headers: &HeaderMap,
+    query_token: Option<&str>,
+) -> std::result::Result<Principal, (StatusCode, String)> {
+    authorize(guard, headers, query_token, Scope::Stream)
+        .map_err(|(status, Json(body))| (status, body.message))
+}
+
 // ---------------------------------------------------------------------------
 // Handlers
 // ---------------------------------------------------------------------------
@@ -207,8 +265,11 @@ pub(crate) async fn manifest_handler(
 /// POST /api/execute - Execute a pipeline
 pub(crate) async fn execute_handler(
     State(state): State<AppState>,
+    headers: HeaderMap,
     Json(request): Json<ExecuteRequest>,
 ) -> std::result::Result<Json<ExecuteResponse>, (StatusCode, Json<ErrorResponse>)> {
+    let principal = authorize(&state.auth, &headers, None, Scope::Execute)?;
+
     // Resolve manifest: use request manifest, fall back to server manifest
     let manifest = if let Some(manifest_value) = request.manifest {
         let m: Manifest = serde_json::from_value(manifest_value).map_err(|e| {
@@ -234,10 +295,13 @@ pub(crate) async fn execute_handler(
             }),
         ));
     };
+    principal
+        .check_manifest(&manifest)
+        .map_err(map_auth_error)?;
 
     let output = state
         .executor
-        .execute_unary(manifest, request.input)
+        .execute_unary_as(manifest, request.input, Some(&principal.tenant_id))
         .await
         .map_err(map_runtime_error)?;
 
@@ -247,8 +311,11 @@ pub(crate) async fn execute_handler(
 /// POST /api/stream - Create a streaming session
 pub(crate) async fn create_stream_handler(
     State(state): State<AppState>,
+    headers: HeaderMap,
     Json(request): Json<CreateStreamRequest>,
 ) -> std::result::Result<Json<CreateStreamResponse>, (StatusCode, Json<ErrorResponse>)> {
+    let principal = authorize(&state.auth, &headers, None, Scope::Stream)?;
+
     // Resolve manifest
     let manifest = if let Some(manifest_value) = request.manifest {
         let m: Manifest = serde_json::from_value(manifest_value).map_err(|e| {
@@ -274,10 +341,13 @@ pub(crate) async fn create_stream_handler(
             }),
         ));
     };
+    principal
+        .check_manifest(&manifest)
+        .map_err(map_auth_error)?;
 
     let session = state
         .executor
-        .create_session(manifest)
+        .create_session_as(manifest, Some(&principal.tenant_id))
         .await
         .map_err(map_runtime_error)?;
 
@@ -315,6 +385,7 @@ pub(crate) async fn create_stream_handler(
 
     let handle = SessionHandle {
         session_id: session_id.clone(),
+        tenant_id: principal.tenant_id,
         input_tx,
         output_tx,
         drain_handle,
@@ -335,16 +406,21 @@ pub(crate) async fn create_stream_handler(
 pub(crate) async fn stream_input_handler(
     State(state): State<AppState>,
     Path(session_id): Path<String>,
+    headers: HeaderMap,
     Json(request): Json<StreamInputRequest>,
 ) -> std::result::Result<StatusCode, (StatusCode, String)> {
+    let principal = authorize_session(&state.auth, &headers, None)?;
     let sessions = state.sessions.read().await;
 
-    let handle = sessions.get(&session_id).ok_or_else(|| {
-        (
-            StatusCode::NOT_FOUND,
-            format!("Session not found: {}", session_id),
-        )
-    })?;
+    let handle = sessions
+        .get(&session_id)
+        .filter(|h| h.visible_to(&principal))
+        .ok_or_else(|| {
+            (
+                StatusCode::NOT_FOUND,
+                format!("Session not found: {}", session_id),
+            )
+        })?;
 
     handle.input_tx.send(request.data).map_err(|e| {
         (
@@ -360,18 +436,24 @@ pub(crate) async fn stream_input_handler(
 pub(crate) async fn stream_output_handler(
     State(state): State<AppState>,
     Path(session_id): Path<String>,
+    Query(query): Query<TokenQuery>,
+    headers: HeaderMap,
 ) -> std::result::Result<
     Sse<impl Stream<Item = std::result::Result<Event, Infallible>>>,
     (StatusCode, String),
 > {
+    let principal = authorize_session(&state.auth, &headers, query.token.as_deref())?;
     let rx = {
         let sessions = state.sessions.read().await;
-        let handle = sessions.get(&session_id).ok_or_else(|| {
-            (
-                StatusCode::NOT_FOUND,
-                format!("Session not found: {}", session_id),
-            )
-        })?;
+        let handle = sessions
+            .get(&session_id)
+            .filter(|h| h.visible_to(&principal))
+            .ok_or_else(|| {
+                (
+                    StatusCode::NOT_FOUND,
+                    format!("Session not found: {}", session_id),
+                )
+            })?;
         handle.output_tx.subscribe()
     };
 
@@ -400,15 +482,23 @@ pub(crate) async fn stream_output_handler(
 pub(crate) async fn close_stream_handler(
     State(state): State<AppState>,
     Path(session_id): Path<String>,
+    headers: HeaderMap,
 ) -> std::result::Result<StatusCode, (StatusCode, String)> {
+    let principal = authorize_session(&state.auth, &headers, None)?;
     let mut sessions = state.sessions.write().await;
 
-    let handle = sessions.remove(&session_id).ok_or_else(|| {
-        (
-            StatusCode::NOT_FOUND,
-            format!("Session not found: {}", session_id),
-        )
-    })?;
+    let visible = sessions
+        .get(&session_id)
+        .is_some_and(|h| h.visible_to(&principal));
+    let handle = visible
+        .then(|| sessions.remove(&session_id))
+        .flatten()
+        .ok_or_else(|| {
+            (
+                StatusCode::NOT_FOUND,
+                format!("Session not found: {}", session_id),
+            )
+        })?;
 
     // Abort the background drain task (which owns the session)
     handle.drain_handle.abort();
@@ -17,6 +17,10 @@
 //!     .build()?;
 //! server.run().await?;
 //! ```
+//!
+//! API keys are read from the `REMOTEMEDIA_AUTH_*` environment variables
+//! (see [`remotemedia_core::auth::AuthGuard::from_env`]) unless a guard is
+//! set with [`UiServerBuilder::auth`]. With none configured the API is open.
 
 pub mod api;
 pub mod assets;
@@ -26,6 +30,7 @@ use axum::{
     routing::{delete, get, post},
     Router,
 };
+pub use remotemedia_core::auth::AuthGuard;
 pub use remotemedia_core::manifest::Manifest;
 pub use remotemedia_core::transport::PipelineExecutor;
 use serde::{Deserialize, Serialize};
@@ -49,6 +54,7 @@ pub(crate) struct AppState {
     pub manifest: Option<Arc<Manifest>>,
     pub transport_info: Option<TransportInfo>,
     pub sessions: Arc<RwLock<HashMap<String, SessionHandle>>>,
+    pub auth: AuthGuard,
 }
 
 /// Builder for configuring and creating a [`UiServer`].
@@ -72,6 +78,7 @@ pub struct UiServerBuilder {
     executor: Option<Arc<PipelineExecutor>>,
     manifest: Option<Arc<Manifest>>,
     transport_info: Option<TransportInfo>,
+    auth: Option<AuthGuard>,
 }
 
 impl UiServerBuilder {
@@ -80,12 +87,14 @@ impl UiServerBuilder {
     /// Defaults:
     /// - `bind_address`: `"127.0.0.1:3001"`
     /// - `executor`: `None` (must be provided before calling `build`)
+    /// - `auth`: loaded from the environment
     pub fn new() -> Self {
         Self {
             bind_address: None,
             executor: None,
             manifest: None,
             transport_info: None,
+            auth: None,
         }
     }
 
@@ -117,11 +126,20 @@ impl UiServerBuilder {
         self
     }
 
+    /// Set the API key guard.
+    ///
+    /// If not called, the guard is built with [`AuthGuard::from_env`].
+    pub fn auth(mut self, guard: AuthGuard) -> Self {
+        self.auth = Some(guard);
+        self
+    }
+
     /// Build the [`UiServer`].
     ///
     /// # Errors
     ///
-    /// Returns an error if the executor has not been set.
+    /// Returns an error if the executor has not been set, or if the
+    /// environment names a key store that cannot be loaded.
     pub fn build(self) -> std::result::Result<UiServer, Box<dyn std::error::Error>> {
         let executor = self
             .executor
@@ -131,11 +149,17 @@ impl UiServerBuilder {
             .bind_address
             .unwrap_or_else(|| "127.0.0.1:3001".to_string());
 
+        let auth = match self.auth {
+            Some(guard) => guard,
+            None => AuthGuard::from_env()?,
+        };
+
         let state = AppState {
             executor,
             manifest: self.manifest,
             transport_info: self.transport_info,
             sessions: Arc::new(RwLock::new(HashMap::new())),
+            auth,
         };
 
         Ok(UiServer {
@@ -264,6 +264,7 @@ fn inject_audio_nodes(manifest: &Manifest, config: &EffectiveConfig) -> Manifest
             new_manifest.connections.insert(0, Connection {
                 from: "_cli_mic_input".to_string(),
                 to: source_id.clone(),
+                ..Default::default()
             });
         }
         
@@ -296,6 +297,7 @@ fn inject_audio_nodes(manifest: &Manifest, config: &EffectiveConfig) -> Manifest
             new_manifest.connections.push(Connection {
                 from: sink_id.clone(),
                 to: "_cli_speaker_output".to_string(),
+                ..Default::default()
             });
         }
         
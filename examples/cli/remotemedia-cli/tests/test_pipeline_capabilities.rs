@@ -1739,6 +1739,7 @@ mod manifest_integration_tests {
                 .map(|(from, to)| Connection {
                     from: from.to_string(),
                     to: to.to_string(),
+                    ..Default::default()
                 })
                 .collect(),
         }
@@ -2082,6 +2083,56 @@ mod manifest_integration_tests {
             ctx.errors
         );
     }
+
+    #[test]
+    fn test_manifest_valid_subtitle_sink_pipeline() {
+        // Test a valid subtitle pipeline similar to transcribe-subtitles.yaml
+        // MicInput(16kHz mono) -> RustWhisperNode -> SubtitleSinkNode
+
+        let manifest = create_manifest(
+            "transcribe-subtitles",
+            vec![
+                (
+                    "mic-input",
+                    "MicInput",
+                    serde_json::json!({
+                        "sample_rate": 16000,
+                        "channels": 1,
+                        "device": "test"
+                    }),
+                ),
+                (
+                    "whisper",
+                    "RustWhisperNode",
+                    serde_json::json!({
+                        "model_source": "tiny"
+                    }),
+                ),
+                (
+                    "subtitles",
+                    "SubtitleSinkNode",
+                    serde_json::json!({ "emit": "srt" }),
+                ),
+            ],
+            vec![("mic-input", "whisper"), ("whisper", "subtitles")],
+        );
+
+        let graph = PipelineGraph::from_manifest(&manifest).expect("Failed to build graph");
+        assert_eq!(
+            graph.execution_order,
+            vec!["mic-input", "whisper", "subtitles"]
+        );
+
+        let registry = create_test_registry();
+        assert!(registry.has_node_type("SubtitleSinkNode"));
+        let ctx = resolve_from_manifest(&manifest, &registry);
+
+        assert!(
+            !ctx.has_errors(),
+            "Valid subtitle pipeline should pass. Errors: {:?}",
+            ctx.errors
+        );
+    }
 }
 
 // =============================================================================
@@ -3744,6 +3795,7 @@ mod auto_resample_tests {
                 .map(|(from, to)| Connection {
                     from: from.to_string(),
                     to: to.to_string(),
+                    ..Default::default()
                 })
                 .collect(),
         }
@@ -19,6 +19,17 @@
 //!   [+00:00.123] llm.out        text    "Yes, I can hear you perfectly."
 //!
 //! Offsets are milliseconds relative to the first record in the file.
+//!
+//! Given a lossless input capture (`<session>.inputs.bin`, written when
+//! `REMOTEMEDIA_RECORD_INPUTS=1`), the CLI instead re-feeds the recorded
+//! inputs into a fresh in-process session and diffs its outputs against
+//! the sibling `<session>.jsonl`:
+//!
+//!   session-replay trace/s1776961697_0.inputs.bin
+//!   session-replay trace/s1776961697_0.inputs.bin --speed 0
+//!   session-replay trace/s1776961697_0.inputs.bin --manifest fixed.json
+//!
+//! Exits non-zero when the outputs diverge.
 
 use clap::Parser;
 use console::{style, Style};
@@ -68,6 +79,15 @@ struct Cli {
     /// Summarise at the end: count events per (source, kind).
     #[arg(long)]
     summary: bool,
+
+    /// Replay pace for `.inputs.bin` captures: 1 = original timing,
+    /// 4 = four times faster, 0 = as fast as possible.
+    #[arg(long, default_value_t = 1.0)]
+    speed: f64,
+
+    /// Replay against this manifest (JSON) instead of the recorded one.
+    #[arg(long)]
+    manifest: Option<PathBuf>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -90,6 +110,10 @@ fn open_reader(path: &str) -> anyhow::Result<Box<dyn Read>> {
 
 fn main() -> anyhow::Result<()> {
     let cli = Cli::parse();
+    if cli.path.ends_with(".inputs.bin") {
+        let matched = tokio::runtime::Runtime::new()?.block_on(replay(&cli))?;
+        std::process::exit(if matched { 0 } else { 1 });
+    }
     let reader = BufReader::new(open_reader(&cli.path)?);
 
     let grep_needle = cli.grep.as_deref().map(str::to_ascii_lowercase);
@@ -174,6 +198,43 @@ fn main() -> anyhow::Result<()> {
     Ok(())
 }
 
+/// Re-run a lossless capture and print where the outputs diverge.
+async fn replay(cli: &Cli) -> anyhow::Result<bool> {
+    use remotemedia_core::transport::session_replay::SessionReplay;
+    use remotemedia_core::transport::PipelineExecutor;
+
+    let mut replay = SessionReplay::open(&cli.path)?.with_speed(cli.speed);
+    if let Some(path) = &cli.manifest {
+        replay = replay.with_manifest(serde_json::from_reader(File::open(path)?)?);
+    }
+    let capture = replay.capture();
+    println!(
+        "{} {} ({} inputs)",
+        style("replaying").bold(),
+        capture.header.session_id,
+        capture.inputs.len()
+    );
+
+    let executor = PipelineExecutor::new()?;
+    let report = replay.run(&executor).await?;
+
+    for d in &report.divergences {
+        println!("{} {}", style("≠").red().bold(), d);
+    }
+    println!(
+        "{} outputs from session {} in {:.1}s — {}",
+        report.outputs.len(),
+        report.session_id,
+        report.elapsed.as_secs_f64(),
+        if report.is_match() {
+            style("match".to_string()).green()
+        } else {
+            style(format!("{} divergences", report.divergences.len())).red()
+        }
+    );
+    Ok(report.is_match())
+}
+
 fn format_offset(offset_ms: i64) -> String {
     let sign = if offset_ms < 0 { "-" } else { "+" };
     let ms = offset_ms.unsigned_abs();
@@ -25,21 +25,21 @@ _sym_db = _symbol_database.Default()
 from remotemedia.protos import common_pb2 as common__pb2
 
 
-DESCRIPTOR = _descriptor_pool.Default().AddSerializedFile(b'\n\rcontrol.proto\x12\x0eremotemedia.v1\x1a\x0c\x63ommon.proto\"d\n\x0e\x43ontrolAddress\x12\x0f\n\x07node_id\x18\x01 \x01(\t\x12\x0c\n\x04port\x18\x02 \x01(\t\x12\x33\n\tdirection\x18\x03 \x01(\x0e\x32 .remotemedia.v1.ControlDirection\"\x9c\x01\n\x11InterceptDecision\x12%\n\x04pass\x18\x01 \x01(\x0b\x32\x15.remotemedia.v1.EmptyH\x00\x12-\n\x07replace\x18\x02 \x01(\x0b\x32\x1a.remotemedia.v1.DataBufferH\x00\x12%\n\x04\x64rop\x18\x03 \x01(\x0b\x32\x15.remotemedia.v1.EmptyH\x00\x42\n\n\x08\x64\x65\x63ision\"\x07\n\x05\x45mpty\"\xe8\x03\n\x0c\x43ontrolFrame\x12&\n\x05hello\x18\x01 \x01(\x0b\x32\x15.remotemedia.v1.HelloH\x00\x12.\n\tsubscribe\x18\x02 \x01(\x0b\x32\x19.remotemedia.v1.SubscribeH\x00\x12\x32\n\x0bunsubscribe\x18\x03 \x01(\x0b\x32\x1b.remotemedia.v1.UnsubscribeH\x00\x12*\n\x07publish\x18\x04 \x01(\x0b\x32\x17.remotemedia.v1.PublishH\x00\x12.\n\tintercept\x18\x05 \x01(\x0b\x32\x19.remotemedia.v1.InterceptH\x00\x12;\n\x10remove_intercept\x18\x06 \x01(\x0b\x32\x1f.remotemedia.v1.RemoveInterceptH\x00\x12\x39\n\x0fintercept_reply\x18\x07 \x01(\x0b\x32\x1e.remotemedia.v1.InterceptReplyH\x00\x12\x36\n\x0eset_node_state\x18\x08 \x01(\x0b\x32\x1c.remotemedia.v1.SetNodeStateH\x00\x12:\n\x10\x63lear_node_state\x18\t \x01(\x0b\x32\x1e.remotemedia.v1.ClearNodeStateH\x00\x42\x04\n\x02op\".\n\x05Hello\x12\x12\n\nsession_id\x18\x01 \x01(\t\x12\x11\n\tattach_id\x18\x02 \x01(\t\"9\n\tSubscribe\x12,\n\x04\x61\x64\x64r\x18\x01 \x01(\x0b\x32\x1e.remotemedia.v1.ControlAddress\";\n\x0bUnsubscribe\x12,\n\x04\x61\x64\x64r\x18\x01 \x01(\x0b\x32\x1e.remotemedia.v1.ControlAddress\"a\n\x07Publish\x12,\n\x04\x61\x64\x64r\x18\x01 \x01(\x0b\x32\x1e.remotemedia.v1.ControlAddress\x12(\n\x04\x64\x61ta\x18\x02 \x01(\x0b\x32\x1a.remotemedia.v1.DataBuffer\"?\n\x0fRemoveIntercept\x12,\n\x04\x61\x64\x64r\x18\x01 \x01(\x0b\x32\x1e.remotemedia.v1.ControlAddress\"N\n\tIntercept\x12,\n\x04\x61\x64\x64r\x18\x01 \x01(\x0b\x32\x1e.remotemedia.v1.ControlAddress\x12\x13\n\x0b\x64\x65\x61\x64line_ms\x18\x02 \x01(\r\"]\n\x0eInterceptReply\x12\x16\n\x0e\x63orrelation_id\x18\x01 \x01(\x04\x12\x33\n\x08\x64\x65\x63ision\x18\x02 \x01(\x0b\x32!.remotemedia.v1.InterceptDecision\"I\n\x0cSetNodeState\x12\x0f\n\x07node_id\x18\x01 \x01(\t\x12(\n\x05state\x18\x02 \x01(\x0e\x32\x19.remotemedia.v1.NodeState\"!\n\x0e\x43learNodeState\x12\x0f\n\x07node_id\x18\x01 \x01(\t\"\x93\x02\n\x0c\x43ontrolEvent\x12,\n\x08\x61ttached\x18\x01 \x01(\x0b\x32\x18.remotemedia.v1.AttachedH\x00\x12\'\n\x03tap\x18\x02 \x01(\x0b\x32\x18.remotemedia.v1.TapEventH\x00\x12=\n\x11intercept_request\x18\x03 \x01(\x0b\x32 .remotemedia.v1.InterceptRequestH\x00\x12+\n\x05\x65rror\x18\x04 \x01(\x0b\x32\x1a.remotemedia.v1.ErrorEventH\x00\x12\x37\n\x0esession_closed\x18\x05 \x01(\x0b\x32\x1d.remotemedia.v1.SessionClosedH\x00\x42\x07\n\x05\x65vent\"1\n\x08\x41ttached\x12\x12\n\nsession_id\x18\x01 \x01(\t\x12\x11\n\tattach_id\x18\x02 \x01(\t\"b\n\x08TapEvent\x12,\n\x04\x61\x64\x64r\x18\x01 \x01(\x0b\x32\x1e.remotemedia.v1.ControlAddress\x12(\n\x04\x64\x61ta\x18\x02 \x01(\x0b\x32\x1a.remotemedia.v1.DataBuffer\"\x82\x01\n\x10InterceptRequest\x12,\n\x04\x61\x64\x64r\x18\x01 \x01(\x0b\x32\x1e.remotemedia.v1.ControlAddress\x12\x16\n\x0e\x63orrelation_id\x18\x02 \x01(\x04\x12(\n\x04\x64\x61ta\x18\x03 \x01(\x0b\x32\x1a.remotemedia.v1.DataBuffer\"{\n\nErrorEvent\x12.\n\x04\x63ode\x18\x01 \x01(\x0e\x32 .remotemedia.v1.ControlErrorCode\x12\x0f\n\x07message\x18\x02 \x01(\t\x12,\n\x04\x61\x64\x64r\x18\x03 \x01(\x0b\x32\x1e.remotemedia.v1.ControlAddress\"P\n\rSessionClosed\x12/\n\x06reason\x18\x01 \x01(\x0e\x32\x1f.remotemedia.v1.CloseReasonCode\x12\x0e\n\x06\x64\x65tail\x18\x02 \x01(\t*j\n\x10\x43ontrolDirection\x12!\n\x1d\x43ONTROL_DIRECTION_UNSPECIFIED\x10\x00\x12\x18\n\x14\x43ONTROL_DIRECTION_IN\x10\x01\x12\x19\n\x15\x43ONTROL_DIRECTION_OUT\x10\x02*o\n\tNodeState\x12\x1a\n\x16NODE_STATE_UNSPECIFIED\x10\x00\x12\x16\n\x12NODE_STATE_ENABLED\x10\x01\x12\x15\n\x11NODE_STATE_BYPASS\x10\x02\x12\x17\n\x13NODE_STATE_DISABLED\x10\x03*\x94\x02\n\x10\x43ontrolErrorCode\x12\"\n\x1e\x43ONTROL_ERROR_CODE_UNSPECIFIED\x10\x00\x12(\n$CONTROL_ERROR_CODE_SESSION_NOT_FOUND\x10\x01\x12#\n\x1f\x43ONTROL_ERROR_CODE_UNKNOWN_NODE\x10\x02\x12&\n\"CONTROL_ERROR_CODE_INVALID_ADDRESS\x10\x03\x12\x1f\n\x1b\x43ONTROL_ERROR_CODE_PROTOCOL\x10\x04\x12#\n\x1f\x43ONTROL_ERROR_CODE_UNAUTHORIZED\x10\x05\x12\x1f\n\x1b\x43ONTROL_ERROR_CODE_INTERNAL\x10\x06*o\n\x0f\x43loseReasonCode\x12!\n\x1d\x43LOSE_REASON_CODE_UNSPECIFIED\x10\x00\x12\x1c\n\x18\x43LOSE_REASON_CODE_NORMAL\x10\x01\x12\x1b\n\x17\x43LOSE_REASON_CODE_ERROR\x10\x02\x32[\n\x0fPipelineControl\x12H\n\x06\x41ttach\x12\x1c.remotemedia.v1.ControlFrame\x1a\x1c.remotemedia.v1.ControlEvent(\x01\x30\x01\x62\x06proto3')
+DESCRIPTOR = _descriptor_pool.Default().AddSerializedFile(b'\n\rcontrol.proto\x12\x0eremotemedia.v1\x1a\x0c\x63ommon.proto\"d\n\x0e\x43ontrolAddress\x12\x0f\n\x07node_id\x18\x01 \x01(\t\x12\x0c\n\x04port\x18\x02 \x01(\t\x12\x33\n\tdirection\x18\x03 \x01(\x0e\x32 .remotemedia.v1.ControlDirection\"\x9c\x01\n\x11InterceptDecision\x12%\n\x04pass\x18\x01 \x01(\x0b\x32\x15.remotemedia.v1.EmptyH\x00\x12-\n\x07replace\x18\x02 \x01(\x0b\x32\x1a.remotemedia.v1.DataBufferH\x00\x12%\n\x04\x64rop\x18\x03 \x01(\x0b\x32\x15.remotemedia.v1.EmptyH\x00\x42\n\n\x08\x64\x65\x63ision\"\x07\n\x05\x45mpty\"\x8f\x05\n\x0c\x43ontrolFrame\x12&\n\x05hello\x18\x01 \x01(\x0b\x32\x15.remotemedia.v1.HelloH\x00\x12.\n\tsubscribe\x18\x02 \x01(\x0b\x32\x19.remotemedia.v1.SubscribeH\x00\x12\x32\n\x0bunsubscribe\x18\x03 \x01(\x0b\x32\x1b.remotemedia.v1.UnsubscribeH\x00\x12*\n\x07publish\x18\x04 \x01(\x0b\x32\x17.remotemedia.v1.PublishH\x00\x12.\n\tintercept\x18\x05 \x01(\x0b\x32\x19.remotemedia.v1.InterceptH\x00\x12;\n\x10remove_intercept\x18\x06 \x01(\x0b\x32\x1f.remotemedia.v1.RemoveInterceptH\x00\x12\x39\n\x0fintercept_reply\x18\x07 \x01(\x0b\x32\x1e.remotemedia.v1.InterceptReplyH\x00\x12\x36\n\x0eset_node_state\x18\x08 \x01(\x0b\x32\x1c.remotemedia.v1.SetNodeStateH\x00\x12:\n\x10\x63lear_node_state\x18\t \x01(\x0b\x32\x1e.remotemedia.v1.ClearNodeStateH\x00\x12\x31\n\x0b\x61pply_patch\x18\n \x01(\x0b\x32\x1a.remotemedia.v1.ApplyPatchH\x00\x12\x39\n\x0fstart_recording\x18\x0b \x01(\x0b\x32\x1e.remotemedia.v1.StartRecordingH\x00\x12\x37\n\x0estop_recording\x18\x0c \x01(\x0b\x32\x1d.remotemedia.v1.StopRecordingH\x00\x42\x04\n\x02op\".\n\x05Hello\x12\x12\n\nsession_id\x18\x01 \x01(\t\x12\x11\n\tattach_id\x18\x02 \x01(\t\"9\n\tSubscribe\x12,\n\x04\x61\x64\x64r\x18\x01 \x01(\x0b\x32\x1e.remotemedia.v1.ControlAddress\";\n\x0bUnsubscribe\x12,\n\x04\x61\x64\x64r\x18\x01 \x01(\x0b\x32\x1e.remotemedia.v1.ControlAddress\"a\n\x07Publish\x12,\n\x04\x61\x64\x64r\x18\x01 \x01(\x0b\x32\x1e.remotemedia.v1.ControlAddress\x12(\n\x04\x64\x61ta\x18\x02 \x01(\x0b\x32\x1a.remotemedia.v1.DataBuffer\"?\n\x0fRemoveIntercept\x12,\n\x04\x61\x64\x64r\x18\x01 \x01(\x0b\x32\x1e.remotemedia.v1.ControlAddress\"N\n\tIntercept\x12,\n\x04\x61\x64\x64r\x18\x01 \x01(\x0b\x32\x1e.remotemedia.v1.ControlAddress\x12\x13\n\x0b\x64\x65\x61\x64line_ms\x18\x02 \x01(\r\"]\n\x0eInterceptReply\x12\x16\n\x0e\x63orrelation_id\x18\x01 \x01(\x04\x12\x33\n\x08\x64\x65\x63ision\x18\x02 \x01(\x0b\x32!.remotemedia.v1.InterceptDecision\"I\n\x0cSetNodeState\x12\x0f\n\x07node_id\x18\x01 \x01(\t\x12(\n\x05state\x18\x02 \x01(\x0e\x32\x19.remotemedia.v1.NodeState\"!\n\x0e\x43learNodeState\x12\x0f\n\x07node_id\x18\x01 \x01(\t\" \n\nApplyPatch\x12\x12\n\npatch_json\x18\x01 \x01(\t\"*\n\x0eStartRecording\x12\x18\n\x10include_outbound\x18\x01 \x01(\x08\"\x0f\n\rStopRecording\"\xc8\x03\n\x0c\x43ontrolEvent\x12,\n\x08\x61ttached\x18\x01 \x01(\x0b\x32\x18.remotemedia.v1.AttachedH\x00\x12\'\n\x03tap\x18\x02 \x01(\x0b\x32\x18.remotemedia.v1.TapEventH\x00\x12=\n\x11intercept_request\x18\x03 \x01(\x0b\x32 .remotemedia.v1.InterceptRequestH\x00\x12+\n\x05\x65rror\x18\x04 \x01(\x0b\x32\x1a.remotemedia.v1.ErrorEventH\x00\x12\x37\n\x0esession_closed\x18\x05 \x01(\x0b\x32\x1d.remotemedia.v1.SessionClosedH\x00\x12\x35\n\rpatch_applied\x18\x06 \x01(\x0b\x32\x1c.remotemedia.v1.PatchAppliedH\x00\x12=\n\x11recording_started\x18\x07 \x01(\x0b\x32 .remotemedia.v1.RecordingStartedH\x00\x12=\n\x11recording_stopped\x18\x08 \x01(\x0b\x32 .remotemedia.v1.RecordingStoppedH\x00\x42\x07\n\x05\x65vent\"1\n\x08\x41ttached\x12\x12\n\nsession_id\x18\x01 \x01(\t\x12\x11\n\tattach_id\x18\x02 \x01(\t\"#\n\x0cPatchApplied\x12\x13\n\x0breport_json\x18\x01 \x01(\t\"F\n\x10RecordingStarted\x12\x14\n\x0crecording_id\x18\x01 \x01(\t\x12\x0c\n\x04path\x18\x02 \x01(\t\x12\x0e\n\x06tracks\x18\x03 \x03(\t\"_\n\x10RecordingStopped\x12\x14\n\x0crecording_id\x18\x01 \x01(\t\x12\x0c\n\x04path\x18\x02 \x01(\t\x12\x13\n\x0b\x64uration_ms\x18\x03 \x01(\x04\x12\x12\n\nsize_bytes\x18\x04 \x01(\x04\"b\n\x08TapEvent\x12,\n\x04\x61\x64\x64r\x18\x01 \x01(\x0b\x32\x1e.remotemedia.v1.ControlAddress\x12(\n\x04\x64\x61ta\x18\x02 \x01(\x0b\x32\x1a.remotemedia.v1.DataBuffer\"\x82\x01\n\x10InterceptRequest\x12,\n\x04\x61\x64\x64r\x18\x01 \x01(\x0b\x32\x1e.remotemedia.v1.ControlAddress\x12\x16\n\x0e\x63orrelation_id\x18\x02 \x01(\x04\x12(\n\x04\x64\x61ta\x18\x03 \x01(\x0b\x32\x1a.remotemedia.v1.DataBuffer\"{\n\nErrorEvent\x12.\n\x04\x63ode\x18\x01 \x01(\x0e\x32 .remotemedia.v1.ControlErrorCode\x12\x0f\n\x07message\x18\x02 \x01(\t\x12,\n\x04\x61\x64\x64r\x18\x03 \x01(\x0b\x32\x1e.remotemedia.v1.ControlAddress\"P\n\rSessionClosed\x12/\n\x06reason\x18\x01 \x01(\x0e\x32\x1f.remotemedia.v1.CloseReasonCode\x12\x0e\n\x06\x64\x65tail\x18\x02 \x01(\t*j\n\x10\x43ontrolDirection\x12!\n\x1d\x43ONTROL_DIRECTION_UNSPECIFIED\x10\x00\x12\x18\n\x14\x43ONTROL_DIRECTION_IN\x10\x01\x12\x19\n\x15\x43ONTROL_DIRECTION_OUT\x10\x02*o\n\tNodeState\x12\x1a\n\x16NODE_STATE_UNSPECIFIED\x10\x00\x12\x16\n\x12NODE_STATE_ENABLED\x10\x01\x12\x15\n\x11NODE_STATE_BYPASS\x10\x02\x12\x17\n\x13NODE_STATE_DISABLED\x10\x03*\xdc\x02\n\x10\x43ontrolErrorCode\x12\"\n\x1e\x43ONTROL_ERROR_CODE_UNSPECIFIED\x10\x00\x12(\n$CONTROL_ERROR_CODE_SESSION_NOT_FOUND\x10\x01\x12#\n\x1f\x43ONTROL_ERROR_CODE_UNKNOWN_NODE\x10\x02\x12&\n\"CONTROL_ERROR_CODE_INVALID_ADDRESS\x10\x03\x12\x1f\n\x1b\x43ONTROL_ERROR_CODE_PROTOCOL\x10\x04\x12#\n\x1f\x43ONTROL_ERROR_CODE_UNAUTHORIZED\x10\x05\x12\x1f\n\x1b\x43ONTROL_ERROR_CODE_INTERNAL\x10\x06\x12$\n CONTROL_ERROR_CODE_INVALID_PATCH\x10\x07\x12 \n\x1c\x43ONTROL_ERROR_CODE_RECORDING\x10\x08*o\n\x0f\x43loseReasonCode\x12!\n\x1d\x43LOSE_REASON_CODE_UNSPECIFIED\x10\x00\x12\x1c\n\x18\x43LOSE_REASON_CODE_NORMAL\x10\x01\x12\x1b\n\x17\x43LOSE_REASON_CODE_ERROR\x10\x02\x32[\n\x0fPipelineControl\x12H\n\x06\x41ttach\x12\x1c.remotemedia.v1.ControlFrame\x1a\x1c.remotemedia.v1.ControlEvent(\x01\x30\x01\x62\x06proto3')
 
 _globals = globals()
 _builder.BuildMessageAndEnumDescriptors(DESCRIPTOR, _globals)
 _builder.BuildTopDescriptorsAndMessages(DESCRIPTOR, 'control_pb2', _globals)
 if not _descriptor._USE_C_DESCRIPTORS:
   DESCRIPTOR._loaded_options = None
-  _globals['_CONTROLDIRECTION']._serialized_start=2194
-  _globals['_CONTROLDIRECTION']._serialized_end=2300
-  _globals['_NODESTATE']._serialized_start=2302
-  _globals['_NODESTATE']._serialized_end=2413
-  _globals['_CONTROLERRORCODE']._serialized_start=2416
-  _globals['_CONTROLERRORCODE']._serialized_end=2692
-  _globals['_CLOSEREASONCODE']._serialized_start=2694
-  _globals['_CLOSEREASONCODE']._serialized_end=2805
+  _globals['_CONTROLDIRECTION']._serialized_start=2843
+  _globals['_CONTROLDIRECTION']._serialized_end=2949
+  _globals['_NODESTATE']._serialized_start=2951
+  _globals['_NODESTATE']._serialized_end=3062
+  _globals['_CONTROLERRORCODE']._serialized_start=3065
+  _globals['_CONTROLERRORCODE']._serialized_end=3413
+  _globals['_CLOSEREASONCODE']._serialized_start=3415
+  _globals['_CLOSEREASONCODE']._serialized_end=3526
   _globals['_CONTROLADDRESS']._serialized_start=47
   _globals['_CONTROLADDRESS']._serialized_end=147
   _globals['_INTERCEPTDECISION']._serialized_start=150
@@ -47,37 +47,49 @@ if not _descriptor._USE_C_DESCRIPTORS:
   _globals['_EMPTY']._serialized_start=308
   _globals['_EMPTY']._serialized_end=315
   _globals['_CONTROLFRAME']._serialized_start=318
-  _globals['_CONTROLFRAME']._serialized_end=806
-  _globals['_HELLO']._serialized_start=808
-  _globals['_HELLO']._serialized_end=854
-  _globals['_SUBSCRIBE']._serialized_start=856
-  _globals['_SUBSCRIBE']._serialized_end=913
-  _globals['_UNSUBSCRIBE']._serialized_start=915
-  _globals['_UNSUBSCRIBE']._serialized_end=974
-  _globals['_PUBLISH']._serialized_start=976
-  _globals['_PUBLISH']._serialized_end=1073
-  _globals['_REMOVEINTERCEPT']._serialized_start=1075
-  _globals['_REMOVEINTERCEPT']._serialized_end=1138
-  _globals['_INTERCEPT']._serialized_start=1140
-  _globals['_INTERCEPT']._serialized_end=1218
-  _globals['_INTERCEPTREPLY']._serialized_start=1220
-  _globals['_INTERCEPTREPLY']._serialized_end=1313
-  _globals['_SETNODESTATE']._serialized_start=1315
-  _globals['_SETNODESTATE']._serialized_end=1388
-  _globals['_CLEARNODESTATE']._serialized_start=1390
-  _globals['_CLEARNODESTATE']._serialized_end=1423
-  _globals['_CONTROLEVENT']._serialized_start=1426
-  _globals['_CONTROLEVENT']._serialized_end=1701
-  _globals['_ATTACHED']._serialized_start=1703
-  _globals['_ATTACHED']._serialized_end=1752
-  _globals['_TAPEVENT']._serialized_start=1754
-  _globals['_TAPEVENT']._serialized_end=1852
-  _globals['_INTERCEPTREQUEST']._serialized_start=1855
-  _globals['_INTERCEPTREQUEST']._serialized_end=1985
-  _globals['_ERROREVENT']._serialized_start=1987
-  _globals['_ERROREVENT']._serialized_end=2110
-  _globals['_SESSIONCLOSED']._serialized_start=2112
-  _globals['_SESSIONCLOSED']._serialized_end=2192
-  _globals['_PIPELINECONTROL']._serialized_start=2807
-  _globals['_PIPELINECONTROL']._serialized_end=2898
+  _globals['_CONTROLFRAME']._serialized_end=973
+  _globals['_HELLO']._serialized_start=975
+  _globals['_HELLO']._serialized_end=1021
+  _globals['_SUBSCRIBE']._serialized_start=1023
+  _globals['_SUBSCRIBE']._serialized_end=1080
+  _globals['_UNSUBSCRIBE']._serialized_start=1082
+  _globals['_UNSUBSCRIBE']._serialized_end=1141
+  _globals['_PUBLISH']._serialized_start=1143
+  _globals['_PUBLISH']._serialized_end=1240
+  _globals['_REMOVEINTERCEPT']._serialized_start=1242
+  _globals['_REMOVEINTERCEPT']._serialized_end=1305
+  _globals['_INTERCEPT']._serialized_start=1307
+  _globals['_INTERCEPT']._serialized_end=1385
+  _globals['_INTERCEPTREPLY']._serialized_start=1387
+  _globals['_INTERCEPTREPLY']._serialized_end=1480
+  _globals['_SETNODESTATE']._serialized_start=1482
+  _globals['_SETNODESTATE']._serialized_end=1555
+  _globals['_CLEARNODESTATE']._serialized_start=1557
+  _globals['_CLEARNODESTATE']._serialized_end=1590
+  _globals['_APPLYPATCH']._serialized_start=1592
+  _globals['_APPLYPATCH']._serialized_end=1624
+  _globals['_STARTRECORDING']._serialized_start=1626
+  _globals['_STARTRECORDING']._serialized_end=1668
+  _globals['_STOPRECORDING']._serialized_start=1670
+  _globals['_STOPRECORDING']._serialized_end=1685
+  _globals['_CONTROLEVENT']._serialized_start=1688
+  _globals['_CONTROLEVENT']._serialized_end=2144
+  _globals['_ATTACHED']._serialized_start=2146
+  _globals['_ATTACHED']._serialized_end=2195
+  _globals['_PATCHAPPLIED']._serialized_start=2197
+  _globals['_PATCHAPPLIED']._serialized_end=2232
+  _globals['_RECORDINGSTARTED']._serialized_start=2234
+  _globals['_RECORDINGSTARTED']._serialized_end=2304
+  _globals['_RECORDINGSTOPPED']._serialized_start=2306
+  _globals['_RECORDINGSTOPPED']._serialized_end=2401
+  _globals['_TAPEVENT']._serialized_start=2403
+  _globals['_TAPEVENT']._serialized_end=2501
+  _globals['_INTERCEPTREQUEST']._serialized_start=2504
+  _globals['_INTERCEPTREQUEST']._serialized_end=2634
+  _globals['_ERROREVENT']._serialized_start=2636
+  _globals['_ERROREVENT']._serialized_end=2759
+  _globals['_SESSIONCLOSED']._serialized_start=2761
+  _globals['_SESSIONCLOSED']._serialized_end=2841
+  _globals['_PIPELINECONTROL']._serialized_start=3528
+  _globals['_PIPELINECONTROL']._serialized_end=3619
 # @@protoc_insertion_point(module_scope)
@@ -4,17 +4,19 @@
 //!   remotemedia-test-manifest <MANIFEST_PATH>
 //!   remotemedia-test-manifest pipeline.yaml --dry-run
 //!   remotemedia-test-manifest pipeline.json --skip-ml --output-format json
+//!   remotemedia-test-manifest migrate pipeline.yaml [more.json ...] [--check | --in-place]
 
 // Link node crates so inventory auto-registration activates
 use remotemedia_candle_nodes as _;
 use remotemedia_python_nodes as _;
 
 use anyhow::Result;
-use clap::Parser;
+use clap::{Parser, Subcommand};
+use remotemedia_core::manifest::migrate::{migrate_source, ManifestFormat, CURRENT_VERSION};
 use remotemedia_manifest_tester::tester::ManifestTester;
 use remotemedia_manifest_tester::probes::ProbeSpec;
 use remotemedia_manifest_tester::TestStatus;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::time::Duration;
 use tracing_subscriber::{fmt, prelude::*, EnvFilter};
 
@@ -23,9 +25,14 @@ use tracing_subscriber::{fmt, prelude::*, EnvFilter};
 #[command(name = "remotemedia-test-manifest")]
 #[command(about = "Test a pipeline manifest end-to-end")]
 #[command(version)]
+#[command(args_conflicts_with_subcommands = true, subcommand_negates_reqs = true)]
 struct Cli {
+    #[command(subcommand)]
+    command: Option<Command>,
+
     /// Path to the pipeline manifest (YAML or JSON)
-    manifest: PathBuf,
+    #[arg(required = true)]
+    manifest: Option<PathBuf>,
 
     /// Transport probes to run
     #[arg(short, long, default_value = "direct")]
@@ -64,6 +71,27 @@ struct Cli {
     verbose: u8,
 }
 
+#[derive(Subcommand)]
+enum Command {
+    /// Upgrade manifests to the current schema version
+    ///
+    /// Each migrated manifest is written next to the original as
+    /// `<name>.v2.<ext>`; the original is left untouched.
+    Migrate {
+        /// Manifest files (YAML or JSON)
+        #[arg(required = true)]
+        manifests: Vec<PathBuf>,
+
+        /// Report what would change without writing; exits 1 if any file needs migrating
+        #[arg(long, conflicts_with = "in_place")]
+        check: bool,
+
+        /// Overwrite the original files instead (YAML comments are lost)
+        #[arg(long)]
+        in_place: bool,
+    },
+}
+
 #[derive(Clone, Debug, clap::ValueEnum)]
 enum TransportArg {
     Direct,
@@ -101,6 +129,72 @@ fn transport_args_to_specs(args: &[TransportArg]) -> Vec<ProbeSpec> {
     specs
 }
 
+/// Where the migrated copy of `path` goes: `pipeline.yaml` → `pipeline.v2.yaml`
+fn migrated_path(path: &Path) -> PathBuf {
+    let stem = path.file_stem().unwrap_or_default().to_string_lossy();
+    let name = match path.extension() {
+        Some(ext) => format!("{}.{}.{}", stem, CURRENT_VERSION, ext.to_string_lossy()),
+        None => format!("{}.{}", stem, CURRENT_VERSION),
+    };
+    path.with_file_name(name)
+}
+
+/// Migrate each manifest, printing what changed
+///
+/// Exit code: 0 on success, 1 if `--check` found files to migrate,
+/// 2 if any file could not be read, migrated or written.
+fn run_migrate(paths: &[PathBuf], check: bool, in_place: bool) -> i32 {
+    let mut pending = false;
+    let mut failed = false;
+    for path in paths {
+        let result = std::fs::read_to_string(path)
+            .map_err(anyhow::Error::from)
+            .and_then(|source| Ok(migrate_source(&source, ManifestFormat::from_path(path))?));
+        let (migrated, report) = match result {
+            Ok(migrated) => migrated,
+            Err(e) => {
+                eprintln!("{}: {e}", path.display());
+                failed = true;
+                continue;
+            }
+        };
+
+        if report.is_noop() {
+            println!("{}: already {}", path.display(), report.to);
+            continue;
+        }
+        println!("{}: {} -> {}", path.display(), report.from, report.to);
+        for change in &report.changes {
+            println!("  {change}");
+        }
+        if check {
+            pending = true;
+            continue;
+        }
+        let target = if in_place {
+            path.clone()
+        } else {
+            migrated_path(path)
+        };
+        match std::fs::write(&target, migrated) {
+            Ok(()) if !in_place => println!("  written to {}", target.display()),
+            Ok(()) => {}
+            Err(e) => {
+                eprintln!("{}: failed to write: {e}", target.display());
+                failed = true;
+            }
+        }
+    }
+
+    if failed {
+        2
+    } else if pending {
+        1
+    } else {
+        0
+    }
+}
+
 fn main() -> Result<()> {
     // Use a manually-built runtime so that when main() returns, the runtime
     // is dropped first (cleaning up async tasks) and then all remaining Rust
@@ -119,6 +213,16 @@ fn main() -> Result<()> {
 async fn async_main() -> Result<i32> {
     let cli = Cli::parse();
 
+    if let Some(Command::Migrate {
+        manifests,
+        check,
+        in_place,
+    }) = &cli.command
+    {
+        return Ok(run_migrate(manifests, *check, *in_place));
+    }
+    let manifest = cli.manifest.as_ref().expect("clap requires a manifest");
+
     // Setup logging
     let log_level = match cli.verbose {
         0 => "warn",
@@ -157,7 +261,7 @@ async fn async_main() -> Result<i32> {
         std::sync::Arc::new(std::sync::Mutex::new(Vec::<remotemedia_core::data::RuntimeData>::new()))
     });
 
-    let mut tester = ManifestTester::test(&cli.manifest)
+    let mut tester = ManifestTester::test(manifest)
         .with_probes(&specs)
         .with_timeout(Duration::from_secs(cli.timeout))
         .skip_ml(cli.skip_ml)